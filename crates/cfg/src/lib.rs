@@ -139,6 +139,16 @@ impl CfgDiff {
         Some(CfgDiff { enable, disable })
     }
 
+    /// Returns the atoms enabled by this diff.
+    pub fn enable(&self) -> &[CfgAtom] {
+        &self.enable
+    }
+
+    /// Returns the atoms disabled by this diff.
+    pub fn disable(&self) -> &[CfgAtom] {
+        &self.disable
+    }
+
     /// Returns the total number of atoms changed by this diff.
     pub fn len(&self) -> usize {
         self.enable.len() + self.disable.len()
@@ -108,7 +108,7 @@ impl FlycheckHandle {
     }
 
     /// Schedule a re-start of the cargo check worker to do a package wide check.
-    pub fn restart_for_package(&self, package: String) {
+    pub fn restart_for_package(&self, package: PackageSpecifier) {
         self.sender
             .send(StateChange::Restart { package: Some(package), saved_file: None })
             .unwrap();
@@ -126,7 +126,13 @@ impl FlycheckHandle {
 
 pub enum Message {
     /// Request adding a diagnostic with fixes included to a file
-    AddDiagnostic { id: usize, workspace_root: AbsPathBuf, diagnostic: Diagnostic },
+    AddDiagnostic {
+        id: usize,
+        workspace_root: AbsPathBuf,
+        diagnostic: Diagnostic,
+        /// The id of the package the diagnostic was reported for, if known.
+        package_id: Option<String>,
+    },
 
     /// Request check progress notification to client
     Progress {
@@ -139,10 +145,11 @@ pub enum Message {
 impl fmt::Debug for Message {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Message::AddDiagnostic { id, workspace_root, diagnostic } => f
+            Message::AddDiagnostic { id, workspace_root, diagnostic, package_id } => f
                 .debug_struct("AddDiagnostic")
                 .field("id", id)
                 .field("workspace_root", workspace_root)
+                .field("package_id", package_id)
                 .field("diagnostic_code", &diagnostic.code.as_ref().map(|it| &it.code))
                 .finish(),
             Message::Progress { id, progress } => {
@@ -152,9 +159,21 @@ impl fmt::Debug for Message {
     }
 }
 
+/// A package to check on its own.
+#[derive(Debug, Clone)]
+pub struct PackageSpecifier {
+    /// The name of the package, as passed to `cargo check -p`.
+    pub name: String,
+    /// The id of the package, as cargo reports it in its messages.
+    pub id: String,
+}
+
 #[derive(Debug)]
 pub enum Progress {
-    DidStart,
+    /// A check started, of a single package or of the whole workspace.
+    DidStart {
+        package_id: Option<String>,
+    },
     DidCheckCrate(String),
     DidFinish(io::Result<()>),
     DidCancel,
@@ -162,7 +181,7 @@ pub enum Progress {
 }
 
 enum StateChange {
-    Restart { package: Option<String>, saved_file: Option<AbsPathBuf> },
+    Restart { package: Option<PackageSpecifier>, saved_file: Option<AbsPathBuf> },
     Cancel,
 }
 
@@ -190,6 +209,7 @@ enum Event {
 }
 
 const SAVED_FILE_PLACEHOLDER: &str = "$saved_file";
+const WORKSPACE_ROOT_PLACEHOLDER: &str = "$workspace_root";
 
 impl FlycheckActor {
     fn new(
@@ -243,11 +263,13 @@ impl FlycheckActor {
                         }
                     }
 
-                    let command =
-                        match self.check_command(package.as_deref(), saved_file.as_deref()) {
-                            Some(c) => c,
-                            None => continue,
-                        };
+                    let command = match self.check_command(
+                        package.as_ref().map(|it| it.name.as_str()),
+                        saved_file.as_deref(),
+                    ) {
+                        Some(c) => c,
+                        None => continue,
+                    };
                     let formatted_command = format!("{:?}", command);
 
                     tracing::debug!(?command, "will restart flycheck");
@@ -255,7 +277,9 @@ impl FlycheckActor {
                         Ok(command_handle) => {
                             tracing::debug!(command = formatted_command, "did  restart flycheck");
                             self.command_handle = Some(command_handle);
-                            self.report_progress(Progress::DidStart);
+                            self.report_progress(Progress::DidStart {
+                                package_id: package.map(|it| it.id),
+                            });
                         }
                         Err(error) => {
                             self.report_progress(Progress::DidFailToRestart(format!(
@@ -291,16 +315,17 @@ impl FlycheckActor {
                         self.report_progress(Progress::DidCheckCrate(msg.target.name));
                     }
 
-                    CargoMessage::Diagnostic(msg) => {
+                    CargoMessage::Diagnostic { diagnostic, package_id } => {
                         tracing::trace!(
                             flycheck_id = self.id,
-                            message = msg.message,
+                            message = diagnostic.message,
                             "diagnostic received"
                         );
                         self.send(Message::AddDiagnostic {
                             id: self.id,
                             workspace_root: self.root.clone(),
-                            diagnostic: msg,
+                            diagnostic,
+                            package_id,
                         });
                     }
                 },
@@ -412,30 +437,32 @@ impl FlycheckActor {
                         cmd.current_dir(root);
                     }
                 }
-
-                if args.contains(&SAVED_FILE_PLACEHOLDER.to_owned()) {
-                    // If the custom command has a $saved_file placeholder, and
-                    // we're saving a file, replace the placeholder in the arguments.
-                    if let Some(saved_file) = saved_file {
-                        let args = args
-                            .iter()
-                            .map(|arg| {
-                                if arg == SAVED_FILE_PLACEHOLDER {
-                                    saved_file.to_string()
-                                } else {
-                                    arg.clone()
-                                }
-                            })
-                            .collect();
-                        (cmd, args)
-                    } else {
-                        // The custom command has a $saved_file placeholder,
-                        // but we had an IDE event that wasn't a file save. Do nothing.
-                        return None;
+                let root = self.root.to_string();
+                for (key, value) in extra_env {
+                    if value.contains(WORKSPACE_ROOT_PLACEHOLDER) {
+                        cmd.env(key, value.replace(WORKSPACE_ROOT_PLACEHOLDER, &root));
                     }
-                } else {
-                    (cmd, args.clone())
                 }
+
+                // If the custom command has a $saved_file placeholder but we had an IDE event
+                // that wasn't a file save, do nothing.
+                if args.iter().any(|arg| arg.contains(SAVED_FILE_PLACEHOLDER))
+                    && saved_file.is_none()
+                {
+                    return None;
+                }
+                let saved_file = saved_file.map(ToString::to_string);
+                let args = args
+                    .iter()
+                    .map(|arg| {
+                        let arg = arg.replace(WORKSPACE_ROOT_PLACEHOLDER, &root);
+                        match &saved_file {
+                            Some(saved_file) => arg.replace(SAVED_FILE_PLACEHOLDER, saved_file),
+                            None => arg,
+                        }
+                    })
+                    .collect();
+                (cmd, args)
             }
         };
 
@@ -556,12 +583,22 @@ impl CargoActor {
                             self.sender.send(CargoMessage::CompilerArtifact(artifact)).unwrap();
                         }
                         cargo_metadata::Message::CompilerMessage(msg) => {
-                            self.sender.send(CargoMessage::Diagnostic(msg.message)).unwrap();
+                            self.sender
+                                .send(CargoMessage::Diagnostic {
+                                    diagnostic: msg.message,
+                                    package_id: Some(msg.package_id.repr),
+                                })
+                                .unwrap();
                         }
                         _ => (),
                     },
                     JsonMessage::Rustc(message) => {
-                        self.sender.send(CargoMessage::Diagnostic(message)).unwrap();
+                        self.sender
+                            .send(CargoMessage::Diagnostic {
+                                diagnostic: message,
+                                package_id: None,
+                            })
+                            .unwrap();
                     }
                 }
                 return true;
@@ -600,7 +637,7 @@ impl CargoActor {
 #[allow(clippy::large_enum_variant)]
 enum CargoMessage {
     CompilerArtifact(cargo_metadata::Artifact),
-    Diagnostic(Diagnostic),
+    Diagnostic { diagnostic: Diagnostic, package_id: Option<String> },
 }
 
 #[derive(Deserialize)]
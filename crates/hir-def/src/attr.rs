@@ -182,6 +182,14 @@ impl Attrs {
         self.by_key("export_name").string_value()
     }
 
+    pub fn link_name(&self) -> Option<&SmolStr> {
+        self.by_key("link_name").string_value()
+    }
+
+    pub fn is_no_mangle(&self) -> bool {
+        self.by_key("no_mangle").exists()
+    }
+
     pub fn is_proc_macro(&self) -> bool {
         self.by_key("proc_macro").exists()
     }
@@ -216,6 +224,54 @@ impl Attrs {
     pub fn is_unstable(&self) -> bool {
         self.by_key("unstable").exists()
     }
+
+    /// Parses `#[deprecated]`, `#[deprecated = "note"]` and
+    /// `#[deprecated(since = "version", note = "note")]`.
+    pub fn deprecation(&self) -> Option<Deprecation> {
+        let attr = self.by_key("deprecated").attrs().next()?;
+        if let Some(note) = attr.string_value() {
+            return Some(Deprecation { since: None, note: Some(note.clone()) });
+        }
+        Some(attr.token_tree_value().map(Deprecation::parse).unwrap_or_default())
+    }
+}
+
+/// The arguments of a `#[deprecated]` attribute.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct Deprecation {
+    pub since: Option<SmolStr>,
+    pub note: Option<SmolStr>,
+}
+
+impl Deprecation {
+    fn parse<S>(tt: &tt::Subtree<S>) -> Deprecation {
+        let mut res = Deprecation::default();
+        let mut it = tt.token_trees.iter();
+        while let Some(expr) = next_doc_expr(&mut it) {
+            if let DocExpr::Atom(DocAtom::KeyValue { key, value }) = expr {
+                match key.as_str() {
+                    "since" => res.since = Some(value),
+                    "note" => res.note = Some(value),
+                    _ => {}
+                }
+            }
+        }
+        res
+    }
+
+    /// The path named as a replacement by the note, as in ``note = "use `new_fn` instead"``.
+    pub fn replacement(&self) -> Option<&str> {
+        let note = self.note.as_deref()?;
+        let (_, rest) = note.split_once("use `")?;
+        let (path, _) = rest.split_once('`')?;
+        let is_path = !path.is_empty()
+            && path.split("::").all(|segment| {
+                !segment.is_empty()
+                    && segment.chars().all(|c| c.is_alphanumeric() || c == '_')
+                    && !segment.starts_with(|c: char| c.is_ascii_digit())
+            });
+        is_path.then_some(path)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Ord, PartialOrd)]
@@ -1,5 +1,5 @@
 //! This module contains tests for doc-expression parsing.
-//! Currently, it tests `#[doc(hidden)]` and `#[doc(alias)]`.
+//! Currently, it tests `#[doc(hidden)]`, `#[doc(alias)]` and `#[deprecated]`.
 
 use triomphe::Arc;
 
@@ -8,18 +8,21 @@ use hir_expand::span_map::{RealSpanMap, SpanMap};
 use mbe::syntax_node_to_token_tree;
 use syntax::{ast, AstNode, TextRange};
 
-use crate::attr::{DocAtom, DocExpr};
+use crate::attr::{Deprecation, DocAtom, DocExpr};
 
-fn assert_parse_result(input: &str, expected: DocExpr) {
+fn parse_tt(input: &str) -> crate::tt::Subtree {
     let source_file = ast::SourceFile::parse(input).ok().unwrap();
     let tt = source_file.syntax().descendants().find_map(ast::TokenTree::cast).unwrap();
     let map = SpanMap::RealSpanMap(Arc::new(RealSpanMap::absolute(FileId::from_raw(0))));
-    let tt = syntax_node_to_token_tree(
+    syntax_node_to_token_tree(
         tt.syntax(),
         map.as_ref(),
         map.span_for_range(TextRange::empty(0.into())),
-    );
-    let cfg = DocExpr::parse(&tt);
+    )
+}
+
+fn assert_parse_result(input: &str, expected: DocExpr) {
+    let cfg = DocExpr::parse(&parse_tt(input));
     assert_eq!(cfg, expected);
 }
 
@@ -45,3 +48,18 @@ fn test_doc_expr_parser() {
         DocExpr::Alias(["Bar".into(), "Qux".into()].into()),
     );
 }
+
+#[test]
+fn test_deprecation_parser() {
+    let deprecation = Deprecation::parse(&parse_tt(
+        r#"#[deprecated(since = "1.2.0", note = "use `bar::baz` instead")]"#,
+    ));
+    assert_eq!(
+        deprecation,
+        Deprecation { since: Some("1.2.0".into()), note: Some("use `bar::baz` instead".into()) }
+    );
+    assert_eq!(deprecation.replacement(), Some("bar::baz"));
+
+    let deprecation = Deprecation::parse(&parse_tt(r#"#[deprecated(note = "use `a b` instead")]"#));
+    assert_eq!(deprecation.replacement(), None);
+}
@@ -12,10 +12,10 @@ use base_db::CrateId;
 use cfg::{CfgExpr, CfgOptions};
 use either::Either;
 use hir_expand::{name::Name, HirFileId, InFile};
-use la_arena::{Arena, ArenaMap};
+use la_arena::{Arena, ArenaMap, Idx};
 use profile::Count;
 use rustc_hash::FxHashMap;
-use syntax::{ast, AstPtr, SyntaxNodePtr};
+use syntax::{ast, AstNode, AstPtr, SyntaxKind, SyntaxNodePtr, TextSize};
 use triomphe::Arc;
 
 use crate::{
@@ -81,14 +81,19 @@ pub type PatFieldSource = InFile<PatFieldPtr>;
 /// this properly for macros.
 #[derive(Default, Debug, Eq, PartialEq)]
 pub struct BodySourceMap {
+    /// Sources that aren't the source of an expression themselves but point to one, like
+    /// parenthesized expressions and macro calls.
     expr_map: FxHashMap<ExprSource, ExprId>,
     expr_map_back: ArenaMap<ExprId, ExprSource>,
+    expr_index: SourceIndex<Expr>,
 
+    /// Like `expr_map`, for macro calls in pattern position.
     pat_map: FxHashMap<PatSource, PatId>,
     pat_map_back: ArenaMap<PatId, PatSource>,
+    pat_index: SourceIndex<Pat>,
 
-    label_map: FxHashMap<LabelSource, LabelId>,
     label_map_back: ArenaMap<LabelId, LabelSource>,
+    label_index: SourceIndex<Label>,
 
     /// We don't create explicit nodes for record fields (`S { record_field: 92 }`).
     /// Instead, we use id of expression (`92`) to identify the field.
@@ -104,6 +109,47 @@ pub struct BodySourceMap {
     diagnostics: Vec<BodyDiagnostic>,
 }
 
+/// Maps sources back to the ids they're the source of, by keeping the ids sorted by their source.
+///
+/// Most sources belong to exactly one id, so a binary search through the source map saves storing
+/// every source a second time as the key of a hash map.
+#[derive(Debug, Eq, PartialEq)]
+struct SourceIndex<T>(Box<[Idx<T>]>);
+
+impl<T> Default for SourceIndex<T> {
+    fn default() -> Self {
+        SourceIndex(Box::default())
+    }
+}
+
+impl<T> SourceIndex<T> {
+    fn new<N: AstNode>(map_back: &ArenaMap<Idx<T>, InFile<AstPtr<N>>>) -> Self {
+        let mut ids = map_back.iter().map(|(id, _)| id).collect::<Vec<_>>();
+        // Desugarings allocate several ids for the same source, the last one is the one which
+        // represents it.
+        ids.sort_by(|&a, &b| {
+            source_key(&map_back[a]).cmp(&source_key(&map_back[b])).then(b.cmp(&a))
+        });
+        ids.dedup_by_key(|id| source_key(&map_back[*id]));
+        SourceIndex(ids.into_boxed_slice())
+    }
+
+    fn get<N: AstNode>(
+        &self,
+        map_back: &ArenaMap<Idx<T>, InFile<AstPtr<N>>>,
+        src: &InFile<AstPtr<N>>,
+    ) -> Option<Idx<T>> {
+        let key = source_key(src);
+        let idx = self.0.binary_search_by(|&id| source_key(&map_back[id]).cmp(&key)).ok()?;
+        Some(self.0[idx])
+    }
+}
+
+fn source_key<N: AstNode>(src: &InFile<AstPtr<N>>) -> (HirFileId, TextSize, TextSize, SyntaxKind) {
+    let range = src.value.text_range();
+    (src.file_id, range.start(), range.end(), src.value.kind())
+}
+
 #[derive(Default, Debug, Eq, PartialEq, Clone, Copy)]
 pub struct SyntheticSyntax;
 
@@ -346,7 +392,7 @@ impl BodySourceMap {
 
     pub fn node_expr(&self, node: InFile<&ast::Expr>) -> Option<ExprId> {
         let src = node.map(AstPtr::new);
-        self.expr_map.get(&src).cloned()
+        self.expr_id(&src)
     }
 
     pub fn node_macro_file(&self, node: InFile<&ast::MacroCall>) -> Option<HirFileId> {
@@ -360,12 +406,12 @@ impl BodySourceMap {
 
     pub fn node_pat(&self, node: InFile<&ast::Pat>) -> Option<PatId> {
         let src = node.map(|it| AstPtr::new(it).wrap_left());
-        self.pat_map.get(&src).cloned()
+        self.pat_id(&src)
     }
 
     pub fn node_self_param(&self, node: InFile<&ast::SelfParam>) -> Option<PatId> {
         let src = node.map(|it| AstPtr::new(it).wrap_right());
-        self.pat_map.get(&src).cloned()
+        self.pat_id(&src)
     }
 
     pub fn label_syntax(&self, label: LabelId) -> LabelSource {
@@ -374,7 +420,7 @@ impl BodySourceMap {
 
     pub fn node_label(&self, node: InFile<&ast::Label>) -> Option<LabelId> {
         let src = node.map(AstPtr::new);
-        self.label_map.get(&src).cloned()
+        self.label_index.get(&self.label_map_back, &src)
     }
 
     pub fn field_syntax(&self, expr: ExprId) -> FieldSource {
@@ -387,7 +433,7 @@ impl BodySourceMap {
 
     pub fn macro_expansion_expr(&self, node: InFile<&ast::MacroExpr>) -> Option<ExprId> {
         let src = node.map(AstPtr::new).map(AstPtr::upcast::<ast::MacroExpr>).map(AstPtr::upcast);
-        self.expr_map.get(&src).copied()
+        self.expr_id(&src)
     }
 
     pub fn implicit_format_args(
@@ -395,7 +441,15 @@ impl BodySourceMap {
         node: InFile<&ast::FormatArgsExpr>,
     ) -> Option<&[(syntax::TextRange, Name)]> {
         let src = node.map(AstPtr::new).map(AstPtr::upcast::<ast::Expr>);
-        self.format_args_template_map.get(self.expr_map.get(&src)?).map(std::ops::Deref::deref)
+        self.format_args_template_map.get(&self.expr_id(&src)?).map(std::ops::Deref::deref)
+    }
+
+    fn expr_id(&self, src: &ExprSource) -> Option<ExprId> {
+        self.expr_map.get(src).copied().or_else(|| self.expr_index.get(&self.expr_map_back, src))
+    }
+
+    fn pat_id(&self, src: &PatSource) -> Option<PatId> {
+        self.pat_map.get(src).copied().or_else(|| self.pat_index.get(&self.pat_map_back, src))
     }
 
     /// Get a reference to the body source map's diagnostics.
@@ -407,10 +461,12 @@ impl BodySourceMap {
         let Self {
             expr_map,
             expr_map_back,
+            expr_index,
             pat_map,
             pat_map_back,
-            label_map,
+            pat_index,
             label_map_back,
+            label_index,
             field_map_back,
             pat_field_map_back,
             expansions,
@@ -420,10 +476,12 @@ impl BodySourceMap {
         format_args_template_map.shrink_to_fit();
         expr_map.shrink_to_fit();
         expr_map_back.shrink_to_fit();
+        *expr_index = SourceIndex::new(expr_map_back);
         pat_map.shrink_to_fit();
         pat_map_back.shrink_to_fit();
-        label_map.shrink_to_fit();
+        *pat_index = SourceIndex::new(pat_map_back);
         label_map_back.shrink_to_fit();
+        *label_index = SourceIndex::new(label_map_back);
         field_map_back.shrink_to_fit();
         pat_field_map_back.shrink_to_fit();
         expansions.shrink_to_fit();
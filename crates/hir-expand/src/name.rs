@@ -1,7 +1,12 @@
 //! See [`Name`].
 
-use std::fmt;
+use std::{
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+};
 
+use intern::Interned;
 use syntax::{ast, format_smolstr, utils::is_raw_identifier, SmolStr};
 
 /// `Name` is a wrapper around string, which is used in hir for both references
@@ -18,12 +23,69 @@ pub struct Name(Repr);
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct UnescapedName<'a>(&'a Name);
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[derive(Debug, Clone)]
 enum Repr {
+    /// Names short enough to be stored inline.
     Text(SmolStr),
+    /// Longer names, which are interned so that all their occurrences share one allocation.
+    Interned(Interned<str>),
+    TupleField(usize),
+}
+
+/// What names are compared and hashed by, regardless of how their text is stored.
+#[derive(PartialEq, Eq, Hash, PartialOrd, Ord)]
+enum ReprKey<'a> {
+    Text(&'a str),
     TupleField(usize),
 }
 
+impl Repr {
+    fn key(&self) -> ReprKey<'_> {
+        match self {
+            Repr::Text(it) => ReprKey::Text(it),
+            Repr::Interned(it) => ReprKey::Text(it),
+            Repr::TupleField(it) => ReprKey::TupleField(*it),
+        }
+    }
+
+    fn as_str(&self) -> Option<&str> {
+        match self {
+            Repr::Text(it) => Some(it),
+            Repr::Interned(it) => Some(it),
+            Repr::TupleField(_) => None,
+        }
+    }
+}
+
+impl PartialEq for Repr {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Repr::Interned(a), Repr::Interned(b)) => a == b,
+            _ => self.key() == other.key(),
+        }
+    }
+}
+
+impl Eq for Repr {}
+
+impl Hash for Repr {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key().hash(state)
+    }
+}
+
+impl PartialOrd for Repr {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Repr {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
 impl UnescapedName<'_> {
     /// Returns the textual representation of this name as a [`SmolStr`]. Prefer using this over
     /// [`ToString::to_string`] if possible as this conversion is cheaper in the general case.
@@ -36,6 +98,7 @@ impl UnescapedName<'_> {
                     it.clone()
                 }
             }
+            Repr::Interned(it) => SmolStr::new(it.strip_prefix("r#").unwrap_or(it)),
             Repr::TupleField(it) => SmolStr::new(it.to_string()),
         }
     }
@@ -49,15 +112,19 @@ impl UnescapedName<'_> {
 impl Name {
     /// Note: this is private to make creating name from random string hard.
     /// Hopefully, this should allow us to integrate hygiene cleaner in the
-    /// future, and to switch to interned representation of names.
-    const fn new_text(text: SmolStr) -> Name {
-        Name(Repr::Text(text))
+    /// future.
+    fn new_text(text: SmolStr) -> Name {
+        if text.is_heap_allocated() {
+            Name(Repr::Interned(Interned::new_str(&text)))
+        } else {
+            Name(Repr::Text(text))
+        }
     }
 
     // FIXME: See above, unfortunately some places really need this right now
     #[doc(hidden)]
-    pub const fn new_text_dont_use(text: SmolStr) -> Name {
-        Name(Repr::Text(text))
+    pub fn new_text_dont_use(text: SmolStr) -> Name {
+        Name::new_text(text)
     }
 
     pub fn new_tuple_field(idx: usize) -> Name {
@@ -70,7 +137,7 @@ impl Name {
 
     /// Shortcut to create inline plain text name. Panics if `text.len() > 22`
     const fn new_static(text: &'static str) -> Name {
-        Name::new_text(SmolStr::new_static(text))
+        Name(Repr::Text(SmolStr::new_static(text)))
     }
 
     /// Resolve a name from the text of token.
@@ -134,16 +201,14 @@ impl Name {
     pub fn as_text(&self) -> Option<SmolStr> {
         match &self.0 {
             Repr::Text(it) => Some(it.clone()),
-            _ => None,
+            Repr::Interned(it) => Some(SmolStr::new(&**it)),
+            Repr::TupleField(_) => None,
         }
     }
 
     /// Returns the text this name represents if it isn't a tuple field.
     pub fn as_str(&self) -> Option<&str> {
-        match &self.0 {
-            Repr::Text(it) => Some(it),
-            _ => None,
-        }
+        self.0.as_str()
     }
 
     /// Returns the textual representation of this name as a [`SmolStr`].
@@ -152,6 +217,7 @@ impl Name {
     pub fn to_smol_str(&self) -> SmolStr {
         match &self.0 {
             Repr::Text(it) => it.clone(),
+            Repr::Interned(it) => SmolStr::new(&**it),
             Repr::TupleField(it) => SmolStr::new(it.to_string()),
         }
     }
@@ -161,10 +227,7 @@ impl Name {
     }
 
     pub fn is_escaped(&self) -> bool {
-        match &self.0 {
-            Repr::Text(it) => it.starts_with("r#"),
-            Repr::TupleField(_) => false,
-        }
+        self.0.as_str().map_or(false, |it| it.starts_with("r#"))
     }
 
     pub fn display<'a>(&'a self, db: &dyn crate::db::ExpandDatabase) -> impl fmt::Display + 'a {
@@ -181,6 +244,7 @@ impl fmt::Display for Display<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match &self.name.0 {
             Repr::Text(text) => fmt::Display::fmt(&text, f),
+            Repr::Interned(text) => fmt::Display::fmt(&text, f),
             Repr::TupleField(idx) => fmt::Display::fmt(&idx, f),
         }
     }
@@ -193,11 +257,12 @@ struct UnescapedDisplay<'a> {
 impl fmt::Display for UnescapedDisplay<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match &self.name.0 .0 {
-            Repr::Text(text) => {
+            Repr::TupleField(idx) => fmt::Display::fmt(&idx, f),
+            repr => {
+                let text = repr.as_str().unwrap_or_default();
                 let text = text.strip_prefix("r#").unwrap_or(text);
                 fmt::Display::fmt(&text, f)
             }
-            Repr::TupleField(idx) => fmt::Display::fmt(&idx, f),
         }
     }
 }
@@ -495,3 +560,22 @@ pub mod known {
 }
 
 pub use crate::name;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interned_names_compare_by_text() {
+        let long = "a_rather_long_identifier_name";
+        let a = Name::resolve(long);
+        let b = Name::new_text_dont_use(SmolStr::new(long));
+        assert!(matches!(a.0, Repr::Interned(_)));
+        assert_eq!(a, b);
+        assert_eq!(a.as_str(), Some(long));
+
+        let short = Name::resolve("b");
+        assert!(a < short);
+        assert!(short < Name::new_tuple_field(0));
+    }
+}
@@ -151,7 +151,10 @@ pub(crate) fn deref_by_trait(
 
     let deref_trait =
         db.lang_item(table.trait_env.krate, LangItem::Deref).and_then(|l| l.as_trait())?;
-    let target = db.trait_data(deref_trait).associated_type_by_name(&name![Target])?;
+    let target = db
+        .lang_item(table.trait_env.krate, LangItem::DerefTarget)
+        .and_then(|l| l.as_type_alias())
+        .or_else(|| db.trait_data(deref_trait).associated_type_by_name(&name![Target]))?;
 
     let projection = {
         let b = TyBuilder::subst_for_def(db, deref_trait, None);
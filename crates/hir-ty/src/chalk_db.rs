@@ -5,7 +5,11 @@ use std::{iter, ops::ControlFlow, sync::Arc};
 
 use tracing::debug;
 
-use chalk_ir::{cast::Caster, fold::shift::Shift, CanonicalVarKinds};
+use chalk_ir::{
+    cast::{Cast, Caster},
+    fold::shift::Shift,
+    CanonicalVarKinds,
+};
 use chalk_solve::rust_ir::{self, OpaqueTyDatumBound, WellKnownTrait};
 
 use base_db::CrateId;
@@ -228,7 +232,7 @@ impl chalk_solve::RustIrDatabase<Interner> for ChalkContext<'_> {
     }
 
     fn custom_clauses(&self) -> Vec<chalk_ir::ProgramClause<Interner>> {
-        vec![]
+        async_fn_trait_clauses(self.db, self.krate)
     }
     fn local_impls_to_coherence_check(&self, _trait_id: TraitId) -> Vec<ImplId> {
         // We don't do coherence checking (yet)
@@ -671,6 +675,122 @@ pub(crate) fn trait_datum_query(
     Arc::new(trait_datum)
 }
 
+/// Clauses for the `AsyncFn*` impls the compiler provides for callables returning futures,
+/// including async closures, whose type implements the `Fn*` traits with the future of their
+/// body as output:
+///
+/// ```text
+/// forall<F, Args, Fut, Output> {
+///     Implemented(F: AsyncFnX<Args>) :- Implemented(F: FnX<Args>),
+///         AliasEq(<F as FnOnce<Args>>::Output = Fut), Implemented(Fut: Future),
+///         AliasEq(<Fut as Future>::Output = Output)
+///     Normalize(<F as AsyncFnOnce<Args>>::CallOnceFuture -> Fut) :- ...
+///     Normalize(<F as AsyncFnOnce<Args>>::Output -> Output) :- ...
+/// }
+/// ```
+fn async_fn_trait_clauses(
+    db: &dyn HirDatabase,
+    krate: CrateId,
+) -> Vec<chalk_ir::ProgramClause<Interner>> {
+    let lang_trait = |item| db.lang_item(krate, item).and_then(|it| it.as_trait());
+    let lang_alias = |item| db.lang_item(krate, item).and_then(|it| it.as_type_alias());
+    let (
+        Some(future_trait),
+        Some(async_fn_once_trait),
+        Some(fn_once_output),
+        Some(async_fn_once_output),
+        Some(call_once_future),
+    ) = (
+        lang_trait(LangItem::Future),
+        lang_trait(LangItem::AsyncFnOnce),
+        lang_alias(LangItem::FnOnceOutput),
+        lang_alias(LangItem::AsyncFnOnceOutput),
+        lang_alias(LangItem::CallOnceFuture),
+    )
+    else {
+        return vec![];
+    };
+    let Some(future_output) = db.trait_data(future_trait).associated_type_by_name(&name![Output])
+    else {
+        return vec![];
+    };
+
+    let var =
+        |index| TyKind::BoundVar(BoundVar::new(DebruijnIndex::INNERMOST, index)).intern(Interner);
+    let (f, args, fut, output) = (var(0), var(1), var(2), var(3));
+    let fn_subst = Substitution::from_iter(Interner, [f, args]);
+    let projection = |alias, substitution: &Substitution| {
+        AliasTy::Projection(ProjectionTy {
+            associated_ty_id: to_assoc_type_id(alias),
+            substitution: substitution.clone(),
+        })
+    };
+    let conditions = |fn_trait| -> Vec<chalk_ir::Goal<Interner>> {
+        vec![
+            TraitRef { trait_id: to_chalk_trait_id(fn_trait), substitution: fn_subst.clone() }
+                .cast(Interner),
+            AliasEq { alias: projection(fn_once_output, &fn_subst), ty: fut.clone() }
+                .cast(Interner),
+            TraitRef {
+                trait_id: to_chalk_trait_id(future_trait),
+                substitution: Substitution::from1(Interner, fut.clone()),
+            }
+            .cast(Interner),
+            AliasEq {
+                alias: projection(future_output, &Substitution::from1(Interner, fut.clone())),
+                ty: output.clone(),
+            }
+            .cast(Interner),
+        ]
+    };
+    let clause = |consequence: chalk_ir::DomainGoal<Interner>,
+                  conditions: Vec<chalk_ir::Goal<Interner>>| {
+        chalk_ir::ProgramClauseData(chalk_ir::Binders::new(
+            chalk_ir::VariableKinds::from_iter(
+                Interner,
+                iter::repeat(chalk_ir::VariableKind::Ty(chalk_ir::TyVariableKind::General)).take(4),
+            ),
+            chalk_ir::ProgramClauseImplication {
+                consequence,
+                conditions: chalk_ir::Goals::from_iter(Interner, conditions),
+                constraints: chalk_ir::Constraints::empty(Interner),
+                priority: chalk_ir::ClausePriority::High,
+            },
+        ))
+        .intern(Interner)
+    };
+
+    let mut clauses = Vec::new();
+    for (async_fn_trait, fn_trait) in [
+        (LangItem::AsyncFnOnce, LangItem::FnOnce),
+        (LangItem::AsyncFnMut, LangItem::FnMut),
+        (LangItem::AsyncFn, LangItem::Fn),
+    ] {
+        let (Some(async_fn_trait), Some(fn_trait)) =
+            (lang_trait(async_fn_trait), lang_trait(fn_trait))
+        else {
+            continue;
+        };
+        let implemented = WhereClause::Implemented(TraitRef {
+            trait_id: to_chalk_trait_id(async_fn_trait),
+            substitution: fn_subst.clone(),
+        });
+        clauses.push(clause(implemented.cast(Interner), conditions(fn_trait)));
+        if async_fn_trait == async_fn_once_trait {
+            for (alias, ty) in
+                [(call_once_future, fut.clone()), (async_fn_once_output, output.clone())]
+            {
+                let normalize = chalk_ir::DomainGoal::Normalize(chalk_ir::Normalize {
+                    alias: projection(alias, &fn_subst),
+                    ty,
+                });
+                clauses.push(clause(normalize, conditions(fn_trait)));
+            }
+        }
+    }
+    clauses
+}
+
 fn well_known_trait_from_lang_item(item: LangItem) -> Option<WellKnownTrait> {
     Some(match item {
         LangItem::Clone => WellKnownTrait::Clone,
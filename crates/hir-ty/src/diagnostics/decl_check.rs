@@ -59,6 +59,17 @@ pub enum CaseType {
     UpperCamelCase,
 }
 
+impl CaseType {
+    /// Converts `ident` to this case, returning `None` if it already follows it.
+    pub fn convert(&self, ident: &str) -> Option<String> {
+        match self {
+            CaseType::LowerSnakeCase => to_lower_snake_case(ident),
+            CaseType::UpperSnakeCase => to_upper_snake_case(ident),
+            CaseType::UpperCamelCase => to_camel_case(ident),
+        }
+    }
+}
+
 impl fmt::Display for CaseType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let repr = match self {
@@ -626,12 +637,7 @@ impl<'a> DeclValidator<'a> {
         S: HasSource<Value = N>,
         L: Lookup<Data = S, Database<'a> = dyn DefDatabase + 'a>,
     {
-        let to_expected_case_type = match expected_case {
-            CaseType::LowerSnakeCase => to_lower_snake_case,
-            CaseType::UpperSnakeCase => to_upper_snake_case,
-            CaseType::UpperCamelCase => to_camel_case,
-        };
-        let Some(replacement) = to_expected_case_type(&name.to_smol_str()).map(|new_name| {
+        let Some(replacement) = expected_case.convert(&name.to_smol_str()).map(|new_name| {
             Replacement { current_name: name.clone(), suggested_text: new_name, expected_case }
         }) else {
             return;
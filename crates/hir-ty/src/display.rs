@@ -1360,7 +1360,7 @@ impl HirDisplay for CallableSig {
 
 fn fn_traits(db: &dyn DefDatabase, trait_: TraitId) -> impl Iterator<Item = TraitId> + '_ {
     let krate = trait_.lookup(db).container.krate();
-    utils::fn_traits(db, krate)
+    utils::fn_traits(db, krate).chain(utils::async_fn_traits(db, krate))
 }
 
 #[derive(Clone, Copy, PartialEq, Eq)]
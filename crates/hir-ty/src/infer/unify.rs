@@ -9,6 +9,7 @@ use chalk_ir::{
 use chalk_solve::infer::ParameterEnaVariableExt;
 use either::Either;
 use ena::unify::UnifyKey;
+use hir_def::lang_item::LangItem;
 use hir_expand::name;
 use triomphe::Arc;
 
@@ -714,12 +715,50 @@ impl<'a> InferenceTable<'a> {
         match ty.callable_sig(self.db) {
             Some(sig) => Some((None, sig.params().to_vec(), sig.ret().clone())),
             None => {
-                let (f, args_ty, return_ty) = self.callable_sig_from_fn_trait(ty, num_args)?;
-                Some((Some(f), args_ty, return_ty))
+                if let Some((f, args_ty, return_ty)) = self.callable_sig_from_fn_trait(ty, num_args)
+                {
+                    return Some((Some(f), args_ty, return_ty));
+                }
+                let (args_ty, return_ty) = self.callable_sig_from_async_fn_trait(ty, num_args)?;
+                Some((None, args_ty, return_ty))
             }
         }
     }
 
+    /// Calling a type only known to implement `AsyncFnOnce`, like a type parameter bounded by
+    /// `AsyncFn`, returns its `CallOnceFuture`.
+    fn callable_sig_from_async_fn_trait(
+        &mut self,
+        ty: &Ty,
+        num_args: usize,
+    ) -> Option<(Vec<Ty>, Ty)> {
+        let krate = self.trait_env.krate;
+        let async_fn_once_trait = self.db.lang_item(krate, LangItem::AsyncFnOnce)?.as_trait()?;
+        let call_once_future =
+            self.db.lang_item(krate, LangItem::CallOnceFuture)?.as_type_alias()?;
+
+        let arg_tys = (0..num_args).map(|_| self.new_type_var()).collect::<Vec<_>>();
+        let arg_ty = TyKind::Tuple(num_args, Substitution::from_iter(Interner, arg_tys.clone()))
+            .intern(Interner);
+
+        let b = TyBuilder::subst_for_def(self.db, async_fn_once_trait, None);
+        if b.remaining() != 2 {
+            return None;
+        }
+        let subst = b.push(ty.clone()).push(arg_ty).build();
+        let projection =
+            TyBuilder::assoc_type_projection(self.db, call_once_future, Some(subst)).build();
+
+        let obligation = InEnvironment {
+            goal: projection.trait_ref(self.db).cast(Interner),
+            environment: self.trait_env.env.clone(),
+        };
+        let canonical = self.canonicalize(obligation.clone());
+        self.db.trait_solve(krate, self.trait_env.block, canonical.value.cast(Interner))?;
+        self.register_obligation(obligation.goal);
+        Some((arg_tys, self.normalize_projection_ty(projection)))
+    }
+
     fn callable_sig_from_fn_trait(
         &mut self,
         ty: &Ty,
@@ -727,8 +766,13 @@ impl<'a> InferenceTable<'a> {
     ) -> Option<(FnTrait, Vec<Ty>, Ty)> {
         let krate = self.trait_env.krate;
         let fn_once_trait = FnTrait::FnOnce.get_id(self.db, krate)?;
-        let trait_data = self.db.trait_data(fn_once_trait);
-        let output_assoc_type = trait_data.associated_type_by_name(&name![Output])?;
+        let output_assoc_type = self
+            .db
+            .lang_item(krate, LangItem::FnOnceOutput)
+            .and_then(|l| l.as_type_alias())
+            .or_else(|| {
+                self.db.trait_data(fn_once_trait).associated_type_by_name(&name![Output])
+            })?;
 
         let mut arg_tys = vec![];
         let arg_ty = TyBuilder::tuple(num_args)
@@ -59,7 +59,10 @@ use chalk_ir::{
     NoSolution,
 };
 use either::Either;
-use hir_def::{hir::ExprId, type_ref::Rawness, GeneralConstId, TypeOrConstParamId};
+use hir_def::{
+    hir::ExprId, lang_item::LangItem, type_ref::Rawness, GeneralConstId, TraitId, TypeAliasId,
+    TypeOrConstParamId,
+};
 use hir_expand::name;
 use la_arena::{Arena, Idx};
 use mir::{MirEvalError, VTableMap};
@@ -835,10 +838,31 @@ pub fn callable_sig_from_fnonce(
     }
     let krate = env.krate;
     let fn_once_trait = FnTrait::FnOnce.get_id(db, krate)?;
-    let output_assoc_type = db.trait_data(fn_once_trait).associated_type_by_name(&name![Output])?;
+    let output_assoc_type = db
+        .lang_item(krate, LangItem::FnOnceOutput)
+        .and_then(|l| l.as_type_alias())
+        .or_else(|| db.trait_data(fn_once_trait).associated_type_by_name(&name![Output]))?;
+
+    callable_sig_from_trait(self_ty, env.clone(), db, fn_once_trait, output_assoc_type).or_else(
+        || {
+            // Types only known to implement `AsyncFnOnce` return its `CallOnceFuture`.
+            let async_fn_once_trait = db.lang_item(krate, LangItem::AsyncFnOnce)?.as_trait()?;
+            let call_once_future =
+                db.lang_item(krate, LangItem::CallOnceFuture)?.as_type_alias()?;
+            callable_sig_from_trait(self_ty, env, db, async_fn_once_trait, call_once_future)
+        },
+    )
+}
 
+fn callable_sig_from_trait(
+    self_ty: &Ty,
+    env: Arc<TraitEnvironment>,
+    db: &dyn HirDatabase,
+    trait_: TraitId,
+    output_assoc_type: TypeAliasId,
+) -> Option<CallableSig> {
     let mut table = InferenceTable::new(db, env);
-    let b = TyBuilder::trait_ref(db, fn_once_trait);
+    let b = TyBuilder::trait_ref(db, trait_);
     if b.remaining() != 2 {
         return None;
     }
@@ -846,6 +870,7 @@ pub fn callable_sig_from_fnonce(
     // Register two obligations:
     // - Self: FnOnce<?args_ty>
     // - <Self as FnOnce<?args_ty>>::Output == ?ret_ty
+    // (or the `AsyncFnOnce` and `CallOnceFuture` equivalents)
     let args_ty = table.new_type_var();
     let trait_ref = b.push(self_ty.clone()).push(args_ty.clone()).build();
     let projection = TyBuilder::assoc_type_projection(
@@ -2143,6 +2143,52 @@ async fn main() {
     )
 }
 
+#[test]
+fn const_block_locals() {
+    check_types(
+        r#"
+fn main() {
+    let w = const {
+        let x = 92u8;
+        x
+    };
+    w;
+  //^ u8
+}
+"#,
+    );
+}
+
+#[test]
+fn const_block_items_of_enclosing_block() {
+    check_types(
+        r#"
+fn main() {
+    struct S;
+    const N: u8 = 1;
+    let w = const { (S, N) };
+    w;
+  //^ (S, u8)
+}
+"#,
+    );
+}
+
+#[test]
+fn let_chain_bindings() {
+    check_types(
+        r#"
+//- minicore: option
+fn f(a: Option<Option<u32>>) {
+    if let Some(x) = a && let Some(y) = x && y > 0 {
+        y;
+      //^ u32
+    }
+}
+"#,
+    );
+}
+
 #[test]
 fn async_fn_and_try_operator() {
     check_no_mismatches(
@@ -122,6 +122,47 @@ async fn test() {
     );
 }
 
+#[test]
+fn infer_async_fn_bounds() {
+    check_types(
+        r#"
+//- minicore: async_fn, option
+async fn call<F: AsyncFn(i32) -> Option<u64>>(f: F) {
+    let x = f(4).await;
+    x;
+//  ^ Option<u64>
+}
+
+async fn call_once<F>(f: F)
+where
+    F: AsyncFnOnce() -> u8,
+{
+    let x = f().await;
+    x;
+//  ^ u8
+}
+
+async fn call_impl(mut f: impl AsyncFnMut(u8) -> u16) {
+    f;
+//  ^ impl AsyncFnMut(u8) -> u16
+    let x = f(1).await;
+    x;
+//  ^ u16
+}
+
+fn take<F: AsyncFn(u32) -> u32>(f: F) {}
+
+fn test() {
+    take(async |x| {
+        x;
+    //  ^ u32
+        x
+    });
+}
+"#,
+    );
+}
+
 #[test]
 fn auto_sized_async_block() {
     check_no_mismatches(
@@ -714,6 +755,33 @@ fn test(s: Arc<S>) {
     );
 }
 
+#[test]
+fn deref_target_by_lang_item() {
+    check_types(
+        r#"
+#[lang = "sized"]
+trait Sized {}
+#[lang = "deref"]
+trait Deref {
+    #[lang = "deref_target"]
+    type Pointee: ?Sized;
+    fn deref(&self) -> &Self::Pointee;
+}
+
+struct A;
+struct B;
+impl Deref for A {
+    type Pointee = B;
+    fn deref(&self) -> &B { loop {} }
+}
+
+fn test(a: A) {
+    *a;
+} //^^ B
+"#,
+    );
+}
+
 #[test]
 fn deref_trait_with_inference_var() {
     check_types(
@@ -48,6 +48,16 @@ pub(crate) fn fn_traits(
         .flat_map(|it| it.as_trait())
 }
 
+pub(crate) fn async_fn_traits(
+    db: &dyn DefDatabase,
+    krate: CrateId,
+) -> impl Iterator<Item = TraitId> + '_ {
+    [LangItem::AsyncFn, LangItem::AsyncFnMut, LangItem::AsyncFnOnce]
+        .into_iter()
+        .filter_map(move |lang| db.lang_item(krate, lang))
+        .flat_map(|it| it.as_trait())
+}
+
 /// Returns an iterator over the whole super trait hierarchy (including the
 /// trait itself).
 pub fn all_super_traits(db: &dyn DefDatabase, trait_: TraitId) -> SmallVec<[TraitId; 4]> {
@@ -595,7 +595,8 @@ impl AnyDiagnostic {
                 UnresolvedAssocItem { expr_or_pat }.into()
             }
             &InferenceDiagnostic::UnresolvedIdent { expr } => {
-                let expr = expr_syntax(expr);
+                // Captures of format strings are desugared, they are diagnosed by the IDE instead.
+                let expr = source_map.expr_syntax(expr).ok()?;
                 UnresolvedIdent { expr }.into()
             }
             &InferenceDiagnostic::BreakOutsideOfLoop { expr, is_break, bad_value_break } => {
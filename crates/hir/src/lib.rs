@@ -109,7 +109,7 @@ pub use crate::{
 pub use {
     cfg::{CfgAtom, CfgExpr, CfgOptions},
     hir_def::{
-        attr::{builtin::AttributeTemplate, AttrSourceMap, Attrs, AttrsWithOwner},
+        attr::{builtin::AttributeTemplate, AttrSourceMap, Attrs, AttrsWithOwner, Deprecation},
         data::adt::StructKind,
         find_path::PrefixKind,
         import_map,
@@ -1991,6 +1991,27 @@ impl Function {
             || data.attrs.export_name().map(core::ops::Deref::deref) == Some("main")
     }
 
+    /// Is this function declared in an `extern` block?
+    pub fn is_extern_decl(self, db: &dyn HirDatabase) -> bool {
+        matches!(self.id.lookup(db.upcast()).container, ItemContainerId::ExternBlockId(_))
+    }
+
+    /// The name of the symbol this function is linked as: the `#[link_name]` or the name of a
+    /// declaration in an `extern` block, or the `#[export_name]` or the name of a `#[no_mangle]`
+    /// definition.
+    pub fn link_symbol(self, db: &dyn HirDatabase) -> Option<SmolStr> {
+        let data = db.function_data(self.id);
+        if self.is_extern_decl(db) {
+            return Some(
+                data.attrs.link_name().cloned().unwrap_or_else(|| data.name.to_smol_str()),
+            );
+        }
+        match data.attrs.export_name() {
+            Some(name) => Some(name.clone()),
+            None => data.attrs.is_no_mangle().then(|| data.name.to_smol_str()),
+        }
+    }
+
     /// Does this function have the ignore attribute?
     pub fn is_ignore(self, db: &dyn HirDatabase) -> bool {
         db.function_data(self.id).attrs.is_ignore()
@@ -4094,6 +4115,59 @@ impl Type {
         self.impls_trait(db, copy_trait.into(), &[])
     }
 
+    /// Returns whether dropping a value of this type runs any code, that is whether the type or
+    /// any of the types it owns implements `Drop`.
+    pub fn has_significant_drop(&self, db: &dyn HirDatabase) -> bool {
+        let drop_trait = match db.lang_item(self.env.krate, LangItem::Drop) {
+            Some(LangItemTarget::Trait(it)) => it,
+            _ => return false,
+        };
+        self.has_significant_drop_rec(db, drop_trait.into(), &mut FxHashSet::default())
+    }
+
+    fn has_significant_drop_rec(
+        &self,
+        db: &dyn HirDatabase,
+        drop_trait: Trait,
+        visited: &mut FxHashSet<Ty>,
+    ) -> bool {
+        if !visited.insert(self.ty.clone()) {
+            return false;
+        }
+        if self.impls_trait(db, drop_trait, &[]) {
+            return true;
+        }
+        match self.ty.kind(Interner) {
+            TyKind::Adt(hir_ty::AdtId(adt), substs) => {
+                let variants: Vec<hir_def::VariantId> = match *adt {
+                    AdtId::StructId(it) => vec![it.into()],
+                    // union fields are never dropped implicitly
+                    AdtId::UnionId(_) => return false,
+                    AdtId::EnumId(it) => {
+                        db.enum_data(it).variants.iter().map(|&(id, _)| id.into()).collect()
+                    }
+                };
+                variants.into_iter().any(|variant| {
+                    db.field_types(variant).iter().any(|(_, ty)| {
+                        self.derived(ty.clone().substitute(Interner, substs))
+                            .has_significant_drop_rec(db, drop_trait, visited)
+                    })
+                })
+            }
+            TyKind::Tuple(_, substs) => {
+                substs.iter(Interner).filter_map(|it| it.ty(Interner)).any(|ty| {
+                    self.derived(ty.clone()).has_significant_drop_rec(db, drop_trait, visited)
+                })
+            }
+            TyKind::Array(ty, _) | TyKind::Slice(ty) => {
+                self.derived(ty.clone()).has_significant_drop_rec(db, drop_trait, visited)
+            }
+            // we can't see through these, so assume the worst unless they are `Copy`
+            TyKind::Dyn(_) | TyKind::Placeholder(_) | TyKind::Closure(..) => !self.is_copy(db),
+            _ => false,
+        }
+    }
+
     pub fn as_callable(&self, db: &dyn HirDatabase) -> Option<Callable> {
         let mut the_ty = &self.ty;
         let callee = match self.ty.kind(Interner) {
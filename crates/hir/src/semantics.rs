@@ -9,18 +9,23 @@ use std::{
 };
 
 use base_db::{FileId, FileRange};
+use cfg::CfgExpr;
 use either::Either;
 use hir_def::{
     hir::Expr,
+    item_scope::{ImportId, ImportOrExternCrate},
     lower::LowerCtx,
     nameres::MacroSubNs,
-    resolver::{self, HasResolver, Resolver, TypeNs},
+    resolver::{self, HasResolver, ResolveValueResult, Resolver, TypeNs},
     type_ref::Mutability,
-    AsMacroCall, DefWithBodyId, FunctionId, MacroId, TraitId, VariantId,
+    AsMacroCall, DefWithBodyId, FunctionId, Lookup, MacroId, TraitId, VariantId,
 };
 use hir_expand::{
-    attrs::collect_attrs, db::ExpandDatabase, files::InRealFile, name::AsName, ExpansionInfo,
-    InMacroFile, MacroCallId, MacroFileId, MacroFileIdExt,
+    attrs::{collect_attrs, RawAttrs},
+    db::ExpandDatabase,
+    files::InRealFile,
+    name::AsName,
+    ExpansionInfo, InMacroFile, MacroCallId, MacroFileId, MacroFileIdExt,
 };
 use itertools::Itertools;
 use rustc_hash::{FxHashMap, FxHashSet};
@@ -1261,6 +1266,20 @@ impl<'db> SemanticsImpl<'db> {
         )
     }
 
+    /// Returns the `#[cfg]` predicate that excludes `node` from compilation, if `node` lies in
+    /// code that is disabled for the crate it belongs to.
+    pub fn inactive_cfg(&self, node: &SyntaxNode) -> Option<CfgExpr> {
+        let file_id = self.find_file(node).file_id;
+        let cfg_options = self.scope(node)?.krate().cfg(self.db);
+        let span_map = self.db.span_map(file_id);
+        node.ancestors().filter_map(ast::AnyHasAttrs::cast).find_map(|owner| {
+            RawAttrs::new(self.db.upcast(), &owner, span_map.as_ref())
+                .iter()
+                .filter_map(|attr| attr.cfg())
+                .find(|cfg| cfg_options.check(cfg) == Some(false))
+        })
+    }
+
     /// Search for a definition's source and cache its syntax tree
     pub fn source<Def: HasSource>(&self, def: Def) -> Option<InFile<Def::Ast>>
     where
@@ -1644,6 +1663,46 @@ impl SemanticsScope<'_> {
         resolve_hir_path(self.db, &self.resolver, &path)
     }
 
+    /// Returns the `use` trees that the resolution of the last segment of `path` at this scope
+    /// goes through, in any namespace. Names brought in by glob imports are not traced back to
+    /// their `use`.
+    pub fn imports_of_path(&self, path: &ast::Path) -> Vec<InFile<ast::UseTree>> {
+        let db: &dyn hir_def::db::DefDatabase = self.db.upcast();
+        let ctx = LowerCtx::with_file_id(db, self.file_id);
+        let Some(path) = Path::from_src(&ctx, path.clone()) else { return Vec::new() };
+
+        let mut imports = Vec::new();
+        let type_import = match self.resolver.resolve_path_in_type_ns(db, &path) {
+            Some((_, _, import)) => import,
+            None => None,
+        };
+        imports.extend(type_import.and_then(ImportOrExternCrate::into_import));
+        match self.resolver.resolve_path_in_value_ns(db, &path) {
+            Some(ResolveValueResult::ValueNs(_, import)) => imports.extend(import),
+            Some(ResolveValueResult::Partial(_, _, import)) => {
+                imports.extend(import.and_then(ImportOrExternCrate::into_import))
+            }
+            None => (),
+        }
+        if let Some(mod_path) = path.mod_path() {
+            if let Some((_, import)) = self.resolver.resolve_path_as_macro(db, mod_path, None) {
+                imports.extend(import);
+            }
+        }
+
+        imports.sort();
+        imports.dedup();
+        imports
+            .into_iter()
+            .map(|ImportId { import, idx }| {
+                let loc = import.lookup(db);
+                let file_id = loc.id.file_id();
+                let use_ = &loc.id.item_tree(db)[loc.id.value];
+                InFile::new(file_id, use_.use_tree_to_ast(db, file_id, idx))
+            })
+            .collect()
+    }
+
     /// Iterates over associated types that may be specified after the given path (using
     /// `Ty::Assoc` syntax).
     pub fn assoc_type_shorthand_candidates<R>(
@@ -1,6 +1,6 @@
 //! File symbol extraction.
 
-use base_db::FileRange;
+use base_db::{FileId, FileRange};
 use hir_def::{
     db::DefDatabase,
     item_scope::ItemInNs,
@@ -8,8 +8,10 @@ use hir_def::{
     AdtId, AssocItemId, DefWithBodyId, HasModule, ImplId, Lookup, MacroId, ModuleDefId, ModuleId,
     TraitId,
 };
-use hir_expand::{HirFileId, InFile};
+use hir_expand::{db::ExpandDatabase, HirFileId, InFile};
 use hir_ty::{db::HirDatabase, display::HirDisplay};
+use rustc_hash::FxHashSet;
+use span::HirFileIdRepr;
 use syntax::{ast::HasName, AstNode, AstPtr, SmolStr, SyntaxNode, SyntaxNodePtr};
 
 use crate::{Module, ModuleDef, Semantics};
@@ -74,6 +76,7 @@ pub struct SymbolCollector<'a> {
     symbols: Vec<FileSymbol>,
     work: Vec<SymbolCollectorWork>,
     current_container_name: Option<SmolStr>,
+    skip_bodies_in: Option<&'a FxHashSet<FileId>>,
 }
 
 /// Given a [`ModuleId`] and a [`HirDatabase`], use the DefMap for the module's crate to collect
@@ -85,9 +88,16 @@ impl<'a> SymbolCollector<'a> {
             symbols: Default::default(),
             work: Default::default(),
             current_container_name: None,
+            skip_bodies_in: None,
         }
     }
 
+    /// Doesn't lower the bodies of the items in `files` (or included by them) to find the items
+    /// declared in there, so that huge generated files are only lowered when they are used.
+    pub fn skip_bodies_in(&mut self, files: &'a FxHashSet<FileId>) {
+        self.skip_bodies_in = Some(files);
+    }
+
     pub fn collect(&mut self, module: Module) {
         // The initial work is the root module we're collecting, additional work will
         // be populated as we traverse the module's definitions.
@@ -215,6 +225,9 @@ impl<'a> SymbolCollector<'a> {
 
     fn collect_from_body(&mut self, body_id: impl Into<DefWithBodyId>) {
         let body_id = body_id.into();
+        if self.skips_body(body_id) {
+            return;
+        }
         let body = self.db.body(body_id);
 
         // Descend into the blocks and enqueue collection of all modules within.
@@ -228,6 +241,33 @@ impl<'a> SymbolCollector<'a> {
         }
     }
 
+    fn skips_body(&self, body_id: DefWithBodyId) -> bool {
+        let Some(files) = self.skip_bodies_in else { return false };
+        let db: &dyn DefDatabase = self.db.upcast();
+        let mut file_id = match body_id {
+            DefWithBodyId::FunctionId(it) => it.lookup(db).id.file_id(),
+            DefWithBodyId::StaticId(it) => it.lookup(db).id.file_id(),
+            DefWithBodyId::ConstId(it) => it.lookup(db).id.file_id(),
+            DefWithBodyId::VariantId(it) => it.lookup(db).id.file_id(),
+            DefWithBodyId::InTypeConstId(_) => return false,
+        };
+        let db: &dyn ExpandDatabase = db.upcast();
+        loop {
+            match file_id.repr() {
+                HirFileIdRepr::FileId(it) => return files.contains(&it),
+                HirFileIdRepr::MacroFile(macro_file) => {
+                    let loc = db.lookup_intern_macro_call(macro_file.macro_call_id);
+                    if let Some(included) = loc.include_file_id(db, macro_file.macro_call_id) {
+                        if files.contains(&included) {
+                            return true;
+                        }
+                    }
+                    file_id = loc.kind.file_id();
+                }
+            }
+        }
+    }
+
     fn collect_from_impl(&mut self, impl_id: ImplId) {
         let impl_data = self.db.impl_data(impl_id);
         let impl_name = Some(SmolStr::new(impl_data.self_ty.display(self.db).to_string()));
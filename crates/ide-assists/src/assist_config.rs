@@ -16,4 +16,38 @@ pub struct AssistConfig {
     pub prefer_no_std: bool,
     pub prefer_prelude: bool,
     pub assist_emit_must_use: bool,
+    pub group_missing_match_arms: bool,
+    pub smart_impl_member_stubs: bool,
+    pub wrap_return_type_callers: WrapReturnTypeCallers,
+    pub promoted_const_placement: PromotedConstPlacement,
+    pub panic_error_type: PanicErrorType,
+}
+
+/// How calls to a function get updated when its return type is wrapped into `Result` or `Option`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WrapReturnTypeCallers {
+    /// Leave the calls as they are.
+    None,
+    /// Use `?` in callers returning the same wrapper type, `.unwrap()` elsewhere.
+    Try,
+    /// Always `.unwrap()` the returned value.
+    Unwrap,
+}
+
+/// Where a local promoted to a module-level constant gets declared.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PromotedConstPlacement {
+    /// Right before the item containing the local.
+    BeforeItem,
+    /// At the top of the module, after its imports.
+    ModuleTop,
+}
+
+/// Which error type a function gets when its panics are turned into `Result` propagation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PanicErrorType {
+    /// A dedicated error enum with a variant per distinct error type.
+    Enum,
+    /// `anyhow::Error`, attaching the panic messages as context.
+    Anyhow,
 }
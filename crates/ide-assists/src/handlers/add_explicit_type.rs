@@ -1,12 +1,13 @@
 use hir::HirDisplay;
-use ide_db::syntax_helpers::node_ext::walk_ty;
-use syntax::ast::{self, AstNode, LetStmt, Param};
+use ide_db::{famous_defs::FamousDefs, syntax_helpers::node_ext::walk_ty};
+use syntax::ast::{self, AstNode, HasName, LetStmt, Param};
 
-use crate::{AssistContext, AssistId, AssistKind, Assists};
+use crate::{AssistContext, AssistId, AssistKind, Assists, GroupLabel};
 
 // Assist: add_explicit_type
 //
-// Specify type for a let binding.
+// Specify type for a let binding, a closure parameter, a `const` or `static` item, or replace a
+// `_` placeholder in a function's return type.
 //
 // ```
 // fn main() {
@@ -20,7 +21,9 @@ use crate::{AssistContext, AssistId, AssistKind, Assists};
 // }
 // ```
 pub(crate) fn add_explicit_type(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
-    let (ascribed_ty, expr, pat) = if let Some(let_stmt) = ctx.find_node_at_offset::<LetStmt>() {
+    let (ascribed_ty, target, anchor, ty, allows_impl_trait) = if let Some(let_stmt) =
+        ctx.find_node_at_offset::<LetStmt>()
+    {
         let cursor_in_range = {
             let eq_range = let_stmt.eq_token()?.text_range();
             ctx.offset() < eq_range.start()
@@ -30,19 +33,52 @@ pub(crate) fn add_explicit_type(acc: &mut Assists, ctx: &AssistContext<'_>) -> O
             return None;
         }
 
-        (let_stmt.ty(), let_stmt.initializer(), let_stmt.pat()?)
+        let pat = let_stmt.pat()?;
+        let ty = match (&pat, let_stmt.initializer()) {
+            (ast::Pat::IdentPat(_), Some(expr)) => ctx.sema.type_of_expr(&expr)?,
+            (pat, _) => ctx.sema.type_of_pat(pat)?,
+        };
+        (let_stmt.ty(), pat.syntax().text_range(), pat.syntax().clone(), ty, false)
     } else if let Some(param) = ctx.find_node_at_offset::<Param>() {
         if param.syntax().ancestors().nth(2).and_then(ast::ClosureExpr::cast).is_none() {
             cov_mark::hit!(add_explicit_type_not_applicable_in_fn_param);
             return None;
         }
-        (param.ty(), None, param.pat()?)
+        let pat = param.pat()?;
+        let ty = ctx.sema.type_of_pat(&pat)?;
+        (param.ty(), pat.syntax().text_range(), pat.syntax().clone(), ty, false)
+    } else if let Some(item) = ctx.find_node_at_offset::<ast::Item>() {
+        match item {
+            // Annotations are mandatory for these, but the parser recovers from missing ones.
+            ast::Item::Const(it) => {
+                if ctx.offset() >= it.eq_token()?.text_range().start() {
+                    return None;
+                }
+                let ty = ctx.sema.type_of_expr(&it.body()?)?;
+                (it.ty(), it.name()?.syntax().text_range(), it.syntax().clone(), ty, false)
+            }
+            ast::Item::Static(it) => {
+                if ctx.offset() >= it.eq_token()?.text_range().start() {
+                    return None;
+                }
+                let ty = ctx.sema.type_of_expr(&it.body()?)?;
+                (it.ty(), it.name()?.syntax().text_range(), it.syntax().clone(), ty, false)
+            }
+            ast::Item::Fn(it) => {
+                let ret_ty = it.ret_type()?.ty()?;
+                if !ret_ty.syntax().text_range().contains_range(ctx.selection_trimmed()) {
+                    return None;
+                }
+                let ty = ctx.sema.type_of_expr(&ast::Expr::BlockExpr(it.body()?))?;
+                (Some(ret_ty.clone()), ret_ty.syntax().text_range(), it.syntax().clone(), ty, true)
+            }
+            _ => return None,
+        }
     } else {
         return None;
     };
 
-    let module = ctx.sema.scope(pat.syntax())?.module();
-    let pat_range = pat.syntax().text_range();
+    let module = ctx.sema.scope(&anchor)?.module();
 
     // Don't enable the assist if there is a type ascription without any placeholders
     if let Some(ty) = &ascribed_ty {
@@ -57,39 +93,86 @@ pub(crate) fn add_explicit_type(acc: &mut Assists, ctx: &AssistContext<'_>) -> O
         }
     }
 
-    let ty = match (pat, expr) {
-        (ast::Pat::IdentPat(_), Some(expr)) => ctx.sema.type_of_expr(&expr)?,
-        (pat, _) => ctx.sema.type_of_pat(&pat)?,
-    }
-    .adjusted();
-
+    let ty = ty.adjusted();
+    let mut candidates = Vec::new();
     // Fully unresolved or unnameable types can't be annotated
-    if (ty.contains_unknown() && ty.type_arguments().count() == 0) || ty.is_closure() {
+    if !(ty.contains_unknown() && ty.type_arguments().count() == 0) && !ty.is_closure() {
+        candidates.extend(ty.display_source_code(ctx.db(), module.into(), false).ok());
+    }
+    if let Some(bound) = trait_bound(ctx, &ty, module) {
+        if allows_impl_trait {
+            candidates.push(format!("impl {bound}"));
+        }
+    }
+    if let Some((pointer, pointee)) = pointer_like(ctx, &ty) {
+        if let Some(bound) = trait_bound(ctx, &pointee, module) {
+            candidates.push(pointer.replace("{}", &format!("dyn {bound}")));
+        }
+    }
+    if candidates.is_empty() {
         cov_mark::hit!(add_explicit_type_not_applicable_if_ty_not_inferred);
         return None;
     }
 
-    let inferred_type = ty.display_source_code(ctx.db(), module.into(), false).ok()?;
-    acc.add(
-        AssistId("add_explicit_type", AssistKind::RefactorRewrite),
-        format!("Insert explicit type `{inferred_type}`"),
-        pat_range,
-        |builder| match ascribed_ty {
-            Some(ascribed_ty) => {
-                builder.replace(ascribed_ty.syntax().text_range(), inferred_type);
-            }
-            None => {
-                builder.insert(pat_range.end(), format!(": {inferred_type}"));
-            }
-        },
-    )
+    for candidate in candidates {
+        acc.add_group(
+            &GroupLabel("Insert explicit type".to_owned()),
+            AssistId("add_explicit_type", AssistKind::RefactorRewrite),
+            format!("Insert explicit type `{candidate}`"),
+            target,
+            |builder| match &ascribed_ty {
+                Some(ascribed_ty) => {
+                    builder.replace(ascribed_ty.syntax().text_range(), candidate);
+                }
+                None => {
+                    builder.insert(target.end(), format!(": {candidate}"));
+                }
+            },
+        );
+    }
+    Some(())
+}
+
+/// The trait the type is best known by, for types that are usually referred to through one.
+fn trait_bound(ctx: &AssistContext<'_>, ty: &hir::Type, module: hir::Module) -> Option<String> {
+    let db = ctx.db();
+    if ty.is_closure() {
+        let bound = ty.display_source_code(db, module.into(), true).ok()?;
+        return bound.strip_prefix("impl ").map(ToOwned::to_owned);
+    }
+    let iterator = FamousDefs(&ctx.sema, module.krate()).core_iter_Iterator()?;
+    if !ty.impls_trait(db, iterator, &[]) {
+        return None;
+    }
+    let item = iterator.items(db).into_iter().find_map(|it| match it {
+        hir::AssocItem::TypeAlias(alias) if alias.name(db).to_smol_str() == "Item" => Some(alias),
+        _ => None,
+    })?;
+    let item = ty.normalize_trait_assoc_type(db, &[], item)?;
+    let item = item.display_source_code(db, module.into(), false).ok()?;
+    Some(format!("Iterator<Item = {item}>"))
+}
+
+/// Splits references and smart pointers into a template for the pointer, with `{}` standing in
+/// for the pointee, and the pointee's type.
+fn pointer_like(ctx: &AssistContext<'_>, ty: &hir::Type) -> Option<(String, hir::Type)> {
+    if let Some((pointee, mutability)) = ty.as_reference() {
+        return Some((format!("&{}{{}}", mutability.as_keyword_for_ref()), pointee));
+    }
+    let name = ty.as_adt()?.name(ctx.db()).to_smol_str();
+    if !matches!(name.as_str(), "Box" | "Rc" | "Arc") {
+        return None;
+    }
+    Some((format!("{name}<{{}}>"), ty.type_arguments().next()?))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    use crate::tests::{check_assist, check_assist_not_applicable, check_assist_target};
+    use crate::tests::{
+        check_assist, check_assist_by_label, check_assist_not_applicable, check_assist_target,
+    };
 
     #[test]
     fn add_explicit_type_target() {
@@ -320,6 +403,73 @@ fn f() {
         y = Some(3);
     };
 }
+"#,
+        );
+    }
+
+    #[test]
+    fn add_explicit_type_const_and_static() {
+        check_assist(add_explicit_type, r#"const MAX$0 = 5u8;"#, r#"const MAX: u8 = 5u8;"#);
+        check_assist(
+            add_explicit_type,
+            r#"static mut COUNT$0: _ = 0usize;"#,
+            r#"static mut COUNT: usize = 0usize;"#,
+        );
+    }
+
+    #[test]
+    fn add_explicit_type_impl_trait_in_return_position() {
+        check_assist(
+            add_explicit_type,
+            r#"
+//- minicore: fn
+fn adder(n: i32) -> _$0 {
+    move |x: i32| x + n
+}
+"#,
+            r#"
+fn adder(n: i32) -> impl Fn(i32) -> i32 {
+    move |x: i32| x + n
+}
+"#,
+        );
+        check_assist_by_label(
+            add_explicit_type,
+            r#"
+//- minicore: iterator
+struct Counter(u32);
+impl Iterator for Counter {
+    type Item = u32;
+    fn next(&mut self) -> Option<u32> { None }
+}
+fn counter() -> $0_ { Counter(0) }
+"#,
+            r#"
+struct Counter(u32);
+impl Iterator for Counter {
+    type Item = u32;
+    fn next(&mut self) -> Option<u32> { None }
+}
+fn counter() -> impl Iterator<Item = u32> { Counter(0) }
+"#,
+            "Insert explicit type `impl Iterator<Item = u32>`",
+        );
+    }
+
+    #[test]
+    fn add_explicit_type_trait_object_behind_reference() {
+        check_assist(
+            add_explicit_type,
+            r#"
+//- minicore: fn
+fn f() {
+    let g$0 = &mut |x: u8| x;
+}
+"#,
+            r#"
+fn f() {
+    let g: &mut dyn Fn(u8) -> u8 = &mut |x: u8| x;
+}
 "#,
         );
     }
@@ -1,5 +1,10 @@
-use hir::HasSource;
-use syntax::ast::{self, make, AstNode};
+use hir::{HasSource, ModuleDef};
+use ide_db::{famous_defs::FamousDefs, helpers::mod_path_to_ast};
+use syntax::{
+    ast::{self, make, AstNode, HasName},
+    ted::{self, Position},
+    T,
+};
 
 use crate::{
     assist_context::{AssistContext, Assists},
@@ -142,6 +147,20 @@ fn add_missing_impl_members_inner(
         return None;
     }
 
+    let smart_stubs = match mode {
+        DefaultMethods::No if ctx.config.smart_impl_member_stubs => {
+            let missing_names = missing_items
+                .iter()
+                .filter_map(|it| assoc_item_name(&it.value))
+                .collect::<Vec<_>>();
+            smart_stubs(ctx, trait_, &impl_def)
+                .into_iter()
+                .filter(|(name, _)| missing_names.iter().any(|it| it == name))
+                .collect()
+        }
+        _ => Vec::new(),
+    };
+
     let target = impl_def.syntax().text_range();
     acc.add(AssistId(assist_id, AssistKind::QuickFix), label, target, |edit| {
         let new_impl_def = edit.make_mut(impl_def.clone());
@@ -153,6 +172,16 @@ fn add_missing_impl_members_inner(
             target_scope,
         );
 
+        if let Some(assoc_item_list) = new_impl_def.assoc_item_list() {
+            for item in assoc_item_list.assoc_items() {
+                let stub = assoc_item_name(&item)
+                    .and_then(|name| smart_stubs.iter().find(|(it, _)| *it == name));
+                if let Some((_, stub)) = stub {
+                    apply_smart_stub(&item, stub);
+                }
+            }
+        }
+
         if let Some(cap) = ctx.config.snippet_cap {
             let mut placeholder = None;
             if let DefaultMethods::No = mode {
@@ -190,9 +219,145 @@ fn try_gen_trait_body(
     gen_trait_fn_body(func, &trait_path, &adt.value, Some(trait_ref))
 }
 
+/// A body or type for a member of a well-known trait that is a better guess than `todo!()`.
+enum SmartStub {
+    /// `Debug::fmt(self, f)`
+    DebugFmt(ast::Path),
+    /// `&self.field` or `&mut self.field`
+    FieldRef { field: String, mutable: bool },
+    /// `self.field.next()`
+    FieldNext(String),
+    /// The value of an associated type.
+    Type(ast::Type),
+}
+
+fn smart_stubs(
+    ctx: &AssistContext<'_>,
+    trait_: hir::Trait,
+    impl_def: &ast::Impl,
+) -> Vec<(&'static str, SmartStub)> {
+    let db = ctx.db();
+    let mut stubs = Vec::new();
+    let Some(self_ty) = impl_def.self_ty().and_then(|it| ctx.sema.resolve_type(&it)) else {
+        return stubs;
+    };
+    let Some(module) = ctx.sema.scope(impl_def.syntax()).map(|it| it.module()) else {
+        return stubs;
+    };
+    let famous_defs = FamousDefs(&ctx.sema, module.krate());
+    let make_ty = |ty: &hir::Type| {
+        ty.display_source_code(db, module.into(), false).ok().map(|it| make::ty(&it))
+    };
+    // The field a newtype-like struct delegates to.
+    let single_field = match (self_ty.as_adt(), self_ty.fields(db).as_slice()) {
+        (Some(hir::Adt::Struct(_)), [(field, ty)]) => {
+            Some((field.name(db).display(db).to_string(), ty.clone()))
+        }
+        _ => None,
+    };
+
+    if Some(trait_) == famous_defs.core_fmt_Display() {
+        let debug = famous_defs.core_fmt_Debug().filter(|it| self_ty.impls_trait(db, *it, &[]));
+        let debug_path = debug.and_then(|it| {
+            module.find_use_path(
+                db,
+                ModuleDef::Trait(it),
+                ctx.config.prefer_no_std,
+                ctx.config.prefer_prelude,
+            )
+        });
+        if let Some(path) = debug_path {
+            stubs.push(("fmt", SmartStub::DebugFmt(mod_path_to_ast(&path))));
+        }
+    } else if Some(trait_) == famous_defs.core_ops_Deref()
+        || Some(trait_) == famous_defs.core_ops_DerefMut()
+    {
+        if let Some((field, ty)) = single_field {
+            if let Some(ty) = make_ty(&ty) {
+                stubs.push(("Target", SmartStub::Type(ty)));
+            }
+            stubs.push(("deref", SmartStub::FieldRef { field: field.clone(), mutable: false }));
+            stubs.push(("deref_mut", SmartStub::FieldRef { field, mutable: true }));
+        }
+    } else if Some(trait_) == famous_defs.core_iter_Iterator() {
+        if let Some((field, ty)) = single_field.filter(|(_, ty)| ty.impls_trait(db, trait_, &[])) {
+            let item = trait_.items(db).into_iter().find_map(|it| match it {
+                hir::AssocItem::TypeAlias(alias)
+                    if alias.name(db).display(db).to_string() == "Item" =>
+                {
+                    Some(alias)
+                }
+                _ => None,
+            });
+            let item_ty = item.and_then(|it| ty.normalize_trait_assoc_type(db, &[], it));
+            if let Some(item_ty) = item_ty.as_ref().and_then(make_ty) {
+                stubs.push(("Item", SmartStub::Type(item_ty)));
+            }
+            stubs.push(("next", SmartStub::FieldNext(field)));
+        }
+    }
+    stubs
+}
+
+fn apply_smart_stub(item: &ast::AssocItem, stub: &SmartStub) -> Option<()> {
+    let expr = match (item, stub) {
+        (ast::AssocItem::TypeAlias(type_alias), SmartStub::Type(ty)) => {
+            if type_alias.ty().is_some() {
+                return None;
+            }
+            ted::insert_all_raw(
+                Position::before(type_alias.semicolon_token()?),
+                vec![
+                    make::tokens::single_space().into(),
+                    make::token(T![=]).into(),
+                    make::tokens::single_space().into(),
+                    ty.clone_for_update().syntax().clone().into(),
+                ],
+            );
+            return Some(());
+        }
+        (ast::AssocItem::Fn(fn_), SmartStub::DebugFmt(debug_path)) => {
+            let formatter = fn_.param_list()?.params().next()?.pat()?;
+            make::expr_call(
+                make::expr_path(make::path_from_text(&format!("{debug_path}::fmt"))),
+                make::arg_list([
+                    make::ext::expr_self(),
+                    make::expr_path(make::ext::ident_path(&formatter.to_string())),
+                ]),
+            )
+        }
+        (ast::AssocItem::Fn(_), SmartStub::FieldRef { field, mutable }) => {
+            make::expr_ref(make::expr_field(make::ext::expr_self(), field), *mutable)
+        }
+        (ast::AssocItem::Fn(_), SmartStub::FieldNext(field)) => make::expr_method_call(
+            make::expr_field(make::ext::expr_self(), field),
+            make::name_ref("next"),
+            make::arg_list(None),
+        ),
+        _ => return None,
+    };
+    let ast::AssocItem::Fn(fn_) = item else { return None };
+    let tail_expr = fn_.body()?.stmt_list()?.tail_expr()?;
+    ted::replace(tail_expr.syntax(), expr.clone_for_update().syntax());
+    Some(())
+}
+
+fn assoc_item_name(item: &ast::AssocItem) -> Option<String> {
+    let name = match item {
+        ast::AssocItem::Fn(it) => it.name(),
+        ast::AssocItem::TypeAlias(it) => it.name(),
+        ast::AssocItem::Const(it) => it.name(),
+        ast::AssocItem::MacroCall(_) => None,
+    };
+    Some(name?.text().to_string())
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::tests::{check_assist, check_assist_not_applicable};
+    use crate::{
+        tests::{check_assist, check_assist_not_applicable, check_assist_with_config, TEST_CONFIG},
+        AssistConfig,
+    };
 
     use super::*;
 
@@ -2280,4 +2445,91 @@ impl b::LocalTrait for B {
             "#,
         )
     }
+
+    #[test]
+    fn smart_stub_display_via_debug() {
+        check_assist_with_config(
+            AssistConfig { smart_impl_member_stubs: true, ..TEST_CONFIG },
+            add_missing_impl_members,
+            r#"
+//- minicore: fmt, derive
+#[derive(Debug)]
+struct S(u8);
+
+impl core::fmt::Display for S {$0}
+"#,
+            r#"
+#[derive(Debug)]
+struct S(u8);
+
+impl core::fmt::Display for S {
+    $0fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(self, f)
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn smart_stub_deref_to_single_field() {
+        check_assist_with_config(
+            AssistConfig { smart_impl_member_stubs: true, ..TEST_CONFIG },
+            add_missing_impl_members,
+            r#"
+//- minicore: deref_mut
+struct Inner;
+struct Wrapper { inner: Inner }
+
+impl core::ops::Deref for Wrapper {$0}
+"#,
+            r#"
+struct Inner;
+struct Wrapper { inner: Inner }
+
+impl core::ops::Deref for Wrapper {
+    $0type Target = Inner;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn smart_stub_iterator_over_single_field() {
+        check_assist_with_config(
+            AssistConfig { smart_impl_member_stubs: true, ..TEST_CONFIG },
+            add_missing_impl_members,
+            r#"
+//- minicore: iterator
+struct Inner;
+impl Iterator for Inner {
+    type Item = u32;
+    fn next(&mut self) -> Option<u32> { None }
+}
+struct Outer(Inner);
+
+impl Iterator for Outer {$0}
+"#,
+            r#"
+struct Inner;
+impl Iterator for Inner {
+    type Item = u32;
+    fn next(&mut self) -> Option<u32> { None }
+}
+struct Outer(Inner);
+
+impl Iterator for Outer {
+    $0type Item = u32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+}
+"#,
+        );
+    }
 }
@@ -1,7 +1,8 @@
 use std::iter::{self, Peekable};
 
 use either::Either;
-use hir::{Adt, Crate, HasAttrs, HasSource, ModuleDef, Semantics};
+use hir::{Adt, ModuleDef, Semantics};
+use ide_db::match_arms::{self, build_pat, lift_enum, ExtendedEnum, ExtendedVariant};
 use ide_db::RootDatabase;
 use ide_db::{famous_defs::FamousDefs, helpers::mod_path_to_ast};
 use itertools::Itertools;
@@ -98,13 +99,61 @@ pub(crate) fn add_missing_match_arms(acc: &mut Assists, ctx: &AssistContext<'_>)
                     variant.should_be_hidden(ctx.db(), module.krate()),
                 ))
             })
-            .filter(|(variant_pat, _)| is_variant_missing(&top_lvl_pats, variant_pat));
+            .filter(|(variant_pat, _)| is_variant_missing(&ctx.sema, &top_lvl_pats, variant_pat));
 
         let option_enum = FamousDefs(&ctx.sema, module.krate()).core_option_Option().map(lift_enum);
         let missing_pats: Box<dyn Iterator<Item = _>> = if Some(enum_def) == option_enum {
-            // Match `Some` variant first.
-            cov_mark::hit!(option_order);
-            Box::new(missing_pats.rev())
+            match resolve_option_payload_enum_def(&ctx.sema, &expr) {
+                Some((some_variant, payload_def)) => {
+                    // Match on the payload of `Some` as well, e.g. `Some(Enum::A)`, `Some(Enum::B)`
+                    // and `None`.
+                    cov_mark::hit!(option_payload_enum);
+                    let some_path = mod_path_to_ast(&module.find_use_path(
+                        ctx.db(),
+                        ModuleDef::from(some_variant),
+                        ctx.config.prefer_no_std,
+                        ctx.config.prefer_prelude,
+                    )?);
+                    let some_pats = payload_def
+                        .variants(ctx.db())
+                        .into_iter()
+                        .filter_map(|variant| {
+                            let pat = build_pat(
+                                ctx.db(),
+                                module,
+                                variant,
+                                ctx.config.prefer_no_std,
+                                ctx.config.prefer_prelude,
+                            )?;
+                            Some((
+                                ast::Pat::from(make::tuple_struct_pat(
+                                    some_path.clone(),
+                                    iter::once(pat),
+                                )),
+                                variant.should_be_hidden(ctx.db(), module.krate()),
+                            ))
+                        })
+                        .collect::<Vec<_>>();
+                    let some_path = some_path.to_string();
+                    let none_pats = missing_pats.filter(move |(pat, _)| {
+                        !matches!(pat, Pat::TupleStructPat(it)
+                            if it.path().map_or(false, |it| it.to_string() == some_path))
+                    });
+                    Box::new(
+                        some_pats
+                            .into_iter()
+                            .filter(|(variant_pat, _)| {
+                                is_variant_missing(&ctx.sema, &top_lvl_pats, variant_pat)
+                            })
+                            .chain(none_pats),
+                    )
+                }
+                None => {
+                    // Match `Some` variant first.
+                    cov_mark::hit!(option_order);
+                    Box::new(missing_pats.rev())
+                }
+            }
         } else {
             Box::new(missing_pats)
         };
@@ -157,7 +206,7 @@ pub(crate) fn add_missing_match_arms(acc: &mut Assists, ctx: &AssistContext<'_>)
 
                 (ast::Pat::from(make::tuple_pat(patterns)), is_hidden)
             })
-            .filter(|(variant_pat, _)| is_variant_missing(&top_lvl_pats, variant_pat));
+            .filter(|(variant_pat, _)| is_variant_missing(&ctx.sema, &top_lvl_pats, variant_pat));
         (
             (Box::new(missing_pats) as Box<dyn Iterator<Item = _>>).peekable(),
             is_non_exhaustive,
@@ -195,7 +244,7 @@ pub(crate) fn add_missing_match_arms(acc: &mut Assists, ctx: &AssistContext<'_>)
                 });
                 (ast::Pat::from(make::slice_pat(patterns)), is_hidden)
             })
-            .filter(|(variant_pat, _)| is_variant_missing(&top_lvl_pats, variant_pat));
+            .filter(|(variant_pat, _)| is_variant_missing(&ctx.sema, &top_lvl_pats, variant_pat));
         (
             (Box::new(missing_pats) as Box<dyn Iterator<Item = _>>).peekable(),
             is_non_exhaustive,
@@ -252,9 +301,20 @@ pub(crate) fn add_missing_match_arms(acc: &mut Assists, ctx: &AssistContext<'_>)
             }
 
             let mut first_new_arm = None;
-            for arm in missing_arms {
-                first_new_arm.get_or_insert_with(|| arm.clone());
-                new_match_arm_list.add_arm(arm);
+            if ctx.config.group_missing_match_arms {
+                let missing_pats = missing_arms.filter_map(|arm| arm.pat()).collect::<Vec<_>>();
+                if !missing_pats.is_empty() {
+                    cov_mark::hit!(add_missing_match_arms_grouped);
+                    let arm = make::match_arm(missing_pats, None, make::ext::expr_todo())
+                        .clone_for_update();
+                    first_new_arm = Some(arm.clone());
+                    new_match_arm_list.add_arm(arm);
+                }
+            } else {
+                for arm in missing_arms {
+                    first_new_arm.get_or_insert_with(|| arm.clone());
+                    new_match_arm_list.add_arm(arm);
+                }
             }
 
             if needs_catch_all_arm && !has_catch_all_arm {
@@ -342,80 +402,69 @@ fn cursor_at_trivial_match_arm_list(
     None
 }
 
-fn is_variant_missing(existing_pats: &[Pat], var: &Pat) -> bool {
-    !existing_pats.iter().any(|pat| does_pat_match_variant(pat, var))
+fn is_variant_missing(
+    sema: &Semantics<'_, RootDatabase>,
+    existing_pats: &[Pat],
+    var: &Pat,
+) -> bool {
+    !existing_pats.iter().any(|pat| does_pat_match_variant(sema, pat, var))
 }
 
 // Fixme: this is still somewhat limited, use hir_ty::diagnostics::match_check?
-fn does_pat_match_variant(pat: &Pat, var: &Pat) -> bool {
+fn does_pat_match_variant(sema: &Semantics<'_, RootDatabase>, pat: &Pat, var: &Pat) -> bool {
     match (pat, var) {
         (Pat::WildcardPat(_), _) => true,
         (Pat::SlicePat(spat), Pat::SlicePat(svar)) => {
-            spat.pats().zip(svar.pats()).all(|(p, v)| does_pat_match_variant(&p, &v))
+            spat.pats().zip(svar.pats()).all(|(p, v)| does_pat_match_variant(sema, &p, &v))
         }
         (Pat::TuplePat(tpat), Pat::TuplePat(tvar)) => {
-            tpat.fields().zip(tvar.fields()).all(|(p, v)| does_pat_match_variant(&p, &v))
+            tpat.fields().zip(tvar.fields()).all(|(p, v)| does_pat_match_variant(sema, &p, &v))
+        }
+        (Pat::OrPat(opat), _) => opat.pats().any(|p| does_pat_match_variant(sema, &p, var)),
+        (Pat::TupleStructPat(tpat), Pat::TupleStructPat(tvar))
+            if tpat.path().map(|it| it.to_string()) == tvar.path().map(|it| it.to_string()) =>
+        {
+            tpat.fields().zip(tvar.fields()).all(|(p, v)| {
+                matches!(v, Pat::WildcardPat(_))
+                    || is_binding_pat(sema, &p)
+                    || does_pat_match_variant(sema, &p, &v)
+            })
         }
-        (Pat::OrPat(opat), _) => opat.pats().any(|p| does_pat_match_variant(&p, var)),
         _ => utils::does_pat_match_variant(pat, var),
     }
 }
 
-#[derive(Eq, PartialEq, Clone, Copy)]
-enum ExtendedEnum {
-    Bool,
-    Enum(hir::Enum),
-}
-
-#[derive(Eq, PartialEq, Clone, Copy, Debug)]
-enum ExtendedVariant {
-    True,
-    False,
-    Variant(hir::Variant),
-}
-
-impl ExtendedVariant {
-    fn should_be_hidden(self, db: &RootDatabase, krate: Crate) -> bool {
-        match self {
-            ExtendedVariant::Variant(var) => {
-                var.attrs(db).has_doc_hidden() && var.module(db).krate() != krate
-            }
-            _ => false,
-        }
+/// Whether `pat` is a plain binding like `x` or `ref mut x`, which matches anything, rather than
+/// a path to a constant or a unit struct or variant.
+fn is_binding_pat(sema: &Semantics<'_, RootDatabase>, pat: &Pat) -> bool {
+    match pat {
+        Pat::IdentPat(it) => it.pat().is_none() && sema.resolve_bind_pat_to_const(it).is_none(),
+        _ => false,
     }
 }
 
-fn lift_enum(e: hir::Enum) -> ExtendedEnum {
-    ExtendedEnum::Enum(e)
-}
-
-impl ExtendedEnum {
-    fn is_non_exhaustive(self, db: &RootDatabase, krate: Crate) -> bool {
-        match self {
-            ExtendedEnum::Enum(e) => {
-                e.attrs(db).by_key("non_exhaustive").exists() && e.module(db).krate() != krate
-            }
-            _ => false,
-        }
-    }
-
-    fn variants(self, db: &RootDatabase) -> Vec<ExtendedVariant> {
-        match self {
-            ExtendedEnum::Enum(e) => {
-                e.variants(db).into_iter().map(ExtendedVariant::Variant).collect::<Vec<_>>()
-            }
-            ExtendedEnum::Bool => {
-                Vec::<ExtendedVariant>::from([ExtendedVariant::True, ExtendedVariant::False])
-            }
-        }
-    }
+fn resolve_enum_def(sema: &Semantics<'_, RootDatabase>, expr: &ast::Expr) -> Option<ExtendedEnum> {
+    ExtendedEnum::from_ty(sema.db, &sema.type_of_expr(expr)?.adjusted())
 }
 
-fn resolve_enum_def(sema: &Semantics<'_, RootDatabase>, expr: &ast::Expr) -> Option<ExtendedEnum> {
-    sema.type_of_expr(expr)?.adjusted().autoderef(sema.db).find_map(|ty| match ty.as_adt() {
-        Some(Adt::Enum(e)) => Some(ExtendedEnum::Enum(e)),
-        _ => ty.is_bool().then_some(ExtendedEnum::Bool),
-    })
+/// If `expr` is an `Option` of an enum (or `bool`), returns the `Some` variant and the enum.
+fn resolve_option_payload_enum_def(
+    sema: &Semantics<'_, RootDatabase>,
+    expr: &ast::Expr,
+) -> Option<(hir::Variant, ExtendedEnum)> {
+    let ty = sema.type_of_expr(expr)?.adjusted();
+    let option_ty = ty.autoderef(sema.db).find(|ty| ty.as_adt().is_some())?;
+    let Some(Adt::Enum(option_enum)) = option_ty.as_adt() else { return None };
+    let some_variant = option_enum
+        .variants(sema.db)
+        .into_iter()
+        .find(|variant| variant.name(sema.db).to_smol_str() == "Some")?;
+    let payload = option_ty.type_arguments().next()?;
+    let payload_def = match payload.as_adt() {
+        Some(Adt::Enum(e)) if e != option_enum => lift_enum(e),
+        _ => payload.is_bool().then_some(ExtendedEnum::Bool)?,
+    };
+    Some((some_variant, payload_def))
 }
 
 fn resolve_tuple_of_enum_def(
@@ -453,50 +502,31 @@ fn resolve_array_of_enum_def(
     })
 }
 
-fn build_pat(
-    db: &RootDatabase,
+/// Builds the patterns of all variants of the enum (or `bool`) `expr` evaluates to, for the arms
+/// of a new `match`. Also returns whether a wildcard arm is needed for variants that can't be named.
+pub(crate) fn enum_variant_pats(
+    ctx: &AssistContext<'_>,
     module: hir::Module,
-    var: ExtendedVariant,
-    prefer_no_std: bool,
-    prefer_prelude: bool,
-) -> Option<ast::Pat> {
-    match var {
-        ExtendedVariant::Variant(var) => {
-            let path = mod_path_to_ast(&module.find_use_path(
-                db,
-                ModuleDef::from(var),
-                prefer_no_std,
-                prefer_prelude,
-            )?);
-
-            // FIXME: use HIR for this; it doesn't currently expose struct vs. tuple vs. unit variants though
-            Some(match var.source(db)?.value.kind() {
-                ast::StructKind::Tuple(field_list) => {
-                    let pats =
-                        iter::repeat(make::wildcard_pat().into()).take(field_list.fields().count());
-                    make::tuple_struct_pat(path, pats).into()
-                }
-                ast::StructKind::Record(field_list) => {
-                    let pats = field_list.fields().map(|f| {
-                        make::ext::simple_ident_pat(
-                            f.name().expect("Record field must have a name"),
-                        )
-                        .into()
-                    });
-                    make::record_pat(path, pats).into()
-                }
-                ast::StructKind::Unit => make::path_pat(path),
-            })
-        }
-        ExtendedVariant::True => Some(ast::Pat::from(make::literal_pat("true"))),
-        ExtendedVariant::False => Some(ast::Pat::from(make::literal_pat("false"))),
-    }
+    expr: &ast::Expr,
+) -> Option<(Vec<ast::Pat>, bool)> {
+    let ty = ctx.sema.type_of_expr(expr)?.adjusted();
+    match_arms::enum_variant_pats(
+        ctx.db(),
+        module,
+        &ty,
+        ctx.config.prefer_no_std,
+        ctx.config.prefer_prelude,
+    )
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::tests::{
-        check_assist, check_assist_not_applicable, check_assist_target, check_assist_unresolved,
+    use crate::{
+        tests::{
+            check_assist, check_assist_not_applicable, check_assist_target,
+            check_assist_unresolved, check_assist_with_config, TEST_CONFIG,
+        },
+        AssistConfig,
     };
 
     use super::add_missing_match_arms;
@@ -1164,8 +1194,7 @@ fn main() {
 
     #[test]
     fn add_missing_match_arms_partial_with_deep_pattern() {
-        // Fixme: cannot handle deep patterns
-        check_assist_not_applicable(
+        check_assist(
             add_missing_match_arms,
             r#"
 //- minicore: option
@@ -1175,6 +1204,169 @@ fn main() {
         None => {}
     }
 }
+"#,
+            r#"
+fn main() {
+    match Some(true) {
+        Some(true) => {}
+        None => {}
+        $0Some(false) => todo!(),
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn fill_option_of_enum() {
+        cov_mark::check!(option_payload_enum);
+        check_assist(
+            add_missing_match_arms,
+            r#"
+//- minicore: option
+enum A { One, Two(u32) }
+
+fn foo(opt: Option<A>) {
+    match opt$0 {
+    }
+}
+"#,
+            r#"
+enum A { One, Two(u32) }
+
+fn foo(opt: Option<A>) {
+    match opt {
+        $0Some(A::One) => todo!(),
+        Some(A::Two(_)) => todo!(),
+        None => todo!(),
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn partial_fill_option_of_enum() {
+        check_assist(
+            add_missing_match_arms,
+            r#"
+//- minicore: option
+enum A { One, Two }
+
+fn foo(opt: Option<A>) {
+    match opt$0 {
+        Some(A::Two) => {}
+    }
+}
+"#,
+            r#"
+enum A { One, Two }
+
+fn foo(opt: Option<A>) {
+    match opt {
+        Some(A::Two) => {}
+        $0Some(A::One) => todo!(),
+        None => todo!(),
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn option_of_enum_with_binding_is_covered() {
+        check_assist_not_applicable(
+            add_missing_match_arms,
+            r#"
+//- minicore: option
+enum A { One, Two }
+
+fn foo(opt: Option<A>) {
+    match opt$0 {
+        Some(a) => {}
+        None => {}
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn option_of_enum_with_uppercase_binding_is_covered() {
+        check_assist_not_applicable(
+            add_missing_match_arms,
+            r#"
+//- minicore: option
+enum A { One, Two }
+
+fn foo(opt: Option<A>) {
+    match opt$0 {
+        Some(X) => {}
+        None => {}
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn option_of_enum_with_const_is_not_covered() {
+        check_assist(
+            add_missing_match_arms,
+            r#"
+//- minicore: option
+enum A { One, Two }
+const two: A = A::Two;
+
+fn foo(opt: Option<A>) {
+    match opt$0 {
+        Some(two) => {}
+        None => {}
+    }
+}
+"#,
+            r#"
+enum A { One, Two }
+const two: A = A::Two;
+
+fn foo(opt: Option<A>) {
+    match opt {
+        Some(two) => {}
+        None => {}
+        $0Some(A::One) => todo!(),
+        Some(A::Two) => todo!(),
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn group_missing_arms() {
+        cov_mark::check!(add_missing_match_arms_grouped);
+        check_assist_with_config(
+            AssistConfig { group_missing_match_arms: true, ..TEST_CONFIG },
+            add_missing_match_arms,
+            r#"
+enum A { One, Two, Three }
+
+fn foo(a: A) {
+    match a$0 {
+        // keep me
+        A::Two => {}
+    }
+}
+"#,
+            r#"
+enum A { One, Two, Three }
+
+fn foo(a: A) {
+    match a {
+        // keep me
+        A::Two => {}
+        $0A::One | A::Three => todo!(),
+    }
+}
 "#,
         );
     }
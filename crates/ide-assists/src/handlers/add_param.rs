@@ -0,0 +1,266 @@
+use ide_db::{defs::Definition, search::FileReference};
+use syntax::{
+    ast::{self, make, HasArgList, HasName},
+    match_ast, ted, AstNode, SyntaxElement, SyntaxNode, T,
+};
+
+use crate::{
+    handlers::introduce_parameter::unique_name, utils::fn_call_args_at, AssistContext, AssistId,
+    AssistKind, Assists,
+};
+
+// Assist: add_param
+//
+// Adds a new parameter to the end of the parameter list of a function, passing `todo!()` for it
+// at all call sites.
+//
+// ```
+// fn frobnicate(x: i32$0) {}
+//
+// fn main() {
+//     frobnicate(92);
+// }
+// ```
+// ->
+// ```
+// fn frobnicate(x: i32, new_param: ${1:()}) {}
+//
+// fn main() {
+//     frobnicate(92, ${0:todo!()});
+// }
+// ```
+pub(crate) fn add_param(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    let param_list: ast::ParamList = ctx.find_node_at_offset()?;
+    let func = param_list.syntax().parent().and_then(ast::Fn::cast)?;
+    let in_trait_or_trait_impl =
+        func.syntax().parent().and_then(|it| it.parent()).map_or(false, |it| {
+            match_ast! {
+                match it {
+                    ast::Trait(_) => true,
+                    ast::Impl(it) => it.trait_().is_some(),
+                    _ => false,
+                }
+            }
+        });
+    if in_trait_or_trait_impl {
+        return None;
+    }
+    let has_self_param = param_list.self_param().is_some();
+    let fn_def = Definition::Function(ctx.sema.to_def(&func)?);
+
+    // Any other use of the function, like as a function pointer, would break with the new
+    // parameter, while imports of it are fine.
+    let mut calls = Vec::new();
+    for (file_id, references) in fn_def.usages(&ctx.sema).all() {
+        let source_file = ctx.sema.parse(file_id);
+        let mut arg_lists = Vec::new();
+        for FileReference { range, name, .. } in references {
+            let name_ref = name.as_name_ref();
+            if name_ref.is_some_and(|it| {
+                it.syntax().ancestors().any(|it| ast::UseTree::can_cast(it.kind()))
+            }) {
+                continue;
+            }
+            let Some((arg_list, _)) = fn_call_args_at(&source_file, range, has_self_param) else {
+                cov_mark::hit!(add_param_non_call_usage);
+                return None;
+            };
+            arg_lists.push(arg_list);
+        }
+        calls.push((file_id, arg_lists));
+    }
+
+    let existing_names = param_list
+        .params()
+        .filter_map(|param| match param.pat()? {
+            ast::Pat::IdentPat(it) => Some(it.name()?.to_string()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let name = unique_name("new_param".to_owned(), &existing_names);
+
+    acc.add(
+        AssistId("add_param", AssistKind::Refactor),
+        "Add parameter",
+        param_list.syntax().text_range(),
+        |builder| {
+            // Snippets only work in a single file, the one of the function.
+            let (here, elsewhere): (Vec<_>, Vec<_>) =
+                calls.into_iter().partition(|(file_id, _)| *file_id == ctx.file_id());
+
+            let param_list = builder.make_mut(param_list);
+            let param =
+                make::param(make::ext::simple_ident_pat(make::name(&name)).into(), make::ty_unit())
+                    .clone_for_update();
+            let last_param = param_list
+                .params()
+                .last()
+                .map(|it| it.syntax().clone())
+                .or_else(|| param_list.self_param().map(|it| it.syntax().clone()));
+            match last_param {
+                Some(last) => append(&last, param.syntax().clone()),
+                None => {
+                    if let Some(l_paren) = param_list.l_paren_token() {
+                        ted::insert(ted::Position::after(l_paren), param.syntax());
+                    }
+                }
+            }
+
+            let arg_lists = here
+                .into_iter()
+                .flat_map(|(_, arg_lists)| arg_lists)
+                .map(|arg_list| builder.make_mut(arg_list))
+                .collect::<Vec<_>>();
+            let todos = arg_lists
+                .iter()
+                .filter_map(|arg_list| {
+                    let todo = make::ext::expr_todo().clone_for_update();
+                    match arg_list.args().last() {
+                        Some(last) => append(last.syntax(), todo.syntax().clone()),
+                        None => ted::insert(
+                            ted::Position::after(arg_list.l_paren_token()?),
+                            todo.syntax(),
+                        ),
+                    }
+                    Some(todo.syntax().clone())
+                })
+                .collect::<Vec<_>>();
+
+            if let Some(cap) = ctx.config.snippet_cap {
+                if let Some(ty) = param.ty() {
+                    builder.add_placeholder_snippet(cap, ty);
+                }
+                if !todos.is_empty() {
+                    builder.add_placeholder_snippet_group(cap, todos);
+                }
+            }
+
+            for (file_id, arg_lists) in elsewhere {
+                builder.edit_file(file_id);
+                for arg_list in arg_lists {
+                    match arg_list.args().last() {
+                        Some(last) => builder.insert(last.syntax().text_range().end(), ", todo!()"),
+                        None => {
+                            let Some(l_paren) = arg_list.l_paren_token() else { continue };
+                            builder.insert(l_paren.text_range().end(), "todo!()");
+                        }
+                    }
+                }
+            }
+        },
+    )
+}
+
+/// Inserts `node` as the next item of the comma separated list `last` ends.
+fn append(last: &SyntaxNode, node: SyntaxNode) {
+    let elements: Vec<SyntaxElement> =
+        vec![make::token(T![,]).into(), make::tokens::single_space().into(), node.into()];
+    ted::insert_all(ted::Position::after(last), elements);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn add_param_to_empty_list() {
+        check_assist(
+            add_param,
+            r#"
+fn f($0) {}
+fn main() {
+    f();
+}
+"#,
+            r#"
+fn f(new_param: ${1:()}) {}
+fn main() {
+    f(${0:todo!()});
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn add_param_to_method() {
+        check_assist(
+            add_param,
+            r#"
+struct S;
+impl S {
+    fn f(&self$0, new_param: u8) {}
+}
+fn main() {
+    S.f(1);
+    S::f(&S, 1);
+}
+"#,
+            r#"
+struct S;
+impl S {
+    fn f(&self, new_param: u8, new_param1: ${1:()}) {}
+}
+fn main() {
+    S.f(1, ${0:todo!()});
+    S::f(&S, 1, ${0:todo!()});
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn add_param_in_other_files() {
+        check_assist(
+            add_param,
+            r#"
+//- /main.rs
+mod m;
+use m::f;
+fn main() {
+    f(1);
+}
+//- /m.rs
+pub fn f(x: u8$0) {}
+"#,
+            r#"
+//- /main.rs
+mod m;
+use m::f;
+fn main() {
+    f(1, todo!());
+}
+//- /m.rs
+pub fn f(x: u8, new_param: ${0:()}) {}
+"#,
+        );
+    }
+
+    #[test]
+    fn add_param_not_applicable_with_non_call_usages() {
+        cov_mark::check!(add_param_non_call_usage);
+        check_assist_not_applicable(
+            add_param,
+            r#"
+fn f(x: u8$0) {}
+fn main() {
+    f(1);
+    let g: fn(u8) = f;
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn add_param_not_applicable_in_trait() {
+        check_assist_not_applicable(
+            add_param,
+            r#"
+trait T {
+    fn f(&self$0);
+}
+"#,
+        );
+    }
+}
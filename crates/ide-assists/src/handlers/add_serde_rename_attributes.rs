@@ -0,0 +1,784 @@
+use std::iter;
+
+use hir::CaseType;
+use ide_db::{
+    defs::Definition,
+    rename::{source_edit_from_references, IdentifierKind},
+    FxHashMap,
+};
+use itertools::Itertools;
+use syntax::{
+    ast::{self, edit::IndentLevel, HasAttrs, HasName, HasVisibility},
+    AstNode, AstToken, NodeOrToken, SyntaxKind, SyntaxNode, TextSize, T,
+};
+
+use crate::{assist_context::SourceChangeBuilder, AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: add_serde_rename_attributes
+//
+// Renames the fields of a serde-derived struct to snake_case, adding `#[serde(rename_all)]` or
+// per-field `#[serde(rename)]` attributes so that the serialized names stay the same.
+//
+// ```
+// #[derive(Serialize, Deserialize)]
+// struct $0User {
+//     userId: u32,
+//     displayName: String,
+// }
+// ```
+// ->
+// ```
+// #[derive(Serialize, Deserialize)]
+// #[serde(rename_all = "camelCase")]
+// struct User {
+//     user_id: u32,
+//     display_name: String,
+// }
+// ```
+pub(crate) fn add_serde_rename_attributes(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let serde_struct = SerdeStruct::at_cursor(ctx)?;
+    let new_names = serde_struct
+        .fields
+        .iter()
+        .map(|field| match CaseType::LowerSnakeCase.convert(unraw(&field.name_text)) {
+            Some(snake) => to_ident(&snake),
+            None => Some(field.name_text.clone()),
+        })
+        .collect::<Option<Vec<_>>>()?;
+    if !serde_struct.renames_fields(&new_names) {
+        return None;
+    }
+
+    // Keep the existing `rename_all` rule unless another one needs fewer per-field renames.
+    let rule = serde_struct
+        .rename_all
+        .as_ref()
+        .map(|(_, rule)| Some(*rule))
+        .into_iter()
+        .chain(iter::once(None))
+        .chain(RenameRule::FIELD_RULES.into_iter().map(Some))
+        .min_by_key(|rule| serde_struct.renames_needed(*rule, &new_names))?;
+
+    acc.add(
+        AssistId("add_serde_rename_attributes", AssistKind::RefactorRewrite),
+        "Rename fields to snake_case, keeping their serde names",
+        serde_struct.strukt.syntax().text_range(),
+        |edit| serde_struct.rename_fields(edit, ctx, rule, &new_names, false),
+    )
+}
+
+// Assist: remove_serde_rename_attributes
+//
+// Renames the fields of a serde-derived struct to their serialized names, removing the
+// `#[serde(rename_all)]` and per-field `#[serde(rename)]` attributes that become unnecessary.
+//
+// ```
+// #[derive(Serialize, Deserialize)]
+// #[serde(rename_all = "camelCase")]
+// struct $0User {
+//     user_id: u32,
+//     display_name: String,
+// }
+// ```
+// ->
+// ```
+// #[derive(Serialize, Deserialize)]
+// #[allow(non_snake_case)]
+// struct User {
+//     userId: u32,
+//     displayName: String,
+// }
+// ```
+pub(crate) fn remove_serde_rename_attributes(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let serde_struct = SerdeStruct::at_cursor(ctx)?;
+    // Serialized names that aren't identifiers, like `user-id`, stay renamed.
+    let new_names = serde_struct
+        .fields
+        .iter()
+        .map(|field| to_ident(&field.wire_name).unwrap_or_else(|| field.name_text.clone()))
+        .collect::<Vec<_>>();
+    if !serde_struct.renames_fields(&new_names) {
+        return None;
+    }
+    let needs_allow =
+        new_names.iter().any(|name| CaseType::LowerSnakeCase.convert(unraw(name)).is_some());
+
+    acc.add(
+        AssistId("remove_serde_rename_attributes", AssistKind::RefactorRewrite),
+        "Rename fields to their serde names",
+        serde_struct.strukt.syntax().text_range(),
+        |edit| serde_struct.rename_fields(edit, ctx, None, &new_names, needs_allow),
+    )
+}
+
+/// A serde `rename_all` rule, as applied to field names.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum RenameRule {
+    LowerCase,
+    UpperCase,
+    PascalCase,
+    CamelCase,
+    SnakeCase,
+    ScreamingSnakeCase,
+    KebabCase,
+    ScreamingKebabCase,
+}
+
+impl RenameRule {
+    /// The rules that can change a snake_case field name, in order of preference.
+    const FIELD_RULES: [RenameRule; 5] = [
+        RenameRule::CamelCase,
+        RenameRule::PascalCase,
+        RenameRule::ScreamingSnakeCase,
+        RenameRule::KebabCase,
+        RenameRule::ScreamingKebabCase,
+    ];
+
+    fn from_str(rule: &str) -> Option<RenameRule> {
+        let rule = match rule {
+            "lowercase" => RenameRule::LowerCase,
+            "UPPERCASE" => RenameRule::UpperCase,
+            "PascalCase" => RenameRule::PascalCase,
+            "camelCase" => RenameRule::CamelCase,
+            "snake_case" => RenameRule::SnakeCase,
+            "SCREAMING_SNAKE_CASE" => RenameRule::ScreamingSnakeCase,
+            "kebab-case" => RenameRule::KebabCase,
+            "SCREAMING-KEBAB-CASE" => RenameRule::ScreamingKebabCase,
+            _ => return None,
+        };
+        Some(rule)
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            RenameRule::LowerCase => "lowercase",
+            RenameRule::UpperCase => "UPPERCASE",
+            RenameRule::PascalCase => "PascalCase",
+            RenameRule::CamelCase => "camelCase",
+            RenameRule::SnakeCase => "snake_case",
+            RenameRule::ScreamingSnakeCase => "SCREAMING_SNAKE_CASE",
+            RenameRule::KebabCase => "kebab-case",
+            RenameRule::ScreamingKebabCase => "SCREAMING-KEBAB-CASE",
+        }
+    }
+
+    /// Mirrors how serde applies the rule, which assumes the field is in snake_case.
+    fn apply(self, field: &str) -> String {
+        match self {
+            RenameRule::LowerCase | RenameRule::SnakeCase => field.to_owned(),
+            RenameRule::UpperCase | RenameRule::ScreamingSnakeCase => field.to_ascii_uppercase(),
+            RenameRule::PascalCase => {
+                let mut pascal = String::new();
+                let mut capitalize = true;
+                for ch in field.chars() {
+                    if ch == '_' {
+                        capitalize = true;
+                    } else if capitalize {
+                        pascal.push(ch.to_ascii_uppercase());
+                        capitalize = false;
+                    } else {
+                        pascal.push(ch);
+                    }
+                }
+                pascal
+            }
+            RenameRule::CamelCase => {
+                let pascal = RenameRule::PascalCase.apply(field);
+                let mut chars = pascal.chars();
+                match chars.next() {
+                    Some(first) => first.to_ascii_lowercase().to_string() + chars.as_str(),
+                    None => pascal,
+                }
+            }
+            RenameRule::KebabCase => field.replace('_', "-"),
+            RenameRule::ScreamingKebabCase => field.to_ascii_uppercase().replace('_', "-"),
+        }
+    }
+}
+
+struct SerdeStruct {
+    strukt: ast::Struct,
+    rename_all: Option<(SerdeAttr, RenameRule)>,
+    fields: Vec<SerdeField>,
+}
+
+struct SerdeField {
+    field: ast::RecordField,
+    def: hir::Field,
+    name_text: String,
+    rename: Option<SerdeAttr>,
+    /// The name the field is serialized with.
+    wire_name: String,
+}
+
+impl SerdeStruct {
+    fn at_cursor(ctx: &AssistContext<'_>) -> Option<SerdeStruct> {
+        let strukt = ctx.find_node_at_offset::<ast::Struct>()?;
+        if !derives_serde(&strukt) {
+            return None;
+        }
+        let rename_all = match SerdeAttr::find(&strukt, "rename_all") {
+            Some(attr) => {
+                let rule = RenameRule::from_str(attr.value("rename_all")?)?;
+                Some((attr, rule))
+            }
+            None => None,
+        };
+        let ast::FieldList::RecordFieldList(field_list) = strukt.field_list()? else {
+            return None;
+        };
+        let fields = field_list
+            .fields()
+            .map(|field| {
+                let def = ctx.sema.to_def(&field)?;
+                let name_text = field.name()?.text().to_string();
+                let rename = SerdeAttr::find(&field, "rename");
+                let wire_name = match &rename {
+                    Some(attr) => attr.value("rename")?.to_owned(),
+                    None => match &rename_all {
+                        Some((_, rule)) => rule.apply(unraw(&name_text)),
+                        None => unraw(&name_text).to_owned(),
+                    },
+                };
+                Some(SerdeField { field, def, name_text, rename, wire_name })
+            })
+            .collect::<Option<Vec<_>>>()?;
+        Some(SerdeStruct { strukt, rename_all, fields })
+    }
+
+    fn renames_fields(&self, new_names: &[String]) -> bool {
+        new_names.iter().all_unique()
+            && self
+                .fields
+                .iter()
+                .zip(new_names)
+                .any(|(field, new_name)| field.name_text != *new_name)
+    }
+
+    fn renames_needed(&self, rule: Option<RenameRule>, new_names: &[String]) -> usize {
+        let fields = self.fields.iter().zip(new_names);
+        fields.filter(|(field, new_name)| field.rename_for(rule, new_name).is_some()).count()
+    }
+
+    fn rename_fields(
+        &self,
+        edit: &mut SourceChangeBuilder,
+        ctx: &AssistContext<'_>,
+        rule: Option<RenameRule>,
+        new_names: &[String],
+        needs_allow: bool,
+    ) {
+        let strukt = &self.strukt;
+        let mut new_attrs = Vec::new();
+        match (&self.rename_all, rule) {
+            (Some((_, old_rule)), Some(rule)) if *old_rule == rule => (),
+            (Some((attr, _)), rule) => {
+                let entry = rule.map(|rule| format!("rename_all = \"{}\"", rule.as_str()));
+                attr.replace_entry(edit, "rename_all", entry);
+            }
+            (None, Some(rule)) => {
+                new_attrs.push(format!("#[serde(rename_all = \"{}\")]", rule.as_str()))
+            }
+            (None, None) => (),
+        }
+        match (allow_non_snake_case(strukt), needs_allow) {
+            (Some(attr), false) => delete_attr(edit, &attr),
+            (None, true) => new_attrs.push("#[allow(non_snake_case)]".to_owned()),
+            _ => (),
+        }
+        let decl_start = match strukt.visibility() {
+            Some(vis) => Some(vis.syntax().text_range().start()),
+            None => strukt.struct_token().map(|it| it.text_range().start()),
+        };
+        if let Some(offset) = decl_start {
+            insert_attrs(edit, offset, strukt.syntax(), &new_attrs);
+        }
+
+        let struct_range = strukt.syntax().text_range();
+        let mut ref_edits = FxHashMap::default();
+        for (field, new_name) in self.fields.iter().zip(new_names) {
+            match (&field.rename, field.rename_for(rule, new_name)) {
+                (Some(attr), None) => attr.replace_entry(edit, "rename", None),
+                (Some(_), Some(_)) => (),
+                (None, Some(wire_name)) => {
+                    if let Some(offset) = field_decl_start(&field.field) {
+                        let attr = format!("#[serde(rename = \"{}\")]", escape(wire_name));
+                        insert_attrs(edit, offset, field.field.syntax(), &[attr]);
+                    }
+                }
+                (None, None) => (),
+            }
+
+            if field.name_text == *new_name {
+                continue;
+            }
+            if let Some(name) = field.field.name() {
+                edit.replace(name.syntax().text_range(), new_name);
+            }
+            let def = Definition::Field(field.def);
+            for (file_id, references) in def.usages(&ctx.sema).all() {
+                // The struct's own derives refer to the fields, but the names are renamed above.
+                let references = references
+                    .into_iter()
+                    .filter(|it| file_id != ctx.file_id() || !struct_range.contains_range(it.range))
+                    .collect::<Vec<_>>();
+                let text_edit = source_edit_from_references(&references, def, new_name);
+                ref_edits.entry(file_id).or_insert_with(Vec::new).push(text_edit);
+            }
+        }
+
+        // The builder is still on the current file, so its references go first.
+        let current_file = ref_edits.remove(&ctx.file_id()).unwrap_or_default();
+        for indel in current_file.iter().flat_map(|it| it.iter()) {
+            edit.replace(indel.delete, indel.insert.clone());
+        }
+        for (file_id, text_edits) in ref_edits.into_iter().sorted_by_key(|(file_id, _)| *file_id) {
+            edit.edit_file(file_id);
+            for indel in text_edits.iter().flat_map(|it| it.iter()) {
+                edit.replace(indel.delete, indel.insert.clone());
+            }
+        }
+    }
+}
+
+impl SerdeField {
+    /// Returns the `rename` the field needs to keep its serialized name under the given name and
+    /// `rename_all` rule.
+    fn rename_for(&self, rule: Option<RenameRule>, new_name: &str) -> Option<&str> {
+        let new_name = unraw(new_name);
+        let serialized = match rule {
+            Some(rule) => rule.apply(new_name),
+            None => new_name.to_owned(),
+        };
+        (serialized != self.wire_name).then_some(&*self.wire_name)
+    }
+}
+
+/// A `#[serde(..)]` attribute, split into its comma separated entries.
+struct SerdeAttr {
+    attr: ast::Attr,
+    entries: Vec<SerdeEntry>,
+}
+
+struct SerdeEntry {
+    text: String,
+    key: String,
+    /// The value of a `key = "value"` entry.
+    value: Option<String>,
+}
+
+impl SerdeAttr {
+    /// Finds the `#[serde(..)]` attribute with the given key.
+    fn find(owner: &impl HasAttrs, key: &str) -> Option<SerdeAttr> {
+        owner
+            .attrs()
+            .filter_map(SerdeAttr::parse)
+            .find(|attr| attr.entries.iter().any(|entry| entry.key == key))
+    }
+
+    fn parse(attr: ast::Attr) -> Option<SerdeAttr> {
+        let (name, tt) = attr.as_simple_call()?;
+        if name != "serde" {
+            return None;
+        }
+        let elements = tt.syntax().children_with_tokens().collect::<Vec<_>>();
+        // Skip the delimiters of the token tree.
+        let elements = elements.get(1..elements.len().saturating_sub(1))?;
+        let entries = elements
+            .split(|it| it.kind() == T![,])
+            .filter_map(|elements| {
+                let text = elements.iter().map(|it| it.to_string()).collect::<String>();
+                let text = text.trim().to_owned();
+                let mut parts = elements.iter().filter(|it| !it.kind().is_trivia());
+                let key = parts.next()?.as_token()?.text().to_owned();
+                let value = match (parts.next(), parts.next(), parts.next()) {
+                    (Some(eq), Some(NodeOrToken::Token(value)), None) if eq.kind() == T![=] => {
+                        let value = ast::String::cast(value.clone());
+                        value.and_then(|it| it.value().map(|it| it.into_owned()))
+                    }
+                    _ => None,
+                };
+                Some(SerdeEntry { text, key, value })
+            })
+            .collect();
+        Some(SerdeAttr { attr, entries })
+    }
+
+    /// The value of the `key = "value"` entry, `None` if the entry has a different form.
+    fn value(&self, key: &str) -> Option<&str> {
+        self.entries.iter().find(|entry| entry.key == key)?.value.as_deref()
+    }
+
+    /// Replaces the entry with the given key, removing the whole attribute if it was the only one.
+    fn replace_entry(&self, edit: &mut SourceChangeBuilder, key: &str, new_entry: Option<String>) {
+        let mut new_entry = new_entry;
+        let entries = self
+            .entries
+            .iter()
+            .filter_map(|entry| match entry.key == key {
+                true => new_entry.take(),
+                false => Some(entry.text.clone()),
+            })
+            .collect::<Vec<_>>();
+        if entries.is_empty() {
+            delete_attr(edit, &self.attr);
+        } else {
+            let attr = format!("#[serde({})]", entries.join(", "));
+            edit.replace(self.attr.syntax().text_range(), attr);
+        }
+    }
+}
+
+fn derives_serde(strukt: &ast::Struct) -> bool {
+    strukt.attrs().filter_map(|attr| attr.as_simple_call()).any(|(name, tt)| {
+        name == "derive"
+            && tt.syntax().children_with_tokens().any(|it| {
+                matches!(it.as_token().map(|it| it.text()), Some("Serialize" | "Deserialize"))
+            })
+    })
+}
+
+fn allow_non_snake_case(strukt: &ast::Struct) -> Option<ast::Attr> {
+    strukt.attrs().find(|attr| {
+        matches!(
+            attr.as_simple_call(),
+            Some((name, tt)) if name == "allow" && tt.syntax().text() == "(non_snake_case)"
+        )
+    })
+}
+
+fn delete_attr(edit: &mut SourceChangeBuilder, attr: &ast::Attr) {
+    let mut range = attr.syntax().text_range();
+    if let Some(ws) = attr.syntax().next_sibling_or_token() {
+        if ws.kind() == SyntaxKind::WHITESPACE {
+            range = range.cover(ws.text_range());
+        }
+    }
+    edit.delete(range);
+}
+
+/// Inserts attributes at `offset`, on their own lines indented like `node`.
+fn insert_attrs(
+    edit: &mut SourceChangeBuilder,
+    offset: TextSize,
+    node: &SyntaxNode,
+    attrs: &[String],
+) {
+    if attrs.is_empty() {
+        return;
+    }
+    let indent = IndentLevel::from_node(node);
+    let attrs = attrs.iter().map(|attr| format!("{attr}\n{indent}")).collect::<String>();
+    edit.insert(offset, attrs);
+}
+
+fn field_decl_start(field: &ast::RecordField) -> Option<TextSize> {
+    match field.visibility() {
+        Some(vis) => Some(vis.syntax().text_range().start()),
+        None => Some(field.name()?.syntax().text_range().start()),
+    }
+}
+
+fn unraw(name: &str) -> &str {
+    name.strip_prefix("r#").unwrap_or(name)
+}
+
+/// Turns a name into an identifier, making it raw if it's a keyword.
+fn to_ident(name: &str) -> Option<String> {
+    [name.to_owned(), format!("r#{name}")]
+        .into_iter()
+        .find(|it| matches!(IdentifierKind::classify(it), Ok(IdentifierKind::Ident)))
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn adds_rename_all() {
+        check_assist(
+            add_serde_rename_attributes,
+            r#"
+#[derive(Serialize, Deserialize)]
+pub struct $0User {
+    id: u32,
+    /// The name to show.
+    pub displayName: String,
+}
+
+fn name(user: &User) -> &str {
+    &user.displayName
+}
+"#,
+            r#"
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct User {
+    id: u32,
+    /// The name to show.
+    pub display_name: String,
+}
+
+fn name(user: &User) -> &str {
+    &user.display_name
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn adds_field_renames_when_no_rule_fits() {
+        check_assist(
+            add_serde_rename_attributes,
+            r#"
+#[derive(Deserialize)]
+#[allow(non_snake_case)]
+struct $0Response {
+    userID: u32,
+    page_count: u32,
+}
+
+fn f(userID: u32) -> Response {
+    Response { userID, page_count: 0 }
+}
+"#,
+            r#"
+#[derive(Deserialize)]
+struct Response {
+    #[serde(rename = "userID")]
+    user_id: u32,
+    page_count: u32,
+}
+
+fn f(userID: u32) -> Response {
+    Response { user_id: userID, page_count: 0 }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn prefers_rule_with_fewest_renames() {
+        check_assist(
+            add_serde_rename_attributes,
+            r#"
+#[derive(Serialize)]
+struct $0Event {
+    EventName: String,
+    CreatedAt: u64,
+    legacy_id: u64,
+}
+"#,
+            r#"
+#[derive(Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct Event {
+    event_name: String,
+    created_at: u64,
+    #[serde(rename = "legacy_id")]
+    legacy_id: u64,
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn keeps_existing_rule_and_attributes() {
+        check_assist(
+            add_serde_rename_attributes,
+            r#"
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct $0Config {
+    #[serde(rename = "type")]
+    Kind: String,
+    maxSize: u32,
+}
+"#,
+            r#"
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct Config {
+    #[serde(rename = "type")]
+    kind: String,
+    max_size: u32,
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn renames_usages_in_other_files() {
+        check_assist(
+            add_serde_rename_attributes,
+            r#"
+//- /main.rs
+mod other;
+#[derive(Serialize)]
+pub struct $0S {
+    pub fieldName: u32,
+}
+//- /other.rs
+fn f(s: crate::S) -> u32 {
+    let crate::S { fieldName } = s;
+    fieldName
+}
+"#,
+            r#"
+//- /main.rs
+mod other;
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct S {
+    pub field_name: u32,
+}
+//- /other.rs
+fn f(s: crate::S) -> u32 {
+    let crate::S { field_name: fieldName } = s;
+    fieldName
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn add_not_applicable() {
+        check_assist_not_applicable(
+            add_serde_rename_attributes,
+            r#"
+#[derive(Serialize)]
+struct $0S {
+    field_name: u32,
+}
+"#,
+        );
+        check_assist_not_applicable(
+            add_serde_rename_attributes,
+            r#"
+#[derive(Debug)]
+struct $0S {
+    fieldName: u32,
+}
+"#,
+        );
+        check_assist_not_applicable(
+            add_serde_rename_attributes,
+            r#"
+#[derive(Serialize)]
+struct $0S {
+    fieldName: u32,
+    field_name: u32,
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn removes_rename_all() {
+        check_assist(
+            remove_serde_rename_attributes,
+            r#"
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct $0User {
+    id: u32,
+    display_name: String,
+}
+
+fn name(user: User) -> String {
+    user.display_name
+}
+"#,
+            r#"
+#[derive(Serialize, Deserialize)]
+#[allow(non_snake_case)]
+struct User {
+    id: u32,
+    displayName: String,
+}
+
+fn name(user: User) -> String {
+    user.displayName
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn removes_field_renames() {
+        check_assist(
+            remove_serde_rename_attributes,
+            r#"
+#[derive(Deserialize)]
+struct $0Item {
+    #[serde(rename = "type", default)]
+    kind: String,
+    #[serde(rename = "item-id")]
+    id: u32,
+    #[serde(rename = "name")]
+    title: String,
+}
+"#,
+            r#"
+#[derive(Deserialize)]
+struct Item {
+    #[serde(default)]
+    r#type: String,
+    #[serde(rename = "item-id")]
+    id: u32,
+    name: String,
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn keeps_renames_of_non_identifiers() {
+        check_assist(
+            remove_serde_rename_attributes,
+            r#"
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct $0S {
+    user_id: u32,
+    #[serde(rename = "user-name")]
+    user_name: String,
+}
+"#,
+            r#"
+#[derive(Serialize)]
+#[allow(non_snake_case)]
+struct S {
+    userId: u32,
+    #[serde(rename = "user-name")]
+    user_name: String,
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn remove_not_applicable_without_renames() {
+        check_assist_not_applicable(
+            remove_serde_rename_attributes,
+            r#"
+#[derive(Serialize)]
+struct $0S {
+    field_name: u32,
+}
+"#,
+        );
+    }
+}
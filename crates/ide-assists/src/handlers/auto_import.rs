@@ -1,12 +1,13 @@
 use std::cmp::Reverse;
 
-use hir::{db::HirDatabase, Module};
+use hir::{db::HirDatabase, ModPath, Module, ModuleDef, Name, PathKind, ScopeDef};
 use ide_db::{
     helpers::mod_path_to_ast,
     imports::{
         import_assets::{ImportAssets, ImportCandidate, LocatedImport},
         insert_use::{insert_use, insert_use_as_alias, ImportScope},
     },
+    FxHashMap,
 };
 use syntax::{ast, AstNode, NodeOrToken, SyntaxElement};
 
@@ -68,6 +69,13 @@ use crate::{AssistContext, AssistId, AssistKind, Assists, GroupLabel};
 //
 // In `VS Code` the configuration for this is `rust-analyzer.imports.prefix`.
 //
+// .Candidate Ranking
+//
+// When an item can be imported from several places, the candidates are ordered so that items
+// close to the current module come first. Items from modules that are already imported and
+// re-exports of foreign items by the current crate are preferred. Items of other crates that are
+// `#[doc(hidden)]` or unstable are never proposed.
+//
 // image::https://user-images.githubusercontent.com/48062697/113020673-b85be580-917a-11eb-9022-59585f35d4f8.gif[]
 
 // Assist: auto_import
@@ -125,12 +133,21 @@ pub(crate) fn auto_import(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<
         NodeOrToken::Token(token) => token.parent(),
     };
 
-    let current_module =
-        current_node.as_ref().and_then(|node| ctx.sema.scope(node)).map(|scope| scope.module());
+    let current_scope = current_node.as_ref().and_then(|node| ctx.sema.scope(node));
+    let current_module = current_scope.as_ref().map(|scope| scope.module());
+    let mut modules_in_scope = FxHashMap::default();
+    if let Some(scope) = &current_scope {
+        scope.process_all_names(&mut |name, def| {
+            if let ScopeDef::ModuleDef(ModuleDef::Module(module)) = def {
+                modules_in_scope.insert(name, module);
+            }
+        });
+    }
 
     // prioritize more relevant imports
-    proposed_imports
-        .sort_by_key(|import| Reverse(relevance_score(ctx, import, current_module.as_ref())));
+    proposed_imports.sort_by_key(|import| {
+        Reverse(relevance_score(ctx, import, current_module.as_ref(), &modules_in_scope))
+    });
 
     for import in proposed_imports {
         let import_path = import.import_path;
@@ -229,6 +246,7 @@ fn relevance_score(
     ctx: &AssistContext<'_>,
     import: &LocatedImport,
     current_module: Option<&Module>,
+    modules_in_scope: &FxHashMap<Name, Module>,
 ) -> i32 {
     let mut score = 0;
 
@@ -244,6 +262,22 @@ fn relevance_score(
         // (prefer items that are more local)
         Some((item_module, current_module)) => {
             score -= module_distance_heuristic(db, current_module, &item_module) as i32;
+
+            // prefer items from modules that are already imported
+            let imported = |module: &Module| {
+                !module.is_crate_root() && modules_in_scope.values().any(|it| it == module)
+            };
+            if item_module.path_to_root(db).iter().any(imported) {
+                score += 2;
+            }
+
+            // prefer the current crate's re-exports of foreign items over paths into the
+            // foreign crate
+            if item_module.krate() != current_module.krate()
+                && is_path_into_crate(db, &import.import_path, current_module, modules_in_scope)
+            {
+                score += 3;
+            }
         }
 
         // could not find relevant modules, so just use the length of the path as an estimate
@@ -253,6 +287,28 @@ fn relevance_score(
     score
 }
 
+/// Whether `path`, as written in `module`, starts in the crate of `module` instead of in one of
+/// its dependencies.
+fn is_path_into_crate(
+    db: &dyn HirDatabase,
+    path: &ModPath,
+    module: &Module,
+    modules_in_scope: &FxHashMap<Name, Module>,
+) -> bool {
+    let krate = module.krate();
+    match path.kind {
+        PathKind::Plain => match path.segments().first() {
+            Some(first) => match modules_in_scope.get(first) {
+                Some(it) => it.krate() == krate,
+                None => krate.dependencies(db).iter().all(|dep| dep.name != *first),
+            },
+            None => false,
+        },
+        PathKind::Crate | PathKind::Super(_) => true,
+        PathKind::Abs | PathKind::DollarCrate(_) => false,
+    }
+}
+
 /// A heuristic that gives a higher score to modules that are more separated.
 fn module_distance_heuristic(db: &dyn HirDatabase, current: &Module, item: &Module) -> usize {
     // get the path starting from the item to the respective crate roots
@@ -365,6 +421,72 @@ pub struct HashMap;
         )
     }
 
+    #[test]
+    fn prefer_imported_parent_modules() {
+        let before = r"
+//- /main.rs crate:main deps:foo
+use foo::io;
+
+fn f(_: Error$0) {}
+
+//- /lib.rs crate:foo
+pub mod fmt { pub struct Error; }
+pub mod io { pub struct Error; }
+        ";
+
+        check_auto_import_order(before, &["Import `io::Error`", "Import `foo::fmt::Error`"])
+    }
+
+    #[test]
+    fn prefer_own_reexports() {
+        let before = r"
+//- /main.rs crate:main deps:foo,bar
+mod reexports {
+    pub use foo::a::b::Thing;
+}
+
+fn f(_: Thing$0) {}
+
+//- /lib.rs crate:foo
+pub mod a { pub mod b { pub struct Thing; } }
+
+//- /lib.rs crate:bar
+pub struct Thing;
+        ";
+
+        check_auto_import_order(before, &["Import `reexports::Thing`", "Import `bar::Thing`"])
+    }
+
+    #[test]
+    fn no_foreign_hidden_or_unstable_items() {
+        check_assist_not_applicable(
+            auto_import,
+            r"
+//- /main.rs crate:main deps:foo
+fn f(_: Hidden$0, _: Unstable) {}
+
+//- /lib.rs crate:foo
+#[doc(hidden)]
+pub struct Hidden;
+#[unstable(feature = "unstable", issue = "none")]
+pub struct Unstable;
+",
+        );
+        check_assist_not_applicable(
+            auto_import,
+            r"
+//- /main.rs crate:main deps:foo
+fn f(_: Hidden, _: Unstable$0) {}
+
+//- /lib.rs crate:foo
+#[doc(hidden)]
+pub struct Hidden;
+#[unstable(feature = "unstable", issue = "none")]
+pub struct Unstable;
+",
+        );
+    }
+
     #[test]
     fn not_applicable_if_scope_inside_macro() {
         check_assist_not_applicable(
@@ -0,0 +1,338 @@
+use hir::{ModuleDef, PathResolution};
+use ide_db::{famous_defs::FamousDefs, helpers::mod_path_to_ast};
+use itertools::Itertools;
+use syntax::{
+    ast::{
+        self,
+        edit::{AstNodeEdit, IndentLevel},
+    },
+    AstNode, Direction, SyntaxKind, TextRange,
+};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: convert_async_fn_to_impl_future
+//
+// Converts an `async fn` into a plain function returning `impl Future`, moving its body into an
+// `async move` block.
+//
+// ```
+// # //- minicore: future
+// async fn $0fetch(id: u32) -> u32 {
+//     id
+// }
+// ```
+// ->
+// ```
+// fn fetch(id: u32) -> impl core::future::Future<Output = u32> {
+//     async move {
+//         id
+//     }
+// }
+// ```
+pub(crate) fn convert_async_fn_to_impl_future(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let fn_ = ctx.find_node_at_offset::<ast::Fn>()?;
+    let async_token = fn_.async_token()?;
+    let body = fn_.body()?;
+    if body.syntax().text_range().contains_inclusive(ctx.offset()) {
+        return None;
+    }
+    let param_list = fn_.param_list()?;
+
+    // The returned future captures all the borrows of the parameters, which `impl Trait` has to
+    // spell out. That's only expressible with a single lifetime.
+    let lifetimes = param_lifetimes(&param_list);
+    if lifetimes.len() > 1 {
+        cov_mark::hit!(convert_async_fn_multiple_lifetimes);
+        return None;
+    }
+
+    let module = ctx.sema.scope(fn_.syntax())?.module();
+    let future_trait = FamousDefs(&ctx.sema, module.krate()).core_future_Future()?;
+    let future_path = mod_path_to_ast(&module.find_use_path(
+        ctx.db(),
+        ModuleDef::Trait(future_trait),
+        ctx.config.prefer_no_std,
+        ctx.config.prefer_prelude,
+    )?);
+
+    let target = fn_.syntax().text_range();
+    acc.add(
+        AssistId("convert_async_fn_to_impl_future", AssistKind::RefactorRewrite),
+        "Convert `async fn` to `fn` returning `impl Future`",
+        target,
+        |builder| {
+            builder.delete(with_trailing_whitespace(&async_token));
+
+            let output =
+                fn_.ret_type().and_then(|it| it.ty()).map_or("()".to_owned(), |it| it.to_string());
+            let bound = lifetimes.first().map_or(String::new(), |it| format!(" + {it}"));
+            let ret_type = format!("-> impl {future_path}<Output = {output}>{bound}");
+            match fn_.ret_type() {
+                Some(it) => builder.replace(it.syntax().text_range(), ret_type),
+                None => {
+                    builder.insert(param_list.syntax().text_range().end(), format!(" {ret_type}"))
+                }
+            }
+
+            let indent = IndentLevel::from_node(fn_.syntax());
+            let body_text = body.indent(1.into());
+            builder.replace(
+                body.syntax().text_range(),
+                format!("{{\n{}async move {body_text}\n{indent}}}", indent + 1),
+            );
+        },
+    )
+}
+
+// Assist: convert_impl_future_to_async_fn
+//
+// Converts a function returning `impl Future` whose body is a single `async` block into an
+// `async fn`.
+//
+// ```
+// # //- minicore: future
+// fn $0fetch(id: u32) -> impl core::future::Future<Output = u32> {
+//     async move {
+//         id
+//     }
+// }
+// ```
+// ->
+// ```
+// async fn fetch(id: u32) -> u32 {
+//     id
+// }
+// ```
+pub(crate) fn convert_impl_future_to_async_fn(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let fn_ = ctx.find_node_at_offset::<ast::Fn>()?;
+    if fn_.async_token().is_some() || fn_.const_token().is_some() {
+        return None;
+    }
+    let body = fn_.body()?;
+    if body.syntax().text_range().contains_inclusive(ctx.offset()) {
+        return None;
+    }
+    let ret_type = fn_.ret_type()?;
+    let output = future_output(ctx, &ret_type.ty()?)?;
+
+    let stmt_list = body.stmt_list()?;
+    if stmt_list.statements().next().is_some() {
+        return None;
+    }
+    let Some(ast::Expr::BlockExpr(async_block)) = stmt_list.tail_expr() else { return None };
+    async_block.async_token()?;
+    let async_stmt_list = async_block.stmt_list()?;
+
+    let target = fn_.syntax().text_range();
+    acc.add(
+        AssistId("convert_impl_future_to_async_fn", AssistKind::RefactorRewrite),
+        "Convert to `async fn`",
+        target,
+        |builder| {
+            let qualifier = fn_.unsafe_token().or_else(|| fn_.abi()?.syntax().first_token());
+            let insert_at = match qualifier.or_else(|| fn_.fn_token()) {
+                Some(it) => it.text_range().start(),
+                None => return,
+            };
+            builder.insert(insert_at, "async ");
+
+            match output {
+                ast::Type::TupleType(it) if it.fields().next().is_none() => {
+                    let ws = ret_type
+                        .syntax()
+                        .prev_sibling_or_token()
+                        .filter(|it| it.kind() == SyntaxKind::WHITESPACE);
+                    let range = match ws {
+                        Some(ws) => ws.text_range().cover(ret_type.syntax().text_range()),
+                        None => ret_type.syntax().text_range(),
+                    };
+                    builder.delete(range);
+                }
+                output => builder.replace(ret_type.syntax().text_range(), format!("-> {output}")),
+            }
+
+            builder
+                .replace(body.syntax().text_range(), async_stmt_list.dedent(1.into()).to_string());
+        },
+    )
+}
+
+/// Lifetimes that the parameters borrow for, elided ones spelled as `'_`.
+fn param_lifetimes(param_list: &ast::ParamList) -> Vec<String> {
+    let self_lifetime = param_list.self_param().and_then(|it| {
+        it.amp_token()?;
+        Some(it.lifetime().map_or("'_".to_owned(), |it| it.to_string()))
+    });
+    let lifetimes_in_params = param_list.params().flat_map(|param| {
+        param.syntax().descendants().filter_map(|node| match ast::RefType::cast(node.clone()) {
+            Some(ref_type) => {
+                Some(ref_type.lifetime().map_or("'_".to_owned(), |it| it.to_string()))
+            }
+            None => {
+                let lifetime = ast::LifetimeArg::cast(node)?.lifetime()?;
+                Some(lifetime.to_string())
+            }
+        })
+    });
+    // Every elided lifetime is a distinct one, named lifetimes may be repeated.
+    let (elided, named): (Vec<_>, Vec<_>) =
+        self_lifetime.into_iter().chain(lifetimes_in_params).partition(|it| it == "'_");
+    elided.into_iter().chain(named.into_iter().unique()).collect()
+}
+
+/// Returns `T` for an `impl Future<Output = T>` type, lifetime bounds aside.
+fn future_output(ctx: &AssistContext<'_>, ty: &ast::Type) -> Option<ast::Type> {
+    let ast::Type::ImplTraitType(impl_trait) = ty else { return None };
+    let (future_bound,) = impl_trait
+        .type_bound_list()?
+        .bounds()
+        .filter(|it| it.lifetime().is_none())
+        .collect_tuple()?;
+    let ast::Type::PathType(path_type) = future_bound.ty()? else { return None };
+    let path = path_type.path()?;
+
+    let module = ctx.sema.scope(ty.syntax())?.module();
+    let future_trait = FamousDefs(&ctx.sema, module.krate()).core_future_Future()?;
+    match ctx.sema.resolve_path(&path)? {
+        PathResolution::Def(ModuleDef::Trait(it)) if it == future_trait => (),
+        _ => return None,
+    }
+
+    path.segment()?.generic_arg_list()?.generic_args().find_map(|arg| match arg {
+        ast::GenericArg::AssocTypeArg(it) if it.name_ref()?.text() == "Output" => it.ty(),
+        _ => None,
+    })
+}
+
+fn with_trailing_whitespace(token: &syntax::SyntaxToken) -> TextRange {
+    let ws = token
+        .siblings_with_tokens(Direction::Next)
+        .nth(1)
+        .filter(|it| it.kind() == SyntaxKind::WHITESPACE);
+    match ws {
+        Some(ws) => token.text_range().cover(ws.text_range()),
+        None => token.text_range(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn async_fn_without_return_type() {
+        check_assist(
+            convert_async_fn_to_impl_future,
+            r#"
+//- minicore: future
+mod m {
+    pub async fn $0run() {
+        let x = 1;
+        drop(x);
+    }
+}
+"#,
+            r#"
+mod m {
+    pub fn run() -> impl core::future::Future<Output = ()> {
+        async move {
+            let x = 1;
+            drop(x);
+        }
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn async_fn_borrowing_param() {
+        check_assist(
+            convert_async_fn_to_impl_future,
+            r#"
+//- minicore: future
+struct S;
+impl S {
+    async fn $0len(&self, x: u32) -> u32 { x }
+}
+"#,
+            r#"
+struct S;
+impl S {
+    fn len(&self, x: u32) -> impl core::future::Future<Output = u32> + '_ {
+        async move { x }
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn async_fn_not_applicable_with_multiple_lifetimes() {
+        cov_mark::check!(convert_async_fn_multiple_lifetimes);
+        check_assist_not_applicable(
+            convert_async_fn_to_impl_future,
+            r#"
+//- minicore: future
+async fn $0f(a: &u8, b: &u8) {}
+"#,
+        );
+    }
+
+    #[test]
+    fn impl_future_to_async_fn() {
+        check_assist(
+            convert_impl_future_to_async_fn,
+            r#"
+//- minicore: future
+use core::future::Future;
+
+pub unsafe fn $0run(x: &u8) -> impl Future<Output = ()> + '_ {
+    async move {
+        let _ = x;
+    }
+}
+"#,
+            r#"
+use core::future::Future;
+
+pub async unsafe fn run(x: &u8) {
+    let _ = x;
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn impl_future_not_applicable_with_statements_or_other_bounds() {
+        check_assist_not_applicable(
+            convert_impl_future_to_async_fn,
+            r#"
+//- minicore: future
+fn $0run() -> impl core::future::Future<Output = u8> {
+    let x = 1;
+    async move { x }
+}
+"#,
+        );
+        check_assist_not_applicable(
+            convert_impl_future_to_async_fn,
+            r#"
+//- minicore: future
+trait Send {}
+fn $0run() -> impl core::future::Future<Output = u8> + Send {
+    async move { 1 }
+}
+"#,
+        );
+    }
+}
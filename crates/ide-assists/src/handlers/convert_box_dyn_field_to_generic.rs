@@ -0,0 +1,824 @@
+use hir::{PathResolution, Semantics};
+use ide_db::{
+    base_db::{FileId, FileRange},
+    defs::Definition,
+    search::SearchScope,
+    FxHashMap, FxHashSet, RootDatabase,
+};
+use syntax::{
+    ast::{
+        self, edit_in_place::GenericParamsOwnerEdit, make, AstNode, HasGenericParams, HasName,
+        HasTypeBounds,
+    },
+    ted, SmolStr,
+};
+
+use crate::{
+    utils::{bounded_type_param, remove_where_clause, suggest_name},
+    AssistContext, AssistId, AssistKind, Assists,
+};
+
+// Assist: convert_box_dyn_field_to_generic
+//
+// Converts a `Box<dyn Trait>` field into a field of a new type parameter bounded by the trait.
+// The impls of the struct get the parameter too, and its constructors stop boxing the value.
+//
+// ```
+// trait Draw {}
+// struct Window {
+//     content: $0Box<dyn Draw>,
+// }
+// impl Window {
+//     fn new(content: Box<dyn Draw>) -> Self {
+//         Window { content }
+//     }
+// }
+// ```
+// ->
+// ```
+// trait Draw {}
+// struct Window<D: Draw> {
+//     content: D,
+// }
+// impl<D: Draw> Window<D> {
+//     fn new(content: D) -> Self {
+//         Window { content }
+//     }
+// }
+// ```
+pub(crate) fn convert_box_dyn_field_to_generic(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let (strukt, field_ty, field_key) = field_at_cursor(ctx)?;
+    let bounds = boxed_dyn_bounds(&field_ty)?;
+    let strukt_def = ctx.sema.to_def(&strukt)?;
+    let field_ty_text = field_ty.syntax().text().to_string();
+    // Named after the trait, `Box<dyn fmt::Debug>` becomes `D: fmt::Debug`.
+    let base_name = bounds
+        .bounds()
+        .find_map(|it| match it.ty()? {
+            ast::Type::PathType(it) => it.path()?.segment()?.name_ref(),
+            _ => None,
+        })
+        .and_then(|it| it.text().chars().next())
+        .map_or('T', |it| it.to_ascii_uppercase())
+        .to_string();
+
+    let mut files: FxHashMap<FileId, ToGenericEdits> = FxHashMap::default();
+    files.entry(ctx.file_id()).or_default();
+    for (file_id, paths) in struct_paths(ctx, strukt_def) {
+        let edits = files.entry(file_id).or_default();
+        edits.impls = paths.iter().filter_map(impl_of_self_ty).collect();
+        for path in &paths {
+            match field_init_expr(path, &field_key) {
+                Some(ast::Expr::CallExpr(call)) => {
+                    if let Some(inner) = box_new_arg(&call) {
+                        edits.unboxed.push((call, inner));
+                    }
+                }
+                Some(ast::Expr::PathExpr(path_expr)) => {
+                    // Constructors in the impls can take the new type parameter instead.
+                    let Some(param) = local_param(&ctx.sema, &path_expr) else { continue };
+                    let in_impl = param
+                        .syntax()
+                        .ancestors()
+                        .find_map(ast::Impl::cast)
+                        .map_or(false, |it| edits.impls.contains(&it));
+                    let is_boxed =
+                        param.ty().map_or(false, |it| it.syntax().text() == field_ty_text.as_str());
+                    if in_impl && is_boxed && !edits.params.contains(&param) {
+                        edits.params.push(param);
+                    }
+                }
+                _ => (),
+            }
+        }
+    }
+
+    let target = field_ty.syntax().text_range();
+    acc.add(
+        AssistId("convert_box_dyn_field_to_generic", AssistKind::RefactorRewrite),
+        "Convert boxed trait object field to generic parameter",
+        target,
+        |edit| {
+            let bounds = bounds.clone_for_update();
+
+            for (file_id, edits) in sorted_by_file(ctx, files) {
+                edit.edit_file(file_id);
+                let strukt_and_field = (file_id == ctx.file_id())
+                    .then(|| (edit.make_mut(strukt.clone()), edit.make_mut(field_ty.clone())));
+                let impls = edits.impls.into_iter().map(|it| edit.make_mut(it)).collect::<Vec<_>>();
+                let unboxed = edits
+                    .unboxed
+                    .into_iter()
+                    .map(|(call, inner)| (edit.make_mut(call), edit.make_mut(inner)))
+                    .collect::<Vec<_>>();
+                let params =
+                    edits.params.into_iter().map(|it| edit.make_mut(it)).collect::<Vec<_>>();
+
+                if let Some((strukt, field_ty)) = strukt_and_field {
+                    let name = add_type_param(&strukt, &base_name, &bounds);
+                    ted::replace(field_ty.syntax(), make::ty(&name).clone_for_update().syntax());
+                }
+
+                let mut impl_names = Vec::new();
+                for impl_ in impls {
+                    let name = add_type_param(&impl_, &base_name, &bounds);
+                    if let Some(segment) = impl_.self_ty().and_then(|it| match it {
+                        ast::Type::PathType(it) => it.path()?.segment(),
+                        _ => None,
+                    }) {
+                        let arg = make::type_arg(make::ty(&name)).clone_for_update();
+                        segment.get_or_create_generic_arg_list().add_generic_arg(arg.into());
+                    }
+                    impl_names.push((impl_, name));
+                }
+
+                for (call, inner) in unboxed {
+                    inner.syntax().detach();
+                    ted::replace(call.syntax(), inner.syntax());
+                }
+
+                for param in params {
+                    let impl_ = param.syntax().ancestors().find_map(ast::Impl::cast);
+                    let name = impl_names.iter().find(|(it, _)| Some(it) == impl_.as_ref());
+                    if let (Some((_, name)), Some(ty)) = (name, param.ty()) {
+                        ted::replace(ty.syntax(), make::ty(name).clone_for_update().syntax());
+                    }
+                }
+            }
+        },
+    )
+}
+
+// Assist: convert_generic_field_to_box_dyn
+//
+// Converts a field of a bounded type parameter into a `Box<dyn Trait>` field, removing the
+// parameter from the struct and its impls, and boxing the values the struct is constructed with.
+//
+// ```
+// trait Draw {}
+// struct Window<D: Draw> {
+//     content: $0D,
+// }
+// impl<D: Draw> Window<D> {
+//     fn new(content: D) -> Self {
+//         Window { content }
+//     }
+// }
+// ```
+// ->
+// ```
+// trait Draw {}
+// struct Window {
+//     content: Box<dyn Draw>,
+// }
+// impl Window {
+//     fn new(content: Box<dyn Draw>) -> Self {
+//         Window { content }
+//     }
+// }
+// ```
+pub(crate) fn convert_generic_field_to_box_dyn(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let (strukt, field_ty, field_key) = field_at_cursor(ctx)?;
+    let ast::Type::PathType(path_ty) = &field_ty else { return None };
+    let type_param_name = path_ty.path()?.as_single_name_ref()?;
+    let generic_param_list = strukt.generic_param_list()?;
+    let type_param = generic_param_list.type_or_const_params().find_map(|it| match it {
+        ast::TypeOrConstParam::Type(it) if it.name()?.text() == type_param_name.text() => Some(it),
+        _ => None,
+    })?;
+    let arg_index = generic_param_list
+        .type_or_const_params()
+        .position(|it| it.syntax() == type_param.syntax())?;
+
+    let where_preds = strukt
+        .where_clause()
+        .into_iter()
+        .flat_map(|it| it.predicates())
+        .filter(|pred| bounded_type_param(&generic_param_list, pred).as_ref() == Some(&type_param))
+        .collect::<Vec<_>>();
+    // `?Sized` doesn't mean anything for a trait object.
+    let bounds = make::type_bound_list(
+        type_param
+            .type_bound_list()
+            .into_iter()
+            .chain(where_preds.iter().filter_map(|it| it.type_bound_list()))
+            .flat_map(|it| it.bounds())
+            .filter(|it| it.question_mark_token().is_none()),
+    )?;
+    let boxed_ty = make::ty(&format!("Box<dyn {bounds}>"));
+
+    // The parameter can't be used anywhere else in the struct.
+    let type_param_def = ctx.sema.to_def(&type_param)?;
+    let valid_ranges = std::iter::once(field_ty.syntax().text_range())
+        .chain(where_preds.iter().filter_map(|it| Some(it.ty()?.syntax().text_range())))
+        .collect::<Vec<_>>();
+    let struct_range = FileRange { file_id: ctx.file_id(), range: strukt.syntax().text_range() };
+    let all_usages_valid = Definition::GenericParam(type_param_def.into())
+        .usages(&ctx.sema)
+        .in_scope(&SearchScope::file_range(struct_range))
+        .all()
+        .iter()
+        .flat_map(|(_, refs)| refs)
+        .all(|it| valid_ranges.iter().any(|range| range.contains_range(it.range)));
+    if !all_usages_valid {
+        return None;
+    }
+
+    let strukt_def = ctx.sema.to_def(&strukt)?;
+    let mut files: FxHashMap<FileId, ToBoxDynEdits> = FxHashMap::default();
+    files.entry(ctx.file_id()).or_default();
+    for (file_id, paths) in struct_paths(ctx, strukt_def) {
+        let edits = files.entry(file_id).or_default();
+        for path in &paths {
+            let arg = path.segment().and_then(|it| it.generic_arg_list()).and_then(|it| {
+                it.generic_args()
+                    .filter(|arg| !matches!(arg, ast::GenericArg::LifetimeArg(_)))
+                    .nth(arg_index)
+            });
+            if let Some(impl_) = impl_of_self_ty(path) {
+                edits.impls.push(impl_edits(impl_, arg.as_ref()));
+            }
+            edits.args.extend(arg);
+        }
+        for path in &paths {
+            match field_init_expr(path, &field_key) {
+                Some(ast::Expr::PathExpr(path_expr)) => {
+                    // Parameters of the removed type become trait objects themselves.
+                    let is_boxed = local_param(&ctx.sema, &path_expr).map_or(false, |param| {
+                        edits.impls.iter().any(|it| it.boxed_params.contains(&param))
+                    });
+                    if !is_boxed {
+                        edits.boxed.push(path_expr.into());
+                    }
+                }
+                Some(expr) => edits.boxed.push(expr),
+                None => (),
+            }
+        }
+    }
+
+    let target = field_ty.syntax().text_range();
+    acc.add(
+        AssistId("convert_generic_field_to_box_dyn", AssistKind::RefactorRewrite),
+        "Convert generic field to boxed trait object",
+        target,
+        |edit| {
+            for (file_id, edits) in sorted_by_file(ctx, files) {
+                edit.edit_file(file_id);
+                let strukt_edits = (file_id == ctx.file_id()).then(|| {
+                    (
+                        edit.make_mut(strukt.clone()),
+                        edit.make_mut(field_ty.clone()),
+                        edit.make_mut(type_param.clone()),
+                        where_preds.iter().map(|it| edit.make_mut(it.clone())).collect::<Vec<_>>(),
+                    )
+                });
+                let impls = edits
+                    .impls
+                    .into_iter()
+                    .map(|it| ImplEdits {
+                        impl_: edit.make_mut(it.impl_),
+                        type_param: it.type_param.map(|it| edit.make_mut(it)),
+                        where_preds: it
+                            .where_preds
+                            .into_iter()
+                            .map(|it| edit.make_mut(it))
+                            .collect(),
+                        uses: it.uses.into_iter().map(|it| edit.make_mut(it)).collect(),
+                        boxed_params: Vec::new(),
+                    })
+                    .collect::<Vec<_>>();
+                let args = edits.args.into_iter().map(|it| edit.make_mut(it)).collect::<Vec<_>>();
+                let boxed = edits.boxed.into_iter().map(|it| edit.make_mut(it)).collect::<Vec<_>>();
+
+                if let Some((strukt, field_ty, type_param, where_preds)) = strukt_edits {
+                    ted::replace(field_ty.syntax(), boxed_ty.clone_for_update().syntax());
+                    remove_type_param(&strukt, type_param, where_preds);
+                }
+
+                for arg in args {
+                    let Some(arg_list) = arg.syntax().parent().and_then(ast::GenericArgList::cast)
+                    else {
+                        continue;
+                    };
+                    arg_list.remove_generic_arg(arg);
+                    if arg_list.generic_args().next().is_none() {
+                        ted::remove(arg_list.syntax());
+                    }
+                }
+
+                for impl_ in impls {
+                    for path_ty in impl_.uses {
+                        ted::replace(path_ty.syntax(), boxed_ty.clone_for_update().syntax());
+                    }
+                    if let Some(type_param) = impl_.type_param {
+                        remove_type_param(&impl_.impl_, type_param, impl_.where_preds);
+                    }
+                }
+
+                for expr in boxed {
+                    let box_new = make::expr_call(
+                        make::expr_path(make::path_from_text("Box::new")),
+                        make::arg_list([expr.clone()]),
+                    )
+                    .clone_for_update();
+                    match expr.syntax().parent().and_then(ast::RecordExprField::cast) {
+                        Some(field) => field.replace_expr(box_new),
+                        None => ted::replace(expr.syntax(), box_new.syntax()),
+                    }
+                }
+            }
+        },
+    )
+}
+
+#[derive(Default)]
+struct ToGenericEdits {
+    impls: Vec<ast::Impl>,
+    /// `Box::new` calls setting the field, and their arguments.
+    unboxed: Vec<(ast::CallExpr, ast::Expr)>,
+    /// Parameters of the boxed type that constructors set the field to.
+    params: Vec<ast::Param>,
+}
+
+#[derive(Default)]
+struct ToBoxDynEdits {
+    impls: Vec<ImplEdits>,
+    /// Generic arguments given for the removed type parameter.
+    args: Vec<ast::GenericArg>,
+    /// Expressions setting the field, which get boxed.
+    boxed: Vec<ast::Expr>,
+}
+
+struct ImplEdits {
+    impl_: ast::Impl,
+    /// The type parameter of the impl given for the removed one.
+    type_param: Option<ast::TypeParam>,
+    where_preds: Vec<ast::WherePred>,
+    /// The other uses of the impl's type parameter, which become trait objects.
+    uses: Vec<ast::PathType>,
+    /// The function parameters among `uses`.
+    boxed_params: Vec<ast::Param>,
+}
+
+fn impl_edits(impl_: ast::Impl, arg: Option<&ast::GenericArg>) -> ImplEdits {
+    let type_param = arg.and_then(|arg| impl_type_param(&impl_, arg));
+    let (where_preds, uses) = match (&type_param, impl_.generic_param_list()) {
+        (Some(type_param), Some(generic_param_list)) => {
+            let where_preds = impl_
+                .where_clause()
+                .into_iter()
+                .flat_map(|it| it.predicates())
+                .filter(|pred| {
+                    bounded_type_param(&generic_param_list, pred).as_ref() == Some(type_param)
+                })
+                .collect::<Vec<_>>();
+            let uses = type_param_uses(&impl_, type_param, &where_preds);
+            (where_preds, uses)
+        }
+        _ => (Vec::new(), Vec::new()),
+    };
+    let boxed_params =
+        uses.iter().filter_map(|it| it.syntax().parent().and_then(ast::Param::cast)).collect();
+    ImplEdits { impl_, type_param, where_preds, uses, boxed_params }
+}
+
+/// Returns the uses of `type_param` in the impl, other than in its self type and `where_preds`.
+fn type_param_uses(
+    impl_: &ast::Impl,
+    type_param: &ast::TypeParam,
+    where_preds: &[ast::WherePred],
+) -> Vec<ast::PathType> {
+    let Some(name) = type_param.name() else { return Vec::new() };
+    let skipped_ranges = impl_
+        .self_ty()
+        .map(|it| it.syntax().text_range())
+        .into_iter()
+        .chain(where_preds.iter().map(|it| it.syntax().text_range()))
+        .collect::<Vec<_>>();
+    impl_
+        .syntax()
+        .descendants()
+        .filter_map(ast::PathType::cast)
+        .filter(|it| {
+            it.path()
+                .and_then(|it| it.as_single_name_ref())
+                .map_or(false, |it| it.text() == name.text())
+        })
+        .filter(|it| {
+            let range = it.syntax().text_range();
+            !skipped_ranges.iter().any(|skipped| skipped.contains_range(range))
+        })
+        .collect()
+}
+
+enum FieldKey {
+    Name(String),
+    Index(usize),
+}
+
+/// Returns the struct and type of the field at the cursor, and how its constructors refer to it.
+fn field_at_cursor(ctx: &AssistContext<'_>) -> Option<(ast::Struct, ast::Type, FieldKey)> {
+    if let Some(field) = ctx.find_node_at_offset::<ast::RecordField>() {
+        let strukt = field.syntax().parent()?.parent().and_then(ast::Struct::cast)?;
+        let key = FieldKey::Name(field.name()?.text().to_string());
+        return Some((strukt, field.ty()?, key));
+    }
+    let field = ctx.find_node_at_offset::<ast::TupleField>()?;
+    let field_list = field.syntax().parent().and_then(ast::TupleFieldList::cast)?;
+    let strukt = field_list.syntax().parent().and_then(ast::Struct::cast)?;
+    let index = field_list.fields().position(|it| it == field)?;
+    Some((strukt, field.ty()?, FieldKey::Index(index)))
+}
+
+/// Returns the bounds of the trait object in a `Box<dyn Trait>` type.
+fn boxed_dyn_bounds(ty: &ast::Type) -> Option<ast::TypeBoundList> {
+    let ast::Type::PathType(ty) = ty else { return None };
+    let segment = ty.path()?.segment()?;
+    if segment.name_ref()?.text() != "Box" {
+        return None;
+    }
+    let mut args = segment.generic_arg_list()?.generic_args();
+    let (Some(ast::GenericArg::TypeArg(arg)), None) = (args.next(), args.next()) else {
+        return None;
+    };
+    let ast::Type::DynTraitType(dyn_ty) = arg.ty()? else { return None };
+    dyn_ty.type_bound_list()
+}
+
+/// Returns the paths naming the struct outside of macro calls, grouped by file.
+fn struct_paths(ctx: &AssistContext<'_>, strukt: hir::Struct) -> Vec<(FileId, Vec<ast::Path>)> {
+    let usages = Definition::Adt(strukt.into()).usages(&ctx.sema).include_self_refs().all();
+    usages
+        .into_iter()
+        .map(|(file_id, refs)| {
+            let paths = refs
+                .iter()
+                .filter_map(|it| it.name.as_name_ref())
+                .filter(|it| !ctx.sema.hir_file_for(it.syntax()).is_macro())
+                .filter_map(|name_ref| {
+                    let segment = name_ref.syntax().parent().and_then(ast::PathSegment::cast)?;
+                    let path = segment.parent_path();
+                    // Only the last segment of a path names the struct.
+                    path.parent_path().is_none().then_some(path)
+                })
+                .collect();
+            (file_id, paths)
+        })
+        .collect()
+}
+
+/// Orders the edits of each file so that the assist's own file comes first.
+fn sorted_by_file<T>(ctx: &AssistContext<'_>, files: FxHashMap<FileId, T>) -> Vec<(FileId, T)> {
+    let mut files = files.into_iter().collect::<Vec<_>>();
+    files.sort_by_key(|(file_id, _)| (*file_id != ctx.file_id(), *file_id));
+    files
+}
+
+/// Returns the impl whose self type is `path`.
+fn impl_of_self_ty(path: &ast::Path) -> Option<ast::Impl> {
+    let path_ty = path.syntax().parent().and_then(ast::PathType::cast)?;
+    let impl_ = path_ty.syntax().parent().and_then(ast::Impl::cast)?;
+    (impl_.self_ty()?.syntax() == path_ty.syntax()).then_some(impl_)
+}
+
+/// Returns the impl's own type parameter given as `arg` to its self type.
+fn impl_type_param(impl_: &ast::Impl, arg: &ast::GenericArg) -> Option<ast::TypeParam> {
+    let ast::GenericArg::TypeArg(arg) = arg else { return None };
+    let ast::Type::PathType(ty) = arg.ty()? else { return None };
+    let name = ty.path()?.as_single_name_ref()?;
+    impl_.generic_param_list()?.type_or_const_params().find_map(|it| match it {
+        ast::TypeOrConstParam::Type(it) if it.name()?.text() == name.text() => Some(it),
+        _ => None,
+    })
+}
+
+/// Returns the expression setting the field in a constructor of the struct called by `path`.
+fn field_init_expr(path: &ast::Path, field_key: &FieldKey) -> Option<ast::Expr> {
+    match field_key {
+        FieldKey::Name(name) => {
+            let record_expr = path.syntax().parent().and_then(ast::RecordExpr::cast)?;
+            let field = record_expr
+                .record_expr_field_list()?
+                .fields()
+                .find(|it| it.field_name().map_or(false, |it| it.text() == name.as_str()))?;
+            field.expr()
+        }
+        FieldKey::Index(index) => {
+            let path_expr = path.syntax().parent().and_then(ast::PathExpr::cast)?;
+            let call = path_expr.syntax().parent().and_then(ast::CallExpr::cast)?;
+            if call.expr()?.syntax() != path_expr.syntax() {
+                return None;
+            }
+            call.arg_list()?.args().nth(*index)
+        }
+    }
+}
+
+/// Returns the argument of a `Box::new` call.
+fn box_new_arg(call: &ast::CallExpr) -> Option<ast::Expr> {
+    let ast::Expr::PathExpr(callee) = call.expr()? else { return None };
+    let callee = callee.path()?;
+    if callee.segment()?.name_ref()?.text() != "new"
+        || callee.qualifier()?.segment()?.name_ref()?.text() != "Box"
+    {
+        return None;
+    }
+    let mut args = call.arg_list()?.args();
+    match (args.next(), args.next()) {
+        (Some(arg), None) => Some(arg),
+        _ => None,
+    }
+}
+
+/// Returns the function parameter a local refers to.
+fn local_param(
+    sema: &Semantics<'_, RootDatabase>,
+    path_expr: &ast::PathExpr,
+) -> Option<ast::Param> {
+    let path = path_expr.path()?;
+    let PathResolution::Local(local) = sema.resolve_path(&path)? else { return None };
+    if !local.is_param(sema.db) {
+        return None;
+    }
+    let name = path.as_single_name_ref()?;
+    let fn_ = path_expr.syntax().ancestors().find_map(ast::Fn::cast)?;
+    fn_.param_list()?.params().find(|param| match param.pat() {
+        Some(ast::Pat::IdentPat(pat)) => pat.name().map_or(false, |it| it.text() == name.text()),
+        _ => false,
+    })
+}
+
+/// Adds a type parameter with the given bounds to `owner`, returning its name. The name is unique
+/// among the parameters of `owner` and the names it refers to.
+fn add_type_param(
+    owner: &(impl GenericParamsOwnerEdit + AstNode),
+    base_name: &str,
+    bounds: &ast::TypeBoundList,
+) -> SmolStr {
+    let referred = owner
+        .syntax()
+        .descendants()
+        .filter_map(ast::NameRef::cast)
+        .map(|it| it.text().to_string())
+        .collect::<FxHashSet<_>>();
+    let generic_param_list = owner.get_or_create_generic_param_list();
+    let mut name = suggest_name::for_unique_generic_name(base_name, &generic_param_list);
+    let mut idx = 0;
+    while referred.contains(name.as_str()) {
+        name = suggest_name::for_unique_generic_name(
+            &format!("{base_name}{idx}"),
+            &generic_param_list,
+        );
+        idx += 1;
+    }
+    let type_param = make::type_param(make::name(&name), Some(bounds.clone())).clone_for_update();
+    generic_param_list.add_generic_param(type_param.into());
+    name
+}
+
+/// Removes `type_param` from `owner`, along with the where clause predicates bounding it.
+fn remove_type_param(
+    owner: &impl HasGenericParams,
+    type_param: ast::TypeParam,
+    where_preds: Vec<ast::WherePred>,
+) {
+    if let Some(generic_param_list) = owner.generic_param_list() {
+        generic_param_list.remove_generic_param(ast::GenericParam::TypeParam(type_param));
+        if generic_param_list.generic_params().next().is_none() {
+            ted::remove(generic_param_list.syntax());
+        }
+    }
+    if let Some(where_clause) = owner.where_clause() {
+        for pred in where_preds {
+            where_clause.remove_predicate(pred);
+        }
+        if where_clause.predicates().next().is_none() {
+            remove_where_clause(where_clause);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    #[test]
+    fn box_dyn_field_to_generic() {
+        check_assist(
+            convert_box_dyn_field_to_generic,
+            r#"
+trait Draw {}
+struct Window {
+    content: $0Box<dyn Draw>,
+}
+impl Window {
+    fn new(content: Box<dyn Draw>) -> Self {
+        Self { content }
+    }
+}
+impl Clone for Window {
+    fn clone(&self) -> Self { todo!() }
+}
+fn make(d: impl Draw + 'static) {
+    let _ = Window { content: Box::new(d) };
+}
+"#,
+            r#"
+trait Draw {}
+struct Window<D: Draw> {
+    content: D,
+}
+impl<D: Draw> Window<D> {
+    fn new(content: D) -> Self {
+        Self { content }
+    }
+}
+impl<D: Draw> Clone for Window<D> {
+    fn clone(&self) -> Self { todo!() }
+}
+fn make(d: impl Draw + 'static) {
+    let _ = Window { content: d };
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn box_dyn_tuple_field_to_generic() {
+        check_assist(
+            convert_box_dyn_field_to_generic,
+            r#"
+trait Draw {}
+struct Wrapper(u32, $0Box<dyn Draw>);
+fn make(d: Button) -> u32 {
+    let w = Wrapper(0, Box::new(d));
+    w.0
+}
+"#,
+            r#"
+trait Draw {}
+struct Wrapper<D: Draw>(u32, D);
+fn make(d: Button) -> u32 {
+    let w = Wrapper(0, d);
+    w.0
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn box_dyn_field_to_generic_with_existing_params() {
+        check_assist(
+            convert_box_dyn_field_to_generic,
+            r#"
+trait Draw {}
+struct Window<D> {
+    data: D,
+    content: $0Box<dyn Draw + Send>,
+}
+impl<D> Window<D> {}
+"#,
+            r#"
+trait Draw {}
+struct Window<D, D0: Draw + Send> {
+    data: D,
+    content: D0,
+}
+impl<D, D0: Draw + Send> Window<D, D0> {}
+"#,
+        );
+    }
+
+    #[test]
+    fn box_dyn_field_to_generic_named_after_last_segment() {
+        check_assist(
+            convert_box_dyn_field_to_generic,
+            r#"
+mod fmt { pub trait Debug {} }
+struct D;
+struct Window {
+    previous: D,
+    content: $0Box<dyn fmt::Debug>,
+}
+"#,
+            r#"
+mod fmt { pub trait Debug {} }
+struct D;
+struct Window<D0: fmt::Debug> {
+    previous: D,
+    content: D0,
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn box_dyn_field_to_generic_not_applicable_without_trait_object() {
+        check_assist_not_applicable(
+            convert_box_dyn_field_to_generic,
+            r#"
+struct Window {
+    content: $0Box<u32>,
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn generic_field_to_box_dyn() {
+        check_assist(
+            convert_generic_field_to_box_dyn,
+            r#"
+trait Draw {}
+struct Window<T, D>
+where
+    D: Draw,
+{
+    title: T,
+    content: $0D,
+}
+impl<T, D: Draw> Window<T, D> {
+    fn new(title: T, content: D) -> Self {
+        Window { title, content }
+    }
+}
+fn show(w: Window<u32, Button>) {}
+fn make(b: Button) {
+    let _ = Window::<u32, Button> { title: 0, content: b };
+}
+"#,
+            r#"
+trait Draw {}
+struct Window<T> {
+    title: T,
+    content: Box<dyn Draw>,
+}
+impl<T> Window<T> {
+    fn new(title: T, content: Box<dyn Draw>) -> Self {
+        Window { title, content }
+    }
+}
+fn show(w: Window<u32>) {}
+fn make(b: Button) {
+    let _ = Window::<u32> { title: 0, content: Box::new(b) };
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn generic_tuple_field_to_box_dyn() {
+        check_assist(
+            convert_generic_field_to_box_dyn,
+            r#"
+trait Draw {}
+struct Wrapper<D: Draw + ?Sized>(u32, $0D);
+fn make() {
+    let _ = Wrapper(0, Button::new());
+}
+"#,
+            r#"
+trait Draw {}
+struct Wrapper(u32, Box<dyn Draw>);
+fn make() {
+    let _ = Wrapper(0, Box::new(Button::new()));
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn generic_field_to_box_dyn_not_applicable_when_param_used_elsewhere() {
+        check_assist_not_applicable(
+            convert_generic_field_to_box_dyn,
+            r#"
+trait Draw {}
+struct Window<D: Draw> {
+    content: $0D,
+    previous: Vec<D>,
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn generic_field_to_box_dyn_not_applicable_without_bounds() {
+        check_assist_not_applicable(
+            convert_generic_field_to_box_dyn,
+            r#"
+struct Window<D> {
+    content: $0D,
+}
+"#,
+        );
+    }
+}
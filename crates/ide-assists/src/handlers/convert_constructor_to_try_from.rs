@@ -0,0 +1,320 @@
+use hir::ModuleDef;
+use ide_db::{defs::Definition, famous_defs::FamousDefs, helpers::mod_path_to_ast};
+use itertools::Itertools;
+use syntax::{
+    ast::{self, edit::IndentLevel, AstNode, HasGenericParams, HasName, HasVisibility},
+    SyntaxKind, TextRange, T,
+};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: convert_constructor_to_try_from
+//
+// Converts an associated function building `Self` from a single value, or failing with an error,
+// into a `TryFrom` impl, updating its calls.
+//
+// ```
+// # //- minicore: result, try_from
+// struct Port(u16);
+//
+// impl Port {
+//     fn $0from_number(number: u32) -> Result<Self, ()> {
+//         if number > 65535 { Err(()) } else { Ok(Port(number as u16)) }
+//     }
+// }
+//
+// fn main() {
+//     let _ = Port::from_number(80);
+// }
+// ```
+// ->
+// ```
+// struct Port(u16);
+//
+// impl TryFrom<u32> for Port {
+//     type Error = ();
+//
+//     fn try_from(number: u32) -> Result<Self, Self::Error> {
+//         if number > 65535 { Err(()) } else { Ok(Port(number as u16)) }
+//     }
+// }
+//
+// fn main() {
+//     let _ = Port::try_from(80);
+// }
+// ```
+pub(crate) fn convert_constructor_to_try_from(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let fn_ = ctx.find_node_at_offset::<ast::Fn>()?;
+    let name = fn_.name()?;
+    let body = fn_.body()?;
+    if body.syntax().text_range().contains_inclusive(ctx.offset()) {
+        return None;
+    }
+    let impl_ = fn_.syntax().parent()?.parent().and_then(ast::Impl::cast)?;
+    if impl_.trait_().is_some()
+        || fn_.generic_param_list().is_some()
+        || fn_.const_token().is_some()
+        || fn_.async_token().is_some()
+        || fn_.unsafe_token().is_some()
+    {
+        return None;
+    }
+    let param_list = fn_.param_list()?;
+    if param_list.self_param().is_some() {
+        return None;
+    }
+    let (param,) = param_list.params().collect_tuple()?;
+    let param_ty = param.ty()?;
+    // `Self` can't be named in the header of the new impl.
+    let mentions_self = param_ty.syntax().descendants_with_tokens().any(|it| it.kind() == T![Self]);
+    if mentions_self {
+        return None;
+    }
+    // The return type has to be spelled out as `Result<Self, E>`, `E` becoming the `Error`.
+    let ret_type = fn_.ret_type()?;
+    let ast::Type::PathType(ret_ty) = ret_type.ty()? else { return None };
+    let (_, error_arg) =
+        ret_ty.path()?.segment()?.generic_arg_list()?.generic_args().collect_tuple()?;
+    let ast::GenericArg::TypeArg(error_ty) = error_arg else { return None };
+    let error_ty = error_ty.ty()?;
+
+    let db = ctx.db();
+    let func = ctx.sema.to_def(&fn_)?;
+    let module = func.module(db);
+    let famous_defs = FamousDefs(&ctx.sema, module.krate());
+    let self_adt = ctx.sema.to_def(&impl_)?.self_ty(db).as_adt()?;
+    let ret_ty = func.ret_type(db);
+    let returns_result = ret_ty.as_adt() == Some(hir::Adt::Enum(famous_defs.core_result_Result()?));
+    if !returns_result || ret_ty.type_arguments().next()?.as_adt() != Some(self_adt) {
+        return None;
+    }
+    let try_from_path = mod_path_to_ast(&module.find_use_path(
+        db,
+        ModuleDef::Trait(famous_defs.core_convert_TryFrom()?),
+        ctx.config.prefer_no_std,
+        ctx.config.prefer_prelude,
+    )?);
+    let calls = Definition::Function(func).usages(&ctx.sema).all();
+
+    acc.add(
+        AssistId("convert_constructor_to_try_from", AssistKind::RefactorRewrite),
+        format!("Convert `{name}` to a TryFrom impl"),
+        fn_.syntax().text_range(),
+        |builder| {
+            let indent = IndentLevel::from_node(impl_.syntax());
+            let new_fn = try_from_fn(&fn_, &name, &ret_type);
+            let generic_params =
+                impl_.generic_param_list().map_or(String::new(), |it| it.to_string());
+            let self_ty = impl_.self_ty().map_or(String::new(), |it| it.to_string());
+            let where_clause = impl_.where_clause().map_or(String::new(), |it| format!(" {it}"));
+            let new_impl = format!(
+                "impl{generic_params} {try_from_path}<{param_ty}> for {self_ty}{where_clause} {{\n\
+                {}type Error = {error_ty};\n\n\
+                {}{new_fn}\n\
+                {indent}}}",
+                indent + 1,
+                indent + 1,
+            );
+
+            let is_only_item =
+                impl_.assoc_item_list().map_or(false, |it| it.assoc_items().count() == 1);
+            if is_only_item {
+                builder.replace(impl_.syntax().text_range(), new_impl);
+            } else {
+                // Take the whitespace separating the function from the next item, or the previous
+                // one if it's the last.
+                let ws = match fn_.syntax().next_sibling() {
+                    Some(_) => fn_.syntax().next_sibling_or_token(),
+                    None => fn_.syntax().prev_sibling_or_token(),
+                };
+                let range = match ws {
+                    Some(ws) if ws.kind() == SyntaxKind::WHITESPACE => {
+                        fn_.syntax().text_range().cover(ws.text_range())
+                    }
+                    _ => fn_.syntax().text_range(),
+                };
+                builder.delete(range);
+                builder
+                    .insert(impl_.syntax().text_range().end(), format!("\n\n{indent}{new_impl}"));
+            }
+
+            // The builder starts out editing the current file.
+            let calls = calls.into_iter().sorted_by_key(|(it, _)| (*it != ctx.file_id(), *it));
+            for (file_id, refs) in calls {
+                builder.edit_file(file_id);
+                for reference in refs {
+                    builder.replace(reference.range, "try_from");
+                }
+            }
+        },
+    )
+}
+
+/// The text of `fn_` as `TryFrom::try_from`.
+fn try_from_fn(fn_: &ast::Fn, name: &ast::Name, ret_type: &ast::RetType) -> String {
+    let fn_range = fn_.syntax().text_range();
+    let mut edits: Vec<(TextRange, &str)> = vec![
+        (name.syntax().text_range(), "try_from"),
+        (ret_type.syntax().text_range(), "-> Result<Self, Self::Error>"),
+    ];
+    if let Some(vis) = fn_.visibility() {
+        let mut range = vis.syntax().text_range();
+        if let Some(ws) = vis.syntax().next_sibling_or_token() {
+            if ws.kind() == SyntaxKind::WHITESPACE {
+                range = range.cover(ws.text_range());
+            }
+        }
+        edits.push((range, ""));
+    }
+    edits.sort_by_key(|(range, _)| range.start());
+
+    let text = fn_.syntax().to_string();
+    let mut buf = String::new();
+    let mut offset = 0;
+    for (range, replacement) in edits {
+        let range = range - fn_range.start();
+        buf.push_str(&text[offset..usize::from(range.start())]);
+        buf.push_str(replacement);
+        offset = range.end().into();
+    }
+    buf.push_str(&text[offset..]);
+    buf
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn keeps_other_items_of_the_impl() {
+        check_assist(
+            convert_constructor_to_try_from,
+            r#"
+//- minicore: result, try_from
+pub struct Name(String);
+
+impl Name {
+    /// Checks that `name` isn't empty.
+    pub fn $0parse(name: String) -> Result<Name, String> {
+        if name.is_empty() {
+            return Err(name);
+        }
+        Ok(Name(name))
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+fn f() {
+    let _ = Name::parse(String::new()).map(|it| it.len());
+}
+"#,
+            r#"
+pub struct Name(String);
+
+impl Name {
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+impl TryFrom<String> for Name {
+    type Error = String;
+
+    /// Checks that `name` isn't empty.
+    fn try_from(name: String) -> Result<Self, Self::Error> {
+        if name.is_empty() {
+            return Err(name);
+        }
+        Ok(Name(name))
+    }
+}
+
+fn f() {
+    let _ = Name::try_from(String::new()).map(|it| it.len());
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn updates_calls_in_other_files() {
+        check_assist(
+            convert_constructor_to_try_from,
+            r#"
+//- minicore: result, try_from
+//- /main.rs
+mod other;
+pub struct Even<T>(T);
+
+impl<T> Even<T> {
+    pub fn new$0(value: (T, u32)) -> Result<Self, u32> {
+        if value.1 % 2 == 0 { Ok(Even(value.0)) } else { Err(value.1) }
+    }
+}
+//- /other.rs
+fn f() {
+    let _ = [((), 2)].into_iter().map(crate::Even::new);
+}
+"#,
+            r#"
+//- /main.rs
+mod other;
+pub struct Even<T>(T);
+
+impl<T> TryFrom<(T, u32)> for Even<T> {
+    type Error = u32;
+
+    fn try_from(value: (T, u32)) -> Result<Self, Self::Error> {
+        if value.1 % 2 == 0 { Ok(Even(value.0)) } else { Err(value.1) }
+    }
+}
+//- /other.rs
+fn f() {
+    let _ = [((), 2)].into_iter().map(crate::Even::try_from);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable() {
+        check_assist_not_applicable(
+            convert_constructor_to_try_from,
+            r#"
+//- minicore: result, option, try_from
+struct S;
+impl S {
+    fn $0new(value: u32) -> Option<Self> { None }
+}
+"#,
+        );
+        check_assist_not_applicable(
+            convert_constructor_to_try_from,
+            r#"
+//- minicore: result, try_from
+struct S;
+impl S {
+    fn $0new(a: u32, b: u32) -> Result<Self, ()> { Err(()) }
+}
+"#,
+        );
+        check_assist_not_applicable(
+            convert_constructor_to_try_from,
+            r#"
+//- minicore: result, try_from
+struct S;
+impl S {
+    fn $0check(&self, value: u32) -> Result<Self, ()> { Err(()) }
+}
+"#,
+        );
+    }
+}
@@ -0,0 +1,694 @@
+use ide_db::{
+    defs::Definition,
+    syntax_helpers::node_ext::{is_pattern_cond, preorder_expr},
+};
+use stdx::format_to;
+use syntax::{
+    ast::{
+        self,
+        edit::{AstNodeEdit, IndentLevel},
+        make, ArithOp, BinaryOp, HasArgList, HasLoopBody,
+    },
+    AstNode, TextRange, WalkEvent,
+};
+
+use crate::{
+    handlers::convert_iter_for_each_to_for::iterable_to_iterator, utils::invert_boolean_expression,
+    AssistContext, AssistId, AssistKind, Assists,
+};
+
+// Assist: convert_for_loop_to_iterator_chain
+//
+// Converts a `for` loop that only pushes into a new `Vec` or adds to a sum, possibly skipping some
+// of the items, into an iterator chain.
+//
+// ```
+// fn main() {
+//     let xs = [1, 2, 3];
+//     let mut doubled = Vec::new();
+//     for$0 x in xs {
+//         doubled.push(x * 2);
+//     }
+// }
+// ```
+// ->
+// ```
+// fn main() {
+//     let xs = [1, 2, 3];
+//     let doubled: Vec<_> = xs.into_iter().map(|x| x * 2).collect();
+// }
+// ```
+pub(crate) fn convert_for_loop_to_iterator_chain(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let for_loop = ctx.find_node_at_offset::<ast::ForExpr>()?;
+    let iterable = for_loop.iterable()?;
+    let pat = for_loop.pat()?;
+    let body = for_loop.loop_body()?;
+    if body.syntax().text_range().start() < ctx.offset() {
+        return None;
+    }
+
+    let (filter, accumulation) = split_loop_body(&body)?;
+    let (accumulator, acc_ref, item) = match accumulation {
+        ast::Expr::MethodCallExpr(push_call) => {
+            if push_call.name_ref()?.text() != "push" {
+                return None;
+            }
+            let mut args = push_call.arg_list()?.args();
+            let pushed = args.next()?;
+            if args.next().is_some() {
+                return None;
+            }
+            (Accumulator::Push, push_call.receiver()?, pushed)
+        }
+        ast::Expr::BinExpr(add_assign)
+            if add_assign.op_kind() == Some(BinaryOp::Assignment { op: Some(ArithOp::Add) }) =>
+        {
+            (Accumulator::Sum, add_assign.lhs()?, add_assign.rhs()?)
+        }
+        _ => return None,
+    };
+    let ast::Expr::PathExpr(acc_ref) = acc_ref else { return None };
+
+    // The accumulator has to be created right before the loop.
+    let for_stmt = match for_loop.syntax().parent().and_then(ast::ExprStmt::cast) {
+        Some(stmt) => stmt.syntax().clone(),
+        None => for_loop.syntax().clone(),
+    };
+    let let_stmt = for_stmt.prev_sibling().and_then(ast::LetStmt::cast)?;
+    let ast::Pat::IdentPat(acc_pat) = let_stmt.pat()? else { return None };
+    let acc_name = acc_pat.name()?;
+    if acc_pat.pat().is_some() || acc_ref.path()?.as_single_name_ref()?.text() != acc_name.text() {
+        return None;
+    }
+    let ty = match accumulator {
+        Accumulator::Push => {
+            let ast::Expr::CallExpr(init) = let_stmt.initializer()? else { return None };
+            if init.expr()?.syntax().text() != "Vec::new"
+                || init.arg_list()?.args().next().is_some()
+            {
+                return None;
+            }
+            let_stmt.ty().map_or_else(|| "Vec<_>".to_owned(), |it| it.to_string())
+        }
+        Accumulator::Sum => {
+            let ast::Expr::Literal(init) = let_stmt.initializer()? else { return None };
+            if !is_zero(&init) {
+                return None;
+            }
+            // `sum` can't infer the type of the result on its own.
+            match let_stmt.ty() {
+                Some(ty) => ty.to_string(),
+                None => {
+                    let module = ctx.sema.scope(let_stmt.syntax())?.module();
+                    let ty = ctx.sema.type_of_pat(&acc_pat.clone().into())?.original;
+                    ty.display_source_code(ctx.db(), module.into(), false).ok()?
+                }
+            }
+        }
+    };
+
+    let target = for_loop.syntax().text_range();
+    acc.add(
+        AssistId("convert_for_loop_to_iterator_chain", AssistKind::RefactorRewrite),
+        "Replace this for loop with an iterator chain",
+        target,
+        |builder| {
+            let mut chain = iterable_to_iterator(&ctx.sema, &iterable);
+            if let Some(filter) = filter {
+                // `filter` passes the items by reference, copies can be destructured right away.
+                let is_copy =
+                    ctx.sema.type_of_pat(&pat).map_or(false, |it| it.original.is_copy(ctx.db()));
+                let is_binding_mode = matches!(
+                    &pat,
+                    ast::Pat::IdentPat(it) if it.ref_token().is_some() || it.mut_token().is_some()
+                );
+                if is_copy && !is_binding_mode {
+                    format_to!(chain, ".filter(|&{pat}| {filter})");
+                } else {
+                    format_to!(chain, ".filter(|{pat}| {filter})");
+                }
+            }
+            let is_identity = matches!(
+                (&item, &pat),
+                (ast::Expr::PathExpr(path), ast::Pat::IdentPat(ident))
+                    if path.syntax().text() == ident.syntax().text()
+            );
+            if !is_identity {
+                format_to!(chain, ".map(|{pat}| {item})");
+            }
+            match accumulator {
+                Accumulator::Push => chain.push_str(".collect()"),
+                Accumulator::Sum => chain.push_str(".sum()"),
+            }
+
+            // The accumulator no longer needs to be mutable if it was only ever changed in the
+            // loop.
+            let used_after_loop = ctx
+                .sema
+                .to_def(&acc_pat)
+                .map(|local| Definition::Local(local).usages(&ctx.sema).all())
+                .map_or(true, |usages| {
+                    usages.file_ranges().any(|it| !target.contains_range(it.range))
+                });
+            let acc_pat = if used_after_loop { acc_pat.to_string() } else { acc_name.to_string() };
+
+            builder.replace(
+                TextRange::new(let_stmt.syntax().text_range().start(), for_stmt.text_range().end()),
+                format!("let {acc_pat}: {ty} = {chain};"),
+            );
+        },
+    )
+}
+
+enum Accumulator {
+    Push,
+    Sum,
+}
+
+/// Splits the body of a `for` loop into the condition of the items that aren't skipped, if any,
+/// and the expression accumulating the items.
+fn split_loop_body(body: &ast::BlockExpr) -> Option<(Option<ast::Expr>, ast::Expr)> {
+    match block_exprs(body)?.as_slice() {
+        // `if cond { continue; } acc.push(x);`
+        [ast::Expr::IfExpr(if_expr), accumulation] => {
+            let cond = if_condition(if_expr)?;
+            let then_exprs = block_exprs(&if_expr.then_branch()?)?;
+            let [ast::Expr::ContinueExpr(continue_expr)] = then_exprs.as_slice() else {
+                return None;
+            };
+            if continue_expr.lifetime().is_some() {
+                return None;
+            }
+            Some((Some(invert_boolean_expression(cond)), accumulation.clone()))
+        }
+        // `if cond { acc.push(x); }`
+        [ast::Expr::IfExpr(if_expr)] => {
+            let cond = if_condition(if_expr)?;
+            let then_exprs = block_exprs(&if_expr.then_branch()?)?;
+            let [accumulation] = then_exprs.as_slice() else { return None };
+            Some((Some(cond), accumulation.clone()))
+        }
+        [accumulation] => Some((None, accumulation.clone())),
+        _ => None,
+    }
+}
+
+/// The expressions of a block made of expression statements only.
+fn block_exprs(block: &ast::BlockExpr) -> Option<Vec<ast::Expr>> {
+    block
+        .statements()
+        .map(|stmt| match stmt {
+            ast::Stmt::ExprStmt(it) => it.expr(),
+            _ => None,
+        })
+        .chain(block.tail_expr().map(Some))
+        .collect()
+}
+
+fn if_condition(if_expr: &ast::IfExpr) -> Option<ast::Expr> {
+    if if_expr.else_branch().is_some() {
+        return None;
+    }
+    let cond = if_expr.condition()?;
+    if is_pattern_cond(cond.clone()) {
+        return None;
+    }
+    Some(cond)
+}
+
+fn is_zero(literal: &ast::Literal) -> bool {
+    match literal.kind() {
+        ast::LiteralKind::IntNumber(it) => it.value().map_or(false, |it| it == 0),
+        ast::LiteralKind::FloatNumber(it) => it.value().map_or(false, |it| it == 0.0),
+        _ => false,
+    }
+}
+
+// Assist: convert_iterator_chain_to_for_loop
+//
+// Converts an iterator chain that is collected into a `Vec` into a `for` loop pushing into the
+// vector.
+//
+// ```
+// fn main() {
+//     let xs = [1, 2, 3];
+//     let doubled: Vec<_> = xs.into_iter().map(|x| x * 2).col$0lect();
+// }
+// ```
+// ->
+// ```
+// fn main() {
+//     let xs = [1, 2, 3];
+//     let mut doubled = Vec::new();
+//     for x in xs {
+//         doubled.push(x * 2);
+//     }
+// }
+// ```
+pub(crate) fn convert_iterator_chain_to_for_loop(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let collect_call = ctx.find_node_at_offset::<ast::MethodCallExpr>()?;
+    let name_ref = collect_call.name_ref()?;
+    if name_ref.text() != "collect"
+        || !name_ref.syntax().text_range().contains_range(ctx.selection_trimmed())
+        || collect_call.arg_list()?.args().next().is_some()
+    {
+        return None;
+    }
+    let let_stmt = collect_call.syntax().parent().and_then(ast::LetStmt::cast)?;
+    let ast::Pat::IdentPat(vec_pat) = let_stmt.pat()? else { return None };
+    if vec_pat.pat().is_some() {
+        return None;
+    }
+    let vec_name = vec_pat.name()?;
+
+    // Only `Vec`s can be filled with `push`.
+    let vec_ty = match (let_stmt.ty(), collect_call.generic_arg_list()) {
+        (Some(ty), _) => ty.to_string(),
+        (None, Some(generic_args)) => generic_args.generic_args().next()?.to_string(),
+        (None, None) => return None,
+    };
+    if vec_ty != "Vec" && !vec_ty.starts_with("Vec<") {
+        return None;
+    }
+
+    let receiver = collect_call.receiver()?;
+    let (source, pat, body) = match &receiver {
+        ast::Expr::MethodCallExpr(map_call)
+            if map_call.name_ref().map_or(false, |it| it.text() == "map") =>
+        {
+            let mut args = map_call.arg_list()?.args();
+            let ast::Expr::ClosureExpr(closure) = args.next()? else { return None };
+            if args.next().is_some() {
+                return None;
+            }
+            let mut params = closure.param_list()?.params();
+            let pat = params.next()?.pat()?;
+            if params.next().is_some() {
+                return None;
+            }
+            (map_call.receiver()?, pat, closure.body()?)
+        }
+        _ => {
+            let item = make::ext::simple_ident_pat(make::name("item"));
+            (receiver.clone(), item.into(), make::expr_path(make::ext::ident_path("item")))
+        }
+    };
+
+    // `for` loops call `into_iter` themselves.
+    let source = match source {
+        ast::Expr::MethodCallExpr(call)
+            if call.name_ref().map_or(false, |it| it.text() == "into_iter")
+                && call.arg_list().map_or(false, |it| it.args().next().is_none()) =>
+        {
+            call.receiver()?
+        }
+        source => source,
+    };
+
+    // `return` and `?` would leave the enclosing function instead of the closure.
+    let mut returns = false;
+    preorder_expr(&body, &mut |ev| {
+        returns |= matches!(ev, WalkEvent::Enter(ast::Expr::ReturnExpr(_) | ast::Expr::TryExpr(_)));
+        returns
+    });
+    if returns {
+        return None;
+    }
+
+    let (stmts, pushed) = match body {
+        ast::Expr::BlockExpr(block) if block.modifier().is_none() && block.label().is_none() => {
+            (block.statements().collect::<Vec<_>>(), block.tail_expr()?)
+        }
+        body => (Vec::new(), body),
+    };
+
+    acc.add(
+        AssistId("convert_iterator_chain_to_for_loop", AssistKind::RefactorRewrite),
+        "Replace this iterator chain with a for loop",
+        let_stmt.syntax().text_range(),
+        |builder| {
+            let indent = IndentLevel::from_node(let_stmt.syntax());
+            let push = make::expr_method_call(
+                make::expr_path(make::ext::ident_path(&vec_name.text())),
+                make::name_ref("push"),
+                make::arg_list([pushed.reset_indent()]),
+            );
+            let stmts =
+                stmts.into_iter().map(|it| it.reset_indent()).chain([make::expr_stmt(push).into()]);
+            let block = make::block_expr(stmts, None).indent(indent);
+            let for_loop = make::expr_for_loop(pat, source, block);
+
+            let ty = match let_stmt.ty() {
+                Some(_) if vec_ty == "Vec<_>" => String::new(),
+                Some(ty) => format!(": {ty}"),
+                None if vec_ty == "Vec<_>" || vec_ty == "Vec" => String::new(),
+                None => format!(": {vec_ty}"),
+            };
+
+            builder.replace(
+                let_stmt.syntax().text_range(),
+                format!("let mut {vec_name}{ty} = Vec::new();\n{indent}{for_loop}"),
+            );
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn for_loop_to_map_collect() {
+        check_assist(
+            convert_for_loop_to_iterator_chain,
+            r#"
+fn main() {
+    let xs = [1, 2, 3];
+    let mut doubled = Vec::new();
+    for$0 x in xs {
+        doubled.push(x * 2);
+    }
+}
+"#,
+            r#"
+fn main() {
+    let xs = [1, 2, 3];
+    let doubled: Vec<_> = xs.into_iter().map(|x| x * 2).collect();
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn for_loop_to_collect_without_map() {
+        check_assist(
+            convert_for_loop_to_iterator_chain,
+            r#"
+fn main() {
+    let xs = [1, 2, 3];
+    let mut copy: Vec<i32> = Vec::new();
+    $0for x in xs {
+        copy.push(x)
+    }
+}
+"#,
+            r#"
+fn main() {
+    let xs = [1, 2, 3];
+    let copy: Vec<i32> = xs.into_iter().collect();
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn for_loop_keeps_mut_if_used_later() {
+        check_assist(
+            convert_for_loop_to_iterator_chain,
+            r#"
+fn main() {
+    let xs = [1, 2, 3];
+    let mut ys = Vec::new();
+    for$0 x in xs {
+        ys.push(x + 1);
+    }
+    ys.push(0);
+}
+"#,
+            r#"
+fn main() {
+    let xs = [1, 2, 3];
+    let mut ys: Vec<_> = xs.into_iter().map(|x| x + 1).collect();
+    ys.push(0);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn for_loop_to_sum() {
+        check_assist(
+            convert_for_loop_to_iterator_chain,
+            r#"
+fn main() {
+    let xs = [1, 2, 3];
+    let mut total = 0;
+    for$0 x in xs {
+        total += x * x;
+    }
+}
+"#,
+            r#"
+fn main() {
+    let xs = [1, 2, 3];
+    let total: i32 = xs.into_iter().map(|x| x * x).sum();
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn for_loop_with_continue_to_filter() {
+        check_assist(
+            convert_for_loop_to_iterator_chain,
+            r#"
+//- minicore: copy
+fn main() {
+    let xs = [1, 2, 3];
+    let mut evens = Vec::new();
+    for$0 x in xs {
+        if x % 2 != 0 {
+            continue;
+        }
+        evens.push(x);
+    }
+}
+"#,
+            r#"
+fn main() {
+    let xs = [1, 2, 3];
+    let evens: Vec<_> = xs.into_iter().filter(|&x| x % 2 == 0).collect();
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn for_loop_with_if_to_filter_sum() {
+        check_assist(
+            convert_for_loop_to_iterator_chain,
+            r#"
+//- minicore: copy
+fn main() {
+    let xs = [1, 2, 3];
+    let mut total: u64 = 0;
+    for$0 x in xs {
+        if x > 1 {
+            total += x;
+        }
+    }
+}
+"#,
+            r#"
+fn main() {
+    let xs = [1, 2, 3];
+    let total: u64 = xs.into_iter().filter(|&x| x > 1).sum();
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn for_loop_not_applicable_with_labeled_continue() {
+        check_assist_not_applicable(
+            convert_for_loop_to_iterator_chain,
+            r#"
+fn main() {
+    let xs = [1, 2, 3];
+    'outer: for _ in 0..2 {
+        let mut ys = Vec::new();
+        for$0 x in xs {
+            if x > 1 {
+                continue 'outer;
+            }
+            ys.push(x);
+        }
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn for_loop_not_applicable_to_nonzero_sum() {
+        check_assist_not_applicable(
+            convert_for_loop_to_iterator_chain,
+            r#"
+fn main() {
+    let xs = [1, 2, 3];
+    let mut total = 10;
+    for$0 x in xs {
+        total += x;
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn for_loop_not_applicable_with_other_statements() {
+        check_assist_not_applicable(
+            convert_for_loop_to_iterator_chain,
+            r#"
+fn main() {
+    let xs = [1, 2, 3];
+    let mut ys = Vec::new();
+    for$0 x in xs {
+        let y = x + 1;
+        ys.push(y);
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn for_loop_not_applicable_without_fresh_vec() {
+        check_assist_not_applicable(
+            convert_for_loop_to_iterator_chain,
+            r#"
+fn main(mut ys: Vec<i32>) {
+    let xs = [1, 2, 3];
+    for$0 x in xs {
+        ys.push(x);
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn map_collect_to_for_loop() {
+        check_assist(
+            convert_iterator_chain_to_for_loop,
+            r#"
+fn main() {
+    let xs = [1, 2, 3];
+    let doubled: Vec<_> = xs.into_iter().map(|x| x * 2).col$0lect();
+}
+"#,
+            r#"
+fn main() {
+    let xs = [1, 2, 3];
+    let mut doubled = Vec::new();
+    for x in xs {
+        doubled.push(x * 2);
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn turbofish_collect_to_for_loop() {
+        check_assist(
+            convert_iterator_chain_to_for_loop,
+            r#"
+fn main() {
+    let xs = [1, 2, 3];
+    let ys = xs.iter().map(|x| {
+        let y = x + 1;
+        y * 2
+    }).collect$0::<Vec<i32>>();
+}
+"#,
+            r#"
+fn main() {
+    let xs = [1, 2, 3];
+    let mut ys: Vec<i32> = Vec::new();
+    for x in xs.iter() {
+        let y = x + 1;
+        ys.push(y * 2);
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn collect_without_map_to_for_loop() {
+        check_assist(
+            convert_iterator_chain_to_for_loop,
+            r#"
+fn main() {
+    let xs = [1, 2, 3];
+    let ys: Vec<_> = xs.iter().$0collect();
+}
+"#,
+            r#"
+fn main() {
+    let xs = [1, 2, 3];
+    let mut ys = Vec::new();
+    for item in xs.iter() {
+        ys.push(item);
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn map_with_return_to_for_loop_not_applicable() {
+        check_assist_not_applicable(
+            convert_iterator_chain_to_for_loop,
+            r#"
+fn main() {
+    let xs = [1, 2, 3];
+    let ys: Vec<_> = xs
+        .into_iter()
+        .map(|x| {
+            if x > 1 {
+                return 0;
+            }
+            x
+        })
+        .col$0lect();
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn map_with_try_to_for_loop_not_applicable() {
+        check_assist_not_applicable(
+            convert_iterator_chain_to_for_loop,
+            r#"
+fn main() {
+    let xs = [Some(1), None];
+    let ys: Vec<Option<i32>> = xs.into_iter().map(|x| Some(x? + 1)).col$0lect();
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn collect_into_other_collection_not_applicable() {
+        check_assist_not_applicable(
+            convert_iterator_chain_to_for_loop,
+            r#"
+fn main() {
+    let xs = ['a', 'b'];
+    let s: String = xs.into_iter().col$0lect();
+}
+"#,
+        );
+    }
+}
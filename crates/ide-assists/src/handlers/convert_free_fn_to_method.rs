@@ -0,0 +1,392 @@
+use hir::{ModuleDef, PathResolution};
+use ide_db::{
+    base_db::FileRange,
+    defs::Definition,
+    helpers::mod_path_to_ast,
+    imports::insert_use::remove_path_if_in_use_stmt,
+    search::{FileReference, SearchScope},
+    source_change::SourceChangeBuilder,
+};
+use itertools::Itertools;
+use syntax::{
+    ast::{self, edit::IndentLevel, make, AstNode, HasArgList, HasName},
+    ted, SyntaxNode, TextRange,
+};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+use super::inline_call::split_refs_and_uses;
+
+// Assist: convert_free_fn_to_method
+//
+// Turns a free function whose first parameter is (a reference to) a local type into a method of
+// that type, rewriting all of its calls into method calls.
+//
+// ```
+// struct Counter { n: u32 }
+//
+// fn $0bump(counter: &mut Counter, by: u32) {
+//     counter.n += by;
+// }
+//
+// fn main() {
+//     let mut c = Counter { n: 0 };
+//     bump(&mut c, 2);
+// }
+// ```
+// ->
+// ```
+// struct Counter { n: u32 }
+//
+// impl Counter {
+//     fn bump(&mut self, by: u32) {
+//         self.n += by;
+//     }
+// }
+//
+// fn main() {
+//     let mut c = Counter { n: 0 };
+//     c.bump(2);
+// }
+// ```
+pub(crate) fn convert_free_fn_to_method(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    let def_file = ctx.file_id();
+    let name = ctx.find_node_at_offset::<ast::Name>()?;
+    let ast_func = name.syntax().parent().and_then(ast::Fn::cast)?;
+    // Only free functions, not associated ones or functions nested in blocks.
+    let parent = ast_func.syntax().parent()?.kind();
+    if !ast::SourceFile::can_cast(parent) && !ast::ItemList::can_cast(parent) {
+        return None;
+    }
+    let func_body = ast_func.body()?;
+    let param_list = ast_func.param_list()?;
+    let mut params = param_list.params();
+    let first_param = params.next()?;
+    let second_param = params.next();
+    let ast::Pat::IdentPat(pat) = first_param.pat()? else { return None };
+    if pat.ref_token().is_some() || pat.pat().is_some() {
+        return None;
+    }
+    let param_name = pat.name()?;
+
+    let (self_param, self_ty, receiver_ref) = match first_param.ty()? {
+        ast::Type::RefType(it) => {
+            let lifetime = it.lifetime().map_or(String::new(), |it| format!("{it} "));
+            let mut_ = if it.mut_token().is_some() { "mut " } else { "" };
+            (format!("&{lifetime}{mut_}self"), it.ty()?, Some(it.mut_token().is_some()))
+        }
+        ty => {
+            let mut_ = if pat.mut_token().is_some() { "mut " } else { "" };
+            (format!("{mut_}self"), ty, None)
+        }
+    };
+    let ast::Type::PathType(self_ty) = self_ty else { return None };
+    let Some(PathResolution::Def(ModuleDef::Adt(adt))) = ctx.sema.resolve_path(&self_ty.path()?)
+    else {
+        return None;
+    };
+    let db = ctx.db();
+    let module = ctx.sema.scope(ast_func.syntax())?.module();
+    // Inherent impls have to live in the crate defining the type.
+    if adt.module(db).krate() != module.krate() {
+        return None;
+    }
+    if !hir::GenericDef::from(adt).params(db).is_empty() {
+        return None;
+    }
+
+    let function = ctx.sema.to_def(&ast_func)?;
+    let usages = Definition::Function(function).usages(&ctx.sema);
+    let is_recursive_fn = usages
+        .clone()
+        .in_scope(&SearchScope::file_range(FileRange {
+            file_id: def_file,
+            range: func_body.syntax().text_range(),
+        }))
+        .at_least_one();
+    if is_recursive_fn {
+        cov_mark::hit!(convert_free_fn_to_method_recursive);
+        return None;
+    }
+    let adt_path = |scope: &SyntaxNode| {
+        let module = ctx.sema.scope(scope)?.module();
+        let path = module.find_use_path(
+            db,
+            ModuleDef::Adt(adt),
+            ctx.config.prefer_no_std,
+            ctx.config.prefer_prelude,
+        )?;
+        Some(mod_path_to_ast(&path))
+    };
+    let impl_path = adt_path(ast_func.syntax())?;
+
+    // Uses of the parameter in the body, to be replaced by `self`.
+    let self_usages = match ctx.sema.to_def(&pat) {
+        Some(local) => Definition::Local(local)
+            .usages(&ctx.sema)
+            .in_scope(&SearchScope::single_file(def_file))
+            .all()
+            .references
+            .remove(&def_file)
+            .unwrap_or_default(),
+        None => Vec::new(),
+    };
+
+    acc.add(
+        AssistId("convert_free_fn_to_method", AssistKind::RefactorRewrite),
+        format!("Convert into method of `{impl_path}`"),
+        name.syntax().text_range(),
+        |builder| {
+            let fn_start = ast_func.syntax().text_range().start();
+            let mut edits = Vec::new();
+            let param_range = match &second_param {
+                Some(next) => TextRange::new(
+                    first_param.syntax().text_range().start(),
+                    next.syntax().text_range().start(),
+                ),
+                None => first_param.syntax().text_range(),
+            };
+            let sep = if second_param.is_some() { ", " } else { "" };
+            edits.push((param_range, format!("{self_param}{sep}")));
+            for usage in &self_usages {
+                let Some(name_ref) = usage.name.as_name_ref() else { continue };
+                match ast::RecordExprField::for_name_ref(name_ref) {
+                    Some(field) if field.name_ref().is_none() => {
+                        edits.push((usage.range, format!("{param_name}: self")))
+                    }
+                    _ => edits.push((usage.range, "self".to_owned())),
+                }
+            }
+            let mut method = ast_func.syntax().to_string();
+            for (range, text) in edits.into_iter().sorted_by_key(|(range, _)| range.start()).rev() {
+                let range = range - fn_start;
+                method.replace_range(std::ops::Range::<usize>::from(range), &text);
+            }
+
+            let indent = IndentLevel::from_node(ast_func.syntax());
+            let method = method
+                .lines()
+                .map(|line| if line.is_empty() { String::new() } else { format!("    {line}") })
+                .join("\n");
+
+            let mut usages = usages.all();
+            let current_file_usage = usages.references.remove(&def_file);
+            for (file_id, refs) in usages.into_iter() {
+                builder.edit_file(file_id);
+                rewrite_usages(builder, refs, &name, receiver_ref, &adt_path);
+            }
+            builder.edit_file(def_file);
+            if let Some(refs) = current_file_usage {
+                rewrite_usages(builder, refs, &name, receiver_ref, &adt_path);
+            }
+            builder.replace(
+                ast_func.syntax().text_range(),
+                format!("impl {impl_path} {{\n{indent}{method}\n{indent}}}"),
+            );
+        },
+    )
+}
+
+fn rewrite_usages(
+    builder: &mut SourceChangeBuilder,
+    refs: Vec<FileReference>,
+    name: &ast::Name,
+    receiver_ref: Option<bool>,
+    adt_path: &dyn Fn(&SyntaxNode) -> Option<ast::Path>,
+) {
+    let count = refs.len();
+    let (paths, uses) = split_refs_and_uses(builder, refs, |name_ref| {
+        // NAME_REF -> PATH_SEGMENT -> PATH -> PATH_EXPR
+        name_ref.syntax().ancestors().nth(3).and_then(ast::PathExpr::cast)
+    });
+    let replacements = paths
+        .into_iter()
+        .filter_map(|path_expr| {
+            let call = path_expr.syntax().parent().and_then(ast::CallExpr::cast);
+            let (target, replacement) = match call {
+                Some(call) if call.expr().map_or(false, |it| it.syntax() == path_expr.syntax()) => {
+                    let mut args = call.arg_list()?.args();
+                    let receiver = method_receiver(args.next()?, receiver_ref);
+                    let method_call = make::expr_method_call(
+                        receiver,
+                        make::name_ref(&name.text()),
+                        make::arg_list(args),
+                    );
+                    (call.syntax().clone(), method_call)
+                }
+                // The function is used as a value, e.g. `map(f)`.
+                _ => {
+                    let path = adt_path(path_expr.syntax())?;
+                    let path = make::path_from_text(&format!("{path}::{name}"));
+                    (path_expr.syntax().clone(), make::expr_path(path))
+                }
+            };
+            Some((builder.make_syntax_mut(target), replacement))
+        })
+        .collect::<Vec<_>>();
+    let replaced = replacements.len();
+    for (target, replacement) in replacements {
+        ted::replace(target, replacement.clone_for_update().syntax());
+    }
+    if replaced + uses.len() == count {
+        uses.iter().for_each(remove_path_if_in_use_stmt);
+    }
+}
+
+/// Turns the first argument of a call into the receiver of the method call, dropping borrows the
+/// method's `self` parameter takes care of.
+fn method_receiver(arg: ast::Expr, receiver_ref: Option<bool>) -> ast::Expr {
+    let arg = match (&arg, receiver_ref) {
+        (ast::Expr::RefExpr(it), Some(mut_)) if it.mut_token().is_some() == mut_ => {
+            it.expr().unwrap_or(arg)
+        }
+        _ => arg,
+    };
+    match arg {
+        ast::Expr::PathExpr(_)
+        | ast::Expr::FieldExpr(_)
+        | ast::Expr::MethodCallExpr(_)
+        | ast::Expr::CallExpr(_)
+        | ast::Expr::IndexExpr(_)
+        | ast::Expr::ParenExpr(_)
+        | ast::Expr::Literal(_)
+        | ast::Expr::MacroExpr(_)
+        | ast::Expr::TupleExpr(_)
+        | ast::Expr::ArrayExpr(_) => arg,
+        _ => make::expr_paren(arg),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn by_value_with_calls_in_other_files() {
+        check_assist(
+            convert_free_fn_to_method,
+            r#"
+//- /main.rs
+mod other;
+
+pub struct Meters(pub f64);
+
+pub fn double$0(mut m: Meters) -> Meters {
+    m.0 *= 2.0;
+    m
+}
+//- /other.rs
+use crate::{double, Meters};
+
+fn f() {
+    let m = double(Meters(1.0));
+    let ms = [Meters(2.0)].map(double);
+}
+"#,
+            r#"
+//- /main.rs
+mod other;
+
+pub struct Meters(pub f64);
+
+impl Meters {
+    pub fn double(mut self) -> Meters {
+        self.0 *= 2.0;
+        self
+    }
+}
+//- /other.rs
+use crate::{Meters};
+
+fn f() {
+    let m = Meters(1.0).double();
+    let ms = [Meters(2.0)].map(Meters::double);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn by_reference_in_module() {
+        check_assist(
+            convert_free_fn_to_method,
+            r#"
+mod shapes {
+    pub struct Rect { pub w: u32, pub h: u32 }
+
+    pub fn $0area(rect: &Rect, scale: u32) -> Rect {
+        let w = rect.w * scale;
+        Rect { w, h: rect.h }
+    }
+}
+
+fn main() {
+    let r = shapes::Rect { w: 1, h: 2 };
+    let a = shapes::area(&r, 1);
+    let b = shapes::area(&&r, 2);
+    let c = shapes::area(&(if true { r } else { r }), 3);
+}
+"#,
+            r#"
+mod shapes {
+    pub struct Rect { pub w: u32, pub h: u32 }
+
+    impl Rect {
+        pub fn area(&self, scale: u32) -> Rect {
+            let w = self.w * scale;
+            Rect { w, h: self.h }
+        }
+    }
+}
+
+fn main() {
+    let r = shapes::Rect { w: 1, h: 2 };
+    let a = r.area(1);
+    let b = (&r).area(2);
+    let c = (if true { r } else { r }).area(3);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_to_recursive_functions() {
+        cov_mark::check!(convert_free_fn_to_method_recursive);
+        check_assist_not_applicable(
+            convert_free_fn_to_method,
+            r#"
+struct List(Option<Box<List>>);
+
+fn len$0(list: &List) -> usize {
+    match &list.0 {
+        Some(rest) => 1 + len(rest),
+        None => 1,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_foreign_or_generic_types() {
+        check_assist_not_applicable(convert_free_fn_to_method, r#"fn f$0(x: &u32) {}"#);
+        check_assist_not_applicable(
+            convert_free_fn_to_method,
+            r#"
+struct W<T>(T);
+fn f$0(w: &W<u8>) {}
+"#,
+        );
+        check_assist_not_applicable(
+            convert_free_fn_to_method,
+            r#"
+struct S;
+impl S {
+    fn f$0(s: &S) {}
+}
+"#,
+        );
+    }
+}
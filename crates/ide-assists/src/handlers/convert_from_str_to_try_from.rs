@@ -0,0 +1,455 @@
+use hir::{ModuleDef, PathResolution};
+use ide_db::{
+    base_db::FileId, defs::Definition, famous_defs::FamousDefs, helpers::mod_path_to_ast,
+    imports::insert_use::remove_path_if_in_use_stmt, search::SearchScope,
+    traits::resolve_target_trait, FxHashMap,
+};
+use itertools::Itertools;
+use syntax::{
+    ast::{self, AstNode, HasName},
+    TextRange,
+};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: convert_from_str_to_try_from
+//
+// Converts a `FromStr` impl to an equivalent `TryFrom<&str>` impl, updating direct calls of
+// `from_str`, `parse` calls producing the type and `<T as FromStr>::Err` projections.
+//
+// ```
+// # //- minicore: from_str, try_from
+// struct Version(u32);
+//
+// impl $0core::str::FromStr for Version {
+//     type Err = ();
+//
+//     fn from_str(s: &str) -> Result<Self, Self::Err> {
+//         Err(())
+//     }
+// }
+//
+// fn main() {
+//     let _: Result<Version, _> = "1".parse();
+// }
+// ```
+// ->
+// ```
+// struct Version(u32);
+//
+// impl TryFrom<&str> for Version {
+//     type Error = ();
+//
+//     fn try_from(s: &str) -> Result<Self, Self::Error> {
+//         Err(())
+//     }
+// }
+//
+// fn main() {
+//     let _: Result<Version, _> = Version::try_from("1");
+// }
+// ```
+pub(crate) fn convert_from_str_to_try_from(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let impl_ = ctx.find_node_at_offset::<ast::Impl>()?;
+    let ast_trait = impl_.trait_()?;
+
+    let module = ctx.sema.scope(impl_.syntax())?.module();
+    let famous_defs = FamousDefs(&ctx.sema, module.krate());
+    let from_str = famous_defs.core_str_FromStr()?;
+    if resolve_target_trait(&ctx.sema, &impl_)? != from_str {
+        return None;
+    }
+    let try_from = famous_defs.core_convert_TryFrom()?;
+    let adt = ctx.sema.to_def(&impl_)?.self_ty(ctx.db()).as_adt()?;
+
+    let assoc_items = impl_.assoc_item_list()?;
+    let from_str_fn = assoc_items.assoc_items().find_map(|item| match item {
+        ast::AssocItem::Fn(f) if f.name()?.text() == "from_str" => Some(f),
+        _ => None,
+    })?;
+    let from_str_name = from_str_fn.name()?;
+    let err_name = assoc_items.assoc_items().find_map(|item| match item {
+        ast::AssocItem::TypeAlias(it) if it.name()?.text() == "Err" => it.name(),
+        _ => None,
+    });
+    // `Self::Err` has to follow the associated type's new name.
+    let err_refs = impl_
+        .syntax()
+        .descendants()
+        .filter_map(ast::Path::cast)
+        .filter(|path| {
+            path.qualifier().map_or(false, |it| it.to_string() == "Self")
+                && path
+                    .segment()
+                    .and_then(|it| it.name_ref())
+                    .map_or(false, |it| it.text() == "Err")
+        })
+        .filter_map(|path| path.segment()?.name_ref())
+        .collect::<Vec<_>>();
+
+    let file_id = ctx.file_id();
+    let try_from_path = try_from_path(ctx, try_from, module)?;
+    let mut edits = vec![
+        (file_id, ast_trait.syntax().text_range(), format!("{try_from_path}<&str>")),
+        (file_id, from_str_name.syntax().text_range(), "try_from".to_owned()),
+    ];
+    edits.extend(err_name.map(|it| (file_id, it.syntax().text_range(), "Error".to_owned())));
+    edits.extend(err_refs.iter().map(|it| (file_id, it.syntax().text_range(), "Error".to_owned())));
+    if let Some(from_str_fn) = ctx.sema.to_def(&from_str_fn) {
+        for (file_id, refs) in Definition::Function(from_str_fn).usages(&ctx.sema).all() {
+            edits.extend(refs.into_iter().map(|it| (file_id, it.range, "try_from".to_owned())));
+        }
+    }
+    // Only the type's own crate and its dependents can name it.
+    let scope = SearchScope::reverse_dependencies(ctx.db(), module.krate());
+    edits.extend(parse_call_edits(ctx, &famous_defs, adt, &scope)?);
+    edits.extend(err_projection_edits(ctx, from_str, try_from, adt, &scope)?);
+    let mut unused_imports = unused_imports(ctx, from_str, &edits);
+
+    acc.add(
+        AssistId("convert_from_str_to_try_from", AssistKind::RefactorRewrite),
+        "Convert FromStr to TryFrom<&str>",
+        impl_.syntax().text_range(),
+        |builder| {
+            let edits = edits.into_iter().into_group_map_by(|(file_id, ..)| *file_id);
+            // The current file comes first, as the builder starts out editing it.
+            let edits = edits.into_iter().sorted_by_key(|(it, _)| (*it != file_id, *it));
+            for (file_id, edits) in edits {
+                builder.edit_file(file_id);
+                let imports = unused_imports.remove(&file_id).unwrap_or_default();
+                let imports = imports.into_iter().map(|it| builder.make_mut(it)).collect_vec();
+                imports.iter().for_each(remove_path_if_in_use_stmt);
+                for (_, range, text) in edits {
+                    builder.replace(range, text);
+                }
+            }
+        },
+    )
+}
+
+fn try_from_path(
+    ctx: &AssistContext<'_>,
+    try_from: hir::Trait,
+    module: hir::Module,
+) -> Option<ast::Path> {
+    let path = module.find_use_path(
+        ctx.db(),
+        ModuleDef::Trait(try_from),
+        ctx.config.prefer_no_std,
+        ctx.config.prefer_prelude,
+    )?;
+    Some(mod_path_to_ast(&path))
+}
+
+/// `str::parse` goes through `FromStr`, so its calls producing `adt` become `try_from` calls.
+fn parse_call_edits(
+    ctx: &AssistContext<'_>,
+    famous_defs: &FamousDefs<'_, '_>,
+    adt: hir::Adt,
+    scope: &SearchScope,
+) -> Option<Vec<(FileId, TextRange, String)>> {
+    let db = ctx.db();
+    let parse = famous_defs
+        .core()
+        .into_iter()
+        .flat_map(|core| hir::Impl::all_in_crate(db, core))
+        .filter(|impl_| impl_.self_ty(db).as_builtin().map_or(false, |it| it.is_str()))
+        .flat_map(|impl_| impl_.items(db))
+        .find_map(|item| match item {
+            hir::AssocItem::Function(it) if it.name(db).to_smol_str() == "parse" => Some(it),
+            _ => None,
+        });
+    let Some(parse) = parse else { return Some(Vec::new()) };
+
+    let mut edits = Vec::new();
+    for (file_id, refs) in Definition::Function(parse).usages(&ctx.sema).in_scope(scope).all() {
+        for reference in refs {
+            let Some(name_ref) = reference.name.as_name_ref() else { continue };
+            let Some(call) = name_ref.syntax().ancestors().find_map(ast::CallableExpr::cast) else {
+                continue;
+            };
+            let expr = match &call {
+                ast::CallableExpr::Call(it) => ast::Expr::from(it.clone()),
+                ast::CallableExpr::MethodCall(it) => ast::Expr::from(it.clone()),
+            };
+            let parses_adt = ctx
+                .sema
+                .type_of_expr(&expr)
+                .and_then(|it| it.original.type_arguments().next())
+                .and_then(|it| it.as_adt());
+            if parses_adt != Some(adt) {
+                continue;
+            }
+            // Calls inside of macros and `str::parse(s)` calls aren't rewritten, so bail on those.
+            let ast::CallableExpr::MethodCall(call) = call else { return None };
+            if name_ref.syntax().text_range() != reference.range {
+                return None;
+            }
+            let module = ctx.sema.scope(call.syntax())?.module();
+            let adt_path = module.find_use_path(
+                db,
+                ModuleDef::Adt(adt),
+                ctx.config.prefer_no_std,
+                ctx.config.prefer_prelude,
+            )?;
+            let arg = str_arg(ctx, &call.receiver()?)?;
+            let text = format!("{}::try_from({arg})", mod_path_to_ast(&adt_path));
+            edits.push((file_id, call.syntax().text_range(), text));
+        }
+    }
+    Some(edits)
+}
+
+/// The receiver of a `parse` call as the `&str` that `try_from` takes.
+fn str_arg(ctx: &AssistContext<'_>, receiver: &ast::Expr) -> Option<String> {
+    let ty = ctx.sema.type_of_expr(receiver)?.original;
+    let is_str = |ty: &hir::Type| ty.as_builtin().map_or(false, |it| it.is_str());
+    let arg = match ty.remove_ref() {
+        Some(inner) if is_str(&inner) => receiver.to_string(),
+        None if is_str(&ty) => format!("&{receiver}"),
+        _ => match ty.strip_references().as_adt() {
+            Some(adt) if adt.name(ctx.db()).to_smol_str() == "String" => {
+                format!("{receiver}.as_str()")
+            }
+            _ => format!("&{receiver}[..]"),
+        },
+    };
+    Some(arg)
+}
+
+/// `<T as FromStr>::Err` becomes `<T as TryFrom<&str>>::Error`.
+fn err_projection_edits(
+    ctx: &AssistContext<'_>,
+    from_str: hir::Trait,
+    try_from: hir::Trait,
+    adt: hir::Adt,
+    scope: &SearchScope,
+) -> Option<Vec<(FileId, TextRange, String)>> {
+    let db = ctx.db();
+    let err = from_str.items(db).into_iter().find_map(|item| match item {
+        hir::AssocItem::TypeAlias(it) if it.name(db).to_smol_str() == "Err" => Some(it),
+        _ => None,
+    });
+    let Some(err) = err else { return Some(Vec::new()) };
+
+    let mut edits = Vec::new();
+    for (file_id, refs) in Definition::TypeAlias(err).usages(&ctx.sema).in_scope(scope).all() {
+        for reference in refs {
+            let Some(name_ref) = reference.name.as_name_ref() else { continue };
+            let qualifier = name_ref.syntax().ancestors().find_map(ast::Path::cast)?.qualifier();
+            let Some(ast::PathSegmentKind::Type { type_ref: Some(ty), trait_ref: Some(trait_ref) }) =
+                qualifier.and_then(|it| it.segment()).and_then(|it| it.kind())
+            else {
+                continue;
+            };
+            let ast::Type::PathType(ty) = ty else { continue };
+            let is_adt = match ctx.sema.resolve_path(&ty.path()?) {
+                Some(PathResolution::Def(ModuleDef::Adt(it))) => it == adt,
+                Some(PathResolution::SelfType(it)) => it.self_ty(db).as_adt() == Some(adt),
+                _ => false,
+            };
+            if !is_adt {
+                continue;
+            }
+            if name_ref.syntax().text_range() != reference.range {
+                return None;
+            }
+            let module = ctx.sema.scope(trait_ref.syntax())?.module();
+            let try_from_path = try_from_path(ctx, try_from, module)?;
+            edits.push((
+                file_id,
+                trait_ref.syntax().text_range(),
+                format!("{try_from_path}<&str>"),
+            ));
+            edits.push((file_id, reference.range, "Error".to_owned()));
+        }
+    }
+    Some(edits)
+}
+
+/// The imports of `FromStr` in the edited files that nothing but the edited code needed.
+fn unused_imports(
+    ctx: &AssistContext<'_>,
+    from_str: hir::Trait,
+    edits: &[(FileId, TextRange, String)],
+) -> FxHashMap<FileId, Vec<ast::Path>> {
+    let files = edits.iter().map(|(file_id, ..)| *file_id).unique().collect::<Vec<_>>();
+    let usages =
+        Definition::Trait(from_str).usages(&ctx.sema).in_scope(&SearchScope::files(&files)).all();
+
+    let mut res = FxHashMap::default();
+    for (file_id, refs) in usages {
+        let is_edited = |range: TextRange| {
+            edits.iter().any(|(it, edited, _)| *it == file_id && edited.contains_range(range))
+        };
+        let (imports, others): (Vec<_>, Vec<_>) = refs
+            .iter()
+            .filter_map(|it| it.name.as_name_ref())
+            .partition(|it| it.syntax().ancestors().any(|it| ast::Use::can_cast(it.kind())));
+        if others.iter().any(|it| !is_edited(it.syntax().text_range())) {
+            continue;
+        }
+        // Calls of other types' `from_str` need the trait in scope as well.
+        let calls_from_str = ctx
+            .sema
+            .parse(file_id)
+            .syntax()
+            .descendants()
+            .filter_map(ast::NameRef::cast)
+            .any(|it| it.text() == "from_str" && !is_edited(it.syntax().text_range()));
+        if calls_from_str {
+            continue;
+        }
+        let paths = imports
+            .iter()
+            .filter_map(|it| it.syntax().ancestors().find_map(ast::Path::cast))
+            .collect::<Vec<_>>();
+        res.insert(file_id, paths);
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn from_str_with_calls_in_other_files() {
+        check_assist(
+            convert_from_str_to_try_from,
+            r#"
+//- minicore: from_str, try_from
+//- /main.rs
+mod parse;
+use core::str::FromStr;
+
+pub struct Version(u32);
+
+impl FromStr$0 for Version {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Version, Self::Err> {
+        let _ = s;
+        Self::from_str("1")
+    }
+}
+//- /parse.rs
+use core::str::FromStr;
+use crate::Version;
+
+fn f() -> Result<Version, ()> {
+    Version::from_str("1")
+}
+
+fn g(s: &str) -> Result<Version, <Version as FromStr>::Err> {
+    s.parse::<Version>()
+}
+"#,
+            r#"
+//- /main.rs
+mod parse;
+
+pub struct Version(u32);
+
+impl TryFrom<&str> for Version {
+    type Error = ();
+
+    fn try_from(s: &str) -> Result<Version, Self::Error> {
+        let _ = s;
+        Self::try_from("1")
+    }
+}
+//- /parse.rs
+use crate::Version;
+
+fn f() -> Result<Version, ()> {
+    Version::try_from("1")
+}
+
+fn g(s: &str) -> Result<Version, <Version as TryFrom<&str>>::Error> {
+    Version::try_from(s)
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn keeps_import_used_by_other_impls() {
+        check_assist(
+            convert_from_str_to_try_from,
+            r#"
+//- minicore: from_str, try_from
+use core::str::FromStr;
+
+struct A;
+struct B;
+
+impl FromStr$0 for A {
+    type Err = ();
+
+    fn from_str(_: &str) -> Result<Self, Self::Err> {
+        Err(())
+    }
+}
+
+impl FromStr for B {
+    type Err = ();
+
+    fn from_str(_: &str) -> Result<Self, Self::Err> {
+        Err(())
+    }
+}
+
+fn f() {
+    let _: Result<B, _> = "b".parse();
+}
+"#,
+            r#"
+use core::str::FromStr;
+
+struct A;
+struct B;
+
+impl TryFrom<&str> for A {
+    type Error = ();
+
+    fn try_from(_: &str) -> Result<Self, Self::Error> {
+        Err(())
+    }
+}
+
+impl FromStr for B {
+    type Err = ();
+
+    fn from_str(_: &str) -> Result<Self, Self::Err> {
+        Err(())
+    }
+}
+
+fn f() {
+    let _: Result<B, _> = "b".parse();
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_other_traits() {
+        check_assist_not_applicable(
+            convert_from_str_to_try_from,
+            r#"
+//- minicore: from
+struct A;
+struct B;
+impl From$0<A> for B {
+    fn from(_: A) -> B { B }
+}
+"#,
+        );
+    }
+}
@@ -0,0 +1,273 @@
+use ide_db::{
+    assists::{AssistId, AssistKind},
+    ty_filter::TryEnum,
+};
+use syntax::{
+    ast::{self, make},
+    AstNode, T,
+};
+
+use crate::{
+    assist_context::{AssistContext, Assists},
+    utils::{extract_trivial_expression, try_exits_like_return},
+};
+
+// Assist: convert_let_else_to_try_expr
+//
+// Converts a let-else statement that returns `None` into a `try` expression.
+//
+// ```
+// # //- minicore: try, option
+// fn main() -> Option<()> {
+//     let Some(x) = Some(92) else$0 { return None };
+//     None
+// }
+// ```
+// ->
+// ```
+// fn main() -> Option<()> {
+//     let x = Some(92)?;
+//     None
+// }
+// ```
+pub(crate) fn convert_let_else_to_try_expr(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let let_stmt = ctx
+        .find_token_syntax_at_offset(T![else])
+        .and_then(|it| it.parent()?.parent())
+        .or_else(|| ctx.find_token_syntax_at_offset(T![let])?.parent())
+        .and_then(ast::LetStmt::cast)?;
+    let diverging_block = let_stmt.let_else()?.block_expr()?;
+    if let_stmt.ty().is_some() {
+        return None;
+    }
+    let init = let_stmt.initializer()?;
+    let init_ty = ctx.sema.type_of_expr(&init)?.original;
+    // The error value of a `Result` isn't available in the else branch, so it can't have been
+    // forwarded to the caller.
+    let TryEnum::Option = TryEnum::from_ty(&ctx.sema, &init_ty)? else { return None };
+
+    let ast::Pat::TupleStructPat(pat) = let_stmt.pat()? else { return None };
+    if pat.path()?.as_single_name_ref()?.text() != "Some" {
+        return None;
+    }
+    let mut fields = pat.fields();
+    let inner_pat = fields.next()?;
+    if fields.next().is_some()
+        || !matches!(inner_pat, ast::Pat::IdentPat(ref it) if it.pat().is_none())
+    {
+        return None;
+    }
+
+    match extract_trivial_expression(&diverging_block)? {
+        ast::Expr::ReturnExpr(ret) if ret.expr()?.syntax().text() == "None" => (),
+        _ => return None,
+    }
+    if !try_exits_like_return(let_stmt.syntax()) {
+        return None;
+    }
+
+    let target = let_stmt.syntax().text_range();
+    acc.add(
+        AssistId("convert_let_else_to_try_expr", AssistKind::RefactorRewrite),
+        "Convert let-else to try expression",
+        target,
+        |edit| {
+            let init = match init {
+                ast::Expr::BinExpr(_)
+                | ast::Expr::CastExpr(_)
+                | ast::Expr::PrefixExpr(_)
+                | ast::Expr::RangeExpr(_)
+                | ast::Expr::RefExpr(_) => make::expr_paren(init),
+                init => init,
+            };
+            let try_expr = make::expr_try(init);
+            edit.replace(target, format!("let {inner_pat} = {try_expr};"));
+        },
+    )
+}
+
+// Assist: convert_try_expr_to_let_else
+//
+// Converts a `let` statement initialized with a `try` expression on an `Option` into a let-else
+// statement.
+//
+// ```
+// # //- minicore: try, option
+// fn main() -> Option<()> {
+//     let x = Some(92)$0?;
+//     None
+// }
+// ```
+// ->
+// ```
+// fn main() -> Option<()> {
+//     let Some(x) = Some(92) else { return None };
+//     None
+// }
+// ```
+pub(crate) fn convert_try_expr_to_let_else(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let qm_kw = ctx.find_token_syntax_at_offset(T![?])?;
+    let try_expr = qm_kw.parent().and_then(ast::TryExpr::cast)?;
+    let let_stmt = try_expr.syntax().parent().and_then(ast::LetStmt::cast)?;
+    if let_stmt.let_else().is_some() || let_stmt.ty().is_some() {
+        return None;
+    }
+    let pat = let_stmt.pat()?;
+    let expr = try_expr.expr()?;
+    let expr_ty = ctx.sema.type_of_expr(&expr)?.original;
+    let TryEnum::Option = TryEnum::from_ty(&ctx.sema, &expr_ty)? else { return None };
+    if !try_exits_like_return(let_stmt.syntax()) {
+        return None;
+    }
+
+    let target = let_stmt.syntax().text_range();
+    acc.add(
+        AssistId("convert_try_expr_to_let_else", AssistKind::RefactorRewrite),
+        "Convert try expression to let-else",
+        target,
+        |edit| {
+            edit.replace(target, format!("let Some({pat}) = {expr} else {{ return None }};"));
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    #[test]
+    fn let_else_to_try_expr() {
+        check_assist(
+            convert_let_else_to_try_expr,
+            r#"
+//- minicore: try, option
+fn f(opt: Option<u8>) -> Option<u8> {
+    let Some(mut x) = opt else$0 {
+        return None;
+    };
+    x += 1;
+    Some(x)
+}
+"#,
+            r#"
+fn f(opt: Option<u8>) -> Option<u8> {
+    let mut x = opt?;
+    x += 1;
+    Some(x)
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn let_else_to_try_expr_not_applicable_for_other_diverging_blocks() {
+        check_assist_not_applicable(
+            convert_let_else_to_try_expr,
+            r#"
+//- minicore: try, option
+fn f(opt: Option<u8>) -> Option<u8> {
+    let Some(x) = opt else$0 { return Some(0) };
+    Some(x)
+}
+"#,
+        );
+        check_assist_not_applicable(
+            convert_let_else_to_try_expr,
+            r#"
+//- minicore: try, option
+fn f(opt: Option<u8>) {
+    let Some(x) = opt else$0 { return };
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn let_else_to_try_expr_not_applicable_for_result() {
+        check_assist_not_applicable(
+            convert_let_else_to_try_expr,
+            r#"
+//- minicore: try, result
+fn f(res: Result<u8, ()>) -> Result<u8, ()> {
+    let Ok(x) = res else$0 { return Err(()) };
+    Ok(x)
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn let_else_to_try_expr_not_applicable_for_refutable_inner_pattern() {
+        check_assist_not_applicable(
+            convert_let_else_to_try_expr,
+            r#"
+//- minicore: try, option
+fn f(opt: Option<u8>) -> Option<u8> {
+    let Some(1) = opt else$0 { return None };
+    Some(1)
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn try_expr_to_let_else() {
+        check_assist(
+            convert_try_expr_to_let_else,
+            r#"
+//- minicore: try, option
+fn f(opt: Option<u8>) -> Option<u8> {
+    let mut x = opt$0?;
+    x += 1;
+    Some(x)
+}
+"#,
+            r#"
+fn f(opt: Option<u8>) -> Option<u8> {
+    let Some(mut x) = opt else { return None };
+    x += 1;
+    Some(x)
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn try_expr_to_let_else_not_applicable_for_result() {
+        check_assist_not_applicable(
+            convert_try_expr_to_let_else,
+            r#"
+//- minicore: try, from, result
+fn f(res: Result<u8, ()>) -> Result<u8, ()> {
+    let x = res$0?;
+    Ok(x)
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn try_expr_to_let_else_not_applicable_in_try_block() {
+        check_assist_not_applicable(
+            convert_try_expr_to_let_else,
+            r#"
+//- minicore: try, option
+fn f(opt: Option<u8>) -> Option<u8> {
+    let y: Option<u8> = try {
+        let x = opt$0?;
+        x
+    };
+    y
+}
+"#,
+        );
+    }
+}
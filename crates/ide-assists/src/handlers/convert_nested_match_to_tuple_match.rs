@@ -0,0 +1,835 @@
+use ide_db::FxHashSet;
+use itertools::Itertools;
+use syntax::{
+    ast::{
+        self,
+        edit::{AstNodeEdit, IndentLevel},
+        HasName,
+    },
+    AstNode, SyntaxKind,
+};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: convert_nested_match_to_tuple_match
+//
+// Flattens a `match` whose arms match on a second value into a single `match` on a tuple of both.
+// Works the same on an `if` or `if let` whose body is another one.
+//
+// ```
+// //- minicore: copy, option
+// fn f(a: Option<u8>, b: bool) -> u8 {
+//     $0match a {
+//         Some(x) => match b {
+//             true => x,
+//             false => 0,
+//         },
+//         None => 1,
+//     }
+// }
+// ```
+// ->
+// ```
+// fn f(a: Option<u8>, b: bool) -> u8 {
+//     match (a, b) {
+//         (Some(x), true) => x,
+//         (Some(x), false) => 0,
+//         (None, _) => 1,
+//     }
+// }
+// ```
+pub(crate) fn convert_nested_match_to_tuple_match(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let match_expr = ctx.find_node_at_offset::<ast::MatchExpr>();
+    let if_expr = ctx.find_node_at_offset::<ast::IfExpr>();
+    match (match_expr, if_expr) {
+        (Some(match_expr), Some(if_expr))
+            if match_expr.syntax().text_range().contains_range(if_expr.syntax().text_range()) =>
+        {
+            flatten_if(acc, ctx, if_expr)
+        }
+        (Some(match_expr), _) => flatten_match(acc, ctx, match_expr),
+        (None, Some(if_expr)) => flatten_if(acc, ctx, if_expr),
+        (None, None) => None,
+    }
+}
+
+fn flatten_match(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+    match_expr: ast::MatchExpr,
+) -> Option<()> {
+    let arm_list = match_expr.match_arm_list()?;
+    if arm_list.syntax().text_range().contains_inclusive(ctx.offset()) {
+        return None;
+    }
+    let outer_scrutinee = match_expr.expr()?;
+
+    let mut inner_scrutinee: Option<ast::Expr> = None;
+    let mut new_arms = Vec::new();
+    let mut other_exprs = Vec::new();
+    for arm in arm_list.arms() {
+        // A failing guard falls through to the next outer arm, which a tuple match can't express.
+        if arm.guard().is_some() {
+            return None;
+        }
+        let pat = arm.pat()?;
+        let expr = arm.expr()?;
+        let Some(inner_match) = nested_match(&expr) else {
+            new_arms.push(format_arm(&format!("({pat}, _)"), None, &expr));
+            other_exprs.push(expr);
+            continue;
+        };
+
+        let scrutinee = inner_match.expr()?;
+        match &inner_scrutinee {
+            Some(it) if it.syntax().text() != scrutinee.syntax().text() => return None,
+            Some(_) => (),
+            None => inner_scrutinee = Some(scrutinee),
+        }
+        for inner_arm in inner_match.match_arm_list()?.arms() {
+            let inner_expr = inner_arm.expr()?;
+            let depth = IndentLevel::from_node(inner_arm.syntax()).0;
+            let dedent = IndentLevel(depth.saturating_sub(IndentLevel::from_node(arm.syntax()).0));
+            new_arms.push(format_arm(
+                &format!("({pat}, {})", inner_arm.pat()?),
+                inner_arm.guard(),
+                &inner_expr.dedent(dedent),
+            ));
+        }
+    }
+    let inner_scrutinee = inner_scrutinee?;
+    let outer_pats = arm_list.arms().filter_map(|arm| arm.pat());
+    if !can_flatten(ctx, &outer_scrutinee, &inner_scrutinee, outer_pats, &other_exprs) {
+        return None;
+    }
+
+    acc.add(
+        AssistId("convert_nested_match_to_tuple_match", AssistKind::RefactorRewrite),
+        "Convert to match on a tuple",
+        match_expr.syntax().text_range(),
+        |builder| {
+            let indent = IndentLevel::from_node(match_expr.syntax());
+            let arms =
+                new_arms.iter().map(|arm| format!("\n{}{arm}", indent + 1)).collect::<String>();
+            builder.replace(
+                match_expr.syntax().text_range(),
+                format!("match ({outer_scrutinee}, {inner_scrutinee}) {{{arms}\n{indent}}}"),
+            );
+        },
+    )
+}
+
+fn flatten_if(acc: &mut Assists, ctx: &AssistContext<'_>, if_expr: ast::IfExpr) -> Option<()> {
+    let then_branch = if_expr.then_branch()?;
+    if then_branch.syntax().text_range().contains_inclusive(ctx.offset()) {
+        return None;
+    }
+    // A `match` can't take the place of an `else if`.
+    if if_expr.syntax().parent().and_then(ast::IfExpr::cast).is_some() {
+        return None;
+    }
+    let ast::Expr::IfExpr(inner_if) = sole_expr(&then_branch)? else { return None };
+    let (outer_scrutinee, outer_pat) = if_pattern(&if_expr.condition()?)?;
+    let (inner_scrutinee, inner_pat) = if_pattern(&inner_if.condition()?)?;
+    let inner_then = inner_if.then_branch()?;
+    let inner_else = else_block(&inner_if)?;
+    let outer_else = else_block(&if_expr)?;
+
+    let other_exprs: Vec<ast::Expr> = outer_else.iter().map(|it| it.clone().into()).collect();
+    if !can_flatten(
+        ctx,
+        &outer_scrutinee,
+        &inner_scrutinee,
+        outer_pat.iter().cloned(),
+        &other_exprs,
+    ) {
+        return None;
+    }
+
+    acc.add(
+        AssistId("convert_nested_match_to_tuple_match", AssistKind::RefactorRewrite),
+        "Convert to match on a tuple",
+        if_expr.syntax().text_range(),
+        |builder| {
+            let indent = IndentLevel::from_node(if_expr.syntax());
+            let depth = IndentLevel::from_node(inner_if.syntax()).0;
+            let dedent = IndentLevel(depth.saturating_sub((indent + 1).0));
+            let pat =
+                |pat: Option<ast::Pat>| pat.map_or_else(|| "true".to_owned(), |it| it.to_string());
+            let (outer_pat, inner_pat) = (pat(outer_pat), pat(inner_pat));
+            let or_empty = |block: Option<ast::BlockExpr>| {
+                block.map_or_else(|| "{}".to_owned(), |it| it.to_string())
+            };
+            let arms = [
+                format!("({outer_pat}, {inner_pat}) => {}", inner_then.dedent(dedent)),
+                format!("({outer_pat}, _) => {}", or_empty(inner_else.map(|it| it.dedent(dedent)))),
+                format!("_ => {}", or_empty(outer_else.map(|it| it.indent(1.into())))),
+            ];
+            let arms = arms.iter().map(|arm| format!("\n{}{arm}", indent + 1)).collect::<String>();
+            builder.replace(
+                if_expr.syntax().text_range(),
+                format!("match ({outer_scrutinee}, {inner_scrutinee}) {{{arms}\n{indent}}}"),
+            );
+        },
+    )
+}
+
+/// Whether matching on `outer` and `inner` at once behaves like matching on `inner` only after
+/// `outer` matched one of `outer_pats`, with `other_exprs` being evaluated otherwise.
+fn can_flatten(
+    ctx: &AssistContext<'_>,
+    outer: &ast::Expr,
+    inner: &ast::Expr,
+    outer_pats: impl Iterator<Item = ast::Pat>,
+    other_exprs: &[ast::Expr],
+) -> bool {
+    // The inner scrutinee can't depend on what the outer patterns bind.
+    if uses_bindings_of(inner, outer_pats) {
+        cov_mark::hit!(convert_nested_match_scrutinee_uses_bindings);
+        return false;
+    }
+    // The tuple evaluates the inner scrutinee for the other outer arms too, so it has to be a
+    // place without side effects that those arms don't use.
+    let Some(root) = place_root(inner) else { return false };
+    if let Some(name) = root.as_single_name_ref() {
+        let used = other_exprs.iter().any(|expr| {
+            expr.syntax()
+                .descendants()
+                .filter_map(ast::NameRef::cast)
+                .any(|it| it.text() == name.text())
+        });
+        if used {
+            cov_mark::hit!(convert_nested_match_scrutinee_used_elsewhere);
+            return false;
+        }
+    }
+    // The tuple moves out of the places it is made of, which the nested matches may have only
+    // borrowed from, or only moved out of in some of the arms.
+    let moves = |expr: &ast::Expr| {
+        place_root(expr).is_some()
+            && !ctx.sema.type_of_expr(expr).map_or(false, |it| it.original.is_copy(ctx.db()))
+    };
+    if moves(outer) || moves(inner) {
+        cov_mark::hit!(convert_nested_match_scrutinee_not_copy);
+        return false;
+    }
+    true
+}
+
+// Assist: convert_tuple_match_to_nested_match
+//
+// Splits a `match` on a pair into a `match` on its first element with nested matches on the
+// second one.
+//
+// ```
+// enum Dir { Up, Down }
+//
+// fn f(a: Dir, b: bool) -> u8 {
+//     $0match (a, b) {
+//         (Dir::Up, true) => 1,
+//         (Dir::Up, false) => 2,
+//         (Dir::Down, _) => 3,
+//     }
+// }
+// ```
+// ->
+// ```
+// enum Dir { Up, Down }
+//
+// fn f(a: Dir, b: bool) -> u8 {
+//     match a {
+//         Dir::Up => match b {
+//             true => 1,
+//             false => 2,
+//         }
+//         Dir::Down => 3,
+//     }
+// }
+// ```
+pub(crate) fn convert_tuple_match_to_nested_match(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let match_expr = ctx.find_node_at_offset::<ast::MatchExpr>()?;
+    let arm_list = match_expr.match_arm_list()?;
+    if arm_list.syntax().text_range().contains_inclusive(ctx.offset()) {
+        return None;
+    }
+    let ast::Expr::TupleExpr(scrutinee) = match_expr.expr()? else { return None };
+    let (first, second) = scrutinee.fields().collect_tuple()?;
+    // Only the groups which look at the second value evaluate it, so it can't have side effects.
+    if !matches!(second, ast::Expr::PathExpr(_)) {
+        cov_mark::hit!(convert_tuple_match_second_not_path);
+        return None;
+    }
+
+    // Arms grouped by their first pattern, each group becoming one arm of the outer match.
+    let mut groups: Vec<(ast::Pat, Vec<ast::MatchArm>)> = Vec::new();
+    for arm in arm_list.arms() {
+        let first_pat = match arm.pat()? {
+            ast::Pat::TuplePat(it) => {
+                let (first_pat, second_pat) = it.fields().collect_tuple()?;
+                if [&first_pat, &second_pat].iter().any(|it| matches!(it, ast::Pat::RestPat(_))) {
+                    return None;
+                }
+                first_pat
+            }
+            ast::Pat::WildcardPat(it) => it.into(),
+            _ => return None,
+        };
+        match groups.last_mut() {
+            Some((pat, arms)) if pat.syntax().text() == first_pat.syntax().text() => arms.push(arm),
+            _ => {
+                // Otherwise the arms in between would have to be duplicated into both groups.
+                if groups.iter().any(|(pat, _)| pat.syntax().text() == first_pat.syntax().text()) {
+                    cov_mark::hit!(convert_tuple_match_groups_not_adjacent);
+                    return None;
+                }
+                groups.push((first_pat, vec![arm]));
+            }
+        }
+    }
+    if groups.is_empty() {
+        return None;
+    }
+    if uses_bindings_of(&second, groups.iter().map(|(pat, _)| pat.clone())) {
+        return None;
+    }
+    let groups = groups
+        .into_iter()
+        .map(|(pat, arms)| {
+            let inner_arms = arms
+                .iter()
+                .map(|arm| {
+                    let second_pat = match arm.pat()? {
+                        ast::Pat::TuplePat(it) => it.fields().nth(1)?,
+                        it => it,
+                    };
+                    Some((second_pat, arm.guard(), arm.expr()?))
+                })
+                .collect::<Option<Vec<_>>>()?;
+            Some((pat, inner_arms))
+        })
+        .collect::<Option<Vec<_>>>()?;
+    // A value the inner arms of a group don't match would fall through to the later groups in
+    // the tuple match, but not in the nested one.
+    if let Some((_, init)) = groups.split_last() {
+        if !init.iter().all(|(_, inner_arms)| covers_all(ctx, &second, inner_arms)) {
+            cov_mark::hit!(convert_tuple_match_group_not_exhaustive);
+            return None;
+        }
+    }
+
+    acc.add(
+        AssistId("convert_tuple_match_to_nested_match", AssistKind::RefactorRewrite),
+        "Convert to nested matches",
+        match_expr.syntax().text_range(),
+        |builder| {
+            let indent = IndentLevel::from_node(match_expr.syntax());
+            let mut buf = format!("match {first} {{");
+            for (pat, inner_arms) in groups {
+                buf.push_str(&format!("\n{}", indent + 1));
+                match inner_arms.as_slice() {
+                    [(ast::Pat::WildcardPat(_), None, expr)] => {
+                        buf.push_str(&format_arm(&pat.to_string(), None, expr))
+                    }
+                    _ => {
+                        buf.push_str(&format!("{pat} => match {second} {{"));
+                        for (second_pat, guard, expr) in inner_arms {
+                            let arm =
+                                format_arm(&second_pat.to_string(), guard, &expr.indent(1.into()));
+                            buf.push_str(&format!("\n{}{arm}", indent + 2));
+                        }
+                        buf.push_str(&format!("\n{}}}", indent + 1));
+                    }
+                }
+            }
+            buf.push_str(&format!("\n{indent}}}"));
+            builder.replace(match_expr.syntax().text_range(), buf);
+        },
+    )
+}
+
+type InnerArm = (ast::Pat, Option<ast::MatchGuard>, ast::Expr);
+
+/// Whether the unguarded `arms` match every value of `scrutinee`, as far as their patterns tell.
+fn covers_all(ctx: &AssistContext<'_>, scrutinee: &ast::Expr, arms: &[InnerArm]) -> bool {
+    let mut bools = FxHashSet::default();
+    let mut variants = FxHashSet::default();
+    for (pat, ..) in arms.iter().filter(|(_, guard, _)| guard.is_none()) {
+        if is_irrefutable(ctx, pat) {
+            return true;
+        }
+        match pat {
+            ast::Pat::LiteralPat(it) => {
+                if let Some(ast::LiteralKind::Bool(value)) = it.literal().map(|it| it.kind()) {
+                    bools.insert(value);
+                }
+            }
+            _ => variants.extend(matched_variant(ctx, pat)),
+        }
+    }
+    if bools.len() == 2 {
+        return true;
+    }
+    match ctx.sema.type_of_expr(scrutinee).and_then(|it| it.original.as_adt()) {
+        Some(hir::Adt::Enum(enum_)) => {
+            enum_.variants(ctx.db()).into_iter().all(|it| variants.contains(&it))
+        }
+        _ => false,
+    }
+}
+
+/// The variant `pat` matches whenever the value is of it.
+fn matched_variant(ctx: &AssistContext<'_>, pat: &ast::Pat) -> Option<hir::Variant> {
+    let (path, fields_irrefutable) = match pat {
+        ast::Pat::PathPat(it) => (it.path()?, true),
+        ast::Pat::TupleStructPat(it) => {
+            (it.path()?, it.fields().all(|it| is_irrefutable(ctx, &it)))
+        }
+        ast::Pat::RecordPat(it) => {
+            let fields = it.record_pat_field_list()?.fields().collect::<Vec<_>>();
+            let irrefutable =
+                fields.iter().all(|it| it.pat().map_or(false, |pat| is_irrefutable(ctx, &pat)));
+            (it.path()?, irrefutable)
+        }
+        ast::Pat::IdentPat(it) => {
+            return match ctx.sema.resolve_bind_pat_to_const(it)? {
+                hir::ModuleDef::Variant(it) => Some(it),
+                _ => None,
+            }
+        }
+        _ => return None,
+    };
+    match ctx.sema.resolve_path(&path)? {
+        hir::PathResolution::Def(hir::ModuleDef::Variant(it)) if fields_irrefutable => Some(it),
+        _ => None,
+    }
+}
+
+fn is_irrefutable(ctx: &AssistContext<'_>, pat: &ast::Pat) -> bool {
+    match pat {
+        ast::Pat::WildcardPat(_) | ast::Pat::RestPat(_) => true,
+        ast::Pat::IdentPat(it) => match it.pat() {
+            Some(pat) => is_irrefutable(ctx, &pat),
+            None => ctx.sema.resolve_bind_pat_to_const(it).is_none(),
+        },
+        ast::Pat::TuplePat(it) => it.fields().all(|it| is_irrefutable(ctx, &it)),
+        ast::Pat::ParenPat(it) => it.pat().map_or(false, |it| is_irrefutable(ctx, &it)),
+        _ => false,
+    }
+}
+
+fn uses_bindings_of(expr: &ast::Expr, pats: impl Iterator<Item = ast::Pat>) -> bool {
+    let bindings = pats
+        .flat_map(|pat| pat.syntax().descendants().filter_map(ast::IdentPat::cast))
+        .filter_map(|it| Some(it.name()?.text().to_string()))
+        .collect::<FxHashSet<_>>();
+    expr.syntax()
+        .descendants_with_tokens()
+        .filter_map(|it| it.into_token())
+        .any(|it| it.kind() == SyntaxKind::IDENT && bindings.contains(it.text()))
+}
+
+/// The path a place expression like `a.b.c` is based on, `None` for expressions that may have
+/// side effects.
+fn place_root(expr: &ast::Expr) -> Option<ast::Path> {
+    match expr {
+        ast::Expr::PathExpr(it) => it.path(),
+        ast::Expr::FieldExpr(it) => place_root(&it.expr()?),
+        ast::Expr::ParenExpr(it) => place_root(&it.expr()?),
+        _ => None,
+    }
+}
+
+/// Returns the `match` making up all of `expr`, possibly wrapped in a plain block.
+fn nested_match(expr: &ast::Expr) -> Option<ast::MatchExpr> {
+    match expr {
+        ast::Expr::MatchExpr(it) => Some(it.clone()),
+        ast::Expr::BlockExpr(block) if block.modifier().is_none() => match sole_expr(block)? {
+            ast::Expr::MatchExpr(it) => Some(it),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// The tail expression of `block`, if it has no statements.
+fn sole_expr(block: &ast::BlockExpr) -> Option<ast::Expr> {
+    let stmt_list = block.stmt_list()?;
+    if stmt_list.statements().next().is_some() {
+        return None;
+    }
+    stmt_list.tail_expr()
+}
+
+/// The scrutinee and pattern of an `if` condition, without a pattern for a plain boolean one.
+fn if_pattern(cond: &ast::Expr) -> Option<(ast::Expr, Option<ast::Pat>)> {
+    match cond {
+        ast::Expr::LetExpr(it) => Some((it.expr()?, Some(it.pat()?))),
+        // `let` chains
+        _ if cond.syntax().descendants().any(|it| ast::LetExpr::can_cast(it.kind())) => None,
+        _ => Some((cond.clone(), None)),
+    }
+}
+
+/// The `else` block of `if_expr`, `Some(None)` if there is none and `None` for an `else if`.
+fn else_block(if_expr: &ast::IfExpr) -> Option<Option<ast::BlockExpr>> {
+    match if_expr.else_branch() {
+        None => Some(None),
+        Some(ast::ElseBranch::Block(it)) => Some(Some(it)),
+        Some(ast::ElseBranch::IfExpr(_)) => None,
+    }
+}
+
+fn format_arm(pat: &str, guard: Option<ast::MatchGuard>, expr: &ast::Expr) -> String {
+    let guard = guard.map_or(String::new(), |it| format!(" {it}"));
+    let comma = if expr.is_block_like() { "" } else { "," };
+    format!("{pat}{guard} => {expr}{comma}")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn flatten_with_guards_and_blocks() {
+        check_assist(
+            convert_nested_match_to_tuple_match,
+            r#"
+//- minicore: copy, option
+fn f(a: Option<u8>, b: Option<u8>) -> u8 {
+    match$0 a {
+        Some(x) => {
+            match b {
+                Some(y) if y > x => {
+                    y
+                }
+                Some(_) => x,
+                None => 1,
+            }
+        }
+        None => 0,
+    }
+}
+"#,
+            r#"
+fn f(a: Option<u8>, b: Option<u8>) -> u8 {
+    match (a, b) {
+        (Some(x), Some(y)) if y > x => {
+            y
+        }
+        (Some(x), Some(_)) => x,
+        (Some(x), None) => 1,
+        (None, _) => 0,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn flatten_not_applicable_with_different_scrutinees() {
+        check_assist_not_applicable(
+            convert_nested_match_to_tuple_match,
+            r#"
+fn f(a: bool, b: bool, c: bool) -> u8 {
+    match$0 a {
+        true => match b { _ => 1 },
+        false => match c { _ => 2 },
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn flatten_not_applicable_if_scrutinee_uses_bindings() {
+        cov_mark::check!(convert_nested_match_scrutinee_uses_bindings);
+        check_assist_not_applicable(
+            convert_nested_match_to_tuple_match,
+            r#"
+fn f(a: Option<(u8, u8)>) -> u8 {
+    match$0 a {
+        Some(pair) => match pair.0 { _ => 1 },
+        None => 0,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn flatten_not_applicable_with_side_effects_in_scrutinee() {
+        check_assist_not_applicable(
+            convert_nested_match_to_tuple_match,
+            r#"
+fn f(a: bool, mut it: impl Iterator<Item = u8>) -> u8 {
+    match$0 a {
+        true => match it.next() {
+            Some(x) => x,
+            None => 1,
+        },
+        false => 0,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn flatten_not_applicable_if_other_arm_uses_scrutinee() {
+        cov_mark::check!(convert_nested_match_scrutinee_used_elsewhere);
+        check_assist_not_applicable(
+            convert_nested_match_to_tuple_match,
+            r#"
+fn f(a: bool, b: Option<String>) -> String {
+    match$0 a {
+        true => match b {
+            Some(x) => x,
+            None => String::new(),
+        },
+        false => consume(b),
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn flatten_not_applicable_if_scrutinee_is_moved() {
+        cov_mark::check!(convert_nested_match_scrutinee_not_copy);
+        check_assist_not_applicable(
+            convert_nested_match_to_tuple_match,
+            r#"
+//- minicore: copy, option
+struct S;
+fn f(a: bool, b: Option<S>) -> Option<S> {
+    match$0 a {
+        true => match b {
+            Some(_) => None,
+            None => None,
+        },
+        false => None,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn flatten_if_let() {
+        check_assist(
+            convert_nested_match_to_tuple_match,
+            r#"
+//- minicore: copy, option
+fn f(a: Option<u8>, b: Option<u8>) -> u8 {
+    $0if let Some(x) = a {
+        if let Some(y) = b {
+            x + y
+        } else {
+            x
+        }
+    } else {
+        0
+    }
+}
+"#,
+            r#"
+fn f(a: Option<u8>, b: Option<u8>) -> u8 {
+    match (a, b) {
+        (Some(x), Some(y)) => {
+            x + y
+        }
+        (Some(x), _) => {
+            x
+        }
+        _ => {
+            0
+        }
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn flatten_ifs_without_else() {
+        check_assist(
+            convert_nested_match_to_tuple_match,
+            r#"
+//- minicore: copy
+fn f(a: bool, b: bool) {
+    i$0f a {
+        if b {
+            g();
+        }
+    }
+}
+"#,
+            r#"
+fn f(a: bool, b: bool) {
+    match (a, b) {
+        (true, true) => {
+            g();
+        }
+        (true, _) => {}
+        _ => {}
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn flatten_not_applicable_with_else_if() {
+        check_assist_not_applicable(
+            convert_nested_match_to_tuple_match,
+            r#"
+//- minicore: copy
+fn f(a: bool, b: bool, c: bool) {
+    $0if a {
+        if b {
+            g();
+        }
+    } else if c {
+        g();
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn flatten_not_applicable_with_side_effects_in_if_let() {
+        check_assist_not_applicable(
+            convert_nested_match_to_tuple_match,
+            r#"
+//- minicore: copy, option, iterator
+fn f(a: bool, mut it: impl Iterator<Item = u8>) -> u8 {
+    $0if a {
+        if let Some(x) = it.next() {
+            x
+        } else {
+            1
+        }
+    } else {
+        0
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn split_with_guards() {
+        check_assist(
+            convert_tuple_match_to_nested_match,
+            r#"
+fn f(a: Option<u8>, b: bool) -> u8 {
+    $0match (a, b) {
+        (Some(x), true) if x > 1 => x,
+        (Some(x), _) => x + 1,
+        _ => 0,
+    }
+}
+"#,
+            r#"
+fn f(a: Option<u8>, b: bool) -> u8 {
+    match a {
+        Some(x) => match b {
+            true if x > 1 => x,
+            _ => x + 1,
+        }
+        _ => 0,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn split_with_exhaustive_enum_group() {
+        check_assist(
+            convert_tuple_match_to_nested_match,
+            r#"
+enum E { A, B(u8) }
+fn f(a: bool, b: E) -> u8 {
+    $0match (a, b) {
+        (true, E::A) => 1,
+        (true, E::B(x)) => x,
+        (false, _) => 0,
+    }
+}
+"#,
+            r#"
+enum E { A, B(u8) }
+fn f(a: bool, b: E) -> u8 {
+    match a {
+        true => match b {
+            E::A => 1,
+            E::B(x) => x,
+        }
+        false => 0,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn split_not_applicable_if_group_falls_through() {
+        cov_mark::check!(convert_tuple_match_group_not_exhaustive);
+        check_assist_not_applicable(
+            convert_tuple_match_to_nested_match,
+            r#"
+fn f(a: Option<u8>, b: bool) -> u8 {
+    $0match (a, b) {
+        (Some(x), true) => x,
+        (_, false) => 0,
+        (None, true) => 1,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn split_not_applicable_with_side_effects_in_second() {
+        cov_mark::check!(convert_tuple_match_second_not_path);
+        check_assist_not_applicable(
+            convert_tuple_match_to_nested_match,
+            r#"
+fn f(a: bool, mut it: impl Iterator<Item = u8>) -> u8 {
+    $0match (a, it.next()) {
+        (true, Some(x)) => x,
+        (true, None) => 1,
+        (false, _) => 0,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn split_not_applicable_with_interleaved_arms() {
+        cov_mark::check!(convert_tuple_match_groups_not_adjacent);
+        check_assist_not_applicable(
+            convert_tuple_match_to_nested_match,
+            r#"
+fn f(a: bool, b: bool) -> u8 {
+    $0match (a, b) {
+        (true, true) => 1,
+        (false, _) => 2,
+        (true, false) => 3,
+    }
+}
+"#,
+        );
+    }
+}
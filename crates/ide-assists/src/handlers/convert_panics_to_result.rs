@@ -0,0 +1,1017 @@
+use hir::HirDisplay;
+use ide_db::{
+    famous_defs::FamousDefs,
+    imports::insert_use::{insert_use, ImportScope},
+    syntax_helpers::node_ext::{for_each_tail_expr, walk_expr},
+    ty_filter::TryEnum,
+};
+use stdx::{format_to, to_camel_case};
+use syntax::{
+    ast::{self, edit::IndentLevel, make, HasName, HasVisibility},
+    match_ast, AstNode, NodeOrToken, SyntaxKind, SyntaxNode, TextRange, TextSize,
+};
+
+use crate::{
+    handlers::wrap_return_type_in_result::{collect_callers, tail_cb_impl, update_callers},
+    AssistContext, AssistId, AssistKind, Assists, PanicErrorType, WrapReturnTypeCallers,
+};
+
+// Assist: convert_panics_to_result
+//
+// Turns `unwrap()`, `expect()` and `panic!` in a function into error propagation, wrapping the
+// return type into `Result`. Without a selection, all of the function body is converted.
+// The distinct error types get collected into an error enum generated for the function, or into
+// `anyhow::Error` with the `assist.panicToResult.errorType` setting.
+//
+// ```
+// # //- minicore: option, result, panic, derive
+// # #[derive(Debug)]
+// # struct ParseError;
+// # fn parse(s: &str) -> Result<i32, ParseError> { Ok(0) }
+// fn $0sum(a: Option<&str>, b: &str) -> i32 {
+//     let a = a.expect("missing a");
+//     parse(a).unwrap() + parse(b).unwrap()
+// }
+// ```
+// ->
+// ```
+// # #[derive(Debug)]
+// # struct ParseError;
+// # fn parse(s: &str) -> Result<i32, ParseError> { Ok(0) }
+// fn sum(a: Option<&str>, b: &str) -> Result<i32, SumError> {
+//     let a = a.ok_or_else(|| SumError::Message("missing a".to_owned()))?;
+//     Ok(parse(a)? + parse(b)?)
+// }
+//
+// #[derive(Debug)]
+// enum SumError {
+//     Parse(ParseError),
+//     Message(String),
+// }
+//
+// impl From<ParseError> for SumError {
+//     fn from(err: ParseError) -> Self {
+//         Self::Parse(err)
+//     }
+// }
+// ```
+pub(crate) fn convert_panics_to_result(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    let fn_ = ctx.find_node_at_range::<ast::Fn>()?;
+    let body = fn_.body()?;
+    if fn_.const_token().is_some() {
+        return None;
+    }
+    // The signature of a trait method has to match the one of the trait.
+    let in_trait = fn_.syntax().parent().and_then(ast::AssocItemList::cast).map_or(false, |it| {
+        it.syntax().parent().map_or(false, |parent| {
+            match_ast! {
+                match parent {
+                    ast::Impl(it) => it.trait_().is_some(),
+                    ast::Trait(_) => true,
+                    _ => false,
+                }
+            }
+        })
+    });
+    if in_trait {
+        return None;
+    }
+    let selection = ctx.selection_trimmed();
+    if selection.is_empty() {
+        // Without a selection, only offer the assist on the signature, not anywhere in the body.
+        let signature =
+            TextRange::new(fn_.syntax().text_range().start(), body.syntax().text_range().start());
+        if !signature.contains_inclusive(ctx.offset()) {
+            return None;
+        }
+    }
+
+    let sites = panic_sites(ctx, &body)
+        .into_iter()
+        .filter(|site| selection.is_empty() || selection.contains_range(site.range()))
+        .collect::<Vec<_>>();
+    if sites.is_empty() {
+        return None;
+    }
+
+    let module = ctx.sema.scope(fn_.syntax())?.module();
+    let ret_ty = fn_.ret_type().and_then(|it| it.ty());
+    let existing_error_ty = match &ret_ty {
+        Some(ty) => {
+            let ty = ctx.sema.resolve_type(ty)?;
+            match TryEnum::from_ty(&ctx.sema, &ty) {
+                Some(TryEnum::Result) => Some(ty.type_arguments().nth(1)?),
+                Some(TryEnum::Option) => return None,
+                None => None,
+            }
+        }
+        None => None,
+    };
+
+    let changes_signature = existing_error_ty.is_none();
+    let target = match existing_error_ty {
+        Some(error_ty)
+            if ctx.config.panic_error_type == PanicErrorType::Anyhow
+                && is_anyhow_error(ctx, &error_ty) =>
+        {
+            ErrorTarget::Anyhow
+        }
+        Some(error_ty) => {
+            let ty_text = error_ty.display_source_code(ctx.db(), module.into(), false).ok()?;
+            ErrorTarget::Single { ty: error_ty, ty_text }
+        }
+        None => match ctx.config.panic_error_type {
+            PanicErrorType::Anyhow => ErrorTarget::Anyhow,
+            PanicErrorType::Enum => {
+                new_error_target(ctx, module, fn_.name()?.text().as_str(), &sites)?
+            }
+        },
+    };
+
+    let returns_unit = match &ret_ty {
+        Some(ty) => ctx.sema.resolve_type(ty)?.is_unit(),
+        None => true,
+    };
+    let body_expr = ast::Expr::BlockExpr(body.clone());
+    let mut values_to_wrap = Vec::new();
+    let mut bare_returns = Vec::new();
+    if changes_signature {
+        let tail_cb = &mut |e: &_| tail_cb_impl(&mut values_to_wrap, e);
+        walk_expr(&body_expr, &mut |expr| {
+            if let ast::Expr::ReturnExpr(ret_expr) = expr {
+                match ret_expr.expr() {
+                    Some(arg) => for_each_tail_expr(&arg, tail_cb),
+                    None => bare_returns.push(ret_expr),
+                }
+            }
+        });
+        if !returns_unit {
+            for_each_tail_expr(&body_expr, tail_cb);
+        }
+    }
+    // Expressions producing the value of the function, where a `panic!` can become `Err(..)`.
+    let mut value_ranges =
+        values_to_wrap.iter().map(|it| it.syntax().text_range()).collect::<Vec<_>>();
+    if returns_unit {
+        value_ranges.extend(body.tail_expr().map(|it| it.syntax().text_range()));
+    }
+
+    let site_edits = sites
+        .iter()
+        .filter_map(|site| convert_site(site, &target, value_ranges.contains(&site.range())))
+        .collect::<Vec<_>>();
+    if site_edits.is_empty() {
+        return None;
+    }
+    let diverging = sites
+        .iter()
+        .filter(|site| matches!(site, PanicSite::Panic { .. }))
+        .map(|site| site.range())
+        .filter(|range| site_edits.iter().any(|(it, _)| it == range))
+        .collect::<Vec<_>>();
+    let needs_context = site_edits.iter().any(|(_, text)| text.contains("context("));
+
+    let callers = match (ctx.config.wrap_return_type_callers, changes_signature) {
+        (WrapReturnTypeCallers::None, _) | (_, false) => Vec::new(),
+        (mode, true) => {
+            let fn_def = ctx.sema.to_def(&fn_)?;
+            collect_callers(ctx, fn_def, fn_.syntax().text_range(), mode, TryEnum::Result)
+        }
+    };
+    let import_scope = if needs_context {
+        Some(ImportScope::find_insert_use_container(fn_.syntax(), &ctx.sema)?)
+    } else {
+        None
+    };
+
+    let target_range = if selection.is_empty() { fn_.syntax().text_range() } else { selection };
+    acc.add(
+        AssistId("convert_panics_to_result", AssistKind::RefactorRewrite),
+        "Convert panics to `Result` propagation",
+        target_range,
+        |edit| {
+            for (range, text) in site_edits {
+                edit.replace(range, text);
+            }
+
+            if changes_signature {
+                let error_ty = target.type_text();
+                let new_ret_ty = |ty: &str| match &target {
+                    ErrorTarget::Anyhow => format!("anyhow::Result<{ty}>"),
+                    _ => format!("Result<{ty}, {error_ty}>"),
+                };
+                match &ret_ty {
+                    Some(ty) => edit.replace(ty.syntax().text_range(), new_ret_ty(&ty.to_string())),
+                    None => {
+                        if let Some(param_list) = fn_.param_list() {
+                            edit.insert(
+                                param_list.syntax().text_range().end(),
+                                format!(" -> {}", new_ret_ty("()")),
+                            );
+                        }
+                    }
+                }
+
+                for expr in values_to_wrap {
+                    let range = expr.syntax().text_range();
+                    if diverging.contains(&range) {
+                        continue;
+                    }
+                    edit.insert(range.start(), "Ok(");
+                    edit.insert(range.end(), ")");
+                }
+                for ret_expr in bare_returns {
+                    edit.replace(ret_expr.syntax().text_range(), "return Ok(())");
+                }
+                if returns_unit {
+                    if let Some((offset, text)) = unit_ok_tail(&body, &diverging) {
+                        edit.insert(offset, text);
+                    }
+                }
+            }
+
+            if let ErrorTarget::Enum(error_enum) = &target {
+                if let Some(item) = enclosing_module_item(&fn_) {
+                    let vis = fn_.visibility().map_or_else(String::new, |it| format!("{it} "));
+                    let indent = IndentLevel::from_node(&item);
+                    edit.insert(item.text_range().end(), error_enum.to_text(&vis, indent));
+                }
+            }
+
+            if let Some(scope) = import_scope {
+                let scope = match scope {
+                    ImportScope::File(it) => ImportScope::File(edit.make_mut(it)),
+                    ImportScope::Module(it) => ImportScope::Module(edit.make_mut(it)),
+                    ImportScope::Block(it) => ImportScope::Block(edit.make_mut(it)),
+                };
+                insert_use(&scope, make::path_from_text("anyhow::Context"), &ctx.config.insert_use);
+            }
+
+            update_callers(edit, ctx, callers);
+        },
+    )
+}
+
+/// An expression in the function body that panics on failure.
+enum PanicSite {
+    /// `.unwrap()` or `.expect(msg)` on a `Result` with the given error type.
+    Result { call: ast::MethodCallExpr, error_ty: hir::Type },
+    /// `.unwrap()` or `.expect(msg)` on an `Option`.
+    Option { call: ast::MethodCallExpr },
+    /// A `panic!` invocation.
+    Panic { expr: ast::MacroExpr },
+}
+
+impl PanicSite {
+    fn range(&self) -> TextRange {
+        match self {
+            PanicSite::Result { call, .. } | PanicSite::Option { call } => {
+                call.syntax().text_range()
+            }
+            PanicSite::Panic { expr } => expr.syntax().text_range(),
+        }
+    }
+}
+
+/// The error type the function propagates.
+enum ErrorTarget {
+    /// `anyhow::Error`, which every site can be converted into.
+    Anyhow,
+    /// A single error type, only `Result`s with exactly that error type get converted.
+    Single { ty: hir::Type, ty_text: String },
+    /// An enum generated for the function.
+    Enum(ErrorEnum),
+}
+
+impl ErrorTarget {
+    fn type_text(&self) -> &str {
+        match self {
+            ErrorTarget::Anyhow => "anyhow::Error",
+            ErrorTarget::Single { ty_text, .. } => ty_text,
+            ErrorTarget::Enum(it) => &it.name,
+        }
+    }
+}
+
+struct ErrorEnum {
+    name: String,
+    /// A variant wrapping each distinct error type, along with its `From` impl.
+    variants: Vec<ErrorVariant>,
+    /// Whether there is a `Message(String)` variant for `None`s and `panic!`s.
+    has_message: bool,
+    /// Whether every wrapped error type implements `Debug`, so that the enum can derive it.
+    derive_debug: bool,
+}
+
+struct ErrorVariant {
+    name: String,
+    ty: hir::Type,
+    ty_text: String,
+}
+
+impl ErrorEnum {
+    fn to_text(&self, vis: &str, indent: IndentLevel) -> String {
+        let name = &self.name;
+        let mut buf = String::from("\n\n");
+        if self.derive_debug {
+            format_to!(buf, "{indent}#[derive(Debug)]\n");
+        }
+        format_to!(buf, "{indent}{vis}enum {name} {{\n");
+        for variant in &self.variants {
+            format_to!(buf, "{indent}    {}({}),\n", variant.name, variant.ty_text);
+        }
+        if self.has_message {
+            format_to!(buf, "{indent}    Message(String),\n");
+        }
+        format_to!(buf, "{indent}}}");
+        for ErrorVariant { name: variant, ty_text, .. } in &self.variants {
+            format_to!(
+                buf,
+                "\n\n{indent}impl From<{ty_text}> for {name} {{\n\
+                 {indent}    fn from(err: {ty_text}) -> Self {{\n\
+                 {indent}        Self::{variant}(err)\n\
+                 {indent}    }}\n\
+                 {indent}}}"
+            );
+        }
+        buf
+    }
+}
+
+fn panic_sites(ctx: &AssistContext<'_>, body: &ast::BlockExpr) -> Vec<PanicSite> {
+    let mut sites = Vec::new();
+    walk_expr(&ast::Expr::BlockExpr(body.clone()), &mut |expr| match expr {
+        ast::Expr::MethodCallExpr(call) => sites.extend(unwrap_site(ctx, call)),
+        ast::Expr::MacroExpr(expr) if is_panic(ctx, &expr) => sites.push(PanicSite::Panic { expr }),
+        _ => (),
+    });
+    sites
+}
+
+fn unwrap_site(ctx: &AssistContext<'_>, call: ast::MethodCallExpr) -> Option<PanicSite> {
+    let arg_count = call.arg_list()?.args().count();
+    match (call.name_ref()?.text().as_str(), arg_count) {
+        ("unwrap", 0) | ("expect", 1) => (),
+        _ => return None,
+    }
+    let ty = ctx.sema.type_of_expr(&call.receiver()?)?.original;
+    match TryEnum::from_ty(&ctx.sema, &ty)? {
+        TryEnum::Result => {
+            let error_ty = ty.type_arguments().nth(1)?;
+            // Borrowed errors can't be stored in the function's error type without a lifetime.
+            if error_ty.is_unknown() || error_ty.is_reference() {
+                return None;
+            }
+            Some(PanicSite::Result { call, error_ty })
+        }
+        TryEnum::Option => Some(PanicSite::Option { call }),
+    }
+}
+
+fn is_panic(ctx: &AssistContext<'_>, expr: &ast::MacroExpr) -> bool {
+    let Some(mac) = expr.macro_call().and_then(|it| ctx.sema.resolve_macro_call(&it)) else {
+        return false;
+    };
+    let Some(scope) = ctx.sema.scope(expr.syntax()) else { return false };
+    let krate = mac.module(ctx.db()).krate();
+    mac.name(ctx.db()).to_smol_str() == "panic"
+        && FamousDefs(&ctx.sema, scope.krate()).builtin_crates().any(|it| it == krate)
+}
+
+fn is_anyhow_error(ctx: &AssistContext<'_>, ty: &hir::Type) -> bool {
+    ty.as_adt().map_or(false, |adt| {
+        adt.name(ctx.db()).to_smol_str() == "Error"
+            && adt
+                .module(ctx.db())
+                .krate()
+                .display_name(ctx.db())
+                .map_or(false, |it| it.to_string() == "anyhow")
+    })
+}
+
+/// Picks the error type for a function that doesn't return a `Result` yet: the error type of the
+/// converted `Result`s if they all share it, an enum generated for the function otherwise.
+fn new_error_target(
+    ctx: &AssistContext<'_>,
+    module: hir::Module,
+    fn_name: &str,
+    sites: &[PanicSite],
+) -> Option<ErrorTarget> {
+    let mut error_tys = Vec::new();
+    for site in sites {
+        if let PanicSite::Result { error_ty, .. } = site {
+            if !error_tys.contains(error_ty) {
+                error_tys.push(error_ty.clone());
+            }
+        }
+    }
+    let has_message = sites.iter().any(|it| !matches!(it, PanicSite::Result { .. }));
+    let display = |ty: &hir::Type| ty.display_source_code(ctx.db(), module.into(), false).ok();
+
+    if let ([ty], false) = (&*error_tys, has_message) {
+        return Some(ErrorTarget::Single { ty: ty.clone(), ty_text: display(ty)? });
+    }
+
+    let mut variants: Vec<ErrorVariant> = Vec::new();
+    for ty in error_tys {
+        let base = variant_name(ctx, &ty);
+        let mut name = base.clone();
+        let mut idx = 1;
+        while variants.iter().any(|it| it.name == name) || (has_message && name == "Message") {
+            idx += 1;
+            name = format!("{base}{idx}");
+        }
+        let ty_text = display(&ty)?;
+        variants.push(ErrorVariant { name, ty, ty_text });
+    }
+    let debug = FamousDefs(&ctx.sema, module.krate()).core_fmt_Debug();
+    let derive_debug = variants
+        .iter()
+        .all(|variant| debug.map_or(false, |debug| variant.ty.impls_trait(ctx.db(), debug, &[])));
+    let name = format!("{}Error", to_camel_case(fn_name));
+    Some(ErrorTarget::Enum(ErrorEnum { name, variants, has_message, derive_debug }))
+}
+
+/// `ParseIntError` becomes `ParseInt`, and `io::Error` becomes `Io`.
+fn variant_name(ctx: &AssistContext<'_>, ty: &hir::Type) -> String {
+    let Some(adt) = ty.as_adt() else { return "Other".to_owned() };
+    let name = adt.name(ctx.db()).to_smol_str();
+    match name.strip_suffix("Error") {
+        Some("") => adt
+            .module(ctx.db())
+            .name(ctx.db())
+            .map_or_else(|| "Other".to_owned(), |it| to_camel_case(&it.to_smol_str())),
+        Some(stripped) => stripped.to_owned(),
+        None => name.to_string(),
+    }
+}
+
+/// Returns the replacement of a site, if it can be converted into the target error type.
+/// `is_value` tells whether a `panic!` produces the value of the function.
+fn convert_site(
+    site: &PanicSite,
+    target: &ErrorTarget,
+    is_value: bool,
+) -> Option<(TextRange, String)> {
+    let (call, is_result) = match site {
+        PanicSite::Result { call, .. } => (call, true),
+        PanicSite::Option { call } => (call, false),
+        PanicSite::Panic { expr } => {
+            let text = match target {
+                ErrorTarget::Anyhow => format!("anyhow::bail!({})", panic_args(expr)?),
+                ErrorTarget::Single { .. } => return None,
+                ErrorTarget::Enum(error_enum) => {
+                    let ret = if is_value { "" } else { "return " };
+                    format!("{ret}Err({}::Message({}))", error_enum.name, panic_message(expr)?)
+                }
+            };
+            return Some((expr.syntax().text_range(), text));
+        }
+    };
+
+    // Only the `.unwrap()` or `.expect(..)` part of the call gets replaced, so that sites nested
+    // in the receiver don't overlap with this one.
+    let receiver = call.receiver()?;
+    let range =
+        TextRange::new(receiver.syntax().text_range().end(), call.syntax().text_range().end());
+    let msg = call.arg_list()?.args().next();
+    let text = match (target, site) {
+        (ErrorTarget::Anyhow, _) => match msg {
+            Some(msg) if is_string_literal(&msg) => format!(".context({msg})?"),
+            Some(msg) => format!(".with_context(|| String::from({msg}))?"),
+            None if is_result => "?".to_owned(),
+            None => format!(".context({})?", none_message(&receiver)),
+        },
+        (ErrorTarget::Single { ty, .. }, PanicSite::Result { error_ty, .. }) if ty == error_ty => {
+            "?".to_owned()
+        }
+        (ErrorTarget::Single { .. }, _) => return None,
+        (ErrorTarget::Enum(error_enum), PanicSite::Result { error_ty, .. }) => {
+            if !error_enum.variants.iter().any(|it| it.ty == *error_ty) {
+                return None;
+            }
+            "?".to_owned()
+        }
+        (ErrorTarget::Enum(error_enum), _) => {
+            let msg = match msg {
+                Some(msg) if is_string_literal(&msg) => format!("{msg}.to_owned()"),
+                Some(msg) => format!("String::from({msg})"),
+                None => format!("{}.to_owned()", none_message(&receiver)),
+            };
+            format!(".ok_or_else(|| {}::Message({msg}))?", error_enum.name)
+        }
+    };
+    Some((range, text))
+}
+
+fn is_string_literal(expr: &ast::Expr) -> bool {
+    matches!(expr, ast::Expr::Literal(lit) if matches!(lit.kind(), ast::LiteralKind::String(_)))
+}
+
+/// A string literal describing the `None` that `receiver.unwrap()` panics on.
+fn none_message(receiver: &ast::Expr) -> String {
+    let receiver = receiver.syntax().text().to_string();
+    if receiver.contains('\n') {
+        return "\"unexpected `None`\"".to_owned();
+    }
+    let receiver = receiver.replace('\\', "\\\\").replace('"', "\\\"");
+    format!("\"`{receiver}` is `None`\"")
+}
+
+/// The arguments of a `panic!`, defaulting to the message it panics with when there are none.
+fn panic_args(expr: &ast::MacroExpr) -> Option<String> {
+    let tt = expr.macro_call()?.token_tree()?;
+    let text = tt.syntax().text().to_string();
+    let args = text.get(1..text.len().saturating_sub(1))?.trim();
+    Some(if args.is_empty() { "\"explicit panic\"".to_owned() } else { args.to_owned() })
+}
+
+/// The message of a `panic!` as an expression of type `String`.
+fn panic_message(expr: &ast::MacroExpr) -> Option<String> {
+    let args = panic_args(expr)?;
+    let tt = expr.macro_call()?.token_tree()?;
+    let elements =
+        tt.syntax().children_with_tokens().filter(|it| !it.kind().is_trivia()).collect::<Vec<_>>();
+    // Skip the delimiters of the token tree.
+    let is_plain_literal = match elements.get(1..elements.len().saturating_sub(1)) {
+        Some([]) => true,
+        Some([NodeOrToken::Token(token)]) => {
+            token.kind() == SyntaxKind::STRING && !token.text().contains('{')
+        }
+        _ => false,
+    };
+    Some(if is_plain_literal { format!("{args}.to_owned()") } else { format!("format!({args})") })
+}
+
+/// Returns where to add the `Ok(())` ending a body that used to return `()`, unless the body
+/// already ends with an `Err` from a converted `panic!`.
+fn unit_ok_tail(body: &ast::BlockExpr, diverging: &[TextRange]) -> Option<(TextSize, String)> {
+    let stmt_list = body.stmt_list()?;
+    let (last, semicolon) = match stmt_list.tail_expr() {
+        Some(tail) => {
+            if diverging.contains(&tail.syntax().text_range()) {
+                return None;
+            }
+            (tail.syntax().clone(), if tail.is_block_like() { "" } else { ";" })
+        }
+        None => {
+            let last = stmt_list.statements().last()?;
+            if let ast::Stmt::ExprStmt(stmt) = &last {
+                if stmt.expr().map_or(false, |it| diverging.contains(&it.syntax().text_range())) {
+                    return None;
+                }
+            }
+            (last.syntax().clone(), "")
+        }
+    };
+    let ws = if stmt_list.syntax().text().contains_char('\n') {
+        format!("\n{}", IndentLevel::from_node(&last))
+    } else {
+        " ".to_owned()
+    };
+    Some((last.text_range().end(), format!("{semicolon}{ws}Ok(())")))
+}
+
+/// The item that the function is nested in, which is a direct child of a module or block.
+fn enclosing_module_item(fn_: &ast::Fn) -> Option<SyntaxNode> {
+    fn_.syntax().ancestors().filter(|it| ast::Item::can_cast(it.kind())).find(|it| {
+        it.parent().map_or(false, |parent| {
+            matches!(
+                parent.kind(),
+                SyntaxKind::SOURCE_FILE | SyntaxKind::ITEM_LIST | SyntaxKind::STMT_LIST
+            )
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{
+        check_assist, check_assist_not_applicable, check_assist_with_config, TEST_CONFIG,
+    };
+    use crate::AssistConfig;
+
+    use super::*;
+
+    #[test]
+    fn collects_error_types_into_enum() {
+        check_assist(
+            convert_panics_to_result,
+            r#"
+//- minicore: option, result, panic, derive
+mod io {
+    #[derive(Debug)]
+    pub struct Error;
+}
+#[derive(Debug)]
+struct ParseIntError;
+fn read() -> Result<i32, io::Error> { loop {} }
+fn parse(s: &str) -> Result<i32, ParseIntError> { loop {} }
+
+pub fn $0load() -> i32 {
+    let n = read().unwrap();
+    n + parse("1").expect("not a number")
+}
+"#,
+            r#"
+mod io {
+    #[derive(Debug)]
+    pub struct Error;
+}
+#[derive(Debug)]
+struct ParseIntError;
+fn read() -> Result<i32, io::Error> { loop {} }
+fn parse(s: &str) -> Result<i32, ParseIntError> { loop {} }
+
+pub fn load() -> Result<i32, LoadError> {
+    let n = read()?;
+    Ok(n + parse("1")?)
+}
+
+#[derive(Debug)]
+pub enum LoadError {
+    Io(io::Error),
+    ParseInt(ParseIntError),
+}
+
+impl From<io::Error> for LoadError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<ParseIntError> for LoadError {
+    fn from(err: ParseIntError) -> Self {
+        Self::ParseInt(err)
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn single_error_type_is_used_directly() {
+        check_assist(
+            convert_panics_to_result,
+            r#"
+//- minicore: option, result, panic
+struct ParseIntError;
+fn parse(s: &str) -> Result<i32, ParseIntError> { loop {} }
+
+fn $0run() {
+    let x = parse("1").unwrap();
+    if x > 0 {
+        return;
+    }
+    parse("2").unwrap();
+}
+"#,
+            r#"
+struct ParseIntError;
+fn parse(s: &str) -> Result<i32, ParseIntError> { loop {} }
+
+fn run() -> Result<(), ParseIntError> {
+    let x = parse("1")?;
+    if x > 0 {
+        return Ok(());
+    }
+    parse("2")?;
+    Ok(())
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn single_line_body() {
+        check_assist(
+            convert_panics_to_result,
+            r#"
+//- minicore: option, result, panic
+struct ParseIntError;
+fn parse(s: &str) -> Result<i32, ParseIntError> { loop {} }
+fn $0f() -> i32 { parse("1").unwrap() }
+"#,
+            r#"
+struct ParseIntError;
+fn parse(s: &str) -> Result<i32, ParseIntError> { loop {} }
+fn f() -> Result<i32, ParseIntError> { Ok(parse("1")?) }
+"#,
+        );
+    }
+
+    #[test]
+    fn panics_become_messages() {
+        check_assist(
+            convert_panics_to_result,
+            r#"
+//- minicore: option, result, panic
+fn $0pick(n: i32, fallback: Option<i32>) -> i32 {
+    if n < 0 {
+        panic!("negative: {}", n);
+    }
+    match n {
+        0 => panic!(),
+        1 => panic!("one"),
+        2 => fallback.unwrap(),
+        n => n,
+    }
+}
+"#,
+            r#"
+fn pick(n: i32, fallback: Option<i32>) -> Result<i32, PickError> {
+    if n < 0 {
+        return Err(PickError::Message(format!("negative: {}", n)));
+    }
+    match n {
+        0 => Err(PickError::Message("explicit panic".to_owned())),
+        1 => Err(PickError::Message("one".to_owned())),
+        2 => Ok(fallback.ok_or_else(|| PickError::Message("`fallback` is `None`".to_owned()))?),
+        n => Ok(n),
+    }
+}
+
+#[derive(Debug)]
+enum PickError {
+    Message(String),
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn unit_fn_ending_in_panic() {
+        check_assist(
+            convert_panics_to_result,
+            r#"
+//- minicore: option, result, panic
+fn $0fail(a: Option<i32>) {
+    a.expect("no value");
+    panic!("failed");
+}
+"#,
+            r#"
+fn fail(a: Option<i32>) -> Result<(), FailError> {
+    a.ok_or_else(|| FailError::Message("no value".to_owned()))?;
+    return Err(FailError::Message("failed".to_owned()));
+}
+
+#[derive(Debug)]
+enum FailError {
+    Message(String),
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn enum_is_declared_after_impl() {
+        check_assist(
+            convert_panics_to_result,
+            r#"
+//- minicore: option, result, panic
+struct S;
+impl S {
+    pub(crate) fn $0get(&self, a: Option<i32>) -> i32 {
+        a.unwrap()
+    }
+}
+"#,
+            r#"
+struct S;
+impl S {
+    pub(crate) fn get(&self, a: Option<i32>) -> Result<i32, GetError> {
+        Ok(a.ok_or_else(|| GetError::Message("`a` is `None`".to_owned()))?)
+    }
+}
+
+#[derive(Debug)]
+pub(crate) enum GetError {
+    Message(String),
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn anyhow_error_type() {
+        check_assist_with_config(
+            convert_panics_to_result,
+            AssistConfig { panic_error_type: PanicErrorType::Anyhow, ..TEST_CONFIG },
+            r#"
+//- minicore: option, result, panic
+struct ParseIntError;
+fn parse(s: &str) -> Result<i32, ParseIntError> { loop {} }
+
+fn $0load(a: Option<i32>) -> i32 {
+    let a = a.unwrap();
+    if a < 0 {
+        panic!("negative: {a}");
+    }
+    parse("1").expect("bad input") + parse("2").unwrap()
+}
+"#,
+            r#"
+use anyhow::Context;
+
+struct ParseIntError;
+fn parse(s: &str) -> Result<i32, ParseIntError> { loop {} }
+
+fn load(a: Option<i32>) -> anyhow::Result<i32> {
+    let a = a.context("`a` is `None`")?;
+    if a < 0 {
+        anyhow::bail!("negative: {a}");
+    }
+    Ok(parse("1").context("bad input")? + parse("2")?)
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn existing_result_keeps_signature() {
+        check_assist(
+            convert_panics_to_result,
+            r#"
+//- minicore: option, result, panic
+struct ParseIntError;
+fn parse(s: &str) -> Result<i32, ParseIntError> { loop {} }
+
+fn $0f(b: Option<i32>) -> Result<i32, ParseIntError> {
+    let a = parse("1").unwrap();
+    Ok(a + b.unwrap())
+}
+"#,
+            r#"
+struct ParseIntError;
+fn parse(s: &str) -> Result<i32, ParseIntError> { loop {} }
+
+fn f(b: Option<i32>) -> Result<i32, ParseIntError> {
+    let a = parse("1")?;
+    Ok(a + b.unwrap())
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn converts_selection_only() {
+        check_assist(
+            convert_panics_to_result,
+            r#"
+//- minicore: option, result, panic
+struct ParseIntError;
+fn parse(s: &str) -> Result<i32, ParseIntError> { loop {} }
+
+fn f() {
+    $0parse("1").unwrap();$0
+    parse("2").unwrap();
+}
+"#,
+            r#"
+struct ParseIntError;
+fn parse(s: &str) -> Result<i32, ParseIntError> { loop {} }
+
+fn f() -> Result<(), ParseIntError> {
+    parse("1")?;
+    parse("2").unwrap();
+    Ok(())
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn updates_callers() {
+        check_assist_with_config(
+            convert_panics_to_result,
+            AssistConfig { wrap_return_type_callers: WrapReturnTypeCallers::Try, ..TEST_CONFIG },
+            r#"
+//- minicore: option, result, panic
+struct ParseIntError;
+fn parse(s: &str) -> Result<i32, ParseIntError> { loop {} }
+
+fn $0f() -> i32 {
+    parse("1").unwrap()
+}
+
+fn g() -> Result<i32, ParseIntError> {
+    Ok(f() + 1)
+}
+
+fn h() {
+    f();
+}
+"#,
+            r#"
+struct ParseIntError;
+fn parse(s: &str) -> Result<i32, ParseIntError> { loop {} }
+
+fn f() -> Result<i32, ParseIntError> {
+    Ok(parse("1")?)
+}
+
+fn g() -> Result<i32, ParseIntError> {
+    Ok(f()? + 1)
+}
+
+fn h() {
+    f().unwrap();
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn no_debug_derive_without_debug_variants() {
+        check_assist(
+            convert_panics_to_result,
+            r#"
+//- minicore: option, result, panic
+struct ParseIntError;
+fn parse(s: &str) -> Result<i32, ParseIntError> { loop {} }
+
+fn $0f(a: Option<i32>) -> i32 {
+    a.unwrap() + parse("1").unwrap()
+}
+"#,
+            r#"
+struct ParseIntError;
+fn parse(s: &str) -> Result<i32, ParseIntError> { loop {} }
+
+fn f(a: Option<i32>) -> Result<i32, FError> {
+    Ok(a.ok_or_else(|| FError::Message("`a` is `None`".to_owned()))? + parse("1")?)
+}
+
+enum FError {
+    ParseInt(ParseIntError),
+    Message(String),
+}
+
+impl From<ParseIntError> for FError {
+    fn from(err: ParseIntError) -> Self {
+        Self::ParseInt(err)
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_in_trait_impl() {
+        check_assist_not_applicable(
+            convert_panics_to_result,
+            r#"
+//- minicore: option, result, panic
+trait Get {
+    fn get(&self, a: Option<i32>) -> i32;
+}
+struct S;
+impl Get for S {
+    fn $0get(&self, a: Option<i32>) -> i32 {
+        a.unwrap()
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn skips_closures() {
+        check_assist_not_applicable(
+            convert_panics_to_result,
+            r#"
+//- minicore: option, result, panic
+fn $0f(a: Option<i32>) {
+    let g = || a.unwrap();
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_in_body_without_selection() {
+        check_assist_not_applicable(
+            convert_panics_to_result,
+            r#"
+//- minicore: option, result, panic
+fn f(a: Option<i32>) -> i32 {
+    a.unw$0rap()
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_option_return_type() {
+        check_assist_not_applicable(
+            convert_panics_to_result,
+            r#"
+//- minicore: option, result, panic
+fn $0f(a: Option<i32>) -> Option<i32> {
+    Some(a.unwrap())
+}
+"#,
+        );
+    }
+}
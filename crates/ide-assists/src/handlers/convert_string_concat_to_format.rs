@@ -0,0 +1,286 @@
+use ide_db::famous_defs::FamousDefs;
+use syntax::{
+    ast::{self, ArithOp, BinaryOp, IsString},
+    AstNode, AstToken,
+};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: convert_string_concat_to_format
+//
+// Replaces a chain of `String` concatenations with a single `format!` call.
+//
+// ```
+// # //- minicore: add
+// # //- /main.rs crate:main deps:alloc
+// # use alloc::string::{String, ToString};
+// fn greet(name: &str) -> String {
+//     "Hello, ".to_string() +$0 name + "!"
+// }
+// # //- /alloc.rs crate:alloc
+// # pub mod string {
+// #     pub struct String;
+// #     impl core::ops::Add<&str> for String {
+// #         type Output = String;
+// #         fn add(self, _: &str) -> String { self }
+// #     }
+// #     pub trait ToString { fn to_string(&self) -> String; }
+// #     impl ToString for str { fn to_string(&self) -> String { String } }
+// # }
+// ```
+// ->
+// ```
+// # use alloc::string::{String, ToString};
+// fn greet(name: &str) -> String {
+//     format!("Hello, {name}!")
+// }
+// ```
+pub(crate) fn convert_string_concat_to_format(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let mut concat = ctx.find_node_at_offset::<ast::BinExpr>().filter(is_add)?;
+    // `a + b + c` parses as `(a + b) + c`, find the outermost addition of the chain.
+    while let Some(parent) = concat.syntax().parent().and_then(ast::BinExpr::cast).filter(is_add) {
+        if parent.lhs().map_or(true, |it| it.syntax() != concat.syntax()) {
+            break;
+        }
+        concat = parent;
+    }
+
+    let mut operands = Vec::new();
+    let mut current = concat.clone();
+    loop {
+        operands.push(current.rhs()?);
+        match current.lhs()? {
+            ast::Expr::BinExpr(lhs) if is_add(&lhs) => current = lhs,
+            lhs => {
+                operands.push(lhs);
+                break;
+            }
+        }
+    }
+    operands.reverse();
+
+    // Only `String` implements `Add<&str>`, so checking the first operand is enough.
+    let krate = ctx.sema.scope(concat.syntax())?.krate();
+    let string = FamousDefs(&ctx.sema, krate).alloc_string_String()?;
+    let first_ty = ctx.sema.type_of_expr(&operands[0])?.adjusted();
+    if first_ty.as_adt() != Some(hir::Adt::Struct(string)) {
+        return None;
+    }
+
+    let mut template = String::new();
+    let mut args = Vec::new();
+    for operand in &operands {
+        let operand = match operand {
+            ast::Expr::RefExpr(it) if it.mut_token().is_none() => it.expr()?,
+            it => it.clone(),
+        };
+        if let Some(literal) = string_literal(&operand) {
+            template.push_str(&template_text(&literal)?);
+            continue;
+        }
+        match &operand {
+            ast::Expr::PathExpr(it) if is_identifier(it) => {
+                template.push_str(&format!("{{{operand}}}"));
+            }
+            _ => {
+                template.push_str("{}");
+                args.push(operand.to_string());
+            }
+        }
+    }
+    let args: String = args.iter().map(|arg| format!(", {arg}")).collect();
+
+    acc.add(
+        AssistId("convert_string_concat_to_format", AssistKind::RefactorRewrite),
+        "Convert to `format!`",
+        concat.syntax().text_range(),
+        |builder| {
+            builder.replace(concat.syntax().text_range(), format!("format!(\"{template}\"{args})"))
+        },
+    )
+}
+
+fn is_add(expr: &ast::BinExpr) -> bool {
+    expr.op_kind() == Some(BinaryOp::ArithOp(ArithOp::Add))
+}
+
+/// Whether the path is a single identifier, which `format!` can capture directly. Raw
+/// identifiers can't be captured.
+fn is_identifier(path_expr: &ast::PathExpr) -> bool {
+    let Some(path) = path_expr.path() else { return false };
+    let Some(segment) = path.segment() else { return false };
+    path.qualifier().is_none()
+        && segment.generic_arg_list().is_none()
+        && segment
+            .name_ref()
+            .and_then(|it| it.ident_token())
+            .map_or(false, |it| !it.text().starts_with("r#"))
+}
+
+/// Returns the string literal of `"lit"`, `"lit".to_string()`, `"lit".to_owned()`,
+/// `"lit".into()` and `String::from("lit")`.
+fn string_literal(expr: &ast::Expr) -> Option<ast::String> {
+    let literal = match expr {
+        ast::Expr::Literal(it) => it.clone(),
+        ast::Expr::MethodCallExpr(call) => {
+            let method = call.name_ref()?;
+            if !matches!(method.text().as_str(), "to_string" | "to_owned" | "into")
+                || call.arg_list()?.args().next().is_some()
+            {
+                return None;
+            }
+            match call.receiver()? {
+                ast::Expr::Literal(it) => it,
+                _ => return None,
+            }
+        }
+        ast::Expr::CallExpr(call) => {
+            let ast::Expr::PathExpr(callee) = call.expr()? else { return None };
+            let path = callee.path()?;
+            if path.segment()?.name_ref()?.text() != "from"
+                || path.qualifier()?.segment()?.name_ref()?.text() != "String"
+            {
+                return None;
+            }
+            let mut args = call.arg_list()?.args();
+            let (Some(ast::Expr::Literal(it)), None) = (args.next(), args.next()) else {
+                return None;
+            };
+            it
+        }
+        _ => return None,
+    };
+    match literal.kind() {
+        ast::LiteralKind::String(it) => Some(it),
+        _ => None,
+    }
+}
+
+/// The literal's contents as a part of a (non-raw) format string.
+fn template_text(literal: &ast::String) -> Option<String> {
+    let text = if literal.is_raw() {
+        let mut escaped = String::new();
+        for c in literal.value()?.chars() {
+            match c {
+                '"' | '\\' => {
+                    escaped.push('\\');
+                    escaped.push(c);
+                }
+                c => escaped.push(c),
+            }
+        }
+        escaped
+    } else {
+        literal.text_without_quotes().to_owned()
+    };
+    Some(text.replace('{', "{{").replace('}', "}}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    const PRELUDE: &str = r#"
+use alloc::string::{String, ToString};
+fn label() -> &'static str { "" }
+"#;
+
+    const ALLOC: &str = r#"//- /alloc.rs crate:alloc
+pub mod string {
+    pub struct String;
+    impl String { pub fn from(s: &str) -> String { String } }
+    impl core::ops::Add<&str> for String {
+        type Output = String;
+        fn add(self, _: &str) -> String { self }
+    }
+    pub trait ToString { fn to_string(&self) -> String; }
+    impl ToString for str { fn to_string(&self) -> String { String } }
+}
+"#;
+
+    fn check(before: &str, after: &str) {
+        check_assist(
+            convert_string_concat_to_format,
+            &format!(
+                "//- minicore: add\n//- /main.rs crate:main deps:alloc{PRELUDE}{before}{ALLOC}"
+            ),
+            &format!("{PRELUDE}{after}"),
+        );
+    }
+
+    #[test]
+    fn escapes_braces_and_extracts_expressions() {
+        check(
+            r##"
+fn f(id: &str) {
+    let s = String::from("{") + id +$0 r#"} "raw" \"# + label();
+}
+"##,
+            r#"
+fn f(id: &str) {
+    let s = format!("{{{id}}} \"raw\" \\{}", label());
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn outermost_chain_from_inner_operator() {
+        check(
+            r#"
+fn f(a: &str, b: &str) {
+    let s = "x".to_string() $0+ a + "\n" + b;
+}
+"#,
+            r#"
+fn f(a: &str, b: &str) {
+    let s = format!("x{a}\n{b}");
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn raw_identifiers_are_positional() {
+        check(
+            r#"
+fn f(r#type: &str) {
+    let s = "type: ".to_string() +$0 r#type;
+}
+"#,
+            r#"
+fn f(r#type: &str) {
+    let s = format!("type: {}", r#type);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_to_other_types() {
+        check_assist_not_applicable(
+            convert_string_concat_to_format,
+            r#"
+//- minicore: add
+struct String;
+impl core::ops::Add<&str> for String {
+    type Output = String;
+    fn add(self, _: &str) -> String { self }
+}
+fn f(s: String) {
+    let s = s +$0 "!";
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_to_numbers() {
+        check_assist_not_applicable(convert_string_concat_to_format, "fn f() { 1 +$0 2; }");
+    }
+}
@@ -0,0 +1,379 @@
+use std::iter;
+
+use hir::{HasVisibility, ModuleDef, ScopeDef, StructKind};
+use ide_db::{
+    defs::Definition,
+    helpers::mod_path_to_ast,
+    search::{FileReference, SearchScope},
+    FxHashSet,
+};
+use syntax::{
+    ast::{self, make, AstNode, HasName},
+    ted, SmolStr, T,
+};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: destructure_struct_binding
+//
+// Destructures a struct binding in place, replacing accesses to its fields with the new bindings.
+//
+// ```
+// struct Foo { bar: i32, baz: i32 }
+//
+// fn main() {
+//     let $0foo = Foo { bar: 1, baz: 2 };
+//     let bar2 = foo.bar * 2;
+//     let baz2 = foo.baz;
+// }
+// ```
+// ->
+// ```
+// struct Foo { bar: i32, baz: i32 }
+//
+// fn main() {
+//     let Foo { $0bar, baz } = Foo { bar: 1, baz: 2 };
+//     let bar2 = bar * 2;
+//     let baz2 = baz;
+// }
+// ```
+pub(crate) fn destructure_struct_binding(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    let ident_pat = ctx.find_node_at_offset::<ast::IdentPat>()?;
+    if ident_pat.at_token().is_some() {
+        return None;
+    }
+    // `Bar { foo }` uses the binding's name as the field name as well.
+    if let Some(field) = ident_pat.syntax().parent().and_then(ast::RecordPatField::cast) {
+        field.colon_token()?;
+    }
+    let name = ident_pat.name()?;
+
+    let db = ctx.db();
+    let ty = ctx.sema.type_of_binding_in_pat(&ident_pat)?;
+    let is_ref = ty.is_reference();
+    let hir::Adt::Struct(strukt) = ty.strip_references().as_adt()? else { return None };
+    let kind = strukt.kind(db);
+    if kind == StructKind::Unit {
+        return None;
+    }
+
+    let module = ctx.sema.scope(ident_pat.syntax())?.module();
+    let fields = strukt.fields(db);
+    if fields.is_empty() || fields.iter().any(|field| !field.is_visible_from(db, module)) {
+        cov_mark::hit!(destructure_struct_binding_private_fields);
+        return None;
+    }
+    let struct_path = mod_path_to_ast(&module.find_use_path(
+        db,
+        ModuleDef::Adt(strukt.into()),
+        ctx.config.prefer_no_std,
+        ctx.config.prefer_prelude,
+    )?);
+
+    let field_names = fields.iter().map(|field| field.name(db).to_smol_str()).collect::<Vec<_>>();
+    let usages = match ctx.sema.to_def(&ident_pat) {
+        Some(local) => Definition::Local(local)
+            .usages(&ctx.sema)
+            .in_scope(&SearchScope::single_file(ctx.file_id()))
+            .all()
+            .into_iter()
+            .flat_map(|(_, refs)| refs)
+            .map(|usage| field_access(&usage, &field_names))
+            .collect::<Option<Vec<_>>>(),
+        None => Some(Vec::new()),
+    };
+    // Any use of the whole value would be left dangling by the destructuring.
+    let Some(usages) = usages else {
+        cov_mark::hit!(destructure_struct_binding_whole_usage);
+        return None;
+    };
+
+    let new_names = match kind {
+        StructKind::Record => {
+            // The new bindings can't shadow the locals visible where the struct is bound, nor be
+            // shadowed by the ones declared before any of the usages.
+            let mut names_in_scope = FxHashSet::default();
+            let nodes = iter::once(ident_pat.syntax().clone())
+                .chain(usages.iter().map(|(_, field_expr)| field_expr.syntax().clone()));
+            for node in nodes {
+                ctx.sema.scope(&node)?.process_all_names(&mut |name, def| {
+                    if let ScopeDef::Local(_) = def {
+                        names_in_scope.insert(name.to_smol_str());
+                    }
+                });
+            }
+            field_names
+                .iter()
+                .map(|field| match names_in_scope.contains(field) {
+                    true => format!("{name}_{field}"),
+                    false => field.to_string(),
+                })
+                .collect::<Vec<_>>()
+        }
+        _ => (0..field_names.len()).map(|idx| format!("_{idx}")).collect(),
+    };
+
+    acc.add(
+        AssistId("destructure_struct_binding", AssistKind::RefactorRewrite),
+        "Destructure struct binding",
+        ident_pat.syntax().text_range(),
+        |builder| {
+            let usages = usages
+                .into_iter()
+                .map(|(idx, field_expr)| {
+                    let (target, needs_deref) = usage_target(&field_expr, is_ref);
+                    (idx, builder.make_mut(target), needs_deref)
+                })
+                .collect::<Vec<_>>();
+            let ident_pat = builder.make_mut(ident_pat.clone());
+
+            let by_ref = ident_pat.ref_token().is_some();
+            // `mut` would reset the binding mode, moving out of the borrowed value.
+            let by_mut = ident_pat.mut_token().is_some() && !is_ref;
+            let field_pats = new_names
+                .iter()
+                .map(|name| make::ident_pat(by_ref, by_mut, make::name(name)))
+                .collect::<Vec<_>>();
+            let pat: ast::Pat = match kind {
+                StructKind::Record => {
+                    let fields = field_names
+                        .iter()
+                        .zip(field_pats)
+                        .map(|(field, pat)| record_pat_field(&struct_path, field, pat));
+                    make::record_pat_with_fields(struct_path, make::record_pat_field_list(fields))
+                        .into()
+                }
+                _ => make::tuple_struct_pat(struct_path, field_pats.into_iter().map(Into::into))
+                    .into(),
+            };
+            let pat = pat.clone_for_update();
+
+            if let Some(cap) = ctx.config.snippet_cap {
+                // place cursor on the first field's binding
+                let first_name = pat
+                    .syntax()
+                    .descendants()
+                    .find_map(ast::IdentPat::cast)
+                    .and_then(|it| it.name());
+                if let Some(name) = first_name {
+                    builder.add_tabstop_before(cap, name);
+                }
+            }
+
+            for (idx, target, needs_deref) in usages {
+                let mut expr = make::expr_path(make::ext::ident_path(&new_names[idx]));
+                if needs_deref {
+                    expr = make::expr_prefix(T![*], expr);
+                }
+                ted::replace(target.syntax(), expr.clone_for_update().syntax());
+            }
+            ted::replace(ident_pat.syntax(), pat.syntax());
+        },
+    )
+}
+
+/// Returns the index of the field accessed by `usage` together with the accessing expression, or
+/// `None` if the binding is used as a whole.
+fn field_access(usage: &FileReference, field_names: &[SmolStr]) -> Option<(usize, ast::FieldExpr)> {
+    let name_ref = usage.name.as_name_ref()?;
+    // NAME_REF -> PATH_SEGMENT -> PATH -> PATH_EXPR
+    let path_expr = name_ref.syntax().ancestors().nth(3).and_then(ast::PathExpr::cast)?;
+    let field_expr = path_expr
+        .syntax()
+        .ancestors()
+        .skip(1)
+        .find(|it| !ast::ParenExpr::can_cast(it.kind()))
+        .and_then(ast::FieldExpr::cast)?;
+    // Accesses inside of macro calls can't be told apart from the ones the macro produces.
+    if field_expr.syntax().ancestors().any(|it| ast::MacroStmts::can_cast(it.kind())) {
+        return None;
+    }
+    let field = field_expr.name_ref()?;
+    let idx = field_names.iter().position(|it| *it == field.text().as_str())?;
+    Some((idx, field_expr))
+}
+
+/// Returns the expression to replace for a field access and whether the new binding has to be
+/// dereferenced in its place.
+fn usage_target(field_expr: &ast::FieldExpr, is_ref: bool) -> (ast::Expr, bool) {
+    if !is_ref {
+        return (field_expr.clone().into(), false);
+    }
+    match field_expr.syntax().parent().and_then(ast::Expr::cast) {
+        // `&foo.bar` is just the new binding.
+        Some(ast::Expr::RefExpr(it)) if it.mut_token().is_none() => (it.into(), false),
+        // Field accesses and method calls auto-deref.
+        Some(ast::Expr::FieldExpr(_) | ast::Expr::MethodCallExpr(_)) => {
+            (field_expr.clone().into(), false)
+        }
+        _ => (field_expr.clone().into(), true),
+    }
+}
+
+fn record_pat_field(path: &ast::Path, field: &str, pat: ast::IdentPat) -> ast::RecordPatField {
+    if pat.name().map_or(true, |it| it.text() != field) {
+        return make::record_pat_field(make::name_ref(field), pat.into());
+    }
+    // Shorthands with binding modes like `S { mut x }` only exist as part of a whole pattern.
+    make::record_pat(path.clone(), [pat.into()])
+        .record_pat_field_list()
+        .and_then(|it| it.fields().next())
+        .expect("record pattern should have a field")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn record_struct() {
+        check_assist(
+            destructure_struct_binding,
+            r#"
+struct Foo { bar: i32, baz: i32 }
+
+fn main() {
+    let $0foo = Foo { bar: 1, baz: 2 };
+    let bar2 = foo.bar;
+    let baz2 = &(foo.baz);
+}
+"#,
+            r#"
+struct Foo { bar: i32, baz: i32 }
+
+fn main() {
+    let Foo { $0bar, baz } = Foo { bar: 1, baz: 2 };
+    let bar2 = bar;
+    let baz2 = &(baz);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn tuple_struct_in_param() {
+        check_assist(
+            destructure_struct_binding,
+            r#"
+mod m { pub struct Pair(pub u8, pub u8); }
+
+fn sum($0pair: m::Pair) -> u8 {
+    pair.0 + pair.1
+}
+"#,
+            r#"
+mod m { pub struct Pair(pub u8, pub u8); }
+
+fn sum(m::Pair($0_0, _1): m::Pair) -> u8 {
+    _0 + _1
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn mutable_binding_with_name_clash() {
+        check_assist(
+            destructure_struct_binding,
+            r#"
+struct Foo { bar: i32, baz: i32 }
+
+fn f(bar: i32) {
+    let mut $0foo = Foo { bar, baz: 2 };
+    foo.baz += bar;
+}
+"#,
+            r#"
+struct Foo { bar: i32, baz: i32 }
+
+fn f(bar: i32) {
+    let Foo { bar: mut $0foo_bar, mut baz } = Foo { bar, baz: 2 };
+    baz += bar;
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn name_clash_with_later_binding() {
+        check_assist(
+            destructure_struct_binding,
+            r#"
+struct Foo { bar: i32, baz: i32 }
+
+fn f() {
+    let $0foo = Foo { bar: 1, baz: 2 };
+    let bar = 3;
+    let sum = foo.bar + bar + foo.baz;
+}
+"#,
+            r#"
+struct Foo { bar: i32, baz: i32 }
+
+fn f() {
+    let Foo { bar: $0foo_bar, baz } = Foo { bar: 1, baz: 2 };
+    let bar = 3;
+    let sum = foo_bar + bar + baz;
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn reference_binding() {
+        check_assist(
+            destructure_struct_binding,
+            r#"
+struct Foo { bar: i32, baz: Vec<i32> }
+
+fn f($0foo: &Foo) -> usize {
+    let x = foo.bar + 1;
+    let y = &foo.bar;
+    foo.baz.len()
+}
+"#,
+            r#"
+struct Foo { bar: i32, baz: Vec<i32> }
+
+fn f(Foo { $0bar, baz }: &Foo) -> usize {
+    let x = *bar + 1;
+    let y = bar;
+    baz.len()
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_used_as_a_whole() {
+        cov_mark::check!(destructure_struct_binding_whole_usage);
+        check_assist_not_applicable(
+            destructure_struct_binding,
+            r#"
+struct Foo { bar: i32 }
+
+fn f() {
+    let $0foo = Foo { bar: 1 };
+    drop(foo);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_with_private_fields() {
+        cov_mark::check!(destructure_struct_binding_private_fields);
+        check_assist_not_applicable(
+            destructure_struct_binding,
+            r#"
+mod m { pub struct Foo { pub bar: i32, baz: i32 } }
+
+fn f($0foo: m::Foo) -> i32 {
+    foo.bar
+}
+"#,
+        );
+    }
+}
@@ -0,0 +1,280 @@
+use hir::PathResolution;
+use ide_db::famous_defs::FamousDefs;
+use syntax::{
+    ast::{self, edit::IndentLevel, make, AstNode, HasGenericParams, HasName},
+    TextRange,
+};
+
+use crate::{utils::suggest_name, AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: extract_generic_param
+//
+// Replaces the concrete type of a function parameter with a new generic parameter that converts
+// into it, `Into<T>` for owned types and `AsRef<T>` for references to `str`, slices, `Path` and
+// `OsStr`, converting the argument back at the start of the body.
+//
+// ```
+// fn greet(name: $0String) -> usize {
+//     name.len()
+// }
+// ```
+// ->
+// ```
+// fn greet<S: Into<String>>(name: S) -> usize {
+//     let name: String = name.into();
+//     name.len()
+// }
+// ```
+pub(crate) fn extract_generic_param(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    let param = ctx.find_node_at_offset::<ast::Param>()?;
+    let ty = param.ty()?;
+    if !ty.syntax().text_range().contains_range(ctx.selection_trimmed()) {
+        return None;
+    }
+    let ast::Pat::IdentPat(pat) = param.pat()? else { return None };
+    if pat.ref_token().is_some() || pat.pat().is_some() {
+        return None;
+    }
+    let param_name = pat.name()?;
+    // PARAM -> PARAM_LIST -> FN, closures can't have generic parameters.
+    let fn_ = param.syntax().parent()?.parent().and_then(ast::Fn::cast)?;
+    // Trait items and their impls have to keep the signature of the trait.
+    if fn_.syntax().parent().and_then(ast::AssocItemList::cast).is_some_and(|it| {
+        it.syntax().parent().and_then(ast::Impl::cast).map_or(true, |imp| imp.trait_().is_some())
+    }) {
+        cov_mark::hit!(extract_generic_param_trait_fn);
+        return None;
+    }
+    let stmt_list = fn_.body()?.stmt_list()?;
+
+    let (bound, converted_ty, conversion) = match &ty {
+        ast::Type::RefType(ref_type)
+            if ref_type.mut_token().is_none() && ref_type.lifetime().is_none() =>
+        {
+            // The converted reference borrows from a local, so it can't be returned.
+            let ret_ty = fn_.ret_type().and_then(|it| it.ty());
+            if ret_ty.is_some_and(|it| {
+                it.syntax().descendants().any(|it| {
+                    ast::RefType::can_cast(it.kind()) || ast::Lifetime::can_cast(it.kind())
+                })
+            }) {
+                cov_mark::hit!(extract_generic_param_returns_borrow);
+                return None;
+            }
+            let inner = ref_type.ty()?;
+            if !is_as_ref_of_itself(ctx, &inner) {
+                cov_mark::hit!(extract_generic_param_as_ref_not_reflexive);
+                return None;
+            }
+            (format!("AsRef<{inner}>"), ty.to_string(), "as_ref")
+        }
+        ast::Type::PathType(path_type) => {
+            // Already generic.
+            if let Some(PathResolution::TypeParam(_)) = ctx.sema.resolve_path(&path_type.path()?) {
+                return None;
+            }
+            (format!("Into<{ty}>"), ty.to_string(), "into")
+        }
+        _ => return None,
+    };
+    if ty.syntax().descendants().any(|it| ast::ImplTraitType::can_cast(it.kind())) {
+        return None;
+    }
+
+    let first_char = ty
+        .syntax()
+        .descendants()
+        .filter_map(ast::NameRef::cast)
+        .last()
+        .and_then(|it| it.text().chars().next())
+        .map_or('T', |it| it.to_ascii_uppercase());
+    let existing_params =
+        fn_.generic_param_list().unwrap_or_else(|| make::generic_param_list(None));
+    let type_param_name = suggest_name::for_unique_generic_name(
+        first_char.encode_utf8(&mut [0; 4]),
+        &existing_params,
+    );
+
+    acc.add(
+        AssistId("extract_generic_param", AssistKind::RefactorExtract),
+        format!("Extract `{type_param_name}: {bound}` generic parameter"),
+        ty.syntax().text_range(),
+        |builder| {
+            let type_param = format!("{type_param_name}: {bound}");
+            match fn_.generic_param_list() {
+                Some(list) => match list.generic_params().last() {
+                    Some(last) => {
+                        builder.insert(last.syntax().text_range().end(), format!(", {type_param}"))
+                    }
+                    None => builder.replace(list.syntax().text_range(), format!("<{type_param}>")),
+                },
+                None => {
+                    if let Some(name) = fn_.name() {
+                        builder.insert(name.syntax().text_range().end(), format!("<{type_param}>"));
+                    }
+                }
+            }
+
+            builder.replace(ty.syntax().text_range(), type_param_name.to_string());
+            // The binding's mutability moves to the converted value.
+            if let Some(mut_token) = pat.mut_token() {
+                let end = param_name.syntax().text_range().start();
+                builder.delete(TextRange::new(mut_token.text_range().start(), end));
+            }
+
+            let mut_ = if pat.mut_token().is_some() { "mut " } else { "" };
+            let let_stmt =
+                format!("let {mut_}{param_name}: {converted_ty} = {param_name}.{conversion}();");
+            let indent = IndentLevel::from_node(fn_.syntax());
+            let (Some(l_curly), Some(r_curly)) =
+                (stmt_list.l_curly_token(), stmt_list.r_curly_token())
+            else {
+                return;
+            };
+            if stmt_list.syntax().text().contains_char('\n') {
+                builder.insert(l_curly.text_range().end(), format!("\n{}{let_stmt}", indent + 1));
+            } else {
+                // Single line bodies get spread out over multiple lines.
+                let inner =
+                    TextRange::new(l_curly.text_range().end(), r_curly.text_range().start());
+                let text = stmt_list.syntax().to_string();
+                let content = text[1..text.len() - 1].trim();
+                let content = if content.is_empty() {
+                    String::new()
+                } else {
+                    format!("\n{}{content}", indent + 1)
+                };
+                builder.replace(inner, format!("\n{}{let_stmt}{content}\n{indent}", indent + 1));
+            }
+        },
+    )
+}
+
+/// Whether `ty` is `AsRef<ty>`, which the standard library only implements for its unsized
+/// types, making `&ty` one as well.
+fn is_as_ref_of_itself(ctx: &AssistContext<'_>, ty: &ast::Type) -> bool {
+    let Some(scope) = ctx.sema.scope(ty.syntax()) else { return false };
+    let Some(ty) = ctx.sema.resolve_type(ty) else { return false };
+    if ty.is_slice() || ty.as_builtin().is_some_and(|it| it.is_str()) {
+        return true;
+    }
+    let Some(hir::Adt::Struct(strukt)) = ty.as_adt() else { return false };
+    let famous_defs = FamousDefs(&ctx.sema, scope.krate());
+    [famous_defs.std_path_Path(), famous_defs.std_ffi_OsStr()].contains(&Some(strukt))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn owned_type_with_existing_generics() {
+        check_assist(
+            extract_generic_param,
+            r#"
+struct Path;
+struct PathBuf;
+fn open<P>(p: P, mut target: PathBuf$0) {
+    drop(target);
+}
+"#,
+            r#"
+struct Path;
+struct PathBuf;
+fn open<P, P0: Into<PathBuf>>(p: P, target: P0) {
+    let mut target: PathBuf = target.into();
+    drop(target);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn reference_in_single_line_body() {
+        check_assist(
+            extract_generic_param,
+            r#"
+fn len(s: &$0str) -> usize { s.len() }
+"#,
+            r#"
+fn len<S: AsRef<str>>(s: S) -> usize {
+    let s: &str = s.as_ref();
+    s.len()
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn reference_to_std_path() {
+        check_assist(
+            extract_generic_param,
+            r#"
+//- /main.rs crate:main deps:std
+use std::path::Path;
+fn exists(p: &$0Path) -> bool {
+    check(p)
+}
+//- /std.rs crate:std
+pub mod path {
+    pub struct Path;
+}
+"#,
+            r#"
+use std::path::Path;
+fn exists<P: AsRef<Path>>(p: P) -> bool {
+    let p: &Path = p.as_ref();
+    check(p)
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_references_to_other_types() {
+        cov_mark::check!(extract_generic_param_as_ref_not_reflexive);
+        check_assist_not_applicable(
+            extract_generic_param,
+            r#"
+struct Config;
+fn load(config: &$0Config) {}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_outside_of_concrete_param_types() {
+        check_assist_not_applicable(extract_generic_param, r#"fn f<T>(t: T$0) {}"#);
+        check_assist_not_applicable(extract_generic_param, r#"fn f(t$0: String) {}"#);
+        check_assist_not_applicable(extract_generic_param, r#"fn f() { |t: $0String| t; }"#);
+        check_assist_not_applicable(extract_generic_param, r#"fn f(t: &mut $0String) {}"#);
+    }
+
+    #[test]
+    fn not_applicable_in_trait_impls() {
+        cov_mark::check!(extract_generic_param_trait_fn);
+        check_assist_not_applicable(
+            extract_generic_param,
+            r#"
+trait Greet { fn greet(&self, name: String); }
+struct S;
+impl Greet for S {
+    fn greet(&self, name: $0String) {}
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_returning_a_borrow() {
+        cov_mark::check!(extract_generic_param_returns_borrow);
+        check_assist_not_applicable(
+            extract_generic_param,
+            r#"
+fn trim(s: &$0str) -> &str { s.trim() }
+"#,
+        );
+    }
+}
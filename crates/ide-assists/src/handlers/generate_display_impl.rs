@@ -0,0 +1,564 @@
+use hir::ModuleDef;
+use ide_db::{famous_defs::FamousDefs, helpers::mod_path_to_ast};
+use itertools::Itertools;
+use stdx::{format_to, to_lower_snake_case};
+use syntax::{
+    ast::{self, AstNode, AstToken, HasDocComments, HasName, HasVisibility},
+    SyntaxKind, T,
+};
+
+use crate::{
+    utils::{generate_trait_impl_text, generate_trait_impl_text_intransitive},
+    AssistContext, AssistId, AssistKind, Assists,
+};
+
+// Assist: generate_display_impl
+//
+// Adds a `Display` impl writing the type's or variant's name, or the first line of its doc
+// comment, followed by its fields.
+//
+// ```
+// # //- minicore: fmt
+// enum Color {
+//     /// Bright red
+//     Red,
+//     Rgb$0(u8, u8, u8),
+// }
+// ```
+// ->
+// ```
+// enum Color {
+//     /// Bright red
+//     Red,
+//     Rgb(u8, u8, u8),
+// }
+//
+// impl core::fmt::Display for Color {
+//     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+//         match self {
+//             Self::Red => write!(f, "Bright red"),
+//             Self::Rgb(arg0, arg1, arg2) => write!(f, "rgb ({arg0}, {arg1}, {arg2})"),
+//         }
+//     }
+// }
+// ```
+pub(crate) fn generate_display_impl(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    let adt = ctx.find_node_at_offset::<ast::Adt>()?;
+    let name = adt.name()?;
+    let adt_def = ctx.sema.to_def(&adt)?;
+    let module = adt_def.module(ctx.db());
+    let display = FamousDefs(&ctx.sema, module.krate()).core_fmt_Display()?;
+    if adt_def.ty(ctx.db()).impls_trait(ctx.db(), display, &[]) {
+        return None;
+    }
+    let fmt_path = module.find_use_path(
+        ctx.db(),
+        ModuleDef::Module(display.module(ctx.db())),
+        ctx.config.prefer_no_std,
+        ctx.config.prefer_prelude,
+    )?;
+    let fmt_path = mod_path_to_ast(&fmt_path);
+
+    let body = match &adt {
+        ast::Adt::Struct(strukt) => {
+            let label = label(&name, strukt);
+            let (format_string, args): (String, Vec<String>) = match strukt.field_list() {
+                Some(ast::FieldList::RecordFieldList(fields)) => {
+                    let names = fields.fields().filter_map(|it| it.name()).collect::<Vec<_>>();
+                    let placeholders = names.iter().map(|name| format!("{name}: {{}}"));
+                    let args = names.iter().map(|name| format!("self.{name}"));
+                    (with_fields(&label, placeholders), args.collect())
+                }
+                Some(ast::FieldList::TupleFieldList(fields)) => {
+                    let count = fields.fields().count();
+                    let placeholders = (0..count).map(|_| "{}".to_owned());
+                    let args = (0..count).map(|i| format!("self.{i}"));
+                    (with_fields(&label, placeholders), args.collect())
+                }
+                None => (label, Vec::new()),
+            };
+            let args = args.into_iter().map(|it| format!(", {it}")).collect::<String>();
+            format!("write!(f, \"{format_string}\"{args})")
+        }
+        ast::Adt::Enum(enum_) => {
+            let mut arms = String::new();
+            for variant in enum_.variant_list()?.variants() {
+                let variant_name = variant.name()?;
+                let label = label(&variant_name, &variant);
+                let (pat, format_string) = match variant.field_list() {
+                    Some(ast::FieldList::RecordFieldList(fields)) => {
+                        let names = fields.fields().filter_map(|it| it.name()).collect::<Vec<_>>();
+                        let placeholders = names.iter().map(|name| format!("{name}: {{{name}}}"));
+                        let pat = format!("Self::{variant_name} {{ {} }}", names.iter().join(", "));
+                        (pat, with_fields(&label, placeholders))
+                    }
+                    Some(ast::FieldList::TupleFieldList(fields)) => {
+                        let names = (0..fields.fields().count()).map(|i| format!("arg{i}"));
+                        let names = names.collect::<Vec<_>>();
+                        let placeholders = names.iter().map(|name| format!("{{{name}}}"));
+                        let pat = format!("Self::{variant_name}({})", names.iter().join(", "));
+                        (pat, with_fields(&label, placeholders))
+                    }
+                    None => (format!("Self::{variant_name}"), label),
+                };
+                format_to!(arms, "\n            {pat} => write!(f, \"{format_string}\"),");
+            }
+            format!("match self {{{arms}\n        }}")
+        }
+        ast::Adt::Union(_) => return None,
+    };
+
+    let target = adt.syntax().text_range();
+    acc.add(
+        AssistId("generate_display_impl", AssistKind::Generate),
+        format!("Generate `Display` impl for `{name}`"),
+        target,
+        |edit| {
+            let code = format!(
+                "    fn fmt(&self, f: &mut {fmt_path}::Formatter<'_>) -> {fmt_path}::Result {{\n        {body}\n    }}"
+            );
+            let display_trait = format!("{fmt_path}::Display");
+            let impl_ = generate_trait_impl_text(&adt, &display_trait, &code);
+            edit.insert(adt.syntax().text_range().end(), impl_);
+        },
+    )
+}
+
+// Assist: generate_from_str_impl
+//
+// Adds a `FromStr` impl parsing back the strings a `Display` impl writes for the variants of an
+// enum, with an error type for the strings matching none of them.
+//
+// ```
+// # //- minicore: fmt, from_str
+// enum Color {
+//     Red,
+//     DarkBlue,
+// }
+//
+// impl core::fmt::Display$0 for Color {
+//     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+//         match self {
+//             Self::Red => write!(f, "red"),
+//             Self::DarkBlue => write!(f, "dark blue"),
+//         }
+//     }
+// }
+// ```
+// ->
+// ```
+// enum Color {
+//     Red,
+//     DarkBlue,
+// }
+//
+// impl core::fmt::Display for Color {
+//     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+//         match self {
+//             Self::Red => write!(f, "red"),
+//             Self::DarkBlue => write!(f, "dark blue"),
+//         }
+//     }
+// }
+//
+// #[derive(Debug, Clone, PartialEq, Eq)]
+// enum ParseColorError {
+//     Unknown(String),
+// }
+//
+// impl core::str::FromStr for Color {
+//     type Err = ParseColorError;
+//
+//     fn from_str(s: &str) -> Result<Self, Self::Err> {
+//         match s {
+//             "red" => Ok(Self::Red),
+//             "dark blue" => Ok(Self::DarkBlue),
+//             _ => Err(ParseColorError::Unknown(s.to_owned())),
+//         }
+//     }
+// }
+// ```
+pub(crate) fn generate_from_str_impl(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    let impl_ = ctx.find_node_at_offset::<ast::Impl>()?;
+    if let Some(items) = impl_.assoc_item_list() {
+        if items.syntax().text_range().contains(ctx.offset()) {
+            return None;
+        }
+    }
+    let impl_def = ctx.sema.to_def(&impl_)?;
+    let module = impl_def.module(ctx.db());
+    let famous_defs = FamousDefs(&ctx.sema, module.krate());
+    if impl_def.trait_(ctx.db())? != famous_defs.core_fmt_Display()? {
+        return None;
+    }
+    let Some(hir::Adt::Enum(enum_def)) = impl_def.self_ty(ctx.db()).as_adt() else {
+        return None;
+    };
+    let from_str = famous_defs.core_str_FromStr()?;
+    if impl_def.self_ty(ctx.db()).impls_trait(ctx.db(), from_str, &[]) {
+        return None;
+    }
+    let enum_ = ctx.sema.source(enum_def)?.value;
+    let variants = enum_.variant_list()?.variants().collect::<Vec<_>>();
+    if variants.iter().any(|it| it.field_list().is_some()) {
+        return None;
+    }
+
+    // The body must write a fixed string for each variant.
+    let fmt = impl_.assoc_item_list()?.assoc_items().find_map(|it| match it {
+        ast::AssocItem::Fn(it) if it.name()?.text() == "fmt" => Some(it),
+        _ => None,
+    })?;
+    let Some(ast::Expr::MatchExpr(match_expr)) = fmt.body()?.tail_expr() else { return None };
+    let mut strings = Vec::new();
+    for arm in match_expr.match_arm_list()?.arms() {
+        let Some(ast::Pat::PathPat(pat)) = arm.pat() else { return None };
+        let variant_name = pat.path()?.segment()?.name_ref()?;
+        if !variants.iter().any(|it| it.name().map_or(false, |it| it.text() == variant_name.text()))
+        {
+            return None;
+        }
+        strings.push((written_string(&arm.expr()?)?, variant_name));
+    }
+    if strings.len() != variants.len() {
+        return None;
+    }
+
+    let from_str_path = module.find_use_path(
+        ctx.db(),
+        ModuleDef::Trait(from_str),
+        ctx.config.prefer_no_std,
+        ctx.config.prefer_prelude,
+    )?;
+    let from_str_path = mod_path_to_ast(&from_str_path);
+    let enum_name = enum_.name()?;
+    let error_name = format!("Parse{enum_name}Error");
+
+    let target = impl_.syntax().text_range();
+    acc.add(
+        AssistId("generate_from_str_impl", AssistKind::Generate),
+        format!("Generate `FromStr` impl for `{enum_name}` from its `Display` impl"),
+        target,
+        |edit| {
+            let vis = enum_.visibility().map_or_else(String::new, |it| format!("{it} "));
+            let mut buf = format!(
+                "\n\n#[derive(Debug, Clone, PartialEq, Eq)]\n{vis}enum {error_name} {{\n    Unknown(String),\n}}"
+            );
+            let mut arms = String::new();
+            for (string, variant_name) in strings {
+                format_to!(arms, "\n            {string:?} => Ok(Self::{variant_name}),");
+            }
+            let code = format!(
+                "    type Err = {error_name};
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {{
+        match s {{{arms}
+            _ => Err({error_name}::Unknown(s.to_owned())),
+        }}
+    }}"
+            );
+            let adt = ast::Adt::Enum(enum_);
+            let impl_text =
+                generate_trait_impl_text_intransitive(&adt, &from_str_path.to_string(), &code);
+            buf.push_str(&impl_text);
+            edit.insert(impl_.syntax().text_range().end(), buf);
+        },
+    )
+}
+
+/// Returns the first line of the doc comment, or the name in lowercase words.
+fn label(name: &ast::Name, item: &impl HasDocComments) -> String {
+    let doc = item
+        .doc_comments()
+        .find_map(|it| it.doc_comment().map(|it| it.trim().to_owned()))
+        .filter(|it| !it.is_empty());
+    let label = match doc {
+        Some(doc) => doc.strip_suffix('.').unwrap_or(&doc).to_owned(),
+        None => to_lower_snake_case(&name.text()).replace('_', " "),
+    };
+    label
+        .chars()
+        .map(|c| match c {
+            '{' => "{{".to_owned(),
+            '}' => "}}".to_owned(),
+            '"' => "\\\"".to_owned(),
+            '\\' => "\\\\".to_owned(),
+            c => c.to_string(),
+        })
+        .collect()
+}
+
+fn with_fields(label: &str, placeholders: impl Iterator<Item = String>) -> String {
+    let placeholders = placeholders.format(", ").to_string();
+    if placeholders.is_empty() {
+        label.to_owned()
+    } else {
+        format!("{label} ({placeholders})")
+    }
+}
+
+/// Returns the string written by `write!(f, "...")` or `f.write_str("...")`.
+fn written_string(expr: &ast::Expr) -> Option<String> {
+    let literal = match expr {
+        ast::Expr::MacroExpr(it) => {
+            let macro_call = it.macro_call()?;
+            if macro_call.path()?.segment()?.name_ref()?.text() != "write" {
+                return None;
+            }
+            let tokens = macro_call
+                .token_tree()?
+                .syntax()
+                .children_with_tokens()
+                .filter_map(|it| it.into_token())
+                .filter(|it| !it.kind().is_trivia())
+                .collect::<Vec<_>>();
+            // `(f, "...")`, possibly with a trailing comma
+            let [_, f, comma, literal, rest @ ..] = tokens.as_slice() else { return None };
+            let no_arguments = match rest {
+                [_] => true,
+                [trailing_comma, _] => trailing_comma.kind() == T![,],
+                _ => false,
+            };
+            if f.kind() != SyntaxKind::IDENT || comma.kind() != T![,] || !no_arguments {
+                return None;
+            }
+            let value = ast::String::cast(literal.clone())?.value()?.into_owned();
+            // Without arguments, braces are the only formatting syntax left.
+            if value.replace("{{", "").replace("}}", "").contains(['{', '}']) {
+                return None;
+            }
+            return Some(value.replace("{{", "{").replace("}}", "}"));
+        }
+        ast::Expr::MethodCallExpr(it) if it.name_ref()?.text() == "write_str" => {
+            let mut args = it.arg_list()?.args();
+            match (args.next(), args.next()) {
+                (Some(ast::Expr::Literal(it)), None) => it,
+                _ => return None,
+            }
+        }
+        _ => return None,
+    };
+    match literal.kind() {
+        ast::LiteralKind::String(it) => Some(it.value()?.into_owned()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    #[test]
+    fn display_for_struct() {
+        check_assist(
+            generate_display_impl,
+            r#"
+//- minicore: fmt
+/// A point on the {plane}.
+struct Point$0 {
+    x: i32,
+    y: i32,
+}
+"#,
+            r#"
+/// A point on the {plane}.
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+impl core::fmt::Display for Point {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "A point on the {{plane}} (x: {}, y: {})", self.x, self.y)
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn display_for_tuple_and_unit_structs() {
+        check_assist(
+            generate_display_impl,
+            r#"
+//- minicore: fmt
+struct UserId$0<T>(T, u32);
+"#,
+            r#"
+struct UserId<T>(T, u32);
+
+impl<T: core::fmt::Display> core::fmt::Display for UserId<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "user id ({}, {})", self.0, self.1)
+    }
+}
+"#,
+        );
+        check_assist(
+            generate_display_impl,
+            r#"
+//- minicore: fmt
+struct Marker$0;
+"#,
+            r#"
+struct Marker;
+
+impl core::fmt::Display for Marker {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "marker")
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn display_for_enum() {
+        check_assist(
+            generate_display_impl,
+            r#"
+//- minicore: fmt
+use core::fmt;
+
+enum Shape$0 {
+    /// An "empty" shape.
+    Empty,
+    Circle { radius: f64 },
+    Rect(f64, f64),
+}
+"#,
+            r#"
+use core::fmt;
+
+enum Shape {
+    /// An "empty" shape.
+    Empty,
+    Circle { radius: f64 },
+    Rect(f64, f64),
+}
+
+impl fmt::Display for Shape {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "An \"empty\" shape"),
+            Self::Circle { radius } => write!(f, "circle (radius: {radius})"),
+            Self::Rect(arg0, arg1) => write!(f, "rect ({arg0}, {arg1})"),
+        }
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn display_not_applicable_when_implemented() {
+        check_assist_not_applicable(
+            generate_display_impl,
+            r#"
+//- minicore: fmt
+struct Point$0;
+
+impl core::fmt::Display for Point {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        loop {}
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn from_str_from_display() {
+        check_assist(
+            generate_from_str_impl,
+            r#"
+//- minicore: fmt, from_str
+pub enum Level {
+    Low,
+    High,
+}
+
+impl core::fmt::Display for $0Level {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Level::Low => f.write_str("low {level}"),
+            Self::High => write!(f, "high {{level}}",),
+        }
+    }
+}
+"#,
+            r#"
+pub enum Level {
+    Low,
+    High,
+}
+
+impl core::fmt::Display for Level {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Level::Low => f.write_str("low {level}"),
+            Self::High => write!(f, "high {{level}}",),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseLevelError {
+    Unknown(String),
+}
+
+impl core::str::FromStr for Level {
+    type Err = ParseLevelError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "low {level}" => Ok(Self::Low),
+            "high {level}" => Ok(Self::High),
+            _ => Err(ParseLevelError::Unknown(s.to_owned())),
+        }
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn from_str_not_applicable_with_fields_or_arguments() {
+        check_assist_not_applicable(
+            generate_from_str_impl,
+            r#"
+//- minicore: fmt, from_str
+enum Level {
+    Low(u8),
+}
+
+impl core::fmt::Display for $0Level {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Low(it) => write!(f, "low"),
+        }
+    }
+}
+"#,
+        );
+        check_assist_not_applicable(
+            generate_from_str_impl,
+            r#"
+//- minicore: fmt, from_str
+enum Level {
+    Low,
+}
+
+impl core::fmt::Display for $0Level {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Low => write!(f, "low {}", 1),
+        }
+    }
+}
+"#,
+        );
+    }
+}
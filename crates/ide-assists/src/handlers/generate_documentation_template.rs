@@ -60,13 +60,17 @@ pub(crate) fn generate_documentation_template(
         text_range,
         |builder| {
             // Introduction / short function description before the sections
-            let mut doc_lines = vec![introduction_builder(&ast_func, ctx).unwrap_or(".".into())];
+            let intro = introduction_builder(&ast_func, ctx);
+            // Generated introductions of constructors and setters already cover the arguments
+            let arguments = if intro.is_none() { arguments_builder(&ast_func) } else { None };
+            let mut doc_lines = vec![intro.unwrap_or(".".into())];
             // Then come the sections
-            for section_builder in [panics_builder, errors_builder, safety_builder] {
-                if let Some(mut lines) = section_builder(&ast_func) {
-                    doc_lines.push("".into());
-                    doc_lines.append(&mut lines);
-                }
+            let sections = [panics_builder, errors_builder, safety_builder]
+                .into_iter()
+                .map(|section_builder| section_builder(&ast_func));
+            for mut lines in std::iter::once(arguments).chain(sections).flatten() {
+                doc_lines.push("".into());
+                doc_lines.append(&mut lines);
             }
             builder.insert(text_range.start(), documentation_from_lines(doc_lines, indent_level));
         },
@@ -253,6 +257,25 @@ fn introduction_builder(ast_func: &ast::Fn, ctx: &AssistContext<'_>) -> Option<S
     None
 }
 
+/// Builds an optional `# Arguments` section
+fn arguments_builder(ast_func: &ast::Fn) -> Option<Vec<String>> {
+    let arguments = ast_func
+        .param_list()?
+        .params()
+        .filter_map(|param| match param.pat()? {
+            ast::Pat::WildcardPat(_) => None,
+            ast::Pat::IdentPat(it) => Some(format!("* `{}` - .", it.name()?)),
+            pat => Some(format!("* `{pat}` - .")),
+        })
+        .collect::<Vec<_>>();
+    if arguments.is_empty() {
+        return None;
+    }
+    let mut lines = string_vec_from(&["# Arguments", ""]);
+    lines.extend(arguments);
+    Some(lines)
+}
+
 /// Builds an optional `# Panics` section
 fn panics_builder(ast_func: &ast::Fn) -> Option<Vec<String>> {
     match can_panic(ast_func) {
@@ -663,6 +686,10 @@ pub fn panic$0s_if(a: bool) {
             r#"
 /// .
 ///
+/// # Arguments
+///
+/// * `a` - .
+///
 /// # Panics
 ///
 /// Panics if .
@@ -687,6 +714,10 @@ pub fn $0panics_if_not(a: bool) {
             r#"
 /// .
 ///
+/// # Arguments
+///
+/// * `a` - .
+///
 /// # Panics
 ///
 /// Panics if .
@@ -709,6 +740,10 @@ pub fn $0panics_if_none(a: Option<()>) {
             r#"
 /// .
 ///
+/// # Arguments
+///
+/// * `a` - .
+///
 /// # Panics
 ///
 /// Panics if .
@@ -731,6 +766,10 @@ pub fn $0panics_if_none2(a: Option<()>) {
             r#"
 /// .
 ///
+/// # Arguments
+///
+/// * `a` - .
+///
 /// # Panics
 ///
 /// Panics if .
@@ -1332,6 +1371,33 @@ impl S {
     /// Sets the domain name of this [`S`].
     pub fn set_domain_name(&mut self, name: String) {}
 }
+"#,
+        );
+    }
+
+    #[test]
+    fn documents_arguments() {
+        check_assist(
+            generate_documentation_template,
+            r#"
+pub fn $0scale((x, y): (f32, f32), factor: f32, _: bool) -> Result<f32, ()> {
+    Ok(x * y * factor)
+}
+"#,
+            r#"
+/// .
+///
+/// # Arguments
+///
+/// * `(x, y)` - .
+/// * `factor` - .
+///
+/// # Errors
+///
+/// This function will return an error if .
+pub fn scale((x, y): (f32, f32), factor: f32, _: bool) -> Result<f32, ()> {
+    Ok(x * y * factor)
+}
 "#,
         );
     }
@@ -0,0 +1,431 @@
+use std::iter;
+
+use hir::{HasVisibility, ModuleDef, StructKind};
+use ide_db::{famous_defs::FamousDefs, helpers::mod_path_to_ast};
+use itertools::Itertools;
+use stdx::format_to;
+use syntax::ast::{self, AstNode};
+
+use crate::{
+    utils::generate_trait_impl_text_intransitive, AssistContext, AssistId, AssistKind, Assists,
+    GroupLabel,
+};
+
+// Assist: generate_from_impl_for_struct
+//
+// Adds a `From` or `TryFrom` impl converting from another struct with similar fields. Fields
+// with the same name are moved over, the rest are left as `todo!()`.
+//
+// ```
+// # //- minicore: from
+// struct User { id: u32, age: u8 }
+//
+// struct $0UserDto { id: u32, age: u8, active: bool }
+// ```
+// ->
+// ```
+// struct User { id: u32, age: u8 }
+//
+// struct UserDto { id: u32, age: u8, active: bool }
+//
+// impl From<User> for UserDto {
+//     fn from(value: User) -> Self {
+//         Self {
+//             id: value.id,
+//             age: value.age,
+//             active: todo!(),
+//         }
+//     }
+// }
+// ```
+pub(crate) fn generate_from_impl_for_struct(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let name = ctx.find_node_at_offset::<ast::Name>()?;
+    let strukt = name.syntax().parent().and_then(ast::Struct::cast)?;
+    if !matches!(strukt.kind(), ast::StructKind::Record(_)) {
+        return None;
+    }
+    let db = ctx.db();
+    let strukt_def = ctx.sema.to_def(&strukt)?;
+    let fields = strukt_def.fields(db);
+    let module = strukt_def.module(db);
+    let krate = module.krate();
+    let famous_defs = FamousDefs(&ctx.sema, krate);
+    let from_trait = famous_defs.core_convert_From()?;
+    let try_from_trait = famous_defs.core_convert_TryFrom();
+    let strukt_ty = strukt_def.ty(db);
+
+    // Related types are looked for next to the struct only, in its module, the parent of that
+    // and their direct submodules, as searching the whole crate would be too slow.
+    let mut modules = iter::once(module).chain(module.parent(db)).collect::<Vec<_>>();
+    let submodules = modules.iter().flat_map(|it| it.children(db)).collect::<Vec<_>>();
+    modules.extend(submodules);
+    let candidates = modules
+        .into_iter()
+        .unique()
+        .flat_map(|it| it.declarations(db))
+        .filter_map(|def| match def {
+            ModuleDef::Adt(hir::Adt::Struct(it)) if it != strukt_def => Some(it),
+            _ => None,
+        })
+        .filter(|other| {
+            other.kind(db) == StructKind::Record
+                && other.is_visible_from(db, module)
+                && hir::GenericDef::Adt((*other).into()).params(db).is_empty()
+        })
+        .filter_map(|other| {
+            let other_fields = other.fields(db);
+            let matched = fields
+                .iter()
+                .filter_map(|field| other_fields.iter().find(|it| it.name(db) == field.name(db)))
+                .collect::<Vec<_>>();
+            // Only offer conversions between types that are actually similar, i.e. share at
+            // least half of the fields of the type we are converting into.
+            if matched.is_empty() || matched.len() * 2 < fields.len() {
+                return None;
+            }
+            // The fields get moved out of the other type, so all of them need to be accessible.
+            if !matched.iter().all(|it| it.is_visible_from(db, module)) {
+                cov_mark::hit!(generate_from_impl_for_struct_private_field);
+                return None;
+            }
+            Some((other, other_fields))
+        })
+        .collect::<Vec<_>>();
+    if candidates.is_empty() {
+        return None;
+    }
+
+    let adt = ast::Adt::Struct(strukt.clone());
+    let group = GroupLabel("Generate conversion impl".to_owned());
+    let target = name.syntax().text_range();
+    for (other, other_fields) in candidates {
+        let Some(other_path) = module.find_use_path(
+            db,
+            ModuleDef::from(other),
+            ctx.config.prefer_no_std,
+            ctx.config.prefer_prelude,
+        ) else {
+            continue;
+        };
+        let other_path = mod_path_to_ast(&other_path);
+        let other_ty = other.ty(db);
+
+        let field_inits = |fallible: bool| {
+            let mut buf = String::new();
+            for field in &fields {
+                let field_name = field.name(db);
+                let field_name = field_name.display(db);
+                let converts = |trait_: Option<hir::Trait>, other_field: &hir::Field| {
+                    trait_.is_some_and(|it| field.ty(db).impls_trait(db, it, &[other_field.ty(db)]))
+                };
+                let init = match other_fields.iter().find(|it| it.name(db) == field.name(db)) {
+                    Some(other_field) if other_field.ty(db).could_unify_with(db, &field.ty(db)) => {
+                        format!("value.{field_name}")
+                    }
+                    Some(other_field) if fallible && converts(try_from_trait, other_field) => {
+                        format!("value.{field_name}.try_into().map_err(|_| ())?")
+                    }
+                    Some(other_field) if converts(Some(from_trait), other_field) => {
+                        format!("value.{field_name}.into()")
+                    }
+                    _ => "todo!()".to_owned(),
+                };
+                format_to!(buf, "            {field_name}: {init},\n");
+            }
+            buf
+        };
+
+        if !strukt_ty.impls_trait(db, from_trait, &[other_ty.clone()]) {
+            acc.add_group(
+                &group,
+                AssistId("generate_from_impl_for_struct", AssistKind::Generate),
+                format!("Generate `From<{other_path}>` impl"),
+                target,
+                |edit| {
+                    let field_inits = field_inits(false);
+                    let impl_code = format!(
+                        r#"    fn from(value: {other_path}) -> Self {{
+        Self {{
+{field_inits}        }}
+    }}"#
+                    );
+                    let from_impl = generate_trait_impl_text_intransitive(
+                        &adt,
+                        &format!("From<{other_path}>"),
+                        &impl_code,
+                    );
+                    edit.insert(strukt.syntax().text_range().end(), from_impl);
+                },
+            );
+        }
+
+        let Some(try_from_trait) = try_from_trait else { continue };
+        if strukt_ty.impls_trait(db, try_from_trait, &[other_ty]) {
+            continue;
+        }
+        acc.add_group(
+            &group,
+            AssistId("generate_from_impl_for_struct", AssistKind::Generate),
+            format!("Generate `TryFrom<{other_path}>` impl"),
+            target,
+            |edit| {
+                let field_inits = field_inits(true);
+                let impl_code = format!(
+                    r#"    type Error = ();
+
+    fn try_from(value: {other_path}) -> Result<Self, Self::Error> {{
+        Ok(Self {{
+{field_inits}        }})
+    }}"#
+                );
+                let try_from_impl = generate_trait_impl_text_intransitive(
+                    &adt,
+                    &format!("TryFrom<{other_path}>"),
+                    &impl_code,
+                );
+                edit.insert(strukt.syntax().text_range().end(), try_from_impl);
+            },
+        );
+    }
+    Some(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_by_label, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn maps_fields_by_name() {
+        check_assist(
+            generate_from_impl_for_struct,
+            r#"
+//- minicore: from
+struct Domain { id: u32, name: u8, secret: u64 }
+
+struct Dto$0 { id: u32, name: u8, extra: bool }
+"#,
+            r#"
+struct Domain { id: u32, name: u8, secret: u64 }
+
+struct Dto { id: u32, name: u8, extra: bool }
+
+impl From<Domain> for Dto {
+    fn from(value: Domain) -> Self {
+        Self {
+            id: value.id,
+            name: value.name,
+            extra: todo!(),
+        }
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn converts_fields_with_different_types() {
+        check_assist(
+            generate_from_impl_for_struct,
+            r#"
+//- minicore: from
+struct Id(u32);
+impl From<u32> for Id {
+    fn from(value: u32) -> Self { Id(value) }
+}
+struct Domain { id: u32, score: u8, name: u8 }
+
+struct Dto$0 { id: Id, score: u8, name: bool }
+"#,
+            r#"
+struct Id(u32);
+impl From<u32> for Id {
+    fn from(value: u32) -> Self { Id(value) }
+}
+struct Domain { id: u32, score: u8, name: u8 }
+
+struct Dto { id: Id, score: u8, name: bool }
+
+impl From<Domain> for Dto {
+    fn from(value: Domain) -> Self {
+        Self {
+            id: value.id.into(),
+            score: value.score,
+            name: todo!(),
+        }
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn other_type_in_other_module() {
+        check_assist(
+            generate_from_impl_for_struct,
+            r#"
+//- minicore: from
+mod domain {
+    pub struct User { pub id: u32 }
+}
+
+struct $0UserDto<T> { id: u32, extra: T }
+"#,
+            r#"
+mod domain {
+    pub struct User { pub id: u32 }
+}
+
+struct UserDto<T> { id: u32, extra: T }
+
+impl<T> From<domain::User> for UserDto<T> {
+    fn from(value: domain::User) -> Self {
+        Self {
+            id: value.id,
+            extra: todo!(),
+        }
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn one_assist_per_candidate() {
+        check_assist_by_label(
+            generate_from_impl_for_struct,
+            r#"
+//- minicore: from
+struct A { id: u32 }
+struct B { id: u32 }
+
+struct $0Dto { id: u32 }
+"#,
+            r#"
+struct A { id: u32 }
+struct B { id: u32 }
+
+struct Dto { id: u32 }
+
+impl From<B> for Dto {
+    fn from(value: B) -> Self {
+        Self {
+            id: value.id,
+        }
+    }
+}
+"#,
+            "Generate `From<B>` impl",
+        );
+    }
+
+    #[test]
+    fn generates_try_from() {
+        check_assist_by_label(
+            generate_from_impl_for_struct,
+            r#"
+//- minicore: from, try_from
+struct Id(u32);
+impl TryFrom<u64> for Id {
+    type Error = ();
+    fn try_from(value: u64) -> Result<Self, ()> { Err(()) }
+}
+struct Domain { id: u64, name: u8 }
+
+struct $0Dto { id: Id, name: u8, extra: bool }
+"#,
+            r#"
+struct Id(u32);
+impl TryFrom<u64> for Id {
+    type Error = ();
+    fn try_from(value: u64) -> Result<Self, ()> { Err(()) }
+}
+struct Domain { id: u64, name: u8 }
+
+struct Dto { id: Id, name: u8, extra: bool }
+
+impl TryFrom<Domain> for Dto {
+    type Error = ();
+
+    fn try_from(value: Domain) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: value.id.try_into().map_err(|_| ())?,
+            name: value.name,
+            extra: todo!(),
+        })
+    }
+}
+"#,
+            "Generate `TryFrom<Domain>` impl",
+        );
+    }
+
+    #[test]
+    fn not_applicable_with_private_fields() {
+        cov_mark::check!(generate_from_impl_for_struct_private_field);
+        check_assist_not_applicable(
+            generate_from_impl_for_struct,
+            r#"
+//- minicore: from
+mod domain {
+    pub struct User { pub id: u32, name: u8 }
+}
+
+struct $0UserDto { id: u32, name: u8 }
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_without_similar_struct() {
+        check_assist_not_applicable(
+            generate_from_impl_for_struct,
+            r#"
+//- minicore: from
+struct Domain { id: u32 }
+
+struct $0Dto { name: u32, extra: bool }
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_with_distant_struct() {
+        check_assist_not_applicable(
+            generate_from_impl_for_struct,
+            r#"
+//- minicore: from
+mod a {
+    pub mod b {
+        pub struct Domain { pub id: u32 }
+    }
+}
+
+struct $0Dto { id: u32 }
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_if_impl_exists() {
+        check_assist_not_applicable(
+            generate_from_impl_for_struct,
+            r#"
+//- minicore: from
+struct Domain { id: u32 }
+
+struct $0Dto { id: u32 }
+
+impl From<Domain> for Dto {
+    fn from(value: Domain) -> Self {
+        Self { id: value.id }
+    }
+}
+"#,
+        );
+    }
+}
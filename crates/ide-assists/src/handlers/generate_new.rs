@@ -1,5 +1,6 @@
 use ide_db::{
-    imports::import_assets::item_for_path_search, use_trivial_constructor::use_trivial_constructor,
+    famous_defs::FamousDefs, imports::import_assets::item_for_path_search,
+    source_change::SourceChangeBuilder, use_trivial_constructor::use_trivial_constructor,
 };
 use syntax::{
     ast::{self, edit_in_place::Indent, make, AstNode, HasName, HasVisibility, StructKind},
@@ -49,102 +50,200 @@ pub(crate) fn generate_new(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option
 
     let target = strukt.syntax().text_range();
     acc.add(AssistId("generate_new", AssistKind::Generate), "Generate `new`", target, |builder| {
-        let trivial_constructors = field_list
+        let inits = field_list
             .fields()
-            .map(|f| {
-                let name = f.name()?;
+            .map(|f| match trivial_constructor(ctx, current_module, &f) {
+                Some(expr) => FieldInit::Expr(expr),
+                None => FieldInit::Param,
+            })
+            .collect();
+        insert_new_fn(ctx, builder, &strukt, impl_def, &field_list, inits, false);
+    })
+}
+
+// Assist: generate_new_with_defaults
+//
+// Adds a `fn new` for a type that only takes the fields not implementing `Default`, filling in
+// the others with their default values.
+//
+// ```
+// # //- minicore: default, builtin_impls
+// struct Counter {
+//     name: &'static str,$0
+//     count: u32,
+// }
+// ```
+// ->
+// ```
+// struct Counter {
+//     name: &'static str,
+//     count: u32,
+// }
+//
+// impl Counter {
+//     fn $0new(name: &'static str) -> Self {
+//         Self { name, count: Default::default() }
+//     }
+// }
+// ```
+pub(crate) fn generate_new_with_defaults(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    let strukt = ctx.find_node_at_offset::<ast::Struct>()?;
+    let StructKind::Record(field_list) = strukt.kind() else { return None };
+    let impl_def =
+        find_struct_impl(ctx, &ast::Adt::Struct(strukt.clone()), &[String::from("new")])?;
+
+    let db = ctx.db();
+    let current_module = ctx.sema.scope(strukt.syntax())?.module();
+    let default_trait = FamousDefs(&ctx.sema, current_module.krate()).core_default_Default()?;
+    let inits = field_list
+        .fields()
+        .map(|f| {
+            if let Some(expr) = trivial_constructor(ctx, current_module, &f) {
+                return FieldInit::Expr(expr);
+            }
+            let ty = f.ty().and_then(|it| ctx.sema.resolve_type(&it));
+            match ty {
+                Some(ty) if ty.impls_trait(db, default_trait, &[]) => FieldInit::Default,
+                _ => FieldInit::Param,
+            }
+        })
+        .collect::<Vec<_>>();
+    // Without any defaults, this is the same as `generate_new`.
+    if !inits.iter().any(|it| matches!(it, FieldInit::Default)) {
+        return None;
+    }
+    // Struct update syntax is only used if the struct derives `Default`. A hand-written `Default`
+    // impl may well call `new`, which would then recurse.
+    let use_struct_update =
+        hir::Impl::all_for_type(db, ctx.sema.to_def(&strukt)?.ty(db)).into_iter().any(|impl_| {
+            impl_.trait_(db) == Some(default_trait) && impl_.as_builtin_derive_path(db).is_some()
+        });
 
-                let ty = ctx.sema.resolve_type(&f.ty()?)?;
+    let target = strukt.syntax().text_range();
+    acc.add(
+        AssistId("generate_new_with_defaults", AssistKind::Generate),
+        "Generate `new` with default values",
+        target,
+        |builder| {
+            insert_new_fn(ctx, builder, &strukt, impl_def, &field_list, inits, use_struct_update);
+        },
+    )
+}
 
-                let item_in_ns = hir::ItemInNs::from(hir::ModuleDef::from(ty.as_adt()?));
+enum FieldInit {
+    /// The field is passed in as a parameter.
+    Param,
+    /// The field is initialized with `Default::default()`.
+    Default,
+    Expr(ast::Expr),
+}
 
-                let type_path = current_module.find_use_path(
-                    ctx.sema.db,
-                    item_for_path_search(ctx.sema.db, item_in_ns)?,
-                    ctx.config.prefer_no_std,
-                    ctx.config.prefer_prelude,
-                )?;
+fn trivial_constructor(
+    ctx: &AssistContext<'_>,
+    current_module: hir::Module,
+    field: &ast::RecordField,
+) -> Option<ast::Expr> {
+    let ty = ctx.sema.resolve_type(&field.ty()?)?;
 
-                let expr = use_trivial_constructor(
-                    ctx.sema.db,
-                    ide_db::helpers::mod_path_to_ast(&type_path),
-                    &ty,
-                )?;
+    let item_in_ns = hir::ItemInNs::from(hir::ModuleDef::from(ty.as_adt()?));
 
-                Some(make::record_expr_field(make::name_ref(&name.text()), Some(expr)))
-            })
-            .collect::<Vec<_>>();
+    let type_path = current_module.find_use_path(
+        ctx.sema.db,
+        item_for_path_search(ctx.sema.db, item_in_ns)?,
+        ctx.config.prefer_no_std,
+        ctx.config.prefer_prelude,
+    )?;
 
-        let params = field_list.fields().enumerate().filter_map(|(i, f)| {
-            if trivial_constructors[i].is_none() {
-                let name = f.name()?;
-                let ty = f.ty()?;
+    use_trivial_constructor(ctx.sema.db, ide_db::helpers::mod_path_to_ast(&type_path), &ty)
+}
 
-                Some(make::param(make::ident_pat(false, false, name).into(), ty))
-            } else {
-                None
-            }
-        });
-        let params = make::param_list(None, params);
-
-        let fields = field_list.fields().enumerate().filter_map(|(i, f)| {
-            let constructor = trivial_constructors[i].clone();
-            if constructor.is_some() {
-                constructor
-            } else {
-                Some(make::record_expr_field(make::name_ref(&f.name()?.text()), None))
-            }
-        });
-        let fields = make::record_expr_field_list(fields);
-
-        let record_expr = make::record_expr(make::ext::ident_path("Self"), fields);
-        let body = make::block_expr(None, Some(record_expr.into()));
-
-        let ret_type = make::ret_type(make::ty_path(make::ext::ident_path("Self")));
-
-        let fn_ = make::fn_(
-            strukt.visibility(),
-            make::name("new"),
-            None,
-            None,
-            params,
-            body,
-            Some(ret_type),
-            false,
-            false,
-            false,
+fn insert_new_fn(
+    ctx: &AssistContext<'_>,
+    builder: &mut SourceChangeBuilder,
+    strukt: &ast::Struct,
+    impl_def: Option<ast::Impl>,
+    field_list: &ast::RecordFieldList,
+    inits: Vec<FieldInit>,
+    use_struct_update: bool,
+) {
+    let params = field_list.fields().zip(&inits).filter_map(|(f, init)| match init {
+        FieldInit::Param => {
+            let name = f.name()?;
+            let ty = f.ty()?;
+
+            Some(make::param(make::ident_pat(false, false, name).into(), ty))
+        }
+        _ => None,
+    });
+    let params = make::param_list(None, params);
+
+    let default_call = || {
+        make::expr_call(
+            make::expr_path(make::path_from_text("Default::default")),
+            make::arg_list(None),
         )
-        .clone_for_update();
-        fn_.indent(1.into());
+    };
+    let fields = field_list.fields().zip(inits).filter_map(|(f, init)| {
+        let name_ref = make::name_ref(&f.name()?.text());
+        match init {
+            FieldInit::Param => Some(make::record_expr_field(name_ref, None)),
+            FieldInit::Default if use_struct_update => None,
+            FieldInit::Default => Some(make::record_expr_field(name_ref, Some(default_call()))),
+            FieldInit::Expr(expr) => Some(make::record_expr_field(name_ref, Some(expr))),
+        }
+    });
+    let fields = match use_struct_update {
+        true => make::record_expr_field_list_with_spread(fields, default_call()),
+        false => make::record_expr_field_list(fields),
+    };
 
-        // Add a tabstop before the name
-        if let Some(cap) = ctx.config.snippet_cap {
-            if let Some(name) = fn_.name() {
-                builder.add_tabstop_before(cap, name);
-            }
+    let record_expr = make::record_expr(make::ext::ident_path("Self"), fields);
+    let body = make::block_expr(None, Some(record_expr.into()));
+
+    let ret_type = make::ret_type(make::ty_path(make::ext::ident_path("Self")));
+
+    let fn_ = make::fn_(
+        strukt.visibility(),
+        make::name("new"),
+        None,
+        None,
+        params,
+        body,
+        Some(ret_type),
+        false,
+        false,
+        false,
+    )
+    .clone_for_update();
+    fn_.indent(1.into());
+
+    // Add a tabstop before the name
+    if let Some(cap) = ctx.config.snippet_cap {
+        if let Some(name) = fn_.name() {
+            builder.add_tabstop_before(cap, name);
         }
+    }
 
-        // Get the mutable version of the impl to modify
-        let impl_def = if let Some(impl_def) = impl_def {
-            builder.make_mut(impl_def)
-        } else {
-            // Generate a new impl to add the method to
-            let impl_def = generate_impl(&ast::Adt::Struct(strukt.clone()));
+    // Get the mutable version of the impl to modify
+    let impl_def = if let Some(impl_def) = impl_def {
+        builder.make_mut(impl_def)
+    } else {
+        // Generate a new impl to add the method to
+        let impl_def = generate_impl(&ast::Adt::Struct(strukt.clone()));
 
-            // Insert it after the adt
-            let strukt = builder.make_mut(strukt.clone());
+        // Insert it after the adt
+        let strukt = builder.make_mut(strukt.clone());
 
-            ted::insert_all_raw(
-                ted::Position::after(strukt.syntax()),
-                vec![make::tokens::blank_line().into(), impl_def.syntax().clone().into()],
-            );
+        ted::insert_all_raw(
+            ted::Position::after(strukt.syntax()),
+            vec![make::tokens::blank_line().into(), impl_def.syntax().clone().into()],
+        );
 
-            impl_def
-        };
+        impl_def
+    };
 
-        // Add the `new` method at the start of the impl
-        impl_def.get_or_create_assoc_item_list().add_item_at_start(fn_.into());
-    })
+    // Add the `new` method at the start of the impl
+    impl_def.get_or_create_assoc_item_list().add_item_at_start(fn_.into());
 }
 
 #[cfg(test)]
@@ -553,6 +652,105 @@ impl<T> Source<T> {
         Source { file_id: self.file_id, ast: f(self.ast) }
     }
 }
+"#,
+        );
+    }
+
+    #[test]
+    fn generate_new_with_defaults_per_field() {
+        check_assist(
+            generate_new_with_defaults,
+            r#"
+//- minicore: default, builtin_impls
+struct Empty;
+struct Name(char);
+pub struct Entry {
+    pub name: Name,
+    $0hits: u64,
+    empty: Empty,
+    weight: f32,
+}
+"#,
+            r#"
+struct Empty;
+struct Name(char);
+pub struct Entry {
+    pub name: Name,
+    hits: u64,
+    empty: Empty,
+    weight: f32,
+}
+
+impl Entry {
+    pub fn $0new(name: Name) -> Self {
+        Self { name, hits: Default::default(), empty: Empty, weight: Default::default() }
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn generate_new_with_defaults_struct_update() {
+        check_assist(
+            generate_new_with_defaults,
+            r#"
+//- minicore: default, derive, builtin_impls
+struct Name(char);
+#[derive(Default)]
+struct Entry { name: Name, hits: u64 $0}
+"#,
+            r#"
+struct Name(char);
+#[derive(Default)]
+struct Entry { name: Name, hits: u64 }
+
+impl Entry {
+    fn $0new(name: Name) -> Self {
+        Self { name, ..Default::default() }
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn generate_new_with_defaults_no_struct_update_in_manual_default() {
+        // `default` calls `new`, so `..Default::default()` would recurse.
+        check_assist(
+            generate_new_with_defaults,
+            r#"
+//- minicore: default, builtin_impls
+struct Name(char);
+struct Entry { name: Name, hits: u64 $0}
+impl Default for Entry {
+    fn default() -> Self { Self::new(Name('a')) }
+}
+"#,
+            r#"
+struct Name(char);
+struct Entry { name: Name, hits: u64 }
+
+impl Entry {
+    fn $0new(name: Name) -> Self {
+        Self { name, hits: Default::default() }
+    }
+}
+impl Default for Entry {
+    fn default() -> Self { Self::new(Name('a')) }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn generate_new_with_defaults_not_applicable_without_defaults() {
+        check_assist_not_applicable(
+            generate_new_with_defaults,
+            r#"
+//- minicore: default
+struct Name(char);
+struct Entry { name: Name $0}
 "#,
         );
     }
@@ -0,0 +1,204 @@
+use ide_db::famous_defs::FamousDefs;
+use syntax::{
+    ast::{self, edit_in_place::Indent, make, AstNode, HasGenericParams, HasVisibility},
+    ted,
+};
+
+use crate::{
+    utils::{find_struct_impl, generate_impl},
+    AssistContext, AssistId, AssistKind, Assists,
+};
+
+// Assist: generate_new_from_default
+//
+// Generates a `new` method delegating to the type's `Default` implementation.
+//
+// ```
+// # //- minicore: default, derive
+// #[derive(Default)]
+// struct Example$0 { _inner: () }
+// ```
+// ->
+// ```
+// #[derive(Default)]
+// struct Example { _inner: () }
+//
+// impl Example {
+//     fn $0new() -> Self {
+//         Self::default()
+//     }
+// }
+// ```
+pub(crate) fn generate_new_from_default(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    let adt = ctx.find_node_at_offset::<ast::Adt>()?;
+    if adt.generic_param_list().map_or(false, |it| it.type_or_const_params().next().is_some()) {
+        // `Self::default()` would need extra `Default` bounds on the parameters.
+        cov_mark::hit!(generate_new_from_default_generic_adt);
+        return None;
+    }
+
+    let db = ctx.db();
+    let adt_def = ctx.sema.to_def(&adt)?;
+    let krate = adt_def.module(db).krate();
+    let default_trait = FamousDefs(&ctx.sema, krate).core_default_Default()?;
+    if !adt_def.ty(db).impls_trait(db, default_trait, &[]) {
+        return None;
+    }
+
+    // Return early if there already is a `new` fn
+    let impl_def = find_struct_impl(ctx, &adt, &[String::from("new")])?;
+
+    let target = adt.syntax().text_range();
+    acc.add(
+        AssistId("generate_new_from_default", AssistKind::Generate),
+        "Generate `new` from the Default impl",
+        target,
+        |builder| {
+            let body = make::block_expr(
+                None,
+                Some(make::expr_call(
+                    make::expr_path(make::path_from_text("Self::default")),
+                    make::arg_list(None),
+                )),
+            );
+            let ret_type = make::ret_type(make::ty_path(make::ext::ident_path("Self")));
+            let fn_ = make::fn_(
+                adt.visibility(),
+                make::name("new"),
+                None,
+                None,
+                make::param_list(None, None),
+                body,
+                Some(ret_type),
+                false,
+                false,
+                false,
+            )
+            .clone_for_update();
+            fn_.indent(1.into());
+
+            if let Some(cap) = ctx.config.snippet_cap {
+                if let Some(name) = fn_.name() {
+                    builder.add_tabstop_before(cap, name);
+                }
+            }
+
+            let impl_def = match impl_def {
+                Some(impl_def) => builder.make_mut(impl_def),
+                None => {
+                    let impl_def = generate_impl(&adt);
+                    let adt = builder.make_mut(adt.clone());
+                    ted::insert_all_raw(
+                        ted::Position::after(adt.syntax()),
+                        vec![make::tokens::blank_line().into(), impl_def.syntax().clone().into()],
+                    );
+                    impl_def
+                }
+            };
+            impl_def.get_or_create_assoc_item_list().add_item_at_start(fn_.into());
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn new_from_derived_default() {
+        check_assist(
+            generate_new_from_default,
+            r#"
+//- minicore: default, derive
+#[derive(Default)]
+pub struct Example$0 { _inner: () }
+"#,
+            r#"
+#[derive(Default)]
+pub struct Example { _inner: () }
+
+impl Example {
+    pub fn $0new() -> Self {
+        Self::default()
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn new_from_manual_default_into_existing_impl() {
+        check_assist(
+            generate_new_from_default,
+            r#"
+//- minicore: default
+enum Mode$0 { A, B }
+
+impl Default for Mode {
+    fn default() -> Self { Mode::A }
+}
+
+impl Mode {
+    fn flip(self) -> Self { self }
+}
+"#,
+            r#"
+enum Mode { A, B }
+
+impl Default for Mode {
+    fn default() -> Self { Mode::A }
+}
+
+impl Mode {
+    fn $0new() -> Self {
+        Self::default()
+    }
+
+    fn flip(self) -> Self { self }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_without_default() {
+        check_assist_not_applicable(
+            generate_new_from_default,
+            r#"
+//- minicore: default
+struct Example$0 { _inner: () }
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_with_existing_new() {
+        check_assist_not_applicable(
+            generate_new_from_default,
+            r#"
+//- minicore: default, derive
+#[derive(Default)]
+struct Example$0 { _inner: () }
+
+impl Example {
+    fn new() -> Self { Self { _inner: () } }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_generic_types() {
+        cov_mark::check!(generate_new_from_default_generic_adt);
+        check_assist_not_applicable(
+            generate_new_from_default,
+            r#"
+//- minicore: default, derive
+#[derive(Default)]
+struct Example$0<T> { inner: T }
+"#,
+        );
+    }
+}
@@ -0,0 +1,391 @@
+use itertools::Itertools;
+use stdx::to_lower_snake_case;
+use syntax::{
+    ast::{self, edit::IndentLevel, HasAttrs, HasModuleItem, HasName},
+    AstNode, SyntaxNode, TextSize,
+};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: generate_unit_test
+//
+// Adds a unit test stub for the function under the cursor to the module's `tests` module,
+// creating it if needed.
+//
+// ```
+// fn add$0(a: u32, b: u32) -> u32 {
+//     a + b
+// }
+// ```
+// ->
+// ```
+// fn add(a: u32, b: u32) -> u32 {
+//     a + b
+// }
+//
+// #[cfg(test)]
+// mod tests {
+//     use super::*;
+//
+//     #[test]
+//     fn test_add() {
+//         let a: u32 = 0;
+//         let b: u32 = 0;
+//         let result = add(a, b);
+//         assert_eq!(result, todo!());
+//     }
+// }
+// ```
+pub(crate) fn generate_unit_test(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    let name = ctx.find_node_at_offset::<ast::Name>()?;
+    let ast_func = name.syntax().parent().and_then(ast::Fn::cast)?;
+    if ast_func.async_token().is_some() || ast_func.body().is_none() {
+        return None;
+    }
+    if crate::utils::test_related_attribute(&ast_func).is_some() {
+        return None;
+    }
+    let function = ctx.sema.to_def(&ast_func)?;
+    let db = ctx.db();
+
+    // The item list of the module the function (or its impl) is defined in.
+    let container =
+        ast_func.syntax().ancestors().skip(1).find(|it| {
+            ast::SourceFile::can_cast(it.kind()) || ast::ItemList::can_cast(it.kind())
+        })?;
+    let callee = match ast_func.syntax().parent().and_then(ast::AssocItemList::cast) {
+        Some(assoc_items) => {
+            let impl_ = assoc_items.syntax().parent().and_then(ast::Impl::cast)?;
+            if impl_.trait_().is_some() {
+                return None;
+            }
+            Callee::Method(impl_.self_ty()?)
+        }
+        None if container == *ast_func.syntax().parent()? => Callee::Free,
+        // Functions local to a block can't be reached from the tests.
+        None => return None,
+    };
+
+    let test_name = format!("test_{name}");
+    // The file and item container of an existing `tests` module, which may be a file of its own.
+    let tests_module = match tests_module(&container) {
+        Some(module) => Some(match module.item_list() {
+            Some(item_list) => (ctx.file_id(), item_list.syntax().clone()),
+            None => {
+                let source = ctx.sema.to_def(&module)?.definition_source(db);
+                let hir::ModuleSource::SourceFile(file) = source.value else { return None };
+                (source.file_id.original_file(db), file.syntax().clone())
+            }
+        }),
+        None => None,
+    };
+    if let Some((_, tests_container)) = &tests_module {
+        let exists = items(tests_container).into_iter().any(|item| match item {
+            ast::Item::Fn(f) => f.name().map_or(false, |it| it.text() == test_name),
+            _ => false,
+        });
+        if exists {
+            cov_mark::hit!(generate_unit_test_exists);
+            return None;
+        }
+    }
+
+    let mut body = Vec::new();
+    let mut args = Vec::new();
+    let params = ast_func.param_list()?;
+    for (idx, (param, ast_param)) in
+        function.params_without_self(db).into_iter().zip(params.params()).enumerate()
+    {
+        let arg = match ast_param.pat() {
+            Some(ast::Pat::IdentPat(pat)) if pat.pat().is_none() => pat.name()?.to_string(),
+            _ => format!("arg{idx}"),
+        };
+        let ty = ast_param.ty()?;
+        body.push(format!("let {arg}: {ty} = {};", placeholder(param.ty())));
+        args.push(arg);
+    }
+    let args = args.join(", ");
+    let call = match callee {
+        Callee::Free => format!("{name}({args})"),
+        Callee::Method(self_ty) => match params.self_param() {
+            Some(self_param) => {
+                let receiver = self_ty
+                    .syntax()
+                    .descendants()
+                    .filter_map(ast::NameRef::cast)
+                    .next()
+                    .map_or("receiver".to_owned(), |it| to_lower_snake_case(&it.text()));
+                let mut_ = match self_param.amp_token().and(self_param.mut_token()) {
+                    Some(_) => "mut ",
+                    None => "",
+                };
+                body.insert(0, format!("let {mut_}{receiver}: {self_ty} = todo!();"));
+                format!("{receiver}.{name}({args})")
+            }
+            None => format!("{self_ty}::{name}({args})"),
+        },
+    };
+    if function.ret_type(db).is_unit() {
+        body.push(format!("{call};"));
+    } else {
+        body.push(format!("let result = {call};"));
+        body.push("assert_eq!(result, todo!());".to_owned());
+    }
+
+    acc.add(
+        AssistId("generate_unit_test", AssistKind::Generate),
+        format!("Generate unit test for `{name}`"),
+        name.syntax().text_range(),
+        |builder| {
+            let (offset, indent) = end_of_items(&container);
+            match tests_module {
+                Some((file_id, tests_container)) => {
+                    builder.edit_file(file_id);
+                    let (end, indent) = end_of_items(&tests_container);
+                    let test_fn = test_fn(&test_name, &body, indent);
+                    builder.insert(end, format!("\n\n{test_fn}"));
+                    let test_items = items(&tests_container);
+                    let imports_super = test_items.iter().any(|item| match item {
+                        ast::Item::Use(it) => it.syntax().text() == "use super::*;",
+                        _ => false,
+                    });
+                    if !imports_super {
+                        match ast::ItemList::cast(tests_container) {
+                            Some(item_list) => {
+                                if let Some(l_curly) = item_list.l_curly_token() {
+                                    builder.insert(
+                                        l_curly.text_range().end(),
+                                        format!("\n{indent}use super::*;\n"),
+                                    );
+                                }
+                            }
+                            // Inner attributes and doc comments stay at the top of the file.
+                            None => {
+                                let start = test_items
+                                    .first()
+                                    .map_or(end, |it| it.syntax().text_range().start());
+                                builder.insert(start, "use super::*;\n\n");
+                            }
+                        }
+                    }
+                }
+                None => {
+                    let test_fn = test_fn(&test_name, &body, indent + 1);
+                    builder.insert(
+                        offset,
+                        format!(
+                            "\n\n{indent}#[cfg(test)]\n{indent}mod tests {{\n{}use super::*;\n\n{test_fn}\n{indent}}}",
+                            indent + 1
+                        ),
+                    );
+                }
+            }
+        },
+    )
+}
+
+enum Callee {
+    Free,
+    Method(ast::Type),
+}
+
+fn tests_module(container: &SyntaxNode) -> Option<ast::Module> {
+    items(container).into_iter().find_map(|item| match item {
+        ast::Item::Module(module)
+            if module.name().map_or(false, |it| it.text() == "tests")
+                && module.attrs().any(|attr| attr.syntax().text() == "#[cfg(test)]") =>
+        {
+            Some(module)
+        }
+        _ => None,
+    })
+}
+
+fn items(container: &SyntaxNode) -> Vec<ast::Item> {
+    match ast::SourceFile::cast(container.clone()) {
+        Some(file) => file.items().collect(),
+        None => {
+            ast::ItemList::cast(container.clone()).map_or(Vec::new(), |it| it.items().collect())
+        }
+    }
+}
+
+/// Returns the offset after the last item of `container` and the indentation of its items.
+fn end_of_items(container: &SyntaxNode) -> (TextSize, IndentLevel) {
+    match ast::ItemList::cast(container.clone()) {
+        Some(item_list) => {
+            let indent = IndentLevel::from_node(item_list.syntax()) + 1;
+            let end = match item_list.items().last() {
+                Some(item) => item.syntax().text_range().end(),
+                None => item_list
+                    .l_curly_token()
+                    .map_or(item_list.syntax().text_range().start(), |it| it.text_range().end()),
+            };
+            (end, indent)
+        }
+        None => {
+            let end = container
+                .children()
+                .last()
+                .map_or(container.text_range().end(), |it| it.text_range().end());
+            (end, IndentLevel(0))
+        }
+    }
+}
+
+fn test_fn(name: &str, body: &[String], indent: IndentLevel) -> String {
+    let body = body.iter().map(|line| format!("{}{line}", indent + 1)).join("\n");
+    format!("{indent}#[test]\n{indent}fn {name}() {{\n{body}\n{indent}}}")
+}
+
+fn placeholder(ty: &hir::Type) -> &'static str {
+    let Some(builtin) = ty.strip_references().as_builtin() else { return "todo!()" };
+    match ty.as_reference() {
+        Some(_) if builtin.is_str() => "\"\"",
+        Some(_) => "todo!()",
+        None if builtin.is_bool() => "false",
+        None if builtin.is_int() || builtin.is_uint() => "0",
+        None if builtin.is_float() => "0.0",
+        None if builtin.is_char() => "'a'",
+        None => "todo!()",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn method_into_existing_tests_module() {
+        check_assist(
+            generate_unit_test,
+            r#"
+mod parse {
+    pub struct Parser { pos: usize }
+
+    impl Parser {
+        pub fn eat$0(&mut self, text: &str, strict: bool) {}
+        pub fn new() -> Parser { Parser { pos: 0 } }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        #[test]
+        fn works() {}
+    }
+}
+"#,
+            r#"
+mod parse {
+    pub struct Parser { pos: usize }
+
+    impl Parser {
+        pub fn eat(&mut self, text: &str, strict: bool) {}
+        pub fn new() -> Parser { Parser { pos: 0 } }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn works() {}
+
+        #[test]
+        fn test_eat() {
+            let mut parser: Parser = todo!();
+            let text: &str = "";
+            let strict: bool = false;
+            parser.eat(text, strict);
+        }
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn associated_fn_with_pattern_param() {
+        check_assist(
+            generate_unit_test,
+            r#"
+struct P;
+impl P {
+    fn from_pair$0((a, b): (u8, u8), c: P) -> P { P }
+}
+"#,
+            r#"
+struct P;
+impl P {
+    fn from_pair((a, b): (u8, u8), c: P) -> P { P }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_pair() {
+        let arg0: (u8, u8) = todo!();
+        let c: P = todo!();
+        let result = P::from_pair(arg0, c);
+        assert_eq!(result, todo!());
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn into_tests_module_file() {
+        check_assist(
+            generate_unit_test,
+            r#"
+//- /main.rs
+fn double$0(x: u32) -> u32 { x * 2 }
+
+#[cfg(test)]
+mod tests;
+//- /tests.rs
+//! Tests.
+
+#[test]
+fn works() {}
+"#,
+            r#"
+//! Tests.
+
+use super::*;
+
+#[test]
+fn works() {}
+
+#[test]
+fn test_double() {
+    let x: u32 = 0;
+    let result = double(x);
+    assert_eq!(result, todo!());
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_if_test_exists() {
+        cov_mark::check!(generate_unit_test_exists);
+        check_assist_not_applicable(
+            generate_unit_test,
+            r#"
+fn f$0() {}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_f() {}
+}
+"#,
+        );
+    }
+}
@@ -0,0 +1,292 @@
+use hir::DescendPreference;
+use ide_db::syntax_helpers::format_string::{
+    is_format_string, lex_format_specifiers, FormatSpecifier,
+};
+use itertools::Itertools;
+use syntax::{
+    ast, AstNode, AstToken, NodeOrToken, SyntaxElement,
+    SyntaxKind::{COMMA, EQ, IDENT},
+    TextRange,
+};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: inline_format_args
+//
+// Moves identifiers passed as positional arguments of a format string into the format string.
+//
+// ```
+// # //- minicore: fmt
+// fn main() {
+//     let (name, age) = ("Ferris", 8);
+//     print!("{} is {:>3}$0", name, age);
+// }
+// ```
+// ->
+// ```
+// fn main() {
+//     let (name, age) = ("Ferris", 8);
+//     print!("{name} is {age:>3}");
+// }
+// ```
+pub(crate) fn inline_format_args(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    let (fmt_string, args) = format_args_at_cursor(ctx)?;
+    let placeholders = placeholders(&fmt_string)?;
+    if placeholders.is_empty()
+        || placeholders.len() != args.args.len()
+        || placeholders.iter().any(|it| it.name.is_some())
+    {
+        return None;
+    }
+    let names = args
+        .args
+        .iter()
+        .map(|arg| match arg.elements.as_slice() {
+            [NodeOrToken::Token(it)] if it.kind() == IDENT => Some(it.text().to_owned()),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    acc.add(
+        AssistId("inline_format_args", AssistKind::RefactorRewrite),
+        "Inline format arguments",
+        fmt_string.syntax().text_range(),
+        |builder| {
+            for (placeholder, name) in placeholders.iter().zip(names) {
+                builder.insert(placeholder.open.end(), name);
+            }
+            builder.delete(args.tail);
+        },
+    )
+}
+
+// Assist: uninline_format_args
+//
+// Moves identifiers captured by a format string out into positional arguments.
+//
+// ```
+// # //- minicore: fmt
+// fn main() {
+//     let (name, age) = ("Ferris", 8);
+//     print!("{name} is {}, {age:>3}$0", age);
+// }
+// ```
+// ->
+// ```
+// fn main() {
+//     let (name, age) = ("Ferris", 8);
+//     print!("{} is {}, {:>3}", name, age, age);
+// }
+// ```
+pub(crate) fn uninline_format_args(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    let (fmt_string, args) = format_args_at_cursor(ctx)?;
+    let placeholders = placeholders(&fmt_string)?;
+    if placeholders.iter().all(|it| it.name.is_none()) {
+        return None;
+    }
+    // Named arguments are referred to by the same syntax as captured variables.
+    let is_named_arg = |arg: &Arg| match arg.elements.as_slice() {
+        [NodeOrToken::Token(name), NodeOrToken::Token(eq), ..] => {
+            name.kind() == IDENT && eq.kind() == EQ
+        }
+        _ => false,
+    };
+    if args.args.iter().any(is_named_arg) {
+        return None;
+    }
+
+    let mut existing_args = args.args.iter().map(|arg| arg.text.clone());
+    let mut new_args = Vec::new();
+    for placeholder in &placeholders {
+        match &placeholder.name {
+            Some((_, name)) => new_args.push(name.clone()),
+            None => new_args.push(existing_args.next()?),
+        }
+    }
+    // Any surplus arguments, which rustc rejects, are kept as they are.
+    new_args.extend(existing_args);
+
+    acc.add(
+        AssistId("uninline_format_args", AssistKind::RefactorRewrite),
+        "Convert to positional format arguments",
+        fmt_string.syntax().text_range(),
+        |builder| {
+            for (range, _) in placeholders.iter().filter_map(|it| it.name.as_ref()) {
+                builder.delete(*range);
+            }
+            let new_args: String = new_args.iter().map(|arg| format!(", {arg}")).collect();
+            builder.replace(args.tail, new_args);
+        },
+    )
+}
+
+/// The arguments following a format string in a macro call.
+struct FormatArgs {
+    args: Vec<Arg>,
+    /// The range from the end of the format string to the closing delimiter.
+    tail: TextRange,
+}
+
+struct Arg {
+    /// The non-trivia elements of the argument.
+    elements: Vec<SyntaxElement>,
+    text: String,
+}
+
+fn format_args_at_cursor(ctx: &AssistContext<'_>) -> Option<(ast::String, FormatArgs)> {
+    let fmt_string = ctx.find_token_at_offset::<ast::String>()?;
+    let tt = fmt_string.syntax().parent().and_then(ast::TokenTree::cast)?;
+    let expanded = ast::String::cast(
+        ctx.sema
+            .descend_into_macros_single(DescendPreference::SameKind, fmt_string.syntax().clone()),
+    )?;
+    if !is_format_string(&expanded) {
+        return None;
+    }
+
+    let mut elements = tt
+        .token_trees_and_tokens()
+        .map(|it| match it {
+            NodeOrToken::Node(tt) => NodeOrToken::Node(tt.syntax().clone()),
+            NodeOrToken::Token(token) => NodeOrToken::Token(token),
+        })
+        .skip_while(|it| it.as_token() != Some(fmt_string.syntax()))
+        .skip(1)
+        .collect::<Vec<_>>();
+    let r_delim = elements.pop()?;
+    let tail = TextRange::new(fmt_string.syntax().text_range().end(), r_delim.text_range().start());
+
+    let mut groups = elements.split(|it| it.kind() == COMMA).map(|group| Arg {
+        elements: group.iter().filter(|it| !it.kind().is_trivia()).cloned().collect(),
+        text: group.iter().join("").trim().to_owned(),
+    });
+    // Nothing but whitespace may come between the format string and the first comma.
+    if !groups.next()?.elements.is_empty() {
+        return None;
+    }
+    let mut args = groups.collect::<Vec<_>>();
+    // Ignore a trailing comma.
+    if args.last().map_or(false, |it| it.elements.is_empty()) {
+        args.pop();
+    }
+    if args.iter().any(|arg| arg.elements.is_empty()) {
+        return None;
+    }
+    Some((fmt_string, FormatArgs { args, tail }))
+}
+
+struct Placeholder {
+    /// The range of the opening `{`.
+    open: TextRange,
+    /// The range and text of a captured identifier.
+    name: Option<(TextRange, String)>,
+}
+
+/// Collects the placeholders of a format string, returns `None` if any of them refers to an
+/// argument by its index or takes its width or precision from an argument.
+fn placeholders(fmt_string: &ast::String) -> Option<Vec<Placeholder>> {
+    let start = fmt_string.syntax().text_range().start();
+    let text = fmt_string.text();
+    let mut placeholders = Vec::new();
+    let mut after_open = false;
+    let mut supported = true;
+    lex_format_specifiers(fmt_string, &mut |range, kind| {
+        match kind {
+            FormatSpecifier::Open => {
+                placeholders.push(Placeholder { open: range + start, name: None });
+            }
+            FormatSpecifier::Identifier if after_open => {
+                if let Some(placeholder) = placeholders.last_mut() {
+                    placeholder.name = Some((range + start, text[range].to_owned()));
+                }
+            }
+            FormatSpecifier::Integer if after_open => supported = false,
+            FormatSpecifier::DollarSign | FormatSpecifier::Asterisk => supported = false,
+            _ => (),
+        }
+        after_open = matches!(kind, FormatSpecifier::Open);
+    });
+    supported.then_some(placeholders)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn inline_with_trailing_comma_and_specs() {
+        check_assist(
+            inline_format_args,
+            r#"
+//- minicore: fmt
+fn f(a: u32, b: &str) {
+    format_args!("$0{}\n{{}} {:?} {:.2}", a, b, a,);
+}
+"#,
+            r#"
+fn f(a: u32, b: &str) {
+    format_args!("{a}\n{{}} {b:?} {a:.2}");
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn inline_not_applicable() {
+        // Not an identifier.
+        check_assist_not_applicable(
+            inline_format_args,
+            r#"
+//- minicore: fmt
+fn f(a: u32) { format_args!("$0{}", a + 1); }
+"#,
+        );
+        // Explicit index.
+        check_assist_not_applicable(
+            inline_format_args,
+            r#"
+//- minicore: fmt
+fn f(a: u32) { format_args!("$0{0}", a); }
+"#,
+        );
+        // Width taken from an argument.
+        check_assist_not_applicable(
+            inline_format_args,
+            r#"
+//- minicore: fmt
+fn f(a: u32, w: usize) { format_args!("$0{:1$}", a, w); }
+"#,
+        );
+    }
+
+    #[test]
+    fn uninline_mixed_placeholders() {
+        check_assist(
+            uninline_format_args,
+            r#"
+//- minicore: fmt
+fn f(a: u32, b: &str) {
+    format_args!("$0{a:>4} {} {b}", a + 1);
+}
+"#,
+            r#"
+fn f(a: u32, b: &str) {
+    format_args!("{:>4} {} {}", a, a + 1, b);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn uninline_not_applicable_with_named_args() {
+        check_assist_not_applicable(
+            uninline_format_args,
+            r#"
+//- minicore: fmt
+fn f() { format_args!("$0{a}", a = 1); }
+"#,
+        );
+    }
+}
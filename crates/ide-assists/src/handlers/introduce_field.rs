@@ -0,0 +1,388 @@
+use hir::{Adt, HasSource, HirDisplay, InRealFile};
+use ide_db::{defs::Definition, famous_defs::FamousDefs, search::FileReference};
+use syntax::{
+    ast::{self, edit::IndentLevel, HasName},
+    AstNode, Direction, SyntaxNode, SyntaxToken, T,
+};
+
+use crate::{
+    assist_context::SourceChangeBuilder,
+    handlers::introduce_parameter::{is_movable, transplant, unique_name},
+    utils::suggest_name,
+    AssistContext, AssistId, AssistKind, Assists,
+};
+
+// Assist: introduce_field
+//
+// Turns the selected expression inside of a method into a new field of the struct, initialized
+// in all of the struct's literals.
+//
+// ```
+// # //- minicore: copy
+// fn step() -> u32 { 10 }
+//
+// struct Counter { count: u32 }
+//
+// impl Counter {
+//     fn new() -> Self {
+//         Self { count: 0 }
+//     }
+//
+//     fn bump(&mut self) {
+//         self.count += $0step()$0;
+//     }
+// }
+// ```
+// ->
+// ```
+// fn step() -> u32 { 10 }
+//
+// struct Counter { count: u32, step: u32 }
+//
+// impl Counter {
+//     fn new() -> Self {
+//         Self { count: 0, step: step() }
+//     }
+//
+//     fn bump(&mut self) {
+//         self.count += self.step;
+//     }
+// }
+// ```
+pub(crate) fn introduce_field(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    if ctx.has_empty_selection() {
+        return None;
+    }
+    let expr = ctx.find_node_at_range::<ast::Expr>()?;
+    if expr.syntax().text_range() != ctx.selection_trimmed() {
+        return None;
+    }
+    let method = expr.syntax().ancestors().find_map(ast::Fn::cast)?;
+    method.param_list()?.self_param()?;
+    let impl_ = method.syntax().parent()?.parent().and_then(ast::Impl::cast)?;
+    if impl_.trait_().is_some() {
+        return None;
+    }
+
+    let db = ctx.db();
+    let Adt::Struct(strukt) = ctx.sema.to_def(&impl_)?.self_ty(db).as_adt()? else { return None };
+    let InRealFile { file_id: strukt_file_id, value: strukt_node } =
+        strukt.source(db)?.original_ast_node_rooted(db)?;
+    let Some(ast::FieldList::RecordFieldList(field_list)) = strukt_node.field_list() else {
+        return None;
+    };
+
+    if !is_movable(&ctx.sema, &expr) {
+        return None;
+    }
+    let module = ctx.sema.scope(expr.syntax())?.module();
+
+    let ty = ctx.sema.type_of_expr(&expr)?.adjusted();
+    if ty.is_unit() || ty.is_unknown() {
+        return None;
+    }
+    let is_copy = ty.is_copy(db);
+    if !is_copy {
+        let clone = FamousDefs(&ctx.sema, module.krate()).core_clone_Clone()?;
+        if !ty.impls_trait(db, clone, &[]) {
+            cov_mark::hit!(introduce_field_not_clone);
+            return None;
+        }
+    }
+    let ty = ty.display_source_code(db, module.into(), false).ok()?;
+
+    let existing_names =
+        field_list.fields().filter_map(|field| Some(field.name()?.to_string())).collect::<Vec<_>>();
+    let name =
+        suggest_name::try_for_variable(&expr, &ctx.sema).unwrap_or_else(|| "new_field".to_owned());
+    let name = unique_name(name, &existing_names);
+
+    let target = expr.syntax().text_range();
+    acc.add(
+        AssistId("introduce_field", AssistKind::RefactorExtract),
+        "Introduce field",
+        target,
+        |builder| {
+            let field_access =
+                if is_copy { format!("self.{name}") } else { format!("self.{name}.clone()") };
+            builder.replace(target, field_access);
+
+            // The field has to be initialized wherever the struct gets constructed.
+            let usages = Definition::Adt(strukt.into()).usages(&ctx.sema).include_self_refs().all();
+            for (file_id, references) in usages {
+                builder.edit_file(file_id);
+                for FileReference { name: reference, .. } in references {
+                    let Some(field_list) = reference.as_name_ref().and_then(record_expr_field_list)
+                    else {
+                        continue;
+                    };
+                    // Functional updates take the new field from the base expression, and
+                    // literals inside of macros are left for the user to update.
+                    if field_list.spread().is_some()
+                        || ctx.sema.hir_file_for(field_list.syntax()).is_macro()
+                    {
+                        continue;
+                    }
+                    let init = transplant(ctx, &expr, field_list.syntax());
+                    append_to_record_list(
+                        builder,
+                        field_list.l_curly_token(),
+                        field_list.fields().last().map(|it| it.syntax().clone()),
+                        &format!("{name}: {init}"),
+                    );
+                }
+            }
+
+            builder.edit_file(strukt_file_id);
+            append_to_record_list(
+                builder,
+                field_list.l_curly_token(),
+                field_list.fields().last().map(|it| it.syntax().clone()),
+                &format!("{name}: {ty}"),
+            );
+        },
+    )
+}
+
+/// Returns the fields of the struct literal whose path ends in `name_ref`.
+fn record_expr_field_list(name_ref: &ast::NameRef) -> Option<ast::RecordExprFieldList> {
+    let path = name_ref.syntax().ancestors().find_map(ast::Path::cast)?;
+    let record_expr = path.syntax().parent().and_then(ast::RecordExpr::cast)?;
+    record_expr.record_expr_field_list()
+}
+
+/// Appends `item` to a `{ a, b }` style list, keeping to the list's layout.
+fn append_to_record_list(
+    builder: &mut SourceChangeBuilder,
+    l_curly: Option<SyntaxToken>,
+    last: Option<SyntaxNode>,
+    item: &str,
+) {
+    let Some(last) = last else {
+        if let Some(l_curly) = l_curly {
+            builder.insert(l_curly.text_range().end(), format!(" {item} "));
+        }
+        return;
+    };
+    let trailing_comma = last
+        .siblings_with_tokens(Direction::Next)
+        .skip(1)
+        .find(|it| !it.kind().is_trivia())
+        .filter(|it| it.kind() == T![,]);
+    let is_multiline = last
+        .prev_sibling_or_token()
+        .and_then(|it| it.into_token())
+        .map_or(false, |it| it.kind().is_trivia() && it.text().contains('\n'));
+
+    let (offset, text) = match (trailing_comma, is_multiline) {
+        (Some(comma), true) => {
+            (comma.text_range().end(), format!("\n{}{item},", IndentLevel::from_node(&last)))
+        }
+        (None, true) => {
+            (last.text_range().end(), format!(",\n{}{item},", IndentLevel::from_node(&last)))
+        }
+        (Some(comma), false) => (comma.text_range().end(), format!(" {item},")),
+        (None, false) => (last.text_range().end(), format!(", {item}")),
+    };
+    builder.insert(offset, text);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn introduce_field_inline_lists() {
+        check_assist(
+            introduce_field,
+            r#"
+//- minicore: copy
+struct Counter { count: u32 }
+
+impl Counter {
+    fn new() -> Self {
+        Self { count: 0 }
+    }
+
+    fn bump(&mut self) {
+        self.count += $010$0;
+    }
+}
+"#,
+            r#"
+struct Counter { count: u32, new_field: u32 }
+
+impl Counter {
+    fn new() -> Self {
+        Self { count: 0, new_field: 10 }
+    }
+
+    fn bump(&mut self) {
+        self.count += self.new_field;
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn introduce_field_multiline_lists() {
+        check_assist(
+            introduce_field,
+            r#"
+//- minicore: copy
+struct Counter {
+    count: u32,
+}
+
+impl Counter {
+    fn new(count: u32) -> Counter {
+        Counter {
+            count
+        }
+    }
+
+    fn bump(&mut self) {
+        self.count += $010$0;
+    }
+}
+"#,
+            r#"
+struct Counter {
+    count: u32,
+    new_field: u32,
+}
+
+impl Counter {
+    fn new(count: u32) -> Counter {
+        Counter {
+            count,
+            new_field: 10,
+        }
+    }
+
+    fn bump(&mut self) {
+        self.count += self.new_field;
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn introduce_field_clones_non_copy_values() {
+        check_assist(
+            introduce_field,
+            r#"
+//- minicore: clone
+struct Name(u8);
+impl Clone for Name { fn clone(&self) -> Self { Name(self.0) } }
+struct S { a: u8 }
+
+impl S {
+    fn new() -> Self { Self { a: 0 } }
+    fn name(&self) -> Name { $0Name(1)$0 }
+}
+"#,
+            r#"
+struct Name(u8);
+impl Clone for Name { fn clone(&self) -> Self { Name(self.0) } }
+struct S { a: u8, name: Name }
+
+impl S {
+    fn new() -> Self { Self { a: 0, name: Name(1) } }
+    fn name(&self) -> Name { self.name.clone() }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn introduce_field_updates_literals_in_other_files() {
+        check_assist(
+            introduce_field,
+            r#"
+//- minicore: copy
+//- /main.rs
+mod other;
+
+struct S { a: u8 }
+
+impl S {
+    fn new() -> Self { Self { a: 0 } }
+    fn get(&self) -> u8 { $01u8$0 }
+}
+
+fn make() -> S {
+    let s = S { a: 1 };
+    S { a: 2, ..s }
+}
+//- /other.rs
+fn make() -> crate::S {
+    crate::S {
+        a: 3,
+    }
+}
+"#,
+            r#"
+//- /main.rs
+mod other;
+
+struct S { a: u8, new_field: u8 }
+
+impl S {
+    fn new() -> Self { Self { a: 0, new_field: 1u8 } }
+    fn get(&self) -> u8 { self.new_field }
+}
+
+fn make() -> S {
+    let s = S { a: 1, new_field: 1u8 };
+    S { a: 2, ..s }
+}
+//- /other.rs
+fn make() -> crate::S {
+    crate::S {
+        a: 3,
+        new_field: 1u8,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn introduce_field_not_applicable_for_non_clone_values() {
+        cov_mark::check!(introduce_field_not_clone);
+        check_assist_not_applicable(
+            introduce_field,
+            r#"
+//- minicore: clone
+struct Name(u8);
+struct S { a: u8 }
+
+impl S {
+    fn new() -> Self { Self { a: 0 } }
+    fn name(&self) -> Name { $0Name(1)$0 }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn introduce_field_not_applicable_in_free_fn() {
+        check_assist_not_applicable(
+            introduce_field,
+            r#"
+struct S { a: u8 }
+
+impl S {
+    fn new() -> Self { Self { a: 0 } }
+}
+
+fn get() -> u8 { $01u8$0 }
+"#,
+        );
+    }
+}
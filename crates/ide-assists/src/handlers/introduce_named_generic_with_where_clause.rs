@@ -0,0 +1,172 @@
+use syntax::{
+    ast::{self, edit_in_place::GenericParamsOwnerEdit, make, AstNode},
+    ted,
+};
+
+use crate::{
+    utils::{suggest_name, turbofish_call_sites},
+    AssistContext, AssistId, AssistKind, Assists,
+};
+
+// Assist: introduce_named_generic_with_where_clause
+//
+// Replaces `impl Trait` function argument with a named generic bounded in the where clause.
+// Calls that specify the generic arguments of the function get a `_` for the new one.
+//
+// ```
+// fn foo(bar: $0impl Bar) {}
+// ```
+// ->
+// ```
+// fn foo<$0B>(bar: B) where B: Bar {}
+// ```
+pub(crate) fn introduce_named_generic_with_where_clause(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let impl_trait_type = ctx.find_node_at_offset::<ast::ImplTraitType>()?;
+    let param = impl_trait_type.syntax().ancestors().find_map(ast::Param::cast)?;
+    let fn_ = param.syntax().ancestors().find_map(ast::Fn::cast)?;
+
+    let type_bound_list = impl_trait_type.type_bound_list()?;
+    let mut call_sites =
+        ctx.sema.to_def(&fn_).map(|func| turbofish_call_sites(&ctx.sema, func)).unwrap_or_default();
+
+    let target = fn_.syntax().text_range();
+    acc.add(
+        AssistId("introduce_named_generic_with_where_clause", AssistKind::RefactorRewrite),
+        "Replace impl trait with generic and where clause",
+        target,
+        |edit| {
+            let impl_trait_type = edit.make_mut(impl_trait_type);
+            let fn_ = edit.make_mut(fn_);
+            let arg_lists = call_sites
+                .remove(&ctx.file_id())
+                .into_iter()
+                .flatten()
+                .map(|it| edit.make_mut(it))
+                .collect::<Vec<_>>();
+
+            let fn_generic_param_list = fn_.get_or_create_generic_param_list();
+            let type_param_name =
+                suggest_name::for_impl_trait_as_generic(&impl_trait_type, &fn_generic_param_list);
+
+            let type_param =
+                make::type_param(make::name(&type_param_name), None).clone_for_update();
+            let new_ty = make::ty(&type_param_name).clone_for_update();
+            let predicate =
+                make::where_pred(make::ext::ident_path(&type_param_name), type_bound_list.bounds())
+                    .clone_for_update();
+
+            ted::replace(impl_trait_type.syntax(), new_ty.syntax());
+            fn_generic_param_list.add_generic_param(type_param.into());
+            fn_.get_or_create_where_clause().add_predicate(predicate);
+
+            if let Some(cap) = ctx.config.snippet_cap {
+                if let Some(generic_param) =
+                    fn_.generic_param_list().and_then(|it| it.generic_params().last())
+                {
+                    edit.add_tabstop_before(cap, generic_param);
+                }
+            }
+
+            // The new parameter comes last, so explicit generic arguments stay valid with an
+            // inferred one appended.
+            add_inferred_arg(arg_lists);
+            for (file_id, arg_lists) in call_sites {
+                edit.edit_file(file_id);
+                add_inferred_arg(arg_lists.into_iter().map(|it| edit.make_mut(it)).collect());
+            }
+        },
+    )
+}
+
+fn add_inferred_arg(arg_lists: Vec<ast::GenericArgList>) {
+    for arg_list in arg_lists {
+        let arg = make::type_arg(make::ty_placeholder()).clone_for_update();
+        arg_list.add_generic_arg(arg.into());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::tests::check_assist;
+
+    #[test]
+    fn introduce_named_generic_with_where_clause_params() {
+        check_assist(
+            introduce_named_generic_with_where_clause,
+            r#"fn foo<G>(bar: $0impl Bar) {}"#,
+            r#"fn foo<G, $0B>(bar: B) where B: Bar {}"#,
+        );
+    }
+
+    #[test]
+    fn extends_existing_where_clause() {
+        check_assist(
+            introduce_named_generic_with_where_clause,
+            r#"fn foo<G>(g: G, bar: $0impl Bar + Baz) -> G where G: Clone {}"#,
+            r#"fn foo<G, $0B>(g: G, bar: B) -> G where G: Clone, B: Bar + Baz {}"#,
+        );
+    }
+
+    #[test]
+    fn keeps_turbofish_call_sites_valid() {
+        check_assist(
+            introduce_named_generic_with_where_clause,
+            r#"
+//- /main.rs
+mod other;
+pub fn foo<G>(bar: $0impl Bar) -> G {}
+fn main() {
+    foo::<u32>(1);
+    foo(1);
+}
+//- /other.rs
+fn f() {
+    crate::foo::<u8>(1);
+}
+"#,
+            r#"
+//- /main.rs
+mod other;
+pub fn foo<G, $0B>(bar: B) -> G where B: Bar {}
+fn main() {
+    foo::<u32, _>(1);
+    foo(1);
+}
+//- /other.rs
+fn f() {
+    crate::foo::<u8, _>(1);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn keeps_method_turbofish_valid() {
+        check_assist(
+            introduce_named_generic_with_where_clause,
+            r#"
+struct S;
+impl S {
+    fn foo<G>(&self, bar: $0impl Bar) {}
+}
+fn main() {
+    S.foo::<u32>(1);
+}
+"#,
+            r#"
+struct S;
+impl S {
+    fn foo<G, $0B>(&self, bar: B) where B: Bar {}
+}
+fn main() {
+    S.foo::<u32, _>(1);
+}
+"#,
+        );
+    }
+}
@@ -2,7 +2,7 @@ use ide_db::FxHashSet;
 use syntax::{
     ast::{self, edit_in_place::GenericParamsOwnerEdit, make, HasGenericParams},
     ted::{self, Position},
-    AstNode, TextRange,
+    AstNode, SyntaxKind, SyntaxNode, TextRange,
 };
 
 use crate::{assist_context::SourceChangeBuilder, AssistContext, AssistId, AssistKind, Assists};
@@ -35,14 +35,33 @@ static ASSIST_LABEL: &str = "Introduce named lifetime";
 // ```
 pub(crate) fn introduce_named_lifetime(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
     // FIXME: How can we handle renaming any one of multiple anonymous lifetimes?
-    // FIXME: should also add support for the case fun(f: &Foo) -> &$0Foo
-    let lifetime =
-        ctx.find_node_at_offset::<ast::Lifetime>().filter(|lifetime| lifetime.text() == "'_")?;
-    let lifetime_loc = lifetime.lifetime_ident_token()?.text_range();
-
-    if let Some(fn_def) = lifetime.syntax().ancestors().find_map(ast::Fn::cast) {
-        generate_fn_def_assist(acc, fn_def, lifetime_loc, lifetime)
-    } else if let Some(impl_def) = lifetime.syntax().ancestors().find_map(ast::Impl::cast) {
+    let anonymous_lifetime = match ctx.find_node_at_offset::<ast::Lifetime>() {
+        Some(lifetime) if lifetime.text() == "'_" => AnonymousLifetime::Explicit(lifetime),
+        Some(_) => return None,
+        None => {
+            // Elided lifetimes are offered on the `&` of references in return types only, the ones
+            // of the parameters are tied to each other by the elision rules.
+            let ref_type =
+                ctx.find_node_at_offset::<ast::RefType>().filter(|it| it.lifetime().is_none())?;
+            let on_amp = ref_type.amp_token()?.text_range().contains_inclusive(ctx.offset());
+            let in_ret_type =
+                ref_type.syntax().ancestors().any(|it| ast::RetType::can_cast(it.kind()));
+            if !on_amp || !in_ret_type {
+                return None;
+            }
+            AnonymousLifetime::Elided(ref_type)
+        }
+    };
+    let lifetime_loc = match &anonymous_lifetime {
+        AnonymousLifetime::Explicit(it) => it.lifetime_ident_token()?.text_range(),
+        AnonymousLifetime::Elided(it) => it.amp_token()?.text_range(),
+    };
+
+    if let Some(fn_def) = anonymous_lifetime.syntax().ancestors().find_map(ast::Fn::cast) {
+        generate_fn_def_assist(acc, fn_def, lifetime_loc, anonymous_lifetime)
+    } else if let Some(impl_def) = anonymous_lifetime.syntax().ancestors().find_map(ast::Impl::cast)
+    {
+        let AnonymousLifetime::Explicit(lifetime) = anonymous_lifetime else { return None };
         generate_impl_def_assist(acc, impl_def, lifetime_loc, lifetime)
     } else {
         None
@@ -54,7 +73,7 @@ fn generate_fn_def_assist(
     acc: &mut Assists,
     fn_def: ast::Fn,
     lifetime_loc: TextRange,
-    lifetime: ast::Lifetime,
+    anonymous_lifetime: AnonymousLifetime,
 ) -> Option<()> {
     let param_list: ast::ParamList = fn_def.param_list()?;
     let new_lifetime_param = generate_unique_lifetime_param_name(fn_def.generic_param_list())?;
@@ -83,16 +102,41 @@ fn generate_fn_def_assist(
             _ => return None,
         }
     };
+    // All anonymous lifetimes of the return type stand for the same lifetime, so they get named
+    // together.
+    let mut anonymous_lifetimes = fn_def
+        .ret_type()
+        .filter(|it| it.syntax().text_range().contains_range(lifetime_loc))
+        .map(|ret_type| {
+            ret_type
+                .syntax()
+                .descendants()
+                .filter_map(AnonymousLifetime::cast)
+                .filter(|it| it.syntax() != anonymous_lifetime.syntax())
+                // Lifetimes in the signatures of `Fn` traits and fn pointers aren't tied to ours.
+                .filter(|it| {
+                    !it.syntax().ancestors().take_while(|node| node != ret_type.syntax()).any(
+                        |node| matches!(node.kind(), SyntaxKind::PARAM_LIST | SyntaxKind::RET_TYPE),
+                    )
+                })
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    anonymous_lifetimes.push(anonymous_lifetime);
+
     acc.add(AssistId(ASSIST_NAME, AssistKind::Refactor), ASSIST_LABEL, lifetime_loc, |builder| {
         let fn_def = builder.make_mut(fn_def);
-        let lifetime = builder.make_mut(lifetime);
+        let anonymous_lifetimes =
+            anonymous_lifetimes.into_iter().map(|it| it.make_mut(builder)).collect::<Vec<_>>();
         let loc_needing_lifetime =
             loc_needing_lifetime.and_then(|it| it.make_mut(builder).to_position());
 
         fn_def.get_or_create_generic_param_list().add_generic_param(
             make::lifetime_param(new_lifetime_param.clone()).clone_for_update().into(),
         );
-        ted::replace(lifetime.syntax(), new_lifetime_param.clone_for_update().syntax());
+        for anonymous_lifetime in anonymous_lifetimes {
+            anonymous_lifetime.name(&new_lifetime_param);
+        }
         if let Some(position) = loc_needing_lifetime {
             ted::insert(position, new_lifetime_param.clone_for_update().syntax());
         }
@@ -134,6 +178,50 @@ fn generate_unique_lifetime_param_name(
     .map(|it| make::lifetime(&it))
 }
 
+/// An anonymous lifetime to give a name to.
+enum AnonymousLifetime {
+    /// An explicit `'_`.
+    Explicit(ast::Lifetime),
+    /// The lifetime of a reference that doesn't spell it out.
+    Elided(ast::RefType),
+}
+
+impl AnonymousLifetime {
+    fn cast(node: SyntaxNode) -> Option<Self> {
+        if let Some(lifetime) = ast::Lifetime::cast(node.clone()) {
+            return (lifetime.text() == "'_").then_some(Self::Explicit(lifetime));
+        }
+        let ref_type = ast::RefType::cast(node)?;
+        ref_type.lifetime().is_none().then_some(Self::Elided(ref_type))
+    }
+
+    fn syntax(&self) -> &SyntaxNode {
+        match self {
+            Self::Explicit(it) => it.syntax(),
+            Self::Elided(it) => it.syntax(),
+        }
+    }
+
+    fn make_mut(self, builder: &mut SourceChangeBuilder) -> Self {
+        match self {
+            Self::Explicit(it) => Self::Explicit(builder.make_mut(it)),
+            Self::Elided(it) => Self::Elided(builder.make_mut(it)),
+        }
+    }
+
+    fn name(self, lifetime: &ast::Lifetime) {
+        let lifetime = lifetime.clone_for_update();
+        match self {
+            Self::Explicit(it) => ted::replace(it.syntax(), lifetime.syntax()),
+            Self::Elided(it) => {
+                if let Some(amp) = it.amp_token() {
+                    ted::insert(Position::after(amp), lifetime.syntax());
+                }
+            }
+        }
+    }
+}
+
 enum NeedsLifetime {
     SelfParam(ast::SelfParam),
     RefType(ast::RefType),
@@ -335,4 +423,27 @@ mod tests {
             r#"fn foo<'a>(&'a mut self) -> &'a ()"#,
         );
     }
+
+    #[test]
+    fn test_function_return_value_elided_reference_lifetime() {
+        check_assist(
+            introduce_named_lifetime,
+            r#"fn my_fun(f: &Foo) -> &$0Foo"#,
+            r#"fn my_fun<'a>(f: &'a Foo) -> &'a Foo"#,
+        );
+    }
+
+    #[test]
+    fn test_function_names_all_anonymous_lifetimes_of_return_type() {
+        check_assist(
+            introduce_named_lifetime,
+            r#"fn my_fun(&self) -> (X<'_$0>, &Y, Z<'_>)"#,
+            r#"fn my_fun<'a>(&'a self) -> (X<'a>, &'a Y, Z<'a>)"#,
+        );
+    }
+
+    #[test]
+    fn test_not_applicable_for_elided_param_lifetime() {
+        check_assist_not_applicable(introduce_named_lifetime, r#"fn my_fun(f: &$0Foo) {}"#);
+    }
 }
@@ -0,0 +1,458 @@
+use hir::{HirDisplay, PathResolution, Semantics};
+use ide_db::{
+    defs::Definition, path_transform::PathTransform, search::FileReference, RootDatabase,
+};
+use syntax::{
+    ast::{self, make, HasArgList, HasName},
+    match_ast, AstNode,
+    SyntaxKind::{AWAIT_EXPR, BREAK_EXPR, CONTINUE_EXPR, RETURN_EXPR, TRY_EXPR, YIELD_EXPR},
+    SyntaxNode, TextSize,
+};
+
+use crate::{
+    assist_context::SourceChangeBuilder, utils::suggest_name, AssistContext, AssistId, AssistKind,
+    Assists,
+};
+
+// Assist: introduce_parameter
+//
+// Turns the selected expression into a new parameter of the enclosing function, passing the
+// expression in at every call site instead.
+//
+// ```
+// fn default_scale() -> u32 { 100 }
+//
+// fn scale(x: u32) -> u32 {
+//     x * $0default_scale()$0
+// }
+//
+// fn main() {
+//     scale(1);
+// }
+// ```
+// ->
+// ```
+// fn default_scale() -> u32 { 100 }
+//
+// fn scale(x: u32, default_scale: u32) -> u32 {
+//     x * default_scale
+// }
+//
+// fn main() {
+//     scale(1, default_scale());
+// }
+// ```
+pub(crate) fn introduce_parameter(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    if ctx.has_empty_selection() {
+        return None;
+    }
+    let expr = ctx.find_node_at_range::<ast::Expr>()?;
+    if expr.syntax().text_range() != ctx.selection_trimmed() {
+        return None;
+    }
+    let func = expr.syntax().ancestors().find_map(ast::Fn::cast)?;
+    let param_list = func.param_list()?;
+    let in_trait_or_trait_impl =
+        func.syntax().parent().and_then(|it| it.parent()).map_or(false, |it| {
+            match_ast! {
+                match it {
+                    ast::Trait(_) => true,
+                    ast::Impl(it) => it.trait_().is_some(),
+                    _ => false,
+                }
+            }
+        });
+    if in_trait_or_trait_impl {
+        return None;
+    }
+
+    if !is_movable(&ctx.sema, &expr) {
+        return None;
+    }
+    let module = ctx.sema.scope(expr.syntax())?.module();
+    let ty = ctx.sema.type_of_expr(&expr)?.adjusted();
+    if ty.is_unit() || ty.is_unknown() {
+        return None;
+    }
+    let ty = ty.display_source_code(ctx.db(), module.into(), false).ok()?;
+    let fn_def = ctx.sema.to_def(&func)?;
+
+    let existing_names = param_list
+        .params()
+        .filter_map(|param| match param.pat()? {
+            ast::Pat::IdentPat(it) => Some(it.name()?.to_string()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let name =
+        suggest_name::try_for_variable(&expr, &ctx.sema).unwrap_or_else(|| "new_param".to_owned());
+    let name = unique_name(name, &existing_names);
+
+    // Where the function is used as a value, it gets wrapped in a closure passing the new
+    // argument.
+    let closure_params = param_list
+        .self_param()
+        .map(|_| "this".to_owned())
+        .into_iter()
+        .chain(param_list.params().enumerate().map(|(idx, param)| match param.pat() {
+            Some(ast::Pat::IdentPat(it)) if it.pat().is_none() => {
+                it.name().map_or_else(|| format!("arg{idx}"), |it| it.to_string())
+            }
+            _ => format!("arg{idx}"),
+        }))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let target = expr.syntax().text_range();
+    acc.add(
+        AssistId("introduce_parameter", AssistKind::RefactorExtract),
+        "Introduce parameter",
+        target,
+        |builder| {
+            let last_param = param_list
+                .params()
+                .last()
+                .map(|it| it.syntax().clone())
+                .or_else(|| param_list.self_param().map(|it| it.syntax().clone()));
+            match last_param {
+                Some(last) => builder.insert(last.text_range().end(), format!(", {name}: {ty}")),
+                None => {
+                    if let Some(l_paren) = param_list.l_paren_token() {
+                        builder.insert(l_paren.text_range().end(), format!("{name}: {ty}"));
+                    }
+                }
+            }
+            builder.replace(target, &name);
+
+            let usages = Definition::Function(fn_def).usages(&ctx.sema).all();
+            for (file_id, references) in usages {
+                builder.edit_file(file_id);
+                for FileReference { name: reference, range, .. } in references {
+                    let Some(name_ref) = reference.as_name_ref() else { continue };
+                    // Usages inside of macros are left for the user to update, and usages inside
+                    // of the expression are gone.
+                    if ctx.sema.hir_file_for(name_ref.syntax()).is_macro()
+                        || (file_id == ctx.file_id() && target.contains_range(range))
+                    {
+                        continue;
+                    }
+                    if let Some(arg_list) = call_arg_list(name_ref) {
+                        let arg = transplant(ctx, &expr, arg_list.syntax());
+                        append_arg(builder, &arg_list, &arg);
+                    } else if let Some(path_expr) = value_path_expr(name_ref) {
+                        let arg = transplant(ctx, &expr, path_expr.syntax());
+                        let args = if closure_params.is_empty() {
+                            arg
+                        } else {
+                            format!("{closure_params}, {arg}")
+                        };
+                        builder.replace(
+                            path_expr.syntax().text_range(),
+                            format!("|{closure_params}| {path_expr}({args})"),
+                        );
+                    }
+                }
+            }
+        },
+    )
+}
+
+/// Returns `false` if the expression can't be moved out of its body, because it uses locals,
+/// generic parameters or `Self`, or affects the control flow of its body.
+pub(crate) fn is_movable(sema: &Semantics<'_, RootDatabase>, expr: &ast::Expr) -> bool {
+    for node in expr.syntax().descendants() {
+        if matches!(
+            node.kind(),
+            RETURN_EXPR | BREAK_EXPR | CONTINUE_EXPR | TRY_EXPR | AWAIT_EXPR | YIELD_EXPR
+        ) {
+            return false;
+        }
+        match_ast! {
+            match node {
+                ast::RecordExprField(it) => {
+                    // shorthand `Foo { local }`
+                    if it.expr().is_none() {
+                        return false;
+                    }
+                },
+                ast::Path(it) => {
+                    if let Some(
+                        PathResolution::Local(_)
+                        | PathResolution::TypeParam(_)
+                        | PathResolution::ConstParam(_)
+                        | PathResolution::SelfType(_),
+                    ) = sema.resolve_path(&it)
+                    {
+                        return false;
+                    }
+                },
+                _ => (),
+            }
+        }
+    }
+    true
+}
+
+/// Returns the text of `expr`, with its paths rewritten to resolve the same way at `site`.
+pub(crate) fn transplant(ctx: &AssistContext<'_>, expr: &ast::Expr, site: &SyntaxNode) -> String {
+    let (Some(source_scope), Some(target_scope)) =
+        (ctx.sema.scope(expr.syntax()), ctx.sema.scope(site))
+    else {
+        return expr.to_string();
+    };
+    if source_scope.module() == target_scope.module() {
+        return expr.to_string();
+    }
+    // The expression is wrapped, as a path making up all of it gets replaced.
+    let holder = make::arg_list([expr.clone()]).clone_for_update();
+    PathTransform::generic_transformation(&target_scope, &source_scope).apply(holder.syntax());
+    holder.args().next().map_or_else(|| expr.to_string(), |it| it.to_string())
+}
+
+pub(crate) fn unique_name(name: String, existing: &[String]) -> String {
+    if !existing.contains(&name) {
+        return name;
+    }
+    (1..).map(|idx| format!("{name}{idx}")).find(|it| !existing.contains(it)).unwrap()
+}
+
+fn call_arg_list(name_ref: &ast::NameRef) -> Option<ast::ArgList> {
+    if let Some(method_call) = name_ref.syntax().parent().and_then(ast::MethodCallExpr::cast) {
+        return method_call.arg_list();
+    }
+    let path_expr = name_ref.syntax().ancestors().find_map(ast::PathExpr::cast)?;
+    let call = path_expr.syntax().parent().and_then(ast::CallExpr::cast)?;
+    call.arg_list()
+}
+
+/// Returns the path expression `name_ref` is the last segment of, if any.
+fn value_path_expr(name_ref: &ast::NameRef) -> Option<ast::PathExpr> {
+    let path = name_ref.syntax().ancestors().find_map(ast::Path::cast)?;
+    path.syntax().parent().and_then(ast::PathExpr::cast)
+}
+
+fn append_arg(builder: &mut SourceChangeBuilder, arg_list: &ast::ArgList, arg: &str) {
+    let (offset, text): (TextSize, _) = match arg_list.args().last() {
+        Some(last) => (last.syntax().text_range().end(), format!(", {arg}")),
+        None => match arg_list.l_paren_token() {
+            Some(l_paren) => (l_paren.text_range().end(), arg.to_owned()),
+            None => return,
+        },
+    };
+    builder.insert(offset, text);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn introduce_parameter_updates_call_sites() {
+        check_assist(
+            introduce_parameter,
+            r#"
+fn scale(x: u32) -> u32 {
+    x * $0100u32$0
+}
+
+fn main() {
+    scale(1);
+    let y = scale(scale(2));
+}
+"#,
+            r#"
+fn scale(x: u32, new_param: u32) -> u32 {
+    x * new_param
+}
+
+fn main() {
+    scale(1, 100u32);
+    let y = scale(scale(2, 100u32), 100u32);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn introduce_parameter_into_empty_param_list() {
+        check_assist(
+            introduce_parameter,
+            r#"
+const LIMIT: usize = 10;
+
+fn limit() -> usize {
+    $0LIMIT * 2$0
+}
+
+fn main() {
+    limit();
+}
+"#,
+            r#"
+const LIMIT: usize = 10;
+
+fn limit(new_param: usize) -> usize {
+    new_param
+}
+
+fn main() {
+    limit(LIMIT * 2);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn introduce_parameter_method() {
+        check_assist(
+            introduce_parameter,
+            r#"
+struct S;
+
+impl S {
+    fn f(&self, var_name: u8) -> u8 {
+        var_name + $01u8$0
+    }
+}
+
+fn main() {
+    S.f(0);
+    S::f(&S, 0);
+}
+"#,
+            r#"
+struct S;
+
+impl S {
+    fn f(&self, var_name: u8, new_param: u8) -> u8 {
+        var_name + new_param
+    }
+}
+
+fn main() {
+    S.f(0, 1u8);
+    S::f(&S, 0, 1u8);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn introduce_parameter_not_applicable_with_locals() {
+        check_assist_not_applicable(
+            introduce_parameter,
+            r#"
+fn scale(x: u32) -> u32 {
+    $0x * 2$0
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn introduce_parameter_paths_in_other_modules() {
+        check_assist(
+            introduce_parameter,
+            r#"
+const LIMIT: u32 = 10;
+
+fn limit() -> u32 {
+    $0LIMIT$0
+}
+
+mod user {
+    fn f() -> u32 {
+        super::limit()
+    }
+}
+"#,
+            r#"
+const LIMIT: u32 = 10;
+
+fn limit(new_param: u32) -> u32 {
+    new_param
+}
+
+mod user {
+    fn f() -> u32 {
+        super::limit(super::LIMIT)
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn introduce_parameter_wraps_function_values() {
+        check_assist(
+            introduce_parameter,
+            r#"
+fn scale(x: u32, (y, _): (u32, u32)) -> u32 {
+    x * y * $02u32$0
+}
+
+fn main() {
+    let f = scale;
+}
+"#,
+            r#"
+fn scale(x: u32, (y, _): (u32, u32), new_param: u32) -> u32 {
+    x * y * new_param
+}
+
+fn main() {
+    let f = |x, arg1| scale(x, arg1, 2u32);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn introduce_parameter_wraps_method_values() {
+        check_assist(
+            introduce_parameter,
+            r#"
+struct S;
+
+impl S {
+    fn f(&self, x: u32) -> u32 {
+        x * $02u32$0
+    }
+}
+
+fn main() {
+    let g = S::f;
+}
+"#,
+            r#"
+struct S;
+
+impl S {
+    fn f(&self, x: u32, new_param: u32) -> u32 {
+        x * new_param
+    }
+}
+
+fn main() {
+    let g = |this, x| S::f(this, x, 2u32);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn introduce_parameter_not_applicable_in_trait_impl() {
+        check_assist_not_applicable(
+            introduce_parameter,
+            r#"
+trait T { fn f(&self) -> u32; }
+struct S;
+impl T for S {
+    fn f(&self) -> u32 { $02u32$0 }
+}
+"#,
+        );
+    }
+}
@@ -3,11 +3,12 @@ use ide_db::FxHashMap;
 use std::iter::successors;
 use syntax::{
     algo::neighbor,
-    ast::{self, AstNode, HasName},
-    Direction,
+    ast::{self, edit::IndentLevel, make, AstNode, HasName},
+    ted::{self, Position},
+    Direction, T,
 };
 
-use crate::{AssistContext, AssistId, AssistKind, Assists, TextRange};
+use crate::{AssistContext, AssistId, AssistKind, Assists};
 
 // Assist: merge_match_arms
 //
@@ -69,24 +70,41 @@ pub(crate) fn merge_match_arms(acc: &mut Assists, ctx: &AssistContext<'_>) -> Op
         current_text_range,
         |edit| {
             let pats = if arms_to_merge.iter().any(contains_placeholder) {
-                "_".into()
+                vec![make::wildcard_pat().into()]
             } else {
-                arms_to_merge
-                    .iter()
-                    .filter_map(ast::MatchArm::pat)
-                    .map(|x| x.syntax().to_string())
-                    .collect::<Vec<String>>()
-                    .join(" | ")
+                arms_to_merge.iter().filter_map(ast::MatchArm::pat).collect()
             };
-
-            let arm = format!("{pats} => {current_expr},");
-
-            if let [first, .., last] = &*arms_to_merge {
-                let start = first.syntax().text_range().start();
-                let end = last.syntax().text_range().end();
-
-                edit.replace(TextRange::new(start, end), arm);
+            let merged_arm = make::match_arm(pats, None, current_expr).clone_for_update();
+
+            let arms_to_merge =
+                arms_to_merge.into_iter().map(|arm| edit.make_mut(arm)).collect::<Vec<_>>();
+            let Some((first, rest)) = arms_to_merge.split_first() else { return };
+            let indent = IndentLevel::from_node(first.syntax());
+
+            // Keep the comments of the removed arms above the merged one.
+            let mut comments = Vec::new();
+            for arm in rest {
+                if let Some(comma) =
+                    arm.syntax().next_sibling_or_token().filter(|it| it.kind() == T![,])
+                {
+                    ted::remove(comma);
+                }
+                comments.extend(ted::attached_comments(arm.syntax()));
+                comments.extend(ted::trailing_comment(arm.syntax()));
+                ted::remove_with_comments(arm.syntax());
             }
+            let comments = comments
+                .into_iter()
+                .flat_map(|comment| {
+                    [comment.into(), make::tokens::whitespace(&format!("\n{indent}")).into()]
+                })
+                .collect();
+            ted::insert_all_raw(Position::before(first.syntax()), comments);
+
+            if !first.syntax().next_sibling_or_token().map_or(false, |it| it.kind() == T![,]) {
+                ted::insert_raw(Position::after(first.syntax()), make::token(T![,]));
+            }
+            ted::replace(first.syntax(), merged_arm.syntax());
         },
     )
 }
@@ -298,6 +316,42 @@ fn main() {
         )
     }
 
+    #[test]
+    fn keeps_comments_of_merged_arms() {
+        check_assist(
+            merge_match_arms,
+            r#"
+enum X { A, B, C, D }
+
+fn main() {
+    match X::A {
+        // The common case.
+        X::A$0 => 92,
+        // Only happens on retries.
+        X::B => 92, // see #1234
+        X::C => 92,
+
+        X::D => 62,
+    }
+}
+"#,
+            r#"
+enum X { A, B, C, D }
+
+fn main() {
+    match X::A {
+        // The common case.
+        // Only happens on retries.
+        // see #1234
+        X::A | X::B | X::C => 92,
+
+        X::D => 62,
+    }
+}
+"#,
+        )
+    }
+
     #[test]
     fn merge_match_arms_rejects_guards() {
         check_assist_not_applicable(
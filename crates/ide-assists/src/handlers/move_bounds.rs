@@ -2,12 +2,17 @@ use syntax::{
     ast::{
         self,
         edit_in_place::{GenericParamsOwnerEdit, Removable},
-        make, AstNode, HasName, HasTypeBounds,
+        make, AstNode, HasGenericParams, HasName, HasTypeBounds,
     },
     match_ast,
+    ted::{self, Position},
+    T,
 };
 
-use crate::{AssistContext, AssistId, AssistKind, Assists};
+use crate::{
+    utils::{bounded_type_param, remove_where_clause},
+    AssistContext, AssistId, AssistKind, Assists,
+};
 
 // Assist: move_bounds_to_where_clause
 //
@@ -82,11 +87,87 @@ fn build_predicate(param: ast::TypeParam) -> Option<ast::WherePred> {
     Some(predicate.clone_for_update())
 }
 
+// Assist: move_where_clause_to_bounds
+//
+// Moves the predicates of a where clause onto the generic parameters they constrain.
+//
+// ```
+// fn apply<T, U, F>(f: F, x: T) -> U where $0F: FnOnce(T) -> U {
+//     f(x)
+// }
+// ```
+// ->
+// ```
+// fn apply<T, U, F: FnOnce(T) -> U>(f: F, x: T) -> U {
+//     f(x)
+// }
+// ```
+pub(crate) fn move_where_clause_to_bounds(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let where_clause = ctx.find_node_at_offset::<ast::WhereClause>()?;
+    let parent = where_clause.syntax().parent()?;
+    let generic_param_list = ast::AnyHasGenericParams::cast(parent)?.generic_param_list()?;
+
+    let movable = where_clause
+        .predicates()
+        .filter_map(|pred| {
+            let type_param = bounded_type_param(&generic_param_list, &pred)?;
+            Some((pred, type_param))
+        })
+        .collect::<Vec<_>>();
+    if movable.is_empty() {
+        return None;
+    }
+
+    let target = where_clause.syntax().text_range();
+    acc.add(
+        AssistId("move_where_clause_to_bounds", AssistKind::RefactorRewrite),
+        "Move to generic parameters",
+        target,
+        |edit| {
+            let where_clause = edit.make_mut(where_clause);
+            let movable = movable
+                .into_iter()
+                .map(|(pred, type_param)| (edit.make_mut(pred), edit.make_mut(type_param)))
+                .collect::<Vec<_>>();
+
+            for (pred, type_param) in movable {
+                let existing = type_param.type_bound_list();
+                let bounds = existing
+                    .iter()
+                    .flat_map(|it| it.bounds())
+                    .chain(pred.type_bound_list().into_iter().flat_map(|it| it.bounds()));
+                let Some(bounds) = make::type_bound_list(bounds) else { continue };
+                let bounds = bounds.clone_for_update();
+                match (existing, type_param.name()) {
+                    (Some(existing), _) => ted::replace(existing.syntax(), bounds.syntax()),
+                    (None, Some(name)) => ted::insert_all_raw(
+                        Position::after(name.syntax()),
+                        vec![
+                            make::token(T![:]).into(),
+                            make::tokens::single_space().into(),
+                            bounds.syntax().clone().into(),
+                        ],
+                    ),
+                    (None, None) => continue,
+                }
+                where_clause.remove_predicate(pred);
+            }
+
+            if where_clause.predicates().next().is_none() {
+                remove_where_clause(where_clause);
+            }
+        },
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    use crate::tests::check_assist;
+    use crate::tests::{check_assist, check_assist_not_applicable};
 
     #[test]
     fn move_bounds_to_where_clause_fn() {
@@ -123,4 +204,48 @@ mod tests {
             r#"struct Pair<T>(T, T) where T: u32;"#,
         );
     }
+
+    #[test]
+    fn move_where_clause_to_bounds_merges_bounds() {
+        check_assist(
+            move_where_clause_to_bounds,
+            r#"fn foo<T: Clone, U>() where T: Copy, U::Item: Clone, $0U: Iterator {}"#,
+            r#"fn foo<T: Clone + Copy, U: Iterator>() where U::Item: Clone {}"#,
+        );
+    }
+
+    #[test]
+    fn move_where_clause_to_bounds_multiline() {
+        check_assist(
+            move_where_clause_to_bounds,
+            r#"
+fn foo<T>(t: T)
+where
+    $0T: Copy,
+{
+}
+"#,
+            r#"
+fn foo<T: Copy>(t: T) {
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn move_where_clause_to_bounds_tuple_struct() {
+        check_assist(
+            move_where_clause_to_bounds,
+            r#"struct Pair<T>(T, T) where $0T: u32;"#,
+            r#"struct Pair<T: u32>(T, T);"#,
+        );
+    }
+
+    #[test]
+    fn move_where_clause_to_bounds_not_applicable_for_other_types() {
+        check_assist_not_applicable(
+            move_where_clause_to_bounds,
+            r#"fn foo<T>() where $0for<'a> &'a T: Copy, Vec<T>: Clone {}"#,
+        );
+    }
 }
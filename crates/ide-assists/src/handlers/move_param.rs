@@ -0,0 +1,244 @@
+use ide_db::{base_db::FileId, defs::Definition, search::FileReference};
+use syntax::{
+    ast::{self, make, HasArgList},
+    match_ast, ted, AstNode, Direction, SyntaxNode,
+};
+
+use crate::{
+    assist_context::SourceChangeBuilder, utils::fn_call_args_at, AssistContext, AssistId,
+    AssistKind, Assists,
+};
+
+// Assist: move_param_left
+//
+// Moves a function parameter one position to the left, swapping the arguments at all call sites.
+//
+// ```
+// fn frobnicate(x: i32, y: bool$0) {}
+//
+// fn main() {
+//     frobnicate(92, true);
+// }
+// ```
+// ->
+// ```
+// fn frobnicate(y: bool, x: i32) {}
+//
+// fn main() {
+//     frobnicate(true, 92);
+// }
+// ```
+pub(crate) fn move_param_left(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    move_param(acc, ctx, Direction::Prev)
+}
+
+// Assist: move_param_right
+//
+// Moves a function parameter one position to the right, swapping the arguments at all call sites.
+//
+// ```
+// fn frobnicate(x: i32$0, y: bool) {}
+//
+// fn main() {
+//     frobnicate(92, true);
+// }
+// ```
+// ->
+// ```
+// fn frobnicate(y: bool, x: i32) {}
+//
+// fn main() {
+//     frobnicate(true, 92);
+// }
+// ```
+pub(crate) fn move_param_right(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    move_param(acc, ctx, Direction::Next)
+}
+
+fn move_param(acc: &mut Assists, ctx: &AssistContext<'_>, dir: Direction) -> Option<()> {
+    let param: ast::Param = ctx.find_node_at_offset()?;
+    let param_list = param.syntax().parent().and_then(ast::ParamList::cast)?;
+    let func = param_list.syntax().parent().and_then(ast::Fn::cast)?;
+    let in_trait_or_trait_impl =
+        func.syntax().parent().and_then(|it| it.parent()).map_or(false, |it| {
+            match_ast! {
+                match it {
+                    ast::Trait(_) => true,
+                    ast::Impl(it) => it.trait_().is_some(),
+                    _ => false,
+                }
+            }
+        });
+    if in_trait_or_trait_impl {
+        cov_mark::hit!(move_param_trait_method);
+        return None;
+    }
+
+    let params = param_list.params().collect::<Vec<_>>();
+    let idx = params.iter().position(|it| *it == param)?;
+    let other_idx = match dir {
+        Direction::Prev => idx.checked_sub(1)?,
+        Direction::Next => idx + 1,
+    };
+    let other = params.get(other_idx)?.clone();
+    let swapped_args = (idx.min(other_idx), idx.max(other_idx));
+    let has_self_param = param_list.self_param().is_some();
+    let fn_def = Definition::Function(ctx.sema.to_def(&func)?);
+
+    let (id, label) = match dir {
+        Direction::Prev => ("move_param_left", "Move parameter left"),
+        Direction::Next => ("move_param_right", "Move parameter right"),
+    };
+    acc.add(AssistId(id, AssistKind::Refactor), label, param.syntax().text_range(), |builder| {
+        let mut usages = fn_def.usages(&ctx.sema).all();
+        let current_file_usages = usages.references.remove(&ctx.file_id()).unwrap_or_default();
+
+        let mut swaps = vec![(param.syntax().clone(), other.syntax().clone())];
+        swaps.extend(call_site_swaps(
+            ctx,
+            ctx.file_id(),
+            current_file_usages,
+            has_self_param,
+            swapped_args,
+        ));
+        swap_nodes(builder, swaps);
+
+        for (file_id, references) in usages {
+            builder.edit_file(file_id);
+            let swaps = call_site_swaps(ctx, file_id, references, has_self_param, swapped_args);
+            swap_nodes(builder, swaps);
+        }
+    })
+}
+
+fn call_site_swaps(
+    ctx: &AssistContext<'_>,
+    file_id: FileId,
+    references: Vec<FileReference>,
+    has_self_param: bool,
+    (first, second): (usize, usize),
+) -> Vec<(SyntaxNode, SyntaxNode)> {
+    let source_file = ctx.sema.parse(file_id);
+    references
+        .into_iter()
+        .filter_map(|FileReference { range, .. }| {
+            let (arg_list, offset) = fn_call_args_at(&source_file, range, has_self_param)?;
+            let args = arg_list.args().collect::<Vec<_>>();
+            let first = args.get(offset + first)?;
+            let second = args.get(offset + second)?;
+            Some((first.syntax().clone(), second.syntax().clone()))
+        })
+        .collect()
+}
+
+fn swap_nodes(builder: &mut SourceChangeBuilder, swaps: Vec<(SyntaxNode, SyntaxNode)>) {
+    // All nodes have to be made mutable before any of them moves, as nested calls get moved
+    // around along with the arguments containing them.
+    let swaps = swaps
+        .into_iter()
+        .map(|(a, b)| (builder.make_syntax_mut(a), builder.make_syntax_mut(b)))
+        .collect::<Vec<_>>();
+    for (a, b) in swaps {
+        let placeholder = make::tokens::single_space();
+        placeholder.detach();
+        ted::replace(&a, &placeholder);
+        ted::replace(&b, &a);
+        ted::replace(&placeholder, &b);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn move_param_updates_calls_and_method_calls() {
+        check_assist(
+            move_param_right,
+            r#"
+struct S;
+impl S {
+    fn f(&self, a$0: u8, b: bool, c: char) {}
+}
+fn main() {
+    S.f(1, true, 'c');
+    S::f(&S, 1, true, 'c');
+}
+"#,
+            r#"
+struct S;
+impl S {
+    fn f(&self, b: bool, a: u8, c: char) {}
+}
+fn main() {
+    S.f(true, 1, 'c');
+    S::f(&S, true, 1, 'c');
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn move_param_nested_calls() {
+        check_assist(
+            move_param_left,
+            r#"
+fn f(a: u8, b$0: u8) -> u8 { a }
+fn main() {
+    f(f(1, 2), f(3, 4));
+}
+"#,
+            r#"
+fn f(b: u8, a: u8) -> u8 { a }
+fn main() {
+    f(f(4, 3), f(2, 1));
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn move_param_skips_macro_calls() {
+        check_assist(
+            move_param_left,
+            r#"
+macro_rules! id { ($($t:tt)*) => { $($t)* } }
+fn f(a: u8, b$0: u8) {}
+fn main() {
+    f(1, 2);
+    id!(f(1, 2));
+}
+"#,
+            r#"
+macro_rules! id { ($($t:tt)*) => { $($t)* } }
+fn f(b: u8, a: u8) {}
+fn main() {
+    f(2, 1);
+    id!(f(1, 2));
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn move_param_not_applicable_at_the_edges() {
+        check_assist_not_applicable(move_param_left, r#"fn f(a$0: u8, b: u8) {}"#);
+        check_assist_not_applicable(move_param_right, r#"fn f(a: u8, b$0: u8) {}"#);
+    }
+
+    #[test]
+    fn move_param_not_applicable_in_trait_impl() {
+        cov_mark::check!(move_param_trait_method);
+        check_assist_not_applicable(
+            move_param_left,
+            r#"
+trait T { fn f(a: u8, b: u8); }
+impl T for () {
+    fn f(a: u8, b$0: u8) {}
+}
+"#,
+        );
+    }
+}
@@ -0,0 +1,221 @@
+use ide_db::imports::insert_use::{organize_imports as organize, ImportScope};
+use itertools::Itertools;
+use syntax::{
+    ast::{self, edit::IndentLevel},
+    AstNode, Direction, SyntaxKind, TextRange,
+};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: organize_imports
+//
+// Merges, sorts and groups the `use` items of the file according to the configured import
+// granularity and grouping. Clients asking for `source.organizeImports` actions get it anywhere in
+// the file, otherwise it is offered on `use` items.
+//
+// ```
+// use std::fmt::Display;
+// use crate::$0foo::Bar;
+// use std::fmt::Debug;
+// use regex::Regex;
+// ```
+// ->
+// ```
+// use std::fmt::{Debug, Display};
+//
+// use regex::Regex;
+//
+// use crate::foo::Bar;
+// ```
+pub(crate) fn organize_imports(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    let use_item = ctx.find_node_at_offset::<ast::Use>();
+    let requested = ctx
+        .config
+        .allowed
+        .as_ref()
+        .map_or(false, |it| it.contains(&AssistKind::SourceOrganizeImports));
+    if use_item.is_none() && !requested {
+        return None;
+    }
+
+    let mut edits = Vec::new();
+    for first in ctx.source_file().syntax().descendants().filter_map(ast::Use::cast) {
+        // Each run of `use` items, only separated by whitespace, is organized from its first item.
+        if !adjacent_uses(&first, Direction::Prev).is_empty() {
+            continue;
+        }
+        let Some(scope) = ImportScope::find_insert_use_container(first.syntax(), &ctx.sema) else {
+            continue;
+        };
+        let mut uses = vec![first.clone()];
+        uses.extend(adjacent_uses(&first, Direction::Next));
+
+        let range = TextRange::new(
+            first.syntax().text_range().start(),
+            uses.last()?.syntax().text_range().end(),
+        );
+        let indent = IndentLevel::from_node(first.syntax());
+        let organized = organize(&scope, &uses, &ctx.config.insert_use)
+            .iter()
+            .map(|group| group.iter().join(&format!("\n{indent}")))
+            .join(&format!("\n\n{indent}"));
+        if organized != ctx.source_file().syntax().text().slice(range).to_string() {
+            edits.push((range, organized));
+        }
+    }
+    if edits.is_empty() {
+        cov_mark::hit!(organize_imports_already_organized);
+        return None;
+    }
+
+    let target = use_item
+        .map_or_else(|| ctx.source_file().syntax().text_range(), |it| it.syntax().text_range());
+    acc.add(
+        AssistId("organize_imports", AssistKind::SourceOrganizeImports),
+        "Organize imports",
+        target,
+        |builder| {
+            for (range, organized) in edits {
+                builder.replace(range, organized);
+            }
+        },
+    )
+}
+
+/// The `use` items following `use_item` in `direction`, only separated by whitespace.
+fn adjacent_uses(use_item: &ast::Use, direction: Direction) -> Vec<ast::Use> {
+    use_item
+        .syntax()
+        .siblings_with_tokens(direction)
+        .skip(1)
+        .take_while(|it| matches!(it.kind(), SyntaxKind::WHITESPACE | SyntaxKind::USE))
+        .filter_map(|it| it.into_node().and_then(ast::Use::cast))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use ide_db::imports::insert_use::{ImportGranularity, InsertUseConfig};
+
+    use crate::{
+        tests::{check_assist, check_assist_not_applicable, check_assist_with_config, TEST_CONFIG},
+        AssistConfig,
+    };
+
+    use super::*;
+
+    #[test]
+    fn merges_and_groups() {
+        check_assist(
+            organize_imports,
+            r#"
+mod foo {
+    use super::Baz;
+    use self::bar::{X, Y};
+    use core::mem;
+    use crate::Qux;$0
+    use self::bar::Z;
+    use core::fmt::{Display, self};
+
+    fn f() {}
+}
+"#,
+            r#"
+mod foo {
+    use core::{fmt::{self, Display}, mem};
+
+    use crate::Qux;
+
+    use self::bar::{X, Y, Z};
+
+    use super::Baz;
+
+    fn f() {}
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn flattens_with_item_granularity() {
+        check_assist_with_config(
+            AssistConfig {
+                insert_use: InsertUseConfig {
+                    granularity: ImportGranularity::Item,
+                    group: false,
+                    ..TEST_CONFIG.insert_use
+                },
+                ..TEST_CONFIG
+            },
+            organize_imports,
+            r#"
+use std::{io::{self, Write}, fmt};
+$0use crate::a as b;
+"#,
+            r#"
+use crate::a as b;
+use std::fmt;
+use std::io;
+use std::io::Write;
+"#,
+        );
+    }
+
+    #[test]
+    fn organizes_whole_file_on_request() {
+        check_assist_with_config(
+            AssistConfig { allowed: Some(vec![AssistKind::SourceOrganizeImports]), ..TEST_CONFIG },
+            organize_imports,
+            r#"
+use std::fmt;
+use crate::a;
+
+mod m {
+    use super::b;
+    use core::mem;
+}
+
+fn f() {$0}
+"#,
+            r#"
+use std::fmt;
+
+use crate::a;
+
+mod m {
+    use core::mem;
+
+    use super::b;
+}
+
+fn f() {}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_outside_of_imports_without_request() {
+        check_assist_not_applicable(
+            organize_imports,
+            r#"
+use std::fmt;
+use crate::a;
+
+fn f() {$0}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_organized() {
+        cov_mark::check!(organize_imports_already_organized);
+        check_assist_not_applicable(
+            organize_imports,
+            r#"
+use std::fmt;
+
+$0use crate::a;
+"#,
+        );
+    }
+}
@@ -1,4 +1,6 @@
-use hir::{HirDisplay, ModuleDef, PathResolution, Semantics};
+use std::iter;
+
+use hir::{HirDisplay, ModuleDef, PathResolution, ScopeDef, Semantics};
 use ide_db::{
     assists::{AssistId, AssistKind},
     defs::Definition,
@@ -7,12 +9,18 @@ use ide_db::{
 };
 use stdx::to_upper_snake_case;
 use syntax::{
-    ast::{self, make, HasName},
-    ted, AstNode, WalkEvent,
+    ast::{
+        self,
+        edit::{AstNodeEdit, IndentLevel},
+        make, HasName,
+    },
+    ted::{self, Position},
+    AstNode, SyntaxKind, WalkEvent,
 };
 
 use crate::{
-    assist_context::{AssistContext, Assists},
+    assist_config::PromotedConstPlacement,
+    assist_context::{AssistContext, Assists, SourceChangeBuilder},
     utils,
 };
 
@@ -45,6 +53,146 @@ use crate::{
 // }
 // ```
 pub(crate) fn promote_local_to_const(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    let Promotion { name, let_stmt, local, ty, initializer } = promotion(ctx)?;
+
+    acc.add(
+        AssistId("promote_local_to_const", AssistKind::Refactor),
+        "Promote local to constant",
+        let_stmt.syntax().text_range(),
+        |edit| {
+            let name = to_upper_snake_case(&name.to_string());
+            replace_usages(ctx, edit, local, &name);
+
+            let item = make::item_const(None, make::name(&name), make::ty(&ty), initializer)
+                .clone_for_update();
+            let let_stmt = edit.make_mut(let_stmt);
+
+            if let Some((cap, name)) = ctx.config.snippet_cap.zip(item.name()) {
+                edit.add_tabstop_before(cap, name);
+            }
+
+            ted::replace(let_stmt.syntax(), item.syntax());
+        },
+    )
+}
+
+// Assist: promote_local_to_item_const
+//
+// Promotes a local variable to a const item outside of the function, next to the enclosing item
+// or at the top of the module depending on the `assist.promoteLocalToConst.placement` setting.
+//
+// ```
+// fn main() {
+//     let retries$0 = 3;
+//     for _ in 0..retries {}
+// }
+// ```
+// ->
+// ```
+// const $0RETRIES: i32 = 3;
+//
+// fn main() {
+//     for _ in 0..RETRIES {}
+// }
+// ```
+pub(crate) fn promote_local_to_item_const(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let Promotion { name, let_stmt, local, ty, initializer } = promotion(ctx)?;
+    if refers_to_fn_scope(&ctx.sema, &initializer) {
+        cov_mark::hit!(promote_local_to_item_const_uses_fn_scope);
+        return None;
+    }
+    // The outermost item of the module containing the local.
+    let anchor = let_stmt.syntax().ancestors().filter_map(ast::Item::cast).find(|it| {
+        it.syntax().parent().map_or(false, |parent| {
+            matches!(parent.kind(), SyntaxKind::SOURCE_FILE | SyntaxKind::ITEM_LIST)
+        })
+    })?;
+    let module = ctx.sema.scope(anchor.syntax())?.module();
+    if is_value_in_module(ctx.db(), module, &to_upper_snake_case(&name.to_string())) {
+        cov_mark::hit!(promote_local_to_item_const_name_taken);
+        return None;
+    }
+
+    acc.add(
+        AssistId("promote_local_to_item_const", AssistKind::Refactor),
+        "Promote local to module-level constant",
+        let_stmt.syntax().text_range(),
+        |edit| {
+            let name = to_upper_snake_case(&name.to_string());
+            replace_usages(ctx, edit, local, &name);
+
+            let indent = IndentLevel::from_node(anchor.syntax());
+            let dedent =
+                IndentLevel(IndentLevel::from_node(let_stmt.syntax()).0.saturating_sub(indent.0));
+            let item = make::item_const(
+                None,
+                make::name(&name),
+                make::ty(&ty),
+                initializer.dedent(dedent),
+            )
+            .clone_for_update();
+            if let Some((cap, name)) = ctx.config.snippet_cap.zip(item.name()) {
+                edit.add_tabstop_before(cap, name);
+            }
+
+            let first_item = match ctx.config.promoted_const_placement {
+                PromotedConstPlacement::BeforeItem => None,
+                PromotedConstPlacement::ModuleTop => {
+                    anchor.syntax().parent().and_then(|it| it.children().find_map(ast::Item::cast))
+                }
+            };
+            let imports = first_item
+                .iter()
+                .flat_map(|it| it.syntax().siblings(syntax::Direction::Next))
+                .map_while(ast::Item::cast)
+                .take_while(|it| matches!(it, ast::Item::Use(_) | ast::Item::ExternCrate(_)))
+                .last();
+            let anchor = imports.clone().or(first_item).unwrap_or_else(|| anchor.clone());
+            let anchor = edit.make_mut(anchor);
+            let let_stmt = edit.make_mut(let_stmt);
+
+            // The comments describing the local move along with it.
+            let comments = ted::attached_comments(let_stmt.syntax());
+            let trailing_comment = ted::trailing_comment(let_stmt.syntax());
+            ted::remove_with_comments(let_stmt.syntax());
+
+            let mut const_item = Vec::new();
+            for comment in comments {
+                const_item.push(comment.into());
+                const_item.push(make::tokens::whitespace(&format!("\n{indent}")).into());
+            }
+            const_item.push(item.syntax().clone().into());
+            if let Some(comment) = trailing_comment {
+                const_item.push(make::tokens::single_space().into());
+                const_item.push(comment.into());
+            }
+
+            let blank_line = make::tokens::whitespace(&format!("\n\n{indent}"));
+            let elements = match imports {
+                Some(_) => iter::once(blank_line.into()).chain(const_item).collect(),
+                None => const_item.into_iter().chain(iter::once(blank_line.into())).collect(),
+            };
+            let position = match imports {
+                Some(_) => Position::after(anchor.syntax()),
+                None => Position::before(anchor.syntax()),
+            };
+            ted::insert_all_raw(position, elements);
+        },
+    )
+}
+
+struct Promotion {
+    name: ast::Name,
+    let_stmt: ast::LetStmt,
+    local: hir::Local,
+    ty: String,
+    initializer: ast::Expr,
+}
+
+fn promotion(ctx: &AssistContext<'_>) -> Option<Promotion> {
     let pat = ctx.find_node_at_offset::<ast::IdentPat>()?;
     let name = pat.name()?;
     if !pat.is_simple_ident() {
@@ -71,39 +219,65 @@ pub(crate) fn promote_local_to_const(acc: &mut Assists, ctx: &AssistContext<'_>)
         return None;
     }
 
-    acc.add(
-        AssistId("promote_local_to_const", AssistKind::Refactor),
-        "Promote local to constant",
-        let_stmt.syntax().text_range(),
-        |edit| {
-            let name = to_upper_snake_case(&name.to_string());
-            let usages = Definition::Local(local).usages(&ctx.sema).all();
-            if let Some(usages) = usages.references.get(&ctx.file_id()) {
-                let name_ref = make::name_ref(&name);
-
-                for usage in usages {
-                    let Some(usage_name) = usage.name.as_name_ref().cloned() else { continue };
-                    if let Some(record_field) = ast::RecordExprField::for_name_ref(&usage_name) {
-                        let name_expr = make::expr_path(make::path_from_text(&name));
-                        utils::replace_record_field_expr(ctx, edit, record_field, name_expr);
-                    } else {
-                        let usage_range = usage.range;
-                        edit.replace(usage_range, name_ref.syntax().text());
-                    }
-                }
-            }
+    Some(Promotion { name, let_stmt, local, ty, initializer })
+}
 
-            let item = make::item_const(None, make::name(&name), make::ty(&ty), initializer)
-                .clone_for_update();
-            let let_stmt = edit.make_mut(let_stmt);
+fn replace_usages(
+    ctx: &AssistContext<'_>,
+    edit: &mut SourceChangeBuilder,
+    local: hir::Local,
+    name: &str,
+) {
+    let usages = Definition::Local(local).usages(&ctx.sema).all();
+    if let Some(usages) = usages.references.get(&ctx.file_id()) {
+        let name_ref = make::name_ref(name);
 
-            if let Some((cap, name)) = ctx.config.snippet_cap.zip(item.name()) {
-                edit.add_tabstop_before(cap, name);
+        for usage in usages {
+            let Some(usage_name) = usage.name.as_name_ref().cloned() else { continue };
+            if let Some(record_field) = ast::RecordExprField::for_name_ref(&usage_name) {
+                let name_expr = make::expr_path(make::path_from_text(name));
+                utils::replace_record_field_expr(ctx, edit, record_field, name_expr);
+            } else {
+                let usage_range = usage.range;
+                edit.replace(usage_range, name_ref.syntax().text());
             }
+        }
+    }
+}
 
-            ted::replace(let_stmt.syntax(), item.syntax());
-        },
-    )
+/// Whether `expr` uses anything that only exists inside of the function, like other locals,
+/// generic parameters or items declared in its body.
+fn refers_to_fn_scope(sema: &Semantics<'_, RootDatabase>, expr: &ast::Expr) -> bool {
+    expr.syntax().descendants().filter_map(ast::Path::cast).any(|path| {
+        match sema.resolve_path(&path) {
+            Some(
+                PathResolution::Local(_)
+                | PathResolution::TypeParam(_)
+                | PathResolution::ConstParam(_)
+                | PathResolution::SelfType(_),
+            ) => true,
+            Some(PathResolution::Def(def)) => def
+                .module(sema.db)
+                .map_or(false, |module| module.nearest_non_block_module(sema.db) != module),
+            _ => false,
+        }
+    })
+}
+
+/// Whether `module` already has something named `name` in the value namespace.
+fn is_value_in_module(db: &RootDatabase, module: hir::Module, name: &str) -> bool {
+    module.scope(db, None).into_iter().any(|(it, def)| {
+        matches!(
+            def,
+            ScopeDef::ModuleDef(
+                ModuleDef::Function(_)
+                    | ModuleDef::Const(_)
+                    | ModuleDef::Static(_)
+                    | ModuleDef::Variant(_)
+                    | ModuleDef::Adt(hir::Adt::Struct(_))
+            )
+        ) && it.display(db).to_string() == name
+    })
 }
 
 fn is_body_const(sema: &Semantics<'_, RootDatabase>, expr: &ast::Expr) -> bool {
@@ -142,7 +316,10 @@ fn is_body_const(sema: &Semantics<'_, RootDatabase>, expr: &ast::Expr) -> bool {
 
 #[cfg(test)]
 mod tests {
-    use crate::tests::{check_assist, check_assist_not_applicable};
+    use crate::{
+        tests::{check_assist, check_assist_not_applicable, check_assist_with_config, TEST_CONFIG},
+        AssistConfig,
+    };
 
     use super::*;
 
@@ -343,6 +520,144 @@ fn foo() {
 fn foo() {
     let mut x$0 = ();
 }
+",
+        );
+    }
+
+    #[test]
+    fn item_const_before_impl() {
+        check_assist(
+            promote_local_to_item_const,
+            r"
+mod m {
+    struct S;
+
+    impl S {
+        fn f(&self) -> bool {
+            let enabled$0 = !false;
+            enabled
+        }
+    }
+}
+",
+            r"
+mod m {
+    struct S;
+
+    const $0ENABLED: bool = !false;
+
+    impl S {
+        fn f(&self) -> bool {
+            ENABLED
+        }
+    }
+}
+",
+        );
+    }
+
+    #[test]
+    fn item_const_keeps_comments() {
+        check_assist(
+            promote_local_to_item_const,
+            r"
+fn f() -> u32 {
+    let x = 1;
+
+    // How often to retry,
+    // before giving up.
+    let retries$0 = 3; // tuned by hand
+    x + retries
+}
+",
+            r"
+// How often to retry,
+// before giving up.
+const $0RETRIES: u32 = 3; // tuned by hand
+
+fn f() -> u32 {
+    let x = 1;
+
+    x + RETRIES
+}
+",
+        );
+    }
+
+    #[test]
+    fn item_const_at_module_top() {
+        check_assist_with_config(
+            AssistConfig {
+                promoted_const_placement: PromotedConstPlacement::ModuleTop,
+                ..TEST_CONFIG
+            },
+            promote_local_to_item_const,
+            r"
+use core::fmt;
+use core::ops;
+
+struct S;
+
+fn foo() -> usize {
+    let limit$0 = 10;
+    limit + 1
+}
+",
+            r"
+use core::fmt;
+use core::ops;
+
+const $0LIMIT: usize = 10;
+
+struct S;
+
+fn foo() -> usize {
+    LIMIT + 1
+}
+",
+        );
+    }
+
+    #[test]
+    fn item_const_not_applicable_with_local_items() {
+        cov_mark::check!(promote_local_to_item_const_uses_fn_scope);
+        check_assist_not_applicable(
+            promote_local_to_item_const,
+            r"
+fn foo() -> i32 {
+    const BASE: i32 = 1;
+    let y$0 = BASE + 1;
+    y
+}
+",
+        );
+    }
+
+    #[test]
+    fn item_const_not_applicable_if_name_taken() {
+        cov_mark::check!(promote_local_to_item_const_name_taken);
+        check_assist_not_applicable(
+            promote_local_to_item_const,
+            r"
+static LIMIT: usize = 5;
+
+fn foo() -> usize {
+    let limit$0 = 10;
+    limit + LIMIT
+}
+",
+        );
+    }
+
+    #[test]
+    fn item_const_not_applicable_with_other_locals() {
+        cov_mark::check!(promote_local_to_item_const_uses_fn_scope);
+        check_assist_not_applicable(
+            promote_local_to_item_const,
+            r"
+fn foo(x: i32) {
+    let y$0 = x + 1;
+}
 ",
         );
     }
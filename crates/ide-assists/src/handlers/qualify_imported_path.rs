@@ -0,0 +1,271 @@
+use hir::{AsAssocItem, PathResolution, PrefixKind};
+use ide_db::{
+    defs::Definition,
+    helpers::mod_path_to_ast,
+    search::{ReferenceCategory, SearchScope},
+};
+use syntax::{
+    ast::{self, make, HasVisibility},
+    ted, AstNode, SyntaxNode,
+};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: qualify_imported_path
+//
+// Replaces a name brought into scope by a `use` item with its fully-qualified path, removing the
+// import if nothing else in the file needs it anymore and it isn't a re-export.
+//
+// ```
+// mod foo { pub struct Bar; }
+// use foo::Bar;
+//
+// fn f(_: Bar$0) {}
+// ```
+// ->
+// ```
+// mod foo { pub struct Bar; }
+//
+// fn f(_: crate::foo::Bar) {}
+// ```
+pub(crate) fn qualify_imported_path(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    let path: ast::Path = ctx.find_node_at_offset()?;
+    if path.qualifier().is_some() {
+        return None;
+    }
+    if path.syntax().ancestors().any(|it| ast::UseTree::can_cast(it.kind())) {
+        return None;
+    }
+    // Only names that are imported are of interest here, everything else is either already
+    // qualified or local to the file.
+    let def = match ctx.sema.resolve_path(&path)? {
+        PathResolution::Def(def) if def.as_assoc_item(ctx.db()).is_none() => def,
+        _ => return None,
+    };
+
+    let module = ctx.sema.scope(path.syntax())?.module();
+    let mod_path = module.find_use_path_prefixed(
+        ctx.db(),
+        def,
+        PrefixKind::ByCrate,
+        ctx.config.prefer_no_std,
+        ctx.config.prefer_prelude,
+    )?;
+    let qualified = mod_path_to_ast(&mod_path);
+    // Items coming from the prelude don't get any more qualified than they already are.
+    qualified.qualifier()?;
+    let generic_args =
+        path.segment()?.generic_arg_list().map_or(String::new(), |it| it.to_string());
+    let qualified = format!("{qualified}{generic_args}");
+    let import = find_import(ctx, path.syntax(), def)?;
+
+    let path_range = path.syntax().text_range();
+    // Re-exports are used by other modules and crates, which aren't searched here.
+    let is_reexport = import
+        .syntax()
+        .ancestors()
+        .find_map(ast::Use::cast)
+        .map_or(true, |it| it.visibility().is_some());
+    let import_still_used = is_reexport
+        || Definition::from(def)
+            .usages(&ctx.sema)
+            .in_scope(&SearchScope::single_file(ctx.file_id()))
+            .all()
+            .into_iter()
+            .flat_map(|(_, refs)| refs)
+            .filter(|it| it.category != Some(ReferenceCategory::Import))
+            .any(|it| !path_range.contains_range(it.range));
+
+    acc.add(
+        AssistId("qualify_imported_path", AssistKind::RefactorRewrite),
+        format!("Qualify as `{qualified}`"),
+        path_range,
+        |builder| {
+            let import = (!import_still_used).then(|| builder.make_mut(import));
+            let path = builder.make_mut(path);
+            let qualified = make::path_from_text(&qualified).clone_for_update();
+            ted::replace(path.syntax(), qualified.syntax());
+            if let Some(import) = import {
+                import.remove_recursive();
+            }
+        },
+    )
+}
+
+/// Finds the `use` tree that brings `def` into scope at `node`, looking through the `use` items of
+/// all the enclosing item scopes.
+fn find_import(
+    ctx: &AssistContext<'_>,
+    node: &SyntaxNode,
+    def: hir::ModuleDef,
+) -> Option<ast::UseTree> {
+    let items = node.ancestors().flat_map(|scope| {
+        let items = if let Some(file) = ast::SourceFile::cast(scope.clone()) {
+            Some(file.items().collect::<Vec<_>>())
+        } else if let Some(module) = ast::Module::cast(scope.clone()) {
+            module.item_list().map(|it| it.items().collect())
+        } else {
+            ast::StmtList::cast(scope).map(|it| {
+                it.statements()
+                    .filter_map(|stmt| match stmt {
+                        ast::Stmt::Item(item) => Some(item),
+                        _ => None,
+                    })
+                    .collect()
+            })
+        };
+        items.unwrap_or_default()
+    });
+    items
+        .filter_map(|item| match item {
+            ast::Item::Use(it) => it.use_tree(),
+            _ => None,
+        })
+        .flat_map(|tree| tree.syntax().descendants().filter_map(ast::UseTree::cast))
+        .filter(|tree| tree.use_tree_list().is_none() && tree.star_token().is_none())
+        .find(|tree| {
+            let Some(tree_path) = tree.path() else { return false };
+            matches!(
+                ctx.sema.resolve_path(&tree_path),
+                Some(PathResolution::Def(it)) if it == def
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn qualify_and_remove_import() {
+        check_assist(
+            qualify_imported_path,
+            r#"
+mod foo { pub struct Bar; }
+use foo::Bar;
+
+fn f(_: Bar$0) {}
+"#,
+            r#"
+mod foo { pub struct Bar; }
+
+fn f(_: crate::foo::Bar) {}
+"#,
+        );
+    }
+
+    #[test]
+    fn qualify_keeps_import_used_elsewhere() {
+        check_assist(
+            qualify_imported_path,
+            r#"
+mod foo { pub struct Bar; }
+use foo::Bar;
+
+fn f(_: Bar$0) -> Bar { Bar }
+"#,
+            r#"
+mod foo { pub struct Bar; }
+use foo::Bar;
+
+fn f(_: crate::foo::Bar) -> Bar { Bar }
+"#,
+        );
+    }
+
+    #[test]
+    fn qualify_keeps_reexport() {
+        check_assist(
+            qualify_imported_path,
+            r#"
+mod foo { pub struct Bar; pub struct Baz; }
+pub use foo::Bar;
+pub(crate) use foo::Baz;
+
+fn f(_: Bar$0, _: Baz) {}
+"#,
+            r#"
+mod foo { pub struct Bar; pub struct Baz; }
+pub use foo::Bar;
+pub(crate) use foo::Baz;
+
+fn f(_: crate::foo::Bar, _: Baz) {}
+"#,
+        );
+        check_assist(
+            qualify_imported_path,
+            r#"
+mod foo { pub struct Bar; pub struct Baz; }
+pub use foo::Bar;
+pub(crate) use foo::Baz;
+
+fn f(_: Bar, _: Baz$0) {}
+"#,
+            r#"
+mod foo { pub struct Bar; pub struct Baz; }
+pub use foo::Bar;
+pub(crate) use foo::Baz;
+
+fn f(_: Bar, _: crate::foo::Baz) {}
+"#,
+        );
+    }
+
+    #[test]
+    fn qualify_removes_tree_from_use_list() {
+        check_assist(
+            qualify_imported_path,
+            r#"
+mod foo { pub struct Bar<T>(T); pub fn baz() {} }
+use foo::{baz, Bar};
+
+fn f(_: Bar$0<u8>) { baz() }
+"#,
+            r#"
+mod foo { pub struct Bar<T>(T); pub fn baz() {} }
+use foo::baz;
+
+fn f(_: crate::foo::Bar<u8>) { baz() }
+"#,
+        );
+    }
+
+    #[test]
+    fn qualify_function_call() {
+        check_assist(
+            qualify_imported_path,
+            r#"
+mod foo { pub mod bar { pub fn baz() {} } }
+fn f() {
+    use foo::bar::baz;
+    baz$0();
+}
+"#,
+            r#"
+mod foo { pub mod bar { pub fn baz() {} } }
+fn f() {
+    crate::foo::bar::baz();
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_to_qualified_paths_and_locals() {
+        check_assist_not_applicable(
+            qualify_imported_path,
+            r#"
+mod foo { pub struct Bar; }
+fn f(_: foo::Bar$0) {}
+"#,
+        );
+        check_assist_not_applicable(
+            qualify_imported_path,
+            r#"
+fn f(x: u8) -> u8 { x$0 }
+"#,
+        );
+    }
+}
@@ -0,0 +1,300 @@
+use hir::{AsAssocItem, AssocItemContainer, DocLinkDef, ModuleDef, PathResolution};
+use ide_db::{
+    assists::{AssistId, AssistKind},
+    defs::Definition,
+    documentation::HasDocs,
+    helpers::mod_path_to_ast,
+    RootDatabase,
+};
+use syntax::{ast, AstNode};
+
+use crate::{AssistContext, Assists};
+
+// Assist: replace_deprecated_path
+//
+// Replaces a use of a deprecated item with the replacement named by its deprecation note.
+//
+// ```
+// #[deprecated(note = "use `new_fn` instead")]
+// fn old_fn() {}
+// fn new_fn() {}
+//
+// fn main() {
+//     old_fn$0();
+// }
+// ```
+// ->
+// ```
+// #[deprecated(note = "use `new_fn` instead")]
+// fn old_fn() {}
+// fn new_fn() {}
+//
+// fn main() {
+//     new_fn();
+// }
+// ```
+pub(crate) fn replace_deprecated_path(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    let name_ref: ast::NameRef = ctx.find_node_at_offset()?;
+    let parent = name_ref.syntax().parent()?;
+
+    let (def, path) = if let Some(method_call) = ast::MethodCallExpr::cast(parent.clone()) {
+        (Definition::Function(ctx.sema.resolve_method_call(&method_call)?), None)
+    } else {
+        let segment = ast::PathSegment::cast(parent)?;
+        let path = segment.parent_path();
+        if path.segment()? != segment {
+            return None;
+        }
+        let def = match ctx.sema.resolve_path(&path)? {
+            PathResolution::Def(def) => Definition::from(def),
+            _ => return None,
+        };
+        (def, Some(path))
+    };
+
+    let db = ctx.db();
+    let deprecation = def.deprecation(db)?;
+    let replacement = resolve_replacement(db, def, deprecation.replacement()?)?;
+
+    // A method call keeps its receiver, a path is rendered as seen from the use site.
+    let (range, text) = match path {
+        None => {
+            let ModuleDef::Function(func) = replacement else { return None };
+            func.as_assoc_item(db)?;
+            (name_ref.syntax().text_range(), func.name(db).display(db).to_string())
+        }
+        Some(path) => {
+            let module = ctx.sema.scope(path.syntax())?.module();
+            let (item, assoc_name) = match replacement.as_assoc_item(db) {
+                Some(assoc) => {
+                    let container = match assoc.container(db) {
+                        AssocItemContainer::Trait(it) => ModuleDef::Trait(it),
+                        AssocItemContainer::Impl(it) => ModuleDef::Adt(it.self_ty(db).as_adt()?),
+                    };
+                    (container, assoc.name(db))
+                }
+                None => (replacement, None),
+            };
+            let mod_path = module.find_use_path(
+                db,
+                item,
+                ctx.config.prefer_no_std,
+                ctx.config.prefer_prelude,
+            )?;
+            let mut text = mod_path_to_ast(&mod_path).to_string();
+            if let Some(name) = assoc_name {
+                text = format!("{text}::{}", name.display(db));
+            }
+            (path.syntax().text_range(), text)
+        }
+    };
+    if Definition::from(replacement) == def {
+        return None;
+    }
+
+    acc.add(
+        AssistId("replace_deprecated_path", AssistKind::QuickFix),
+        format!("Replace deprecated `{name_ref}` with `{text}`"),
+        range,
+        |builder| builder.replace(range, text),
+    )
+}
+
+/// Resolves the path named by a deprecation note like an intra-doc link on the deprecated item,
+/// falling back to the root of its crate for paths spelled out from there.
+fn resolve_replacement(db: &RootDatabase, def: Definition, path: &str) -> Option<ModuleDef> {
+    let resolved = match def {
+        Definition::Macro(it) => it.resolve_doc_path(db, path, None),
+        Definition::Field(it) => it.resolve_doc_path(db, path, None),
+        Definition::Module(it) => it.resolve_doc_path(db, path, None),
+        Definition::Function(it) => it.resolve_doc_path(db, path, None),
+        Definition::Adt(it) => it.resolve_doc_path(db, path, None),
+        Definition::Variant(it) => it.resolve_doc_path(db, path, None),
+        Definition::Const(it) => it.resolve_doc_path(db, path, None),
+        Definition::Static(it) => it.resolve_doc_path(db, path, None),
+        Definition::Trait(it) => it.resolve_doc_path(db, path, None),
+        Definition::TraitAlias(it) => it.resolve_doc_path(db, path, None),
+        Definition::TypeAlias(it) => it.resolve_doc_path(db, path, None),
+        Definition::ExternCrateDecl(it) => it.resolve_doc_path(db, path, None),
+        _ => None,
+    };
+    let resolved =
+        resolved.or_else(|| def.krate(db)?.root_module().resolve_doc_path(db, path, None))?;
+    match resolved {
+        DocLinkDef::ModuleDef(it) => Some(it),
+        DocLinkDef::Field(_) | DocLinkDef::SelfType(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn replaces_qualified_path() {
+        check_assist(
+            replace_deprecated_path,
+            r#"
+mod foo {
+    #[deprecated(since = "1.0.0", note = "use `foo::bar::new` instead")]
+    pub fn old() {}
+    pub mod bar {
+        pub fn new() {}
+    }
+}
+
+fn main() {
+    foo::old$0();
+}
+"#,
+            r#"
+mod foo {
+    #[deprecated(since = "1.0.0", note = "use `foo::bar::new` instead")]
+    pub fn old() {}
+    pub mod bar {
+        pub fn new() {}
+    }
+}
+
+fn main() {
+    foo::bar::new();
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn keeps_qualifier_for_single_name() {
+        check_assist(
+            replace_deprecated_path,
+            r#"
+mod foo {
+    #[deprecated = "use `new` instead"]
+    pub fn old() {}
+    pub fn new() {}
+}
+
+fn main() {
+    foo::old$0();
+}
+"#,
+            r#"
+mod foo {
+    #[deprecated = "use `new` instead"]
+    pub fn old() {}
+    pub fn new() {}
+}
+
+fn main() {
+    foo::new();
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn renders_path_resolved_next_to_deprecated_item() {
+        check_assist(
+            replace_deprecated_path,
+            r#"
+mod foo {
+    #[deprecated(note = "use `bar::new` instead")]
+    pub fn old() {}
+    pub mod bar {
+        pub fn new() {}
+    }
+}
+use foo::old;
+
+fn main() {
+    old$0();
+}
+"#,
+            r#"
+mod foo {
+    #[deprecated(note = "use `bar::new` instead")]
+    pub fn old() {}
+    pub mod bar {
+        pub fn new() {}
+    }
+}
+use foo::old;
+
+fn main() {
+    foo::bar::new();
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn replaces_method_call_through_trait() {
+        check_assist(
+            replace_deprecated_path,
+            r#"
+trait Tr {
+    #[deprecated(note = "use `Tr::fresh` instead")]
+    fn stale(&self);
+    fn fresh(&self);
+}
+struct S;
+impl Tr for S {
+    fn stale(&self) {}
+    fn fresh(&self) {}
+}
+
+fn f(s: S) {
+    s.stale$0();
+}
+"#,
+            r#"
+trait Tr {
+    #[deprecated(note = "use `Tr::fresh` instead")]
+    fn stale(&self);
+    fn fresh(&self);
+}
+struct S;
+impl Tr for S {
+    fn stale(&self) {}
+    fn fresh(&self) {}
+}
+
+fn f(s: S) {
+    s.fresh();
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_without_replacement() {
+        check_assist_not_applicable(
+            replace_deprecated_path,
+            r#"
+#[deprecated(note = "this will go away")]
+fn old() {}
+
+fn main() {
+    old$0();
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_if_replacement_does_not_resolve() {
+        check_assist_not_applicable(
+            replace_deprecated_path,
+            r#"
+#[deprecated(note = "use `does_not_exist` instead")]
+fn old() {}
+
+fn main() {
+    old$0();
+}
+"#,
+        );
+    }
+}
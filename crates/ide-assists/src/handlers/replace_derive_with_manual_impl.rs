@@ -2,10 +2,14 @@ use hir::{InFile, MacroFileIdExt, ModuleDef};
 use ide_db::{helpers::mod_path_to_ast, imports::import_assets::NameToImport, items_locator};
 use itertools::Itertools;
 use syntax::{
-    ast::{self, make, AstNode, HasName},
+    ast::{
+        self,
+        edit::{AstNodeEdit, IndentLevel},
+        make, AstNode, HasName,
+    },
     ted,
-    SyntaxKind::WHITESPACE,
-    T,
+    SyntaxKind::{self, WHITESPACE},
+    SyntaxNode, SyntaxToken, T,
 };
 
 use crate::{
@@ -14,7 +18,7 @@ use crate::{
         add_trait_assoc_items_to_impl, filter_assoc_items, gen_trait_fn_body, generate_trait_impl,
         DefaultMethods, IgnoreAssocItems,
     },
-    AssistId, AssistKind,
+    AssistConfig, AssistId, AssistKind,
 };
 
 // Assist: replace_derive_with_manual_impl
@@ -136,8 +140,15 @@ fn add_assist(
         |builder| {
             let insert_after = ted::Position::after(builder.make_mut(adt.clone()).syntax());
 
-            let impl_def_with_items =
-                impl_def_from_trait(&ctx.sema, adt, &annotated_name, trait_, replace_trait_path);
+            let impl_def_with_items = impl_def_from_trait(
+                &ctx.sema,
+                ctx.config,
+                attr,
+                adt,
+                &annotated_name,
+                trait_,
+                replace_trait_path,
+            );
             update_attribute(builder, old_derives, old_tree, old_trait_path, attr);
 
             let trait_path = make::ty_path(replace_trait_path.clone());
@@ -195,6 +206,8 @@ fn add_assist(
 
 fn impl_def_from_trait(
     sema: &hir::Semantics<'_, ide_db::RootDatabase>,
+    config: &AssistConfig,
+    attr: &ast::Attr,
     adt: &ast::Adt,
     annotated_name: &ast::Name,
     trait_: Option<hir::Trait>,
@@ -223,12 +236,208 @@ fn impl_def_from_trait(
 
     // Generate a default `impl` function body for the derived trait.
     if let ast::AssocItem::Fn(ref func) = first_assoc_item {
-        let _ = gen_trait_fn_body(func, trait_path, adt, None);
+        if gen_trait_fn_body(func, trait_path, adt, None).is_none() {
+            // We don't know how to implement this trait ourselves (e.g. it is derived by a
+            // proc-macro), so use whatever the derive itself expands to instead.
+            bodies_from_derive_expansion(
+                sema,
+                config,
+                attr,
+                trait_,
+                target_scope.module(),
+                &impl_def,
+            );
+        }
     };
 
     Some((impl_def, first_assoc_item))
 }
 
+/// Replaces the placeholder bodies of the functions in `impl_def` with the bodies of the
+/// corresponding functions in the expansion of the derive.
+fn bodies_from_derive_expansion(
+    sema: &hir::Semantics<'_, ide_db::RootDatabase>,
+    config: &AssistConfig,
+    attr: &ast::Attr,
+    trait_: hir::Trait,
+    module: hir::Module,
+    impl_def: &ast::Impl,
+) -> Option<()> {
+    let trait_name = trait_.name(sema.db).to_smol_str();
+    let expanded_impl = sema
+        .expand_derive_macro(attr)?
+        .into_iter()
+        .flat_map(|it| it.descendants().filter_map(ast::Impl::cast).collect::<Vec<_>>())
+        .find(|it| {
+            let trait_segment = match it.trait_() {
+                Some(ast::Type::PathType(path)) => path.path().and_then(|it| it.segment()),
+                _ => None,
+            };
+            trait_segment.and_then(|it| it.name_ref()).map_or(false, |it| it.text() == trait_name)
+        })?;
+    let expanded_fns = expanded_impl
+        .assoc_item_list()?
+        .assoc_items()
+        .filter_map(|it| match it {
+            ast::AssocItem::Fn(it) => Some(it),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    // Builtin derives refer to the crate defining them through `$crate`, which isn't valid
+    // outside of macros.
+    let derive_krate = sema
+        .resolve_derive_macro(attr)?
+        .into_iter()
+        .flatten()
+        .find(|it| it.name(sema.db).to_smol_str() == trait_name)
+        .map_or_else(|| trait_.module(sema.db).krate(), |it| it.module(sema.db).krate());
+    let krate_path = if derive_krate == module.krate() {
+        make::path_unqualified(make::path_segment_crate())
+    } else {
+        let path = module.find_use_path(
+            sema.db,
+            ModuleDef::Module(derive_krate.root_module()),
+            config.prefer_no_std,
+            config.prefer_prelude,
+        )?;
+        mod_path_to_ast(&path)
+    };
+
+    for item in impl_def.assoc_item_list()?.assoc_items() {
+        let ast::AssocItem::Fn(func) = item else { continue };
+        let (Some(name), Some(body)) = (func.name(), func.body()) else { continue };
+        let Some(expanded_body) = expanded_fns
+            .iter()
+            .find(|it| it.name().map_or(false, |it| it.text() == name.text()))
+            .and_then(|it| it.body())
+        else {
+            continue;
+        };
+
+        let expanded_body = expanded_body.syntax().clone_subtree().clone_for_update();
+        let dollar_crates = expanded_body
+            .descendants()
+            .filter_map(ast::Path::cast)
+            .filter(|it| {
+                it.qualifier().is_none()
+                    && it
+                        .segment()
+                        .and_then(|it| it.name_ref())
+                        .map_or(false, |it| it.text() == "$crate")
+            })
+            .collect::<Vec<_>>();
+        for path in dollar_crates {
+            ted::replace(path.syntax(), krate_path.clone_for_update().syntax());
+        }
+
+        let text = format_expansion(&expanded_body);
+        let Some(new_body) = ast::SourceFile::parse(&format!("fn f() {text}"))
+            .tree()
+            .syntax()
+            .descendants()
+            .find_map(ast::BlockExpr::cast)
+        else {
+            continue;
+        };
+        let new_body = new_body.indent(IndentLevel(1));
+        ted::replace(body.syntax(), new_body.clone_for_update().syntax());
+    }
+    Some(())
+}
+
+/// Renders a node of a macro expansion, which has no whitespace of its own, in the usual style.
+fn format_expansion(node: &SyntaxNode) -> String {
+    let tokens = node
+        .descendants_with_tokens()
+        .filter_map(|it| it.into_token())
+        .filter(|it| !it.kind().is_trivia())
+        .collect::<Vec<_>>();
+    let mut buf = String::new();
+    let mut indent = 0;
+    let mut prev: Option<&SyntaxToken> = None;
+    for (idx, token) in tokens.iter().enumerate() {
+        let next = tokens.get(idx + 1);
+        let is_trailing_comma = token.kind() == T![,]
+            && next.map_or(false, |it| matches!(it.kind(), T![')'] | T!['}']))
+            && token.parent().map_or(false, |it| {
+                matches!(
+                    it.kind(),
+                    SyntaxKind::ARG_LIST
+                        | SyntaxKind::RECORD_EXPR_FIELD_LIST
+                        | SyntaxKind::RECORD_PAT_FIELD_LIST
+                        | SyntaxKind::TUPLE_STRUCT_PAT
+                )
+            });
+        if is_trailing_comma {
+            continue;
+        }
+
+        if let Some(prev) = prev {
+            let newline = if is_block_brace(prev, T!['{']) && token.kind() != T!['}'] {
+                indent += 1;
+                true
+            } else if is_block_brace(token, T!['}']) && prev.kind() != T!['{'] {
+                indent = indent.saturating_sub(1);
+                // The last arm gets a comma as well, unless it's a block.
+                let is_arm_list =
+                    token.parent().map(|it| it.kind()) == Some(SyntaxKind::MATCH_ARM_LIST);
+                if is_arm_list && !matches!(prev.kind(), T![,] | T!['}']) {
+                    buf.push(',');
+                }
+                true
+            } else if is_block_brace(prev, T!['}']) {
+                !matches!(token.kind(), T![,] | T![;] | T![')'] | T![.] | T![?] | T![else])
+            } else if prev.kind() == T![;] {
+                true
+            } else {
+                prev.kind() == T![,]
+                    && prev.parent().map(|it| it.kind()) == Some(SyntaxKind::MATCH_ARM)
+            };
+            if newline {
+                buf.push('\n');
+                buf.push_str(&IndentLevel(indent).to_string());
+            } else if needs_space(prev, token) {
+                buf.push(' ');
+            }
+        }
+        buf.push_str(token.text());
+        prev = Some(token);
+    }
+    buf
+}
+
+/// Whether `token` is the brace `kind` of a block or of the arms of a `match`.
+fn is_block_brace(token: &SyntaxToken, kind: SyntaxKind) -> bool {
+    token.kind() == kind
+        && token.parent().map_or(false, |it| {
+            matches!(it.kind(), SyntaxKind::STMT_LIST | SyntaxKind::MATCH_ARM_LIST)
+        })
+}
+
+fn needs_space(prev: &SyntaxToken, token: &SyntaxToken) -> bool {
+    let is_operator = |token: &SyntaxToken| {
+        matches!(token.kind(), T![=] | T![=>] | T![->])
+            || token.parent().map_or(false, |it| {
+                it.kind() == SyntaxKind::BIN_EXPR
+                    || (it.kind() == SyntaxKind::OR_PAT && token.kind() == T![|])
+            })
+    };
+    let is_text = |kind: SyntaxKind| {
+        kind.is_keyword() || kind.is_literal() || matches!(kind, SyntaxKind::IDENT | T![_])
+    };
+
+    match (prev.kind(), token.kind()) {
+        (_, T![,] | T![;] | T![:] | T![')'] | T![']'] | T![.] | T![?] | T![::]) => false,
+        (T!['('] | T!['['] | T![.] | T![::] | T![#], _) => false,
+        (T!['{'], T!['}']) => false,
+        (_, T!['{'] | T!['}']) | (T!['{'] | T![,] | T![:], _) => true,
+        _ if is_operator(prev) || is_operator(token) => true,
+        (T![self] | T![Self] | T![crate] | T![super] | T![true] | T![false], _) => false,
+        (prev, _) if prev.is_keyword() => true,
+        (prev, token) => (is_text(prev) || prev == SyntaxKind::LIFETIME_IDENT) && is_text(token),
+    }
+}
+
 fn update_attribute(
     builder: &mut SourceChangeBuilder,
     old_derives: &[ast::Path],
@@ -280,6 +489,77 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn add_custom_impl_from_expansion() {
+        check_assist(
+            replace_derive_with_manual_impl,
+            r#"
+//- minicore: ord, derive
+#[derive(Or$0d)]
+struct Foo;
+"#,
+            r#"
+struct Foo;
+
+impl Ord for Foo {
+    $0fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        match (self, other) {
+            (Foo, Foo) => core::cmp::Ordering::Equal,
+            _unused => core::cmp::Ordering::Equal,
+        }
+    }
+}
+"#,
+        )
+    }
+
+    #[test]
+    fn add_custom_impl_from_expansion_of_own_crate() {
+        check_assist(
+            replace_derive_with_manual_impl,
+            r#"
+//- minicore: derive
+mod cmp {
+    pub enum Ordering { Less, Equal, Greater }
+    pub trait Ord {
+        fn cmp(&self, other: &Self) -> Ordering;
+    }
+    #[rustc_builtin_macro]
+    pub macro Ord($item:item) {}
+}
+
+#[derive(cmp::Or$0d)]
+struct Foo(u8);
+"#,
+            r#"
+mod cmp {
+    pub enum Ordering { Less, Equal, Greater }
+    pub trait Ord {
+        fn cmp(&self, other: &Self) -> Ordering;
+    }
+    #[rustc_builtin_macro]
+    pub macro Ord($item:item) {}
+}
+
+struct Foo(u8);
+
+impl cmp::Ord for Foo {
+    $0fn cmp(&self, other: &Self) -> cmp::Ordering {
+        match (self, other) {
+            (Foo(f0_self), Foo(f0_other)) => match f0_self.cmp(&f0_other) {
+                crate::cmp::Ordering::Equal => {
+                    crate::cmp::Ordering::Equal
+                }
+                c => return c,
+            },
+            _unused => crate::cmp::Ordering::Equal,
+        }
+    }
+}
+"#,
+        )
+    }
+
     #[test]
     fn add_custom_impl_debug_record_struct() {
         check_assist(
@@ -0,0 +1,310 @@
+use ide_db::{
+    assists::{AssistId, AssistKind},
+    ty_filter::TryEnum,
+};
+use syntax::{
+    ast::{self, make},
+    AstNode, T,
+};
+
+use crate::{
+    assist_context::{AssistContext, Assists},
+    utils::{extract_trivial_expression, try_exits_like_return},
+};
+
+// Assist: replace_match_with_try_expr
+//
+// Replaces a `match` that only hands the `None` or `Err` case back to the caller with a `try`
+// expression.
+//
+// ```
+// # //- minicore: try, option
+// fn handle() -> Option<i32> {
+//     let pat = $0match Some(1) {
+//         Some(it) => it,
+//         None => return None,
+//     };
+//     Some(pat)
+// }
+// ```
+// ->
+// ```
+// fn handle() -> Option<i32> {
+//     let pat = Some(1)?;
+//     Some(pat)
+// }
+// ```
+pub(crate) fn replace_match_with_try_expr(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let match_kw = ctx.find_token_syntax_at_offset(T![match])?;
+    let match_expr = match_kw.parent().and_then(ast::MatchExpr::cast)?;
+    let scrutinee = match_expr.expr()?;
+    let scrutinee_ty = ctx.sema.type_of_expr(&scrutinee)?.original;
+    let try_enum = TryEnum::from_ty(&ctx.sema, &scrutinee_ty)?;
+
+    let arms = match_expr.match_arm_list()?.arms().collect::<Vec<_>>();
+    let [first, second] = &arms[..] else { return None };
+    let forwards_sad_case = |happy: &ast::MatchArm, sad: &ast::MatchArm| {
+        is_happy_arm(try_enum, happy).is_some() && is_sad_arm(try_enum, sad).is_some()
+    };
+    if !forwards_sad_case(first, second) && !forwards_sad_case(second, first) {
+        return None;
+    }
+    // Inside of a `try` block the `?` would no longer leave the function.
+    if !try_exits_like_return(match_expr.syntax()) {
+        return None;
+    }
+
+    let target = match_expr.syntax().text_range();
+    acc.add(
+        AssistId("replace_match_with_try_expr", AssistKind::RefactorRewrite),
+        "Replace match with try expression",
+        target,
+        |edit| {
+            let scrutinee = match scrutinee {
+                ast::Expr::BinExpr(_)
+                | ast::Expr::CastExpr(_)
+                | ast::Expr::ClosureExpr(_)
+                | ast::Expr::PrefixExpr(_)
+                | ast::Expr::RangeExpr(_)
+                | ast::Expr::RefExpr(_) => make::expr_paren(scrutinee),
+                scrutinee => scrutinee,
+            };
+            edit.replace(target, make::expr_try(scrutinee).to_string());
+        },
+    )
+}
+
+/// `Some(it) => it` or `Ok(it) => it`
+fn is_happy_arm(try_enum: TryEnum, arm: &ast::MatchArm) -> Option<()> {
+    if arm.guard().is_some() {
+        return None;
+    }
+    let binding = single_binding_of_variant(&arm.pat()?, try_enum.happy_case())?;
+    is_path_to(&arm_body(arm)?, &binding).then_some(())
+}
+
+/// `None => return None` or `Err(err) => return Err(err)`
+fn is_sad_arm(try_enum: TryEnum, arm: &ast::MatchArm) -> Option<()> {
+    if arm.guard().is_some() {
+        return None;
+    }
+    let pat = arm.pat()?;
+    let ret_value = match arm_body(arm)? {
+        ast::Expr::ReturnExpr(it) => it.expr()?,
+        _ => return None,
+    };
+    match try_enum {
+        TryEnum::Option => {
+            let matches_none =
+                matches!(pat, ast::Pat::WildcardPat(_)) || pat.syntax().text() == "None";
+            (matches_none && ret_value.syntax().text() == "None").then_some(())
+        }
+        TryEnum::Result => {
+            let binding = single_binding_of_variant(&pat, "Err")?;
+            let ast::Expr::CallExpr(call) = ret_value else { return None };
+            if call.expr()?.syntax().text() != "Err" {
+                return None;
+            }
+            let mut args = call.arg_list()?.args();
+            let arg = args.next()?;
+            if args.next().is_some() {
+                return None;
+            }
+            // `?` converts the error with `From::from`, so an explicit `.into()` can be dropped.
+            let arg = match arg {
+                ast::Expr::MethodCallExpr(call)
+                    if call.name_ref()?.text() == "into"
+                        && call.arg_list()?.args().next().is_none() =>
+                {
+                    call.receiver()?
+                }
+                arg => arg,
+            };
+            is_path_to(&arg, &binding).then_some(())
+        }
+    }
+}
+
+fn single_binding_of_variant(pat: &ast::Pat, variant: &str) -> Option<ast::Name> {
+    let ast::Pat::TupleStructPat(pat) = pat else { return None };
+    if pat.path()?.as_single_name_ref()?.text() != variant {
+        return None;
+    }
+    let mut fields = pat.fields();
+    let ast::Pat::IdentPat(binding) = fields.next()? else { return None };
+    if fields.next().is_some() || binding.ref_token().is_some() || binding.pat().is_some() {
+        return None;
+    }
+    binding.name()
+}
+
+fn arm_body(arm: &ast::MatchArm) -> Option<ast::Expr> {
+    match arm.expr()? {
+        ast::Expr::BlockExpr(block) => extract_trivial_expression(&block),
+        expr => Some(expr),
+    }
+}
+
+fn is_path_to(expr: &ast::Expr, name: &ast::Name) -> bool {
+    let ast::Expr::PathExpr(path) = expr else { return false };
+    path.path()
+        .and_then(|path| path.as_single_name_ref())
+        .map_or(false, |name_ref| name_ref.text() == name.text())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    #[test]
+    fn replace_option_match() {
+        check_assist(
+            replace_match_with_try_expr,
+            r#"
+//- minicore: try, option
+fn test() -> Option<bool> {
+    let pat = $0match Some(true) {
+        Some(it) => it,
+        None => return None,
+    };
+    Some(pat)
+}
+"#,
+            r#"
+fn test() -> Option<bool> {
+    let pat = Some(true)?;
+    Some(pat)
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn replace_result_match() {
+        check_assist(
+            replace_match_with_try_expr,
+            r#"
+//- minicore: try, from, result
+fn test() -> Result<bool, ()> {
+    let pat = $0match Ok(true) {
+        Ok(value) => value,
+        Err(err) => return Err(err),
+    };
+    Ok(pat)
+}
+"#,
+            r#"
+fn test() -> Result<bool, ()> {
+    let pat = Ok(true)?;
+    Ok(pat)
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn replace_result_match_with_into_and_blocks() {
+        check_assist(
+            replace_match_with_try_expr,
+            r#"
+//- minicore: try, from, result
+fn test(res: Result<bool, ()>) -> Result<bool, ()> {
+    let pat = $0match res {
+        Err(e) => {
+            return Err(e.into());
+        }
+        Ok(v) => { v }
+    };
+    Ok(pat)
+}
+"#,
+            r#"
+fn test(res: Result<bool, ()>) -> Result<bool, ()> {
+    let pat = res?;
+    Ok(pat)
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn wraps_scrutinee_in_parens() {
+        check_assist(
+            replace_match_with_try_expr,
+            r#"
+//- minicore: try, option
+fn test(x: &Option<u8>) -> Option<u8> {
+    let y = $0match *x {
+        Some(it) => it,
+        _ => return None,
+    };
+    Some(y)
+}
+"#,
+            r#"
+fn test(x: &Option<u8>) -> Option<u8> {
+    let y = (*x)?;
+    Some(y)
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_if_sad_case_is_not_forwarded() {
+        check_assist_not_applicable(
+            replace_match_with_try_expr,
+            r#"
+//- minicore: try, option
+fn test() -> Option<bool> {
+    let pat = $0match Some(true) {
+        Some(it) => it,
+        None => return Some(false),
+    };
+    Some(pat)
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_with_guard() {
+        check_assist_not_applicable(
+            replace_match_with_try_expr,
+            r#"
+//- minicore: try, option
+fn test() -> Option<bool> {
+    let pat = $0match Some(true) {
+        Some(it) if it => it,
+        None => return None,
+    };
+    Some(pat)
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_in_try_block() {
+        check_assist_not_applicable(
+            replace_match_with_try_expr,
+            r#"
+//- minicore: try, option
+fn test() -> Option<bool> {
+    let _: Option<bool> = try {
+        $0match Some(true) {
+            Some(it) => it,
+            None => return None,
+        }
+    };
+    None
+}
+"#,
+        );
+    }
+}
@@ -1,3 +1,5 @@
+use std::iter;
+
 use hir::Semantics;
 use ide_db::{
     base_db::{FileId, FileRange},
@@ -6,15 +8,15 @@ use ide_db::{
     RootDatabase,
 };
 use syntax::{
-    ast::{
-        self, make::impl_trait_type, HasGenericParams, HasName, HasTypeBounds, Name, NameLike,
-        PathType,
-    },
+    ast::{self, make, HasGenericParams, HasName, HasTypeBounds, Name, NameLike, PathType},
     match_ast, ted, AstNode,
 };
 use text_edit::TextRange;
 
-use crate::{AssistContext, AssistId, AssistKind, Assists};
+use crate::{
+    utils::{bounded_type_param, remove_where_clause, turbofish_call_sites},
+    AssistContext, AssistId, AssistKind, Assists,
+};
 
 // Assist: replace_named_generic_with_impl
 //
@@ -36,12 +38,27 @@ pub(crate) fn replace_named_generic_with_impl(
     // returns `P`
     let type_param_name = type_param.name()?;
 
-    // The list of type bounds / traits: `AsRef<Path>`
-    let type_bound_list = type_param.type_bound_list()?;
-
     let fn_ = type_param.syntax().ancestors().find_map(ast::Fn::cast)?;
+    let generic_param_list = fn_.generic_param_list()?;
     let param_list_text_range = fn_.param_list()?.syntax().text_range();
 
+    // The where clause predicates bounding only the type param: `P: AsRef<Path>`
+    let where_preds = fn_
+        .where_clause()
+        .into_iter()
+        .flat_map(|it| it.predicates())
+        .filter(|pred| bounded_type_param(&generic_param_list, pred).as_ref() == Some(&type_param))
+        .collect::<Vec<_>>();
+
+    // The list of type bounds / traits: `AsRef<Path>`
+    let type_bound_list = make::type_bound_list(
+        type_param
+            .type_bound_list()
+            .into_iter()
+            .chain(where_preds.iter().filter_map(|it| it.type_bound_list()))
+            .flat_map(|it| it.bounds()),
+    )?;
+
     let type_param_hir_def = ctx.sema.to_def(&type_param)?;
     let type_param_def = Definition::GenericParam(hir::GenericParam::TypeParam(type_param_hir_def));
 
@@ -51,14 +68,21 @@ pub(crate) fn replace_named_generic_with_impl(
         return None;
     }
 
-    // All usage references need to be valid (inside the function param list)
-    if !check_valid_usages(&usage_refs, param_list_text_range) {
+    // All usage references need to be valid (inside the function param list, or the bounded
+    // type of the where clause predicates that get removed)
+    let valid_ranges = iter::once(param_list_text_range)
+        .chain(where_preds.iter().filter_map(|it| Some(it.ty()?.syntax().text_range())))
+        .collect::<Vec<_>>();
+    if !check_valid_usages(&usage_refs, &valid_ranges) {
         return None;
     }
 
     let mut path_types_to_replace = Vec::new();
     for (_a, refs) in usage_refs.iter() {
         for usage_ref in refs {
+            if !param_list_text_range.contains_range(usage_ref.range) {
+                continue;
+            }
             let Some(name_like) = usage_ref.name.clone().into_name_like() else {
                 continue;
             };
@@ -67,6 +91,13 @@ pub(crate) fn replace_named_generic_with_impl(
         }
     }
 
+    // Calls giving the generic arguments explicitly lose the one of the type param.
+    let arg_index = generic_param_list
+        .type_or_const_params()
+        .position(|it| it.syntax() == type_param.syntax())?;
+    let mut call_sites =
+        ctx.sema.to_def(&fn_).map(|func| turbofish_call_sites(&ctx.sema, func)).unwrap_or_default();
+
     let target = type_param.syntax().text_range();
 
     acc.add(
@@ -76,11 +107,19 @@ pub(crate) fn replace_named_generic_with_impl(
         |edit| {
             let type_param = edit.make_mut(type_param);
             let fn_ = edit.make_mut(fn_);
+            let where_preds =
+                where_preds.into_iter().map(|pred| edit.make_mut(pred)).collect::<Vec<_>>();
 
             let path_types_to_replace = path_types_to_replace
                 .into_iter()
                 .map(|param| edit.make_mut(param))
                 .collect::<Vec<_>>();
+            let arg_lists = call_sites
+                .remove(&ctx.file_id())
+                .into_iter()
+                .flatten()
+                .map(|it| edit.make_mut(it))
+                .collect::<Vec<_>>();
 
             // remove trait from generic param list
             if let Some(generic_params) = fn_.generic_param_list() {
@@ -90,14 +129,46 @@ pub(crate) fn replace_named_generic_with_impl(
                 }
             }
 
-            let new_bounds = impl_trait_type(type_bound_list);
+            // remove the bounds from the where clause
+            if let Some(where_clause) = fn_.where_clause() {
+                for pred in where_preds {
+                    where_clause.remove_predicate(pred);
+                }
+                if where_clause.predicates().next().is_none() {
+                    remove_where_clause(where_clause);
+                }
+            }
+
+            let new_bounds = make::impl_trait_type(type_bound_list);
             for path_type in path_types_to_replace.iter().rev() {
                 ted::replace(path_type.syntax(), new_bounds.clone_for_update().syntax());
             }
+
+            remove_arg(arg_lists, arg_index);
+            for (file_id, arg_lists) in call_sites {
+                edit.edit_file(file_id);
+                remove_arg(arg_lists.into_iter().map(|it| edit.make_mut(it)).collect(), arg_index);
+            }
         },
     )
 }
 
+fn remove_arg(arg_lists: Vec<ast::GenericArgList>, index: usize) {
+    for arg_list in arg_lists {
+        let arg = arg_list
+            .generic_args()
+            .filter(|arg| !matches!(arg, ast::GenericArg::LifetimeArg(_)))
+            .nth(index);
+        if let Some(arg) = arg {
+            arg_list.remove_generic_arg(arg);
+        }
+        if arg_list.generic_args().next().is_none() {
+            // The list includes the `::` of the turbofish.
+            ted::remove(arg_list.syntax());
+        }
+    }
+}
+
 fn find_path_type(
     sema: &Semantics<'_, RootDatabase>,
     type_param_name: &Name,
@@ -163,11 +234,11 @@ fn find_usages(
     type_param_def.usages(sema).in_scope(&SearchScope::file_range(file_range)).all()
 }
 
-fn check_valid_usages(usages: &UsageSearchResult, param_list_range: TextRange) -> bool {
+fn check_valid_usages(usages: &UsageSearchResult, valid_ranges: &[TextRange]) -> bool {
     usages
         .iter()
         .flat_map(|(_, usage_refs)| usage_refs)
-        .all(|usage_ref| param_list_range.contains_range(usage_ref.range))
+        .all(|usage_ref| valid_ranges.iter().any(|range| range.contains_range(usage_ref.range)))
 }
 
 #[cfg(test)]
@@ -351,4 +422,81 @@ mod tests {
             "#,
         );
     }
+
+    #[test]
+    fn replace_generic_with_bounds_in_where_clause() {
+        check_assist(
+            replace_named_generic_with_impl,
+            r#"fn new<P$0>(p: P) -> Self where P: AsRef<Path> {}"#,
+            r#"fn new(p: impl AsRef<Path>) -> Self {}"#,
+        );
+        check_assist(
+            replace_named_generic_with_impl,
+            r#"fn new<P$0: Send, T>(p: P, t: T) -> Self where P: Sync, T: Clone {}"#,
+            r#"fn new<T>(p: impl Send + Sync, t: T) -> Self where T: Clone {}"#,
+        );
+    }
+
+    #[test]
+    fn replace_generic_not_applicable_without_bounds() {
+        check_assist_not_applicable(replace_named_generic_with_impl, r#"fn new<P$0>(p: P) {}"#);
+    }
+
+    #[test]
+    fn replace_generic_removes_turbofish_argument() {
+        check_assist(
+            replace_named_generic_with_impl,
+            r#"
+//- /main.rs
+mod other;
+pub fn foo<'a, T, P$0: Trait>(t: &'a T, p: P) {}
+fn main() {
+    foo::<u32, S>(&1, S);
+    foo(&1, S);
+}
+//- /other.rs
+fn f() {
+    crate::foo::<u8, S>(&1, S);
+}
+"#,
+            r#"
+//- /main.rs
+mod other;
+pub fn foo<'a, T>(t: &'a T, p: impl Trait) {}
+fn main() {
+    foo::<u32>(&1, S);
+    foo(&1, S);
+}
+//- /other.rs
+fn f() {
+    crate::foo::<u8>(&1, S);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn replace_generic_removes_emptied_turbofish() {
+        check_assist(
+            replace_named_generic_with_impl,
+            r#"
+struct S;
+impl S {
+    fn foo<P$0: Trait>(&self, p: P) {}
+}
+fn main() {
+    S.foo::<u32>(1);
+}
+"#,
+            r#"
+struct S;
+impl S {
+    fn foo(&self, p: impl Trait) {}
+}
+fn main() {
+    S.foo(1);
+}
+"#,
+        );
+    }
 }
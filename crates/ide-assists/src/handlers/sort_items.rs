@@ -2,9 +2,10 @@ use std::cmp::Ordering;
 
 use itertools::Itertools;
 
+use ide_db::syntax_helpers::node_ext::parse_tt_as_comma_sep_paths;
 use syntax::{
     ast::{self, HasName},
-    ted, AstNode, TextRange,
+    ted, AstNode, SyntaxKind, TextRange,
 };
 
 use crate::{utils::get_methods, AssistContext, AssistId, AssistKind, Assists};
@@ -81,13 +82,46 @@ use crate::{utils::get_methods, AssistContext, AssistId, AssistKind, Assists};
 //   Cat { name: String, weight: f64 },
 // }
 // ```
+// ---
+// Items of an inline module, as long as all of them are named:
+//
+// ```
+// mod $0shapes$0 {
+//     struct Square;
+//     struct Circle;
+// }
+// ```
+// ->
+// ```
+// mod shapes {
+//     struct Circle;
+//     struct Square;
+// }
+// ```
+// ---
+// And the traits of a derive attribute:
+//
+// ```
+// #[derive($0PartialEq, Debug, Clone$0)]
+// struct Point;
+// ```
+// ->
+// ```
+// #[derive(Clone, Debug, PartialEq)]
+// struct Point;
+// ```
 pub(crate) fn sort_items(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
     if ctx.has_empty_selection() {
         cov_mark::hit!(not_applicable_if_no_selection);
         return None;
     }
 
-    if let Some(struct_ast) = ctx.find_node_at_offset::<ast::Struct>() {
+    if let Some(derive_attr) = ctx
+        .find_node_at_offset::<ast::Attr>()
+        .filter(|it| it.simple_name().as_deref() == Some("derive"))
+    {
+        add_sort_derives_assist(acc, derive_attr)
+    } else if let Some(struct_ast) = ctx.find_node_at_offset::<ast::Struct>() {
         add_sort_field_list_assist(acc, struct_ast.field_list())
     } else if let Some(union_ast) = ctx.find_node_at_offset::<ast::Union>() {
         add_sort_fields_assist(acc, union_ast.record_field_list()?)
@@ -103,6 +137,8 @@ pub(crate) fn sort_items(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<(
         add_sort_methods_assist(acc, ctx, trait_ast.assoc_item_list()?)
     } else if let Some(impl_ast) = ctx.find_node_at_offset::<ast::Impl>() {
         add_sort_methods_assist(acc, ctx, impl_ast.assoc_item_list()?)
+    } else if let Some(module_ast) = ctx.find_node_at_offset::<ast::Module>() {
+        add_sort_module_items_assist(acc, ctx, module_ast.item_list()?)
     } else {
         None
     }
@@ -207,6 +243,56 @@ fn add_sort_variants_assist(acc: &mut Assists, variant_list: ast::VariantList) -
     )
 }
 
+fn add_sort_module_items_assist(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+    item_list: ast::ItemList,
+) -> Option<()> {
+    let selection = ctx.selection_trimmed();
+
+    // ignore assist if the selection intersects with an item.
+    if item_list.items().any(|item| item.syntax().text_range().intersect(selection).is_some()) {
+        return None;
+    }
+
+    // `use`s, `impl`s and macro calls don't have a name to sort by.
+    let items = item_list
+        .items()
+        .map(|item| ast::AnyHasName::cast(item.syntax().clone()))
+        .collect::<Option<Vec<_>>>()?;
+    let sorted = sort_by_name(&items);
+
+    if items == sorted {
+        cov_mark::hit!(not_applicable_if_sorted_or_empty_or_single);
+        return None;
+    }
+
+    acc.add_rewrite("Sort items alphabetically", items, sorted, item_list.syntax().text_range())
+}
+
+fn add_sort_derives_assist(acc: &mut Assists, attr: ast::Attr) -> Option<()> {
+    let token_tree = attr.token_tree()?;
+    // Comments can't be attributed to a single derive, so leave those lists alone.
+    if token_tree.syntax().children_with_tokens().any(|it| it.kind() == SyntaxKind::COMMENT) {
+        return None;
+    }
+    let derives = parse_tt_as_comma_sep_paths(token_tree.clone())?;
+    let sorted = derives.iter().cloned().sorted_by_cached_key(|it| it.to_string()).collect_vec();
+
+    if derives == sorted {
+        cov_mark::hit!(not_applicable_if_sorted_or_empty_or_single);
+        return None;
+    }
+
+    let target = token_tree.syntax().text_range();
+    acc.add(
+        AssistId("sort_items", AssistKind::RefactorRewrite),
+        "Sort derives alphabetically",
+        target,
+        |builder| builder.replace(target, format!("({})", sorted.iter().format(", "))),
+    )
+}
+
 fn sort_by_name<T: HasName + Clone>(initial: &[T]) -> Vec<T> {
     initial
         .iter()
@@ -682,4 +768,71 @@ enum Bar {
         "#,
         )
     }
+
+    #[test]
+    fn sort_module_items() {
+        check_assist(
+            sort_items,
+            r#"
+$0mod shapes$0 {
+    /// Four corners.
+    #[derive(Debug)]
+    pub struct Square;
+    fn area() {}
+    pub(crate) enum Circle {}
+}
+        "#,
+            r#"
+mod shapes {
+    pub(crate) enum Circle {}
+    /// Four corners.
+    #[derive(Debug)]
+    pub struct Square;
+    fn area() {}
+}
+        "#,
+        )
+    }
+
+    #[test]
+    fn not_applicable_if_module_has_unnamed_items() {
+        check_assist_not_applicable(
+            sort_items,
+            r#"
+mod $0shapes$0 {
+    struct Square;
+    impl Square {}
+    struct Circle;
+}
+        "#,
+        )
+    }
+
+    #[test]
+    fn sort_derives() {
+        check_assist(
+            sort_items,
+            r#"
+#[derive($0PartialEq, Debug, core::clone::Clone$0)]
+struct Point;
+        "#,
+            r#"
+#[derive(Debug, PartialEq, core::clone::Clone)]
+struct Point;
+        "#,
+        )
+    }
+
+    #[test]
+    fn not_applicable_if_derives_sorted() {
+        cov_mark::check!(not_applicable_if_sorted_or_empty_or_single);
+
+        check_assist_not_applicable(
+            sort_items,
+            r#"
+#[derive($0Clone, Debug$0)]
+struct Point;
+        "#,
+        )
+    }
 }
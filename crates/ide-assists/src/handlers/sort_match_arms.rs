@@ -0,0 +1,237 @@
+use hir::{PathResolution, Variant};
+use itertools::Itertools;
+use syntax::{
+    ast::{self, make},
+    ted, AstNode, T,
+};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: sort_match_arms
+//
+// Sorts the arms of a `match` on an enum to follow the declaration order of its variants.
+//
+// ```
+// enum Direction { North, East, South, West }
+//
+// fn turn(dir: Direction) -> Direction {
+//     $0match dir {
+//         Direction::South => Direction::West,
+//         Direction::North => Direction::East,
+//         Direction::West => Direction::North,
+//         Direction::East => Direction::South,
+//     }
+// }
+// ```
+// ->
+// ```
+// enum Direction { North, East, South, West }
+//
+// fn turn(dir: Direction) -> Direction {
+//     match dir {
+//         Direction::North => Direction::East,
+//         Direction::East => Direction::South,
+//         Direction::South => Direction::West,
+//         Direction::West => Direction::North,
+//     }
+// }
+// ```
+pub(crate) fn sort_match_arms(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    let match_expr = ctx.find_node_at_offset::<ast::MatchExpr>()?;
+    let arm_list = match_expr.match_arm_list()?;
+    if arm_list.syntax().text_range().contains_inclusive(ctx.offset()) {
+        cov_mark::hit!(sort_match_arms_cursor_in_arms);
+        return None;
+    }
+
+    let scrutinee_ty = ctx.sema.type_of_expr(&match_expr.expr()?)?.adjusted();
+    let hir::Adt::Enum(enum_) = scrutinee_ty.strip_references().as_adt()? else { return None };
+    let variants = enum_.variants(ctx.db());
+
+    let arms = arm_list.arms().collect::<Vec<_>>();
+    let ranks = arms
+        .iter()
+        .map(|arm| {
+            let variant = arm_variant(ctx, &arm.pat()?)?;
+            variants.iter().position(|it| *it == variant)
+        })
+        .collect::<Vec<_>>();
+
+    // Arms that aren't tied to a single variant (wildcards, bindings, ...) may overlap with
+    // anything, so they have to stay at the end for the reordering to keep the semantics.
+    let first_catch_all = ranks.iter().position(Option::is_none).unwrap_or(ranks.len());
+    if ranks[first_catch_all..].iter().any(Option::is_some) {
+        cov_mark::hit!(sort_match_arms_catch_all_in_between);
+        return None;
+    }
+
+    let sorted = arms
+        .iter()
+        .cloned()
+        .zip(ranks)
+        .sorted_by_key(|(_, rank)| rank.unwrap_or(usize::MAX))
+        .map(|(arm, _)| arm)
+        .collect::<Vec<_>>();
+    if arms == sorted {
+        cov_mark::hit!(sort_match_arms_already_sorted);
+        return None;
+    }
+
+    acc.add(
+        AssistId("sort_match_arms", AssistKind::RefactorRewrite),
+        "Sort match arms by variant declaration order",
+        match_expr.syntax().text_range(),
+        |builder| {
+            let last = sorted.len() - 1;
+            let arms = arms.into_iter().map(|arm| builder.make_mut(arm)).collect::<Vec<_>>();
+            for (idx, (old, new)) in arms.into_iter().zip(sorted).enumerate() {
+                let new = new.clone_for_update();
+                let needs_comma = idx != last
+                    && new.comma_token().is_none()
+                    && new.expr().map_or(false, |it| !it.is_block_like());
+                if needs_comma {
+                    ted::append_child(new.syntax(), make::token(T![,]));
+                }
+                ted::replace(old.syntax(), new.syntax());
+            }
+        },
+    )
+}
+
+fn arm_variant(ctx: &AssistContext<'_>, pat: &ast::Pat) -> Option<Variant> {
+    let path = match pat {
+        ast::Pat::PathPat(it) => it.path()?,
+        ast::Pat::TupleStructPat(it) => it.path()?,
+        ast::Pat::RecordPat(it) => it.path()?,
+        ast::Pat::OrPat(it) => {
+            return it.pats().map(|pat| arm_variant(ctx, &pat)).all_equal_value().ok()?;
+        }
+        _ => return None,
+    };
+    match ctx.sema.resolve_path(&path)? {
+        PathResolution::Def(hir::ModuleDef::Variant(it)) => Some(it),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn sort_arms_with_payloads_and_guards() {
+        check_assist(
+            sort_match_arms,
+            r#"
+enum E { A(u8), B { x: u8 }, C }
+
+fn f(e: &E) -> u8 {
+    match$0 e {
+        E::C => { 0 }
+        E::B { x } => *x,
+        E::A(x) if *x > 1 => 1,
+        E::A(_) => 2
+    }
+}
+"#,
+            r#"
+enum E { A(u8), B { x: u8 }, C }
+
+fn f(e: &E) -> u8 {
+    match e {
+        E::A(x) if *x > 1 => 1,
+        E::A(_) => 2,
+        E::B { x } => *x,
+        E::C => { 0 }
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn sort_arms_keeps_catch_all_last() {
+        check_assist(
+            sort_match_arms,
+            r#"
+enum E { A, B, C(u8) }
+
+fn f(e: E) -> u8 {
+    match$0 e {
+        E::C(1) | E::C(2) => 2,
+        E::A => 0,
+        _ => 1,
+    }
+}
+"#,
+            r#"
+enum E { A, B, C(u8) }
+
+fn f(e: E) -> u8 {
+    match e {
+        E::A => 0,
+        E::C(1) | E::C(2) => 2,
+        _ => 1,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_with_catch_all_in_between() {
+        cov_mark::check!(sort_match_arms_catch_all_in_between);
+        check_assist_not_applicable(
+            sort_match_arms,
+            r#"
+enum E { A, B }
+
+fn f(e: E) -> u8 {
+    match$0 e {
+        E::B => 1,
+        _ => 2,
+        E::A => 0,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_if_sorted() {
+        cov_mark::check!(sort_match_arms_already_sorted);
+        check_assist_not_applicable(
+            sort_match_arms,
+            r#"
+enum E { A, B }
+
+fn f(e: E) -> u8 {
+    match$0 e {
+        E::A => 0,
+        E::B => 1,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_in_arms() {
+        cov_mark::check!(sort_match_arms_cursor_in_arms);
+        check_assist_not_applicable(
+            sort_match_arms,
+            r#"
+enum E { A, B }
+
+fn f(e: E) -> u8 {
+    match e {
+        E::B => 1,
+        E::A => $00,
+    }
+}
+"#,
+        );
+    }
+}
@@ -0,0 +1,646 @@
+use ide_db::{base_db::FileRange, defs::Definition, search::SearchScope};
+use syntax::{
+    ast::{
+        self,
+        edit::{AstNodeEdit, IndentLevel},
+        make, AstNode,
+    },
+    ted, NodeOrToken, SyntaxElement, SyntaxKind, SyntaxNode, TextRange, WalkEvent,
+};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists, GroupLabel};
+
+use super::add_missing_match_arms::enum_variant_pats;
+
+// Assist: surround_with_if
+//
+// Wraps the selected statements in an `if`.
+//
+// ```
+// fn main() {
+//     $0let x = 1;
+//     println!("{x}");$0
+// }
+// ```
+// ->
+// ```
+// fn main() {
+//     if ${0:condition} {
+//         let x = 1;
+//         println!("{x}");
+//     }
+// }
+// ```
+pub(crate) fn surround_with_if(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    surround_with(acc, ctx, Construct::If)
+}
+
+// Assist: surround_with_loop
+//
+// Wraps the selected statements in a `loop`.
+//
+// ```
+// fn main() {
+//     $0let x = 1;
+//     println!("{x}");$0
+// }
+// ```
+// ->
+// ```
+// fn main() {
+//     loop {
+//         let x = 1;
+//         println!("{x}");
+//     }
+// }
+// ```
+pub(crate) fn surround_with_loop(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    surround_with(acc, ctx, Construct::Loop)
+}
+
+// Assist: surround_with_unsafe
+//
+// Wraps the selected statements or expression in an `unsafe` block.
+//
+// ```
+// unsafe fn danger() -> i32 { 0 }
+//
+// fn main() {
+//     let x = $0danger()$0;
+// }
+// ```
+// ->
+// ```
+// unsafe fn danger() -> i32 { 0 }
+//
+// fn main() {
+//     let x = unsafe { danger() };
+// }
+// ```
+pub(crate) fn surround_with_unsafe(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    surround_with(acc, ctx, Construct::Unsafe)
+}
+
+// Assist: surround_with_async_move
+//
+// Wraps the selected expression in an `async move` block.
+//
+// ```
+// fn main() {
+//     let s = String::new();
+//     let fut = $0drop(s)$0;
+// }
+// ```
+// ->
+// ```
+// fn main() {
+//     let s = String::new();
+//     let fut = async move { drop(s) };
+// }
+// ```
+pub(crate) fn surround_with_async_move(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    surround_with(acc, ctx, Construct::AsyncMove)
+}
+
+// Assist: surround_with_match
+//
+// Matches on the selected expression, with an arm for each variant of its type.
+//
+// ```
+// enum Dir { Up, Down }
+//
+// fn f(dir: Dir) {
+//     $0dir$0;
+// }
+// ```
+// ->
+// ```
+// enum Dir { Up, Down }
+//
+// fn f(dir: Dir) {
+//     match dir {
+//         Dir::Up => todo!(),
+//         Dir::Down => todo!(),
+//     };
+// }
+// ```
+pub(crate) fn surround_with_match(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    let expr = selected_expr(ctx)?;
+    let module = ctx.sema.scope(expr.syntax())?.module();
+
+    acc.add_group(
+        &GroupLabel("Surround with…".to_owned()),
+        AssistId("surround_with_match", AssistKind::RefactorRewrite),
+        "Surround with `match`",
+        expr.syntax().text_range(),
+        |builder| {
+            let arm = |pat: ast::Pat| make::match_arm([pat], None, make::ext::expr_todo());
+            let arms = match enum_variant_pats(ctx, module, &expr) {
+                Some((pats, needs_wildcard)) => {
+                    let wildcard = needs_wildcard.then(|| arm(make::wildcard_pat().into()));
+                    pats.into_iter().map(arm).chain(wildcard).collect()
+                }
+                None => vec![arm(make::wildcard_pat().into())],
+            };
+            let indent = IndentLevel::from_node(expr.syntax());
+            let match_expr =
+                make::expr_match(expr.clone(), make::match_arm_list(arms)).indent(indent);
+            let expr = builder.make_mut(expr.clone());
+            ted::replace(expr.syntax(), match_expr.clone_for_update().syntax());
+        },
+    )
+}
+
+#[derive(Clone, Copy)]
+enum Construct {
+    If,
+    Loop,
+    Unsafe,
+    AsyncMove,
+}
+
+fn surround_with(acc: &mut Assists, ctx: &AssistContext<'_>, construct: Construct) -> Option<()> {
+    let (id, label) = match construct {
+        Construct::If => ("surround_with_if", "Surround with `if`"),
+        Construct::Loop => ("surround_with_loop", "Surround with `loop`"),
+        Construct::Unsafe => ("surround_with_unsafe", "Surround with `unsafe`"),
+        Construct::AsyncMove => ("surround_with_async_move", "Surround with `async move`"),
+    };
+    // A lone expression is wrapped in place, which an `if` or `loop` can't do without changing
+    // its value.
+    let target = match (construct, selected_expr(ctx)) {
+        (Construct::AsyncMove, expr) => {
+            // A future in statement position would be dropped without ever being awaited.
+            let expr = expr.filter(|it| {
+                !it.syntax().parent().is_some_and(|it| ast::ExprStmt::can_cast(it.kind()))
+            });
+            let Some(expr) = expr else {
+                cov_mark::hit!(surround_with_async_move_unused);
+                return None;
+            };
+            Selection::Expr(expr)
+        }
+        (Construct::Unsafe, Some(expr)) => Selection::Expr(expr),
+        _ => {
+            let stmts = selected_stmts(ctx)?;
+            if stmts.has_tail && matches!(construct, Construct::If | Construct::Loop) {
+                cov_mark::hit!(surround_with_tail_expr);
+                return None;
+            }
+            // The new block ends the scope of the selected `let`s.
+            if stmts.bindings_used_later(ctx) {
+                cov_mark::hit!(surround_with_binding_used_later);
+                return None;
+            }
+            Selection::Stmts(stmts)
+        }
+    };
+    // The new `loop` or `async` block would catch the jumps meant for the code around it.
+    if matches!(construct, Construct::Loop | Construct::AsyncMove) {
+        let jumps_out = match &target {
+            Selection::Expr(expr) => jumps_out_of(expr.syntax()),
+            Selection::Stmts(stmts) => {
+                stmts.elements.iter().filter_map(|it| it.as_node()).any(jumps_out_of)
+            }
+        };
+        if jumps_out {
+            cov_mark::hit!(surround_with_jumps_out);
+            return None;
+        }
+    }
+    let range = match &target {
+        Selection::Expr(expr) => expr.syntax().text_range(),
+        Selection::Stmts(stmts) => stmts.first.text_range().cover(stmts.last.text_range()),
+    };
+
+    acc.add_group(
+        &GroupLabel("Surround with…".to_owned()),
+        AssistId(id, AssistKind::RefactorRewrite),
+        label,
+        range,
+        |builder| {
+            let (block, indent) = match &target {
+                Selection::Expr(expr) => (
+                    make::tail_only_block_expr(expr.clone()),
+                    IndentLevel::from_node(expr.syntax()),
+                ),
+                Selection::Stmts(stmts) => (stmts.block(), stmts.indent),
+            };
+            let new_expr = match construct {
+                Construct::If => {
+                    let condition = make::expr_path(make::ext::ident_path("condition"));
+                    make::expr_if(condition, block, None)
+                }
+                Construct::Loop => make::expr_loop(block),
+                Construct::Unsafe => make::expr_unsafe(block),
+                Construct::AsyncMove => make::expr_async_move(block),
+            }
+            .indent(indent)
+            .clone_for_update();
+
+            match target {
+                Selection::Expr(expr) => {
+                    let expr = builder.make_mut(expr);
+                    ted::replace(expr.syntax(), new_expr.syntax());
+                }
+                Selection::Stmts(stmts) => {
+                    let first = builder.make_syntax_mut(stmts.first);
+                    let last = builder.make_syntax_mut(stmts.last);
+                    ted::replace_all(
+                        first.into()..=last.into(),
+                        vec![new_expr.syntax().clone().into()],
+                    );
+                }
+            }
+
+            if let (Some(cap), ast::Expr::IfExpr(if_expr)) = (ctx.config.snippet_cap, &new_expr) {
+                if let Some(condition) = if_expr.condition() {
+                    builder.add_placeholder_snippet(cap, condition);
+                }
+            }
+        },
+    )
+}
+
+enum Selection {
+    Expr(ast::Expr),
+    Stmts(SelectedStmts),
+}
+
+/// A run of statements of a single block.
+struct SelectedStmts {
+    first: SyntaxNode,
+    last: SyntaxNode,
+    /// The statements with the comments and whitespace in between.
+    elements: Vec<SyntaxElement>,
+    has_tail: bool,
+    indent: IndentLevel,
+}
+
+impl SelectedStmts {
+    fn block(&self) -> ast::BlockExpr {
+        let elements = self.elements.iter().map(|element| match element {
+            NodeOrToken::Node(node) => NodeOrToken::Node(reindent(node, self.indent)),
+            NodeOrToken::Token(token) => NodeOrToken::Token(token.clone()),
+        });
+        make::hacky_block_expr(elements, None)
+    }
+
+    /// Whether a binding of the selected `let` statements is used after them.
+    fn bindings_used_later(&self, ctx: &AssistContext<'_>) -> bool {
+        let Some(stmt_list) = self.last.parent() else { return false };
+        let after = TextRange::new(self.last.text_range().end(), stmt_list.text_range().end());
+        let scope = SearchScope::file_range(FileRange { file_id: ctx.file_id(), range: after });
+        self.elements
+            .iter()
+            .filter_map(|it| ast::LetStmt::cast(it.as_node()?.clone()))
+            .filter_map(|let_stmt| let_stmt.pat())
+            .flat_map(|pat| pat.syntax().descendants().filter_map(ast::IdentPat::cast))
+            .filter_map(|ident_pat| ctx.sema.to_def(&ident_pat))
+            .any(|local| Definition::Local(local).usages(&ctx.sema).in_scope(&scope).at_least_one())
+    }
+}
+
+/// Moves the lines of a statement from `indent` to the first level of indentation.
+fn reindent(node: &SyntaxNode, indent: IndentLevel) -> SyntaxNode {
+    fn reindent_ast<N: AstNodeEdit>(node: N, indent: IndentLevel) -> SyntaxNode {
+        node.dedent(indent).indent(IndentLevel(1)).syntax().clone()
+    }
+    match (ast::Stmt::cast(node.clone()), ast::Expr::cast(node.clone())) {
+        (Some(stmt), _) => reindent_ast(stmt, indent),
+        (_, Some(expr)) => reindent_ast(expr, indent),
+        _ => node.clone(),
+    }
+}
+
+fn selected_stmts(ctx: &AssistContext<'_>) -> Option<SelectedStmts> {
+    let range = ctx.selection_trimmed();
+    if range.is_empty() {
+        return None;
+    }
+    let covering = match ctx.covering_element() {
+        NodeOrToken::Node(it) => it,
+        NodeOrToken::Token(it) => it.parent()?,
+    };
+    let stmt_list = covering.ancestors().find_map(ast::StmtList::cast)?;
+    // Statements can't be taken apart.
+    let splits_stmt = stmt_list.syntax().children().any(|it| {
+        let it = it.text_range();
+        it.intersect(range).map_or(false, |it| !it.is_empty()) && !range.contains_range(it)
+    });
+    if splits_stmt {
+        return None;
+    }
+
+    let selected = stmt_list
+        .syntax()
+        .children_with_tokens()
+        .filter(|it| range.contains_range(it.text_range()))
+        .collect::<Vec<_>>();
+    if selected.iter().any(|it| matches!(it.kind(), SyntaxKind::L_CURLY | SyntaxKind::R_CURLY)) {
+        return None;
+    }
+    let start = selected.iter().position(|it| it.as_node().is_some())?;
+    let end = selected.iter().rposition(|it| it.as_node().is_some())?;
+    let elements = selected[start..=end].to_vec();
+    let first = elements.first()?.as_node()?.clone();
+    let last = elements.last()?.as_node()?.clone();
+    let has_tail = stmt_list.tail_expr().map_or(false, |it| it.syntax() == &last);
+    let indent = IndentLevel::from_node(&first);
+    Some(SelectedStmts { first, last, elements, has_tail, indent })
+}
+
+/// Whether `node` breaks, continues, returns or propagates an error to the code around it.
+fn jumps_out_of(node: &SyntaxNode) -> bool {
+    let mut preorder = node.preorder();
+    while let Some(event) = preorder.next() {
+        let WalkEvent::Enter(it) = event else { continue };
+        match it.kind() {
+            SyntaxKind::RETURN_EXPR | SyntaxKind::TRY_EXPR => return true,
+            SyntaxKind::BREAK_EXPR | SyntaxKind::CONTINUE_EXPR if !jumps_within(&it, node) => {
+                return true
+            }
+            SyntaxKind::CLOSURE_EXPR => preorder.skip_subtree(),
+            kind if ast::Item::can_cast(kind) => preorder.skip_subtree(),
+            SyntaxKind::BLOCK_EXPR => {
+                let modifier = ast::BlockExpr::cast(it).and_then(|it| it.modifier());
+                if matches!(
+                    modifier,
+                    Some(
+                        ast::BlockModifier::Async(_)
+                            | ast::BlockModifier::Try(_)
+                            | ast::BlockModifier::Const(_)
+                    )
+                ) {
+                    preorder.skip_subtree();
+                }
+            }
+            _ => (),
+        }
+    }
+    false
+}
+
+/// Whether the `break` or `continue` `jump` goes to a loop or labeled block within `root`.
+fn jumps_within(jump: &SyntaxNode, root: &SyntaxNode) -> bool {
+    let lifetime = match ast::BreakExpr::cast(jump.clone()) {
+        Some(it) => it.lifetime(),
+        None => ast::ContinueExpr::cast(jump.clone()).and_then(|it| it.lifetime()),
+    };
+    let outside = root.parent();
+    jump.ancestors().take_while(|it| Some(it) != outside.as_ref()).any(|it| match &lifetime {
+        Some(lifetime) => it
+            .children()
+            .find_map(ast::Label::cast)
+            .and_then(|it| it.lifetime())
+            .is_some_and(|it| it.syntax().text() == lifetime.syntax().text()),
+        None => matches!(
+            it.kind(),
+            SyntaxKind::LOOP_EXPR | SyntaxKind::WHILE_EXPR | SyntaxKind::FOR_EXPR
+        ),
+    })
+}
+
+/// The expression the selection covers exactly.
+fn selected_expr(ctx: &AssistContext<'_>) -> Option<ast::Expr> {
+    let range = ctx.selection_trimmed();
+    if range.is_empty() {
+        return None;
+    }
+    let covering = match ctx.covering_element() {
+        NodeOrToken::Node(it) => it,
+        NodeOrToken::Token(it) => it.parent()?,
+    };
+    covering
+        .ancestors()
+        .take_while(|it| it.text_range() == range)
+        .filter_map(ast::Expr::cast)
+        .last()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn if_keeps_comments_and_nested_indentation() {
+        check_assist(
+            surround_with_if,
+            r#"
+fn f() {
+    let a = 1;
+    $0// first
+    for i in 0..a {
+        drop(i);
+    }
+
+    drop(a);$0
+    drop(a);
+}
+"#,
+            r#"
+fn f() {
+    let a = 1;
+    // first
+    if ${0:condition} {
+        for i in 0..a {
+            drop(i);
+        }
+
+        drop(a);
+    }
+    drop(a);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn unsafe_with_tail_expression() {
+        check_assist(
+            surround_with_unsafe,
+            r#"
+unsafe fn g() -> u8 { 0 }
+fn f() -> u8 {
+    $0let x = 1;
+    g() + x$0
+}
+"#,
+            r#"
+unsafe fn g() -> u8 { 0 }
+fn f() -> u8 {
+    unsafe {
+        let x = 1;
+        g() + x
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_a_binding_is_used_later() {
+        cov_mark::check!(surround_with_binding_used_later);
+        check_assist_not_applicable(
+            surround_with_if,
+            r#"
+fn f() {
+    $0let (x, y) = (1, 2);
+    let z = x;$0
+    let w = y;
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn loop_not_applicable_to_tail_or_partial_statements() {
+        cov_mark::check!(surround_with_tail_expr);
+        check_assist_not_applicable(
+            surround_with_loop,
+            r#"
+fn f() -> u8 {
+    $0let x = 1;
+    x$0
+}
+"#,
+        );
+        check_assist_not_applicable(
+            surround_with_loop,
+            r#"
+fn f() {
+    let x = $01;
+    drop(x);$0
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn loop_around_inner_jumps() {
+        check_assist(
+            surround_with_loop,
+            r#"
+fn f() {
+    $0for i in 0..3 {
+        if i == 1 {
+            continue;
+        }
+        let g = || return i;
+    }$0
+    drop(1);
+}
+"#,
+            r#"
+fn f() {
+    loop {
+        for i in 0..3 {
+            if i == 1 {
+                continue;
+            }
+            let g = || return i;
+        }
+    }
+    drop(1);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_around_outward_jumps() {
+        cov_mark::check_count!(surround_with_jumps_out, 3);
+        check_assist_not_applicable(
+            surround_with_loop,
+            r#"
+fn f() {
+    'outer: for i in 0..3 {
+        $0for j in 0..3 {
+            if j == i {
+                continue 'outer;
+            }
+        }$0
+        drop(i);
+    }
+}
+"#,
+        );
+        check_assist_not_applicable(
+            surround_with_loop,
+            r#"
+fn f(x: Option<u8>) -> Option<u8> {
+    $0let y = x?;
+    drop(y);$0
+    None
+}
+"#,
+        );
+        check_assist_not_applicable(
+            surround_with_async_move,
+            r#"
+fn f(x: u8) -> u8 {
+    let y = $0if x == 0 { return 1 } else { x }$0;
+    y
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn async_move_not_applicable_to_statements() {
+        cov_mark::check_count!(surround_with_async_move_unused, 2);
+        check_assist_not_applicable(
+            surround_with_async_move,
+            r#"
+fn f(s: String) {
+    $0drop(s);$0
+}
+"#,
+        );
+        check_assist_not_applicable(
+            surround_with_async_move,
+            r#"
+fn f(s: String) {
+    $0drop(s)$0;
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn match_on_bool_and_other_types() {
+        check_assist(
+            surround_with_match,
+            r#"
+fn f(flag: bool) -> u8 {
+    $0flag$0
+}
+"#,
+            r#"
+fn f(flag: bool) -> u8 {
+    match flag {
+        true => todo!(),
+        false => todo!(),
+    }
+}
+"#,
+        );
+        check_assist(
+            surround_with_match,
+            r#"
+fn f(n: u8) {
+    let x = $0n + 1$0;
+}
+"#,
+            r#"
+fn f(n: u8) {
+    let x = match n + 1 {
+        _ => todo!(),
+    };
+}
+"#,
+        );
+    }
+}
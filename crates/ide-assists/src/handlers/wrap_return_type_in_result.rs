@@ -1,15 +1,24 @@
 use std::iter;
 
+use hir::HirDisplay;
 use ide_db::{
+    base_db::FileId,
+    defs::Definition,
     famous_defs::FamousDefs,
+    search::FileReference,
     syntax_helpers::node_ext::{for_each_tail_expr, walk_expr},
+    ty_filter::TryEnum,
 };
+use itertools::Itertools;
 use syntax::{
     ast::{self, make, Expr},
-    match_ast, ted, AstNode,
+    match_ast, ted, AstNode, TextRange,
 };
 
-use crate::{AssistContext, AssistId, AssistKind, Assists};
+use crate::{
+    assist_context::SourceChangeBuilder, utils::fn_call_at, AssistContext, AssistId, AssistKind,
+    Assists, WrapReturnTypeCallers,
+};
 
 // Assist: wrap_return_type_in_result
 //
@@ -24,13 +33,33 @@ use crate::{AssistContext, AssistId, AssistKind, Assists};
 // fn foo() -> Result<i32, ${0:_}> { Ok(42i32) }
 // ```
 pub(crate) fn wrap_return_type_in_result(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    wrap_return_type(acc, ctx, TryEnum::Result)
+}
+
+// Assist: wrap_return_type_in_option
+//
+// Wrap the function's return type into Option.
+//
+// ```
+// # //- minicore: option
+// fn foo() -> i32$0 { 42i32 }
+// ```
+// ->
+// ```
+// fn foo() -> Option<i32> { Some(42i32) }
+// ```
+pub(crate) fn wrap_return_type_in_option(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    wrap_return_type(acc, ctx, TryEnum::Option)
+}
+
+fn wrap_return_type(acc: &mut Assists, ctx: &AssistContext<'_>, kind: TryEnum) -> Option<()> {
     let ret_type = ctx.find_node_at_offset::<ast::RetType>()?;
     let parent = ret_type.syntax().parent()?;
-    let body = match_ast! {
+    let (func, body) = match_ast! {
         match parent {
-            ast::Fn(func) => func.body()?,
+            ast::Fn(func) => (Some(func.clone()), func.body()?),
             ast::ClosureExpr(closure) => match closure.body()? {
-                Expr::BlockExpr(block) => block,
+                Expr::BlockExpr(block) => (None, block),
                 // closures require a block when a return type is specified
                 _ => return None,
             },
@@ -39,23 +68,55 @@ pub(crate) fn wrap_return_type_in_result(acc: &mut Assists, ctx: &AssistContext<
     };
 
     let type_ref = &ret_type.ty()?;
-    let ty = ctx.sema.resolve_type(type_ref)?.as_adt();
-    let result_enum =
-        FamousDefs(&ctx.sema, ctx.sema.scope(type_ref.syntax())?.krate()).core_result_Result()?;
-
-    if matches!(ty, Some(hir::Adt::Enum(ret_type)) if ret_type == result_enum) {
-        cov_mark::hit!(wrap_return_type_in_result_simple_return_type_already_result);
+    let ty = ctx.sema.resolve_type(type_ref)?;
+    if matches!(TryEnum::from_ty(&ctx.sema, &ty), Some(it) if it == kind) {
+        if kind == TryEnum::Result {
+            cov_mark::hit!(wrap_return_type_in_result_simple_return_type_already_result);
+        }
         return None;
     }
+    let famous_defs = FamousDefs(&ctx.sema, ctx.sema.scope(type_ref.syntax())?.krate());
+    let (new_ty, happy_ctor, id, label) = match kind {
+        TryEnum::Result => {
+            famous_defs.core_result_Result()?;
+            let error_ty = error_type_of_try_exprs(ctx, &body)
+                .map_or_else(make::ty_placeholder, |it| make::ty(&it));
+            (
+                make::ext::ty_result(type_ref.clone(), error_ty),
+                "Ok",
+                "wrap_return_type_in_result",
+                "Wrap return type in Result",
+            )
+        }
+        TryEnum::Option => {
+            famous_defs.core_option_Option()?;
+            (
+                make::ext::ty_option(type_ref.clone()),
+                "Some",
+                "wrap_return_type_in_option",
+                "Wrap return type in Option",
+            )
+        }
+    };
+    let new_ty = new_ty.clone_for_update();
+    let error_ty = match kind {
+        TryEnum::Result => {
+            new_ty.syntax().descendants().find_map(ast::GenericArgList::cast)?.generic_args().last()
+        }
+        TryEnum::Option => None,
+    };
 
-    let new_result_ty =
-        make::ext::ty_result(type_ref.clone(), make::ty_placeholder()).clone_for_update();
-    let generic_args = new_result_ty.syntax().descendants().find_map(ast::GenericArgList::cast)?;
-    let last_genarg = generic_args.generic_args().last()?;
+    let callers = match (ctx.config.wrap_return_type_callers, &func) {
+        (WrapReturnTypeCallers::None, _) | (_, None) => Vec::new(),
+        (mode, Some(func)) => {
+            let fn_def = ctx.sema.to_def(func)?;
+            collect_callers(ctx, fn_def, func.syntax().text_range(), mode, kind)
+        }
+    };
 
     acc.add(
-        AssistId("wrap_return_type_in_result", AssistKind::RefactorRewrite),
-        "Wrap return type in Result",
+        AssistId(id, AssistKind::RefactorRewrite),
+        label,
         type_ref.syntax().text_range(),
         |edit| {
             let body = edit.make_mut(ast::Expr::BlockExpr(body));
@@ -72,26 +133,110 @@ pub(crate) fn wrap_return_type_in_result(acc: &mut Assists, ctx: &AssistContext<
             for_each_tail_expr(&body, tail_cb);
 
             for ret_expr_arg in exprs_to_wrap {
-                let ok_wrapped = make::expr_call(
-                    make::expr_path(make::ext::ident_path("Ok")),
+                let happy_wrapped = make::expr_call(
+                    make::expr_path(make::ext::ident_path(happy_ctor)),
                     make::arg_list(iter::once(ret_expr_arg.clone())),
                 )
                 .clone_for_update();
-                ted::replace(ret_expr_arg.syntax(), ok_wrapped.syntax());
+                ted::replace(ret_expr_arg.syntax(), happy_wrapped.syntax());
             }
 
-            let old_result_ty = edit.make_mut(type_ref.clone());
+            let old_ty = edit.make_mut(type_ref.clone());
 
-            ted::replace(old_result_ty.syntax(), new_result_ty.syntax());
+            ted::replace(old_ty.syntax(), new_ty.syntax());
 
-            if let Some(cap) = ctx.config.snippet_cap {
-                edit.add_placeholder_snippet(cap, last_genarg);
+            if let Some((cap, error_ty)) = ctx.config.snippet_cap.zip(error_ty) {
+                edit.add_placeholder_snippet(cap, error_ty);
             }
+
+            update_callers(edit, ctx, callers);
         },
     )
 }
 
-fn tail_cb_impl(acc: &mut Vec<ast::Expr>, e: &ast::Expr) {
+/// Returns the error type shared by all `?` applied to `Result`s in the body, if there is one.
+fn error_type_of_try_exprs(ctx: &AssistContext<'_>, body: &ast::BlockExpr) -> Option<String> {
+    let module = ctx.sema.scope(body.syntax())?.module();
+    let mut error_types = Vec::new();
+    walk_expr(&ast::Expr::BlockExpr(body.clone()), &mut |expr| {
+        let Expr::TryExpr(try_expr) = expr else { return };
+        let Some(ty) = try_expr.expr().and_then(|it| ctx.sema.type_of_expr(&it)) else { return };
+        let ty = ty.original;
+        if let Some(TryEnum::Result) = TryEnum::from_ty(&ctx.sema, &ty) {
+            error_types.push(ty.type_arguments().nth(1));
+        }
+    });
+    let error_ty = error_types.into_iter().all_equal_value().ok()??;
+    if error_ty.is_unknown() {
+        return None;
+    }
+    error_ty.display_source_code(ctx.db(), module.into(), false).ok()
+}
+
+/// A call to update along with the text to append to it.
+pub(crate) type CallerEdit = (FileId, TextRange, &'static str);
+
+pub(crate) fn collect_callers(
+    ctx: &AssistContext<'_>,
+    fn_def: hir::Function,
+    fn_range: TextRange,
+    mode: WrapReturnTypeCallers,
+    kind: TryEnum,
+) -> Vec<CallerEdit> {
+    let mut res = Vec::new();
+    for (file_id, references) in Definition::Function(fn_def).usages(&ctx.sema).all() {
+        let source_file = ctx.sema.parse(file_id);
+        for FileReference { range, .. } in references {
+            // Calls inside of the function itself are part of the body we are rewriting.
+            if file_id == ctx.file_id() && fn_range.contains_range(range) {
+                continue;
+            }
+            let Some(call) = fn_call_at(&source_file, range) else { continue };
+            let suffix = match mode {
+                WrapReturnTypeCallers::Try if caller_returns(ctx, &call, kind) => "?",
+                _ => ".unwrap()",
+            };
+            res.push((file_id, call.syntax().text_range(), suffix));
+        }
+    }
+    res
+}
+
+/// Whether the body containing `call` returns the same kind of wrapper, so `?` can be used.
+fn caller_returns(ctx: &AssistContext<'_>, call: &ast::Expr, kind: TryEnum) -> bool {
+    let ret_type = call.syntax().ancestors().find_map(|node| {
+        match_ast! {
+            match node {
+                ast::Fn(it) => Some(it.ret_type()),
+                ast::ClosureExpr(it) => Some(it.ret_type()),
+                _ => None,
+            }
+        }
+    });
+    let Some(Some(ty)) = ret_type.flatten().map(|it| it.ty()) else { return false };
+    ctx.sema
+        .resolve_type(&ty)
+        .and_then(|ty| TryEnum::from_ty(&ctx.sema, &ty))
+        .map_or(false, |it| it == kind)
+}
+
+pub(crate) fn update_callers(
+    edit: &mut SourceChangeBuilder,
+    ctx: &AssistContext<'_>,
+    callers: Vec<CallerEdit>,
+) {
+    let (current, others): (Vec<_>, Vec<_>) =
+        callers.into_iter().partition(|(file_id, ..)| *file_id == ctx.file_id());
+    for (_, range, suffix) in current {
+        edit.insert(range.end(), suffix);
+    }
+    for (file_id, range, suffix) in others {
+        edit.edit_file(file_id);
+        edit.insert(range.end(), suffix);
+    }
+}
+
+pub(crate) fn tail_cb_impl(acc: &mut Vec<ast::Expr>, e: &ast::Expr) {
     match e {
         Expr::BreakExpr(break_expr) => {
             if let Some(break_expr_arg) = break_expr.expr() {
@@ -107,7 +252,10 @@ fn tail_cb_impl(acc: &mut Vec<ast::Expr>, e: &ast::Expr) {
 
 #[cfg(test)]
 mod tests {
-    use crate::tests::{check_assist, check_assist_not_applicable};
+    use crate::{
+        tests::{check_assist, check_assist_not_applicable, check_assist_with_config, TEST_CONFIG},
+        AssistConfig,
+    };
 
     use super::*;
 
@@ -995,6 +1143,137 @@ fn foo(the_field: u32) -> Result<u32, ${0:_}> {
     }
     Ok(the_field)
 }
+"#,
+        );
+    }
+
+    #[test]
+    fn wrap_return_type_in_result_infers_error_type() {
+        check_assist(
+            wrap_return_type_in_result,
+            r#"
+//- minicore: result, try, from
+struct Error;
+fn parse() -> Result<u8, Error> { Ok(0) }
+fn foo() -> u8$0 {
+    let a = parse()?;
+    a + parse()?
+}
+"#,
+            r#"
+struct Error;
+fn parse() -> Result<u8, Error> { Ok(0) }
+fn foo() -> Result<u8, ${0:Error}> {
+    let a = parse()?;
+    Ok(a + parse()?)
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn wrap_return_type_in_result_mixed_error_types() {
+        check_assist(
+            wrap_return_type_in_result,
+            r#"
+//- minicore: result, try, from
+fn foo(a: Result<u8, u8>, b: Result<u8, ()>) -> u8$0 {
+    a? + b?
+}
+"#,
+            r#"
+fn foo(a: Result<u8, u8>, b: Result<u8, ()>) -> Result<u8, ${0:_}> {
+    Ok(a? + b?)
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn wrap_return_type_in_option_simple() {
+        check_assist(
+            wrap_return_type_in_option,
+            r#"
+//- minicore: option
+fn foo(x: i32) -> i32$0 {
+    if x > 0 {
+        return x;
+    }
+    0
+}
+"#,
+            r#"
+fn foo(x: i32) -> Option<i32> {
+    if x > 0 {
+        return Some(x);
+    }
+    Some(0)
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn wrap_return_type_in_option_already_option() {
+        check_assist_not_applicable(
+            wrap_return_type_in_option,
+            r#"
+//- minicore: option
+fn foo() -> Option<i32>$0 { None }
+"#,
+        );
+    }
+
+    #[test]
+    fn wrap_return_type_updates_callers_with_try() {
+        check_assist_with_config(
+            AssistConfig { wrap_return_type_callers: WrapReturnTypeCallers::Try, ..TEST_CONFIG },
+            wrap_return_type_in_option,
+            r#"
+//- minicore: option, try
+fn foo() -> i32$0 { 42 }
+fn propagates() -> Option<i32> {
+    Some(foo() + 1)
+}
+fn unwraps() -> i32 {
+    foo()
+}
+"#,
+            r#"
+fn foo() -> Option<i32> { Some(42) }
+fn propagates() -> Option<i32> {
+    Some(foo()? + 1)
+}
+fn unwraps() -> i32 {
+    foo().unwrap()
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn wrap_return_type_updates_callers_with_unwrap() {
+        check_assist_with_config(
+            AssistConfig { wrap_return_type_callers: WrapReturnTypeCallers::Unwrap, ..TEST_CONFIG },
+            wrap_return_type_in_result,
+            r#"
+//- minicore: result, try, from
+struct S;
+impl S {
+    fn foo(&self) -> i32$0 { 42 }
+}
+fn bar() -> Result<i32, ()> {
+    Ok(S.foo())
+}
+"#,
+            r#"
+struct S;
+impl S {
+    fn foo(&self) -> Result<i32, ${0:_}> { Ok(42) }
+}
+fn bar() -> Result<i32, ()> {
+    Ok(S.foo().unwrap())
+}
 "#,
         );
     }
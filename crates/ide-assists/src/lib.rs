@@ -72,7 +72,9 @@ use syntax::TextRange;
 
 pub(crate) use crate::assist_context::{AssistContext, Assists};
 
-pub use assist_config::AssistConfig;
+pub use assist_config::{
+    AssistConfig, PanicErrorType, PromotedConstPlacement, WrapReturnTypeCallers,
+};
 pub use ide_db::assists::{
     Assist, AssistId, AssistKind, AssistResolveStrategy, GroupLabel, SingleResolve,
 };
@@ -107,32 +109,46 @@ mod handlers {
     mod add_lifetime_to_type;
     mod add_missing_impl_members;
     mod add_missing_match_arms;
+    mod add_param;
     mod add_return_type;
+    mod add_serde_rename_attributes;
     mod add_turbo_fish;
     mod apply_demorgan;
     mod auto_import;
     mod bind_unused_param;
     mod bool_to_enum;
     mod change_visibility;
+    mod convert_async_fn_to_impl_future;
     mod convert_bool_then;
+    mod convert_box_dyn_field_to_generic;
     mod convert_comment_block;
+    mod convert_constructor_to_try_from;
+    mod convert_for_loop_to_iterator_chain;
+    mod convert_free_fn_to_method;
+    mod convert_from_str_to_try_from;
     mod convert_integer_literal;
     mod convert_into_to_from;
     mod convert_iter_for_each_to_for;
     mod convert_let_else_to_match;
+    mod convert_let_else_to_try_expr;
     mod convert_match_to_let_else;
     mod convert_named_struct_to_tuple_struct;
     mod convert_nested_function_to_closure;
+    mod convert_nested_match_to_tuple_match;
+    mod convert_panics_to_result;
+    mod convert_string_concat_to_format;
     mod convert_to_guarded_return;
     mod convert_tuple_return_type_to_struct;
     mod convert_tuple_struct_to_named_struct;
     mod convert_two_arm_bool_match_to_matches_macro;
     mod convert_while_to_loop;
+    mod destructure_struct_binding;
     mod destructure_tuple_binding;
     mod desugar_doc_comment;
     mod expand_glob_import;
     mod extract_expressions_from_format_string;
     mod extract_function;
+    mod extract_generic_param;
     mod extract_module;
     mod extract_struct_from_enum_variant;
     mod extract_type_alias;
@@ -148,26 +164,34 @@ mod handlers {
     mod generate_delegate_trait;
     mod generate_deref;
     mod generate_derive;
+    mod generate_display_impl;
     mod generate_documentation_template;
     mod generate_enum_is_method;
     mod generate_enum_projection_method;
     mod generate_enum_variant;
     mod generate_from_impl_for_enum;
+    mod generate_from_impl_for_struct;
     mod generate_function;
     mod generate_getter_or_setter;
     mod generate_impl;
     mod generate_is_empty_from_len;
     mod generate_mut_trait_impl;
     mod generate_new;
+    mod generate_new_from_default;
     mod generate_trait_from_impl;
+    mod generate_unit_test;
     mod inline_call;
     mod inline_const_as_literal;
+    mod inline_format_args;
     mod inline_local_variable;
     mod inline_macro;
     mod inline_type_alias;
     mod into_to_qualified_from;
+    mod introduce_field;
     mod introduce_named_generic;
+    mod introduce_named_generic_with_where_clause;
     mod introduce_named_lifetime;
+    mod introduce_parameter;
     mod invert_if;
     mod merge_imports;
     mod merge_match_arms;
@@ -177,11 +201,14 @@ mod handlers {
     mod move_from_mod_rs;
     mod move_guard;
     mod move_module_to_file;
+    mod move_param;
     mod move_to_mod_rs;
     mod normalize_import;
     mod number_representation;
+    mod organize_imports;
     mod promote_local_to_const;
     mod pull_assignment_up;
+    mod qualify_imported_path;
     mod qualify_method_call;
     mod qualify_path;
     mod raw_string;
@@ -193,10 +220,12 @@ mod handlers {
     mod reorder_fields;
     mod reorder_impl_items;
     mod replace_arith_op;
+    mod replace_deprecated_path;
     mod replace_derive_with_manual_impl;
     mod replace_if_let_with_match;
     mod replace_is_method_with_if_let_method;
     mod replace_let_with_if_let;
+    mod replace_match_with_try_expr;
     mod replace_method_eager_lazy;
     mod replace_named_generic_with_impl;
     mod replace_qualified_name_with_use;
@@ -204,7 +233,9 @@ mod handlers {
     mod replace_try_expr_with_match;
     mod replace_turbofish_with_explicit_type;
     mod sort_items;
+    mod sort_match_arms;
     mod split_import;
+    mod surround_with;
     mod term_search;
     mod toggle_ignore;
     mod unmerge_match_arm;
@@ -223,8 +254,11 @@ mod handlers {
             add_explicit_type::add_explicit_type,
             add_label_to_loop::add_label_to_loop,
             add_missing_match_arms::add_missing_match_arms,
+            add_param::add_param,
             add_lifetime_to_type::add_lifetime_to_type,
             add_return_type::add_return_type,
+            add_serde_rename_attributes::add_serde_rename_attributes,
+            add_serde_rename_attributes::remove_serde_rename_attributes,
             add_turbo_fish::add_turbo_fish,
             apply_demorgan::apply_demorgan,
             apply_demorgan::apply_demorgan_iterator,
@@ -232,26 +266,44 @@ mod handlers {
             bind_unused_param::bind_unused_param,
             bool_to_enum::bool_to_enum,
             change_visibility::change_visibility,
+            convert_async_fn_to_impl_future::convert_async_fn_to_impl_future,
+            convert_async_fn_to_impl_future::convert_impl_future_to_async_fn,
             convert_bool_then::convert_bool_then_to_if,
             convert_bool_then::convert_if_to_bool_then,
+            convert_box_dyn_field_to_generic::convert_box_dyn_field_to_generic,
+            convert_box_dyn_field_to_generic::convert_generic_field_to_box_dyn,
             convert_comment_block::convert_comment_block,
+            convert_constructor_to_try_from::convert_constructor_to_try_from,
+            convert_for_loop_to_iterator_chain::convert_for_loop_to_iterator_chain,
+            convert_for_loop_to_iterator_chain::convert_iterator_chain_to_for_loop,
             convert_integer_literal::convert_integer_literal,
+            convert_free_fn_to_method::convert_free_fn_to_method,
+            convert_from_str_to_try_from::convert_from_str_to_try_from,
             convert_into_to_from::convert_into_to_from,
             convert_iter_for_each_to_for::convert_iter_for_each_to_for,
             convert_iter_for_each_to_for::convert_for_loop_with_for_each,
             convert_let_else_to_match::convert_let_else_to_match,
+            convert_let_else_to_try_expr::convert_let_else_to_try_expr,
+            convert_let_else_to_try_expr::convert_try_expr_to_let_else,
             convert_match_to_let_else::convert_match_to_let_else,
             convert_tuple_return_type_to_struct::convert_tuple_return_type_to_struct,
             convert_named_struct_to_tuple_struct::convert_named_struct_to_tuple_struct,
             convert_nested_function_to_closure::convert_nested_function_to_closure,
+            convert_nested_match_to_tuple_match::convert_nested_match_to_tuple_match,
+            convert_nested_match_to_tuple_match::convert_tuple_match_to_nested_match,
+            convert_panics_to_result::convert_panics_to_result,
             convert_to_guarded_return::convert_to_guarded_return,
             convert_tuple_struct_to_named_struct::convert_tuple_struct_to_named_struct,
             convert_two_arm_bool_match_to_matches_macro::convert_two_arm_bool_match_to_matches_macro,
             convert_while_to_loop::convert_while_to_loop,
             desugar_doc_comment::desugar_doc_comment,
+            destructure_struct_binding::destructure_struct_binding,
             destructure_tuple_binding::destructure_tuple_binding,
             expand_glob_import::expand_glob_import,
             extract_expressions_from_format_string::extract_expressions_from_format_string,
+            convert_string_concat_to_format::convert_string_concat_to_format,
+            inline_format_args::inline_format_args,
+            inline_format_args::uninline_format_args,
             extract_struct_from_enum_variant::extract_struct_from_enum_variant,
             extract_type_alias::extract_type_alias,
             fix_visibility::fix_visibility,
@@ -263,6 +315,8 @@ mod handlers {
             generate_default_from_new::generate_default_from_new,
             generate_delegate_trait::generate_delegate_trait,
             generate_derive::generate_derive,
+            generate_display_impl::generate_display_impl,
+            generate_display_impl::generate_from_str_impl,
             generate_documentation_template::generate_documentation_template,
             generate_documentation_template::generate_doc_example,
             generate_enum_is_method::generate_enum_is_method,
@@ -270,13 +324,17 @@ mod handlers {
             generate_enum_projection_method::generate_enum_try_into_method,
             generate_enum_variant::generate_enum_variant,
             generate_from_impl_for_enum::generate_from_impl_for_enum,
+            generate_from_impl_for_struct::generate_from_impl_for_struct,
             generate_function::generate_function,
             generate_impl::generate_impl,
             generate_impl::generate_trait_impl,
             generate_mut_trait_impl::generate_mut_trait_impl,
             generate_is_empty_from_len::generate_is_empty_from_len,
             generate_new::generate_new,
+            generate_new::generate_new_with_defaults,
+            generate_new_from_default::generate_new_from_default,
             generate_trait_from_impl::generate_trait_from_impl,
+            generate_unit_test::generate_unit_test,
             inline_call::inline_call,
             inline_call::inline_into_callers,
             inline_const_as_literal::inline_const_as_literal,
@@ -284,23 +342,32 @@ mod handlers {
             inline_type_alias::inline_type_alias,
             inline_type_alias::inline_type_alias_uses,
             into_to_qualified_from::into_to_qualified_from,
+            introduce_field::introduce_field,
             introduce_named_generic::introduce_named_generic,
+            introduce_named_generic_with_where_clause::introduce_named_generic_with_where_clause,
+            introduce_parameter::introduce_parameter,
             introduce_named_lifetime::introduce_named_lifetime,
             invert_if::invert_if,
             merge_imports::merge_imports,
             merge_match_arms::merge_match_arms,
             merge_nested_if::merge_nested_if,
             move_bounds::move_bounds_to_where_clause,
+            move_bounds::move_where_clause_to_bounds,
             move_const_to_impl::move_const_to_impl,
             move_guard::move_arm_cond_to_match_guard,
             move_guard::move_guard_to_arm_body,
             move_module_to_file::move_module_to_file,
+            move_param::move_param_left,
+            move_param::move_param_right,
             move_to_mod_rs::move_to_mod_rs,
             move_from_mod_rs::move_from_mod_rs,
             normalize_import::normalize_import,
+            organize_imports::organize_imports,
             number_representation::reformat_number_literal,
             pull_assignment_up::pull_assignment_up,
             promote_local_to_const::promote_local_to_const,
+            promote_local_to_const::promote_local_to_item_const,
+            qualify_imported_path::qualify_imported_path,
             qualify_path::qualify_path,
             qualify_method_call::qualify_method_call,
             raw_string::add_hash,
@@ -313,6 +380,8 @@ mod handlers {
             reorder_fields::reorder_fields,
             reorder_impl_items::reorder_impl_items,
             replace_try_expr_with_match::replace_try_expr_with_match,
+            replace_match_with_try_expr::replace_match_with_try_expr,
+            replace_deprecated_path::replace_deprecated_path,
             replace_derive_with_manual_impl::replace_derive_with_manual_impl,
             replace_if_let_with_match::replace_if_let_with_match,
             replace_if_let_with_match::replace_match_with_if_let,
@@ -327,7 +396,13 @@ mod handlers {
             replace_arith_op::replace_arith_with_checked,
             replace_arith_op::replace_arith_with_saturating,
             sort_items::sort_items,
+            sort_match_arms::sort_match_arms,
             split_import::split_import,
+            surround_with::surround_with_async_move,
+            surround_with::surround_with_if,
+            surround_with::surround_with_loop,
+            surround_with::surround_with_match,
+            surround_with::surround_with_unsafe,
             term_search::term_search,
             toggle_ignore::toggle_ignore,
             unmerge_match_arm::unmerge_match_arm,
@@ -338,6 +413,7 @@ mod handlers {
             unwrap_tuple::unwrap_tuple,
             unqualify_method_call::unqualify_method_call,
             wrap_return_type_in_result::wrap_return_type_in_result,
+            wrap_return_type_in_result::wrap_return_type_in_option,
             // These are manually sorted for better priorities. By default,
             // priority is determined by the size of the target range (smaller
             // target wins). If the ranges are equal, position in this list is
@@ -351,6 +427,7 @@ mod handlers {
             //
             extract_variable::extract_variable,
             extract_function::extract_function,
+            extract_generic_param::extract_generic_param,
             extract_module::extract_module,
             //
             generate_getter_or_setter::generate_getter,
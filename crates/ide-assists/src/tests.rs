@@ -17,7 +17,8 @@ use test_utils::{assert_eq_text, extract_offset};
 
 use crate::{
     assists, handlers::Handler, Assist, AssistConfig, AssistContext, AssistKind,
-    AssistResolveStrategy, Assists, SingleResolve,
+    AssistResolveStrategy, Assists, PanicErrorType, PromotedConstPlacement, SingleResolve,
+    WrapReturnTypeCallers,
 };
 
 pub(crate) const TEST_CONFIG: AssistConfig = AssistConfig {
@@ -33,6 +34,11 @@ pub(crate) const TEST_CONFIG: AssistConfig = AssistConfig {
     prefer_no_std: false,
     prefer_prelude: true,
     assist_emit_must_use: false,
+    group_missing_match_arms: false,
+    smart_impl_member_stubs: false,
+    wrap_return_type_callers: WrapReturnTypeCallers::None,
+    promoted_const_placement: PromotedConstPlacement::BeforeItem,
+    panic_error_type: PanicErrorType::Enum,
 };
 
 pub(crate) const TEST_CONFIG_NO_SNIPPET_CAP: AssistConfig = AssistConfig {
@@ -48,6 +54,11 @@ pub(crate) const TEST_CONFIG_NO_SNIPPET_CAP: AssistConfig = AssistConfig {
     prefer_no_std: false,
     prefer_prelude: true,
     assist_emit_must_use: false,
+    group_missing_match_arms: false,
+    smart_impl_member_stubs: false,
+    wrap_return_type_callers: WrapReturnTypeCallers::None,
+    promoted_const_placement: PromotedConstPlacement::BeforeItem,
+    panic_error_type: PanicErrorType::Enum,
 };
 
 pub(crate) const TEST_CONFIG_IMPORT_ONE: AssistConfig = AssistConfig {
@@ -63,6 +74,11 @@ pub(crate) const TEST_CONFIG_IMPORT_ONE: AssistConfig = AssistConfig {
     prefer_no_std: false,
     prefer_prelude: true,
     assist_emit_must_use: false,
+    group_missing_match_arms: false,
+    smart_impl_member_stubs: false,
+    wrap_return_type_callers: WrapReturnTypeCallers::None,
+    promoted_const_placement: PromotedConstPlacement::BeforeItem,
+    panic_error_type: PanicErrorType::Enum,
 };
 
 pub(crate) fn with_single_file(text: &str) -> (RootDatabase, FileId) {
@@ -75,6 +91,23 @@ pub(crate) fn check_assist(assist: Handler, ra_fixture_before: &str, ra_fixture_
     check(assist, ra_fixture_before, ExpectedResult::After(&ra_fixture_after), None);
 }
 
+#[track_caller]
+pub(crate) fn check_assist_with_config(
+    config: AssistConfig,
+    assist: Handler,
+    ra_fixture_before: &str,
+    ra_fixture_after: &str,
+) {
+    let ra_fixture_after = trim_indent(ra_fixture_after);
+    check_with_config(
+        config,
+        assist,
+        ra_fixture_before,
+        ExpectedResult::After(&ra_fixture_after),
+        None,
+    );
+}
+
 #[track_caller]
 pub(crate) fn check_assist_no_snippet_cap(
     assist: Handler,
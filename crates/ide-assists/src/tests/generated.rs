@@ -195,6 +195,27 @@ fn handle(action: Action) {
     )
 }
 
+#[test]
+fn doctest_add_param() {
+    check_doc_test(
+        "add_param",
+        r#####"
+fn frobnicate(x: i32$0) {}
+
+fn main() {
+    frobnicate(92);
+}
+"#####,
+        r#####"
+fn frobnicate(x: i32, new_param: ${1:()}) {}
+
+fn main() {
+    frobnicate(92, ${0:todo!()});
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_add_return_type() {
     check_doc_test(
@@ -208,6 +229,28 @@ fn foo() -> i32 { 42i32 }
     )
 }
 
+#[test]
+fn doctest_add_serde_rename_attributes() {
+    check_doc_test(
+        "add_serde_rename_attributes",
+        r#####"
+#[derive(Serialize, Deserialize)]
+struct $0User {
+    userId: u32,
+    displayName: String,
+}
+"#####,
+        r#####"
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct User {
+    user_id: u32,
+    display_name: String,
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_add_turbo_fish() {
     check_doc_test(
@@ -345,6 +388,26 @@ pub(crate) fn frobnicate() {}
     )
 }
 
+#[test]
+fn doctest_convert_async_fn_to_impl_future() {
+    check_doc_test(
+        "convert_async_fn_to_impl_future",
+        r#####"
+//- minicore: future
+async fn $0fetch(id: u32) -> u32 {
+    id
+}
+"#####,
+        r#####"
+fn fetch(id: u32) -> impl core::future::Future<Output = u32> {
+    async move {
+        id
+    }
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_convert_bool_then_to_if() {
     check_doc_test(
@@ -367,6 +430,93 @@ fn main() {
     )
 }
 
+#[test]
+fn doctest_convert_box_dyn_field_to_generic() {
+    check_doc_test(
+        "convert_box_dyn_field_to_generic",
+        r#####"
+trait Draw {}
+struct Window {
+    content: $0Box<dyn Draw>,
+}
+impl Window {
+    fn new(content: Box<dyn Draw>) -> Self {
+        Window { content }
+    }
+}
+"#####,
+        r#####"
+trait Draw {}
+struct Window<D: Draw> {
+    content: D,
+}
+impl<D: Draw> Window<D> {
+    fn new(content: D) -> Self {
+        Window { content }
+    }
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_constructor_to_try_from() {
+    check_doc_test(
+        "convert_constructor_to_try_from",
+        r#####"
+//- minicore: result, try_from
+struct Port(u16);
+
+impl Port {
+    fn $0from_number(number: u32) -> Result<Self, ()> {
+        if number > 65535 { Err(()) } else { Ok(Port(number as u16)) }
+    }
+}
+
+fn main() {
+    let _ = Port::from_number(80);
+}
+"#####,
+        r#####"
+struct Port(u16);
+
+impl TryFrom<u32> for Port {
+    type Error = ();
+
+    fn try_from(number: u32) -> Result<Self, Self::Error> {
+        if number > 65535 { Err(()) } else { Ok(Port(number as u16)) }
+    }
+}
+
+fn main() {
+    let _ = Port::try_from(80);
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_for_loop_to_iterator_chain() {
+    check_doc_test(
+        "convert_for_loop_to_iterator_chain",
+        r#####"
+fn main() {
+    let xs = [1, 2, 3];
+    let mut doubled = Vec::new();
+    for$0 x in xs {
+        doubled.push(x * 2);
+    }
+}
+"#####,
+        r#####"
+fn main() {
+    let xs = [1, 2, 3];
+    let doubled: Vec<_> = xs.into_iter().map(|x| x * 2).collect();
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_convert_for_loop_with_for_each() {
     check_doc_test(
@@ -390,6 +540,106 @@ fn main() {
     )
 }
 
+#[test]
+fn doctest_convert_free_fn_to_method() {
+    check_doc_test(
+        "convert_free_fn_to_method",
+        r#####"
+struct Counter { n: u32 }
+
+fn $0bump(counter: &mut Counter, by: u32) {
+    counter.n += by;
+}
+
+fn main() {
+    let mut c = Counter { n: 0 };
+    bump(&mut c, 2);
+}
+"#####,
+        r#####"
+struct Counter { n: u32 }
+
+impl Counter {
+    fn bump(&mut self, by: u32) {
+        self.n += by;
+    }
+}
+
+fn main() {
+    let mut c = Counter { n: 0 };
+    c.bump(2);
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_from_str_to_try_from() {
+    check_doc_test(
+        "convert_from_str_to_try_from",
+        r#####"
+//- minicore: from_str, try_from
+struct Version(u32);
+
+impl $0core::str::FromStr for Version {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Err(())
+    }
+}
+
+fn main() {
+    let _: Result<Version, _> = "1".parse();
+}
+"#####,
+        r#####"
+struct Version(u32);
+
+impl TryFrom<&str> for Version {
+    type Error = ();
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        Err(())
+    }
+}
+
+fn main() {
+    let _: Result<Version, _> = Version::try_from("1");
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_generic_field_to_box_dyn() {
+    check_doc_test(
+        "convert_generic_field_to_box_dyn",
+        r#####"
+trait Draw {}
+struct Window<D: Draw> {
+    content: $0D,
+}
+impl<D: Draw> Window<D> {
+    fn new(content: D) -> Self {
+        Window { content }
+    }
+}
+"#####,
+        r#####"
+trait Draw {}
+struct Window {
+    content: Box<dyn Draw>,
+}
+impl Window {
+    fn new(content: Box<dyn Draw>) -> Self {
+        Window { content }
+    }
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_convert_if_to_bool_then() {
     check_doc_test(
@@ -412,6 +662,26 @@ fn main() {
     )
 }
 
+#[test]
+fn doctest_convert_impl_future_to_async_fn() {
+    check_doc_test(
+        "convert_impl_future_to_async_fn",
+        r#####"
+//- minicore: future
+fn $0fetch(id: u32) -> impl core::future::Future<Output = u32> {
+    async move {
+        id
+    }
+}
+"#####,
+        r#####"
+async fn fetch(id: u32) -> u32 {
+    id
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_convert_integer_literal() {
     check_doc_test(
@@ -479,6 +749,28 @@ fn main() {
     )
 }
 
+#[test]
+fn doctest_convert_iterator_chain_to_for_loop() {
+    check_doc_test(
+        "convert_iterator_chain_to_for_loop",
+        r#####"
+fn main() {
+    let xs = [1, 2, 3];
+    let doubled: Vec<_> = xs.into_iter().map(|x| x * 2).col$0lect();
+}
+"#####,
+        r#####"
+fn main() {
+    let xs = [1, 2, 3];
+    let mut doubled = Vec::new();
+    for x in xs {
+        doubled.push(x * 2);
+    }
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_convert_let_else_to_match() {
     check_doc_test(
@@ -499,6 +791,26 @@ fn main() {
     )
 }
 
+#[test]
+fn doctest_convert_let_else_to_try_expr() {
+    check_doc_test(
+        "convert_let_else_to_try_expr",
+        r#####"
+//- minicore: try, option
+fn main() -> Option<()> {
+    let Some(x) = Some(92) else$0 { return None };
+    None
+}
+"#####,
+        r#####"
+fn main() -> Option<()> {
+    let x = Some(92)?;
+    None
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_convert_match_to_let_else() {
     check_doc_test(
@@ -586,6 +898,103 @@ fn main() {
     )
 }
 
+#[test]
+fn doctest_convert_nested_match_to_tuple_match() {
+    check_doc_test(
+        "convert_nested_match_to_tuple_match",
+        r#####"
+//- minicore: copy, option
+fn f(a: Option<u8>, b: bool) -> u8 {
+    $0match a {
+        Some(x) => match b {
+            true => x,
+            false => 0,
+        },
+        None => 1,
+    }
+}
+"#####,
+        r#####"
+fn f(a: Option<u8>, b: bool) -> u8 {
+    match (a, b) {
+        (Some(x), true) => x,
+        (Some(x), false) => 0,
+        (None, _) => 1,
+    }
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_panics_to_result() {
+    check_doc_test(
+        "convert_panics_to_result",
+        r#####"
+//- minicore: option, result, panic, derive
+#[derive(Debug)]
+struct ParseError;
+fn parse(s: &str) -> Result<i32, ParseError> { Ok(0) }
+fn $0sum(a: Option<&str>, b: &str) -> i32 {
+    let a = a.expect("missing a");
+    parse(a).unwrap() + parse(b).unwrap()
+}
+"#####,
+        r#####"
+#[derive(Debug)]
+struct ParseError;
+fn parse(s: &str) -> Result<i32, ParseError> { Ok(0) }
+fn sum(a: Option<&str>, b: &str) -> Result<i32, SumError> {
+    let a = a.ok_or_else(|| SumError::Message("missing a".to_owned()))?;
+    Ok(parse(a)? + parse(b)?)
+}
+
+#[derive(Debug)]
+enum SumError {
+    Parse(ParseError),
+    Message(String),
+}
+
+impl From<ParseError> for SumError {
+    fn from(err: ParseError) -> Self {
+        Self::Parse(err)
+    }
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_string_concat_to_format() {
+    check_doc_test(
+        "convert_string_concat_to_format",
+        r#####"
+//- minicore: add
+//- /main.rs crate:main deps:alloc
+use alloc::string::{String, ToString};
+fn greet(name: &str) -> String {
+    "Hello, ".to_string() +$0 name + "!"
+}
+//- /alloc.rs crate:alloc
+pub mod string {
+    pub struct String;
+    impl core::ops::Add<&str> for String {
+        type Output = String;
+        fn add(self, _: &str) -> String { self }
+    }
+    pub trait ToString { fn to_string(&self) -> String; }
+    impl ToString for str { fn to_string(&self) -> String { String } }
+}
+"#####,
+        r#####"
+use alloc::string::{String, ToString};
+fn greet(name: &str) -> String {
+    format!("Hello, {name}!")
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_convert_to_guarded_return() {
     check_doc_test(
@@ -610,6 +1019,57 @@ fn main() {
     )
 }
 
+#[test]
+fn doctest_convert_try_expr_to_let_else() {
+    check_doc_test(
+        "convert_try_expr_to_let_else",
+        r#####"
+//- minicore: try, option
+fn main() -> Option<()> {
+    let x = Some(92)$0?;
+    None
+}
+"#####,
+        r#####"
+fn main() -> Option<()> {
+    let Some(x) = Some(92) else { return None };
+    None
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_tuple_match_to_nested_match() {
+    check_doc_test(
+        "convert_tuple_match_to_nested_match",
+        r#####"
+enum Dir { Up, Down }
+
+fn f(a: Dir, b: bool) -> u8 {
+    $0match (a, b) {
+        (Dir::Up, true) => 1,
+        (Dir::Up, false) => 2,
+        (Dir::Down, _) => 3,
+    }
+}
+"#####,
+        r#####"
+enum Dir { Up, Down }
+
+fn f(a: Dir, b: bool) -> u8 {
+    match a {
+        Dir::Up => match b {
+            true => 1,
+            false => 2,
+        }
+        Dir::Down => 3,
+    }
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_convert_tuple_return_type_to_struct() {
     check_doc_test(
@@ -722,6 +1182,31 @@ fn main() {
     )
 }
 
+#[test]
+fn doctest_destructure_struct_binding() {
+    check_doc_test(
+        "destructure_struct_binding",
+        r#####"
+struct Foo { bar: i32, baz: i32 }
+
+fn main() {
+    let $0foo = Foo { bar: 1, baz: 2 };
+    let bar2 = foo.bar * 2;
+    let baz2 = foo.baz;
+}
+"#####,
+        r#####"
+struct Foo { bar: i32, baz: i32 }
+
+fn main() {
+    let Foo { $0bar, baz } = Foo { bar: 1, baz: 2 };
+    let bar2 = bar * 2;
+    let baz2 = baz;
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_destructure_tuple_binding() {
     check_doc_test(
@@ -830,6 +1315,24 @@ fn $0fun_name(n: i32) {
     )
 }
 
+#[test]
+fn doctest_extract_generic_param() {
+    check_doc_test(
+        "extract_generic_param",
+        r#####"
+fn greet(name: $0String) -> usize {
+    name.len()
+}
+"#####,
+        r#####"
+fn greet<S: Into<String>>(name: S) -> usize {
+    let name: String = name.into();
+    name.len()
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_extract_module() {
     check_doc_test(
@@ -1208,6 +1711,37 @@ struct Point {
     )
 }
 
+#[test]
+fn doctest_generate_display_impl() {
+    check_doc_test(
+        "generate_display_impl",
+        r#####"
+//- minicore: fmt
+enum Color {
+    /// Bright red
+    Red,
+    Rgb$0(u8, u8, u8),
+}
+"#####,
+        r#####"
+enum Color {
+    /// Bright red
+    Red,
+    Rgb(u8, u8, u8),
+}
+
+impl core::fmt::Display for Color {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Red => write!(f, "Bright red"),
+            Self::Rgb(arg0, arg1, arg2) => write!(f, "rgb ({arg0}, {arg1}, {arg2})"),
+        }
+    }
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_generate_doc_example() {
     check_doc_test(
@@ -1397,6 +1931,89 @@ impl From<u32> for A {
     )
 }
 
+#[test]
+fn doctest_generate_from_impl_for_struct() {
+    check_doc_test(
+        "generate_from_impl_for_struct",
+        r#####"
+//- minicore: from
+struct User { id: u32, age: u8 }
+
+struct $0UserDto { id: u32, age: u8, active: bool }
+"#####,
+        r#####"
+struct User { id: u32, age: u8 }
+
+struct UserDto { id: u32, age: u8, active: bool }
+
+impl From<User> for UserDto {
+    fn from(value: User) -> Self {
+        Self {
+            id: value.id,
+            age: value.age,
+            active: todo!(),
+        }
+    }
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_generate_from_str_impl() {
+    check_doc_test(
+        "generate_from_str_impl",
+        r#####"
+//- minicore: fmt, from_str
+enum Color {
+    Red,
+    DarkBlue,
+}
+
+impl core::fmt::Display$0 for Color {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Red => write!(f, "red"),
+            Self::DarkBlue => write!(f, "dark blue"),
+        }
+    }
+}
+"#####,
+        r#####"
+enum Color {
+    Red,
+    DarkBlue,
+}
+
+impl core::fmt::Display for Color {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Red => write!(f, "red"),
+            Self::DarkBlue => write!(f, "dark blue"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ParseColorError {
+    Unknown(String),
+}
+
+impl core::str::FromStr for Color {
+    type Err = ParseColorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "red" => Ok(Self::Red),
+            "dark blue" => Ok(Self::DarkBlue),
+            _ => Err(ParseColorError::Unknown(s.to_owned())),
+        }
+    }
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_generate_function() {
     check_doc_test(
@@ -1609,6 +2226,54 @@ impl<T: Clone> Ctx<T> {
     )
 }
 
+#[test]
+fn doctest_generate_new_from_default() {
+    check_doc_test(
+        "generate_new_from_default",
+        r#####"
+//- minicore: default, derive
+#[derive(Default)]
+struct Example$0 { _inner: () }
+"#####,
+        r#####"
+#[derive(Default)]
+struct Example { _inner: () }
+
+impl Example {
+    fn $0new() -> Self {
+        Self::default()
+    }
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_generate_new_with_defaults() {
+    check_doc_test(
+        "generate_new_with_defaults",
+        r#####"
+//- minicore: default, builtin_impls
+struct Counter {
+    name: &'static str,$0
+    count: u32,
+}
+"#####,
+        r#####"
+struct Counter {
+    name: &'static str,
+    count: u32,
+}
+
+impl Counter {
+    fn $0new(name: &'static str) -> Self {
+        Self { name, count: Default::default() }
+    }
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_generate_setter() {
     check_doc_test(
@@ -1707,6 +2372,36 @@ impl<T: Clone> ${0:_} for Ctx<T> {}
     )
 }
 
+#[test]
+fn doctest_generate_unit_test() {
+    check_doc_test(
+        "generate_unit_test",
+        r#####"
+fn add$0(a: u32, b: u32) -> u32 {
+    a + b
+}
+"#####,
+        r#####"
+fn add(a: u32, b: u32) -> u32 {
+    a + b
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add() {
+        let a: u32 = 0;
+        let b: u32 = 0;
+        let result = add(a, b);
+        assert_eq!(result, todo!());
+    }
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_inline_call() {
     check_doc_test(
@@ -1749,6 +2444,26 @@ fn something() -> &'static str {
     )
 }
 
+#[test]
+fn doctest_inline_format_args() {
+    check_doc_test(
+        "inline_format_args",
+        r#####"
+//- minicore: fmt
+fn main() {
+    let (name, age) = ("Ferris", 8);
+    print!("{} is {:>3}$0", name, age);
+}
+"#####,
+        r#####"
+fn main() {
+    let (name, age) = ("Ferris", 8);
+    print!("{name} is {age:>3}");
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_inline_into_callers() {
     check_doc_test(
@@ -1917,6 +2632,44 @@ fn main() -> () {
     )
 }
 
+#[test]
+fn doctest_introduce_field() {
+    check_doc_test(
+        "introduce_field",
+        r#####"
+//- minicore: copy
+fn step() -> u32 { 10 }
+
+struct Counter { count: u32 }
+
+impl Counter {
+    fn new() -> Self {
+        Self { count: 0 }
+    }
+
+    fn bump(&mut self) {
+        self.count += $0step()$0;
+    }
+}
+"#####,
+        r#####"
+fn step() -> u32 { 10 }
+
+struct Counter { count: u32, step: u32 }
+
+impl Counter {
+    fn new() -> Self {
+        Self { count: 0, step: step() }
+    }
+
+    fn bump(&mut self) {
+        self.count += self.step;
+    }
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_introduce_named_generic() {
     check_doc_test(
@@ -1930,6 +2683,19 @@ fn foo<$0B: Bar>(bar: B) {}
     )
 }
 
+#[test]
+fn doctest_introduce_named_generic_with_where_clause() {
+    check_doc_test(
+        "introduce_named_generic_with_where_clause",
+        r#####"
+fn foo(bar: $0impl Bar) {}
+"#####,
+        r#####"
+fn foo<$0B>(bar: B) where B: Bar {}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_introduce_named_lifetime() {
     check_doc_test(
@@ -1955,6 +2721,35 @@ impl<'a> Cursor<'a> {
     )
 }
 
+#[test]
+fn doctest_introduce_parameter() {
+    check_doc_test(
+        "introduce_parameter",
+        r#####"
+fn default_scale() -> u32 { 100 }
+
+fn scale(x: u32) -> u32 {
+    x * $0default_scale()$0
+}
+
+fn main() {
+    scale(1);
+}
+"#####,
+        r#####"
+fn default_scale() -> u32 { 100 }
+
+fn scale(x: u32, default_scale: u32) -> u32 {
+    x * default_scale
+}
+
+fn main() {
+    scale(1, default_scale());
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_invert_if() {
     check_doc_test(
@@ -2213,6 +3008,48 @@ mod foo;
     )
 }
 
+#[test]
+fn doctest_move_param_left() {
+    check_doc_test(
+        "move_param_left",
+        r#####"
+fn frobnicate(x: i32, y: bool$0) {}
+
+fn main() {
+    frobnicate(92, true);
+}
+"#####,
+        r#####"
+fn frobnicate(y: bool, x: i32) {}
+
+fn main() {
+    frobnicate(true, 92);
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_move_param_right() {
+    check_doc_test(
+        "move_param_right",
+        r#####"
+fn frobnicate(x: i32$0, y: bool) {}
+
+fn main() {
+    frobnicate(92, true);
+}
+"#####,
+        r#####"
+fn frobnicate(y: bool, x: i32) {}
+
+fn main() {
+    frobnicate(true, 92);
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_move_to_mod_rs() {
     check_doc_test(
@@ -2229,6 +3066,23 @@ fn t() {}
     )
 }
 
+#[test]
+fn doctest_move_where_clause_to_bounds() {
+    check_doc_test(
+        "move_where_clause_to_bounds",
+        r#####"
+fn apply<T, U, F>(f: F, x: T) -> U where $0F: FnOnce(T) -> U {
+    f(x)
+}
+"#####,
+        r#####"
+fn apply<T, U, F: FnOnce(T) -> U>(f: F, x: T) -> U {
+    f(x)
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_normalize_import() {
     check_doc_test(
@@ -2242,6 +3096,26 @@ use std::{fmt::Formatter, io};
     )
 }
 
+#[test]
+fn doctest_organize_imports() {
+    check_doc_test(
+        "organize_imports",
+        r#####"
+use std::fmt::Display;
+use crate::$0foo::Bar;
+use std::fmt::Debug;
+use regex::Regex;
+"#####,
+        r#####"
+use std::fmt::{Debug, Display};
+
+use regex::Regex;
+
+use crate::foo::Bar;
+"#####,
+    )
+}
+
 #[test]
 fn doctest_promote_local_to_const() {
     check_doc_test(
@@ -2271,6 +3145,26 @@ fn main() {
     )
 }
 
+#[test]
+fn doctest_promote_local_to_item_const() {
+    check_doc_test(
+        "promote_local_to_item_const",
+        r#####"
+fn main() {
+    let retries$0 = 3;
+    for _ in 0..retries {}
+}
+"#####,
+        r#####"
+const $0RETRIES: i32 = 3;
+
+fn main() {
+    for _ in 0..RETRIES {}
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_pull_assignment_up() {
     check_doc_test(
@@ -2300,6 +3194,24 @@ fn main() {
     )
 }
 
+#[test]
+fn doctest_qualify_imported_path() {
+    check_doc_test(
+        "qualify_imported_path",
+        r#####"
+mod foo { pub struct Bar; }
+use foo::Bar;
+
+fn f(_: Bar$0) {}
+"#####,
+        r#####"
+mod foo { pub struct Bar; }
+
+fn f(_: crate::foo::Bar) {}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_qualify_method_call() {
     check_doc_test(
@@ -2427,6 +3339,29 @@ fn main() {
     )
 }
 
+#[test]
+fn doctest_remove_serde_rename_attributes() {
+    check_doc_test(
+        "remove_serde_rename_attributes",
+        r#####"
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct $0User {
+    user_id: u32,
+    display_name: String,
+}
+"#####,
+        r#####"
+#[derive(Serialize, Deserialize)]
+#[allow(non_snake_case)]
+struct User {
+    userId: u32,
+    displayName: String,
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_remove_unused_imports() {
     check_doc_test(
@@ -2584,6 +3519,31 @@ fn main() {
     )
 }
 
+#[test]
+fn doctest_replace_deprecated_path() {
+    check_doc_test(
+        "replace_deprecated_path",
+        r#####"
+#[deprecated(note = "use `new_fn` instead")]
+fn old_fn() {}
+fn new_fn() {}
+
+fn main() {
+    old_fn$0();
+}
+"#####,
+        r#####"
+#[deprecated(note = "use `new_fn` instead")]
+fn old_fn() {}
+fn new_fn() {}
+
+fn main() {
+    new_fn();
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_replace_derive_with_manual_impl() {
     check_doc_test(
@@ -2709,6 +3669,29 @@ fn handle(action: Action) {
     )
 }
 
+#[test]
+fn doctest_replace_match_with_try_expr() {
+    check_doc_test(
+        "replace_match_with_try_expr",
+        r#####"
+//- minicore: try, option
+fn handle() -> Option<i32> {
+    let pat = $0match Some(1) {
+        Some(it) => it,
+        None => return None,
+    };
+    Some(pat)
+}
+"#####,
+        r#####"
+fn handle() -> Option<i32> {
+    let pat = Some(1)?;
+    Some(pat)
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_replace_named_generic_with_impl() {
     check_doc_test(
@@ -2927,6 +3910,71 @@ enum Animal {
     )
 }
 
+#[test]
+fn doctest_sort_items_5() {
+    check_doc_test(
+        "sort_items",
+        r#####"
+mod $0shapes$0 {
+    struct Square;
+    struct Circle;
+}
+"#####,
+        r#####"
+mod shapes {
+    struct Circle;
+    struct Square;
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_sort_items_6() {
+    check_doc_test(
+        "sort_items",
+        r#####"
+#[derive($0PartialEq, Debug, Clone$0)]
+struct Point;
+"#####,
+        r#####"
+#[derive(Clone, Debug, PartialEq)]
+struct Point;
+"#####,
+    )
+}
+
+#[test]
+fn doctest_sort_match_arms() {
+    check_doc_test(
+        "sort_match_arms",
+        r#####"
+enum Direction { North, East, South, West }
+
+fn turn(dir: Direction) -> Direction {
+    $0match dir {
+        Direction::South => Direction::West,
+        Direction::North => Direction::East,
+        Direction::West => Direction::North,
+        Direction::East => Direction::South,
+    }
+}
+"#####,
+        r#####"
+enum Direction { North, East, South, West }
+
+fn turn(dir: Direction) -> Direction {
+    match dir {
+        Direction::North => Direction::East,
+        Direction::East => Direction::South,
+        Direction::South => Direction::West,
+        Direction::West => Direction::North,
+    }
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_split_import() {
     check_doc_test(
@@ -2940,6 +3988,112 @@ use std::{collections::HashMap};
     )
 }
 
+#[test]
+fn doctest_surround_with_async_move() {
+    check_doc_test(
+        "surround_with_async_move",
+        r#####"
+fn main() {
+    let s = String::new();
+    let fut = $0drop(s)$0;
+}
+"#####,
+        r#####"
+fn main() {
+    let s = String::new();
+    let fut = async move { drop(s) };
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_surround_with_if() {
+    check_doc_test(
+        "surround_with_if",
+        r#####"
+fn main() {
+    $0let x = 1;
+    println!("{x}");$0
+}
+"#####,
+        r#####"
+fn main() {
+    if ${0:condition} {
+        let x = 1;
+        println!("{x}");
+    }
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_surround_with_loop() {
+    check_doc_test(
+        "surround_with_loop",
+        r#####"
+fn main() {
+    $0let x = 1;
+    println!("{x}");$0
+}
+"#####,
+        r#####"
+fn main() {
+    loop {
+        let x = 1;
+        println!("{x}");
+    }
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_surround_with_match() {
+    check_doc_test(
+        "surround_with_match",
+        r#####"
+enum Dir { Up, Down }
+
+fn f(dir: Dir) {
+    $0dir$0;
+}
+"#####,
+        r#####"
+enum Dir { Up, Down }
+
+fn f(dir: Dir) {
+    match dir {
+        Dir::Up => todo!(),
+        Dir::Down => todo!(),
+    };
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_surround_with_unsafe() {
+    check_doc_test(
+        "surround_with_unsafe",
+        r#####"
+unsafe fn danger() -> i32 { 0 }
+
+fn main() {
+    let x = $0danger()$0;
+}
+"#####,
+        r#####"
+unsafe fn danger() -> i32 { 0 }
+
+fn main() {
+    let x = unsafe { danger() };
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_toggle_ignore() {
     check_doc_test(
@@ -2960,6 +4114,26 @@ fn arithmetics {
     )
 }
 
+#[test]
+fn doctest_uninline_format_args() {
+    check_doc_test(
+        "uninline_format_args",
+        r#####"
+//- minicore: fmt
+fn main() {
+    let (name, age) = ("Ferris", 8);
+    print!("{name} is {}, {age:>3}$0", age);
+}
+"#####,
+        r#####"
+fn main() {
+    let (name, age) = ("Ferris", 8);
+    print!("{} is {}, {:>3}", name, age, age);
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_unmerge_match_arm() {
     check_doc_test(
@@ -3088,6 +4262,20 @@ fn main() {
     )
 }
 
+#[test]
+fn doctest_wrap_return_type_in_option() {
+    check_doc_test(
+        "wrap_return_type_in_option",
+        r#####"
+//- minicore: option
+fn foo() -> i32$0 { 42i32 }
+"#####,
+        r#####"
+fn foo() -> Option<i32> { Some(42i32) }
+"#####,
+    )
+}
+
 #[test]
 fn doctest_wrap_return_type_in_result() {
     check_doc_test(
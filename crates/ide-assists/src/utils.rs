@@ -3,11 +3,12 @@
 pub(crate) use gen_trait_fn_body::gen_trait_fn_body;
 use hir::{db::HirDatabase, HasAttrs as HirHasAttrs, HirDisplay, InFile, Semantics};
 use ide_db::{
-    famous_defs::FamousDefs, path_transform::PathTransform,
-    syntax_helpers::insert_whitespace_into_node::insert_ws_into, RootDatabase,
+    base_db::FileId, defs::Definition, famous_defs::FamousDefs, path_transform::PathTransform,
+    syntax_helpers::insert_whitespace_into_node::insert_ws_into, FxHashMap, RootDatabase,
 };
 use stdx::format_to;
 use syntax::{
+    algo::find_node_at_range,
     ast::{
         self,
         edit::{AstNodeEdit, IndentLevel},
@@ -215,6 +216,73 @@ pub fn add_trait_assoc_items_to_impl(
     first_item.unwrap()
 }
 
+/// The turbofish generic argument lists of the calls to `func` that supply type or const
+/// arguments, grouped by file. They need updating when a type parameter is added or removed.
+pub(crate) fn turbofish_call_sites(
+    sema: &Semantics<'_, RootDatabase>,
+    func: hir::Function,
+) -> FxHashMap<FileId, Vec<ast::GenericArgList>> {
+    Definition::Function(func)
+        .usages(sema)
+        .all()
+        .into_iter()
+        .filter_map(|(file_id, refs)| {
+            let arg_lists = refs
+                .iter()
+                .filter_map(|it| {
+                    let parent = it.name.as_name_ref()?.syntax().parent()?;
+                    let arg_list = match ast::PathSegment::cast(parent.clone()) {
+                        Some(segment) => segment.generic_arg_list(),
+                        None => ast::MethodCallExpr::cast(parent)?.generic_arg_list(),
+                    }?;
+                    arg_list
+                        .generic_args()
+                        .any(|arg| !matches!(arg, ast::GenericArg::LifetimeArg(_)))
+                        .then_some(arg_list)
+                })
+                .collect::<Vec<_>>();
+            (!arg_lists.is_empty()).then_some((file_id, arg_lists))
+        })
+        .collect()
+}
+
+/// Returns the type parameter `pred` puts bounds on, if it is a plain one of `generic_param_list`.
+pub(crate) fn bounded_type_param(
+    generic_param_list: &ast::GenericParamList,
+    pred: &ast::WherePred,
+) -> Option<ast::TypeParam> {
+    // Higher-ranked bounds can't be written on the parameter itself.
+    if pred.generic_param_list().is_some() {
+        return None;
+    }
+    let ast::Type::PathType(ty) = pred.ty()? else { return None };
+    let path = ty.path()?;
+    let segment = path.segment()?;
+    if path.qualifier().is_some() || segment.generic_arg_list().is_some() {
+        return None;
+    }
+    let name = segment.name_ref()?;
+    generic_param_list.type_or_const_params().find_map(|param| match param {
+        ast::TypeOrConstParam::Type(it) if it.name()?.text() == name.text() => Some(it),
+        _ => None,
+    })
+}
+
+/// Removes `where_clause` along with the whitespace separating it from the item.
+pub(crate) fn remove_where_clause(where_clause: ast::WhereClause) {
+    let is_whitespace = |it: &syntax::SyntaxElement| it.kind() == WHITESPACE;
+    if let Some(ws) = where_clause.syntax().prev_sibling_or_token().filter(is_whitespace) {
+        ted::remove(ws);
+    }
+    // A where clause on its own lines leaves the opening brace of the body on one as well.
+    if let Some(ws) = where_clause.syntax().next_sibling_or_token().filter(is_whitespace) {
+        if ws.to_string().contains('\n') {
+            ted::replace(ws, make::tokens::single_space());
+        }
+    }
+    ted::remove(where_clause.syntax());
+}
+
 pub(crate) fn vis_offset(node: &SyntaxNode) -> TextSize {
     node.children_with_tokens()
         .find(|it| !matches!(it.kind(), WHITESPACE | COMMENT | ATTR))
@@ -277,6 +345,46 @@ pub(crate) fn next_prev() -> impl Iterator<Item = Direction> {
     [Direction::Next, Direction::Prev].into_iter()
 }
 
+/// Returns `true` if a `?` placed at `node` exits the same body a `return` placed there would,
+/// that is, there is no `try` block between `node` and the enclosing function or closure.
+pub(crate) fn try_exits_like_return(node: &SyntaxNode) -> bool {
+    node.ancestors()
+        .take_while(|it| !matches!(it.kind(), FN | CLOSURE_EXPR))
+        .filter_map(ast::BlockExpr::cast)
+        .all(|block| !matches!(block.modifier(), Some(ast::BlockModifier::Try(_))))
+}
+
+/// Finds the call of a function referenced at `range`, which is either an [`ast::CallExpr`] or an
+/// [`ast::MethodCallExpr`].
+///
+/// References that aren't calls, like those inside of macro calls, yield `None`.
+pub(crate) fn fn_call_at(source_file: &SourceFile, range: TextRange) -> Option<ast::Expr> {
+    if let Some(call) = find_node_at_range::<ast::CallExpr>(source_file.syntax(), range) {
+        if call.expr()?.syntax().text_range().contains_range(range) {
+            return Some(call.into());
+        }
+    }
+    let method_call = find_node_at_range::<ast::MethodCallExpr>(source_file.syntax(), range)?;
+    if !method_call.name_ref()?.syntax().text_range().contains_range(range) {
+        return None;
+    }
+    Some(method_call.into())
+}
+
+/// Like [`fn_call_at`], but returns the argument list of the call, together with the index of the
+/// argument that corresponds to the first non-`self` parameter of the function.
+pub(crate) fn fn_call_args_at(
+    source_file: &SourceFile,
+    range: TextRange,
+    has_self_param: bool,
+) -> Option<(ast::ArgList, usize)> {
+    match fn_call_at(source_file, range)? {
+        ast::Expr::CallExpr(call) => Some((call.arg_list()?, has_self_param as usize)),
+        ast::Expr::MethodCallExpr(call) => Some((call.arg_list()?, 0)),
+        _ => None,
+    }
+}
+
 pub(crate) fn does_pat_match_variant(pat: &ast::Pat, var: &ast::Pat) -> bool {
     let first_node_text = |pat: &ast::Pat| pat.syntax().first_child().map(|node| node.text());
 
@@ -133,11 +133,19 @@ pub(crate) fn for_impl_trait_as_generic(
 /// Currently it sticks to the first name found.
 // FIXME: Microoptimize and return a `SmolStr` here.
 pub(crate) fn for_variable(expr: &ast::Expr, sema: &Semantics<'_, RootDatabase>) -> String {
+    try_for_variable(expr, sema).unwrap_or_else(|| "var_name".to_owned())
+}
+
+/// Like [`for_variable`], but returns `None` instead of the fallback name.
+pub(crate) fn try_for_variable(
+    expr: &ast::Expr,
+    sema: &Semantics<'_, RootDatabase>,
+) -> Option<String> {
     // `from_param` does not benefit from stripping
     // it need the largest context possible
     // so we check firstmost
     if let Some(name) = from_param(expr, sema) {
-        return name;
+        return Some(name);
     }
 
     let mut next_expr = Some(expr.clone());
@@ -145,7 +153,7 @@ pub(crate) fn for_variable(expr: &ast::Expr, sema: &Semantics<'_, RootDatabase>)
         let name =
             from_call(&expr).or_else(|| from_type(&expr, sema)).or_else(|| from_field_name(&expr));
         if let Some(name) = name {
-            return name;
+            return Some(name);
         }
 
         match expr {
@@ -165,7 +173,7 @@ pub(crate) fn for_variable(expr: &ast::Expr, sema: &Semantics<'_, RootDatabase>)
         }
     }
 
-    "var_name".to_owned()
+    None
 }
 
 fn normalize(name: &str) -> Option<String> {
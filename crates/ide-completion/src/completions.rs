@@ -1,12 +1,14 @@
 //! This module defines an accumulator for completions which are going to be presented to user.
 
 pub(crate) mod attribute;
+pub(crate) mod call_args;
 pub(crate) mod dot;
 pub(crate) mod env_vars;
 pub(crate) mod expr;
 pub(crate) mod extern_abi;
 pub(crate) mod extern_crate;
 pub(crate) mod field;
+pub(crate) mod file_path;
 pub(crate) mod flyimport;
 pub(crate) mod fn_param;
 pub(crate) mod format_string;
@@ -25,7 +27,7 @@ pub(crate) mod vis;
 use std::iter;
 
 use hir::{known, HasAttrs, ScopeDef, Variant};
-use ide_db::{imports::import_assets::LocatedImport, RootDatabase, SymbolKind};
+use ide_db::{imports::import_assets::LocatedImport, SymbolKind};
 use syntax::{ast, SmolStr};
 
 use crate::{
@@ -64,8 +66,8 @@ impl From<Completions> for Vec<CompletionItem> {
 impl Builder {
     /// Convenience method, which allows to add a freshly created completion into accumulator
     /// without binding it to the variable.
-    pub(crate) fn add_to(self, acc: &mut Completions, db: &RootDatabase) {
-        acc.add(self.build(db))
+    pub(crate) fn add_to(self, acc: &mut Completions, ctx: &CompletionContext<'_>) {
+        acc.add(self.build(ctx))
     }
 }
 
@@ -86,7 +88,7 @@ impl Completions {
             ctx.source_range(),
             SmolStr::new_static(keyword),
         );
-        item.add_to(self, ctx.db);
+        item.add_to(self, ctx);
     }
 
     pub(crate) fn add_nameref_keywords_with_colon(&mut self, ctx: &CompletionContext<'_>) {
@@ -140,7 +142,7 @@ impl Completions {
                 item.insert_text(if snippet.contains('$') { kw } else { snippet });
             }
         };
-        item.add_to(self, ctx.db);
+        item.add_to(self, ctx);
     }
 
     pub(crate) fn add_keyword_snippet(
@@ -155,12 +157,12 @@ impl Completions {
             Some(cap) => item.insert_snippet(cap, snippet),
             None => item.insert_text(if snippet.contains('$') { kw } else { snippet }),
         };
-        item.add_to(self, ctx.db);
+        item.add_to(self, ctx);
     }
 
     pub(crate) fn add_expr(&mut self, ctx: &CompletionContext<'_>, expr: &hir::term_search::Expr) {
         if let Some(item) = render_expr(ctx, expr) {
-            item.add_to(self, ctx.db)
+            item.add_to(self, ctx)
         }
     }
 
@@ -202,7 +204,7 @@ impl Completions {
                 local_name,
                 resolution,
             )
-            .build(ctx.db),
+            .build(ctx),
         );
     }
 
@@ -228,7 +230,7 @@ impl Completions {
                 local_name,
                 resolution,
             )
-            .build(ctx.db),
+            .build(ctx),
         );
     }
 
@@ -288,7 +290,7 @@ impl Completions {
                 local_name,
                 mac,
             )
-            .build(ctx.db),
+            .build(ctx),
         );
     }
 
@@ -317,7 +319,7 @@ impl Completions {
                 local_name,
                 func,
             )
-            .build(ctx.db),
+            .build(ctx),
         );
     }
 
@@ -348,7 +350,7 @@ impl Completions {
                 local_name,
                 func,
             )
-            .build(ctx.db),
+            .build(ctx),
         );
     }
 
@@ -379,7 +381,7 @@ impl Completions {
                 None,
                 func,
             )
-            .build(ctx.db),
+            .build(ctx),
         );
     }
 
@@ -441,7 +443,7 @@ impl Completions {
         if let Some(builder) =
             render_variant_lit(RenderContext::new(ctx), path_ctx, None, variant, Some(path))
         {
-            self.add(builder.build(ctx.db));
+            self.add(builder.build(ctx));
         }
     }
 
@@ -464,7 +466,7 @@ impl Completions {
         if let Some(builder) =
             render_variant_lit(RenderContext::new(ctx), path_ctx, local_name, variant, None)
         {
-            self.add(builder.build(ctx.db));
+            self.add(builder.build(ctx));
         }
     }
 
@@ -509,7 +511,7 @@ impl Completions {
         if let Some(builder) =
             render_struct_literal(RenderContext::new(ctx), path_ctx, strukt, path, local_name)
         {
-            self.add(builder.build(ctx.db));
+            self.add(builder.build(ctx));
         }
     }
 
@@ -542,12 +544,12 @@ impl Completions {
 
     pub(crate) fn add_lifetime(&mut self, ctx: &CompletionContext<'_>, name: hir::Name) {
         CompletionItem::new(SymbolKind::LifetimeParam, ctx.source_range(), name.to_smol_str())
-            .add_to(self, ctx.db)
+            .add_to(self, ctx)
     }
 
     pub(crate) fn add_label(&mut self, ctx: &CompletionContext<'_>, name: hir::Name) {
         CompletionItem::new(SymbolKind::Label, ctx.source_range(), name.to_smol_str())
-            .add_to(self, ctx.db)
+            .add_to(self, ctx)
     }
 
     pub(crate) fn add_variant_pat(
@@ -702,6 +704,7 @@ pub(super) fn complete_name_ref(
                 PathKind::Expr { expr_ctx } => {
                     expr::complete_expr_path(acc, ctx, path_ctx, expr_ctx);
                     expr::complete_expr(acc, ctx);
+                    call_args::complete_call_args(acc, ctx, path_ctx);
 
                     dot::complete_undotted_self(acc, ctx, path_ctx, expr_ctx);
                     item_list::complete_item_list_in_expr(acc, ctx, path_ctx, expr_ctx);
@@ -709,6 +712,7 @@ pub(super) fn complete_name_ref(
                 }
                 PathKind::Type { location } => {
                     r#type::complete_type_path(acc, ctx, path_ctx, location);
+                    snippet::complete_type_snippet(acc, ctx, path_ctx);
 
                     match location {
                         TypeLocation::TupleField => {
@@ -717,11 +721,13 @@ pub(super) fn complete_name_ref(
                         TypeLocation::TypeAscription(ascription) => {
                             r#type::complete_ascribed_type(acc, ctx, path_ctx, ascription);
                         }
+                        TypeLocation::ImplTarget => {
+                            item_list::trait_impl::complete_impl_skeleton(acc, ctx, path_ctx);
+                        }
                         TypeLocation::GenericArg { .. }
                         | TypeLocation::AssocConstEq
                         | TypeLocation::AssocTypeEq
                         | TypeLocation::TypeBound
-                        | TypeLocation::ImplTarget
                         | TypeLocation::ImplTrait
                         | TypeLocation::Other => (),
                     }
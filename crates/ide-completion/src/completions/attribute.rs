@@ -30,6 +30,18 @@ mod repr;
 
 pub(crate) use self::derive::complete_derive_path;
 
+/// rust-analyzer's own diagnostics that can be controlled with lint attributes.
+const RA_LINTS: &[Lint] = &[
+    Lint {
+        label: "rust_analyzer::inactive_code",
+        description: r#"detects code that is disabled by `#[cfg]` attributes"#,
+    },
+    Lint {
+        label: "rust_analyzer::remove_unnecessary_else",
+        description: r#"detects `else` blocks after a diverging `if` branch"#,
+    },
+];
+
 /// Complete inputs to known builtin attributes as well as derive attributes
 pub(crate) fn complete_known_attribute_input(
     acc: &mut Completions,
@@ -60,6 +72,7 @@ pub(crate) fn complete_known_attribute_input(
                 .chain(DEFAULT_LINTS)
                 .chain(CLIPPY_LINTS)
                 .chain(RUSTDOC_LINTS)
+                .chain(RA_LINTS)
                 .cloned()
                 .collect();
 
@@ -142,7 +155,7 @@ pub(crate) fn complete_attribute_path(
         }
 
         if is_inner || !attr_completion.prefer_inner {
-            item.add_to(acc, ctx.db);
+            item.add_to(acc, ctx);
         }
     };
 
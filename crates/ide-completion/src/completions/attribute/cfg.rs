@@ -10,7 +10,7 @@ pub(crate) fn complete_cfg(acc: &mut Completions, ctx: &CompletionContext<'_>) {
     let add_completion = |item: &str| {
         let mut completion = CompletionItem::new(SymbolKind::BuiltinAttr, ctx.source_range(), item);
         completion.insert_text(format!(r#""{item}""#));
-        acc.add(completion.build(ctx.db));
+        acc.add(completion.build(ctx));
     };
 
     // FIXME: Move this into context/analysis.rs
@@ -43,12 +43,12 @@ pub(crate) fn complete_cfg(acc: &mut Completions, ctx: &CompletionContext<'_>) {
                 let mut item = CompletionItem::new(SymbolKind::BuiltinAttr, ctx.source_range(), s);
                 item.insert_text(insert_text);
 
-                acc.add(item.build(ctx.db));
+                acc.add(item.build(ctx));
             }),
         },
         None => ctx.krate.potential_cfg(ctx.db).get_cfg_keys().cloned().unique().for_each(|s| {
             let item = CompletionItem::new(SymbolKind::BuiltinAttr, ctx.source_range(), s);
-            acc.add(item.build(ctx.db));
+            acc.add(item.build(ctx));
         }),
     }
 }
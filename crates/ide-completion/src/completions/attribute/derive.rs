@@ -2,7 +2,7 @@
 use hir::ScopeDef;
 use ide_db::{documentation::HasDocs, SymbolKind};
 use itertools::Itertools;
-use syntax::SmolStr;
+use syntax::{ast, AstNode, SmolStr};
 
 use crate::{
     context::{CompletionContext, ExistingDerives, PathCompletionCtx, Qualified},
@@ -17,6 +17,14 @@ pub(crate) fn complete_derive_path(
     existing_derives: &ExistingDerives,
 ) {
     let core = ctx.famous_defs().core();
+    // Generic ADTs get the derived traits as bounds on their parameters, so whether a derive works
+    // out can't be told up front for those.
+    let adt = ctx
+        .original_token
+        .parent_ancestors()
+        .find_map(ast::Adt::cast)
+        .and_then(|adt| ctx.sema.to_def(&adt))
+        .filter(|&adt| hir::GenericDef::Adt(adt).type_or_const_params(ctx.db).is_empty());
 
     match qualified {
         Qualified::With {
@@ -61,6 +69,11 @@ pub(crate) fn complete_derive_path(
                     (Some(core), mac_krate) if core == mac_krate => {}
                     _ => return acc.add_macro(ctx, path_ctx, mac, name),
                 };
+                if let Some(adt) = adt {
+                    if !fields_implement_derived_trait(ctx, adt, mac) {
+                        return;
+                    }
+                }
 
                 let name_ = name.to_smol_str();
                 let find = DEFAULT_DERIVE_DEPENDENCIES
@@ -90,7 +103,7 @@ pub(crate) fn complete_derive_path(
                             item.documentation(docs);
                         }
                         item.lookup_by(lookup);
-                        item.add_to(acc, ctx.db);
+                        item.add_to(acc, ctx);
                     }
                     None => acc.add_macro(ctx, path_ctx, mac, name),
                 }
@@ -101,6 +114,28 @@ pub(crate) fn complete_derive_path(
     }
 }
 
+/// Checks whether all fields of `adt` implement the trait of the builtin derive `mac`, which lives
+/// next to the macro under the same name.
+fn fields_implement_derived_trait(
+    ctx: &CompletionContext<'_>,
+    adt: hir::Adt,
+    mac: hir::Macro,
+) -> bool {
+    let db = ctx.db;
+    let name = mac.name(db);
+    let trait_ = mac.module(db).scope(db, None).into_iter().find_map(|(it, def)| match def {
+        ScopeDef::ModuleDef(hir::ModuleDef::Trait(trait_)) if it == name => Some(trait_),
+        _ => None,
+    });
+    let Some(trait_) = trait_ else { return true };
+    let fields = match adt {
+        hir::Adt::Struct(it) => it.fields(db),
+        hir::Adt::Union(it) => it.fields(db),
+        hir::Adt::Enum(it) => it.variants(db).into_iter().flat_map(|it| it.fields(db)).collect(),
+    };
+    fields.into_iter().all(|field| field.ty(db).impls_trait(db, trait_, &[]))
+}
+
 struct DeriveDependencies {
     label: &'static str,
     dependencies: &'static [&'static str],
@@ -29,7 +29,7 @@ pub(super) fn complete_macro_use(
             }
 
             let item = CompletionItem::new(SymbolKind::Macro, ctx.source_range(), mac_name);
-            item.add_to(acc, ctx.db);
+            item.add_to(acc, ctx);
         }
     }
 }
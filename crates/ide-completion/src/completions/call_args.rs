@@ -0,0 +1,178 @@
+//! Completes all the remaining arguments of a call at once, from the locals in scope.
+//!
+//! ```text
+//! fn draw(x: u32, y: u32, width: u32, height: u32) {}
+//! fn main() {
+//!     let (x, y, width, height) = (0, 0, 10, 10);
+//!     draw($0) // completes `x, y, width, height`
+//! }
+//! ```
+use hir::{Local, Mutability, Type};
+use ide_db::active_parameter::callable_for_token;
+use itertools::Itertools;
+use syntax::{
+    ast::{self, HasName},
+    AstNode,
+};
+
+use crate::{
+    context::{CompletionContext, PathCompletionCtx},
+    item::CompletionRelevanceTypeMatch,
+    CompletionItem, CompletionItemKind, CompletionRelevance, Completions,
+};
+
+pub(crate) fn complete_call_args(
+    acc: &mut Completions,
+    ctx: &CompletionContext<'_>,
+    path_ctx: &PathCompletionCtx,
+) -> Option<()> {
+    if !path_ctx.is_trivial_path() || !ctx.qualifier_ctx.none() {
+        return None;
+    }
+    let arg_list = ctx.token.parent_ancestors().find_map(ast::ArgList::cast)?;
+    let (callable, active_param) = callable_for_token(&ctx.sema, ctx.token.clone())?;
+    let active_param = active_param?;
+    // Only fill the arguments if the one being completed is the last one written so far.
+    if arg_list.args().count() != active_param + 1 {
+        return None;
+    }
+    let params = callable.params(ctx.db);
+    let remaining = params.get(active_param..)?;
+    if remaining.len() < 2 {
+        return None;
+    }
+
+    let locals = ctx
+        .locals
+        .iter()
+        .map(|(name, local)| (name.to_smol_str(), *local))
+        .sorted_by(|(a, _), (b, _)| a.cmp(b))
+        .collect::<Vec<_>>();
+    let mut used = vec![false; locals.len()];
+    let mut args = Vec::with_capacity(remaining.len());
+    let mut all_names_match = true;
+    for (pat, ty) in remaining {
+        let param_name = match pat.as_ref().and_then(|it| it.as_ref().right()) {
+            Some(ast::Pat::IdentPat(it)) => it.name().map(|it| it.text().to_string()),
+            _ => None,
+        };
+        let (idx, arg, score) = locals
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, (name, local))| {
+                let arg = arg_for_local(ctx, name, *local, ty)?;
+                let score = name_similarity(name, param_name.as_deref());
+                // Locals are only passed twice if the parameters are named after them.
+                if used[idx] && score < 2 {
+                    return None;
+                }
+                Some((idx, arg, score))
+            })
+            .max_by_key(|&(idx, _, score)| (score, std::cmp::Reverse(idx)))?;
+        used[idx] = true;
+        all_names_match &= score == 2;
+        args.push(arg);
+    }
+
+    let label = args.join(", ");
+    let mut item = CompletionItem::new(CompletionItemKind::Snippet, ctx.source_range(), label);
+    item.set_relevance(CompletionRelevance {
+        type_match: Some(CompletionRelevanceTypeMatch::Exact),
+        exact_name_match: all_names_match,
+        is_local: true,
+        ..Default::default()
+    });
+    item.add_to(acc, ctx);
+    Some(())
+}
+
+/// Returns the expression passing `local` for a parameter of type `ty`, borrowing it if needed.
+fn arg_for_local(
+    ctx: &CompletionContext<'_>,
+    name: &str,
+    local: Local,
+    ty: &Type,
+) -> Option<String> {
+    let local_ty = local.ty(ctx.db);
+    if local_ty == *ty {
+        return Some(name.to_owned());
+    }
+    match ty.as_reference() {
+        Some((inner, mutability)) if local_ty == inner => {
+            if mutability == Mutability::Mut && !local.is_mut(ctx.db) {
+                return None;
+            }
+            Some(format!("{}{name}", mutability.as_keyword_for_ref()))
+        }
+        _ => None,
+    }
+}
+
+fn name_similarity(local: &str, param: Option<&str>) -> u8 {
+    match param {
+        Some(param) if param == local => 2,
+        Some(param) if param.contains(local) || local.contains(param) => 1,
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::check_edit;
+
+    #[test]
+    fn fills_remaining_args_by_type_and_name() {
+        check_edit(
+            "x, y, &rect, count",
+            r#"
+struct Rect;
+fn draw(x: u32, y: u32, rect: &Rect, count: usize) {}
+fn main() {
+    let count = 1usize;
+    let y = 0u32;
+    let rect = Rect;
+    let x = 0u32;
+    draw($0);
+}
+"#,
+            r#"
+struct Rect;
+fn draw(x: u32, y: u32, rect: &Rect, count: usize) {}
+fn main() {
+    let count = 1usize;
+    let y = 0u32;
+    let rect = Rect;
+    let x = 0u32;
+    draw(x, y, &rect, count);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn fills_args_after_the_written_ones() {
+        check_edit(
+            "width, height",
+            r#"
+struct Canvas;
+impl Canvas {
+    fn fill(&self, color: u8, width: u16, height: u16) {}
+}
+fn main() {
+    let (height, width) = (1u16, 2u16);
+    Canvas.fill(0, $0);
+}
+"#,
+            r#"
+struct Canvas;
+impl Canvas {
+    fn fill(&self, color: u8, width: u16, height: u16) {}
+}
+fn main() {
+    let (height, width) = (1u16, 2u16);
+    Canvas.fill(0, width, height);
+}
+"#,
+        );
+    }
+}
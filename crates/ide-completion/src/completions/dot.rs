@@ -30,7 +30,7 @@ pub(crate) fn complete_dot(
             SmolStr::new_static("await"),
         );
         item.detail("expr.await");
-        item.add_to(acc, ctx.db);
+        item.add_to(acc, ctx);
     }
 
     let is_field_access = matches!(dot_access.kind, DotAccessKind::Field { .. });
@@ -1278,4 +1278,20 @@ fn baz() {
             "#]],
         );
     }
+
+    #[test]
+    fn completes_fields_of_async_fn_output() {
+        check(
+            r#"
+//- minicore: async_fn
+struct S { foo: u32 }
+async fn call<F: AsyncFn() -> S>(f: F) {
+    f().await.$0
+}
+"#,
+            expect![[r#"
+                fd foo u32
+            "#]],
+        );
+    }
 }
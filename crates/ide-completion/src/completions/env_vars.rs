@@ -1,48 +1,45 @@
 //! Completes environment variables defined by Cargo (https://doc.rust-lang.org/cargo/reference/environment-variables.html)
+//! and the ones set for the crate by build scripts or the configuration.
 use hir::Semantics;
-use ide_db::{syntax_helpers::node_ext::macro_call_for_string_token, RootDatabase};
-use syntax::ast::{self, IsString};
+use ide_db::{
+    base_db::SourceDatabase, env_vars::CARGO_DEFINED_VARS,
+    syntax_helpers::node_ext::macro_call_for_string_token, RootDatabase,
+};
+use syntax::{
+    ast::{self, IsString},
+    AstNode, AstToken,
+};
 
 use crate::{
     completions::Completions, context::CompletionContext, CompletionItem, CompletionItemKind,
 };
 
-const CARGO_DEFINED_VARS: &[(&str, &str)] = &[
-    ("CARGO","Path to the cargo binary performing the build"),
-    ("CARGO_MANIFEST_DIR","The directory containing the manifest of your package"),
-    ("CARGO_PKG_VERSION","The full version of your package"),
-    ("CARGO_PKG_VERSION_MAJOR","The major version of your package"),
-    ("CARGO_PKG_VERSION_MINOR","The minor version of your package"),
-    ("CARGO_PKG_VERSION_PATCH","The patch version of your package"),
-    ("CARGO_PKG_VERSION_PRE","The pre-release version of your package"),
-    ("CARGO_PKG_AUTHORS","Colon separated list of authors from the manifest of your package"),
-    ("CARGO_PKG_NAME","The name of your package"),
-    ("CARGO_PKG_DESCRIPTION","The description from the manifest of your package"),
-    ("CARGO_PKG_HOMEPAGE","The home page from the manifest of your package"),
-    ("CARGO_PKG_REPOSITORY","The repository from the manifest of your package"),
-    ("CARGO_PKG_LICENSE","The license from the manifest of your package"),
-    ("CARGO_PKG_LICENSE_FILE","The license file from the manifest of your package"),
-    ("CARGO_PKG_RUST_VERSION","The Rust version from the manifest of your package. Note that this is the minimum Rust version supported by the package, not the current Rust version"),
-    ("CARGO_CRATE_NAME","The name of the crate that is currently being compiled"),
-    ("CARGO_BIN_NAME","The name of the binary that is currently being compiled (if it is a binary). This name does not include any file extension, such as .exe"),
-    ("CARGO_PRIMARY_PACKAGE","This environment variable will be set if the package being built is primary. Primary packages are the ones the user selected on the command-line, either with -p flags or the defaults based on the current directory and the default workspace members. This environment variable will not be set when building dependencies. This is only set when compiling the package (not when running binaries or tests)"),
-    ("CARGO_TARGET_TMPDIR","Only set when building integration test or benchmark code. This is a path to a directory inside the target directory where integration tests or benchmarks are free to put any data needed by the tests/benches. Cargo initially creates this directory but doesn't manage its content in any way, this is the responsibility of the test code")
-];
-
 pub(crate) fn complete_cargo_env_vars(
     acc: &mut Completions,
     ctx: &CompletionContext<'_>,
+    original: &ast::String,
     expanded: &ast::String,
 ) -> Option<()> {
-    guard_env_macro(expanded, &ctx.sema)?;
+    guard_env_macro(expanded, &ctx.sema).or_else(|| guard_env_var_fn(original, &ctx.sema))?;
     let range = expanded.text_range_between_quotes()?;
 
     CARGO_DEFINED_VARS.iter().for_each(|&(var, detail)| {
         let mut item = CompletionItem::new(CompletionItemKind::Keyword, range, var);
         item.detail(detail);
-        item.add_to(acc, ctx.db);
+        item.add_to(acc, ctx);
     });
 
+    let crate_graph = ctx.db.crate_graph();
+    crate_graph[ctx.krate.into()]
+        .env
+        .iter()
+        .filter(|(var, _)| CARGO_DEFINED_VARS.iter().all(|(it, _)| it != var))
+        .for_each(|(var, value)| {
+            let mut item = CompletionItem::new(CompletionItemKind::Keyword, range, var);
+            item.detail(value);
+            item.add_to(acc, ctx);
+        });
+
     Some(())
 }
 
@@ -58,6 +55,22 @@ fn guard_env_macro(string: &ast::String, semantics: &Semantics<'_, RootDatabase>
     }
 }
 
+/// Checks for an argument of `std::env::var` or `std::env::var_os`.
+fn guard_env_var_fn(string: &ast::String, semantics: &Semantics<'_, RootDatabase>) -> Option<()> {
+    let literal = string.syntax().parent().and_then(ast::Literal::cast)?;
+    let call = literal.syntax().parent()?.parent().and_then(ast::CallExpr::cast)?;
+    let ast::Expr::PathExpr(callee) = call.expr()? else { return None };
+    let hir::PathResolution::Def(hir::ModuleDef::Function(func)) =
+        semantics.resolve_path(&callee.path()?)?
+    else {
+        return None;
+    };
+    let db = semantics.db;
+    let is_env_module = func.module(db).name(db).map_or(false, |it| it.to_smol_str() == "env");
+    let name = func.name(db).to_smol_str();
+    (is_env_module && matches!(name.as_str(), "var" | "var_os")).then_some(())
+}
+
 #[cfg(test)]
 mod tests {
     use crate::tests::{check_edit, completion_list};
@@ -101,6 +114,30 @@ mod tests {
         check("option_env");
     }
 
+    #[test]
+    fn completes_crate_env_variable_in_env_var_call() {
+        check_edit(
+            "BUILD_HASH",
+            r#"
+//- /main.rs env:BUILD_HASH=abc123
+mod env {
+    pub fn var(key: &str) {}
+}
+fn main() {
+    env::var("BUI$0");
+}
+"#,
+            r#"
+mod env {
+    pub fn var(key: &str) {}
+}
+fn main() {
+    env::var("BUILD_HASH");
+}
+"#,
+        );
+    }
+
     #[test]
     fn doesnt_complete_in_random_strings() {
         let fixture = r#"
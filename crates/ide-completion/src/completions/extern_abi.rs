@@ -53,7 +53,7 @@ pub(crate) fn complete_extern_abi(
     let source_range = abi_str.text_range_between_quotes()?;
     for &abi in SUPPORTED_CALLING_CONVENTIONS {
         CompletionItem::new(CompletionItemKind::Keyword, source_range, SmolStr::new_static(abi))
-            .add_to(acc, ctx.db);
+            .add_to(acc, ctx);
     }
     Some(())
 }
@@ -22,7 +22,7 @@ pub(crate) fn complete_extern_crate(acc: &mut Completions, ctx: &CompletionConte
         );
         item.set_documentation(module.docs(ctx.db));
 
-        item.add_to(acc, ctx.db);
+        item.add_to(acc, ctx);
     }
 }
 
@@ -0,0 +1,177 @@
+//! Completes paths to files in string literals that name a file relative to the current one, in
+//! `include!`, `include_str!`, `include_bytes!` and `#[path = "..."]`.
+
+use std::path::Component;
+
+use ide_db::{
+    base_db::{SourceDatabaseExt, VfsPath},
+    syntax_helpers::node_ext::macro_call_for_string_token,
+    FxHashSet, SymbolKind,
+};
+use syntax::{
+    ast::{self, IsString},
+    AstNode, AstToken, TextRange, TextSize,
+};
+
+use crate::{context::CompletionContext, CompletionItem, Completions};
+
+pub(crate) fn complete_file_path(
+    acc: &mut Completions,
+    ctx: &CompletionContext<'_>,
+    original: &ast::String,
+) -> Option<()> {
+    if !is_file_path_literal(ctx, original) {
+        return None;
+    }
+    let token_start = original.syntax().text_range().start();
+    let text_start = original.text_range_between_quotes()?.start();
+    let typed = original.syntax().text().get(
+        usize::from(text_start - token_start)..usize::from(ctx.position.offset - token_start),
+    )?;
+    let (typed_dir, typed_name) = match typed.rsplit_once('/') {
+        Some((dir, name)) => (Some(dir), name),
+        None => (None, typed),
+    };
+
+    let file_id = ctx.position.file_id;
+    let source_root = ctx.db.source_root(ctx.db.file_source_root(file_id));
+    let current_dir = source_root.path_for_file(&file_id)?.parent()?;
+    let dir = match typed_dir {
+        Some(typed_dir) => current_dir.join(typed_dir)?,
+        None => current_dir,
+    };
+
+    let source_range =
+        TextRange::new(ctx.position.offset - TextSize::of(typed_name), ctx.position.offset);
+    let mut seen = FxHashSet::default();
+    for candidate in source_root.iter() {
+        if candidate == file_id {
+            continue;
+        }
+        let Some(label) = source_root.path_for_file(&candidate).and_then(|it| entry_in(it, &dir))
+        else {
+            continue;
+        };
+        if seen.insert(label.clone()) {
+            CompletionItem::new(SymbolKind::Module, source_range, label).add_to(acc, ctx);
+        }
+    }
+    Some(())
+}
+
+/// Returns the name of the file or directory in `dir` that contains `path`, directories ending in
+/// a `/`.
+fn entry_in(path: &VfsPath, dir: &VfsPath) -> Option<String> {
+    let relative = path.strip_prefix(dir)?;
+    let mut components = relative.as_ref().components();
+    let Component::Normal(name) = components.next()? else { return None };
+    let name = name.to_str()?;
+    Some(match components.next() {
+        Some(_) => format!("{name}/"),
+        None => name.to_owned(),
+    })
+}
+
+fn is_file_path_literal(ctx: &CompletionContext<'_>, string: &ast::String) -> bool {
+    if let Some(call) = macro_call_for_string_token(string) {
+        let is_include = call
+            .path()
+            .and_then(|it| it.segment())
+            .and_then(|it| it.name_ref())
+            .map_or(false, |name| {
+                matches!(name.text().as_str(), "include" | "include_str" | "include_bytes")
+            });
+        return is_include
+            && ctx
+                .sema
+                .resolve_macro_call(&call)
+                .map_or(false, |it| it.kind(ctx.db) == hir::MacroKind::BuiltIn);
+    }
+    let Some(meta) = string.syntax().parent().and_then(|it| it.parent()).and_then(ast::Meta::cast)
+    else {
+        return false;
+    };
+    let is_path_attr = meta.path().map_or(false, |it| it.syntax().text() == "path");
+    let on_module = meta
+        .parent_attr()
+        .and_then(|attr| attr.syntax().parent())
+        .map_or(false, |it| ast::Module::can_cast(it.kind()));
+    is_path_attr && on_module
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::{expect, Expect};
+
+    use crate::tests::{check_edit, completion_list};
+
+    fn check(ra_fixture: &str, expect: Expect) {
+        let actual = completion_list(ra_fixture);
+        expect.assert_eq(&actual);
+    }
+
+    #[test]
+    fn completes_files_and_directories_in_include_str() {
+        check(
+            r#"
+//- /main.rs
+#[rustc_builtin_macro]
+macro_rules! include_str {}
+const README: &str = include_str!("$0");
+//- /README.md
+//- /assets/logo.svg
+//- /assets/icons/a.svg
+"#,
+            expect![[r#"
+                md README.md
+                md assets/
+            "#]],
+        );
+    }
+
+    #[test]
+    fn completes_in_subdirectory() {
+        check_edit(
+            "a.svg",
+            r#"
+//- /main.rs
+#[rustc_builtin_macro]
+macro_rules! include_bytes {}
+const ICON: &[u8] = include_bytes!("assets/icons/$0");
+//- /assets/icons/a.svg
+"#,
+            r#"
+#[rustc_builtin_macro]
+macro_rules! include_bytes {}
+const ICON: &[u8] = include_bytes!("assets/icons/a.svg");
+"#,
+        );
+    }
+
+    #[test]
+    fn completes_module_path_attribute() {
+        check(
+            r#"
+//- /main.rs
+#[path = "gen/$0"]
+mod generated;
+//- /gen/bindings.rs
+"#,
+            expect![[r#"
+                md bindings.rs
+            "#]],
+        );
+    }
+
+    #[test]
+    fn no_completion_in_other_strings() {
+        check(
+            r#"
+//- /main.rs
+const S: &str = "$0";
+//- /other.txt
+"#,
+            expect![[""]],
+        );
+    }
+}
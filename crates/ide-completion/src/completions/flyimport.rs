@@ -79,6 +79,9 @@ use crate::{
 // }
 // ```
 //
+// Methods of traits implemented for the receiver type are completed after a `.` too. If the
+// trait's name is already taken in the scope, it is imported as `_` instead.
+//
 // NOTE: currently, if an assoc item comes from a trait that's not currently imported, and it also has an unresolved and/or partially-qualified path,
 // no imports will be proposed.
 //
@@ -283,7 +286,7 @@ fn import_on_the_fly(
         .filter_map(|import| {
             render_resolution_with_import(RenderContext::new(ctx), path_ctx, import)
         })
-        .map(|builder| builder.build(ctx.db))
+        .map(|builder| builder.build(ctx))
         .for_each(|item| acc.add(item));
     Some(())
 }
@@ -334,7 +337,7 @@ fn import_on_the_fly_pat_(
         .filter_map(|import| {
             render_resolution_with_import_pat(RenderContext::new(ctx), pattern_ctx, import)
         })
-        .map(|builder| builder.build(ctx.db))
+        .map(|builder| builder.build(ctx))
         .for_each(|item| acc.add(item));
     Some(())
 }
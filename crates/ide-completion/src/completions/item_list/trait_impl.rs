@@ -31,13 +31,13 @@
 //! }
 //! ```
 
-use hir::HasAttrs;
+use hir::{HasAttrs, ScopeDef};
 use ide_db::{
     documentation::HasDocs, path_transform::PathTransform,
     syntax_helpers::insert_whitespace_into_node, traits::get_missing_assoc_items, SymbolKind,
 };
 use syntax::{
-    ast::{self, edit_in_place::AttrsOwnerEdit, HasTypeBounds},
+    ast::{self, edit::IndentLevel, edit_in_place::AttrsOwnerEdit, HasName, HasTypeBounds},
     format_smolstr, AstNode, SmolStr, SyntaxElement, SyntaxKind, TextRange, T,
 };
 use text_edit::TextEdit;
@@ -216,11 +216,110 @@ fn add_function_impl(
                     item.text_edit(TextEdit::replace(replacement_range, header));
                 }
             };
-            item.add_to(acc, ctx.db);
+            item.add_to(acc, ctx);
         }
     }
 }
 
+/// Completes `impl Tra$0` to a whole `impl Trait for Type` block, with stubs for all the trait's
+/// required items.
+pub(crate) fn complete_impl_skeleton(
+    acc: &mut Completions,
+    ctx: &CompletionContext<'_>,
+    path_ctx: &PathCompletionCtx,
+) -> Option<()> {
+    let cap = ctx.config.snippet_cap?;
+    if !path_ctx.is_trivial_path() {
+        return None;
+    }
+    let impl_ = ctx.token.parent_ancestors().find_map(ast::Impl::cast)?;
+    if impl_.trait_().is_some() || impl_.for_token().is_some() || impl_.assoc_item_list().is_some()
+    {
+        return None;
+    }
+    let indent = IndentLevel::from_node(impl_.syntax());
+
+    ctx.process_all_names(&mut |name, def, _| {
+        let ScopeDef::ModuleDef(hir::ModuleDef::Trait(trait_)) = def else { return };
+        if trait_.type_or_const_param_count(ctx.db, false) != 0 {
+            return;
+        }
+        let Some(stubs) = required_item_stubs(ctx, trait_, indent + 1) else { return };
+        let name = name.display(ctx.db).to_string();
+
+        let mut snippet = format!("{name} for ${{1:Type}} {{");
+        for (idx, stub) in stubs.iter().enumerate() {
+            let sep = if idx == 0 { "\n" } else { "\n\n" };
+            snippet.push_str(&format!("{sep}{}{stub}", indent + 1));
+        }
+        snippet.push_str(&format!("\n{indent}}}$0"));
+
+        let mut item = CompletionItem::new(
+            CompletionItemKind::Snippet,
+            ctx.source_range(),
+            format_smolstr!("{name} for …"),
+        );
+        item.set_documentation(trait_.docs(ctx.db)).insert_snippet(cap, snippet);
+        item.add_to(acc, ctx);
+    });
+    Some(())
+}
+
+/// Renders the items of `trait_` without a default as they would be implemented, with `todo!()`
+/// bodies.
+fn required_item_stubs(
+    ctx: &CompletionContext<'_>,
+    trait_: hir::Trait,
+    indent: IndentLevel,
+) -> Option<Vec<String>> {
+    let mut stubs = Vec::new();
+    for assoc_item in trait_.items(ctx.db) {
+        let stub = match assoc_item {
+            hir::AssocItem::Function(func) => {
+                let source = ctx.sema.source(func)?;
+                if source.value.body().is_some() {
+                    continue;
+                }
+                let func = transform_for_skeleton(ctx, ast::AssocItem::Fn(source.value))?;
+                let ast::AssocItem::Fn(func) = func else { return None };
+                let decl = function_declaration(&func, source.file_id.is_macro());
+                format!("{decl} {{\n{}todo!()\n{indent}}}", indent + 1)
+            }
+            hir::AssocItem::Const(const_) => {
+                let source = ctx.sema.source(const_)?;
+                if source.value.body().is_some() {
+                    continue;
+                }
+                let const_ = transform_for_skeleton(ctx, ast::AssocItem::Const(source.value))?;
+                let ast::AssocItem::Const(const_) = const_ else { return None };
+                format!("{} todo!();", make_const_compl_syntax(&const_, source.file_id.is_macro()))
+            }
+            hir::AssocItem::TypeAlias(type_alias) => {
+                let source = ctx.sema.source(type_alias)?;
+                if source.value.ty().is_some() {
+                    continue;
+                }
+                format!("type {} = ();", source.value.name()?)
+            }
+        };
+        stubs.push(stub);
+    }
+    Some(stubs)
+}
+
+/// Like [`get_transformed_assoc_item`], for an impl that doesn't exist yet.
+fn transform_for_skeleton(
+    ctx: &CompletionContext<'_>,
+    assoc_item: ast::AssocItem,
+) -> Option<ast::AssocItem> {
+    let source_scope = ctx.sema.scope(assoc_item.syntax())?;
+    let transform = PathTransform::generic_transformation(&ctx.scope, &source_scope);
+    let assoc_item = assoc_item.clone_for_update();
+    transform.apply(assoc_item.syntax());
+    assoc_item.remove_attrs_and_docs();
+    Some(assoc_item)
+}
+
 /// Transform a relevant associated item to inline generics from the impl, remove attrs and docs, etc.
 fn get_transformed_assoc_item(
     ctx: &CompletionContext<'_>,
@@ -302,7 +401,7 @@ fn add_type_alias_impl(
                     item.text_edit(TextEdit::replace(replacement_range, decl));
                 }
             };
-            item.add_to(acc, ctx.db);
+            item.add_to(acc, ctx);
         }
     }
 }
@@ -342,7 +441,7 @@ fn add_const_impl(
                     ),
                     None => item.text_edit(TextEdit::replace(replacement_range, replacement)),
                 };
-                item.add_to(acc, ctx.db);
+                item.add_to(acc, ctx);
             }
         }
     }
@@ -1393,6 +1492,39 @@ trait Tr {
 impl Tr for () {
     type Item = $0;
 }
+"#,
+        );
+    }
+
+    #[test]
+    fn impl_skeleton_with_required_items() {
+        check_edit(
+            "Shape for …",
+            r#"
+trait Shape {
+    const SIDES: u32;
+    type Unit;
+    fn area(&self) -> f64;
+    fn name(&self) -> &str { "" }
+}
+impl Sha$0
+"#,
+            r#"
+trait Shape {
+    const SIDES: u32;
+    type Unit;
+    fn area(&self) -> f64;
+    fn name(&self) -> &str { "" }
+}
+impl Shape for ${1:Type} {
+    const SIDES: u32 = todo!();
+
+    type Unit = ();
+
+    fn area(&self) -> f64 {
+        todo!()
+    }
+}$0
 "#,
         );
     }
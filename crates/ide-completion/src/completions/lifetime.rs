@@ -8,7 +8,7 @@
 //! show up for normal completions, or they won't show completions other than lifetimes depending
 //! on the fixture input.
 use hir::{known, ScopeDef};
-use syntax::{ast, TokenText};
+use syntax::{ast, AstNode, SyntaxKind, TokenText};
 
 use crate::{
     completions::Completions,
@@ -57,16 +57,27 @@ pub(crate) fn complete_label(
     ctx: &CompletionContext<'_>,
     lifetime_ctx: &LifetimeContext,
 ) {
-    if !matches!(lifetime_ctx, LifetimeContext { kind: LifetimeKind::LabelRef, .. }) {
+    let LifetimeContext { kind: LifetimeKind::LabelRef { is_continue }, .. } = lifetime_ctx else {
         return;
-    }
+    };
     ctx.process_all_names_raw(&mut |name, res| {
-        if let ScopeDef::Label(_) = res {
+        if let ScopeDef::Label(label) = res {
+            // Only loops can be continued, blocks can only be broken out of.
+            if *is_continue && !labels_loop(ctx, label) {
+                return;
+            }
             acc.add_label(ctx, name);
         }
     });
 }
 
+fn labels_loop(ctx: &CompletionContext<'_>, label: hir::Label) -> bool {
+    let label = label.source(ctx.db).value;
+    label.syntax().parent().map_or(false, |it| {
+        matches!(it.kind(), SyntaxKind::LOOP_EXPR | SyntaxKind::WHILE_EXPR | SyntaxKind::FOR_EXPR)
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use expect_test::{expect, Expect};
@@ -275,6 +286,26 @@ fn foo() {
         );
     }
 
+    #[test]
+    fn complete_only_loop_labels_after_continue() {
+        check(
+            r#"
+fn foo() {
+    'block: {
+        'outer: while true {
+            'inner: {
+                continue '$0
+            }
+        }
+    }
+}
+"#,
+            expect![[r#"
+                lb 'outer
+            "#]],
+        );
+    }
+
     #[test]
     fn complete_label_in_block_nested() {
         check(
@@ -6,6 +6,7 @@ use hir::ItemInNs;
 use ide_db::{
     documentation::{Documentation, HasDocs},
     imports::insert_use::ImportScope,
+    match_arms,
     ty_filter::TryEnum,
     SnippetCap,
 };
@@ -75,7 +76,7 @@ pub(crate) fn complete_postfix(
                         &format!("{path}($0{receiver_text})", path = path.display(ctx.db)),
                     );
                     item.set_documentation(drop_fn.docs(ctx.db));
-                    item.add_to(acc, ctx.db);
+                    item.add_to(acc, ctx);
                 }
             }
         }
@@ -90,21 +91,21 @@ pub(crate) fn complete_postfix(
                     "if let Ok {}",
                     &format!("if let Ok($1) = {receiver_text} {{\n    $0\n}}"),
                 )
-                .add_to(acc, ctx.db);
+                .add_to(acc, ctx);
 
                 postfix_snippet(
                     "lete",
                     "let Ok else {}",
                     &format!("let Ok($1) = {receiver_text} else {{\n    $2\n}};\n$0"),
                 )
-                .add_to(acc, ctx.db);
+                .add_to(acc, ctx);
 
                 postfix_snippet(
                     "while",
                     "while let Ok {}",
                     &format!("while let Ok($1) = {receiver_text} {{\n    $0\n}}"),
                 )
-                .add_to(acc, ctx.db);
+                .add_to(acc, ctx);
             }
             TryEnum::Option => {
                 postfix_snippet(
@@ -112,29 +113,29 @@ pub(crate) fn complete_postfix(
                     "if let Some {}",
                     &format!("if let Some($1) = {receiver_text} {{\n    $0\n}}"),
                 )
-                .add_to(acc, ctx.db);
+                .add_to(acc, ctx);
 
                 postfix_snippet(
                     "lete",
                     "let Some else {}",
                     &format!("let Some($1) = {receiver_text} else {{\n    $2\n}};\n$0"),
                 )
-                .add_to(acc, ctx.db);
+                .add_to(acc, ctx);
 
                 postfix_snippet(
                     "while",
                     "while let Some {}",
                     &format!("while let Some($1) = {receiver_text} {{\n    $0\n}}"),
                 )
-                .add_to(acc, ctx.db);
+                .add_to(acc, ctx);
             }
         }
     } else if receiver_ty.is_bool() || receiver_ty.is_unknown() {
         postfix_snippet("if", "if expr {}", &format!("if {receiver_text} {{\n    $0\n}}"))
-            .add_to(acc, ctx.db);
+            .add_to(acc, ctx);
         postfix_snippet("while", "while expr {}", &format!("while {receiver_text} {{\n    $0\n}}"))
-            .add_to(acc, ctx.db);
-        postfix_snippet("not", "!expr", &format!("!{receiver_text}")).add_to(acc, ctx.db);
+            .add_to(acc, ctx);
+        postfix_snippet("not", "!expr", &format!("!{receiver_text}")).add_to(acc, ctx);
     } else if let Some(trait_) = ctx.famous_defs().core_iter_IntoIterator() {
         if receiver_ty.impls_trait(ctx.db, trait_, &[]) {
             postfix_snippet(
@@ -142,13 +143,13 @@ pub(crate) fn complete_postfix(
                 "for ele in expr {}",
                 &format!("for ele in {receiver_text} {{\n    $0\n}}"),
             )
-            .add_to(acc, ctx.db);
+            .add_to(acc, ctx);
         }
     }
 
-    postfix_snippet("ref", "&expr", &format!("&{receiver_text}")).add_to(acc, ctx.db);
-    postfix_snippet("refm", "&mut expr", &format!("&mut {receiver_text}")).add_to(acc, ctx.db);
-    postfix_snippet("deref", "*expr", &format!("*{receiver_text}")).add_to(acc, ctx.db);
+    postfix_snippet("ref", "&expr", &format!("&{receiver_text}")).add_to(acc, ctx);
+    postfix_snippet("refm", "&mut expr", &format!("&mut {receiver_text}")).add_to(acc, ctx);
+    postfix_snippet("deref", "*expr", &format!("*{receiver_text}")).add_to(acc, ctx);
 
     let mut unsafe_should_be_wrapped = true;
     if dot_receiver.syntax().kind() == BLOCK_EXPR {
@@ -164,7 +165,7 @@ pub(crate) fn complete_postfix(
     } else {
         format!("unsafe {receiver_text}")
     };
-    postfix_snippet("unsafe", "unsafe {}", &unsafe_completion_string).add_to(acc, ctx.db);
+    postfix_snippet("unsafe", "unsafe {}", &unsafe_completion_string).add_to(acc, ctx);
 
     // The rest of the postfix completions create an expression that moves an argument,
     // so it's better to consider references now to avoid breaking the compilation
@@ -189,7 +190,7 @@ pub(crate) fn complete_postfix(
                     "match expr {}",
                     &format!("match {receiver_text} {{\n    Ok(${{1:_}}) => {{$2}},\n    Err(${{3:_}}) => {{$0}},\n}}"),
                 )
-                .add_to(acc, ctx.db);
+                .add_to(acc, ctx);
             }
             TryEnum::Option => {
                 postfix_snippet(
@@ -199,32 +200,84 @@ pub(crate) fn complete_postfix(
                         "match {receiver_text} {{\n    Some(${{1:_}}) => {{$2}},\n    None => {{$0}},\n}}"
                     ),
                 )
-                .add_to(acc, ctx.db);
+                .add_to(acc, ctx);
             }
         },
         None => {
+            let arms = match_arms::enum_variant_pats(
+                ctx.db,
+                ctx.module,
+                receiver_ty,
+                ctx.config.prefer_no_std,
+                ctx.config.prefer_prelude,
+            );
+            let arms = match arms {
+                Some((pats, needs_wildcard)) if !pats.is_empty() => {
+                    let pats = pats
+                        .iter()
+                        .map(ToString::to_string)
+                        .chain(needs_wildcard.then(|| "_".to_owned()))
+                        .collect::<Vec<_>>();
+                    let last = pats.len() - 1;
+                    pats.iter()
+                        .enumerate()
+                        .map(|(idx, pat)| {
+                            let tabstop = if idx == last { 0 } else { idx + 1 };
+                            format!("    {pat} => {{${tabstop}}},\n")
+                        })
+                        .collect::<String>()
+                }
+                _ => "    ${1:_} => {$0},\n".to_owned(),
+            };
             postfix_snippet(
                 "match",
                 "match expr {}",
-                &format!("match {receiver_text} {{\n    ${{1:_}} => {{$0}},\n}}"),
+                &format!("match {receiver_text} {{\n{arms}}}"),
             )
-            .add_to(acc, ctx.db);
+            .add_to(acc, ctx);
         }
     }
 
     postfix_snippet("box", "Box::new(expr)", &format!("Box::new({receiver_text})"))
-        .add_to(acc, ctx.db);
-    postfix_snippet("dbg", "dbg!(expr)", &format!("dbg!({receiver_text})")).add_to(acc, ctx.db); // fixme
-    postfix_snippet("dbgr", "dbg!(&expr)", &format!("dbg!(&{receiver_text})")).add_to(acc, ctx.db);
-    postfix_snippet("call", "function(expr)", &format!("${{1}}({receiver_text})"))
-        .add_to(acc, ctx.db);
+        .add_to(acc, ctx);
+    let famous_defs = ctx.famous_defs();
+    let smart_pointers = [
+        ("pinbox", famous_defs.alloc_boxed_Box(), "pin"),
+        ("arc", famous_defs.alloc_sync_Arc(), "new"),
+        ("rc", famous_defs.alloc_rc_Rc(), "new"),
+    ];
+    for (label, strukt, ctor) in smart_pointers {
+        // custom snippets with the same trigger take precedence
+        if ctx.config.postfix_snippets().any(|(trigger, _)| trigger == label) {
+            continue;
+        }
+        // only offered when `alloc` is available, so never in `no_std` crates without it
+        let Some(strukt) = strukt else { continue };
+        let path = ctx.module.find_use_path(
+            ctx.db,
+            ItemInNs::Types(hir::ModuleDef::from(strukt)),
+            ctx.config.prefer_no_std,
+            ctx.config.prefer_prelude,
+        );
+        if let Some(path) = path {
+            let name = strukt.name(ctx.db);
+            postfix_snippet(
+                label,
+                &format!("{}::{ctor}(expr)", name.display(ctx.db)),
+                &format!("{}::{ctor}({receiver_text})", path.display(ctx.db)),
+            )
+            .add_to(acc, ctx);
+        }
+    }
+    postfix_snippet("dbg", "dbg!(expr)", &format!("dbg!({receiver_text})")).add_to(acc, ctx); // fixme
+    postfix_snippet("dbgr", "dbg!(&expr)", &format!("dbg!(&{receiver_text})")).add_to(acc, ctx);
+    postfix_snippet("call", "function(expr)", &format!("${{1}}({receiver_text})")).add_to(acc, ctx);
 
     if let Some(parent) = dot_receiver.syntax().parent().and_then(|p| p.parent()) {
         if matches!(parent.kind(), STMT_LIST | EXPR_STMT) {
-            postfix_snippet("let", "let", &format!("let $0 = {receiver_text};"))
-                .add_to(acc, ctx.db);
+            postfix_snippet("let", "let", &format!("let $0 = {receiver_text};")).add_to(acc, ctx);
             postfix_snippet("letm", "let mut", &format!("let mut $0 = {receiver_text};"))
-                .add_to(acc, ctx.db);
+                .add_to(acc, ctx);
         }
     }
 
@@ -242,7 +295,7 @@ pub(crate) fn complete_postfix(
             semi = if expr_ctx.in_block_expr { ";" } else { "" }
         ),
     )
-    .add_to(acc, ctx.db);
+    .add_to(acc, ctx);
 
     if let BreakableKind::Block | BreakableKind::Loop = expr_ctx.in_breakable {
         postfix_snippet(
@@ -253,7 +306,7 @@ pub(crate) fn complete_postfix(
                 semi = if expr_ctx.in_block_expr { ";" } else { "" }
             ),
         )
-        .add_to(acc, ctx.db);
+        .add_to(acc, ctx);
     }
 }
 
@@ -365,7 +418,7 @@ fn add_custom_postfix_completions(
             for import in imports.into_iter() {
                 builder.add_import(import);
             }
-            builder.add_to(acc, ctx.db);
+            builder.add_to(acc, ctx);
         },
     );
     None
@@ -568,6 +621,70 @@ fn main() {
         );
     }
 
+    #[test]
+    fn enum_match() {
+        check_edit(
+            "match",
+            r#"
+enum Shape { Circle(f32), Rect { w: f32, h: f32 }, Empty }
+fn f(shape: &Shape) {
+    shape.$0
+}
+"#,
+            r#"
+enum Shape { Circle(f32), Rect { w: f32, h: f32 }, Empty }
+fn f(shape: &Shape) {
+    match shape {
+    Shape::Circle(_) => {$1},
+    Shape::Rect { w, h } => {$2},
+    Shape::Empty => {$0},
+}
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn smart_pointer_wrappers() {
+        check_edit(
+            "pinbox",
+            r#"
+//- /main.rs crate:main deps:alloc
+fn main() {
+    let x = 1;
+    x.$0
+}
+//- /alloc.rs crate:alloc
+pub mod boxed { pub struct Box<T>(T); }
+"#,
+            r#"
+fn main() {
+    let x = 1;
+    alloc::boxed::Box::pin(x)
+}
+"#,
+        );
+        check_edit(
+            "arc",
+            r#"
+//- /main.rs crate:main deps:alloc
+fn main() {
+    let x = 1;
+    x.$0
+}
+//- /alloc.rs crate:alloc
+pub mod sync { pub struct Arc<T>(T); }
+pub mod rc { pub struct Rc<T>(T); }
+"#,
+            r#"
+fn main() {
+    let x = 1;
+    alloc::sync::Arc::new(x)
+}
+"#,
+        );
+    }
+
     #[test]
     fn postfix_completion_works_for_ambiguous_float_literal() {
         check_edit("refm", r#"fn main() { 42.$0 }"#, r#"fn main() { &mut 42 }"#)
@@ -60,7 +60,7 @@ pub(crate) fn add_format_like_completions(
                 format!(r#"{}({}, {})"#, macro_name, out, exprs.join(", "))
             };
 
-            postfix_snippet(label, macro_name, &snippet).add_to(acc, ctx.db);
+            postfix_snippet(label, macro_name, &snippet).add_to(acc, ctx);
         }
     }
 }
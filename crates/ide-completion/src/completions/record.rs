@@ -1,5 +1,6 @@
 //! Complete fields in record literals and patterns.
 use ide_db::SymbolKind;
+use itertools::Itertools;
 use syntax::{
     ast::{self, Expr},
     SmolStr,
@@ -34,10 +35,30 @@ pub(crate) fn complete_record_pattern_fields(
             }
             _ => ctx.sema.record_pattern_missing_fields(record_pat),
         };
+        let has_rest_pat =
+            record_pat.record_pat_field_list().and_then(|fl| fl.rest_pat()).is_some();
+        if !has_rest_pat {
+            add_remaining_fields(acc, ctx, &missing_fields);
+        }
         complete_fields(acc, ctx, missing_fields);
     }
 }
 
+/// Completes all the missing fields of a record pattern at once.
+fn add_remaining_fields(
+    acc: &mut Completions,
+    ctx: &CompletionContext<'_>,
+    missing_fields: &[(hir::Field, hir::Type)],
+) {
+    if missing_fields.len() < 2 {
+        return;
+    }
+    let fields =
+        missing_fields.iter().map(|(field, _)| field.name(ctx.db).to_smol_str()).join(", ");
+    let item = CompletionItem::new(CompletionItemKind::Snippet, ctx.source_range(), fields);
+    item.add_to(acc, ctx);
+}
+
 pub(crate) fn complete_record_expr_fields(
     acc: &mut Completions,
     ctx: &CompletionContext<'_>,
@@ -75,7 +96,7 @@ pub(crate) fn complete_record_expr_fields(
                     SmolStr::new_static(".."),
                 );
                 item.insert_text(".");
-                item.add_to(acc, ctx.db);
+                item.add_to(acc, ctx);
                 return;
             }
             missing_fields
@@ -108,7 +129,7 @@ pub(crate) fn add_default_update(
             postfix_match: Some(CompletionRelevancePostfixMatch::Exact),
             ..Default::default()
         });
-        item.add_to(acc, ctx.db);
+        item.add_to(acc, ctx);
     }
 }
 
@@ -171,6 +192,68 @@ fn baz() {
         )
     }
 
+    #[test]
+    fn literal_struct_completion_in_return_position() {
+        check_edit(
+            "FooDesc{}",
+            r#"
+struct FooDesc { pub bar: bool, pub baz: u32 }
+
+fn create_foo(cond: bool) -> FooDesc {
+    if cond {
+        return $0;
+    }
+    loop {}
+}
+            "#,
+            r#"
+struct FooDesc { pub bar: bool, pub baz: u32 }
+
+fn create_foo(cond: bool) -> FooDesc {
+    if cond {
+        return FooDesc { bar: ${1:()}, baz: ${2:()} }$0;
+    }
+    loop {}
+}
+            "#,
+        )
+    }
+
+    #[test]
+    fn literal_struct_completion_default_rest() {
+        check_edit_with_config(
+            CompletionConfig { struct_literal_default_rest: true, ..TEST_CONFIG },
+            "FooDesc{}",
+            r#"
+//- minicore: default, builtin_impls
+struct Handle;
+struct FooDesc { pub count: u32, pub handle: Handle }
+impl Default for FooDesc {
+    fn default() -> Self { FooDesc { count: 0, handle: Handle } }
+}
+
+fn create_foo(foo_desc: FooDesc) {}
+
+fn baz() {
+    create_foo($0);
+}
+            "#,
+            r#"
+struct Handle;
+struct FooDesc { pub count: u32, pub handle: Handle }
+impl Default for FooDesc {
+    fn default() -> Self { FooDesc { count: 0, handle: Handle } }
+}
+
+fn create_foo(foo_desc: FooDesc) {}
+
+fn baz() {
+    create_foo(FooDesc { handle: ${1:()}, ..Default::default() }$0);
+}
+            "#,
+        )
+    }
+
     #[test]
     fn enum_variant_no_snippets() {
         let conf = CompletionConfig { snippet_cap: SnippetCap::new(false), ..TEST_CONFIG };
@@ -31,8 +31,8 @@ pub(crate) fn complete_expr_snippet(
     }
 
     if in_block_expr {
-        snippet(ctx, cap, "pd", "eprintln!(\"$0 = {:?}\", $0);").add_to(acc, ctx.db);
-        snippet(ctx, cap, "ppd", "eprintln!(\"$0 = {:#?}\", $0);").add_to(acc, ctx.db);
+        snippet(ctx, cap, "pd", "eprintln!(\"$0 = {:?}\", $0);").add_to(acc, ctx);
+        snippet(ctx, cap, "ppd", "eprintln!(\"$0 = {:#?}\", $0);").add_to(acc, ctx);
         let item = snippet(
             ctx,
             cap,
@@ -44,7 +44,7 @@ macro_rules! $1 {
     };
 }",
         );
-        item.add_to(acc, ctx.db);
+        item.add_to(acc, ctx);
     }
 }
 
@@ -87,7 +87,7 @@ mod tests {
 }",
         );
         item.lookup_by("tmod");
-        item.add_to(acc, ctx.db);
+        item.add_to(acc, ctx);
 
         let mut item = snippet(
             ctx,
@@ -100,7 +100,7 @@ fn ${1:feature}() {
 }",
         );
         item.lookup_by("tfn");
-        item.add_to(acc, ctx.db);
+        item.add_to(acc, ctx);
 
         let item = snippet(
             ctx,
@@ -113,7 +113,25 @@ macro_rules! $1 {
     };
 }",
         );
-        item.add_to(acc, ctx.db);
+        item.add_to(acc, ctx);
+    }
+}
+
+pub(crate) fn complete_type_snippet(
+    acc: &mut Completions,
+    ctx: &CompletionContext<'_>,
+    path_ctx: &PathCompletionCtx,
+) {
+    if !matches!(path_ctx.qualified, Qualified::No) {
+        return;
+    }
+    let cap = match ctx.config.snippet_cap {
+        Some(it) => it,
+        None => return,
+    };
+
+    if !ctx.config.snippets.is_empty() {
+        add_custom_completions(acc, ctx, cap, SnippetScope::Type);
     }
 }
 
@@ -143,7 +161,7 @@ fn add_custom_completions(
                 builder.add_import(import);
             }
             builder.set_detail(snip.description.clone());
-            builder.add_to(acc, ctx.db);
+            builder.add_to(acc, ctx);
         },
     );
     None
@@ -180,6 +198,41 @@ fn main() { $0 }
 use core::ops::ControlFlow;
 
 fn main() { ControlFlow::Break(()) }
+"#,
+        );
+    }
+
+    #[test]
+    fn custom_type_snippet_completion() {
+        check_edit_with_config(
+            CompletionConfig {
+                snippets: vec![Snippet::new(
+                    &["shared".into()],
+                    &[],
+                    &["Arc<Mutex<$0>>".into()],
+                    "",
+                    &["sync::Arc".into(), "sync::Mutex".into()],
+                    crate::SnippetScope::Type,
+                )
+                .unwrap()],
+                ..TEST_CONFIG
+            },
+            "shared",
+            r#"
+mod sync {
+    pub struct Arc<T>(T);
+    pub struct Mutex<T>(T);
+}
+struct Cache { entries: $0 }
+"#,
+            r#"
+use sync::{Arc, Mutex};
+
+mod sync {
+    pub struct Arc<T>(T);
+    pub struct Mutex<T>(T);
+}
+struct Cache { entries: Arc<Mutex<$0>> }
 "#,
         );
     }
@@ -2,7 +2,11 @@
 
 use hir::ScopeDef;
 use ide_db::{FxHashSet, SymbolKind};
-use syntax::{ast, format_smolstr, AstNode};
+use syntax::{
+    ast::{self, edit::IndentLevel},
+    format_smolstr, AstNode,
+};
+use text_edit::TextEdit;
 
 use crate::{
     context::{CompletionContext, PathCompletionCtx, Qualified},
@@ -75,7 +79,7 @@ pub(crate) fn complete_use_path(
                                 is_name_already_imported,
                                 ..r
                             });
-                            acc.add(builder.build(ctx.db));
+                            acc.add(builder.build(ctx));
                         }
                     }
                 }
@@ -94,9 +98,11 @@ pub(crate) fn complete_use_path(
         // only show modules and non-std enum in a fresh UseTree
         Qualified::No => {
             cov_mark::hit!(unqualified_path_selected_only);
+            let mut modules = FxHashSet::default();
             ctx.process_all_names(&mut |name, res, doc_aliases| {
                 match res {
                     ScopeDef::ModuleDef(hir::ModuleDef::Module(module)) => {
+                        modules.insert(name.clone());
                         acc.add_module(ctx, path_ctx, module, name, doc_aliases);
                     }
                     ScopeDef::ModuleDef(hir::ModuleDef::Adt(hir::Adt::Enum(e))) => {
@@ -110,14 +116,45 @@ pub(crate) fn complete_use_path(
                                 ctx.source_range(),
                                 format_smolstr!("{}::", e.name(ctx.db).display(ctx.db)),
                             );
-                            acc.add(item.build(ctx.db));
+                            acc.add(item.build(ctx));
                         }
                     }
                     _ => {}
                 };
             });
+            add_unlinked_dependencies(acc, ctx, path_ctx, &modules);
             acc.add_nameref_keywords_with_colon(ctx);
         }
         Qualified::TypeAnchor { .. } | Qualified::With { resolution: None, .. } => {}
     }
 }
+
+/// Adds the direct dependencies of the current crate that aren't in scope, like the ones missing
+/// from its extern prelude, inserting the `extern crate` that brings them into scope.
+fn add_unlinked_dependencies(
+    acc: &mut Completions,
+    ctx: &CompletionContext<'_>,
+    path_ctx: &PathCompletionCtx,
+    in_scope: &FxHashSet<hir::Name>,
+) {
+    let Some(use_item) = ctx.token.parent_ancestors().find_map(ast::Use::cast) else { return };
+    let indent = IndentLevel::from_node(use_item.syntax());
+    for dep in ctx.krate.dependencies(ctx.db) {
+        if in_scope.contains(&dep.name) {
+            continue;
+        }
+        let module = hir::ModuleDef::Module(dep.krate.root_module());
+        let name = dep.name.to_smol_str();
+        let mut edit = TextEdit::builder();
+        edit.insert(
+            use_item.syntax().text_range().start(),
+            format!("extern crate {name};\n{indent}"),
+        );
+        edit.replace(ctx.source_range(), name.to_string());
+
+        let mut builder =
+            Builder::from_resolution(ctx, path_ctx, dep.name, ScopeDef::ModuleDef(module));
+        builder.text_edit(edit.finish());
+        acc.add(builder.build(ctx));
+    }
+}
@@ -16,6 +16,7 @@ pub struct CompletionConfig {
     pub enable_private_editable: bool,
     pub enable_term_search: bool,
     pub full_function_signatures: bool,
+    pub struct_literal_default_rest: bool,
     pub callable: Option<CallableSnippets>,
     pub snippet_cap: Option<SnippetCap>,
     pub insert_use: InsertUseConfig,
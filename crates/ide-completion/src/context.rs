@@ -293,7 +293,7 @@ pub(crate) struct LifetimeContext {
 pub(crate) enum LifetimeKind {
     LifetimeParam { is_decl: bool, param: ast::LifetimeParam },
     Lifetime,
-    LabelRef,
+    LabelRef { is_continue: bool },
     LabelDef,
 }
 
@@ -472,6 +472,21 @@ impl CompletionContext<'_> {
         }
     }
 
+    /// The part of the identifier being completed that precedes the cursor, if anything is typed.
+    pub(crate) fn typed_ident(&self) -> Option<&str> {
+        let start = self.source_range().start();
+        if start >= self.position.offset {
+            return None;
+        }
+        let typed_range = TextRange::new(start, self.position.offset);
+        let token_range = self.original_token.text_range();
+        if !token_range.contains_range(typed_range) {
+            return None;
+        }
+        let typed = &self.original_token.text()[typed_range - token_range.start()];
+        typed.chars().all(|c| c.is_alphanumeric() || c == '_').then_some(typed)
+    }
+
     pub(crate) fn famous_defs(&self) -> FamousDefs<'_, '_> {
         FamousDefs(&self.sema, self.krate)
     }
@@ -468,6 +468,20 @@ fn expected_type_and_name(
                     let def = sema.to_def(&it);
                     (def.map(|def| def.ret_type(sema.db)), None)
                 },
+                ast::ReturnExpr(it) => {
+                    cov_mark::hit!(expected_type_return_expr);
+                    // the expected type is the return type of the enclosing function or closure
+                    let callable = it.syntax().ancestors().find(|it| {
+                        ast::Fn::can_cast(it.kind()) || ast::ClosureExpr::can_cast(it.kind())
+                    });
+                    match callable {
+                        Some(it) => {
+                            node = it;
+                            continue;
+                        }
+                        None => (None, None),
+                    }
+                },
                 ast::ClosureExpr(it) => {
                     let ty = sema.type_of_expr(&it.into());
                     ty.and_then(|ty| ty.original.as_callable(sema.db))
@@ -508,8 +522,8 @@ fn classify_lifetime(
                 is_decl: param.lifetime().as_ref() == Some(&lifetime),
                 param
             },
-            ast::BreakExpr(_) => LifetimeKind::LabelRef,
-            ast::ContinueExpr(_) => LifetimeKind::LabelRef,
+            ast::BreakExpr(_) => LifetimeKind::LabelRef { is_continue: false },
+            ast::ContinueExpr(_) => LifetimeKind::LabelRef { is_continue: true },
             ast::Label(_) => LifetimeKind::LabelDef,
             _ => LifetimeKind::Lifetime,
         }
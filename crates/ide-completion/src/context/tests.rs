@@ -310,6 +310,22 @@ fn foo() -> u32 {
     )
 }
 
+#[test]
+fn expected_type_return_expr() {
+    cov_mark::check!(expected_type_return_expr);
+    check_expected_type_and_name(
+        r#"
+fn foo(cond: bool) -> u32 {
+    if cond {
+        return $0;
+    }
+    0
+}
+"#,
+        expect![[r#"ty: u32, name: ?"#]],
+    )
+}
+
 #[test]
 fn expected_type_closure_param_return() {
     // FIXME: make this work with `|| $0`
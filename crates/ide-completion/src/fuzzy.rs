@@ -0,0 +1,136 @@
+//! Matching of the lookup strings of completion items against the identifier typed so far.
+//!
+//! The typed characters have to occur in order in the lookup string, ignoring case. Matches where
+//! each of them starts a word of the lookup string (or directly follows the previous one) rank
+//! higher, and a single typo, like two swapped characters or a stray one, is tolerated:
+//!
+//! ```text
+//! rec     -> receive   (prefix)
+//! tcp     -> TcpStream (prefix)
+//! ts      -> TcpStream (word boundaries)
+//! rce     -> receive   (subsequence)
+//! recieve -> receive   (typo)
+//! ```
+
+use crate::item::CompletionRelevanceFuzzyMatch;
+
+/// Typos are only tolerated once this many characters are typed, shorter inputs would match
+/// almost anything.
+const MIN_TYPO_LEN: usize = 3;
+
+/// Returns how well `typed` matches `lookup`, or `None` if it doesn't match at all.
+pub(crate) fn fuzzy_match(typed: &str, lookup: &str) -> Option<CompletionRelevanceFuzzyMatch> {
+    let typed = typed.chars().collect::<Vec<_>>();
+    let lookup = lookup.chars().collect::<Vec<_>>();
+    if typed.is_empty() {
+        return None;
+    }
+    if typed.len() <= lookup.len() && typed.iter().zip(&lookup).all(|(&t, &l)| eq(t, l)) {
+        return Some(CompletionRelevanceFuzzyMatch::Prefix);
+    }
+    if !is_subsequence(&typed, &lookup) {
+        return has_typo_match(&typed, &lookup).then_some(CompletionRelevanceFuzzyMatch::Typo);
+    }
+    if matches_word_boundaries(&typed, &lookup) {
+        Some(CompletionRelevanceFuzzyMatch::WordBoundary)
+    } else {
+        Some(CompletionRelevanceFuzzyMatch::Subsequence)
+    }
+}
+
+fn eq(a: char, b: char) -> bool {
+    a.eq_ignore_ascii_case(&b)
+}
+
+fn is_subsequence(typed: &[char], lookup: &[char]) -> bool {
+    let mut lookup = lookup.iter();
+    typed.iter().all(|&t| lookup.any(|&l| eq(t, l)))
+}
+
+/// Whether `typed` becomes a subsequence of `lookup` after undoing a single typo: swapping two
+/// adjacent characters, or dropping one that isn't the first.
+fn has_typo_match(typed: &[char], lookup: &[char]) -> bool {
+    if typed.len() < MIN_TYPO_LEN {
+        return false;
+    }
+    let mut candidate = typed.to_vec();
+    for i in 0..typed.len() - 1 {
+        candidate.swap(i, i + 1);
+        if is_subsequence(&candidate, lookup) {
+            return true;
+        }
+        candidate.swap(i, i + 1);
+    }
+    (1..typed.len()).any(|i| {
+        let mut candidate = typed.to_vec();
+        candidate.remove(i);
+        is_subsequence(&candidate, lookup)
+    })
+}
+
+/// Whether `lookup` starts a word at `idx`, in `snake_case`, `CamelCase` and paths alike.
+fn is_word_start(lookup: &[char], idx: usize) -> bool {
+    let Some(&cur) = lookup.get(idx) else { return false };
+    let Some(&prev) = idx.checked_sub(1).and_then(|it| lookup.get(it)) else { return true };
+    if !cur.is_alphanumeric() {
+        return false;
+    }
+    !prev.is_alphanumeric() || (cur.is_uppercase() && !prev.is_uppercase())
+}
+
+/// Whether `typed` matches `lookup` such that each typed character either starts a word of
+/// `lookup` or directly follows the previously matched one, like `hm` in `HashMap` or `inal` in
+/// `insert_all`.
+fn matches_word_boundaries(typed: &[char], lookup: &[char]) -> bool {
+    let n = lookup.len();
+    // `next[j]`: the rest of the typed text can be matched with its first character at `j`.
+    let mut next = vec![true; n + 1];
+    // `next_at_start[j]`: the same holds for some word start at or after `j`.
+    let mut next_at_start = vec![true; n + 2];
+    for (i, &t) in typed.iter().enumerate().rev() {
+        let is_last = i + 1 == typed.len();
+        let mut cur = vec![false; n + 1];
+        for (j, &l) in lookup.iter().enumerate() {
+            cur[j] = eq(t, l) && (is_last || next[j + 1] || next_at_start[j + 1]);
+        }
+        let mut cur_at_start = vec![false; n + 2];
+        for j in (0..n).rev() {
+            cur_at_start[j] = (cur[j] && is_word_start(lookup, j)) || cur_at_start[j + 1];
+        }
+        next = cur;
+        next_at_start = cur_at_start;
+    }
+    next_at_start[0]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use CompletionRelevanceFuzzyMatch::*;
+
+    #[test]
+    fn match_kinds() {
+        let cases = [
+            ("rec", "receive", Some(Prefix)),
+            ("REC", "receive", Some(Prefix)),
+            ("hm", "HashMap", Some(WordBoundary)),
+            ("hmap", "HashMap", Some(WordBoundary)),
+            ("inal", "insert_all", Some(WordBoundary)),
+            ("iter", "into_iter", Some(WordBoundary)),
+            ("hap", "HashMap", Some(Subsequence)),
+            ("rce", "receive", Some(Subsequence)),
+            ("recieve", "receive", Some(Typo)),
+            ("recevie", "receive", Some(Typo)),
+            ("recxeive", "receive", Some(Typo)),
+            ("lenght", "length", Some(Typo)),
+            ("", "receive", None),
+            ("xy", "receive", None),
+            ("ab", "ba", None),
+            ("xrecieve", "receive", None),
+        ];
+        for (typed, lookup, expected) in cases {
+            assert_eq!(fuzzy_match(typed, lookup), expected, "{typed} -> {lookup}");
+        }
+    }
+}
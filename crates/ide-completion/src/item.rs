@@ -4,8 +4,7 @@ use std::{fmt, mem};
 
 use hir::Mutability;
 use ide_db::{
-    documentation::Documentation, imports::import_assets::LocatedImport, RootDatabase, SnippetCap,
-    SymbolKind,
+    documentation::Documentation, imports::import_assets::LocatedImport, SnippetCap, SymbolKind,
 };
 use itertools::Itertools;
 use smallvec::SmallVec;
@@ -15,6 +14,7 @@ use text_edit::TextEdit;
 
 use crate::{
     context::{CompletionContext, PathCompletionCtx},
+    fuzzy,
     render::{render_path_resolution, RenderContext},
 };
 
@@ -70,9 +70,9 @@ pub struct CompletionItem {
     /// types align precisely?". We can't sort by relevances directly, they are
     /// only partially ordered.
     ///
-    /// Note that Relevance ignores fuzzy match score. We compute Relevance for
-    /// all possible items, and then separately build an ordered completion list
-    /// based on relevance and fuzzy matching with the already typed identifier.
+    /// Note that the fuzzy match with the already typed identifier is recorded,
+    /// but doesn't contribute to the score, it only breaks ties. Clients do
+    /// their own fuzzy matching to filter and order the list.
     pub relevance: CompletionRelevance,
 
     /// Indicates that a reference or mutable reference to this variable is a
@@ -113,7 +113,9 @@ impl fmt::Debug for CompletionItem {
             s.field("deprecated", &true);
         }
 
-        if self.relevance != CompletionRelevance::default() {
+        // The fuzzy match alone is set for most items once something is typed, don't let it clutter
+        // the output.
+        if (CompletionRelevance { fuzzy_match: None, ..self.relevance }) != Default::default() {
             s.field("relevance", &self.relevance);
         }
 
@@ -150,6 +152,20 @@ pub struct CompletionRelevance {
     /// }
     /// ```
     pub is_local: bool,
+    /// This is set for items that are already referred to in earlier statements of the
+    /// enclosing function. It doesn't affect the score, but breaks ties between items of equal
+    /// score.
+    ///
+    /// ```
+    /// fn foo(v: Vec<u32>) {
+    ///     let total = sum(&v);
+    ///     $0 // `sum` was used just before
+    /// }
+    /// ```
+    pub is_recently_used: bool,
+    /// How well the lookup string matches the identifier typed so far, if anything is typed. Like
+    /// `is_recently_used`, it doesn't affect the score but breaks ties.
+    pub fuzzy_match: Option<CompletionRelevanceFuzzyMatch>,
     /// This is set when trait items are completed in an impl of that trait.
     pub is_item_from_trait: bool,
     /// This is set for when trait items are from traits with `#[doc(notable_trait)]`
@@ -209,6 +225,20 @@ pub enum CompletionRelevancePostfixMatch {
     Exact,
 }
 
+/// How the identifier typed so far matches the lookup string of an item, from the worst to the
+/// best kind of match.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord)]
+pub enum CompletionRelevanceFuzzyMatch {
+    /// The typed text only matches after undoing a typo, like `recieve` for `receive`.
+    Typo,
+    /// The typed characters occur in order, like `rce` in `receive`.
+    Subsequence,
+    /// Each typed character starts a word or follows the previous one, like `hm` in `HashMap`.
+    WordBoundary,
+    /// The lookup string starts with the typed text.
+    Prefix,
+}
+
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub struct CompletionRelevanceFn {
     pub has_params: bool,
@@ -243,6 +273,8 @@ impl CompletionRelevance {
             exact_name_match,
             type_match,
             is_local,
+            is_recently_used: _,
+            fuzzy_match: _,
             is_item_from_trait,
             is_name_already_imported,
             requires_import,
@@ -482,9 +514,11 @@ impl Builder {
         )
     }
 
-    pub(crate) fn build(self, db: &RootDatabase) -> CompletionItem {
+    pub(crate) fn build(self, ctx: &CompletionContext<'_>) -> CompletionItem {
         let _p = tracing::span!(tracing::Level::INFO, "item::Builder::build").entered();
 
+        let db = ctx.db;
+
         let label = self.label;
         let mut label_detail = None;
         let mut lookup = self.lookup.unwrap_or_else(|| label.clone());
@@ -512,6 +546,15 @@ impl Builder {
                 lookup = format_smolstr!("{lookup}{lookup_doc_aliases}");
             }
         }
+        let mut relevance = self.relevance;
+        if let Some(typed) = ctx.typed_ident() {
+            relevance.fuzzy_match = fuzzy::fuzzy_match(typed, &lookup);
+            // Items that only match with a typo are looked up by the typed text, so that clients
+            // don't filter them out.
+            if relevance.fuzzy_match == Some(CompletionRelevanceFuzzyMatch::Typo) {
+                lookup = typed.into();
+            }
+        }
         if let [import_edit] = &*self.imports_to_add {
             // snippets can have multiple imports, but normal completions only have up to one
             label_detail.replace(format_smolstr!(
@@ -554,7 +597,7 @@ impl Builder {
             kind: self.kind,
             deprecated: self.deprecated,
             trigger_call_info: self.trigger_call_info,
-            relevance: self.relevance,
+            relevance,
             ref_match: self.ref_match,
             import_to_add,
         }
@@ -5,6 +5,7 @@
 mod completions;
 mod config;
 mod context;
+mod fuzzy;
 mod item;
 mod render;
 
@@ -12,6 +13,7 @@ mod snippet;
 #[cfg(test)]
 mod tests;
 
+use hir::{ItemInNs, ModuleDef, ScopeDef};
 use ide_db::{
     base_db::FilePosition,
     helpers::mod_path_to_ast,
@@ -19,9 +21,9 @@ use ide_db::{
         import_assets::NameToImport,
         insert_use::{self, ImportScope},
     },
-    items_locator, RootDatabase,
+    items_locator, FxHashSet, RootDatabase,
 };
-use syntax::algo;
+use syntax::{algo, ast, AstNode, SmolStr};
 use text_edit::TextEdit;
 
 use crate::{
@@ -35,7 +37,8 @@ use crate::{
 pub use crate::{
     config::{CallableSnippets, CompletionConfig},
     item::{
-        CompletionItem, CompletionItemKind, CompletionRelevance, CompletionRelevancePostfixMatch,
+        CompletionItem, CompletionItemKind, CompletionRelevance, CompletionRelevanceFuzzyMatch,
+        CompletionRelevancePostfixMatch,
     },
     snippet::{Snippet, SnippetScope},
 };
@@ -207,7 +210,8 @@ pub fn completions(
             CompletionAnalysis::String { original, expanded: Some(expanded) } => {
                 completions::extern_abi::complete_extern_abi(acc, ctx, expanded);
                 completions::format_string::format_string(acc, ctx, original, expanded);
-                completions::env_vars::complete_cargo_env_vars(acc, ctx, expanded);
+                completions::env_vars::complete_cargo_env_vars(acc, ctx, original, expanded);
+                completions::file_path::complete_file_path(acc, ctx, original);
             }
             CompletionAnalysis::UnexpandedAttrTT {
                 colon_prefix,
@@ -226,7 +230,47 @@ pub fn completions(
         }
     }
 
-    Some(completions.into())
+    let mut completions: Vec<CompletionItem> = completions.into();
+    mark_recently_used(ctx, &mut completions);
+    retain_fuzzy_matches(ctx, &mut completions);
+    Some(completions)
+}
+
+/// Marks the completions whose names are referred to in the statements of the enclosing function
+/// that precede the one being completed.
+fn mark_recently_used(ctx: &CompletionContext<'_>, items: &mut [CompletionItem]) {
+    let Some(stmt_list) = ctx
+        .original_token
+        .parent_ancestors()
+        .find_map(ast::Fn::cast)
+        .and_then(|it| it.body())
+        .and_then(|it| it.stmt_list())
+    else {
+        return;
+    };
+    let cursor = ctx.source_range().start();
+    let used: FxHashSet<SmolStr> = stmt_list
+        .statements()
+        .take_while(|it| it.syntax().text_range().end() <= cursor)
+        .flat_map(|it| it.syntax().descendants().filter_map(ast::NameRef::cast))
+        .map(|it| SmolStr::from(it.text().as_str()))
+        .collect();
+    for item in items {
+        if matches!(item.kind, CompletionItemKind::Keyword | CompletionItemKind::Snippet) {
+            continue;
+        }
+        if used.contains(item.lookup()) {
+            item.relevance.is_recently_used = true;
+        }
+    }
+}
+
+/// Drops the completions that don't match the identifier typed so far when the number of items is
+/// limited, the client would filter them out anyway.
+fn retain_fuzzy_matches(ctx: &CompletionContext<'_>, items: &mut Vec<CompletionItem>) {
+    if ctx.config.limit.is_some() && ctx.typed_ident().is_some() {
+        items.retain(|it| it.relevance.fuzzy_match.is_some());
+    }
 }
 
 /// Resolves additional completion data at the position given.
@@ -246,7 +290,8 @@ pub fn resolve_completion_edits(
     let position_for_import = &original_token.parent()?;
     let scope = ImportScope::find_insert_use_container(position_for_import, &sema)?;
 
-    let current_module = sema.scope(position_for_import)?.module();
+    let sema_scope = sema.scope(position_for_import)?;
+    let current_module = sema_scope.module();
     let current_crate = current_module.krate();
     let new_ast = scope.clone_for_update();
     let mut import_insert = TextEdit::builder();
@@ -255,25 +300,53 @@ pub fn resolve_completion_edits(
         let items_with_name = items_locator::items_with_name(
             &sema,
             current_crate,
-            NameToImport::exact_case_sensitive(imported_name),
+            NameToImport::exact_case_sensitive(imported_name.clone()),
             items_locator::AssocSearchMode::Include,
         );
         let import = items_with_name
             .filter_map(|candidate| {
-                current_module.find_use_path_prefixed(
+                let mod_path = current_module.find_use_path_prefixed(
                     db,
                     candidate,
                     config.insert_use.prefix_kind,
                     config.prefer_no_std,
                     config.prefer_prelude,
-                )
+                )?;
+                Some((candidate, mod_path))
             })
-            .find(|mod_path| mod_path.display(db).to_string() == full_import_path);
-        if let Some(import_path) = import {
-            insert_use::insert_use(&new_ast, mod_path_to_ast(&import_path), &config.insert_use);
+            .find(|(_, mod_path)| mod_path.display(db).to_string() == full_import_path);
+        if let Some((candidate, import_path)) = import {
+            let path = mod_path_to_ast(&import_path);
+            // Traits are only imported for their methods, so importing one whose name is already
+            // taken (e.g. `anyhow::Context` next to `std::task::Context`) is done anonymously.
+            let is_trait = matches!(candidate, ItemInNs::Types(ModuleDef::Trait(_)));
+            if is_trait && name_is_taken(&sema_scope, &imported_name) {
+                insert_use::insert_use_as_alias(&new_ast, path, &config.insert_use);
+            } else {
+                insert_use::insert_use(&new_ast, path, &config.insert_use);
+            }
         }
     });
 
     algo::diff(scope.as_syntax_node(), new_ast.as_syntax_node()).into_text_edit(&mut import_insert);
     Some(vec![import_insert.finish()])
 }
+
+/// Whether `name` already refers to something in the type namespace of `scope`.
+fn name_is_taken(scope: &hir::SemanticsScope<'_>, name: &str) -> bool {
+    let mut taken = false;
+    scope.process_all_names(&mut |it, def| {
+        let is_value = matches!(
+            def,
+            ScopeDef::Local(_)
+                | ScopeDef::Label(_)
+                | ScopeDef::ModuleDef(
+                    ModuleDef::Function(_) | ModuleDef::Const(_) | ModuleDef::Static(_)
+                )
+        );
+        if !is_value && it.to_smol_str() == name {
+            taken = true;
+        }
+    });
+    taken
+}
@@ -192,7 +192,7 @@ pub(crate) fn render_field(
         }
     }
     item.doc_aliases(ctx.doc_aliases);
-    item.build(db)
+    item.build(ctx.completion)
 }
 
 fn field_with_receiver(
@@ -218,7 +218,7 @@ pub(crate) fn render_tuple_field(
         field_with_receiver(ctx.db(), receiver.as_ref(), &field.to_string()),
     );
     item.detail(ty.display(ctx.db()).to_string()).lookup_by(field.to_string());
-    item.build(ctx.db())
+    item.build(ctx.completion)
 }
 
 pub(crate) fn render_type_inference(
@@ -228,7 +228,7 @@ pub(crate) fn render_type_inference(
     let mut builder =
         CompletionItem::new(CompletionItemKind::InferredType, ctx.source_range(), ty_string);
     builder.set_relevance(CompletionRelevance { is_definite: true, ..Default::default() });
-    builder.build(ctx.db)
+    builder.build(ctx)
 }
 
 pub(crate) fn render_path_resolution(
@@ -1256,6 +1256,10 @@ fn main() { let _: m::Spam = S$0 }
                                 Exact,
                             ),
                             is_local: false,
+                            is_recently_used: false,
+                            fuzzy_match: Some(
+                                Prefix,
+                            ),
                             is_item_from_trait: false,
                             is_item_from_notable_trait: false,
                             is_name_already_imported: false,
@@ -1284,6 +1288,10 @@ fn main() { let _: m::Spam = S$0 }
                                 Exact,
                             ),
                             is_local: false,
+                            is_recently_used: false,
+                            fuzzy_match: Some(
+                                Prefix,
+                            ),
                             is_item_from_trait: false,
                             is_item_from_notable_trait: false,
                             is_name_already_imported: false,
@@ -1340,6 +1348,43 @@ fn main() { som$0 }
             "#]],
         );
 
+        check(
+            r#"
+#[deprecated(note = "use `bar` instead")]
+fn foo() {}
+
+fn main() { fo$0 }
+"#,
+            SymbolKind::Function,
+            expect![[r#"
+                [
+                    CompletionItem {
+                        label: "foo()",
+                        source_range: 67..69,
+                        delete: 67..69,
+                        insert: "foo()$0",
+                        kind: SymbolKind(
+                            Function,
+                        ),
+                        lookup: "foo",
+                        detail: "fn() // deprecated: use `bar` instead",
+                        deprecated: true,
+                    },
+                    CompletionItem {
+                        label: "main()",
+                        source_range: 67..69,
+                        delete: 67..69,
+                        insert: "main()$0",
+                        kind: SymbolKind(
+                            Function,
+                        ),
+                        lookup: "main",
+                        detail: "fn()",
+                    },
+                ]
+            "#]],
+        );
+
         check(
             r#"
 struct A { #[deprecated] the_field: u32 }
@@ -1364,6 +1409,10 @@ fn foo() { A { the$0 } }
                                 CouldUnify,
                             ),
                             is_local: false,
+                            is_recently_used: false,
+                            fuzzy_match: Some(
+                                Prefix,
+                            ),
                             is_item_from_trait: false,
                             is_item_from_notable_trait: false,
                             is_name_already_imported: false,
@@ -1410,6 +1459,8 @@ impl S {
                             exact_name_match: false,
                             type_match: None,
                             is_local: false,
+                            is_recently_used: false,
+                            fuzzy_match: None,
                             is_item_from_trait: false,
                             is_item_from_notable_trait: false,
                             is_name_already_imported: false,
@@ -1535,6 +1586,8 @@ fn foo(s: S) { s.$0 }
                             exact_name_match: false,
                             type_match: None,
                             is_local: false,
+                            is_recently_used: false,
+                            fuzzy_match: None,
                             is_item_from_trait: false,
                             is_item_from_notable_trait: false,
                             is_name_already_imported: false,
@@ -2025,6 +2078,28 @@ fn foo(bar: u32) {
         );
     }
 
+    #[test]
+    fn recently_used() {
+        let items = get_all_items(
+            TEST_CONFIG,
+            r#"
+fn sum() -> u32 { 0 }
+fn other() {}
+fn main() {
+    let total = sum();
+    s$0
+}
+"#,
+            None,
+        );
+        let recent = items
+            .iter()
+            .filter(|it| it.relevance.is_recently_used)
+            .map(|it| it.label.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(recent, ["sum()"]);
+    }
+
     #[test]
     fn enum_owned() {
         check_relevance(
@@ -2423,6 +2498,10 @@ fn foo(f: Foo) { let _: &u32 = f.b$0 }
                             exact_name_match: false,
                             type_match: None,
                             is_local: false,
+                            is_recently_used: false,
+                            fuzzy_match: Some(
+                                Prefix,
+                            ),
                             is_item_from_trait: false,
                             is_item_from_notable_trait: false,
                             is_name_already_imported: false,
@@ -2505,6 +2584,10 @@ fn foo() {
                                 Exact,
                             ),
                             is_local: false,
+                            is_recently_used: false,
+                            fuzzy_match: Some(
+                                Prefix,
+                            ),
                             is_item_from_trait: false,
                             is_item_from_notable_trait: false,
                             is_name_already_imported: false,
@@ -2555,6 +2638,8 @@ fn main() {
                             exact_name_match: false,
                             type_match: None,
                             is_local: false,
+                            is_recently_used: false,
+                            fuzzy_match: None,
                             is_item_from_trait: false,
                             is_item_from_notable_trait: false,
                             is_name_already_imported: false,
@@ -2924,6 +3009,8 @@ fn main() {
                             exact_name_match: false,
                             type_match: None,
                             is_local: false,
+                            is_recently_used: false,
+                            fuzzy_match: None,
                             is_item_from_trait: false,
                             is_item_from_notable_trait: true,
                             is_name_already_imported: false,
@@ -2947,6 +3034,8 @@ fn main() {
                             exact_name_match: false,
                             type_match: None,
                             is_local: false,
+                            is_recently_used: false,
+                            fuzzy_match: None,
                             is_item_from_trait: false,
                             is_item_from_notable_trait: true,
                             is_name_already_imported: false,
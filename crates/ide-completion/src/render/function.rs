@@ -1,7 +1,7 @@
 //! Renderer for function calls.
 
 use hir::{db::HirDatabase, AsAssocItem, HirDisplay};
-use ide_db::{SnippetCap, SymbolKind};
+use ide_db::{defs::Definition, SnippetCap, SymbolKind};
 use itertools::Itertools;
 use stdx::{format_to, to_lower_snake_case};
 use syntax::{format_smolstr, AstNode, SmolStr};
@@ -140,11 +140,14 @@ fn render(
         _ => (),
     }
 
-    let detail = if ctx.completion.config.full_function_signatures {
+    let mut detail = if ctx.completion.config.full_function_signatures {
         detail_full(db, func)
     } else {
         detail(db, func)
     };
+    if let Some(note) = Definition::Function(func).deprecation(db).and_then(|it| it.note) {
+        format_to!(detail, " // deprecated: {note}");
+    }
     item.set_documentation(ctx.docs(func))
         .set_deprecated(ctx.is_deprecated(func) || ctx.is_deprecated_assoc_item(func))
         .detail(detail)
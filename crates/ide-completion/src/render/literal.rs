@@ -85,7 +85,18 @@ fn render(
             render_tuple_lit(db, snippet_cap, &fields, &escaped_qualified_name)
         }
         StructKind::Record if should_add_parens => {
-            render_record_lit(db, snippet_cap, &fields, &escaped_qualified_name)
+            let non_default_fields = match thing {
+                Variant::Struct(strukt) if completion.config.struct_literal_default_rest => {
+                    non_default_fields(completion, strukt, &fields)
+                }
+                _ => None,
+            };
+            match non_default_fields {
+                Some(fields) => {
+                    render_record_lit(db, snippet_cap, &fields, true, &escaped_qualified_name)
+                }
+                None => render_record_lit(db, snippet_cap, &fields, false, &escaped_qualified_name),
+            }
         }
         _ => RenderedLiteral {
             literal: escaped_qualified_name.clone(),
@@ -138,6 +149,26 @@ fn render(
     Some(item)
 }
 
+/// Returns the fields of a struct implementing `Default` whose types don't implement `Default`
+/// themselves, the others can be left to a `..Default::default()`.
+fn non_default_fields(
+    ctx: &CompletionContext<'_>,
+    strukt: hir::Struct,
+    fields: &[hir::Field],
+) -> Option<Vec<hir::Field>> {
+    let default_trait = ctx.famous_defs().core_default_Default()?;
+    if !strukt.ty(ctx.db).impls_trait(ctx.db, default_trait, &[]) {
+        return None;
+    }
+    Some(
+        fields
+            .iter()
+            .copied()
+            .filter(|field| !field.ty(ctx.db).impls_trait(ctx.db, default_trait, &[]))
+            .collect(),
+    )
+}
+
 #[derive(Clone, Copy)]
 enum Variant {
     Struct(hir::Struct),
@@ -124,7 +124,7 @@ fn build_completion(
         Some(snippet_cap) => item.insert_snippet(snippet_cap, pat),
         None => item.insert_text(pat),
     };
-    item.build(ctx.db())
+    item.build(ctx.completion)
 }
 
 fn render_pat(
@@ -53,5 +53,5 @@ fn render(
     }
     item.insert_text(escaped_name);
 
-    Some(item.build(ctx.db()))
+    Some(item.build(ctx.completion))
 }
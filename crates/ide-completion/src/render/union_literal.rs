@@ -82,5 +82,5 @@ pub(crate) fn render_union_literal(
         None => item.insert_text(literal),
     };
 
-    Some(item.build(ctx.db()))
+    Some(item.build(ctx.completion))
 }
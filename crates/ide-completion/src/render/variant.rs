@@ -15,11 +15,13 @@ pub(crate) struct RenderedLiteral {
 }
 
 /// Render a record type (or sub-type) to a `RenderedCompound`. Use `None` for
-/// the `name` argument for an anonymous type.
+/// the `name` argument for an anonymous type. With `default_rest`, the literal ends in
+/// `..Default::default()` covering all fields not listed.
 pub(crate) fn render_record_lit(
     db: &dyn HirDatabase,
     snippet_cap: Option<SnippetCap>,
     fields: &[hir::Field],
+    default_rest: bool,
     path: &str,
 ) -> RenderedLiteral {
     if snippet_cap.is_none() {
@@ -37,6 +39,12 @@ pub(crate) fn render_record_lit(
         f(&format_args!("{}: {}", field.name(db).display(db.upcast()), field.ty(db).display(db)))
     });
 
+    let (completions, types) = match (default_rest, fields.is_empty()) {
+        (false, _) => (completions.to_string(), types.to_string()),
+        (true, true) => ("..Default::default()".to_owned(), "..".to_owned()),
+        (true, false) => (format!("{completions}, ..Default::default()"), format!("{types}, ..")),
+    };
+
     RenderedLiteral {
         literal: format!("{path} {{ {completions} }}"),
         detail: format!("{path} {{ {types} }}"),
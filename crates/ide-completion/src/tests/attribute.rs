@@ -778,6 +778,35 @@ mod derive {
         );
     }
 
+    #[test]
+    fn derive_filters_traits_unsatisfiable_by_fields() {
+        check_derive(
+            r#"
+//- minicore: derive, copy, clone, ord, eq, default, fmt
+struct NotClone;
+#[derive($0)] struct Test { a: u8, b: NotClone }
+"#,
+            expect![[r#"
+                md core
+                kw crate::
+                kw self::
+            "#]],
+        );
+        check_derive(
+            r#"
+//- minicore: derive, copy, clone, ord, eq, default, fmt
+#[derive(Clone)] struct OnlyClone;
+#[derive($0)] enum Test { A, B { b: OnlyClone } }
+"#,
+            expect![[r#"
+                de Clone   macro Clone
+                md core
+                kw crate::
+                kw self::
+            "#]],
+        );
+    }
+
     #[test]
     fn derive_flyimport() {
         check_derive(
@@ -928,6 +957,15 @@ mod lint {
         );
     }
 
+    #[test]
+    fn lint_rust_analyzer_qualified() {
+        check_edit(
+            "remove_unnecessary_else",
+            r#"#[allow(rust_analyzer::$0)] fn f() {}"#,
+            r#"#[allow(rust_analyzer::remove_unnecessary_else)] fn f() {}"#,
+        );
+    }
+
     #[test]
     fn lint_unclosed() {
         check_edit(
@@ -1309,3 +1309,51 @@ fn main() {
         "#]],
     );
 }
+
+#[test]
+fn ufcs_completes_derived_and_blanket_impl_items() {
+    check_edit(
+        "default",
+        r#"
+//- minicore: default, derive
+#[derive(Default)]
+struct Foo;
+fn main() { Foo::def$0 }
+"#,
+        r#"
+#[derive(Default)]
+struct Foo;
+fn main() { Foo::default()$0 }
+"#,
+    );
+    check_edit(
+        "describe",
+        r#"
+trait Describe { fn describe() -> &'static str; }
+impl<T> Describe for T { fn describe() -> &'static str { "" } }
+struct Foo;
+fn main() { Foo::desc$0 }
+"#,
+        r#"
+trait Describe { fn describe() -> &'static str; }
+impl<T> Describe for T { fn describe() -> &'static str { "" } }
+struct Foo;
+fn main() { Foo::describe()$0 }
+"#,
+    );
+}
+
+#[test]
+fn completes_despite_typo() {
+    check_edit(
+        "recieve",
+        r#"
+fn receive() {}
+fn main() { recieve$0 }
+"#,
+        r#"
+fn receive() {}
+fn main() { receive()$0 }
+"#,
+    );
+}
@@ -538,6 +538,46 @@ fn main() {
     );
 }
 
+#[test]
+fn trait_method_import_with_taken_name() {
+    check_edit(
+        "context",
+        r#"
+//- /lib.rs crate:dep
+pub enum Outcome<T, E> { Ok(T), Err(E) }
+pub trait Context<T, E> {
+    fn context(self, context: &str) -> Outcome<T, ()>;
+}
+impl<T, E> Context<T, E> for Outcome<T, E> {
+    fn context(self, context: &str) -> Outcome<T, ()> { loop {} }
+}
+
+//- /main.rs crate:main deps:dep
+mod task {
+    pub struct Context;
+}
+use task::Context;
+
+fn main() {
+    let res: dep::Outcome<(), Context> = dep::Outcome::Ok(());
+    res.cont$0
+}
+"#,
+        r#"
+mod task {
+    pub struct Context;
+}
+use dep::Context as _;
+use task::Context;
+
+fn main() {
+    let res: dep::Outcome<(), Context> = dep::Outcome::Ok(());
+    res.context(${1:context})$0
+}
+"#,
+    );
+}
+
 #[test]
 fn zero_input_deprecated_assoc_item_completion() {
     check(
@@ -18,17 +18,18 @@ fn target_type_or_trait_in_impl_block() {
 impl Tra$0
 "#,
         expect![[r#"
-            en Enum      Enum
-            ma makro!(…) macro_rules! makro
+            en Enum        Enum
+            ma makro!(…)   macro_rules! makro
             md module
-            st Record    Record
-            st Tuple     Tuple
-            st Unit      Unit
+            st Record      Record
+            st Tuple       Tuple
+            st Unit        Unit
             tt Trait
-            un Union     Union
-            bt u32       u32
+            un Union       Union
+            bt u32         u32
             kw crate::
             kw self::
+            sn Trait for …
         "#]],
     )
 }
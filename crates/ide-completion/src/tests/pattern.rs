@@ -375,10 +375,11 @@ fn main() {
 }
 "#,
         expect![[r#"
-            fd cool  u32
-            fd stuff u32
+            fd cool        u32
+            fd stuff       u32
             kw mut
             kw ref
+            sn cool, stuff
         "#]],
     )
 }
@@ -46,6 +46,42 @@ fn foo(s: Struct) {
     );
 }
 
+#[test]
+fn record_pattern_remaining_fields() {
+    check(
+        r#"
+struct Struct { foo: u32, bar: u32, r#type: u32 }
+
+fn foo(s: Struct) {
+    let Struct { foo, $0 } = s;
+}
+"#,
+        expect![[r#"
+            fd bar         u32
+            fd type        u32
+            kw mut
+            kw ref
+            sn bar, r#type
+        "#]],
+    );
+    check(
+        r#"
+struct Struct { foo: u32, bar: u32, baz: u32 }
+
+fn foo(s: Struct) {
+    let Struct { $0, .. } = s;
+}
+"#,
+        expect![[r#"
+            fd bar u32
+            fd baz u32
+            fd foo u32
+            kw mut
+            kw ref
+        "#]],
+    );
+}
+
 #[test]
 fn record_pattern_field_enum() {
     check(
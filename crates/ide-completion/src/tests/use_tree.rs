@@ -1,7 +1,7 @@
 //! Completion tests for use trees.
 use expect_test::{expect, Expect};
 
-use crate::tests::completion_list;
+use crate::tests::{check_edit, completion_list};
 
 fn check(ra_fixture: &str, expect: Expect) {
     let actual = completion_list(ra_fixture);
@@ -456,3 +456,54 @@ marco_rules! m { () => {} }
         "#]],
     );
 }
+
+#[test]
+fn use_tree_unlinked_dependency() {
+    check(
+        r#"
+//- /lib.rs crate:main deps:other_crate,alloc extern-prelude:other_crate
+use $0
+//- /other_crate/lib.rs crate:other_crate
+//- /alloc/lib.rs crate:alloc
+"#,
+        expect![[r#"
+            md alloc
+            md other_crate
+            kw crate::
+            kw self::
+        "#]],
+    );
+}
+
+#[test]
+fn use_tree_unlinked_dependency_inserts_extern_crate() {
+    check_edit(
+        "alloc",
+        r#"
+//- /lib.rs crate:main deps:alloc extern-prelude:
+mod m {
+    use al$0
+}
+//- /alloc/lib.rs crate:alloc
+"#,
+        r#"
+mod m {
+    extern crate alloc;
+    use alloc
+}
+"#,
+    );
+}
+
+#[test]
+fn use_tree_no_path_in_unlinked_dependency() {
+    check(
+        r#"
+//- /lib.rs crate:main deps:alloc extern-prelude:
+use alloc::$0
+//- /alloc/lib.rs crate:alloc
+pub mod vec {}
+"#,
+        expect![[""]],
+    );
+}
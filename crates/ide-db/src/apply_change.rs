@@ -11,7 +11,7 @@ use profile::{memory_usage, Bytes};
 use rustc_hash::FxHashSet;
 use triomphe::Arc;
 
-use crate::{symbol_index::SymbolsDatabase, Change, RootDatabase};
+use crate::{symbol_index::SymbolsDatabase, tasks::TasksDatabase, Change, RootDatabase};
 
 impl RootDatabase {
     pub fn request_cancellation(&mut self) {
@@ -51,8 +51,9 @@ impl RootDatabase {
     // | VS Code | **rust-analyzer: Memory Usage (Clears Database)**
     // |===
     // image::https://user-images.githubusercontent.com/48062697/113065592-08559f00-91b1-11eb-8c96-64b88068ec02.gif[]
-    pub fn per_query_memory_usage(&mut self) -> Vec<(String, Bytes, usize)> {
-        let mut acc: Vec<(String, Bytes, usize)> = vec![];
+    /// Returns the query group, name, memory usage and number of entries of each query.
+    pub fn per_query_memory_usage(&mut self) -> Vec<(&'static str, String, Bytes, usize)> {
+        let mut acc: Vec<(&'static str, String, Bytes, usize)> = vec![];
 
         fn collect_query_count<'q, Q>(table: &QueryTable<'q, Q>) -> usize
         where
@@ -73,7 +74,7 @@ impl RootDatabase {
         }
 
         macro_rules! purge_each_query {
-            ($($q:path)*) => {$(
+            ($($group:ident: [$($q:path)*])*) => {$($(
                 let before = memory_usage().allocated;
                 let table = $q.in_db(self);
                 let count = collect_query_count(&table);
@@ -81,140 +82,154 @@ impl RootDatabase {
                 let after = memory_usage().allocated;
                 let q: $q = Default::default();
                 let name = format!("{:?}", q);
-                acc.push((name, before - after, count));
-            )*}
+                acc.push((stringify!($group), name, before - after, count));
+            )*)*}
         }
         purge_each_query![
-            // SymbolsDatabase
-            crate::symbol_index::ModuleSymbolsQuery
-            crate::symbol_index::LibrarySymbolsQuery
-            crate::symbol_index::LocalRootsQuery
-            crate::symbol_index::LibraryRootsQuery
-            // HirDatabase
-            hir::db::InferQueryQuery
-            hir::db::MirBodyQuery
-            hir::db::BorrowckQuery
-            hir::db::TyQuery
-            hir::db::ValueTyQuery
-            hir::db::ImplSelfTyQuery
-            hir::db::ConstParamTyQuery
-            hir::db::ConstEvalQuery
-            hir::db::ConstEvalDiscriminantQuery
-            hir::db::ImplTraitQuery
-            hir::db::FieldTypesQuery
-            hir::db::LayoutOfAdtQuery
-            hir::db::TargetDataLayoutQuery
-            hir::db::CallableItemSignatureQuery
-            hir::db::ReturnTypeImplTraitsQuery
-            hir::db::GenericPredicatesForParamQuery
-            hir::db::GenericPredicatesQuery
-            hir::db::TraitEnvironmentQuery
-            hir::db::GenericDefaultsQuery
-            hir::db::InherentImplsInCrateQuery
-            hir::db::InherentImplsInBlockQuery
-            hir::db::IncoherentInherentImplCratesQuery
-            hir::db::TraitImplsInCrateQuery
-            hir::db::TraitImplsInBlockQuery
-            hir::db::TraitImplsInDepsQuery
-            hir::db::InternCallableDefQuery
-            hir::db::InternLifetimeParamIdQuery
-            hir::db::InternImplTraitIdQuery
-            hir::db::InternTypeOrConstParamIdQuery
-            hir::db::InternClosureQuery
-            hir::db::InternCoroutineQuery
-            hir::db::AssociatedTyDataQuery
-            hir::db::TraitDatumQuery
-            hir::db::AdtDatumQuery
-            hir::db::ImplDatumQuery
-            hir::db::FnDefDatumQuery
-            hir::db::FnDefVarianceQuery
-            hir::db::AdtVarianceQuery
-            hir::db::AssociatedTyValueQuery
-            hir::db::TraitSolveQueryQuery
-            hir::db::ProgramClausesForChalkEnvQuery
-
-            // DefDatabase
-            hir::db::FileItemTreeQuery
-            hir::db::CrateDefMapQueryQuery
-            hir::db::BlockDefMapQuery
-            hir::db::StructDataWithDiagnosticsQuery
-            hir::db::UnionDataWithDiagnosticsQuery
-            hir::db::EnumDataQuery
-            hir::db::EnumVariantDataWithDiagnosticsQuery
-            hir::db::ImplDataWithDiagnosticsQuery
-            hir::db::TraitDataWithDiagnosticsQuery
-            hir::db::TraitAliasDataQuery
-            hir::db::TypeAliasDataQuery
-            hir::db::FunctionDataQuery
-            hir::db::ConstDataQuery
-            hir::db::StaticDataQuery
-            hir::db::Macro2DataQuery
-            hir::db::MacroRulesDataQuery
-            hir::db::ProcMacroDataQuery
-            hir::db::BodyWithSourceMapQuery
-            hir::db::BodyQuery
-            hir::db::ExprScopesQuery
-            hir::db::GenericParamsQuery
-            hir::db::FieldsAttrsQuery
-            hir::db::FieldsAttrsSourceMapQuery
-            hir::db::AttrsQuery
-            hir::db::CrateLangItemsQuery
-            hir::db::LangItemQuery
-            hir::db::ImportMapQuery
-            hir::db::FieldVisibilitiesQuery
-            hir::db::FunctionVisibilityQuery
-            hir::db::ConstVisibilityQuery
-            hir::db::CrateSupportsNoStdQuery
-            hir::db::BlockItemTreeQueryQuery
-            hir::db::ExternCrateDeclDataQuery
-            hir::db::InternAnonymousConstQuery
-            hir::db::InternExternCrateQuery
-            hir::db::InternInTypeConstQuery
-            hir::db::InternUseQuery
-
-            // InternDatabase
-            hir::db::InternFunctionQuery
-            hir::db::InternStructQuery
-            hir::db::InternUnionQuery
-            hir::db::InternEnumQuery
-            hir::db::InternConstQuery
-            hir::db::InternStaticQuery
-            hir::db::InternTraitQuery
-            hir::db::InternTraitAliasQuery
-            hir::db::InternTypeAliasQuery
-            hir::db::InternImplQuery
-            hir::db::InternExternBlockQuery
-            hir::db::InternBlockQuery
-            hir::db::InternMacro2Query
-            hir::db::InternProcMacroQuery
-            hir::db::InternMacroRulesQuery
-
-            // ExpandDatabase
-            hir::db::AstIdMapQuery
-            hir::db::DeclMacroExpanderQuery
-            hir::db::ExpandProcMacroQuery
-            hir::db::InternMacroCallQuery
-            hir::db::InternSyntaxContextQuery
-            hir::db::MacroArgQuery
-            hir::db::ParseMacroExpansionQuery
-            hir::db::RealSpanMapQuery
-            hir::db::ProcMacrosQuery
-
-            // LineIndexDatabase
-            crate::LineIndexQuery
-
-            // SourceDatabase
-            base_db::ParseQuery
-            base_db::CrateGraphQuery
-
-            // SourceDatabaseExt
-            base_db::FileTextQuery
-            base_db::FileSourceRootQuery
-            base_db::SourceRootQuery
-            base_db::SourceRootCratesQuery
+            SymbolsDatabase: [
+                crate::symbol_index::ModuleSymbolsQuery
+                crate::symbol_index::LibrarySymbolsQuery
+                crate::symbol_index::LocalRootsQuery
+                crate::symbol_index::LibraryRootsQuery
+                crate::symbol_index::LazyBodyFilesQuery
+            ]
+
+            HirDatabase: [
+                hir::db::InferQueryQuery
+                hir::db::MirBodyQuery
+                hir::db::BorrowckQuery
+                hir::db::TyQuery
+                hir::db::ValueTyQuery
+                hir::db::ImplSelfTyQuery
+                hir::db::ConstParamTyQuery
+                hir::db::ConstEvalQuery
+                hir::db::ConstEvalDiscriminantQuery
+                hir::db::ImplTraitQuery
+                hir::db::FieldTypesQuery
+                hir::db::LayoutOfAdtQuery
+                hir::db::TargetDataLayoutQuery
+                hir::db::CallableItemSignatureQuery
+                hir::db::ReturnTypeImplTraitsQuery
+                hir::db::GenericPredicatesForParamQuery
+                hir::db::GenericPredicatesQuery
+                hir::db::TraitEnvironmentQuery
+                hir::db::GenericDefaultsQuery
+                hir::db::InherentImplsInCrateQuery
+                hir::db::InherentImplsInBlockQuery
+                hir::db::IncoherentInherentImplCratesQuery
+                hir::db::TraitImplsInCrateQuery
+                hir::db::TraitImplsInBlockQuery
+                hir::db::TraitImplsInDepsQuery
+                hir::db::InternCallableDefQuery
+                hir::db::InternLifetimeParamIdQuery
+                hir::db::InternImplTraitIdQuery
+                hir::db::InternTypeOrConstParamIdQuery
+                hir::db::InternClosureQuery
+                hir::db::InternCoroutineQuery
+                hir::db::AssociatedTyDataQuery
+                hir::db::TraitDatumQuery
+                hir::db::AdtDatumQuery
+                hir::db::ImplDatumQuery
+                hir::db::FnDefDatumQuery
+                hir::db::FnDefVarianceQuery
+                hir::db::AdtVarianceQuery
+                hir::db::AssociatedTyValueQuery
+                hir::db::TraitSolveQueryQuery
+                hir::db::ProgramClausesForChalkEnvQuery
+            ]
+
+            DefDatabase: [
+                hir::db::FileItemTreeQuery
+                hir::db::CrateDefMapQueryQuery
+                hir::db::BlockDefMapQuery
+                hir::db::StructDataWithDiagnosticsQuery
+                hir::db::UnionDataWithDiagnosticsQuery
+                hir::db::EnumDataQuery
+                hir::db::EnumVariantDataWithDiagnosticsQuery
+                hir::db::ImplDataWithDiagnosticsQuery
+                hir::db::TraitDataWithDiagnosticsQuery
+                hir::db::TraitAliasDataQuery
+                hir::db::TypeAliasDataQuery
+                hir::db::FunctionDataQuery
+                hir::db::ConstDataQuery
+                hir::db::StaticDataQuery
+                hir::db::Macro2DataQuery
+                hir::db::MacroRulesDataQuery
+                hir::db::ProcMacroDataQuery
+                hir::db::BodyWithSourceMapQuery
+                hir::db::BodyQuery
+                hir::db::ExprScopesQuery
+                hir::db::GenericParamsQuery
+                hir::db::FieldsAttrsQuery
+                hir::db::FieldsAttrsSourceMapQuery
+                hir::db::AttrsQuery
+                hir::db::CrateLangItemsQuery
+                hir::db::LangItemQuery
+                hir::db::ImportMapQuery
+                hir::db::FieldVisibilitiesQuery
+                hir::db::FunctionVisibilityQuery
+                hir::db::ConstVisibilityQuery
+                hir::db::CrateSupportsNoStdQuery
+                hir::db::BlockItemTreeQueryQuery
+                hir::db::ExternCrateDeclDataQuery
+                hir::db::InternAnonymousConstQuery
+                hir::db::InternExternCrateQuery
+                hir::db::InternInTypeConstQuery
+                hir::db::InternUseQuery
+            ]
+
+            InternDatabase: [
+                hir::db::InternFunctionQuery
+                hir::db::InternStructQuery
+                hir::db::InternUnionQuery
+                hir::db::InternEnumQuery
+                hir::db::InternConstQuery
+                hir::db::InternStaticQuery
+                hir::db::InternTraitQuery
+                hir::db::InternTraitAliasQuery
+                hir::db::InternTypeAliasQuery
+                hir::db::InternImplQuery
+                hir::db::InternExternBlockQuery
+                hir::db::InternBlockQuery
+                hir::db::InternMacro2Query
+                hir::db::InternProcMacroQuery
+                hir::db::InternMacroRulesQuery
+            ]
+
+            ExpandDatabase: [
+                hir::db::AstIdMapQuery
+                hir::db::DeclMacroExpanderQuery
+                hir::db::ExpandProcMacroQuery
+                hir::db::InternMacroCallQuery
+                hir::db::InternSyntaxContextQuery
+                hir::db::MacroArgQuery
+                hir::db::ParseMacroExpansionQuery
+                hir::db::RealSpanMapQuery
+                hir::db::ProcMacrosQuery
+            ]
+
+            LineIndexDatabase: [
+                crate::LineIndexQuery
+            ]
+
+            TasksDatabase: [
+                crate::tasks::FileTasksQuery
+            ]
+
+            SourceDatabase: [
+                base_db::ParseQuery
+                base_db::CrateGraphQuery
+            ]
+
+            SourceDatabaseExt: [
+                base_db::FileTextQuery
+                base_db::FileSourceRootQuery
+                base_db::SourceRootQuery
+                base_db::SourceRootCratesQuery
+            ]
         ];
 
-        acc.sort_by_key(|it| std::cmp::Reverse(it.1));
+        acc.sort_by_key(|it| std::cmp::Reverse(it.2));
         acc
     }
 }
@@ -43,6 +43,7 @@ pub enum AssistKind {
     RefactorExtract,
     RefactorInline,
     RefactorRewrite,
+    SourceOrganizeImports,
 }
 
 impl AssistKind {
@@ -72,6 +73,7 @@ impl AssistKind {
             AssistKind::RefactorExtract => "RefactorExtract",
             AssistKind::RefactorInline => "RefactorInline",
             AssistKind::RefactorRewrite => "RefactorRewrite",
+            AssistKind::SourceOrganizeImports => "SourceOrganizeImports",
         }
     }
 }
@@ -88,6 +90,7 @@ impl FromStr for AssistKind {
             "RefactorExtract" => Ok(AssistKind::RefactorExtract),
             "RefactorInline" => Ok(AssistKind::RefactorInline),
             "RefactorRewrite" => Ok(AssistKind::RefactorRewrite),
+            "SourceOrganizeImports" => Ok(AssistKind::SourceOrganizeImports),
             unknown => Err(format!("Unknown AssistKind: '{unknown}'")),
         }
     }
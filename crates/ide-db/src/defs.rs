@@ -9,10 +9,10 @@ use arrayvec::ArrayVec;
 use either::Either;
 use hir::{
     Adt, AsAssocItem, AsExternAssocItem, AssocItem, AttributeTemplate, BuiltinAttr, BuiltinType,
-    Const, Crate, DefWithBody, DeriveHelper, DocLinkDef, ExternAssocItem, ExternCrateDecl, Field,
-    Function, GenericParam, HasVisibility, HirDisplay, Impl, Label, Local, Macro, Module,
-    ModuleDef, Name, PathResolution, Semantics, Static, ToolModule, Trait, TraitAlias, TupleField,
-    TypeAlias, Variant, VariantDef, Visibility,
+    Const, Crate, DefWithBody, Deprecation, DeriveHelper, DocLinkDef, ExternAssocItem,
+    ExternCrateDecl, Field, Function, GenericParam, HasAttrs, HasVisibility, HirDisplay, Impl,
+    Label, Local, Macro, Module, ModuleDef, Name, PathResolution, Semantics, Static, ToolModule,
+    Trait, TraitAlias, TupleField, TypeAlias, Variant, VariantDef, Visibility,
 };
 use stdx::{format_to, impl_from};
 use syntax::{
@@ -213,6 +213,33 @@ impl Definition {
         })
     }
 
+    /// Returns the `#[deprecated]` attribute of this definition, or of the trait item or
+    /// trait that this assoc item implements.
+    pub fn deprecation(&self, db: &RootDatabase) -> Option<Deprecation> {
+        let attrs = match *self {
+            Definition::Macro(it) => it.attrs(db),
+            Definition::Field(it) => it.attrs(db),
+            Definition::Module(it) => it.attrs(db),
+            Definition::Function(it) => it.attrs(db),
+            Definition::Adt(it) => it.attrs(db),
+            Definition::Variant(it) => it.attrs(db),
+            Definition::Const(it) => it.attrs(db),
+            Definition::Static(it) => it.attrs(db),
+            Definition::Trait(it) => it.attrs(db),
+            Definition::TraitAlias(it) => it.attrs(db),
+            Definition::TypeAlias(it) => it.attrs(db),
+            Definition::ExternCrateDecl(it) => it.attrs(db),
+            _ => return None,
+        };
+        attrs.deprecation().or_else(|| {
+            let assoc = self.as_assoc_item(db)?;
+            let trait_ = assoc.container_or_implemented_trait(db)?;
+            let name = Some(assoc.name(db)?);
+            let item = trait_.items(db).into_iter().find(|it| it.name(db) == name)?;
+            item.attrs(db).deprecation().or_else(|| trait_.attrs(db).deprecation())
+        })
+    }
+
     pub fn label(&self, db: &RootDatabase) -> String {
         match *self {
             Definition::Macro(it) => it.display(db).to_string(),
@@ -315,6 +342,19 @@ impl IdentClass {
             .or_else(|| NameClass::classify_lifetime(sema, lifetime).map(IdentClass::NameClass))
     }
 
+    /// Best-effort resolution of a name reference inside `#[cfg]`-disabled code, which is never
+    /// lowered and therefore can't be classified. The enclosing path is resolved in the scope
+    /// surrounding the disabled code instead.
+    pub fn classify_inactive(
+        sema: &Semantics<'_, RootDatabase>,
+        node: &SyntaxNode,
+    ) -> Option<Definition> {
+        let name_ref = ast::NameRef::cast(node.clone())?;
+        sema.inactive_cfg(name_ref.syntax())?;
+        let path = name_ref.syntax().ancestors().find_map(ast::Path::cast)?;
+        sema.scope(path.syntax())?.speculative_resolve(&path).map(Definition::from)
+    }
+
     pub fn definitions(self) -> ArrayVec<Definition, 2> {
         let mut res = ArrayVec::new();
         match self {
@@ -1,7 +1,7 @@
 //! See [`FamousDefs`].
 
 use base_db::{CrateOrigin, LangCrateOrigin, SourceDatabase};
-use hir::{Crate, Enum, Function, Macro, Module, ScopeDef, Semantics, Trait};
+use hir::{Crate, Enum, Function, Macro, Module, ScopeDef, Semantics, Struct, Trait};
 
 use crate::RootDatabase;
 
@@ -42,6 +42,10 @@ impl FamousDefs<'_, '_> {
         self.find_lang_crate(LangCrateOrigin::ProcMacro)
     }
 
+    pub fn core_clone_Clone(&self) -> Option<Trait> {
+        self.find_trait("core:clone:Clone")
+    }
+
     pub fn core_cmp_Ord(&self) -> Option<Trait> {
         self.find_trait("core:cmp:Ord")
     }
@@ -58,6 +62,10 @@ impl FamousDefs<'_, '_> {
         self.find_trait("core:ops:Index")
     }
 
+    pub fn core_convert_TryFrom(&self) -> Option<Trait> {
+        self.find_trait("core:convert:TryFrom")
+    }
+
     pub fn core_option_Option(&self) -> Option<Enum> {
         self.find_enum("core:option:Option")
     }
@@ -70,6 +78,18 @@ impl FamousDefs<'_, '_> {
         self.find_trait("core:default:Default")
     }
 
+    pub fn core_fmt_Debug(&self) -> Option<Trait> {
+        self.find_trait("core:fmt:Debug")
+    }
+
+    pub fn core_fmt_Display(&self) -> Option<Trait> {
+        self.find_trait("core:fmt:Display")
+    }
+
+    pub fn core_future_Future(&self) -> Option<Trait> {
+        self.find_trait("core:future:Future")
+    }
+
     pub fn core_iter_Iterator(&self) -> Option<Trait> {
         self.find_trait("core:iter:traits:iterator:Iterator")
     }
@@ -82,6 +102,10 @@ impl FamousDefs<'_, '_> {
         self.find_module("core:iter")
     }
 
+    pub fn core_str_FromStr(&self) -> Option<Trait> {
+        self.find_trait("core:str:FromStr")
+    }
+
     pub fn core_ops_Deref(&self) -> Option<Trait> {
         self.find_trait("core:ops:Deref")
     }
@@ -122,6 +146,30 @@ impl FamousDefs<'_, '_> {
         self.find_macro("core:unimplemented")
     }
 
+    pub fn alloc_boxed_Box(&self) -> Option<Struct> {
+        self.find_struct("alloc:boxed:Box")
+    }
+
+    pub fn alloc_string_String(&self) -> Option<Struct> {
+        self.find_struct("alloc:string:String")
+    }
+
+    pub fn alloc_rc_Rc(&self) -> Option<Struct> {
+        self.find_struct("alloc:rc:Rc")
+    }
+
+    pub fn alloc_sync_Arc(&self) -> Option<Struct> {
+        self.find_struct("alloc:sync:Arc")
+    }
+
+    pub fn std_ffi_OsStr(&self) -> Option<Struct> {
+        self.find_struct("std:ffi:OsStr")
+    }
+
+    pub fn std_path_Path(&self) -> Option<Struct> {
+        self.find_struct("std:path:Path")
+    }
+
     pub fn builtin_crates(&self) -> impl Iterator<Item = Crate> {
         IntoIterator::into_iter([
             self.std(),
@@ -147,6 +195,13 @@ impl FamousDefs<'_, '_> {
         }
     }
 
+    fn find_struct(&self, path: &str) -> Option<Struct> {
+        match self.find_def(path)? {
+            hir::ScopeDef::ModuleDef(hir::ModuleDef::Adt(hir::Adt::Struct(it))) => Some(it),
+            _ => None,
+        }
+    }
+
     fn find_enum(&self, path: &str) -> Option<Enum> {
         match self.find_def(path)? {
             hir::ScopeDef::ModuleDef(hir::ModuleDef::Adt(hir::Adt::Enum(it))) => Some(it),
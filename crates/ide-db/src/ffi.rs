@@ -0,0 +1,47 @@
+//! Links the declarations of foreign functions in `extern` blocks to the `#[no_mangle]` and
+//! `#[export_name]` functions of the workspace that define them, and back.
+
+use base_db::CrateOrigin;
+use hir::{AssocItem, ModuleDef};
+use itertools::Itertools;
+
+use crate::RootDatabase;
+
+/// Returns the functions of the workspace on the other side of the FFI boundary of `func`: the
+/// definitions of a declaration in an `extern` block, or the declarations of an exported
+/// definition.
+pub fn linked_functions(db: &RootDatabase, func: hir::Function) -> Vec<hir::Function> {
+    let Some(symbol) = func.link_symbol(db) else { return Vec::new() };
+    let is_decl = func.is_extern_decl(db);
+
+    // The symbol index is keyed by item names, which `#[link_name]` and `#[export_name]` make
+    // differ from the linked symbol, so the functions are compared by their symbols instead.
+    hir::Crate::all(db)
+        .into_iter()
+        .filter(|krate| !matches!(krate.origin(db), CrateOrigin::Lang(_)))
+        .flat_map(|krate| krate.modules(db))
+        .flat_map(|module| module_functions(db, module))
+        .filter(|&it| {
+            it != func
+                && it.is_extern_decl(db) != is_decl
+                && it.link_symbol(db).as_ref() == Some(&symbol)
+        })
+        .unique()
+        .collect()
+}
+
+fn module_functions(db: &RootDatabase, module: hir::Module) -> Vec<hir::Function> {
+    let impl_items = module.impl_defs(db).into_iter().flat_map(|it| it.items(db));
+    module
+        .declarations(db)
+        .into_iter()
+        .filter_map(|it| match it {
+            ModuleDef::Function(it) => Some(it),
+            _ => None,
+        })
+        .chain(impl_items.filter_map(|it| match it {
+            AssocItem::Function(it) => Some(it),
+            _ => None,
+        }))
+        .collect()
+}
@@ -272,6 +272,9 @@ impl ImportAssets {
         let krate = self.module_with_candidate.krate();
         let scope_definitions = self.scope_definitions(sema);
         let mod_path = |item| {
+            if !is_importable(sema.db, krate, item) {
+                return None;
+            }
             get_mod_path(
                 sema.db,
                 item_for_path_search(sema.db, item)?,
@@ -318,6 +321,15 @@ impl ImportAssets {
     }
 }
 
+/// Items of other crates that are `#[doc(hidden)]` or unstable are not meant to be used directly,
+/// so they are never proposed as imports.
+fn is_importable(db: &RootDatabase, krate: Crate, item: ItemInNs) -> bool {
+    if item.krate(db) == Some(krate) {
+        return true;
+    }
+    item.attrs(db).map_or(true, |attrs| !attrs.has_doc_hidden() && !attrs.is_unstable())
+}
+
 fn path_applicable_imports(
     sema: &Semantics<'_, RootDatabase>,
     current_crate: Crate,
@@ -16,8 +16,8 @@ use syntax::{
 
 use crate::{
     imports::merge_imports::{
-        common_prefix, eq_attrs, eq_visibility, try_merge_imports, use_tree_cmp, MergeBehavior,
-        NormalizationStyle,
+        common_prefix, eq_attrs, eq_visibility, try_merge_imports, try_normalize_import,
+        use_tree_cmp, MergeBehavior, NormalizationStyle,
     },
     RootDatabase,
 };
@@ -195,24 +195,7 @@ fn insert_use_with_alias_option(
     alias: Option<ast::Rename>,
 ) {
     let _p = tracing::span!(tracing::Level::INFO, "insert_use").entered();
-    let mut mb = match cfg.granularity {
-        ImportGranularity::Crate => Some(MergeBehavior::Crate),
-        ImportGranularity::Module => Some(MergeBehavior::Module),
-        ImportGranularity::One => Some(MergeBehavior::One),
-        ImportGranularity::Item | ImportGranularity::Preserve => None,
-    };
-    if !cfg.enforce_granularity {
-        let file_granularity = guess_granularity_from_scope(scope);
-        mb = match file_granularity {
-            ImportGranularityGuess::Unknown => mb,
-            ImportGranularityGuess::Item => None,
-            ImportGranularityGuess::Module => Some(MergeBehavior::Module),
-            ImportGranularityGuess::ModuleOrItem => mb.and(Some(MergeBehavior::Module)),
-            ImportGranularityGuess::Crate => Some(MergeBehavior::Crate),
-            ImportGranularityGuess::CrateOrModule => mb.or(Some(MergeBehavior::Crate)),
-            ImportGranularityGuess::One => Some(MergeBehavior::One),
-        };
-    }
+    let mb = merge_behavior(scope, cfg);
 
     let mut use_tree = make::use_tree(path, None, alias, false);
     if mb == Some(MergeBehavior::One) && use_tree.path().is_some() {
@@ -239,6 +222,30 @@ fn insert_use_with_alias_option(
     insert_use_(scope, use_item, cfg.group);
 }
 
+/// The merge behavior to use for imports in `scope`, guessed from the existing imports unless the
+/// configured granularity is enforced.
+fn merge_behavior(scope: &ImportScope, cfg: &InsertUseConfig) -> Option<MergeBehavior> {
+    let mut mb = match cfg.granularity {
+        ImportGranularity::Crate => Some(MergeBehavior::Crate),
+        ImportGranularity::Module => Some(MergeBehavior::Module),
+        ImportGranularity::One => Some(MergeBehavior::One),
+        ImportGranularity::Item | ImportGranularity::Preserve => None,
+    };
+    if !cfg.enforce_granularity {
+        let file_granularity = guess_granularity_from_scope(scope);
+        mb = match file_granularity {
+            ImportGranularityGuess::Unknown => mb,
+            ImportGranularityGuess::Item => None,
+            ImportGranularityGuess::Module => Some(MergeBehavior::Module),
+            ImportGranularityGuess::ModuleOrItem => mb.and(Some(MergeBehavior::Module)),
+            ImportGranularityGuess::Crate => Some(MergeBehavior::Crate),
+            ImportGranularityGuess::CrateOrModule => mb.or(Some(MergeBehavior::Crate)),
+            ImportGranularityGuess::One => Some(MergeBehavior::One),
+        };
+    }
+    mb
+}
+
 pub fn ast_to_remove_for_path_in_use_stmt(path: &ast::Path) -> Option<Box<dyn Removable>> {
     // FIXME: improve this
     if path.parent_path().is_some() {
@@ -260,7 +267,104 @@ pub fn remove_path_if_in_use_stmt(path: &ast::Path) {
     }
 }
 
-#[derive(Eq, PartialEq, PartialOrd, Ord)]
+/// Reorganizes `uses`, a run of adjacent `use` items in `scope`, according to the configured
+/// granularity. The result is sorted and, if grouping is enabled, split into import groups.
+pub fn organize_imports(
+    scope: &ImportScope,
+    uses: &[ast::Use],
+    cfg: &InsertUseConfig,
+) -> Vec<Vec<ast::Use>> {
+    let _p = tracing::span!(tracing::Level::INFO, "organize_imports").entered();
+    let mb = merge_behavior(scope, cfg);
+
+    let mut items = Vec::new();
+    for use_ in uses {
+        // Attributes apply to the whole item, so those are left as written.
+        let flattened = match cfg.granularity {
+            ImportGranularity::Preserve => None,
+            _ if use_.attrs().next().is_some() => None,
+            _ => flatten_use(use_),
+        };
+        items.extend(flattened.unwrap_or_else(|| vec![use_.clone()]));
+    }
+
+    if let Some(mb) = mb {
+        let mut merged: Vec<ast::Use> = Vec::new();
+        for item in items {
+            let item = item.clone_subtree().clone_for_update();
+            if let (MergeBehavior::One, Some(tree)) = (mb, item.use_tree()) {
+                if tree.path().is_some() {
+                    tree.wrap_in_tree_list();
+                }
+            }
+            let merged_into = merged
+                .iter()
+                .enumerate()
+                .find_map(|(idx, it)| Some((idx, try_merge_imports(it, &item, mb)?)));
+            match merged_into {
+                Some((idx, it)) => merged[idx] = it,
+                None => merged.push(item),
+            }
+        }
+        items = merged
+            .into_iter()
+            .map(|it| try_normalize_import(&it, mb.into()).unwrap_or(it))
+            .collect();
+    }
+
+    let group_of =
+        |use_: &ast::Use| use_.use_tree().filter(|_| cfg.group).map(|it| ImportGroup::new(&it));
+    items.sort_by(|a, b| {
+        group_of(a).cmp(&group_of(b)).then_with(|| match (a.use_tree(), b.use_tree()) {
+            (Some(a), Some(b)) => use_tree_cmp(&a, &b),
+            _ => Ordering::Equal,
+        })
+    });
+    let mut groups: Vec<Vec<ast::Use>> = Vec::new();
+    for item in items {
+        match groups.last_mut() {
+            Some(group) if group_of(&group[0]) == group_of(&item) => group.push(item),
+            _ => groups.push(vec![item]),
+        }
+    }
+    groups
+}
+
+/// Splits `use_` into one `use` item per imported name.
+fn flatten_use(use_: &ast::Use) -> Option<Vec<ast::Use>> {
+    let mut trees = Vec::new();
+    flatten_use_tree(None, &use_.use_tree()?, &mut trees)?;
+    Some(trees.into_iter().map(|tree| make::use_(use_.visibility(), tree)).collect())
+}
+
+fn flatten_use_tree(
+    prefix: Option<ast::Path>,
+    tree: &ast::UseTree,
+    acc: &mut Vec<ast::UseTree>,
+) -> Option<()> {
+    let path = match (prefix.clone(), tree.path()) {
+        (Some(prefix), Some(path)) => Some(make::path_concat(prefix, path)),
+        (prefix, path) => prefix.or(path),
+    };
+    if let Some(list) = tree.use_tree_list() {
+        for tree in list.use_trees() {
+            flatten_use_tree(path.clone(), &tree, acc)?;
+        }
+        return Some(());
+    }
+    let is_self = tree.path().map_or(false, |it| {
+        it.qualifier().is_none() && it.segment().map_or(false, |it| it.self_token().is_some())
+    });
+    let tree = match prefix {
+        // `a::{self}` is written as `a`.
+        Some(prefix) if is_self => make::use_tree(prefix, None, tree.rename(), false),
+        _ => make::use_tree(path?, None, tree.rename(), tree.star_token().is_some()),
+    };
+    acc.push(tree);
+    Some(())
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, PartialOrd, Ord)]
 enum ImportGroup {
     // the order here defines the order of new group inserts
     Std,
@@ -0,0 +1,314 @@
+//! Finding the `use` trees whose imports are not used.
+
+use std::collections::hash_map::Entry;
+
+use hir::{
+    AsAssocItem, HirFileIdExt, InFile, InRealFile, Module, ModuleSource, PathResolution, Semantics,
+};
+use syntax::{
+    ast::{self, HasVisibility},
+    match_ast, AstNode, SmolStr, SyntaxKind, SyntaxNode, TextRange,
+};
+
+use crate::{
+    base_db::FileRange,
+    defs::Definition,
+    search::{FileReference, ReferenceCategory, SearchScope},
+    FxHashMap, FxHashSet, RootDatabase,
+};
+
+/// Returns the leaf use trees of the private `use`s in `file` whose imports are not used.
+///
+/// Unlike [`unused_use_trees`], this doesn't search for the usages of every import, but records
+/// which `use` trees name resolution goes through while resolving the paths of the file (and of
+/// the macro expansions in it). It errs on the side of not reporting anything: glob imports, and
+/// imports that modules in other files or glob imports could refer to, are never reported.
+pub fn unused_imports_in_file(
+    sema: &Semantics<'_, RootDatabase>,
+    file: &ast::SourceFile,
+) -> Vec<ast::UseTree> {
+    let db = sema.db;
+    let file_id = sema.hir_file_for(file.syntax());
+
+    let mut used_trees = FxHashSet::default();
+    let mut used_traits = FxHashSet::default();
+    // Names that may refer to an import without us being able to tell which one: idents in token
+    // trees (like derives and arguments of macros that failed to expand) and unresolved methods.
+    let mut names = FxHashSet::<SmolStr>::default();
+
+    let mut roots = vec![file.syntax().clone()];
+    while let Some(root) = roots.pop() {
+        for node in root.descendants() {
+            match_ast! {
+                match node {
+                    ast::Path(path) => {
+                        // The path of a leaf use tree is the import itself.
+                        let is_leaf_import = path
+                            .syntax()
+                            .parent()
+                            .and_then(ast::UseTree::cast)
+                            .map_or(false, |tree| tree.use_tree_list().is_none());
+                        if is_leaf_import {
+                            continue;
+                        }
+                        if let Some(scope) = sema.scope(path.syntax()) {
+                            used_trees.extend(
+                                scope
+                                    .imports_of_path(&path)
+                                    .into_iter()
+                                    .filter(|tree| tree.file_id == file_id)
+                                    .map(|tree| tree.value.syntax().text_range()),
+                            );
+                        }
+                        if let Some(PathResolution::Def(def)) = sema.resolve_path(&path) {
+                            used_traits.extend(
+                                def.as_assoc_item(db)
+                                    .and_then(|it| it.container_or_implemented_trait(db)),
+                            );
+                        }
+                    },
+                    ast::MethodCallExpr(call) => {
+                        match sema.resolve_method_call(&call) {
+                            Some(func) => used_traits.extend(
+                                func.as_assoc_item(db)
+                                    .and_then(|it| it.container_or_implemented_trait(db)),
+                            ),
+                            None => names.extend(call.name_ref().map(|it| it.text().into())),
+                        }
+                    },
+                    ast::MacroCall(call) => roots.extend(sema.expand(&call)),
+                    ast::TokenTree(tt) => names.extend(
+                        tt.syntax()
+                            .children_with_tokens()
+                            .filter_map(|it| it.into_token())
+                            .filter(|it| it.kind() == SyntaxKind::IDENT)
+                            .map(|it| SmolStr::from(it.text())),
+                    ),
+                    _ => (),
+                }
+            }
+        }
+    }
+
+    let mut hidden_users = FxHashMap::default();
+    file.syntax()
+        .descendants()
+        .filter_map(ast::Use::cast)
+        .filter(|it| it.visibility().is_none())
+        .flat_map(|it| it.syntax().descendants().filter_map(ast::UseTree::cast))
+        .filter(|tree| tree.use_tree_list().is_none() && tree.star_token().is_none())
+        .filter(|tree| !used_trees.contains(&tree.syntax().text_range()))
+        .filter(|tree| {
+            let Some(path) = tree.path() else { return false };
+            // Unresolved imports are reported by another diagnostic.
+            let Some(resolution) = sema.resolve_path(&path) else { return false };
+            let name = match tree.rename() {
+                Some(rename) => rename.name().map(|it| it.text().into()),
+                None => path.segment().and_then(|it| it.name_ref()).map(|it| it.text().into()),
+            };
+            if name.map_or(false, |it: SmolStr| names.contains(&it)) {
+                return false;
+            }
+            if let PathResolution::Def(hir::ModuleDef::Trait(trait_)) = resolution {
+                let item_used = trait_.items(db).into_iter().any(|item| {
+                    item.name(db).map_or(false, |it| names.contains(it.to_smol_str().as_str()))
+                });
+                if used_traits.contains(&trait_) || item_used {
+                    return false;
+                }
+            }
+            let Some(module) = sema.scope(tree.syntax()).map(|it| it.module()) else {
+                return false;
+            };
+            !*hidden_users.entry(module).or_insert_with(|| may_have_hidden_users(db, module))
+        })
+        .collect()
+}
+
+/// Whether a submodule of `module` could refer to its imports without us seeing it, because it is
+/// in another file or has glob imports.
+fn may_have_hidden_users(db: &RootDatabase, module: Module) -> bool {
+    module.children(db).any(|child| match child.definition_source(db).value {
+        ModuleSource::Module(it) => {
+            has_glob_imports(it.syntax()) || may_have_hidden_users(db, child)
+        }
+        ModuleSource::BlockExpr(it) => has_glob_imports(it.syntax()),
+        ModuleSource::SourceFile(_) => true,
+    })
+}
+
+fn has_glob_imports(node: &SyntaxNode) -> bool {
+    node.descendants().filter_map(ast::UseTree::cast).any(|it| it.star_token().is_some())
+}
+
+/// Returns the leaf use trees of `uses` that import something which isn't used in the module (but
+/// not its submodules) the `use` is in.
+pub fn unused_use_trees(
+    sema: &Semantics<'_, RootDatabase>,
+    uses: impl IntoIterator<Item = ast::Use>,
+) -> Vec<ast::UseTree> {
+    // Maps use nodes to the scope that we should search through to find
+    let mut search_scopes = FxHashMap::<Module, Vec<SearchScope>>::default();
+
+    uses.into_iter()
+        .flat_map(|u| u.syntax().descendants().filter_map(ast::UseTree::cast))
+        .filter(|u| u.use_tree_list().is_none())
+        .filter_map(|u| {
+            // Find any uses trees that are unused
+
+            let use_module = sema.scope(u.syntax()).map(|s| s.module())?;
+            let scope = match search_scopes.entry(use_module) {
+                Entry::Occupied(o) => o.into_mut(),
+                Entry::Vacant(v) => v.insert(module_search_scope(sema.db, use_module)),
+            };
+
+            // Gets the path associated with this use tree. If there isn't one, then ignore this use tree.
+            let path = if let Some(path) = u.path() {
+                path
+            } else if u.star_token().is_some() {
+                // This case maps to the situation where the * token is braced.
+                // In this case, the parent use tree's path is the one we should use to resolve the glob.
+                match u.syntax().ancestors().skip(1).find_map(ast::UseTree::cast) {
+                    Some(parent_u) if parent_u.path().is_some() => parent_u.path()?,
+                    _ => return None,
+                }
+            } else {
+                return None;
+            };
+
+            // Get the actual definition associated with this use item.
+            let res = match sema.resolve_path(&path) {
+                Some(x) => x,
+                None => {
+                    return None;
+                }
+            };
+
+            let def = match res {
+                hir::PathResolution::Def(d) => Definition::from(d),
+                _ => return None,
+            };
+
+            if u.star_token().is_some() {
+                // Check if any of the children of this module are used
+                let def_mod = match def {
+                    Definition::Module(module) => module,
+                    _ => return None,
+                };
+
+                if !def_mod
+                    .scope(sema.db, Some(use_module))
+                    .iter()
+                    .filter_map(|(_, x)| match x {
+                        hir::ScopeDef::ModuleDef(d) => Some(Definition::from(*d)),
+                        _ => None,
+                    })
+                    .any(|d| used_once_in_scope(sema, d, scope))
+                {
+                    return Some(u);
+                }
+            } else if let Definition::Trait(ref t) = def {
+                // If the trait or any item is used.
+                if !std::iter::once(def)
+                    .chain(t.items(sema.db).into_iter().map(Definition::from))
+                    .any(|d| used_once_in_scope(sema, d, scope))
+                {
+                    return Some(u);
+                }
+            } else if !used_once_in_scope(sema, def, scope) {
+                return Some(u);
+            }
+
+            None
+        })
+        .collect()
+}
+
+fn used_once_in_scope(
+    sema: &Semantics<'_, RootDatabase>,
+    def: Definition,
+    scopes: &Vec<SearchScope>,
+) -> bool {
+    let mut found = false;
+
+    for scope in scopes {
+        let mut search_non_import = |_, r: FileReference| {
+            // The import itself is a use; we must skip that.
+            if r.category != Some(ReferenceCategory::Import) {
+                found = true;
+                true
+            } else {
+                false
+            }
+        };
+        def.usages(sema).in_scope(scope).search(&mut search_non_import);
+        if found {
+            break;
+        }
+    }
+
+    found
+}
+
+/// Build a search scope spanning the given module but none of its submodules.
+fn module_search_scope(db: &RootDatabase, module: hir::Module) -> Vec<SearchScope> {
+    let (file_id, range) = {
+        let InFile { file_id, value } = module.definition_source(db);
+        if let Some(InRealFile { file_id, value: call_source }) = file_id.original_call_node(db) {
+            (file_id, Some(call_source.text_range()))
+        } else {
+            (
+                file_id.original_file(db),
+                match value {
+                    ModuleSource::SourceFile(_) => None,
+                    ModuleSource::Module(it) => Some(it.syntax().text_range()),
+                    ModuleSource::BlockExpr(it) => Some(it.syntax().text_range()),
+                },
+            )
+        }
+    };
+
+    fn split_at_subrange(first: TextRange, second: TextRange) -> (TextRange, Option<TextRange>) {
+        let intersect = first.intersect(second);
+        if let Some(intersect) = intersect {
+            let start_range = TextRange::new(first.start(), intersect.start());
+
+            if intersect.end() < first.end() {
+                (start_range, Some(TextRange::new(intersect.end(), first.end())))
+            } else {
+                (start_range, None)
+            }
+        } else {
+            (first, None)
+        }
+    }
+
+    let mut scopes = Vec::new();
+    if let Some(range) = range {
+        let mut ranges = vec![range];
+
+        for child in module.children(db) {
+            let rng = match child.definition_source(db).value {
+                ModuleSource::SourceFile(_) => continue,
+                ModuleSource::Module(it) => it.syntax().text_range(),
+                ModuleSource::BlockExpr(_) => continue,
+            };
+            let mut new_ranges = Vec::new();
+            for old_range in ranges.iter_mut() {
+                let split = split_at_subrange(*old_range, rng);
+                *old_range = split.0;
+                new_ranges.extend(split.1);
+            }
+
+            ranges.append(&mut new_ranges);
+        }
+
+        for range in ranges {
+            scopes.push(SearchScope::file_range(FileRange { file_id, range }));
+        }
+    } else {
+        scopes.push(SearchScope::single_file(file_id));
+    }
+
+    scopes
+}
@@ -10,16 +10,20 @@ pub mod active_parameter;
 pub mod assists;
 pub mod defs;
 pub mod documentation;
+pub mod env_vars;
 pub mod famous_defs;
+pub mod ffi;
 pub mod helpers;
 pub mod items_locator;
 pub mod label;
+pub mod match_arms;
 pub mod path_transform;
 pub mod rename;
 pub mod rust_doc;
 pub mod search;
 pub mod source_change;
 pub mod symbol_index;
+pub mod tasks;
 pub mod traits;
 pub mod ty_filter;
 pub mod use_trivial_constructor;
@@ -28,6 +32,7 @@ pub mod imports {
     pub mod import_assets;
     pub mod insert_use;
     pub mod merge_imports;
+    pub mod unused;
 }
 
 pub mod generated {
@@ -38,6 +43,7 @@ pub mod syntax_helpers {
     pub mod format_string;
     pub mod format_string_exprs;
     pub mod insert_whitespace_into_node;
+    pub mod macro_metavars;
     pub mod node_ext;
 
     pub use parser::LexedStr;
@@ -74,7 +80,8 @@ pub type FxIndexMap<K, V> =
     hir::db::HirDatabaseStorage,
     hir::db::InternDatabaseStorage,
     LineIndexDatabaseStorage,
-    symbol_index::SymbolsDatabaseStorage
+    symbol_index::SymbolsDatabaseStorage,
+    tasks::TasksDatabaseStorage
 )]
 pub struct RootDatabase {
     // We use `ManuallyDrop` here because every codegen unit that contains a
@@ -144,6 +151,7 @@ impl RootDatabase {
         db.set_proc_macros_with_durability(Default::default(), Durability::HIGH);
         db.set_local_roots_with_durability(Default::default(), Durability::HIGH);
         db.set_library_roots_with_durability(Default::default(), Durability::HIGH);
+        db.set_lazy_body_files_with_durability(Default::default(), Durability::MEDIUM);
         db.set_expand_proc_attr_macros_with_durability(false, Durability::HIGH);
         db.update_base_query_lru_capacities(lru_capacity);
         db.setup_syntax_context_root();
@@ -294,6 +302,7 @@ impl RootDatabase {
             symbol_index::LibrarySymbolsQuery
             // symbol_index::LocalRootsQuery
             // symbol_index::LibraryRootsQuery
+            // symbol_index::LazyBodyFilesQuery
 
             // LineIndexDatabase
             crate::LineIndexQuery
@@ -324,6 +333,26 @@ impl salsa::ParallelDatabase for RootDatabase {
     }
 }
 
+/// Need to wrap Snapshot to provide `Clone` impl for rayon's `map_with`
+pub struct Snap<DB>(DB);
+impl<DB: salsa::ParallelDatabase> Snap<salsa::Snapshot<DB>> {
+    pub fn new(db: &DB) -> Self {
+        Self(db.snapshot())
+    }
+}
+impl<DB: salsa::ParallelDatabase> Clone for Snap<salsa::Snapshot<DB>> {
+    fn clone(&self) -> Snap<salsa::Snapshot<DB>> {
+        Snap(self.0.snapshot())
+    }
+}
+impl<DB> std::ops::Deref for Snap<DB> {
+    type Target = DB;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
 #[salsa::query_group(LineIndexDatabaseStorage)]
 pub trait LineIndexDatabase: base_db::SourceDatabase {
     fn line_index(&self, file_id: FileId) -> Arc<LineIndex>;
@@ -0,0 +1,128 @@
+//! Building the arms of `match` expressions over enums and `bool`s.
+
+use std::iter;
+
+use hir::{Adt, Crate, HasAttrs, HasSource, ModuleDef};
+use syntax::ast::{self, make, HasName};
+
+use crate::{helpers::mod_path_to_ast, RootDatabase};
+
+/// An enum or `bool`, which can be matched like an enum with two variants.
+#[derive(Eq, PartialEq, Clone, Copy)]
+pub enum ExtendedEnum {
+    Bool,
+    Enum(hir::Enum),
+}
+
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub enum ExtendedVariant {
+    True,
+    False,
+    Variant(hir::Variant),
+}
+
+impl ExtendedVariant {
+    pub fn should_be_hidden(self, db: &RootDatabase, krate: Crate) -> bool {
+        match self {
+            ExtendedVariant::Variant(var) => {
+                var.attrs(db).has_doc_hidden() && var.module(db).krate() != krate
+            }
+            _ => false,
+        }
+    }
+}
+
+pub fn lift_enum(e: hir::Enum) -> ExtendedEnum {
+    ExtendedEnum::Enum(e)
+}
+
+impl ExtendedEnum {
+    /// Finds the enum (or `bool`) behind any number of references of `ty`.
+    pub fn from_ty(db: &RootDatabase, ty: &hir::Type) -> Option<ExtendedEnum> {
+        ty.autoderef(db).find_map(|ty| match ty.as_adt() {
+            Some(Adt::Enum(e)) => Some(ExtendedEnum::Enum(e)),
+            _ => ty.is_bool().then_some(ExtendedEnum::Bool),
+        })
+    }
+
+    pub fn is_non_exhaustive(self, db: &RootDatabase, krate: Crate) -> bool {
+        match self {
+            ExtendedEnum::Enum(e) => {
+                e.attrs(db).by_key("non_exhaustive").exists() && e.module(db).krate() != krate
+            }
+            _ => false,
+        }
+    }
+
+    pub fn variants(self, db: &RootDatabase) -> Vec<ExtendedVariant> {
+        match self {
+            ExtendedEnum::Enum(e) => {
+                e.variants(db).into_iter().map(ExtendedVariant::Variant).collect::<Vec<_>>()
+            }
+            ExtendedEnum::Bool => {
+                Vec::<ExtendedVariant>::from([ExtendedVariant::True, ExtendedVariant::False])
+            }
+        }
+    }
+}
+
+/// Builds the patterns of all variants of the enum (or `bool`) behind `ty`, for the arms of a new
+/// `match`. Also returns whether a wildcard arm is needed for variants that can't be named.
+pub fn enum_variant_pats(
+    db: &RootDatabase,
+    module: hir::Module,
+    ty: &hir::Type,
+    prefer_no_std: bool,
+    prefer_prelude: bool,
+) -> Option<(Vec<ast::Pat>, bool)> {
+    let enum_def = ExtendedEnum::from_ty(db, ty)?;
+    let variants = enum_def.variants(db);
+    let needs_wildcard = enum_def.is_non_exhaustive(db, module.krate())
+        || variants.iter().any(|variant| variant.should_be_hidden(db, module.krate()));
+    let pats = variants
+        .into_iter()
+        .filter(|variant| !variant.should_be_hidden(db, module.krate()))
+        .map(|variant| build_pat(db, module, variant, prefer_no_std, prefer_prelude))
+        .collect::<Option<Vec<_>>>()?;
+    Some((pats, needs_wildcard))
+}
+
+pub fn build_pat(
+    db: &RootDatabase,
+    module: hir::Module,
+    var: ExtendedVariant,
+    prefer_no_std: bool,
+    prefer_prelude: bool,
+) -> Option<ast::Pat> {
+    match var {
+        ExtendedVariant::Variant(var) => {
+            let path = mod_path_to_ast(&module.find_use_path(
+                db,
+                ModuleDef::from(var),
+                prefer_no_std,
+                prefer_prelude,
+            )?);
+
+            // FIXME: use HIR for this; it doesn't currently expose struct vs. tuple vs. unit variants though
+            Some(match var.source(db)?.value.kind() {
+                ast::StructKind::Tuple(field_list) => {
+                    let pats =
+                        iter::repeat(make::wildcard_pat().into()).take(field_list.fields().count());
+                    make::tuple_struct_pat(path, pats).into()
+                }
+                ast::StructKind::Record(field_list) => {
+                    let pats = field_list.fields().map(|f| {
+                        make::ext::simple_ident_pat(
+                            f.name().expect("Record field must have a name"),
+                        )
+                        .into()
+                    });
+                    make::record_pat(path, pats).into()
+                }
+                ast::StructKind::Unit => make::path_pat(path),
+            })
+        }
+        ExtendedVariant::True => Some(ast::Pat::from(make::literal_pat("true"))),
+        ExtendedVariant::False => Some(ast::Pat::from(make::literal_pat("false"))),
+    }
+}
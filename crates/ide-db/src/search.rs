@@ -9,21 +9,55 @@ use std::mem;
 use base_db::{salsa::Database, FileId, FileRange, SourceDatabase, SourceDatabaseExt};
 use hir::{
     AsAssocItem, DefWithBody, DescendPreference, HasAttrs, HasSource, HirFileIdExt, InFile,
-    InRealFile, ModuleSource, PathResolution, Semantics, Visibility,
+    InRealFile, LangItem, ModuleSource, PathResolution, Semantics, Visibility,
 };
 use memchr::memmem::Finder;
 use nohash_hasher::IntMap;
 use once_cell::unsync::Lazy;
 use parser::SyntaxKind;
-use syntax::{ast, match_ast, AstNode, AstToken, SyntaxElement, TextRange, TextSize};
+use syntax::{
+    ast, match_ast, AstNode, AstToken, SyntaxElement, SyntaxNode, SyntaxToken, TextRange, TextSize,
+};
 use triomphe::Arc;
 
 use crate::{
-    defs::{Definition, NameClass, NameRefClass},
+    defs::{Definition, IdentClass, NameClass, NameRefClass, OperatorClass},
     traits::{as_trait_assoc_def, convert_to_def_in_trait},
     RootDatabase,
 };
 
+/// The traits whose methods are called by operator expressions.
+const OPERATOR_TRAITS: &[LangItem] = &[
+    LangItem::Add,
+    LangItem::Sub,
+    LangItem::Mul,
+    LangItem::Div,
+    LangItem::Rem,
+    LangItem::Neg,
+    LangItem::Not,
+    LangItem::BitXor,
+    LangItem::BitAnd,
+    LangItem::BitOr,
+    LangItem::Shl,
+    LangItem::Shr,
+    LangItem::AddAssign,
+    LangItem::SubAssign,
+    LangItem::MulAssign,
+    LangItem::DivAssign,
+    LangItem::RemAssign,
+    LangItem::BitXorAssign,
+    LangItem::BitAndAssign,
+    LangItem::BitOrAssign,
+    LangItem::ShlAssign,
+    LangItem::ShrAssign,
+    LangItem::Index,
+    LangItem::IndexMut,
+    LangItem::Deref,
+    LangItem::DerefMut,
+    LangItem::PartialEq,
+    LangItem::PartialOrd,
+];
+
 #[derive(Debug, Default, Clone)]
 pub struct UsageSearchResult {
     pub references: IntMap<FileId, Vec<FileReference>>,
@@ -73,6 +107,8 @@ pub enum FileReferenceNode {
     NameRef(ast::NameRef),
     Lifetime(ast::Lifetime),
     FormatStringEntry(ast::String, TextRange),
+    /// The operator token of an expression desugaring to a call of the definition.
+    Operator(SyntaxToken),
 }
 
 impl FileReferenceNode {
@@ -82,6 +118,7 @@ impl FileReferenceNode {
             FileReferenceNode::NameRef(it) => it.syntax().text_range(),
             FileReferenceNode::Lifetime(it) => it.syntax().text_range(),
             FileReferenceNode::FormatStringEntry(_, range) => *range,
+            FileReferenceNode::Operator(it) => it.text_range(),
         }
     }
     pub fn syntax(&self) -> SyntaxElement {
@@ -90,6 +127,7 @@ impl FileReferenceNode {
             FileReferenceNode::NameRef(it) => it.syntax().clone().into(),
             FileReferenceNode::Lifetime(it) => it.syntax().clone().into(),
             FileReferenceNode::FormatStringEntry(it, _) => it.syntax().clone().into(),
+            FileReferenceNode::Operator(it) => it.clone().into(),
         }
     }
     pub fn into_name_like(self) -> Option<ast::NameLike> {
@@ -97,7 +135,7 @@ impl FileReferenceNode {
             FileReferenceNode::Name(it) => Some(ast::NameLike::Name(it)),
             FileReferenceNode::NameRef(it) => Some(ast::NameLike::NameRef(it)),
             FileReferenceNode::Lifetime(it) => Some(ast::NameLike::Lifetime(it)),
-            FileReferenceNode::FormatStringEntry(_, _) => None,
+            FileReferenceNode::FormatStringEntry(_, _) | FileReferenceNode::Operator(_) => None,
         }
     }
     pub fn as_name_ref(&self) -> Option<&ast::NameRef> {
@@ -120,6 +158,7 @@ impl FileReferenceNode {
             FileReferenceNode::FormatStringEntry(it, range) => {
                 syntax::TokenText::borrowed(&it.text()[*range - it.syntax().text_range().start()])
             }
+            FileReferenceNode::Operator(it) => syntax::TokenText::borrowed(it.text()),
         }
     }
 }
@@ -166,7 +205,7 @@ impl SearchScope {
     }
 
     /// Build a search scope spanning all the reverse dependencies of the given crate.
-    fn reverse_dependencies(db: &RootDatabase, of: hir::Crate) -> SearchScope {
+    pub fn reverse_dependencies(db: &RootDatabase, of: hir::Crate) -> SearchScope {
         let mut entries = IntMap::default();
         for rev_dep in of.transitive_reverse_dependencies(db) {
             let root_file = rev_dep.root_file(db);
@@ -386,6 +425,7 @@ impl Definition {
             scope: None,
             include_self_kw_refs: None,
             search_self_mod: false,
+            include_operator_refs: false,
         }
     }
 }
@@ -401,6 +441,8 @@ pub struct FindUsages<'a> {
     include_self_kw_refs: Option<hir::Type>,
     /// whether to search for the `self` module
     search_self_mod: bool,
+    /// whether to search for operator expressions desugaring to the definition
+    include_operator_refs: bool,
 }
 
 impl<'a> FindUsages<'a> {
@@ -411,6 +453,14 @@ impl<'a> FindUsages<'a> {
         self
     }
 
+    /// Enable searching for operator expressions (`a + b`, `a[i]`, `*a`, ...) that desugar
+    /// to a call of the definition, should it be an operator trait method or an implementation of
+    /// one.
+    pub fn include_operator_refs(mut self) -> Self {
+        self.include_operator_refs = true;
+        self
+    }
+
     /// Limit the search to a given [`SearchScope`].
     pub fn in_scope(self, scope: &'a SearchScope) -> Self {
         self.set_scope(Some(scope))
@@ -575,6 +625,22 @@ impl<'a> FindUsages<'a> {
             }
         }
 
+        // Search for operator expressions resolving to our method
+        if self.include_operator_refs && self.is_operator_trait_method() {
+            for (_, file_id, search_range) in scope_files(sema, &search_scope) {
+                self.sema.db.unwind_if_cancelled();
+                // FIXME: operators inside of macro calls are not found
+                let tree = sema.parse(file_id).syntax().clone();
+                for node in
+                    tree.descendants().filter(|it| search_range.contains_range(it.text_range()))
+                {
+                    if self.found_operator(file_id, &node, sink) {
+                        return;
+                    }
+                }
+            }
+        }
+
         // Search for `super` and `crate` resolving to our module
         if let Definition::Module(module) = self.def {
             let scope =
@@ -653,6 +719,51 @@ impl<'a> FindUsages<'a> {
         }
     }
 
+    fn is_operator_trait_method(&self) -> bool {
+        let Definition::Function(func) = self.def else { return false };
+        func.as_assoc_item(self.sema.db)
+            .and_then(|assoc| assoc.container_or_implemented_trait(self.sema.db))
+            .and_then(|trait_| trait_.attrs(self.sema.db).lang_item())
+            .map_or(false, |lang| OPERATOR_TRAITS.contains(&lang))
+    }
+
+    fn found_operator(
+        &self,
+        file_id: FileId,
+        node: &SyntaxNode,
+        sink: &mut dyn FnMut(FileId, FileReference) -> bool,
+    ) -> bool {
+        let token = match_ast! {
+            match node {
+                ast::BinExpr(it) => it.op_token(),
+                ast::PrefixExpr(it) => it.op_token(),
+                ast::IndexExpr(it) => it.l_brack_token(),
+                _ => return false,
+            }
+        };
+        let (Some(token), Some(IdentClass::Operator(op))) =
+            (token, IdentClass::classify_node(self.sema, node))
+        else {
+            return false;
+        };
+        let def = Definition::Function(match op {
+            OperatorClass::Prefix(it) | OperatorClass::Index(it) | OperatorClass::Bin(it) => it,
+            OperatorClass::Await(_) | OperatorClass::Try(_) => return false,
+        });
+        let is_ours = self.def == def
+            || matches!(self.assoc_item_container, Some(hir::AssocItemContainer::Trait(_)))
+                && convert_to_def_in_trait(self.sema.db, def) == self.def;
+        if !is_ours {
+            return false;
+        }
+        let reference = FileReference {
+            range: token.text_range(),
+            name: FileReferenceNode::Operator(token),
+            category: None,
+        };
+        sink(file_id, reference)
+    }
+
     fn found_self_ty_name_ref(
         &self,
         self_ty: &hir::Type,
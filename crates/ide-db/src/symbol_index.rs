@@ -27,22 +27,19 @@ use std::{
     mem,
 };
 
-use base_db::{
-    salsa::{self, ParallelDatabase},
-    SourceDatabaseExt, SourceRootId, Upcast,
-};
+use base_db::{salsa, FileId, SourceDatabaseExt, SourceRootId, Upcast};
 use fst::{raw::IndexedValue, Automaton, Streamer};
 use hir::{
     db::HirDatabase,
     import_map::{AssocSearchMode, SearchMode},
     symbols::{FileSymbol, SymbolCollector},
-    Crate, Module,
+    Crate, HasVisibility, Module, Visibility,
 };
 use rayon::prelude::*;
 use rustc_hash::FxHashSet;
 use triomphe::Arc;
 
-use crate::RootDatabase;
+use crate::{RootDatabase, Snap};
 
 #[derive(Debug, Clone)]
 pub struct Query {
@@ -53,6 +50,7 @@ pub struct Query {
     case_sensitive: bool,
     only_types: bool,
     libs: bool,
+    exclude_hidden: bool,
 }
 
 impl Query {
@@ -63,6 +61,7 @@ impl Query {
             lowercased,
             only_types: false,
             libs: false,
+            exclude_hidden: false,
             mode: SearchMode::Fuzzy,
             assoc_mode: AssocSearchMode::Include,
             case_sensitive: false,
@@ -77,6 +76,11 @@ impl Query {
         self.libs = true;
     }
 
+    /// Skips `#[doc(hidden)]` and non-public items of dependencies.
+    pub fn exclude_hidden(&mut self) {
+        self.exclude_hidden = true;
+    }
+
     pub fn fuzzy(&mut self) {
         self.mode = SearchMode::Fuzzy;
     }
@@ -97,6 +101,15 @@ impl Query {
     pub fn case_sensitive(&mut self) {
         self.case_sensitive = true;
     }
+
+    /// Whether a symbol that is not in a [`SymbolIndex`], like one that was persisted between
+    /// sessions, matches this query.
+    pub fn matches(&self, name: &str, is_type: bool, is_assoc: bool, is_hidden: bool) -> bool {
+        !(self.only_types && !is_type)
+            && !(self.libs && self.exclude_hidden && is_hidden)
+            && self.matches_assoc_mode(is_assoc)
+            && self.mode.check(&self.query, self.case_sensitive, name)
+    }
 }
 
 #[salsa::query_group(SymbolsDatabaseStorage)]
@@ -121,12 +134,19 @@ pub trait SymbolsDatabase: HirDatabase + SourceDatabaseExt + Upcast<dyn HirDatab
     /// Files in libraries are assumed to never change.
     #[salsa::input]
     fn library_roots(&self) -> Arc<FxHashSet<SourceRootId>>;
+
+    /// The set of huge generated files. Their bodies are only lowered once they are needed, so
+    /// the items declared inside those bodies are not indexed.
+    #[salsa::input]
+    fn lazy_body_files(&self) -> Arc<FxHashSet<FileId>>;
 }
 
 fn library_symbols(db: &dyn SymbolsDatabase, source_root_id: SourceRootId) -> Arc<SymbolIndex> {
     let _p = tracing::span!(tracing::Level::INFO, "library_symbols").entered();
 
+    let lazy_body_files = db.lazy_body_files();
     let mut symbol_collector = SymbolCollector::new(db.upcast());
+    symbol_collector.skip_bodies_in(&lazy_body_files);
 
     db.source_root_crates(source_root_id)
         .iter()
@@ -144,8 +164,11 @@ fn library_symbols(db: &dyn SymbolsDatabase, source_root_id: SourceRootId) -> Ar
 fn module_symbols(db: &dyn SymbolsDatabase, module: Module) -> Arc<SymbolIndex> {
     let _p = tracing::span!(tracing::Level::INFO, "module_symbols").entered();
 
-    let symbols = SymbolCollector::collect_module(db.upcast(), module);
-    Arc::new(SymbolIndex::new(symbols))
+    let lazy_body_files = db.lazy_body_files();
+    let mut symbol_collector = SymbolCollector::new(db.upcast());
+    symbol_collector.skip_bodies_in(&lazy_body_files);
+    symbol_collector.collect(module);
+    Arc::new(SymbolIndex::new(symbol_collector.finish()))
 }
 
 pub fn crate_symbols(db: &dyn SymbolsDatabase, krate: Crate) -> Box<[Arc<SymbolIndex>]> {
@@ -153,26 +176,6 @@ pub fn crate_symbols(db: &dyn SymbolsDatabase, krate: Crate) -> Box<[Arc<SymbolI
     krate.modules(db.upcast()).into_iter().map(|module| db.module_symbols(module)).collect()
 }
 
-/// Need to wrap Snapshot to provide `Clone` impl for `map_with`
-struct Snap<DB>(DB);
-impl<DB: ParallelDatabase> Snap<salsa::Snapshot<DB>> {
-    fn new(db: &DB) -> Self {
-        Self(db.snapshot())
-    }
-}
-impl<DB: ParallelDatabase> Clone for Snap<salsa::Snapshot<DB>> {
-    fn clone(&self) -> Snap<salsa::Snapshot<DB>> {
-        Snap(self.0.snapshot())
-    }
-}
-impl<DB> std::ops::Deref for Snap<DB> {
-    type Target = DB;
-
-    fn deref(&self) -> &Self::Target {
-        &self.0
-    }
-}
-
 // Feature: Workspace Symbol
 //
 // Uses fuzzy-search to find types, modules and functions by name across your
@@ -185,9 +188,12 @@ impl<DB> std::ops::Deref for Snap<DB> {
 // - `foo#` searches for `foo` function in the current workspace
 // - `Foo*` searches for `Foo` type among dependencies, including `stdlib`
 // - `foo#*` searches for `foo` function among dependencies
+// - `foo#*~` also finds `#[doc(hidden)]` and private `foo` functions of dependencies
 //
 // That is, `#` switches from "types" to all symbols, `*` switches from the current
-// workspace to dependencies.
+// workspace to dependencies. By default, `#[doc(hidden)]` and non-public items of
+// dependencies are skipped (see `rust-analyzer.workspace.symbol.search.excludeHidden`),
+// `~` includes them again.
 //
 // Note that filtering does not currently work in VSCode due to the editor never
 // sending the special symbols to the language server. Instead, you can configure
@@ -220,11 +226,34 @@ pub fn world_symbols(db: &RootDatabase, query: Query) -> Vec<FileSymbol> {
         indices.iter().flat_map(|indices| indices.iter().cloned()).collect()
     };
 
+    let exclude_hidden = query.libs && query.exclude_hidden;
     let mut res = vec![];
-    query.search(&indices, |f| res.push(f.clone()));
+    query.search(&indices, |f| {
+        if !(exclude_hidden && is_hidden(db, f)) {
+            res.push(f.clone());
+        }
+    });
     res
 }
 
+/// Whether the symbol is hidden from the dependents of its crate, see [`Query::exclude_hidden`].
+pub fn is_hidden(db: &RootDatabase, symbol: &FileSymbol) -> bool {
+    symbol.def.visibility(db) != Visibility::Public
+        || symbol.def.attrs(db).map_or(false, |attrs| attrs.has_doc_hidden())
+}
+
+/// Whether the symbol is found by [`Query::only_types`] queries.
+pub fn is_type(symbol: &FileSymbol) -> bool {
+    matches!(
+        symbol.def,
+        hir::ModuleDef::Adt(..)
+            | hir::ModuleDef::TypeAlias(..)
+            | hir::ModuleDef::BuiltinType(..)
+            | hir::ModuleDef::TraitAlias(..)
+            | hir::ModuleDef::Trait(..)
+    )
+}
+
 #[derive(Default)]
 pub struct SymbolIndex {
     symbols: Vec<FileSymbol>,
@@ -252,6 +281,10 @@ impl Hash for SymbolIndex {
 }
 
 impl SymbolIndex {
+    pub fn symbols(&self) -> &[FileSymbol] {
+        &self.symbols
+    }
+
     fn new(mut symbols: Vec<FileSymbol>) -> SymbolIndex {
         fn cmp(lhs: &FileSymbol, rhs: &FileSymbol) -> Ordering {
             let lhs_chars = lhs.name.chars().map(|c| c.to_ascii_lowercase());
@@ -362,15 +395,7 @@ impl Query {
                 let (start, end) = SymbolIndex::map_value_to_range(value);
 
                 for symbol in &symbol_index.symbols[start..end] {
-                    let non_type_for_type_only_query = self.only_types
-                        && !matches!(
-                            symbol.def,
-                            hir::ModuleDef::Adt(..)
-                                | hir::ModuleDef::TypeAlias(..)
-                                | hir::ModuleDef::BuiltinType(..)
-                                | hir::ModuleDef::TraitAlias(..)
-                                | hir::ModuleDef::Trait(..)
-                        );
+                    let non_type_for_type_only_query = self.only_types && !is_type(symbol);
                     if non_type_for_type_only_query || !self.matches_assoc_mode(symbol.is_assoc) {
                         continue;
                     }
@@ -515,4 +540,62 @@ struct Duplicate;
 
         expect_file!["./test_data/test_doc_alias.txt"].assert_debug_eq(&symbols);
     }
+
+    #[test]
+    fn test_exclude_hidden_dependency_items() {
+        let db = RootDatabase::with_files(
+            r#"
+//- /main.rs crate:main deps:dep new_source_root:local
+fn main() {}
+//- /dep/lib.rs crate:dep new_source_root:library
+pub struct Thing;
+#[doc(hidden)]
+pub struct ThingHidden;
+struct ThingPrivate;
+"#,
+        );
+
+        let search = |exclude_hidden| {
+            let mut query = Query::new("Thing".to_owned());
+            query.libs();
+            if exclude_hidden {
+                query.exclude_hidden();
+            }
+            let mut names: Vec<_> =
+                world_symbols(&db, query).into_iter().map(|it| it.name).collect();
+            names.sort();
+            names
+        };
+
+        assert_eq!(search(true), ["Thing"]);
+        assert_eq!(search(false), ["Thing", "ThingHidden", "ThingPrivate"]);
+    }
+
+    #[test]
+    fn test_lazy_body_files() {
+        let (mut db, files) = RootDatabase::with_many_files(
+            r#"
+//- /main.rs
+mod generated;
+fn main() {
+    struct InMain;
+}
+//- /generated.rs
+pub struct Generated;
+fn generated() {
+    struct InGenerated;
+}
+"#,
+        );
+        db.set_lazy_body_files(Arc::new(FxHashSet::from_iter([files[1]])));
+
+        let mut names: Vec<_> = Crate::from(db.test_crate())
+            .modules(&db)
+            .into_iter()
+            .flat_map(|module| db.module_symbols(module).symbols.clone())
+            .map(|it| it.name)
+            .collect();
+        names.sort();
+        assert_eq!(names, ["Generated", "InMain", "generated", "generated", "main"]);
+    }
 }
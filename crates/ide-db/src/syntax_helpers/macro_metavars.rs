@@ -0,0 +1,204 @@
+//! Syntactic helpers for the metavariables (`$name`) of `macro_rules!` definitions.
+//!
+//! Metavariables are declared in the matcher of a rule, `$name:fragment`, and used in the
+//! transcriber of the same rule, `$name`. Their scope is a single rule.
+use syntax::{ast, AstNode, Direction, SyntaxKind, SyntaxToken, T};
+
+/// A rule of a `macro_rules!` definition.
+#[derive(Debug, Clone)]
+pub struct MacroRule {
+    pub matcher: ast::TokenTree,
+    pub transcriber: Option<ast::TokenTree>,
+}
+
+impl MacroRule {
+    /// The metavariables declared in the matcher, as pairs of name and fragment specifier tokens.
+    pub fn metavar_decls(&self) -> Vec<(SyntaxToken, Option<SyntaxToken>)> {
+        metavars(&self.matcher)
+            .into_iter()
+            .map(|name| {
+                let fragment = non_trivia_sibling(&name, Direction::Next)
+                    .filter(|it| it.kind() == T![:])
+                    .and_then(|colon| non_trivia_sibling(&colon, Direction::Next))
+                    .filter(|it| is_ident_like(it.kind()));
+                (name, fragment)
+            })
+            .collect()
+    }
+
+    /// The metavariables used in the transcriber.
+    pub fn metavar_uses(&self) -> Vec<SyntaxToken> {
+        self.transcriber.iter().flat_map(metavars).collect()
+    }
+
+    /// All occurrences of the metavariable named `name` in this rule.
+    pub fn metavar_occurrences(&self, name: &str) -> Vec<SyntaxToken> {
+        metavars(&self.matcher)
+            .into_iter()
+            .chain(self.transcriber.iter().flat_map(metavars))
+            .filter(|it| it.text() == name)
+            .collect()
+    }
+}
+
+/// Returns the rules of a `macro_rules!` definition.
+pub fn macro_rules_rules(mac: &ast::MacroRules) -> Vec<MacroRule> {
+    let Some(body) = mac.token_tree() else { return Vec::new() };
+    let mut trees = body.token_trees_and_tokens().filter_map(|it| it.into_node());
+    let mut rules = Vec::new();
+    while let Some(matcher) = trees.next() {
+        // the transcriber follows the matcher after a `=>`, which token trees keep as `=` and `>`
+        let has_arrow = matcher
+            .syntax()
+            .siblings_with_tokens(Direction::Next)
+            .skip(1)
+            .filter_map(|it| it.into_token())
+            .find(|it| !it.kind().is_trivia())
+            .map_or(false, |it| matches!(it.kind(), T![=] | T![=>]));
+        let transcriber = if has_arrow { trees.next() } else { None };
+        rules.push(MacroRule { matcher, transcriber });
+    }
+    rules
+}
+
+/// If `token` is the name of a metavariable in a `macro_rules!` definition, returns the rule the
+/// metavariable belongs to.
+pub fn metavar_rule(token: &SyntaxToken) -> Option<MacroRule> {
+    if !is_metavar(token) {
+        return None;
+    }
+    let mac = token.parent_ancestors().find_map(ast::MacroRules::cast)?;
+    macro_rules_rules(&mac).into_iter().find(|rule| {
+        let range = token.text_range();
+        rule.matcher.syntax().text_range().contains_range(range)
+            || rule
+                .transcriber
+                .as_ref()
+                .map_or(false, |it| it.syntax().text_range().contains_range(range))
+    })
+}
+
+/// If `token` is a fragment specifier of a metavariable declaration in a `macro_rules!` matcher
+/// (the `expr` in `$e:expr`), returns it.
+pub fn fragment_specifier(token: &SyntaxToken) -> Option<&str> {
+    let colon = non_trivia_sibling(token, Direction::Prev).filter(|it| it.kind() == T![:])?;
+    let name = non_trivia_sibling(&colon, Direction::Prev)?;
+    let rule = metavar_rule(&name)?;
+    if !rule.matcher.syntax().text_range().contains_range(token.text_range()) {
+        return None;
+    }
+    FRAGMENT_SPECIFIERS.iter().any(|(it, ..)| *it == token.text()).then(|| token.text())
+}
+
+/// Returns a description of a fragment specifier and the tokens that may follow it.
+pub fn fragment_specifier_docs(fragment: &str) -> Option<(&'static str, &'static str)> {
+    FRAGMENT_SPECIFIERS
+        .iter()
+        .find(|(it, ..)| *it == fragment)
+        .map(|&(_, description, follow_set)| (description, follow_set))
+}
+
+const FRAGMENT_SPECIFIERS: &[(&str, &str, &str)] = &[
+    ("block", "a block expression: `{ ... }`", "anything"),
+    ("expr", "an expression", "`=>`, `,` or `;`"),
+    ("expr_2021", "an expression, excluding `_` and `const` blocks", "`=>`, `,` or `;`"),
+    ("ident", "an identifier or keyword, including raw identifiers", "anything"),
+    ("item", "an item, like a function, struct or module", "anything"),
+    ("lifetime", "a lifetime: `'a`", "anything"),
+    ("literal", "a literal, optionally prefixed with `-`", "anything"),
+    ("meta", "the contents of an attribute: `path = \"value\"`", "anything"),
+    (
+        "pat",
+        "a pattern, including top-level or-patterns",
+        "`=>`, `,`, `=`, `if` or `in`",
+    ),
+    ("pat_param", "a pattern without top-level or-patterns", "`=>`, `,`, `=`, `|`, `if` or `in`"),
+    (
+        "path",
+        "a type-style path: `std::vec::Vec<T>`",
+        "`=>`, `,`, `=`, `|`, `;`, `:`, `>`, `>>`, `[`, `{`, `as`, `where` or a `block` fragment",
+    ),
+    ("stmt", "a statement without its trailing semicolon", "`=>`, `,` or `;`"),
+    ("tt", "a single token tree: a token or a delimited group", "anything"),
+    (
+        "ty",
+        "a type",
+        "`=>`, `,`, `=`, `|`, `;`, `:`, `>`, `>>`, `[`, `{`, `as`, `where` or a `block` fragment",
+    ),
+    ("vis", "a possibly empty visibility qualifier: `pub(crate)`", "`,`, an identifier other than `priv`, the start of a type, or an `ident`, `ty` or `path` fragment"),
+];
+
+fn metavars(tt: &ast::TokenTree) -> Vec<SyntaxToken> {
+    tt.syntax()
+        .descendants_with_tokens()
+        .filter_map(|it| it.into_token())
+        .filter(is_metavar)
+        .collect()
+}
+
+/// Whether `token` is the name of a metavariable, that is an identifier directly preceded by `$`.
+fn is_metavar(token: &SyntaxToken) -> bool {
+    is_ident_like(token.kind())
+        && token.kind() != T![crate]
+        && token.prev_token().map_or(false, |it| it.kind() == T![$])
+}
+
+fn is_ident_like(kind: SyntaxKind) -> bool {
+    kind == SyntaxKind::IDENT || kind.is_keyword()
+}
+
+fn non_trivia_sibling(token: &SyntaxToken, direction: Direction) -> Option<SyntaxToken> {
+    let mut it = match direction {
+        Direction::Next => token.next_token(),
+        Direction::Prev => token.prev_token(),
+    };
+    while let Some(token) = it {
+        if !token.kind().is_trivia() {
+            return Some(token);
+        }
+        it = match direction {
+            Direction::Next => token.next_token(),
+            Direction::Prev => token.prev_token(),
+        };
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use syntax::{ast, AstNode, SourceFile};
+
+    use super::*;
+
+    fn parse_macro(text: &str) -> ast::MacroRules {
+        let file = SourceFile::parse(text).tree();
+        file.syntax().descendants().find_map(ast::MacroRules::cast).unwrap()
+    }
+
+    #[test]
+    fn collects_rules_and_metavars() {
+        let mac = parse_macro(
+            r#"
+macro_rules! m {
+    ($a:expr, $($b:ident),*) => { $a + $($b)+* };
+    () => { $crate::f($c) };
+}
+"#,
+        );
+        let rules = macro_rules_rules(&mac);
+        assert_eq!(rules.len(), 2);
+
+        let decls = rules[0]
+            .metavar_decls()
+            .into_iter()
+            .map(|(name, fragment)| format!("{name}:{}", fragment.unwrap()))
+            .collect::<Vec<_>>();
+        assert_eq!(decls, ["a:expr", "b:ident"]);
+        let uses = rules[0].metavar_uses().iter().map(|it| it.to_string()).collect::<Vec<_>>();
+        assert_eq!(uses, ["a", "b"]);
+
+        assert!(rules[1].metavar_decls().is_empty());
+        let uses = rules[1].metavar_uses().iter().map(|it| it.to_string()).collect::<Vec<_>>();
+        assert_eq!(uses, ["c"]);
+    }
+}
@@ -0,0 +1,134 @@
+//! Collects the `TODO`, `FIXME` and `XXX` comments of files, along with the items they belong
+//! to.
+
+use base_db::{salsa, FileId, SourceDatabase};
+use syntax::{
+    ast::{self, HasName},
+    AstNode, SourceFile, SyntaxKind, SyntaxToken, TextRange, TextSize,
+};
+use triomphe::Arc;
+
+#[salsa::query_group(TasksDatabaseStorage)]
+pub trait TasksDatabase: SourceDatabase {
+    /// The tasks of a file, computed once per revision of its syntax tree, as both the tasks
+    /// requests and hover need them.
+    fn file_tasks(&self, file_id: FileId) -> Arc<[Task]>;
+}
+
+fn file_tasks(db: &dyn TasksDatabase, file_id: FileId) -> Arc<[Task]> {
+    tasks_in_file(&db.parse(file_id).tree(), file_id).into()
+}
+
+/// A `TODO`, `FIXME` or `XXX` annotation in a comment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Task {
+    pub file_id: FileId,
+    /// The range of the annotation, from its marker to the end of its line.
+    pub range: TextRange,
+    pub kind: TaskKind,
+    /// The text following the marker.
+    pub text: String,
+    /// The innermost named item the comment belongs to.
+    pub owner: Option<TaskOwner>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TaskKind {
+    Todo,
+    Fixme,
+    Xxx,
+}
+
+impl TaskKind {
+    pub fn marker(self) -> &'static str {
+        match self {
+            TaskKind::Todo => "TODO",
+            TaskKind::Fixme => "FIXME",
+            TaskKind::Xxx => "XXX",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaskOwner {
+    /// The name of the item, like `foo` or `impl Trait for Ty`.
+    pub name: String,
+    pub range: TextRange,
+}
+
+fn tasks_in_file(file: &SourceFile, file_id: FileId) -> Vec<Task> {
+    let mut res = Vec::new();
+    for token in file.syntax().descendants_with_tokens().filter_map(|it| it.into_token()) {
+        if token.kind() != SyntaxKind::COMMENT {
+            continue;
+        }
+        let token_start = token.text_range().start();
+        let mut line_start = 0;
+        for line in token.text().split_inclusive('\n') {
+            if let Some((offset, kind)) = find_marker(line) {
+                let rest = line[offset + kind.marker().len()..].trim_end();
+                // Closing a block comment is not part of the task.
+                let rest = rest.strip_suffix("*/").unwrap_or(rest).trim_end();
+                let end = offset + kind.marker().len() + rest.len();
+                let text = rest
+                    .trim_start_matches(|c: char| c == ':' || c == '!' || c.is_whitespace())
+                    .to_owned();
+                let range = TextRange::new(
+                    token_start + TextSize::from((line_start + offset) as u32),
+                    token_start + TextSize::from((line_start + end) as u32),
+                );
+                res.push(Task { file_id, range, kind, text, owner: owner(&token) });
+            }
+            line_start += line.len();
+        }
+    }
+    res
+}
+
+/// Finds the first marker of `line` that is a whole word.
+fn find_marker(line: &str) -> Option<(usize, TaskKind)> {
+    [TaskKind::Todo, TaskKind::Fixme, TaskKind::Xxx]
+        .into_iter()
+        .filter_map(|kind| {
+            line.match_indices(kind.marker())
+                .find(|&(idx, marker)| {
+                    let before = line[..idx].chars().next_back();
+                    let after = line[idx + marker.len()..].chars().next();
+                    !before.map_or(false, is_word_char) && !after.map_or(false, is_word_char)
+                })
+                .map(|(idx, _)| (idx, kind))
+        })
+        .min_by_key(|&(idx, _)| idx)
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+fn owner(token: &SyntaxToken) -> Option<TaskOwner> {
+    token.parent_ancestors().filter_map(ast::Item::cast).find_map(|item| {
+        let name = match &item {
+            ast::Item::Impl(impl_) => {
+                let self_ty = impl_.self_ty()?;
+                match impl_.trait_() {
+                    Some(trait_) => format!("impl {trait_} for {self_ty}"),
+                    None => format!("impl {self_ty}"),
+                }
+            }
+            ast::Item::Const(it) => it.name()?.to_string(),
+            ast::Item::Enum(it) => it.name()?.to_string(),
+            ast::Item::Fn(it) => it.name()?.to_string(),
+            ast::Item::MacroRules(it) => it.name()?.to_string(),
+            ast::Item::MacroDef(it) => it.name()?.to_string(),
+            ast::Item::Module(it) => it.name()?.to_string(),
+            ast::Item::Static(it) => it.name()?.to_string(),
+            ast::Item::Struct(it) => it.name()?.to_string(),
+            ast::Item::Trait(it) => it.name()?.to_string(),
+            ast::Item::TraitAlias(it) => it.name()?.to_string(),
+            ast::Item::TypeAlias(it) => it.name()?.to_string(),
+            ast::Item::Union(it) => it.name()?.to_string(),
+            _ => return None,
+        };
+        Some(TaskOwner { name, range: item.syntax().text_range() })
+    })
+}
@@ -33,6 +33,7 @@ pub(crate) fn inactive_code(
         message,
         ctx.sema.diagnostics_display_range(d.node),
     )
+    .with_main_node(d.node)
     .with_unused(true);
     Some(res)
 }
@@ -70,14 +70,20 @@ pub(crate) fn unused_mut(ctx: &DiagnosticsContext<'_>, d: &hir::UnusedMut) -> Di
         )])
     })();
     let ast = d.local.primary_source(ctx.sema.db).syntax_ptr();
-    Diagnostic::new_with_syntax_node_ptr(
+    let from_macro = ast.file_id.is_macro();
+    let diagnostic = Diagnostic::new_with_syntax_node_ptr(
         ctx,
         DiagnosticCode::RustcLint("unused_mut"),
         "variable does not need to be mutable",
         ast,
     )
-    .experimental() // Not supporting `#[allow(unused_mut)]` in proc macros leads to false positive.
-    .with_fixes(fixes)
+    .with_fixes(fixes);
+    if from_macro {
+        // Not supporting `#[allow(unused_mut)]` in proc macros leads to false positive.
+        diagnostic.experimental()
+    } else {
+        diagnostic
+    }
 }
 
 pub(super) fn token(parent: &SyntaxNode, kind: SyntaxKind) -> Option<SyntaxToken> {
@@ -86,7 +92,13 @@ pub(super) fn token(parent: &SyntaxNode, kind: SyntaxKind) -> Option<SyntaxToken
 
 #[cfg(test)]
 mod tests {
-    use crate::tests::{check_diagnostics, check_diagnostics_with_disabled, check_fix};
+    use crate::{
+        tests::{
+            check_diagnostics, check_diagnostics_with_config, check_diagnostics_with_disabled,
+            check_fix,
+        },
+        DiagnosticsConfig,
+    };
 
     #[test]
     fn unused_mut_simple() {
@@ -1144,6 +1156,42 @@ fn f() {
         );
     }
 
+    #[test]
+    fn unused_mut_is_not_experimental() {
+        let mut config = DiagnosticsConfig::test_sample();
+        config.disable_experimental = true;
+        check_diagnostics_with_config(
+            config,
+            r#"
+fn f(_: i32) {}
+fn main() {
+    let mut x = 2;
+      //^^^^^ 💡 warn: variable does not need to be mutable
+    f(x);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn unused_mut_closure_params_and_captures() {
+        check_diagnostics(
+            r#"
+//- minicore: fn
+fn main() {
+    let mut x = 2;
+      //^^^^^ 💡 warn: variable does not need to be mutable
+    let closure = |mut y: i32| x + y;
+                 //^^^^^ 💡 warn: variable does not need to be mutable
+    _ = closure(1);
+    let mut z = 2;
+    let mut closure = || z += 1;
+    closure();
+}
+"#,
+        );
+    }
+
     #[test]
     fn allow_unused_mut_for_identifiers_starting_with_underline() {
         check_diagnostics(
@@ -30,6 +30,7 @@ pub(crate) fn remove_unnecessary_else(
         "remove unnecessary else block",
         display_range,
     )
+    .with_main_node(d.if_expr.map(|it| it.syntax_node_ptr()))
     .with_fixes(fixes(ctx, d))
 }
 
@@ -440,6 +441,23 @@ fn test() {
         );
     }
 
+    #[test]
+    fn no_diagnostic_if_allowed() {
+        check_diagnostics_with_disabled(
+            r#"
+#[allow(rust_analyzer::remove_unnecessary_else)]
+fn test() {
+    if foo {
+        return bar;
+    } else {
+        do_something_else();
+    }
+}
+"#,
+            &["needless_return", "E0425"],
+        );
+    }
+
     #[test]
     fn no_diagnostic_if_no_divergence() {
         check_diagnostics_with_disabled(
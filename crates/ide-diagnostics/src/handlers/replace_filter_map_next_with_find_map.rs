@@ -22,6 +22,8 @@ pub(crate) fn replace_filter_map_next_with_find_map(
         InFile::new(d.file, d.next_expr.into()),
     )
     .with_fixes(fixes(ctx, d))
+    // Only matches calls resolving to `Iterator::filter_map`.
+    .with_ssr_rule("core::iter::Iterator::filter_map($iter, $f).next() ==>> $iter.find_map($f)")
 }
 
 fn fixes(
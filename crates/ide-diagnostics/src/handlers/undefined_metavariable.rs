@@ -0,0 +1,83 @@
+use hir::InFile;
+use ide_db::{
+    base_db::{FileId, FileRange},
+    syntax_helpers::macro_metavars::macro_rules_rules,
+    FxHashSet,
+};
+use syntax::{ast, AstNode, SyntaxNode, SyntaxNodePtr};
+
+use crate::{Diagnostic, DiagnosticCode, Severity};
+
+// Diagnostic: undefined-metavariable
+//
+// This diagnostic is triggered when the transcriber of a `macro_rules!` rule uses a metavariable
+// that is not declared in the matcher of the same rule.
+pub(crate) fn undefined_metavariable(
+    acc: &mut Vec<Diagnostic>,
+    file_id: FileId,
+    node: &SyntaxNode,
+) -> Option<()> {
+    let mac = ast::MacroRules::cast(node.clone())?;
+    for rule in macro_rules_rules(&mac) {
+        let Some(transcriber) = &rule.transcriber else { continue };
+        // Macros defined by the transcriber declare metavariables of their own.
+        let defines_macro = transcriber
+            .syntax()
+            .descendants_with_tokens()
+            .filter_map(|it| it.into_token())
+            .any(|it| it.text() == "macro_rules");
+        if defines_macro {
+            continue;
+        }
+        let declared: FxHashSet<_> =
+            rule.metavar_decls().into_iter().map(|(name, _)| name.text().to_owned()).collect();
+        for token in rule.metavar_uses() {
+            if declared.contains(token.text()) {
+                continue;
+            }
+            acc.push(
+                Diagnostic::new(
+                    DiagnosticCode::Ra("undefined-metavariable", Severity::Warning),
+                    format!("unknown macro variable `{}`", token.text()),
+                    FileRange { file_id, range: token.text_range() },
+                )
+                .with_main_node(InFile::new(file_id.into(), SyntaxNodePtr::new(node))),
+            );
+        }
+    }
+    Some(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::check_diagnostics;
+
+    #[test]
+    fn undefined_metavariable() {
+        check_diagnostics(
+            r#"
+macro_rules! m {
+    ($a:expr, $($b:ident),*) => { $a + $($b)+* + $c };
+                                                //^ warning: unknown macro variable `c`
+    () => { $crate::f($a) };
+                     //^ warning: unknown macro variable `a`
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn ignores_nested_macro_definitions() {
+        check_diagnostics(
+            r#"
+macro_rules! outer {
+    ($name:ident) => {
+        macro_rules! $name {
+            ($inner:expr) => { $inner };
+        }
+    };
+}
+"#,
+        );
+    }
+}
@@ -1,3 +1,13 @@
+use hir::Semantics;
+use ide_db::{
+    base_db::{FileId, FileRange},
+    RootDatabase,
+};
+use syntax::{
+    ast::{self, AstToken},
+    AstNode, NodeOrToken, SyntaxKind, SyntaxNode, T,
+};
+
 use crate::{Diagnostic, DiagnosticCode, DiagnosticsContext};
 
 // Diagnostic: unresolved-ident
@@ -16,6 +26,61 @@ pub(crate) fn unresolved_ident(
     .experimental()
 }
 
+/// Variables captured by format strings, as in `format!("{x}")`, are desugared and so aren't
+/// reported by the diagnostic above.
+pub(crate) fn unresolved_format_args_captures(
+    sema: &Semantics<'_, RootDatabase>,
+    acc: &mut Vec<Diagnostic>,
+    file_id: FileId,
+    node: &SyntaxNode,
+) {
+    let Some(tt) = ast::TokenTree::cast(node.clone()) else { return };
+    let named_args = named_args(&tt);
+    let strings =
+        tt.syntax().children_with_tokens().filter_map(|it| ast::String::cast(it.into_token()?));
+    for string in strings {
+        let Some(parts) = sema.as_format_args_parts(&string) else { continue };
+        for (range, resolution) in parts {
+            if resolution.is_some() {
+                continue;
+            }
+            let name = &string.syntax().text()[range - string.syntax().text_range().start()];
+            if named_args.iter().any(|it| it == name) {
+                continue;
+            }
+            acc.push(
+                Diagnostic::new(
+                    DiagnosticCode::RustcHardError("E0425"),
+                    "no such value in this scope",
+                    FileRange { file_id, range },
+                )
+                .experimental(),
+            );
+        }
+    }
+}
+
+/// The names of the `name = value` arguments of a macro call.
+fn named_args(tt: &ast::TokenTree) -> Vec<String> {
+    let tokens: Vec<_> = tt
+        .token_trees_and_tokens()
+        .filter(|it| !it.as_token().map_or(false, |it| it.kind().is_trivia()))
+        .collect();
+    tokens
+        .windows(3)
+        .filter_map(|window| match window {
+            [NodeOrToken::Token(comma), NodeOrToken::Token(name), NodeOrToken::Token(eq)]
+                if comma.kind() == T![,]
+                    && name.kind() == SyntaxKind::IDENT
+                    && eq.kind() == T![=] =>
+            {
+                Some(name.text().to_owned())
+            }
+            _ => None,
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use crate::tests::check_diagnostics;
@@ -40,6 +105,20 @@ fn main() {
     let x = 5;
     let _ = x;
 }
+"#,
+        );
+    }
+
+    #[test]
+    fn format_args_captures() {
+        check_diagnostics(
+            r#"
+//- minicore: fmt
+fn main() {
+    let x = 5;
+    format_args!("{x} {y:?} {z}", z = 1);
+                     //^ error: no such value in this scope
+}
 "#,
         );
     }
@@ -0,0 +1,86 @@
+use hir::Semantics;
+use ide_db::{
+    base_db::{FileId, FileRange, SourceDatabase},
+    env_vars::is_set_by_cargo,
+    RootDatabase,
+};
+use syntax::{ast, AstNode, AstToken, SyntaxNode};
+
+use crate::{Diagnostic, DiagnosticCode, Severity};
+
+// Diagnostic: unset-env-var
+//
+// This diagnostic is shown when `env!` refers to a `CARGO_` variable that Cargo doesn't set, which
+// fails the build.
+pub(crate) fn unset_env_var(
+    sema: &Semantics<'_, RootDatabase>,
+    acc: &mut Vec<Diagnostic>,
+    file_id: FileId,
+    node: &SyntaxNode,
+) -> Option<()> {
+    let call = ast::MacroCall::cast(node.clone())?;
+    if call.path()?.segment()?.name_ref()?.text() != "env"
+        || sema.resolve_macro_call(&call)?.kind(sema.db) != hir::MacroKind::BuiltIn
+    {
+        return None;
+    }
+    let string = call
+        .token_tree()?
+        .syntax()
+        .children_with_tokens()
+        .find_map(|it| ast::String::cast(it.into_token()?))?;
+    let name = string.value()?;
+    if !name.starts_with("CARGO_") || is_set_by_cargo(&name) {
+        return None;
+    }
+    let krate = sema.scope(call.syntax())?.krate();
+    let env = &sema.db.crate_graph()[krate.into()].env;
+    // Cargo's variables are only known for crates loaded from Cargo.
+    if env.get("CARGO_PKG_NAME").is_none() || env.get(&name).is_some() {
+        return None;
+    }
+    acc.push(Diagnostic::new(
+        DiagnosticCode::Ra("unset-env-var", Severity::Warning),
+        format!("environment variable `{name}` is not set by Cargo"),
+        FileRange { file_id, range: string.syntax().text_range() },
+    ));
+    Some(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::check_diagnostics;
+
+    #[test]
+    fn unknown_cargo_variable() {
+        check_diagnostics(
+            r#"
+//- /main.rs env:CARGO_PKG_NAME=foo,CARGO_PKG_VERSION=0.1.0
+#[rustc_builtin_macro]
+macro_rules! env { ($var:literal) => { 0 } }
+
+fn main() {
+    let _ = env!("CARGO_PKG_VERISON");
+               //^^^^^^^^^^^^^^^^^^^ warning: environment variable `CARGO_PKG_VERISON` is not set by Cargo
+    let _ = env!("CARGO_PKG_VERSION");
+    let _ = env!("CARGO_BIN_NAME");
+    let _ = env!("HOME");
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_loaded_from_cargo() {
+        check_diagnostics(
+            r#"
+#[rustc_builtin_macro]
+macro_rules! env { ($var:literal) => { 0 } }
+
+fn main() {
+    let _ = env!("CARGO_PKG_VERISON");
+}
+"#,
+        );
+    }
+}
@@ -0,0 +1,197 @@
+use hir::InFile;
+use ide_db::{
+    base_db::{FileId, FileRange},
+    imports::unused::unused_imports_in_file,
+    source_change::SourceChangeBuilder,
+};
+use syntax::{ast, AstNode, SyntaxNode, SyntaxNodePtr};
+
+use crate::{fix, Diagnostic, DiagnosticCode, DiagnosticsContext};
+
+// Diagnostic: unused-imports
+//
+// This diagnostic is triggered when an item imported by a private `use` is not used in its
+// module. It is experimental, and only enabled when experimental diagnostics are.
+pub(crate) fn unused_imports(
+    ctx: &DiagnosticsContext<'_>,
+    acc: &mut Vec<Diagnostic>,
+    file_id: FileId,
+    root: &SyntaxNode,
+) -> Option<()> {
+    if ctx.config.disabled.contains("unused_imports") {
+        return None;
+    }
+    let unused = unused_imports_in_file(&ctx.sema, &ast::SourceFile::cast(root.clone())?);
+    if unused.is_empty() {
+        return None;
+    }
+
+    let remove_all = (unused.len() > 1).then(|| {
+        let mut builder = SourceChangeBuilder::new(file_id);
+        let trees = unused.iter().map(|it| builder.make_mut(it.clone())).collect::<Vec<_>>();
+        trees.iter().for_each(|it| it.remove_recursive());
+        builder.finish()
+    });
+    for tree in &unused {
+        let range = tree.syntax().text_range();
+        let mut builder = SourceChangeBuilder::new(file_id);
+        builder.make_mut(tree.clone()).remove_recursive();
+        let mut fixes =
+            vec![fix("remove_unused_import", "Remove unused import", builder.finish(), range)];
+        if let Some(remove_all) = &remove_all {
+            fixes.push(fix(
+                "remove_unused_imports",
+                "Remove all unused imports",
+                remove_all.clone(),
+                range,
+            ));
+        }
+        let use_item = tree.syntax().ancestors().find_map(ast::Use::cast)?;
+        acc.push(
+            Diagnostic::new(
+                DiagnosticCode::RustcLint("unused_imports"),
+                "unused import",
+                FileRange { file_id, range },
+            )
+            .with_main_node(InFile::new(file_id.into(), SyntaxNodePtr::new(use_item.syntax())))
+            .with_unused(true)
+            .experimental()
+            .with_fixes(Some(fixes)),
+        );
+    }
+    Some(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        tests::{check_diagnostics_with_config, check_nth_fix_with_config},
+        DiagnosticsConfig,
+    };
+
+    fn config() -> DiagnosticsConfig {
+        let mut config = DiagnosticsConfig::test_sample();
+        config.disabled.insert("inactive-code".to_owned());
+        config.disabled.remove("unused_imports");
+        config
+    }
+
+    fn check_diagnostics(ra_fixture: &str) {
+        check_diagnostics_with_config(config(), ra_fixture)
+    }
+
+    #[test]
+    fn unused_imports() {
+        check_diagnostics(
+            r#"
+mod m {
+    pub struct Used;
+    pub struct Unused;
+    pub trait Trait { fn method(&self) {} }
+    impl Trait for Used {}
+}
+use m::Unused;
+  //^^^^^^^^^ 💡 warn: unused import
+use m::{Used, Trait};
+fn f() {
+    Used.method();
+}
+pub use m::Unused as Reexported;
+"#,
+        );
+    }
+
+    #[test]
+    fn unused_tree_in_list() {
+        check_diagnostics(
+            r#"
+mod m {
+    pub struct A;
+    pub struct B;
+}
+use m::{A, B};
+         //^ 💡 warn: unused import
+fn f() -> A { A }
+"#,
+        );
+    }
+
+    #[test]
+    fn used_in_macro_expansion() {
+        check_diagnostics(
+            r#"
+mod m {
+    pub struct A;
+}
+macro_rules! ret {
+    ($t:ty) => { fn f() -> $t { loop {} } };
+}
+use m::A;
+ret!(A);
+"#,
+        );
+    }
+
+    #[test]
+    fn used_by_submodule() {
+        check_diagnostics(
+            r#"
+mod m {
+    pub struct A;
+    pub struct B;
+}
+use m::{A, B};
+mod child {
+    fn f() -> super::A { loop {} }
+}
+mod glob {
+    use super::*;
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn trait_used_through_method_in_macro() {
+        check_diagnostics(
+            r#"
+mod m {
+    pub struct S;
+    pub trait Trait { fn method(&self) {} }
+    impl Trait for S {}
+}
+macro_rules! call {
+    ($e:expr) => { $e.method() };
+}
+use m::{S, Trait};
+fn f() {
+    call!(S);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn remove_unused_import() {
+        check_nth_fix_with_config(
+            config(),
+            0,
+            r#"
+mod m {
+    pub struct A;
+    pub struct B;
+}
+use m::{A, B$0};
+fn f() -> A { A }
+"#,
+            r#"
+mod m {
+    pub struct A;
+    pub struct B;
+}
+use m::A;
+fn f() -> A { A }
+"#,
+        );
+    }
+}
@@ -70,7 +70,10 @@ mod handlers {
     // The handlers below are unusual, the implement the diagnostics as well.
     pub(crate) mod field_shorthand;
     pub(crate) mod json_is_not_rust;
+    pub(crate) mod undefined_metavariable;
     pub(crate) mod unlinked_file;
+    pub(crate) mod unset_env_var;
+    pub(crate) mod unused_imports;
     pub(crate) mod useless_braces;
 }
 
@@ -141,6 +144,9 @@ pub struct Diagnostic {
     pub unused: bool,
     pub experimental: bool,
     pub fixes: Option<Vec<Assist>>,
+    /// A structural search and replace rule that fixes all occurrences of this diagnostic, to be
+    /// applied across the workspace at once.
+    pub ssr_rule: Option<String>,
     // The node that will be affected by `#[allow]` and similar attributes.
     pub main_node: Option<InFile<SyntaxNodePtr>>,
 }
@@ -164,6 +170,7 @@ impl Diagnostic {
             unused: false,
             experimental: false,
             fixes: None,
+            ssr_rule: None,
             main_node: None,
         }
     }
@@ -193,6 +200,11 @@ impl Diagnostic {
         self
     }
 
+    fn with_ssr_rule(mut self, rule: impl Into<String>) -> Diagnostic {
+        self.ssr_rule = Some(rule.into());
+        self
+    }
+
     fn with_unused(mut self, unused: bool) -> Diagnostic {
         self.unused = unused;
         self
@@ -243,7 +255,8 @@ impl DiagnosticsConfig {
             proc_macros_enabled: Default::default(),
             proc_attr_macros_enabled: Default::default(),
             disable_experimental: Default::default(),
-            disabled: Default::default(),
+            // Most fixtures don't use everything they import.
+            disabled: FxHashSet::from_iter(["unused_imports".to_owned()]),
             expr_fill_default: Default::default(),
             insert_use: InsertUseConfig {
                 granularity: ImportGranularity::Preserve,
@@ -313,6 +326,11 @@ pub fn diagnostics(
         handlers::useless_braces::useless_braces(&mut res, file_id, &node);
         handlers::field_shorthand::field_shorthand(&mut res, file_id, &node);
         handlers::json_is_not_rust::json_in_items(&sema, &mut res, file_id, &node, config);
+        handlers::undefined_metavariable::undefined_metavariable(&mut res, file_id, &node);
+        handlers::unresolved_ident::unresolved_format_args_captures(
+            &sema, &mut res, file_id, &node,
+        );
+        handlers::unset_env_var::unset_env_var(&sema, &mut res, file_id, &node);
     }
 
     let module = sema.to_module_def(file_id);
@@ -321,6 +339,7 @@ pub fn diagnostics(
     if module.is_none() {
         handlers::unlinked_file::unlinked_file(&ctx, &mut res, file_id);
     }
+    handlers::unused_imports::unused_imports(&ctx, &mut res, file_id, parse.syntax());
 
     let mut diags = Vec::new();
     if let Some(m) = module {
@@ -407,12 +426,14 @@ pub fn diagnostics(
 
     let mut rustc_stack: FxHashMap<String, Vec<Severity>> = FxHashMap::default();
     let mut clippy_stack: FxHashMap<String, Vec<Severity>> = FxHashMap::default();
+    let mut ra_stack: FxHashMap<String, Vec<Severity>> = FxHashMap::default();
 
     handle_lint_attributes(
         &ctx.sema,
         parse.syntax(),
         &mut rustc_stack,
         &mut clippy_stack,
+        &mut ra_stack,
         &mut diagnostics_of_range,
     );
 
@@ -458,6 +479,7 @@ fn handle_lint_attributes(
     root: &SyntaxNode,
     rustc_stack: &mut FxHashMap<String, Vec<Severity>>,
     clippy_stack: &mut FxHashMap<String, Vec<Severity>>,
+    ra_stack: &mut FxHashMap<String, Vec<Severity>>,
     diagnostics_of_range: &mut FxHashMap<InFile<SyntaxNode>, &mut Diagnostic>,
 ) {
     let file_id = sema.hir_file_for(root);
@@ -466,9 +488,15 @@ fn handle_lint_attributes(
         match ev {
             syntax::WalkEvent::Enter(node) => {
                 for attr in node.children().filter_map(ast::Attr::cast) {
-                    parse_lint_attribute(attr, rustc_stack, clippy_stack, |stack, severity| {
-                        stack.push(severity);
-                    });
+                    parse_lint_attribute(
+                        attr,
+                        rustc_stack,
+                        clippy_stack,
+                        ra_stack,
+                        |stack, severity| {
+                            stack.push(severity);
+                        },
+                    );
                 }
                 if let Some(x) =
                     diagnostics_of_range.get_mut(&InFile { file_id, value: node.clone() })
@@ -483,6 +511,17 @@ fn handle_lint_attributes(
                             CLIPPY_LINT_GROUPS_DICT.get(name).map_or(EMPTY_LINTS, |x| &**x),
                             &mut *clippy_stack,
                         ),
+                        // Our own diagnostics are controlled with `rust_analyzer::` tool lints,
+                        // named like their codes with underscores instead of dashes.
+                        DiagnosticCode::Ra(name, _) => {
+                            let name = name.replace('-', "_");
+                            for name in [&*name, "__RA_EVERY_LINT"] {
+                                if let Some(s) = ra_stack.get(name).and_then(|x| x.last()) {
+                                    x.severity = *s;
+                                }
+                            }
+                            continue;
+                        }
                         _ => continue,
                     };
                     for &name in names {
@@ -493,7 +532,7 @@ fn handle_lint_attributes(
                 }
                 if let Some(item) = ast::Item::cast(node.clone()) {
                     if let Some(me) = sema.expand_attr_macro(&item) {
-                        for stack in [&mut *rustc_stack, &mut *clippy_stack] {
+                        for stack in [&mut *rustc_stack, &mut *clippy_stack, &mut *ra_stack] {
                             stack
                                 .entry("__RA_EVERY_LINT".to_owned())
                                 .or_default()
@@ -504,9 +543,10 @@ fn handle_lint_attributes(
                             &me,
                             rustc_stack,
                             clippy_stack,
+                            ra_stack,
                             diagnostics_of_range,
                         );
-                        for stack in [&mut *rustc_stack, &mut *clippy_stack] {
+                        for stack in [&mut *rustc_stack, &mut *clippy_stack, &mut *ra_stack] {
                             stack.entry("__RA_EVERY_LINT".to_owned()).or_default().pop();
                         }
                     }
@@ -518,6 +558,7 @@ fn handle_lint_attributes(
                             &me,
                             rustc_stack,
                             clippy_stack,
+                            ra_stack,
                             diagnostics_of_range,
                         );
                     }
@@ -525,11 +566,17 @@ fn handle_lint_attributes(
             }
             syntax::WalkEvent::Leave(node) => {
                 for attr in node.children().filter_map(ast::Attr::cast) {
-                    parse_lint_attribute(attr, rustc_stack, clippy_stack, |stack, severity| {
-                        if stack.pop() != Some(severity) {
-                            never!("Mismatched serevity in walking lint attributes");
-                        }
-                    });
+                    parse_lint_attribute(
+                        attr,
+                        rustc_stack,
+                        clippy_stack,
+                        ra_stack,
+                        |stack, severity| {
+                            if stack.pop() != Some(severity) {
+                                never!("Mismatched serevity in walking lint attributes");
+                            }
+                        },
+                    );
                 }
             }
         }
@@ -540,6 +587,7 @@ fn parse_lint_attribute(
     attr: ast::Attr,
     rustc_stack: &mut FxHashMap<String, Vec<Severity>>,
     clippy_stack: &mut FxHashMap<String, Vec<Severity>>,
+    ra_stack: &mut FxHashMap<String, Vec<Severity>>,
     job: impl Fn(&mut Vec<Severity>, Severity),
 ) {
     let Some((tag, args_tt)) = attr.as_simple_call() else {
@@ -557,8 +605,14 @@ fn parse_lint_attribute(
         }
         if let Some(tool) = lint.qualifier().and_then(|x| x.as_single_name_ref()) {
             if let Some(name_ref) = &lint.segment().and_then(|x| x.name_ref()) {
-                if tool.to_string() == "clippy" {
-                    job(clippy_stack.entry(name_ref.to_string()).or_default(), severity);
+                match tool.to_string().as_str() {
+                    "clippy" => {
+                        job(clippy_stack.entry(name_ref.to_string()).or_default(), severity)
+                    }
+                    "rust_analyzer" => {
+                        job(ra_stack.entry(name_ref.to_string()).or_default(), severity)
+                    }
+                    _ => (),
                 }
             }
         }
@@ -540,15 +540,16 @@ impl<'db, 'sema> Matcher<'db, 'sema> {
             .arg_list()
             .ok_or_else(|| match_error!("Pattern function call has no args"))?
             .args();
+        let pattern_receiver = pattern_args.next();
         // If the function we're calling takes a self parameter, then we store additional
         // information on the placeholder match about autoderef and autoref. This allows us to use
-        // the placeholder in a context where autoderef and autoref don't apply.
+        // the placeholder in a context where autoderef and autoref don't apply. Trait methods
+        // have no qualifier type to compare against, their receiver is matched as is.
         if code_resolved_function.self_param(self.sema.db).is_some() {
             if let (Some(pattern_type), Some(expr)) =
                 (&pattern_ufcs.qualifier_type, &code.receiver())
             {
                 let deref_count = self.check_expr_type(pattern_type, expr)?;
-                let pattern_receiver = pattern_args.next();
                 self.attempt_match_opt(phase, pattern_receiver.clone(), code.receiver())?;
                 if let Phase::Second(match_out) = phase {
                     if let Some(placeholder_value) = pattern_receiver
@@ -568,9 +569,11 @@ impl<'db, 'sema> Matcher<'db, 'sema> {
                             .unwrap_or(ast::SelfParamKind::Owned);
                     }
                 }
+            } else {
+                self.attempt_match_opt(phase, pattern_receiver, code.receiver())?;
             }
         } else {
-            self.attempt_match_opt(phase, pattern_args.next(), code.receiver())?;
+            self.attempt_match_opt(phase, pattern_receiver, code.receiver())?;
         }
         let mut code_args =
             code.arg_list().ok_or_else(|| match_error!("Code method call has no args"))?.args();
@@ -1,9 +1,11 @@
 use hir::{DescendPreference, InFile, MacroFileIdExt, Semantics};
 use ide_db::{
-    base_db::FileId, helpers::pick_best_token,
-    syntax_helpers::insert_whitespace_into_node::insert_ws_into, RootDatabase,
+    base_db::{FileId, FileRange},
+    helpers::pick_best_token,
+    syntax_helpers::insert_whitespace_into_node::insert_ws_into,
+    RootDatabase,
 };
-use syntax::{ast, ted, AstNode, NodeOrToken, SyntaxKind, SyntaxNode, T};
+use syntax::{ast, ted, AstNode, NodeOrToken, SyntaxKind, SyntaxNode, SyntaxNodePtr, TextRange, T};
 
 use crate::FilePosition;
 
@@ -12,6 +14,15 @@ pub struct ExpandedMacro {
     pub expansion: String,
 }
 
+pub struct ExpandedMacroDiagnostic {
+    pub name: String,
+    pub expansion: String,
+    /// The range of the diagnostic within `expansion`.
+    pub range: TextRange,
+    /// The macro call that `expansion` was produced by.
+    pub call_site: FileRange,
+}
+
 // Feature: Expand Macro Recursively
 //
 // Shows the full macro expansion of the macro at the current caret position.
@@ -110,6 +121,56 @@ pub(crate) fn expand_macro(db: &RootDatabase, position: FilePosition) -> Option<
     Some(ExpandedMacro { name, expansion })
 }
 
+// Feature: Show Diagnostic in Macro Expansion
+//
+// For a diagnostic reported inside a macro expansion, shows the expansion with the diagnostic
+// highlighted at its expanded position, and a link back to the macro call.
+//
+// |===
+// | Editor  | Action Name
+//
+// | VS Code | **rust-analyzer: Show diagnostic in macro expansion**
+// |===
+pub(crate) fn expand_macro_diagnostic(
+    db: &RootDatabase,
+    node: InFile<SyntaxNodePtr>,
+) -> Option<ExpandedMacroDiagnostic> {
+    let sema = Semantics::new(db);
+    let macro_file = node.file_id.macro_file()?;
+    let root = sema.parse_or_expand(node.file_id);
+    let target = node.value.to_node(&root);
+
+    // Inserting whitespace keeps the node structure intact, so the diagnostic node is found in
+    // the formatted expansion by the positions of it and its ancestors among their siblings.
+    let mut path: Vec<usize> = target
+        .ancestors()
+        .take_while(|it| *it != root)
+        .map(|it| {
+            it.parent().map_or(0, |parent| parent.children().position(|c| c == it).unwrap_or(0))
+        })
+        .collect();
+    path.reverse();
+    let formatted = insert_ws_into(root);
+    let mut formatted_target = formatted.clone();
+    for idx in path {
+        formatted_target = formatted_target.children().nth(idx)?;
+    }
+
+    let call = macro_file.call_node(db);
+    let name = match ast::MacroCall::cast(call.value.clone()) {
+        Some(mac) => format!("{}!", mac.path()?.segment()?.name_ref()?),
+        None if macro_file.is_attr_macro(db) => "attribute macro".to_owned(),
+        None => "derive macro".to_owned(),
+    };
+
+    Some(ExpandedMacroDiagnostic {
+        name,
+        expansion: formatted.to_string(),
+        range: formatted_target.text_range(),
+        call_site: call.as_ref().original_file_range(db),
+    })
+}
+
 fn expand_macro_recur(
     sema: &Semantics<'_, RootDatabase>,
     macro_call: &ast::MacroCall,
@@ -225,8 +286,9 @@ fn _format(
 #[cfg(test)]
 mod tests {
     use expect_test::{expect, Expect};
+    use ide_diagnostics::DiagnosticsConfig;
 
-    use crate::fixture;
+    use crate::{fixture, AssistResolveStrategy};
 
     #[track_caller]
     fn check(ra_fixture: &str, expect: Expect) {
@@ -236,6 +298,33 @@ mod tests {
         expect.assert_eq(&actual);
     }
 
+    #[test]
+    fn macro_expand_diagnostic() {
+        let (analysis, file_id) = fixture::file(
+            r#"
+macro_rules! m {
+    () => { fn f() { break; } }
+}
+m!();
+"#,
+        );
+        let config = DiagnosticsConfig::test_sample();
+        let diagnostic = analysis
+            .diagnostics(&config, AssistResolveStrategy::None, file_id)
+            .unwrap()
+            .into_iter()
+            .find(|it| it.message == "break outside of loop")
+            .unwrap();
+
+        let expanded =
+            analysis.expand_macro_diagnostic(&config, diagnostic.range).unwrap().unwrap();
+        assert_eq!(expanded.name, "m!");
+        assert_eq!(&expanded.expansion[expanded.range], "break");
+        assert_eq!(expanded.call_site.file_id, file_id);
+        let text = analysis.file_text(file_id).unwrap();
+        assert!(text[expanded.call_site.range].starts_with("m!()"));
+    }
+
     #[test]
     fn macro_expand_as_keyword() {
         check(
@@ -0,0 +1,186 @@
+use hir::{
+    Adjust, Adjustment, AutoBorrow, Function, HirDisplay, ModuleDef, Mutability, OverloadedDeref,
+    PathResolution, PointerCast, Safety, Semantics,
+};
+use ide_db::{base_db::FileRange, RootDatabase};
+use syntax::{ast, AstNode, TextRange};
+
+/// How the type of an expression came about.
+#[derive(Debug)]
+pub struct ExpressionExplanation {
+    /// The range of the explained expression.
+    pub range: TextRange,
+    pub ty: String,
+    /// The type the expression is coerced to, if any.
+    pub coerced_ty: Option<String>,
+    /// The adjustments applied to the expression, in order.
+    pub adjustments: Vec<ExplainedAdjustment>,
+    /// The function a call or an overloaded operator resolves to.
+    pub callee: Option<String>,
+    /// The trait `callee` is declared in or implements.
+    pub callee_trait: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct ExplainedAdjustment {
+    pub kind: &'static str,
+    pub source: String,
+    pub target: String,
+}
+
+// Feature: Explain Expression
+//
+// Shows the inferred type of the smallest expression covering the selection, together with the
+// coercions and auto-(de)referencing applied to it and the function that a call or an overloaded
+// operator resolves to.
+//
+// |===
+// | Editor  | Action Name
+//
+// | VS Code | **rust-analyzer: Explain Expression**
+// |===
+pub(crate) fn explain_expression(
+    db: &RootDatabase,
+    frange: FileRange,
+) -> Option<ExpressionExplanation> {
+    let sema = Semantics::new(db);
+    let file = sema.parse(frange.file_id);
+    let expr =
+        file.syntax().covering_element(frange.range).ancestors().find_map(ast::Expr::cast)?;
+    let info = sema.type_of_expr(&expr)?;
+
+    let adjustments = sema
+        .expr_adjustments(&expr)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|Adjustment { source, target, kind }| ExplainedAdjustment {
+            kind: adjustment_kind(kind),
+            source: source.display(db).to_string(),
+            target: target.display(db).to_string(),
+        })
+        .collect();
+    let callee = resolve_callee(&sema, &expr);
+    let callee_trait = callee
+        .and_then(|it| it.as_assoc_item(db))
+        .and_then(|it| it.container_or_implemented_trait(db))
+        .map(|it| it.name(db).display(db).to_string());
+
+    Some(ExpressionExplanation {
+        range: expr.syntax().text_range(),
+        ty: info.original.display(db).to_string(),
+        coerced_ty: info.adjusted.map(|it| it.display(db).to_string()),
+        adjustments,
+        callee: callee.map(|it| it.display(db).to_string()),
+        callee_trait,
+    })
+}
+
+fn resolve_callee(sema: &Semantics<'_, RootDatabase>, expr: &ast::Expr) -> Option<Function> {
+    match expr {
+        ast::Expr::MethodCallExpr(it) => sema.resolve_method_call(it),
+        ast::Expr::CallExpr(it) => match it.expr()? {
+            ast::Expr::PathExpr(callee) => match sema.resolve_path(&callee.path()?)? {
+                PathResolution::Def(ModuleDef::Function(it)) => Some(it),
+                _ => None,
+            },
+            _ => None,
+        },
+        ast::Expr::BinExpr(it) => sema.resolve_bin_expr(it),
+        ast::Expr::PrefixExpr(it) => sema.resolve_prefix_expr(it),
+        ast::Expr::IndexExpr(it) => sema.resolve_index_expr(it),
+        ast::Expr::TryExpr(it) => sema.resolve_try_expr(it),
+        _ => None,
+    }
+}
+
+fn adjustment_kind(kind: Adjust) -> &'static str {
+    match kind {
+        Adjust::NeverToAny => "never to any",
+        Adjust::Deref(None) => "dereference",
+        Adjust::Deref(Some(OverloadedDeref(Mutability::Shared))) => "`Deref` dereference",
+        Adjust::Deref(Some(OverloadedDeref(Mutability::Mut))) => "`DerefMut` dereference",
+        Adjust::Borrow(AutoBorrow::Ref(Mutability::Shared)) => "borrow",
+        Adjust::Borrow(AutoBorrow::Ref(Mutability::Mut)) => "unique borrow",
+        Adjust::Borrow(AutoBorrow::RawPtr(Mutability::Shared)) => "const pointer borrow",
+        Adjust::Borrow(AutoBorrow::RawPtr(Mutability::Mut)) => "mut pointer borrow",
+        Adjust::Pointer(cast) => match cast {
+            PointerCast::ReifyFnPointer => "fn item to fn pointer",
+            PointerCast::UnsafeFnPointer => "safe fn pointer to unsafe fn pointer",
+            PointerCast::ClosureFnPointer(Safety::Unsafe) => "closure to unsafe fn pointer",
+            PointerCast::ClosureFnPointer(Safety::Safe) => "closure to fn pointer",
+            PointerCast::MutToConstPointer => "mut ptr to const ptr",
+            PointerCast::ArrayToPointer => "array ptr to element ptr",
+            PointerCast::Unsize => "unsize",
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::{expect, Expect};
+
+    use crate::fixture;
+
+    fn check(ra_fixture: &str, expect: Expect) {
+        let (analysis, frange) = fixture::range(ra_fixture);
+        let explanation = analysis.explain_expression(frange).unwrap().unwrap();
+        let mut actual = format!("{}\n", explanation.ty);
+        if let Some(coerced) = &explanation.coerced_ty {
+            actual += &format!("coerced to {coerced}\n");
+        }
+        for adjustment in &explanation.adjustments {
+            actual +=
+                &format!("{}: {} -> {}\n", adjustment.kind, adjustment.source, adjustment.target);
+        }
+        if let Some(callee) = &explanation.callee {
+            actual += &format!("calls {callee}");
+            if let Some(trait_) = &explanation.callee_trait {
+                actual += &format!(" of {trait_}");
+            }
+            actual += "\n";
+        }
+        expect.assert_eq(&actual);
+    }
+
+    #[test]
+    fn method_call_with_autoref() {
+        check(
+            r#"
+struct S;
+trait Len {
+    fn len(&self) -> usize;
+}
+impl Len for S {
+    fn len(&self) -> usize { 0 }
+}
+fn f(s: S) {
+    let _ = $0s.len()$0;
+}
+"#,
+            expect![[r#"
+                usize
+                calls fn len(&self) -> usize of Len
+            "#]],
+        );
+    }
+
+    #[test]
+    fn coerced_argument() {
+        check(
+            r#"
+//- minicore: coerce_unsized
+fn takes(_: &[u8]) {}
+fn f(array: &[u8; 2]) {
+    takes($0array$0);
+}
+"#,
+            expect![[r#"
+                &[u8; 2]
+                coerced to &[u8]
+                dereference: &[u8; 2] -> [u8; 2]
+                borrow: [u8; 2] -> &[u8; 2]
+                unsize: &[u8; 2] -> &[u8]
+            "#]],
+        );
+    }
+}
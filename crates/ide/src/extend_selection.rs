@@ -4,8 +4,8 @@ use hir::{DescendPreference, Semantics};
 use ide_db::RootDatabase;
 use syntax::{
     algo::{self, skip_trivia_token},
-    ast::{self, AstNode, AstToken},
-    Direction, NodeOrToken,
+    ast::{self, AstNode, AstToken, IsString},
+    Direction, NodeOrToken, SyntaxElement,
     SyntaxKind::{self, *},
     SyntaxNode, SyntaxToken, TextRange, TextSize, TokenAtOffset, T,
 };
@@ -17,6 +17,9 @@ use crate::FileRange;
 // Extends or shrinks the current selection to the encompassing syntactic construct
 // (expression, statement, item, module, etc). It works with multiple cursors.
 //
+// Paths are selected as a whole after the identifier, and the contents of string literals, blocks
+// and macro token trees are selected before their quotes or delimiters.
+//
 // |===
 // | Editor  | Shortcut
 //
@@ -81,6 +84,11 @@ fn try_extend_selection(
     let node = match root.covering_element(range) {
         NodeOrToken::Token(token) => {
             if token.text_range() != range {
+                if let Some(contents) = string_contents(&token) {
+                    if contents.contains_range(range) && contents != range {
+                        return Some(contents);
+                    }
+                }
                 return Some(token.text_range());
             }
             if let Some(comment) = ast::Comment::cast(token.clone()) {
@@ -103,18 +111,38 @@ fn try_extend_selection(
     }
 
     if node.text_range() != range {
+        if let Some(inner) = inner_range(&node) {
+            if inner.contains_range(range) && inner != range {
+                return Some(inner);
+            }
+        }
         return Some(node.text_range());
     }
 
     let node = shallowest_node(&node);
 
+    // From a segment (or qualifier) of a path, go straight to the full path.
+    if node.kind() == PATH || node.kind() == PATH_SEGMENT {
+        if let Some(full_path) = node.ancestors().skip(1).take_while(|it| it.kind() == PATH).last()
+        {
+            return Some(full_path.text_range());
+        }
+    }
+
     if node.parent().is_some_and(|n| list_kinds.contains(&n.kind())) {
         if let Some(range) = extend_list_item(&node) {
             return Some(range);
         }
     }
 
-    node.parent().map(|it| it.text_range())
+    // Select the statements of a block or the tokens of a token tree before their delimiters.
+    let parent = node.parent()?;
+    if let Some(inner) = inner_range(&parent) {
+        if inner.contains_range(range) && inner != range {
+            return Some(inner);
+        }
+    }
+    Some(parent.text_range())
 }
 
 fn extend_tokens_from_range(
@@ -189,6 +217,40 @@ fn extend_tokens_from_range(
     }
 }
 
+/// The range between the quotes of a string literal.
+fn string_contents(token: &SyntaxToken) -> Option<TextRange> {
+    let range = match token.kind() {
+        STRING => ast::String::cast(token.clone())?.text_range_between_quotes(),
+        BYTE_STRING => ast::ByteString::cast(token.clone())?.text_range_between_quotes(),
+        C_STRING => ast::CString::cast(token.clone())?.text_range_between_quotes(),
+        _ => None,
+    };
+    range.filter(|it| !it.is_empty())
+}
+
+/// The range of the contents of a block or token tree, without the delimiters and the whitespace
+/// around them.
+fn inner_range(node: &SyntaxNode) -> Option<TextRange> {
+    if !matches!(node.kind(), STMT_LIST | TOKEN_TREE) {
+        return None;
+    }
+    let is_delimiter = |it: &SyntaxElement| {
+        matches!(it.kind(), T!['{'] | T!['}'] | T!['('] | T![')'] | T!['['] | T![']'])
+    };
+    let children = node.children_with_tokens().collect::<Vec<_>>();
+    let mut children = children.as_slice();
+    if children.first().is_some_and(is_delimiter) {
+        children = &children[1..];
+    }
+    if children.last().is_some_and(is_delimiter) {
+        children = &children[..children.len() - 1];
+    }
+    let mut contents = children.iter().filter(|it| it.kind() != WHITESPACE);
+    let first = contents.next()?;
+    let last = contents.last().unwrap_or(first);
+    Some(first.text_range().cover(last.text_range()))
+}
+
 /// Find the shallowest node with same range, which allows us to traverse siblings.
 fn shallowest_node(node: &SyntaxNode) -> SyntaxNode {
     node.ancestors().take_while(|n| n.text_range() == node.text_range()).last().unwrap()
@@ -499,7 +561,35 @@ fn bar(){}
 
 " fn f$0oo() {"
 "#,
-            &["foo", "\" fn foo() {\""],
+            &["foo", " fn foo() {", "\" fn foo() {\""],
+        );
+    }
+
+    #[test]
+    fn test_extend_selection_path_and_block() {
+        do_check(
+            r#"
+fn main() {
+    let map = std::coll$0ections::HashMap::new();
+    map
+}
+"#,
+            &[
+                "collections",
+                "std::collections::HashMap::new",
+                "std::collections::HashMap::new()",
+                "let map = std::collections::HashMap::new();",
+                "let map = std::collections::HashMap::new();\n    map",
+                "{\n    let map = std::collections::HashMap::new();\n    map\n}",
+            ],
+        );
+    }
+
+    #[test]
+    fn test_extend_selection_token_tree() {
+        do_check(
+            r#"#[cfg(any(foo, ba$0r))] fn f() {}"#,
+            &["bar", "foo, bar", "(foo, bar)", "any(foo, bar)", "(any(foo, bar))"],
         );
     }
 
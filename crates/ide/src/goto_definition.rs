@@ -4,10 +4,11 @@ use crate::{
     doc_links::token_as_doc_comment, navigation_target::ToNav, FilePosition, NavigationTarget,
     RangeInfo, TryToNav,
 };
-use hir::{AsAssocItem, AssocItem, DescendPreference, ModuleDef, Semantics};
+use hir::{AsAssocItem, AssocItem, DescendPreference, HirFileIdExt, ModuleDef, Semantics};
 use ide_db::{
     base_db::{AnchoredPath, FileId, FileLoader},
     defs::{Definition, IdentClass},
+    ffi,
     helpers::pick_best_token,
     RootDatabase,
 };
@@ -49,6 +50,9 @@ pub(crate) fn goto_definition(
         kind if kind.is_trivia() => 0,
         _ => 1,
     })?;
+    if let Some(nav) = try_lookup_path_attr(sema, &original_token) {
+        return Some(RangeInfo::new(original_token.text_range(), vec![nav]));
+    }
     if let Some(doc_comment) = token_as_doc_comment(&original_token) {
         return doc_comment.get_definition_with_descend_at(sema, offset, |def, _, link_range| {
             let nav = def.try_to_nav(db)?;
@@ -96,6 +100,9 @@ pub(crate) fn goto_definition(
                                 .flatten()
                                 .collect();
                         }
+                        if let Some(navs) = try_lookup_ffi_definition(db, def) {
+                            return navs;
+                        }
                         try_filter_trait_item_definition(sema, &def)
                             .unwrap_or_else(|| def_to_nav(sema.db, def))
                     })
@@ -105,10 +112,30 @@ pub(crate) fn goto_definition(
         .flatten()
         .unique()
         .collect::<Vec<NavigationTarget>>();
+    let navs = match original_token.parent() {
+        Some(parent) if navs.is_empty() => {
+            IdentClass::classify_inactive(sema, &parent).map_or(navs, |def| def_to_nav(db, def))
+        }
+        _ => navs,
+    };
 
     Some(RangeInfo::new(original_token.text_range(), navs))
 }
 
+/// Goes from a declaration in an `extern` block to the functions of the workspace defining its
+/// symbol, if any.
+fn try_lookup_ffi_definition(db: &RootDatabase, def: Definition) -> Option<Vec<NavigationTarget>> {
+    let Definition::Function(func) = def else { return None };
+    if !func.is_extern_decl(db) {
+        return None;
+    }
+    let navs = ffi::linked_functions(db, func)
+        .into_iter()
+        .flat_map(|it| def_to_nav(db, it.into()))
+        .collect::<Vec<_>>();
+    (!navs.is_empty()).then_some(navs)
+}
+
 fn try_lookup_include_path(
     sema: &Semantics<'_, RootDatabase>,
     tt: ast::TokenTree,
@@ -131,11 +158,35 @@ fn try_lookup_include_path(
     }
 
     let file_id = sema.db.resolve_path(AnchoredPath { anchor: file_id, path: &path })?;
+    file_nav(sema, file_id, path)
+}
+
+/// Navigates from the string of a `#[path = "..."]` attribute to the file of its module.
+fn try_lookup_path_attr(
+    sema: &Semantics<'_, RootDatabase>,
+    token: &SyntaxToken,
+) -> Option<NavigationTarget> {
+    let string = ast::String::cast(token.clone())?;
+    let meta = string.syntax().parent()?.parent().and_then(ast::Meta::cast)?;
+    if meta.path()?.syntax().text() != "path" {
+        return None;
+    }
+    let module = meta.parent_attr()?.syntax().parent().and_then(ast::Module::cast)?;
+    let module = sema.to_def(&module)?;
+    let file_id = module.definition_source_file_id(sema.db).original_file(sema.db);
+    file_nav(sema, file_id, string.value()?.into_owned())
+}
+
+fn file_nav(
+    sema: &Semantics<'_, RootDatabase>,
+    file_id: FileId,
+    name: String,
+) -> Option<NavigationTarget> {
     let size = sema.db.file_text(file_id).len().try_into().ok()?;
     Some(NavigationTarget {
         file_id,
         full_range: TextRange::new(0.into(), size),
-        name: path.into(),
+        name: name.into(),
         alias: None,
         focus_range: None,
         kind: None,
@@ -1531,6 +1582,21 @@ struct Item;
         );
     }
 
+    #[test]
+    fn goto_path_attr() {
+        check(
+            r#"
+//- /main.rs
+#[path = "gen/bindings.rs$0"]
+mod bindings;
+
+//- /gen/bindings.rs
+// generated
+//^file
+"#,
+        );
+    }
+
     #[test]
     fn goto_shadow_include() {
         check(
@@ -2201,4 +2267,55 @@ macro_rules! baz {
             "#,
         );
     }
+
+    #[test]
+    fn goto_def_in_cfg_disabled_code() {
+        check(
+            r#"
+mod m {
+    pub struct Foo;
+             //^^^
+}
+fn f() {
+    #[cfg(never)]
+    let _ = m::Foo$0;
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn goto_def_of_extern_decl_to_exported_definitions() {
+        check(
+            r#"
+//- /main.rs crate:main deps:ffi
+extern "C" {
+    fn frobnicate();
+}
+fn main() {
+    unsafe { frobnicate$0() };
+}
+//- /ffi.rs crate:ffi
+#[no_mangle]
+pub extern "C" fn frobnicate() {}
+                //^^^^^^^^^^
+pub extern "C" fn other() {}
+"#,
+        );
+        check(
+            r#"
+//- /main.rs crate:main
+extern "C" {
+    #[link_name = "exported"]
+    fn renamed();
+}
+fn main() {
+    unsafe { renamed$0() };
+}
+#[export_name = "exported"]
+extern "C" fn definition() {}
+            //^^^^^^^^^^
+"#,
+        );
+    }
 }
@@ -32,6 +32,8 @@ pub struct HoverConfig {
     pub documentation: bool,
     pub keywords: bool,
     pub format: HoverDocFormat,
+    /// Whether to show if dropping a local runs any code.
+    pub drop_glue: bool,
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -189,6 +191,10 @@ fn hover_simple(
             let attr = token.parent_ancestors().find_map(ast::Attr::cast)?;
             render::try_for_lint(&attr, token)
         })
+        // try cfg feature hover
+        .or_else(|| descended().find_map(|token| render::cfg_feature(sema, file_id, token)))
+        // try macro fragment specifier hover
+        .or_else(|| descended().find_map(render::macro_fragment_specifier))
         // try definitions
         .or_else(|| {
             descended()
@@ -222,6 +228,12 @@ fn hover_simple(
                     acc
                 })
         })
+        // try definitions in `#[cfg]`-disabled code
+        .or_else(|| {
+            let node = original_token.parent()?;
+            let def = IdentClass::classify_inactive(sema, &node)?;
+            Some(hover_for_definition(sema, file_id, def, &node, config))
+        })
         // try keywords
         .or_else(|| descended().find_map(|token| render::keyword(sema, config, token)))
         // try _ hovers
@@ -3,16 +3,16 @@ use std::{mem, ops::Not};
 
 use either::Either;
 use hir::{
-    Adt, AsAssocItem, AsExternAssocItem, CaptureKind, HasCrate, HasSource, HirDisplay, Layout,
-    LayoutError, Name, Semantics, Trait, Type, TypeInfo,
+    Adt, AsAssocItem, AsExternAssocItem, CaptureKind, Deprecation, HasCrate, HasSource, HirDisplay,
+    Layout, LayoutError, Name, Semantics, Trait, Type, TypeInfo,
 };
 use ide_db::{
-    base_db::SourceDatabase,
+    base_db::{FileId, SourceDatabase, SourceDatabaseExt},
     defs::Definition,
     documentation::HasDocs,
     famous_defs::FamousDefs,
     generated::lints::{CLIPPY_LINTS, DEFAULT_LINTS, FEATURES},
-    syntax_helpers::insert_whitespace_into_node,
+    syntax_helpers::{insert_whitespace_into_node, macro_metavars},
     RootDatabase,
 };
 use itertools::Itertools;
@@ -26,7 +26,8 @@ use syntax::{
 use crate::{
     doc_links::{remove_links, rewrite_links},
     hover::{notable_traits, walk_and_push_ty},
-    HoverAction, HoverConfig, HoverResult, Markup, MemoryLayoutHoverConfig,
+    navigation_target::TryToNav,
+    tasks, HoverAction, HoverConfig, HoverResult, Markup, MemoryLayoutHoverConfig,
     MemoryLayoutHoverRenderKind,
 };
 
@@ -353,6 +354,56 @@ pub(super) fn try_for_lint(attr: &ast::Attr, token: &SyntaxToken) -> Option<Hove
     })
 }
 
+/// Hover for the fragment specifier of a metavariable declared in a `macro_rules!` matcher, the
+/// `expr` in `$e:expr`.
+pub(super) fn macro_fragment_specifier(token: &SyntaxToken) -> Option<HoverResult> {
+    let fragment = macro_metavars::fragment_specifier(token)?;
+    let (description, follow_set) = macro_metavars::fragment_specifier_docs(fragment)?;
+    Some(HoverResult {
+        markup: markup(
+            Some(format!("Matches {description}.\n\nCan be followed by: {follow_set}")),
+            fragment.to_owned(),
+            None,
+        ),
+        ..Default::default()
+    })
+}
+
+/// Hover for the feature in a `feature = "name"` predicate of a `cfg` or `cfg_attr` attribute,
+/// telling whether it is enabled for the current crate.
+pub(super) fn cfg_feature(
+    sema: &Semantics<'_, RootDatabase>,
+    file_id: FileId,
+    token: &SyntaxToken,
+) -> Option<HoverResult> {
+    let feature = ast::String::cast(token.clone())?.value()?;
+    let attr = token.parent_ancestors().find_map(ast::Attr::cast)?;
+    if !matches!(attr.path()?.as_single_name_ref()?.text().as_str(), "cfg" | "cfg_attr") {
+        return None;
+    }
+    let is_feature_key = algo::non_trivia_sibling(token.clone().into(), Direction::Prev)
+        .filter(|t| t.kind() == T![=])
+        .and_then(|t| algo::non_trivia_sibling(t, Direction::Prev))
+        .and_then(|t| t.into_token())
+        .map_or(false, |t| t.kind() == T![ident] && t.text() == "feature");
+    if !is_feature_key {
+        return None;
+    }
+
+    let krate = sema.to_module_def(file_id)?.krate();
+    let enabled = krate.cfg(sema.db).get_cfg_values("feature").any(|it| **it == *feature);
+    let crate_name = krate
+        .display_name(sema.db)
+        .map_or_else(|| "this crate".to_owned(), |name| format!("`{name}`"));
+    let status = if enabled { "enabled" } else { "disabled" };
+    Some(HoverResult {
+        markup: Markup::from(format!(
+            "```\nfeature = \"{feature}\"\n```\n___\n\nThis feature is {status} for {crate_name}."
+        )),
+        ..Default::default()
+    })
+}
+
 pub(super) fn process_markup(
     db: &RootDatabase,
     def: Definition,
@@ -479,6 +530,15 @@ pub(super) fn definition(
         _ => None,
     };
 
+    let drop_info = match def {
+        Definition::Local(it) if config.drop_glue => Some(if it.ty(db).has_significant_drop(db) {
+            "// drop: significant"
+        } else {
+            "// drop: no-op"
+        }),
+        _ => None,
+    };
+
     let mut desc = String::new();
     if let Some(notable_traits) = render_notable_trait_comment(db, notable_traits) {
         desc.push_str(&notable_traits);
@@ -488,13 +548,61 @@ pub(super) fn definition(
         desc.push_str(&layout_info);
         desc.push('\n');
     }
+    if let Some(drop_info) = drop_info {
+        desc.push_str(drop_info);
+        desc.push('\n');
+    }
     desc.push_str(&label);
     if let Some(value) = value {
         desc.push_str(" = ");
         desc.push_str(&value);
     }
 
-    markup(docs.map(Into::into), desc, mod_path)
+    let docs = match (def.deprecation(db).map(render_deprecation), docs) {
+        (Some(deprecation), Some(docs)) => Some(format!("{deprecation}\n\n{}", docs.as_str())),
+        (deprecation, docs) => deprecation.or(docs.map(Into::into)),
+    };
+    let docs = match (docs, render_task_count(db, def)) {
+        (Some(docs), Some(tasks)) => Some(format!("{docs}\n\n{tasks}")),
+        (docs, tasks) => docs.or(tasks),
+    };
+
+    markup(docs, desc, mod_path)
+}
+
+fn render_task_count(db: &RootDatabase, def: Definition) -> Option<String> {
+    let kind = match def {
+        Definition::Function(_) => "function",
+        Definition::Adt(Adt::Struct(_)) => "struct",
+        Definition::Adt(Adt::Enum(_)) => "enum",
+        Definition::Adt(Adt::Union(_)) => "union",
+        Definition::Trait(_) => "trait",
+        Definition::Module(_) => "module",
+        Definition::Const(_) => "constant",
+        Definition::Static(_) => "static",
+        Definition::TypeAlias(_) => "type alias",
+        _ => return None,
+    };
+    let nav = def.try_to_nav(db)?.call_site;
+    if db.source_root(db.file_source_root(nav.file_id)).is_library {
+        return None;
+    }
+    match tasks::count_tasks_in_range(db, nav.file_id, nav.full_range) {
+        0 => None,
+        1 => Some(format!("*This {kind} has 1 TODO/FIXME comment*")),
+        n => Some(format!("*This {kind} has {n} TODO/FIXME comments*")),
+    }
+}
+
+fn render_deprecation(deprecation: Deprecation) -> String {
+    let mut buf = "**Deprecated**".to_owned();
+    if let Some(since) = deprecation.since {
+        format_to!(buf, " since {since}");
+    }
+    if let Some(note) = deprecation.note {
+        format_to!(buf, ": {note}");
+    }
+    buf
 }
 
 fn render_notable_trait_comment(
@@ -17,6 +17,7 @@ const HOVER_BASE_CONFIG: HoverConfig = HoverConfig {
     documentation: true,
     format: HoverDocFormat::Markdown,
     keywords: true,
+    drop_glue: false,
 };
 
 fn check_hover_no_result(ra_fixture: &str) {
@@ -82,6 +83,23 @@ fn check_hover_no_memory_layout(ra_fixture: &str, expect: Expect) {
     expect.assert_eq(&actual)
 }
 
+fn check_hover_drop_glue(ra_fixture: &str, expect: Expect) {
+    let (analysis, position) = fixture::position(ra_fixture);
+    let hover = analysis
+        .hover(
+            &HoverConfig { memory_layout: None, drop_glue: true, ..HOVER_BASE_CONFIG },
+            FileRange { file_id: position.file_id, range: TextRange::empty(position.offset) },
+        )
+        .unwrap()
+        .unwrap();
+
+    let content = analysis.db.file_text(position.file_id);
+    let hovered_element = &content[hover.range];
+
+    let actual = format!("*{hovered_element}*\n{}\n", hover.info.markup);
+    expect.assert_eq(&actual)
+}
+
 fn check_hover_no_markdown(ra_fixture: &str, expect: Expect) {
     let (analysis, position) = fixture::position(ra_fixture);
     let hover = analysis
@@ -648,6 +666,36 @@ fn main() { }
     );
 }
 
+#[test]
+fn hover_shows_deprecation_note() {
+    check(
+        r#"
+/// Does the thing.
+#[deprecated(since = "1.2.0", note = "use `bar` instead")]
+pub fn foo() {}
+
+fn main() { foo$0(); }
+"#,
+        expect![[r#"
+            *foo*
+
+            ```rust
+            test
+            ```
+
+            ```rust
+            pub fn foo()
+            ```
+
+            ---
+
+            **Deprecated** since 1.2.0: use `bar` instead
+
+            Does the thing.
+        "#]],
+    );
+}
+
 #[test]
 fn hover_shows_fn_doc_attr_raw_string() {
     check(
@@ -5497,6 +5545,42 @@ fn hover_clippy_lint() {
     )
 }
 
+#[test]
+fn hover_cfg_feature() {
+    check(
+        r#"
+//- /lib.rs crate:foo cfg:feature=serde
+#[cfg(all(unix, feature = "ser$0de"))]
+fn f() {}
+"#,
+        expect![[r#"
+            *"serde"*
+            ```
+            feature = "serde"
+            ```
+            ___
+
+            This feature is enabled for `foo`.
+        "#]],
+    );
+    check(
+        r#"
+//- /lib.rs crate:foo cfg:feature=serde
+#[cfg_attr(feature = "st$0d", derive(Debug))]
+struct S;
+"#,
+        expect![[r#"
+            *"std"*
+            ```
+            feature = "std"
+            ```
+            ___
+
+            This feature is disabled for `foo`.
+        "#]],
+    );
+}
+
 #[test]
 fn hover_attr_path_qualifier() {
     check(
@@ -7412,3 +7496,97 @@ impl Iterator for S {
         "#]],
     );
 }
+
+#[test]
+fn hover_local_drop_glue() {
+    check_hover_drop_glue(
+        r#"
+//- minicore: copy, drop
+struct Guard;
+impl Drop for Guard {
+    fn drop(&mut self) {}
+}
+struct Wrapper(u8, Guard);
+fn main() {
+    let wrapper$0 = Wrapper(0, Guard);
+}
+"#,
+        expect![[r#"
+            *wrapper*
+
+            ```rust
+            // drop: significant
+            let wrapper: Wrapper
+            ```
+        "#]],
+    );
+    check_hover_drop_glue(
+        r#"
+//- minicore: copy, drop
+struct Plain(u8, (i32, bool));
+fn main() {
+    let plain$0 = Plain(0, (1, true));
+}
+"#,
+        expect![[r#"
+            *plain*
+
+            ```rust
+            // drop: no-op
+            let plain: Plain
+            ```
+        "#]],
+    );
+}
+
+#[test]
+fn hover_macro_fragment_specifier() {
+    check(
+        r#"
+macro_rules! m {
+    ($e:ex$0pr) => { $e };
+}
+"#,
+        expect![[r#"
+            *expr*
+            ```rust
+            expr
+            ```
+            ___
+
+            Matches an expression.
+
+            Can be followed by: `=>`, `,` or `;`
+        "#]],
+    );
+}
+
+#[test]
+fn hover_shows_task_count() {
+    check(
+        r#"
+/// Does things.
+fn foo$0() {
+    // TODO: do more things
+    // FIXME: do them faster
+}
+"#,
+        expect![[r#"
+            *foo*
+
+            ```rust
+            test
+            ```
+
+            ```rust
+            fn foo()
+            ```
+
+            ---
+
+            Does things.
+
+            *This function has 2 TODO/FIXME comments*
+        "#]],
+    );
+}
@@ -0,0 +1,70 @@
+//! Computes the cfg options that would make `#[cfg]`-disabled code active, so that clients can
+//! offer to analyze such code as if it were enabled.
+
+use cfg::{CfgAtom, DnfExpr};
+use hir::Semantics;
+use ide_db::{base_db::FilePosition, RootDatabase};
+use syntax::AstNode;
+
+/// Returns the alternative sets of cfg atoms that would each enable the code at `position`.
+pub(crate) fn cfg_enable_hints(db: &RootDatabase, position: FilePosition) -> Vec<Vec<CfgAtom>> {
+    cfg_enable_hints_(db, position).unwrap_or_default()
+}
+
+fn cfg_enable_hints_(db: &RootDatabase, position: FilePosition) -> Option<Vec<Vec<CfgAtom>>> {
+    let sema = Semantics::new(db);
+    let file = sema.parse(position.file_id);
+    let node = file.syntax().token_at_offset(position.offset).left_biased()?.parent()?;
+    let cfg = sema.inactive_cfg(&node)?;
+    let cfg_options = sema.scope(&node)?.krate().cfg(db);
+    let hints = DnfExpr::new(cfg)
+        .compute_enable_hints(&cfg_options)
+        // Overrides can only add cfg options, not remove them.
+        .filter(|diff| diff.disable().is_empty())
+        .map(|diff| diff.enable().to_vec())
+        .collect();
+    Some(hints)
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::{expect, Expect};
+    use itertools::Itertools;
+
+    use crate::fixture;
+
+    fn check(ra_fixture: &str, expect: Expect) {
+        let (analysis, position) = fixture::position(ra_fixture);
+        let hints = analysis.cfg_enable_hints(position).unwrap();
+        let actual = hints.iter().map(|atoms| atoms.iter().join(", ")).join("\n");
+        expect.assert_eq(&actual);
+    }
+
+    #[test]
+    fn hints_for_disabled_item() {
+        check(
+            r#"
+//- /lib.rs crate:foo cfg:feature=a
+#[cfg(any(feature = "b", all(unix, not(feature = "a"))))]
+fn f() {
+    let _ = 92$0;
+}
+"#,
+            expect![[r#"feature = "b""#]],
+        );
+    }
+
+    #[test]
+    fn no_hints_for_active_code() {
+        check(
+            r#"
+//- /lib.rs crate:foo cfg:feature=a
+#[cfg(feature = "a")]
+fn f() {
+    let _ = 92$0;
+}
+"#,
+            expect![[""]],
+        );
+    }
+}
@@ -1,10 +1,12 @@
 //! Implementation of "implicit drop" inlay hints:
 //! ```ignore
 //! let x = vec![2];
+//! let y = vec![3];
 //! if some_condition() {
-//!     /* drop(x) */return;
+//!     /* drop(y, x) */return;
 //! }
 //! ```
+//! Bindings dropped at the same place are listed in the order they are dropped.
 use hir::{
     db::{DefDatabase as _, HirDatabase as _},
     mir::{MirSpan, TerminatorKind},
@@ -14,10 +16,12 @@ use ide_db::{base_db::FileRange, RootDatabase};
 
 use syntax::{
     ast::{self, AstNode},
-    match_ast,
+    match_ast, SmolStr, TextRange,
 };
 
-use crate::{InlayHint, InlayHintLabel, InlayHintPosition, InlayHintsConfig, InlayKind};
+use crate::{
+    InlayHint, InlayHintLabel, InlayHintLabelPart, InlayHintPosition, InlayHintsConfig, InlayKind,
+};
 
 pub(super) fn hints(
     acc: &mut Vec<InlayHint>,
@@ -39,8 +43,11 @@ pub(super) fn hints(
 
     let local_to_binding = mir.local_to_binding_map();
 
+    // the bindings dropped at each position, in drop order
+    let mut drops: Vec<(TextRange, Vec<(SmolStr, Option<FileRange>)>)> = Vec::new();
+
     for (_, bb) in mir.basic_blocks.iter() {
-        let terminator = bb.terminator.as_ref()?;
+        let Some(terminator) = bb.terminator.as_ref() else { continue };
         if let TerminatorKind::Drop { place, .. } = terminator.kind {
             if !place.projection.is_empty() {
                 continue; // Ignore complex cases for now
@@ -88,26 +95,41 @@ pub(super) fn hints(
             if name.starts_with("<ra@") {
                 continue; // Ignore desugared variables
             }
-            let mut label = InlayHintLabel::simple(
-                name,
-                Some(crate::InlayTooltip::String("moz".into())),
-                binding_source,
-            );
-            label.prepend_str("drop(");
-            label.append_str(")");
-            acc.push(InlayHint {
-                range,
-                position: InlayHintPosition::After,
-                pad_left: true,
-                pad_right: true,
-                kind: InlayKind::Drop,
-                needs_resolve: label.needs_resolve(),
-                label,
-                text_edit: None,
-            })
+            let dropped = (name, binding_source);
+            match drops.iter_mut().find(|(it, _)| *it == range) {
+                Some((_, bindings)) if bindings.contains(&dropped) => (),
+                Some((_, bindings)) => bindings.push(dropped),
+                None => drops.push((range, vec![dropped])),
+            }
         }
     }
 
+    for (range, bindings) in drops {
+        let mut label = InlayHintLabel::default();
+        label.append_str("drop(");
+        for (idx, (name, binding_source)) in bindings.into_iter().enumerate() {
+            if idx != 0 {
+                label.append_str(", ");
+            }
+            label.parts.push(InlayHintLabelPart {
+                text: name.into(),
+                linked_location: binding_source,
+                tooltip: None,
+            });
+        }
+        label.append_str(")");
+        acc.push(InlayHint {
+            range,
+            position: InlayHintPosition::After,
+            pad_left: true,
+            pad_right: true,
+            kind: InlayKind::Drop,
+            needs_resolve: label.needs_resolve(),
+            label,
+            text_edit: None,
+        })
+    }
+
     Some(())
 }
 
@@ -209,6 +231,26 @@ mod tests {
       //^ drop(y)
     }
   //^ drop(x)
+"#,
+        );
+    }
+
+    #[test]
+    fn drops_in_order() {
+        check_with_config(
+            ONLY_DROP_CONFIG,
+            r#"
+    struct X;
+    fn f() {
+        let x = X;
+        let y = X;
+        if 2 == 5 {
+            let z = X;
+            return;
+                //^ drop(z, y, x)
+        }
+    }
+  //^ drop(y, x)
 "#,
         );
     }
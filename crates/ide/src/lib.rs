@@ -23,6 +23,7 @@ mod annotations;
 mod call_hierarchy;
 mod doc_links;
 mod expand_macro;
+mod explain_expression;
 mod extend_selection;
 mod fetch_crates;
 mod file_structure;
@@ -33,6 +34,7 @@ mod goto_implementation;
 mod goto_type_definition;
 mod highlight_related;
 mod hover;
+mod inactive_code;
 mod inlay_hints;
 mod interpret_function;
 mod join_lines;
@@ -41,6 +43,7 @@ mod matching_brace;
 mod moniker;
 mod move_item;
 mod parent_module;
+mod paste_imports;
 mod references;
 mod rename;
 mod runnables;
@@ -51,6 +54,9 @@ mod static_index;
 mod status;
 mod syntax_highlighting;
 mod syntax_tree;
+mod tasks;
+mod test_explorer;
+mod type_search;
 mod typing;
 mod view_crate_graph;
 mod view_hir;
@@ -60,7 +66,7 @@ mod view_mir;
 
 use std::ffi::OsStr;
 
-use cfg::CfgOptions;
+use cfg::{CfgAtom, CfgOptions};
 use fetch_crates::CrateInfo;
 use hir::Change;
 use ide_db::{
@@ -68,7 +74,8 @@ use ide_db::{
         salsa::{self, ParallelDatabase},
         CrateOrigin, Env, FileLoader, FileSet, SourceDatabase, VfsPath,
     },
-    symbol_index, FxHashMap, FxIndexSet, LineIndexDatabase,
+    symbol_index::{self, SymbolsDatabase},
+    FxHashMap, FxIndexSet, LineIndexDatabase,
 };
 use syntax::SourceFile;
 use triomphe::Arc;
@@ -79,7 +86,8 @@ use crate::navigation_target::ToNav;
 pub use crate::{
     annotations::{Annotation, AnnotationConfig, AnnotationKind, AnnotationLocation},
     call_hierarchy::CallItem,
-    expand_macro::ExpandedMacro,
+    expand_macro::{ExpandedMacro, ExpandedMacroDiagnostic},
+    explain_expression::{ExplainedAdjustment, ExpressionExplanation},
     file_structure::{StructureNode, StructureNodeKind},
     folding_ranges::{Fold, FoldKind},
     highlight_related::{HighlightRelatedConfig, HighlightedRange},
@@ -100,6 +108,7 @@ pub use crate::{
     },
     move_item::Direction,
     navigation_target::{NavigationTarget, TryToNav, UpmappingResult},
+    paste_imports::{AmbiguousImport, PasteImports},
     prime_caches::ParallelPrimeCachesProgress,
     references::ReferenceSearchResult,
     rename::RenameError,
@@ -110,14 +119,17 @@ pub use crate::{
         tags::{Highlight, HlMod, HlMods, HlOperator, HlPunct, HlTag},
         HighlightConfig, HlRange,
     },
+    test_explorer::{TestItem, TestItemKind},
+    type_search::{TypePattern, TypePatternError, TypeSearchMatch, TypeSearchMatchKind},
 };
 pub use hir::Semantics;
 pub use ide_assists::{
-    Assist, AssistConfig, AssistId, AssistKind, AssistResolveStrategy, SingleResolve,
+    Assist, AssistConfig, AssistId, AssistKind, AssistResolveStrategy, PanicErrorType,
+    PromotedConstPlacement, SingleResolve, WrapReturnTypeCallers,
 };
 pub use ide_completion::{
     CallableSnippets, CompletionConfig, CompletionItem, CompletionItemKind, CompletionRelevance,
-    Snippet, SnippetScope,
+    CompletionRelevanceFuzzyMatch, Snippet, SnippetScope,
 };
 pub use ide_db::{
     base_db::{
@@ -130,6 +142,7 @@ pub use ide_db::{
     search::{ReferenceCategory, SearchScope},
     source_change::{FileSystemEdit, SnippetEdit, SourceChange},
     symbol_index::Query,
+    tasks::{Task, TaskKind, TaskOwner},
     RootDatabase, SymbolKind,
 };
 pub use ide_diagnostics::{
@@ -154,6 +167,15 @@ impl<T> RangeInfo<T> {
     }
 }
 
+/// A symbol of a library, along with the properties workspace symbol queries filter on.
+#[derive(Debug)]
+pub struct LibrarySymbol {
+    pub nav: NavigationTarget,
+    pub is_type: bool,
+    pub is_assoc: bool,
+    pub is_hidden: bool,
+}
+
 /// `AnalysisHost` stores the current state of the world.
 #[derive(Debug)]
 pub struct AnalysisHost {
@@ -186,7 +208,7 @@ impl AnalysisHost {
     }
 
     /// NB: this clears the database
-    pub fn per_query_memory_usage(&mut self) -> Vec<(String, profile::Bytes, usize)> {
+    pub fn per_query_memory_usage(&mut self) -> Vec<(&'static str, String, profile::Bytes, usize)> {
         self.db.per_query_memory_usage()
     }
     pub fn request_cancellation(&mut self) {
@@ -267,11 +289,19 @@ impl Analysis {
         self.with_db(|db| status::status(db, file_id))
     }
 
-    pub fn parallel_prime_caches<F>(&self, num_worker_threads: u8, cb: F) -> Cancellable<()>
+    /// Primes the caches of the workspace, starting with what's needed for `prioritized_files`.
+    pub fn parallel_prime_caches<F>(
+        &self,
+        num_worker_threads: u8,
+        prioritized_files: &[FileId],
+        cb: F,
+    ) -> Cancellable<()>
     where
         F: Fn(ParallelPrimeCachesProgress) + Sync + std::panic::UnwindSafe,
     {
-        self.with_db(move |db| prime_caches::parallel_prime_caches(db, num_worker_threads, &cb))
+        self.with_db(move |db| {
+            prime_caches::parallel_prime_caches(db, num_worker_threads, prioritized_files, &cb)
+        })
     }
 
     /// Gets the text of the source file.
@@ -420,6 +450,36 @@ impl Analysis {
         })
     }
 
+    /// Returns the library source roots, along with the files in them.
+    pub fn library_roots(&self) -> Cancellable<Vec<(SourceRootId, Vec<FileId>)>> {
+        use ide_db::base_db::SourceDatabaseExt;
+        self.with_db(|db| {
+            db.library_roots()
+                .iter()
+                .map(|&root| (root, db.source_root(root).iter().collect()))
+                .collect()
+        })
+    }
+
+    /// Returns the symbols that the workspace symbol search finds in the library `root`.
+    pub fn library_symbols(&self, root: SourceRootId) -> Cancellable<Vec<LibrarySymbol>> {
+        self.with_db(|db| {
+            db.library_symbols(root)
+                .symbols()
+                .iter()
+                .filter_map(|symbol| {
+                    let nav = symbol.try_to_nav(db)?.call_site;
+                    Some(LibrarySymbol {
+                        nav,
+                        is_type: symbol_index::is_type(symbol),
+                        is_assoc: symbol.is_assoc,
+                        is_hidden: symbol_index::is_hidden(db, symbol),
+                    })
+                })
+                .collect()
+        })
+    }
+
     /// Returns the definitions from the symbol at `position`.
     pub fn goto_definition(
         &self,
@@ -541,6 +601,26 @@ impl Analysis {
         self.with_db(|db| db.crate_graph()[crate_id].edition)
     }
 
+    /// Returns the features of the given crate, and whether each of them is enabled.
+    pub fn crate_features(&self, crate_id: CrateId) -> Cancellable<Vec<(String, bool)>> {
+        self.with_db(|db| {
+            let data = &db.crate_graph()[crate_id];
+            let mut features = data
+                .potential_cfg_options
+                .as_ref()
+                .unwrap_or(&data.cfg_options)
+                .get_cfg_values("feature")
+                .map(|feature| {
+                    let enabled =
+                        data.cfg_options.get_cfg_values("feature").any(|it| it == feature);
+                    (feature.to_string(), enabled)
+                })
+                .collect::<Vec<_>>();
+            features.sort();
+            features
+        })
+    }
+
     /// Returns true if this crate has `no_std` or `no_core` specified.
     pub fn is_crate_no_std(&self, crate_id: CrateId) -> Cancellable<bool> {
         self.with_db(|db| hir::db::DefDatabase::crate_def_map(db, crate_id).is_no_std())
@@ -551,11 +631,79 @@ impl Analysis {
         self.with_db(|db| db.crate_graph()[crate_id].root_file_id)
     }
 
+    /// Explains the type of the smallest expression covering `frange`.
+    pub fn explain_expression(
+        &self,
+        frange: FileRange,
+    ) -> Cancellable<Option<ExpressionExplanation>> {
+        self.with_db(|db| explain_expression::explain_expression(db, frange))
+    }
+
+    /// Returns the `TODO`, `FIXME` and `XXX` comments of all workspace files.
+    pub fn workspace_tasks(&self) -> Cancellable<Vec<Task>> {
+        self.with_db(tasks::workspace_tasks)
+    }
+
+    /// Returns the `TODO`, `FIXME` and `XXX` comments of the given file.
+    pub fn file_tasks(&self, file_id: FileId) -> Cancellable<Vec<Task>> {
+        self.with_db(|db| tasks::file_tasks(db, file_id))
+    }
+
+    /// Returns the `TODO`, `FIXME` and `XXX` comments of the innermost item containing
+    /// `position`.
+    pub fn item_tasks(&self, position: FilePosition) -> Cancellable<Vec<Task>> {
+        self.with_db(|db| tasks::item_tasks(db, position))
+    }
+
+    /// Finds the expressions and bindings of the workspace whose type matches `pattern`, like
+    /// `Arc<Mutex<_>>`.
+    pub fn search_by_type(
+        &self,
+        pattern: &str,
+    ) -> Cancellable<Result<Vec<TypeSearchMatch>, TypePatternError>> {
+        self.with_db(|db| {
+            let pattern: TypePattern = pattern.parse()?;
+            Ok(type_search::search_by_type(db, &pattern))
+        })
+    }
+
+    /// Computes the imports needed by code pasted into `frange`. `chosen` lists the paths to
+    /// import for names that can be imported from several places.
+    pub fn paste_imports(
+        &self,
+        config: &AssistConfig,
+        frange: FileRange,
+        chosen: &[String],
+    ) -> Cancellable<Option<PasteImports>> {
+        self.with_db(|db| paste_imports::paste_imports(db, config, frange, chosen))
+    }
+
+    /// Returns the alternative sets of cfg options that would each make the `#[cfg]`-disabled
+    /// code at `position` active.
+    pub fn cfg_enable_hints(&self, position: FilePosition) -> Cancellable<Vec<Vec<CfgAtom>>> {
+        self.with_db(|db| inactive_code::cfg_enable_hints(db, position))
+    }
+
     /// Returns the set of possible targets to run for the current file.
     pub fn runnables(&self, file_id: FileId) -> Cancellable<Vec<Runnable>> {
         self.with_db(|db| runnables::runnables(db, file_id))
     }
 
+    /// Returns the workspace crates, as the roots of the test tree.
+    pub fn discover_test_roots(&self) -> Cancellable<Vec<TestItem>> {
+        self.with_db(test_explorer::discover_test_roots)
+    }
+
+    /// Returns the test tree of the workspace crate with the given name.
+    pub fn discover_tests_in_crate(&self, crate_name: &str) -> Cancellable<Vec<TestItem>> {
+        self.with_db(|db| test_explorer::discover_tests_in_crate(db, crate_name))
+    }
+
+    /// Returns the tests declared in the given file and the modules containing them.
+    pub fn discover_tests_in_file(&self, file_id: FileId) -> Cancellable<Vec<TestItem>> {
+        self.with_db(|db| test_explorer::discover_tests_in_file(db, file_id))
+    }
+
     /// Returns the set of tests for the given file position.
     pub fn related_tests(
         &self,
@@ -669,6 +817,21 @@ impl Analysis {
         })
     }
 
+    /// Returns the expansion of the macro that the diagnostic at `frange` was reported in, along
+    /// with the position of the diagnostic within the expansion.
+    pub fn expand_macro_diagnostic(
+        &self,
+        config: &DiagnosticsConfig,
+        frange: FileRange,
+    ) -> Cancellable<Option<ExpandedMacroDiagnostic>> {
+        self.with_db(|db| {
+            ide_diagnostics::diagnostics(db, config, &AssistResolveStrategy::None, frange.file_id)
+                .into_iter()
+                .filter(|it| it.range == frange)
+                .find_map(|it| expand_macro::expand_macro_diagnostic(db, it.main_node?))
+        })
+    }
+
     /// Returns the edit required to rename reference at the position to the new
     /// name.
     pub fn rename(
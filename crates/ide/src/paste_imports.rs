@@ -0,0 +1,262 @@
+use hir::Semantics;
+use ide_assists::AssistConfig;
+use ide_db::{
+    base_db::FileRange,
+    helpers::mod_path_to_ast,
+    imports::{
+        import_assets::{ImportAssets, ImportCandidate},
+        insert_use::{insert_use, ImportScope},
+    },
+    source_change::{SourceChange, SourceChangeBuilder},
+    FxHashSet, RootDatabase,
+};
+use syntax::{ast, AstNode, NodeOrToken, TextRange};
+
+/// The imports needed by pasted code.
+#[derive(Debug)]
+pub struct PasteImports {
+    /// Inserts the imports of all names that have a single candidate or a chosen one.
+    pub edit: SourceChange,
+    /// Names with several import candidates, none of which was chosen.
+    pub ambiguous: Vec<AmbiguousImport>,
+}
+
+#[derive(Debug)]
+pub struct AmbiguousImport {
+    pub name: String,
+    pub range: TextRange,
+    pub candidates: Vec<String>,
+}
+
+// Feature: Import On Paste
+//
+// Adds `use` items for the names in pasted code that are unresolved in the file they are pasted
+// into, but can be imported from the crate or its dependencies. When a name can be imported from
+// several paths, the editor asks which one to use.
+pub(crate) fn paste_imports(
+    db: &RootDatabase,
+    config: &AssistConfig,
+    frange: FileRange,
+    chosen: &[String],
+) -> Option<PasteImports> {
+    let sema = Semantics::new(db);
+    let file = sema.parse(frange.file_id);
+    let covering = match file.syntax().covering_element(frange.range) {
+        NodeOrToken::Node(it) => it,
+        NodeOrToken::Token(it) => it.parent()?,
+    };
+    let scope = ImportScope::find_insert_use_container(&covering, &sema)?;
+
+    let mut seen = FxHashSet::default();
+    let mut imports = Vec::new();
+    let mut ambiguous = Vec::new();
+    let nodes = covering
+        .descendants()
+        .filter(|it| frange.range.contains_range(it.text_range()))
+        .filter(|it| !it.ancestors().any(|it| ast::Use::can_cast(it.kind())));
+    for node in nodes {
+        let assets = if let Some(path) = ast::Path::cast(node.clone()) {
+            if path.parent_path().is_some() {
+                continue;
+            }
+            ImportAssets::for_exact_path(&path, &sema)
+        } else if let Some(call) = ast::MethodCallExpr::cast(node.clone()) {
+            ImportAssets::for_method_call(&call, &sema)
+        } else {
+            continue;
+        };
+        let Some(assets) = assets else { continue };
+        let name = match assets.import_candidate() {
+            ImportCandidate::Path(candidate) => candidate.name.text().to_owned(),
+            ImportCandidate::TraitAssocItem(candidate)
+            | ImportCandidate::TraitMethod(candidate) => {
+                candidate.assoc_item_name.text().to_owned()
+            }
+        };
+        if !seen.insert(name.clone()) {
+            continue;
+        }
+
+        let mut candidates = assets
+            .search_for_imports(
+                &sema,
+                config.insert_use.prefix_kind,
+                config.prefer_no_std,
+                config.prefer_prelude,
+            )
+            .map(|it| it.import_path)
+            .collect::<Vec<_>>();
+        candidates.sort();
+        candidates.dedup();
+        let import = match &*candidates {
+            [] => continue,
+            [it] => it.clone(),
+            _ => match candidates.iter().find(|it| chosen.contains(&it.display(db).to_string())) {
+                Some(it) => it.clone(),
+                None => {
+                    ambiguous.push(AmbiguousImport {
+                        name,
+                        range: node.text_range(),
+                        candidates: candidates
+                            .iter()
+                            .map(|it| it.display(db).to_string())
+                            .collect(),
+                    });
+                    continue;
+                }
+            },
+        };
+        imports.push(import);
+    }
+
+    let mut builder = SourceChangeBuilder::new(frange.file_id);
+    if !imports.is_empty() {
+        let scope = match scope {
+            ImportScope::File(it) => ImportScope::File(builder.make_mut(it)),
+            ImportScope::Module(it) => ImportScope::Module(builder.make_mut(it)),
+            ImportScope::Block(it) => ImportScope::Block(builder.make_mut(it)),
+        };
+        for import in imports {
+            insert_use(&scope, mod_path_to_ast(&import), &config.insert_use);
+        }
+    }
+    Some(PasteImports { edit: builder.finish(), ambiguous })
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::{expect, Expect};
+    use hir::PrefixKind;
+    use ide_db::imports::insert_use::{ImportGranularity, InsertUseConfig};
+
+    use crate::{
+        fixture, AssistConfig, PanicErrorType, PromotedConstPlacement, WrapReturnTypeCallers,
+    };
+
+    fn config() -> AssistConfig {
+        AssistConfig {
+            snippet_cap: None,
+            allowed: None,
+            insert_use: InsertUseConfig {
+                granularity: ImportGranularity::Crate,
+                prefix_kind: PrefixKind::Plain,
+                enforce_granularity: true,
+                group: true,
+                skip_glob_imports: true,
+            },
+            prefer_no_std: false,
+            prefer_prelude: true,
+            assist_emit_must_use: false,
+            group_missing_match_arms: false,
+            smart_impl_member_stubs: false,
+            wrap_return_type_callers: WrapReturnTypeCallers::None,
+            promoted_const_placement: PromotedConstPlacement::BeforeItem,
+            panic_error_type: PanicErrorType::Enum,
+        }
+    }
+
+    fn check(ra_fixture: &str, chosen: &[&str], expect: Expect) {
+        let (analysis, frange) = fixture::range(ra_fixture);
+        let chosen = chosen.iter().map(|it| it.to_string()).collect::<Vec<_>>();
+        let res = analysis.paste_imports(&config(), frange, &chosen).unwrap().unwrap();
+        let mut text = analysis.file_text(frange.file_id).unwrap().to_string();
+        if let Some((edit, _)) = res.edit.get_source_and_snippet_edit(frange.file_id) {
+            edit.apply(&mut text);
+        }
+        for import in res.ambiguous {
+            text.push_str(&format!(
+                "// ambiguous {}: {}\n",
+                import.name,
+                import.candidates.join(", ")
+            ));
+        }
+        expect.assert_eq(&text);
+    }
+
+    #[test]
+    fn imports_unresolved_names() {
+        check(
+            r#"
+mod a {
+    pub struct Foo;
+    pub trait Ext {
+        fn ext(&self) {}
+    }
+    impl Ext for Foo {}
+}
+mod b {
+    pub struct Bar;
+}
+mod c {
+    pub struct Bar;
+}
+
+fn main() {
+    $0let _ = Foo;
+    a::Foo.ext();
+    let _ = Bar;$0
+}
+"#,
+            &[],
+            expect![[r#"
+
+                use a::{Ext, Foo};
+
+                mod a {
+                    pub struct Foo;
+                    pub trait Ext {
+                        fn ext(&self) {}
+                    }
+                    impl Ext for Foo {}
+                }
+                mod b {
+                    pub struct Bar;
+                }
+                mod c {
+                    pub struct Bar;
+                }
+
+                fn main() {
+                    let _ = Foo;
+                    a::Foo.ext();
+                    let _ = Bar;
+                }
+                // ambiguous Bar: b::Bar, c::Bar
+            "#]],
+        );
+    }
+
+    #[test]
+    fn imports_chosen_candidate() {
+        check(
+            r#"
+mod b {
+    pub struct Bar;
+}
+mod c {
+    pub struct Bar;
+}
+
+fn main() {
+    $0let _ = Bar;$0
+}
+"#,
+            &["c::Bar"],
+            expect![[r#"
+
+                use c::Bar;
+
+                mod b {
+                    pub struct Bar;
+                }
+                mod c {
+                    pub struct Bar;
+                }
+
+                fn main() {
+                    let _ = Bar;
+                }
+            "#]],
+        );
+    }
+}
@@ -2,6 +2,9 @@
 //! sometimes is counter productive when, for example, the first goto definition
 //! request takes longer to compute. This modules implemented prepopulation of
 //! various caches, it's not really advanced at the moment.
+//!
+//! Crates are primed in dependency order, those needed for the currently open
+//! files first.
 mod topologic_sort;
 
 use std::time::Duration;
@@ -10,7 +13,7 @@ use hir::db::DefDatabase;
 use ide_db::{
     base_db::{
         salsa::{Database, ParallelDatabase, Snapshot},
-        Cancelled, CrateGraph, CrateId, SourceDatabase, SourceDatabaseExt,
+        Cancelled, CrateGraph, CrateId, FileId, SourceDatabase, SourceDatabaseExt,
     },
     FxHashSet, FxIndexMap,
 };
@@ -31,13 +34,24 @@ pub struct ParallelPrimeCachesProgress {
 pub(crate) fn parallel_prime_caches(
     db: &RootDatabase,
     num_worker_threads: u8,
+    prioritized_files: &[FileId],
     cb: &(dyn Fn(ParallelPrimeCachesProgress) + Sync),
 ) {
     let _p = tracing::span!(tracing::Level::INFO, "prime_caches").entered();
 
     let graph = db.crate_graph();
+    // The crates of the given files and their direct dependencies, like for the workspace crates
+    // below, as transitive dependencies aren't visible from these files either.
+    let prioritized = prioritized_files
+        .iter()
+        .flat_map(|&file_id| db.relevant_crates(file_id).iter().copied().collect::<Vec<_>>())
+        .flat_map(|krate| {
+            std::iter::once(krate).chain(graph[krate].dependencies.iter().map(|dep| dep.crate_id))
+        })
+        .collect::<FxHashSet<_>>();
     let mut crates_to_prime = {
-        let crate_ids = compute_crates_to_prime(db, &graph);
+        let mut crate_ids = compute_crates_to_prime(db, &graph);
+        crate_ids.extend(prioritized.iter().copied());
 
         let mut builder = topologic_sort::TopologicalSortIter::builder();
 
@@ -92,6 +106,10 @@ pub(crate) fn parallel_prime_caches(
 
     let crates_total = crates_to_prime.pending();
     let mut crates_done = 0;
+    // Crates are only handed to the workers once one of them is idle, so that prioritized crates
+    // becoming ready don't have to wait behind all others.
+    let mut ready = Vec::new();
+    let mut crates_in_flight = 0;
 
     // an index map is used to preserve ordering so we can sort the progress report in order of
     // "longest crate to index" first
@@ -101,13 +119,17 @@ pub(crate) fn parallel_prime_caches(
     while crates_done < crates_total {
         db.unwind_if_cancelled();
 
-        for crate_id in &mut crates_to_prime {
+        ready.extend(&mut crates_to_prime);
+        while crates_in_flight < num_worker_threads.max(1) && !ready.is_empty() {
+            let idx = ready.iter().position(|it| prioritized.contains(it)).unwrap_or(0);
+            let crate_id = ready.remove(idx);
             work_sender
                 .send((
                     crate_id,
                     graph[crate_id].display_name.as_deref().unwrap_or_default().to_owned(),
                 ))
                 .ok();
+            crates_in_flight += 1;
         }
 
         // recv_timeout is somewhat a hack, we need a way to from this thread check to see if the current salsa revision
@@ -132,6 +154,7 @@ pub(crate) fn parallel_prime_caches(
                 crates_currently_indexing.remove(&crate_id);
                 crates_to_prime.mark_done(crate_id);
                 crates_done += 1;
+                crates_in_flight -= 1;
             }
         };
 
@@ -8,11 +8,14 @@
 //! for text occurrences of the identifier. If there's an `ast::NameRef`
 //! at the index that the match starts at and its tree parent is
 //! resolved to the search element definition, we get a reference.
+//! Methods of operator traits additionally get their operator expressions
+//! (`a + b`, `a[i]`, `-a`, ...) reported as references.
 
 use hir::{DescendPreference, PathResolution, Semantics};
 use ide_db::{
     base_db::FileId,
     defs::{Definition, NameClass, NameRefClass},
+    ffi,
     search::{ReferenceCategory, SearchScope, UsageSearchResult},
     RootDatabase,
 };
@@ -59,8 +62,12 @@ pub(crate) fn find_all_refs(
     let syntax = sema.parse(position.file_id).syntax().clone();
     let make_searcher = |literal_search: bool| {
         move |def: Definition| {
-            let mut usages =
-                def.usages(sema).set_scope(search_scope.as_ref()).include_self_refs().all();
+            let mut usages = def
+                .usages(sema)
+                .set_scope(search_scope.as_ref())
+                .include_self_refs()
+                .include_operator_refs()
+                .all();
 
             if literal_search {
                 retain_adt_literal_usages(&mut usages, def, sema);
@@ -116,11 +123,28 @@ pub(crate) fn find_all_refs(
         }
         None => {
             let search = make_searcher(false);
-            Some(find_defs(sema, &syntax, position.offset)?.into_iter().map(search).collect())
+            Some(
+                find_defs(sema, &syntax, position.offset)?
+                    .into_iter()
+                    .flat_map(|def| with_ffi_links(sema.db, def))
+                    .unique()
+                    .map(search)
+                    .collect(),
+            )
         }
     }
 }
 
+/// Returns `def` and, for functions linked across an FFI boundary, the declarations or definitions
+/// on the other side, whose uses refer to the same symbol.
+fn with_ffi_links(db: &RootDatabase, def: Definition) -> Vec<Definition> {
+    let linked = match def {
+        Definition::Function(func) => ffi::linked_functions(db, func),
+        _ => Vec::new(),
+    };
+    std::iter::once(def).chain(linked.into_iter().map(Definition::from)).collect()
+}
+
 pub(crate) fn find_defs<'a>(
     sema: &'a Semantics<'_, RootDatabase>,
     syntax: &SyntaxNode,
@@ -2166,4 +2190,74 @@ fn test() {
             "#]],
         );
     }
+
+    #[test]
+    fn test_find_all_refs_across_ffi_boundary() {
+        check(
+            r#"
+extern "C" {
+    fn frobnicate();
+}
+fn main() {
+    unsafe { frobnicate() };
+}
+#[no_mangle]
+extern "C" fn frobnicate$0() {}
+"#,
+            expect![[r#"
+                frobnicate Function FileId(0) 79..121 106..116
+
+                (no references)
+
+
+                frobnicate Function FileId(0) 17..33 20..30
+
+                FileId(0) 61..71
+            "#]],
+        );
+    }
+
+    #[test]
+    fn test_find_all_refs_bin_op_impl() {
+        check(
+            r#"
+//- minicore: add
+struct S;
+impl core::ops::Add for S {
+    type Output = S;
+    fn add$0(self, _: S) -> S { S }
+}
+fn f(a: S, b: S) -> S {
+    a + b
+}
+"#,
+            expect![[r#"
+                add Function FileId(0) 63..92 66..69
+
+                FileId(0) 123..124
+            "#]],
+        );
+    }
+
+    #[test]
+    fn test_find_all_refs_index_op_impl() {
+        check(
+            r#"
+//- minicore: index
+struct S;
+impl core::ops::Index<usize> for S {
+    type Output = S;
+    fn index$0(&self, _: usize) -> &S { self }
+}
+fn f(s: S) {
+    let _ = &s[0];
+}
+"#,
+            expect![[r#"
+                index Function FileId(0) 72..112 75..80
+
+                FileId(0) 140..141
+            "#]],
+        );
+    }
 }
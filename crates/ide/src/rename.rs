@@ -10,12 +10,14 @@ use ide_db::{
     defs::{Definition, NameClass, NameRefClass},
     rename::{bail, format_err, source_edit_from_references, IdentifierKind},
     source_change::SourceChangeBuilder,
+    syntax_helpers::macro_metavars::{self, MacroRule},
     RootDatabase,
 };
 use itertools::Itertools;
 use stdx::{always, never};
 use syntax::{
-    ast, utils::is_raw_identifier, AstNode, SmolStr, SyntaxKind, SyntaxNode, TextRange, TextSize,
+    ast, utils::is_raw_identifier, AstNode, SmolStr, SyntaxKind, SyntaxNode, SyntaxToken,
+    TextRange, TextSize,
 };
 
 use text_edit::TextEdit;
@@ -36,6 +38,10 @@ pub(crate) fn prepare_rename(
     let source_file = sema.parse(position.file_id);
     let syntax = source_file.syntax();
 
+    if let Some((token, _)) = macro_metavar_at(syntax, position) {
+        return Ok(RangeInfo::new(token.text_range(), ()));
+    }
+
     let res = find_definitions(&sema, syntax, position)?
         .map(|(frange, kind, def)| {
             // ensure all ranges are valid
@@ -90,6 +96,10 @@ pub(crate) fn rename(
     let source_file = sema.parse(position.file_id);
     let syntax = source_file.syntax();
 
+    if let Some((token, rule)) = macro_metavar_at(syntax, position) {
+        return rename_macro_metavar(position.file_id, &token, &rule, new_name);
+    }
+
     let defs = find_definitions(&sema, syntax, position)?;
     let alias_fallback = alias_fallback(syntax, position, new_name);
 
@@ -167,6 +177,37 @@ pub(crate) fn will_rename_file(
     Some(change)
 }
 
+fn macro_metavar_at(
+    syntax: &SyntaxNode,
+    FilePosition { offset, .. }: FilePosition,
+) -> Option<(SyntaxToken, MacroRule)> {
+    syntax.token_at_offset(offset).find_map(|token| {
+        let rule = macro_metavars::metavar_rule(&token)?;
+        Some((token, rule))
+    })
+}
+
+/// Metavariables are scoped to a single rule of a `macro_rules!` definition, so renaming one
+/// only touches the declaration and the uses within that rule.
+fn rename_macro_metavar(
+    file_id: FileId,
+    token: &SyntaxToken,
+    rule: &MacroRule,
+    new_name: &str,
+) -> RenameResult<SourceChange> {
+    let new_name = new_name.strip_prefix('$').unwrap_or(new_name);
+    match IdentifierKind::classify(new_name)? {
+        IdentifierKind::Ident => (),
+        _ => bail!("Invalid name `{}`: not a valid metavariable name", new_name),
+    }
+
+    let mut builder = TextEdit::builder();
+    for occurrence in rule.metavar_occurrences(token.text()) {
+        builder.replace(occurrence.text_range(), new_name.to_owned());
+    }
+    Ok(SourceChange::from_text_edit(file_id, builder.finish()))
+}
+
 // FIXME: Should support `extern crate`.
 fn alias_fallback(
     syntax: &SyntaxNode,
@@ -2779,4 +2820,36 @@ fn main() { let _: Baz; }
 "#,
         )
     }
+
+    #[test]
+    fn rename_macro_metavariable() {
+        check(
+            "$value",
+            r#"
+macro_rules! m {
+    ($e$0:expr) => { $e + $e };
+    ($e:expr, $f:expr) => { $e * $f };
+}
+"#,
+            r#"
+macro_rules! m {
+    ($value:expr) => { $value + $value };
+    ($e:expr, $f:expr) => { $e * $f };
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn rename_macro_metavariable_invalid_name() {
+        check(
+            "_",
+            r#"
+macro_rules! m {
+    ($e:expr) => { $e$0 };
+}
+"#,
+            "error: Invalid name `_`: not a valid metavariable name",
+        );
+    }
 }
@@ -486,7 +486,7 @@ pub struct TestAttr {
 }
 
 impl TestAttr {
-    fn from_fn(db: &dyn HirDatabase, fn_def: hir::Function) -> TestAttr {
+    pub(crate) fn from_fn(db: &dyn HirDatabase, fn_def: hir::Function) -> TestAttr {
         TestAttr { ignore: fn_def.is_ignore(db) }
     }
 }
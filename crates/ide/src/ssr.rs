@@ -261,4 +261,45 @@ mod tests {
         "#]]
         .assert_debug_eq(&apply_in_workspace_assist);
     }
+
+    #[test]
+    fn diagnostic_ssr_rule_fixes_workspace() {
+        let (analysis, position) = crate::fixture::position(
+            r#"
+//- minicore: iterators
+fn foo() {
+    let _a = core::iter::repeat(()).filter_map(|()| Some(1)).$0next();
+    let _b = core::iter::repeat(()).filter_map(|()| Some(2)).next();
+}
+"#,
+        );
+        let config = ide_diagnostics::DiagnosticsConfig::test_sample();
+        let rules = analysis
+            .diagnostics(&config, AssistResolveStrategy::None, position.file_id)
+            .unwrap()
+            .into_iter()
+            .filter_map(|it| it.ssr_rule)
+            .collect::<Vec<_>>();
+        expect![[r#"
+            [
+                "core::iter::Iterator::filter_map($iter, $f).next() ==>> $iter.find_map($f)",
+                "core::iter::Iterator::filter_map($iter, $f).next() ==>> $iter.find_map($f)",
+            ]
+        "#]]
+        .assert_debug_eq(&rules);
+
+        let change = analysis
+            .structural_search_replace(&rules[0], false, position, vec![])
+            .unwrap()
+            .unwrap();
+        let mut text = analysis.file_text(position.file_id).unwrap().to_string();
+        change.get_source_and_snippet_edit(position.file_id).unwrap().0.apply(&mut text);
+        expect![[r#"
+            fn foo() {
+                let _a = core::iter::repeat(()).find_map(|()| Some(1));
+                let _b = core::iter::repeat(()).find_map(|()| Some(2));
+            }
+        "#]]
+        .assert_eq(&text);
+    }
 }
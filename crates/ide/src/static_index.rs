@@ -166,6 +166,7 @@ impl StaticIndex<'_> {
             documentation: true,
             keywords: true,
             format: crate::HoverDocFormat::Markdown,
+            drop_glue: false,
         };
         let tokens = tokens.filter(|token| {
             matches!(
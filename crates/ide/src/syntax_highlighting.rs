@@ -8,6 +8,7 @@ mod format;
 mod highlight;
 mod inject;
 mod macro_;
+mod string_languages;
 
 mod html;
 #[cfg(test)]
@@ -56,6 +57,9 @@ pub struct HighlightConfig {
     pub specialize_operator: bool,
     /// Whether to inject highlights into doc comments
     pub inject_doc_comment: bool,
+    /// Whether to inject regex and SQL highlights into string literals marked with a
+    /// `language=<name>` comment
+    pub inject_string_languages: bool,
     /// Whether to highlight the macro call bang
     pub macro_bang: bool,
     /// Whether to highlight unresolved things be their syntax
@@ -139,6 +143,7 @@ pub struct HighlightConfig {
 // keyword:: Emitted for keywords.
 // label:: Emitted for labels.
 // lifetime:: Emitted for lifetimes.
+// macroMetavariable:: Emitted for the metavariables of `macro_rules!` definitions, the `x` in `$x:expr`.
 // parameter:: Emitted for non-self function parameters.
 // property:: Emitted for struct and union fields.
 // selfKeyword:: Emitted for the self function parameter and self path-specifier.
@@ -165,8 +170,9 @@ pub struct HighlightConfig {
 // crateRoot:: Emitted for crate names, like `serde` and `crate`.
 // declaration:: Emitted for names of definitions, like `foo` in `fn foo() {}`.
 // defaultLibrary:: Emitted for items from built-in crates (std, core, alloc, test and proc_macro).
+// deprecated:: Emitted for items marked `#[deprecated]`, including methods of deprecated traits.
 // documentation:: Emitted for documentation comments.
-// injected:: Emitted for doc-string injected highlighting like rust source blocks in documentation.
+// injected:: Emitted for injected highlighting like rust source blocks in documentation or regexes in marked string literals.
 // intraDocLink:: Emitted for intra doc links in doc-strings.
 // library:: Emitted for items that are defined outside of the current crate.
 // macro::  Emitted for tokens inside macro calls.
@@ -427,8 +433,13 @@ fn traverse(
         };
 
         // FIXME: do proper macro def highlighting https://github.com/rust-lang/rust-analyzer/issues/6232
-        // Skip metavariables from being highlighted to prevent keyword highlighting in them
-        if descended_element.as_token().and_then(|t| macro_highlighter.highlight(t)).is_some() {
+        // Metavariables are highlighted on their own to prevent keyword highlighting in them
+        if let Some(range) =
+            descended_element.as_token().and_then(|t| macro_highlighter.highlight(t))
+        {
+            if range.highlight.tag != HlTag::None {
+                hl.add(range);
+            }
             continue;
         }
 
@@ -445,6 +456,11 @@ fn traverse(
                     {
                         continue;
                     }
+                    if config.inject_string_languages
+                        && inject::marked_string(hl, &string).is_some()
+                    {
+                        continue;
+                    }
                     highlight_format_string(hl, sema, krate, &string, &expanded_string, range);
 
                     if !string.is_raw() {
@@ -529,6 +529,10 @@ pub(super) fn highlight_def(
         h |= HlMod::DefaultLibrary;
     }
 
+    if def.deprecation(db).is_some() {
+        h |= HlMod::Deprecated;
+    }
+
     h
 }
 
@@ -580,6 +584,10 @@ fn highlight_method_call(
         h |= HlMod::DefaultLibrary;
     }
 
+    if Definition::Function(func).deprecation(sema.db).is_some() {
+        h |= HlMod::Deprecated;
+    }
+
     if let Some(self_param) = func.self_param(sema.db) {
         match self_param.access(sema.db) {
             hir::Access::Shared => h |= HlMod::Reference,
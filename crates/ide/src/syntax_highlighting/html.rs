@@ -32,6 +32,7 @@ pub(crate) fn highlight_as_html(db: &RootDatabase, file_id: FileId, rainbow: boo
             specialize_operator: true,
             operator: true,
             inject_doc_comment: true,
+            inject_string_languages: true,
             macro_bang: true,
             syntactic_name_ref_highlighting: false,
         },
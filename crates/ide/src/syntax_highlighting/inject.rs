@@ -1,6 +1,6 @@
-//! "Recursive" Syntax highlighting for code in doctests and fixtures.
+//! "Recursive" Syntax highlighting for code in doctests, fixtures and marked string literals.
 
-use std::mem;
+use std::{iter, mem};
 
 use either::Either;
 use hir::{InFile, Semantics};
@@ -10,12 +10,14 @@ use ide_db::{
 };
 use syntax::{
     ast::{self, AstNode, IsString, QuoteOffsets},
-    AstToken, NodeOrToken, SyntaxNode, TextRange, TextSize,
+    AstToken, NodeOrToken, SyntaxKind, SyntaxNode, SyntaxToken, TextRange, TextSize,
 };
 
 use crate::{
     doc_links::{doc_attributes, extract_definitions_from_docs, resolve_doc_path_for_def},
-    syntax_highlighting::{highlights::Highlights, injector::Injector, HighlightConfig},
+    syntax_highlighting::{
+        highlights::Highlights, injector::Injector, string_languages::Language, HighlightConfig,
+    },
     Analysis, HlMod, HlRange, HlTag, RootDatabase,
 };
 
@@ -78,6 +80,7 @@ pub(super) fn ra_fixture(
                 specialize_punctuation: config.specialize_punctuation,
                 specialize_operator: config.operator,
                 inject_doc_comment: config.inject_doc_comment,
+                inject_string_languages: config.inject_string_languages,
                 macro_bang: config.macro_bang,
             },
             tmp_file_id,
@@ -100,6 +103,58 @@ pub(super) fn ra_fixture(
     Some(())
 }
 
+/// Injection of regex or SQL highlighting into string literals marked with a `language=<name>`
+/// comment, either right before the literal or above the statement or item containing it.
+///
+/// Only literals whose value is their source text are highlighted, so that the ranges don't need
+/// to be remapped around escapes.
+pub(super) fn marked_string(hl: &mut Highlights, literal: &ast::String) -> Option<()> {
+    let language = marker_language(literal)?;
+    let value = literal.value()?;
+    if value != literal.text_without_quotes() {
+        return None;
+    }
+    for (range, tag) in language.highlight(&value) {
+        if let Some(range) = literal.map_range_up(range) {
+            hl.add(HlRange { range, highlight: tag | HlMod::Injected, binding_hash: None });
+        }
+    }
+    Some(())
+}
+
+fn marker_language(literal: &ast::String) -> Option<Language> {
+    let comment_language = |token: &SyntaxToken| {
+        ast::Comment::cast(token.clone()).and_then(|it| Language::from_marker(it.text()))
+    };
+    let preceding_comments = |token: SyntaxToken| {
+        iter::successors(token.prev_token(), |it| it.prev_token())
+            .take_while(|it| matches!(it.kind(), SyntaxKind::WHITESPACE | SyntaxKind::COMMENT))
+            .find_map(|it| comment_language(&it))
+    };
+
+    // `Regex::new(/* language=regex */ r"\d+")`
+    let direct = iter::successors(literal.syntax().prev_token(), |it| it.prev_token())
+        .find(|it| it.kind() != SyntaxKind::WHITESPACE)
+        .and_then(|it| comment_language(&it));
+    if direct.is_some() {
+        return direct;
+    }
+
+    // `// language=sql` above a `let` statement or a `const`
+    let owner = literal.syntax().parent_ancestors().find(|it| ast::Stmt::can_cast(it.kind()))?;
+    if ast::Item::can_cast(owner.kind())
+        && !matches!(owner.kind(), SyntaxKind::CONST | SyntaxKind::STATIC)
+    {
+        return None;
+    }
+    let first_token = owner.first_token()?;
+    // Comments above items are part of the item itself.
+    iter::successors(Some(first_token.clone()), |it| it.next_token())
+        .take_while(|it| matches!(it.kind(), SyntaxKind::WHITESPACE | SyntaxKind::COMMENT))
+        .find_map(|it| comment_language(&it))
+        .or_else(|| preceding_comments(first_token))
+}
+
 const RUSTDOC_FENCE_LENGTH: usize = 3;
 const RUSTDOC_FENCES: [&str; 2] = ["```", "~~~"];
 
@@ -239,6 +294,7 @@ pub(super) fn doc_comment(
                 specialize_punctuation: config.specialize_punctuation,
                 specialize_operator: config.operator,
                 inject_doc_comment: config.inject_doc_comment,
+                inject_string_languages: config.inject_string_languages,
                 macro_bang: config.macro_bang,
             },
             tmp_file_id,
@@ -19,14 +19,24 @@ impl MacroHighlighter {
         }
     }
 
+    /// Highlights metavariables, tokens of `$crate` get `HlTag::None` to keep them from being
+    /// highlighted as keywords.
     pub(super) fn highlight(&self, token: &SyntaxToken) -> Option<HlRange> {
         if let Some(state) = self.state.as_ref() {
             if matches!(state.rule_state, RuleState::Matcher | RuleState::Expander) {
                 if let Some(range) = is_metavariable(token) {
+                    if token.kind() == T![crate] {
+                        return Some(HlRange {
+                            range,
+                            highlight: HlTag::None.into(),
+                            binding_hash: None,
+                        });
+                    }
                     return Some(HlRange {
                         range,
-                        highlight: HlTag::UnresolvedReference.into(),
-                        binding_hash: None,
+                        highlight: HlTag::MacroMetavariable.into(),
+                        // metavariables of the same name share a color
+                        binding_hash: Some(hash(token.text())),
                     });
                 }
             }
@@ -115,6 +125,14 @@ fn update_macro_state(state: &mut MacroMatcherParseState, tok: &SyntaxToken) {
     }
 }
 
+fn hash(name: &str) -> u64 {
+    use std::{collections::hash_map::DefaultHasher, hash::Hasher};
+
+    let mut hasher = DefaultHasher::new();
+    hasher.write(name.as_bytes());
+    hasher.finish()
+}
+
 fn is_metavariable(token: &SyntaxToken) -> Option<TextRange> {
     match token.kind() {
         kind if kind == SyntaxKind::IDENT || kind.is_keyword() => {
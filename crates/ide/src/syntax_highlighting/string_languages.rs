@@ -0,0 +1,291 @@
+//! Lightweight highlighting of regular expressions and SQL queries in string literals marked with a
+//! `// language=regex` or `// language=sql` comment.
+
+use syntax::{TextRange, TextSize};
+
+use crate::{HlOperator, HlPunct, HlTag};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum Language {
+    Regex,
+    Sql,
+}
+
+impl Language {
+    /// Parses the language of a `language=<name>` marker comment.
+    pub(super) fn from_marker(comment: &str) -> Option<Language> {
+        let comment = comment.trim_start_matches(['/', '*', '!']).trim_end_matches(['*', '/']);
+        let name = comment.trim().strip_prefix("language")?.trim_start().strip_prefix('=')?;
+        match name.trim().to_ascii_lowercase().as_str() {
+            "regex" | "regexp" => Some(Language::Regex),
+            "sql" => Some(Language::Sql),
+            _ => None,
+        }
+    }
+
+    /// Splits `text` into highlighted ranges, relative to the start of `text`.
+    pub(super) fn highlight(self, text: &str) -> Vec<(TextRange, HlTag)> {
+        match self {
+            Language::Regex => regex(text),
+            Language::Sql => sql(text),
+        }
+    }
+}
+
+fn regex(text: &str) -> Vec<(TextRange, HlTag)> {
+    let mut res = Vec::new();
+    let mut chars = text.char_indices().peekable();
+    let mut in_class = false;
+    let mut push = |start: usize, len: usize, tag| {
+        res.push((TextRange::at(TextSize::from(start as u32), TextSize::from(len as u32)), tag))
+    };
+    while let Some((idx, c)) = chars.next() {
+        match c {
+            '\\' => {
+                let len = chars.next().map_or(0, |(_, c)| c.len_utf8());
+                push(idx, 1 + len, HlTag::EscapeSequence);
+            }
+            '[' if !in_class => {
+                in_class = true;
+                push(idx, 1, HlTag::Punctuation(HlPunct::Bracket));
+                if let Some(&(idx, '^')) = chars.peek() {
+                    chars.next();
+                    push(idx, 1, HlTag::Operator(HlOperator::Other));
+                }
+            }
+            ']' if in_class => {
+                in_class = false;
+                push(idx, 1, HlTag::Punctuation(HlPunct::Bracket));
+            }
+            '-' if in_class => push(idx, 1, HlTag::Operator(HlOperator::Other)),
+            _ if in_class => (),
+            '(' => {
+                push(idx, 1, HlTag::Punctuation(HlPunct::Parenthesis));
+                // Group flags and names, like `(?:`, `(?i)` and `(?P<name>`.
+                if let Some(&(start, '?')) = chars.peek() {
+                    let mut end = start;
+                    while let Some(&(idx, c)) = chars.peek() {
+                        if c == ')' {
+                            break;
+                        }
+                        chars.next();
+                        end = idx + c.len_utf8();
+                        if c == ':' || c == '>' {
+                            break;
+                        }
+                    }
+                    push(start, end - start, HlTag::Keyword);
+                }
+            }
+            ')' => push(idx, 1, HlTag::Punctuation(HlPunct::Parenthesis)),
+            '{' => {
+                push(idx, 1, HlTag::Punctuation(HlPunct::Brace));
+                while let Some(&(idx, c)) = chars.peek() {
+                    match c {
+                        '0'..='9' => push(idx, 1, HlTag::NumericLiteral),
+                        ',' => push(idx, 1, HlTag::Punctuation(HlPunct::Comma)),
+                        _ => break,
+                    }
+                    chars.next();
+                }
+            }
+            '}' => push(idx, 1, HlTag::Punctuation(HlPunct::Brace)),
+            '*' | '+' | '?' | '|' | '.' | '^' | '$' => {
+                push(idx, 1, HlTag::Operator(HlOperator::Other))
+            }
+            _ => (),
+        }
+    }
+    res
+}
+
+const SQL_KEYWORDS: &[&str] = &[
+    "ADD",
+    "ALL",
+    "ALTER",
+    "AND",
+    "AS",
+    "ASC",
+    "BETWEEN",
+    "BY",
+    "CASE",
+    "CONFLICT",
+    "CREATE",
+    "DEFAULT",
+    "DELETE",
+    "DESC",
+    "DISTINCT",
+    "DO",
+    "DROP",
+    "ELSE",
+    "END",
+    "EXISTS",
+    "FALSE",
+    "FROM",
+    "FULL",
+    "GROUP",
+    "HAVING",
+    "IF",
+    "IN",
+    "INDEX",
+    "INNER",
+    "INSERT",
+    "INTO",
+    "IS",
+    "JOIN",
+    "KEY",
+    "LEFT",
+    "LIKE",
+    "LIMIT",
+    "NOT",
+    "NULL",
+    "OFFSET",
+    "ON",
+    "OR",
+    "ORDER",
+    "OUTER",
+    "PRIMARY",
+    "REFERENCES",
+    "RETURNING",
+    "RIGHT",
+    "SELECT",
+    "SET",
+    "TABLE",
+    "THEN",
+    "TRUE",
+    "UNION",
+    "UNIQUE",
+    "UPDATE",
+    "VALUES",
+    "WHEN",
+    "WHERE",
+    "WITH",
+];
+
+fn sql(text: &str) -> Vec<(TextRange, HlTag)> {
+    let mut res = Vec::new();
+    let bytes = text.as_bytes();
+    let mut push = |start: usize, end: usize, tag| {
+        res.push((TextRange::new((start as u32).into(), (end as u32).into()), tag))
+    };
+    let scan = |start: usize, f: &dyn Fn(u8) -> bool| {
+        start + bytes[start..].iter().take_while(|&&b| f(b)).count()
+    };
+    let is_word = |b: u8| b.is_ascii_alphanumeric() || b == b'_';
+
+    let mut idx = 0;
+    while idx < bytes.len() {
+        let start = idx;
+        let b = bytes[idx];
+        idx += 1;
+        match b {
+            b'-' if bytes.get(idx) == Some(&b'-') => {
+                idx = scan(idx, &|b| b != b'\n');
+                push(start, idx, HlTag::Comment);
+            }
+            b'\'' => {
+                idx = scan(idx, &|b| b != b'\'');
+                idx = (idx + 1).min(bytes.len());
+                push(start, idx, HlTag::StringLiteral);
+            }
+            b'?' => push(start, idx, HlTag::FormatSpecifier),
+            b'$' | b':' if bytes.get(idx).map_or(false, |&b| is_word(b)) => {
+                idx = scan(idx, &is_word);
+                push(start, idx, HlTag::FormatSpecifier);
+            }
+            b'0'..=b'9' => {
+                idx = scan(idx, &|b| b.is_ascii_digit() || b == b'.');
+                push(start, idx, HlTag::NumericLiteral);
+            }
+            _ if is_word(b) => {
+                idx = scan(idx, &is_word);
+                let word = &text[start..idx];
+                if SQL_KEYWORDS.iter().any(|kw| kw.eq_ignore_ascii_case(word)) {
+                    push(start, idx, HlTag::Keyword);
+                }
+            }
+            b'=' | b'<' | b'>' | b'!' => {
+                idx = scan(idx, &|b| matches!(b, b'=' | b'<' | b'>'));
+                push(start, idx, HlTag::Operator(HlOperator::Comparison));
+            }
+            b'+' | b'-' | b'*' | b'/' | b'%' => {
+                push(start, idx, HlTag::Operator(HlOperator::Arithmetic))
+            }
+            b'(' | b')' => push(start, idx, HlTag::Punctuation(HlPunct::Parenthesis)),
+            b',' => push(start, idx, HlTag::Punctuation(HlPunct::Comma)),
+            b';' => push(start, idx, HlTag::Punctuation(HlPunct::Semi)),
+            b'.' => push(start, idx, HlTag::Punctuation(HlPunct::Dot)),
+            _ => (),
+        }
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::{expect, Expect};
+
+    use super::*;
+
+    fn check(language: Language, text: &str, expect: Expect) {
+        let actual = language
+            .highlight(text)
+            .into_iter()
+            .map(|(range, tag)| format!("{:?} {tag}\n", &text[range]))
+            .collect::<String>();
+        expect.assert_eq(&actual);
+    }
+
+    #[test]
+    fn markers() {
+        assert_eq!(Language::from_marker("// language=regex"), Some(Language::Regex));
+        assert_eq!(Language::from_marker("/* language = SQL */"), Some(Language::Sql));
+        assert_eq!(Language::from_marker("// language=html"), None);
+        assert_eq!(Language::from_marker("// regex"), None);
+    }
+
+    #[test]
+    fn regex_tokens() {
+        check(
+            Language::Regex,
+            r"^(?P<key>\w+)=[^a-z.]{1,3}$",
+            expect![[r#"
+                "^" operator
+                "(" parenthesis
+                "?P<key>" keyword
+                "\\w" escape_sequence
+                "+" operator
+                ")" parenthesis
+                "[" bracket
+                "^" operator
+                "-" operator
+                "]" bracket
+                "{" brace
+                "1" numeric_literal
+                "," comma
+                "3" numeric_literal
+                "}" brace
+                "$" operator
+            "#]],
+        );
+    }
+
+    #[test]
+    fn sql_tokens() {
+        check(
+            Language::Sql,
+            "select id from users where name = $1 and age >= 18 -- adults",
+            expect![[r#"
+                "select" keyword
+                "from" keyword
+                "where" keyword
+                "=" comparison
+                "$1" format_specifier
+                "and" keyword
+                ">=" comparison
+                "18" numeric_literal
+                "-- adults" comment
+            "#]],
+        );
+    }
+}
@@ -31,6 +31,7 @@ pub enum HlTag {
     FormatSpecifier,
     InvalidEscapeSequence,
     Keyword,
+    MacroMetavariable,
     NumericLiteral,
     Operator(HlOperator),
     Punctuation(HlPunct),
@@ -62,6 +63,8 @@ pub enum HlMod {
     CrateRoot,
     /// Used for items from built-in crates (std, core, alloc, test and proc_macro).
     DefaultLibrary,
+    /// Used for items marked `#[deprecated]`.
+    Deprecated,
     /// `foo` in `fn foo(x: i32)` is a definition, `foo` in `foo(90 + 2)` is
     /// not.
     Definition,
@@ -182,6 +185,7 @@ impl HlTag {
                 HlPunct::MacroBang => "macro_bang",
                 HlPunct::Other => "punctuation",
             },
+            HlTag::MacroMetavariable => "macro_metavariable",
             HlTag::NumericLiteral => "numeric_literal",
             HlTag::Operator(op) => match op {
                 HlOperator::Bitwise => "bitwise",
@@ -213,6 +217,7 @@ impl HlMod {
         HlMod::ControlFlow,
         HlMod::CrateRoot,
         HlMod::DefaultLibrary,
+        HlMod::Deprecated,
         HlMod::Definition,
         HlMod::Documentation,
         HlMod::Injected,
@@ -237,6 +242,7 @@ impl HlMod {
             HlMod::ControlFlow => "control",
             HlMod::CrateRoot => "crate_root",
             HlMod::DefaultLibrary => "default_library",
+            HlMod::Deprecated => "deprecated",
             HlMod::Definition => "declaration",
             HlMod::Documentation => "documentation",
             HlMod::Injected => "injected",
@@ -13,6 +13,7 @@ const HL_CONFIG: HighlightConfig = HighlightConfig {
     specialize_operator: true,
     operator: true,
     inject_doc_comment: true,
+    inject_string_languages: true,
     macro_bang: true,
     syntactic_name_ref_highlighting: false,
 };
@@ -1041,6 +1042,39 @@ struct Foo {
     assert_eq!(&highlights[0].highlight.to_string(), "field.declaration.public");
 }
 
+#[test]
+fn test_deprecated_method_through_trait() {
+    let (analysis, file_id) = fixture::file(
+        r#"
+trait Tr {
+    #[deprecated(note = "use `new` instead")]
+    fn old(&self);
+}
+struct S;
+impl Tr for S {
+    fn old(&self) {}
+}
+fn f(s: S) {
+    s.old();
+}
+"#,
+    );
+    let text = analysis.file_text(file_id).unwrap();
+    let offset = text.rfind("old").unwrap() as u32;
+
+    let highlights = &analysis
+        .highlight_range(
+            HL_CONFIG,
+            FileRange { file_id, range: TextRange::at(offset.into(), 3.into()) },
+        )
+        .unwrap();
+
+    assert_eq!(
+        &highlights[0].highlight.to_string(),
+        "function.associated.deprecated.reference.trait"
+    );
+}
+
 #[test]
 fn ranges_sorted() {
     let (analysis, file_id) = fixture::file(
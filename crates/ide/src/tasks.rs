@@ -0,0 +1,124 @@
+use ide_db::{
+    base_db::{FileId, FilePosition, SourceDatabase, SourceDatabaseExt},
+    symbol_index::SymbolsDatabase,
+    tasks::{Task, TasksDatabase},
+    RootDatabase,
+};
+use syntax::{ast, AstNode, TextRange};
+
+// Feature: Tasks
+//
+// Collects the `TODO`, `FIXME` and `XXX` comments of the workspace, a file, or the item under
+// the cursor, together with the item each of them belongs to. The number of tasks of an item
+// is also shown when hovering it.
+pub(crate) fn workspace_tasks(db: &RootDatabase) -> Vec<Task> {
+    let mut res = Vec::new();
+    for &root in db.local_roots().iter() {
+        let source_root = db.source_root(root);
+        for file_id in source_root.iter() {
+            let is_rust = source_root
+                .path_for_file(&file_id)
+                .and_then(|path| path.name_and_extension())
+                .map_or(false, |(_, ext)| ext == Some("rs"));
+            if is_rust {
+                res.extend(db.file_tasks(file_id).iter().cloned());
+            }
+        }
+    }
+    res
+}
+
+pub(crate) fn file_tasks(db: &RootDatabase, file_id: FileId) -> Vec<Task> {
+    db.file_tasks(file_id).to_vec()
+}
+
+/// The tasks of the innermost item containing `position`.
+pub(crate) fn item_tasks(db: &RootDatabase, position: FilePosition) -> Vec<Task> {
+    let Some(item) = db
+        .parse(position.file_id)
+        .tree()
+        .syntax()
+        .token_at_offset(position.offset)
+        .left_biased()
+        .and_then(|token| token.parent_ancestors().find_map(ast::Item::cast))
+    else {
+        return Vec::new();
+    };
+    let range = item.syntax().text_range();
+    db.file_tasks(position.file_id)
+        .iter()
+        .filter(|task| range.contains_range(task.range))
+        .cloned()
+        .collect()
+}
+
+/// The number of tasks within `range`, used by hover.
+pub(crate) fn count_tasks_in_range(db: &RootDatabase, file_id: FileId, range: TextRange) -> usize {
+    db.file_tasks(file_id).iter().filter(|task| range.contains_range(task.range)).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::{expect, Expect};
+
+    use crate::fixture;
+
+    fn check(ra_fixture: &str, expect: Expect) {
+        let (analysis, file_id) = fixture::file(ra_fixture);
+        let tasks = analysis.file_tasks(file_id).unwrap();
+        let actual = tasks
+            .into_iter()
+            .map(|task| {
+                let owner = task.owner.map_or_else(|| "<file>".to_owned(), |it| it.name);
+                format!("{} {:?} {owner}: {}\n", task.kind.marker(), task.range, task.text)
+            })
+            .collect::<String>();
+        expect.assert_eq(&actual);
+    }
+
+    #[test]
+    fn collects_tasks_with_owners() {
+        check(
+            r#"
+// TODO: document the module
+
+struct S;
+
+/// FIXME(someone): this is slow
+fn slow() {
+    // not a TODOS marker, but XXX is
+}
+
+impl S {
+    fn f() {
+        /* TODO handle errors */
+    }
+}
+"#,
+            expect![[r#"
+                TODO 3..28 <file>: document the module
+                FIXME 45..73 slow: (someone): this is slow
+                XXX 117..123 slow: is
+                TODO 160..178 f: handle errors
+            "#]],
+        );
+    }
+
+    #[test]
+    fn item_tasks() {
+        let (analysis, position) = fixture::position(
+            r#"
+fn a() {
+    // TODO: a
+}
+fn b() {
+    $0// TODO: b
+    // FIXME: b
+}
+"#,
+        );
+        let tasks = analysis.item_tasks(position).unwrap();
+        let texts = tasks.iter().map(|it| it.text.as_str()).collect::<Vec<_>>();
+        assert_eq!(texts, ["b", "b"]);
+    }
+}
@@ -0,0 +1,320 @@
+//! Discovers the tests of the workspace as a tree of crates, modules and test functions, for
+//! clients that implement a test explorer.
+
+use hir::{AsAssocItem, HasAttrs, HirFileIdExt, Semantics};
+use ide_db::{
+    base_db::{CrateId, FileId, SourceDatabase},
+    FxHashSet, RootDatabase,
+};
+use stdx::format_to;
+use syntax::TextRange;
+
+use crate::{
+    runnables::{runnable_fn, runnable_mod, TestAttr},
+    NavigationTarget, Runnable, RunnableKind, TestId, TryToNav,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TestItemKind {
+    Crate,
+    Module,
+    Function,
+}
+
+#[derive(Debug)]
+pub struct TestItem {
+    /// The path of the item, starting with the name of its crate. Stays the same as long as the
+    /// item isn't renamed or moved.
+    pub id: String,
+    pub kind: TestItemKind,
+    pub label: String,
+    pub parent: Option<String>,
+    pub file: Option<FileId>,
+    pub text_range: Option<TextRange>,
+    /// Runs the tests of this item.
+    pub runnable: Option<Runnable>,
+}
+
+// Feature: Test Explorer
+//
+// Lists the tests of the workspace members as a tree of crates, modules and test functions,
+// including the cases of parameterized `#[rstest]` tests. The editor shows the tree in its test
+// explorer and runs or debugs the tests through the same runnables as the code lenses.
+pub(crate) fn discover_test_roots(db: &RootDatabase) -> Vec<TestItem> {
+    let crate_graph = db.crate_graph();
+    let mut seen = FxHashSet::default();
+    crate_graph
+        .iter()
+        .filter(|&id| crate_graph[id].origin.is_local())
+        .filter_map(|id| {
+            let name = crate_name(db, id)?;
+            seen.insert(name.clone()).then(|| TestItem {
+                id: name.clone(),
+                kind: TestItemKind::Crate,
+                label: name,
+                parent: None,
+                file: Some(crate_graph[id].root_file_id),
+                text_range: None,
+                runnable: None,
+            })
+        })
+        .collect()
+}
+
+/// Returns the modules containing tests and the test functions of the crate with the given name.
+pub(crate) fn discover_tests_in_crate(db: &RootDatabase, name: &str) -> Vec<TestItem> {
+    let crate_graph = db.crate_graph();
+    let Some(id) = crate_graph
+        .iter()
+        .filter(|&id| crate_graph[id].origin.is_local())
+        .find(|&id| crate_name(db, id).as_deref() == Some(name))
+    else {
+        return Vec::new();
+    };
+    let sema = Semantics::new(db);
+    let root = hir::Crate::from(id).root_module();
+    let mut res = Vec::new();
+    discover_tests_in_module(&sema, root, name, None, &mut res);
+    res
+}
+
+/// Returns the tests declared in `file_id`, together with the modules of the file containing
+/// them, so that clients can update their tree when a file changes.
+pub(crate) fn discover_tests_in_file(db: &RootDatabase, file_id: FileId) -> Vec<TestItem> {
+    let sema = Semantics::new(db);
+    let mut res = Vec::new();
+    let mut seen = FxHashSet::default();
+    for module in sema.to_module_defs(file_id) {
+        let Some(crate_name) = crate_name(db, module.krate().into()) else { continue };
+        if !module.krate().origin(db).is_local() || !seen.insert(module_id(db, &crate_name, module))
+        {
+            continue;
+        }
+        discover_tests_in_module(&sema, module, &crate_name, Some(file_id), &mut res);
+    }
+    res
+}
+
+/// Collects the tests of `module` and its children into `acc`, preceded by an item for `module`
+/// itself if it contains any tests. With `file_id`, only children declared in that file are
+/// visited.
+fn discover_tests_in_module(
+    sema: &Semantics<'_, RootDatabase>,
+    module: hir::Module,
+    crate_name: &str,
+    file_id: Option<FileId>,
+    acc: &mut Vec<TestItem>,
+) {
+    let db = sema.db;
+    let id = module_id(db, crate_name, module);
+    let mut items = Vec::new();
+    for def in module.declarations(db) {
+        if let hir::ModuleDef::Function(func) = def {
+            if func.as_assoc_item(db).is_none() {
+                test_fn_items(sema, func, &id, &mut items);
+            }
+        }
+    }
+    for child in module.children(db) {
+        let child_file = child.definition_source_file_id(db).original_file(db);
+        if file_id.map_or(true, |it| it == child_file) {
+            discover_tests_in_module(sema, child, crate_name, file_id, &mut items);
+        }
+    }
+    if items.is_empty() {
+        return;
+    }
+
+    let item = match module.parent(db) {
+        None => TestItem {
+            id: id.clone(),
+            kind: TestItemKind::Crate,
+            label: crate_name.to_owned(),
+            parent: None,
+            file: Some(module.krate().root_file(db)),
+            text_range: None,
+            runnable: runnable_mod(sema, module),
+        },
+        Some(parent) => {
+            let nav = NavigationTarget::from_module_to_decl(db, module).call_site();
+            TestItem {
+                id: id.clone(),
+                kind: TestItemKind::Module,
+                label: nav.name.to_string(),
+                parent: Some(module_id(db, crate_name, parent)),
+                file: Some(nav.file_id),
+                text_range: Some(nav.full_range),
+                runnable: runnable_mod(sema, module),
+            }
+        }
+    };
+    acc.push(item);
+    acc.extend(items);
+}
+
+fn test_fn_items(
+    sema: &Semantics<'_, RootDatabase>,
+    func: hir::Function,
+    parent: &str,
+    acc: &mut Vec<TestItem>,
+) {
+    let db = sema.db;
+    let name = func.name(db).display(db).to_string();
+    let id = format!("{parent}::{name}");
+    let Some(nav) = func.try_to_nav(db).map(|it| it.call_site()) else { return };
+    let item = |id: String, label: String, runnable| TestItem {
+        id,
+        kind: TestItemKind::Function,
+        label,
+        parent: Some(parent.to_owned()),
+        file: Some(nav.file_id),
+        text_range: Some(nav.full_range),
+        runnable,
+    };
+
+    let attrs = func.attrs(db);
+    let is_rstest = attrs
+        .iter()
+        .any(|attr| attr.path().as_ident().map_or(false, |it| it.to_smol_str() == "rstest"));
+    if !is_rstest {
+        if let Some(runnable @ Runnable { kind: RunnableKind::Test { .. }, .. }) =
+            runnable_fn(sema, func)
+        {
+            acc.push(item(id, name, Some(runnable)));
+        }
+        return;
+    }
+
+    // `#[rstest]` turns a function with `#[case]` attributes into a module with one test per
+    // case, named `case_<n>` or `case_<n>_<description>` for `#[case::description(..)]`.
+    let test_path = hir::ModuleDef::from(func).canonical_path(db).unwrap_or_else(|| name.clone());
+    let runnable = |path: String| Runnable {
+        use_name_in_title: false,
+        nav: nav.clone(),
+        kind: RunnableKind::Test { test_id: TestId::Path(path), attr: TestAttr::from_fn(db, func) },
+        cfg: attrs.cfg(),
+    };
+    let cases = attrs
+        .iter()
+        .filter_map(|attr| {
+            let segments = attr.path().segments();
+            match segments {
+                [case] if case.to_smol_str() == "case" => Some(None),
+                [case, description] if case.to_smol_str() == "case" => {
+                    Some(Some(description.display(db).to_string()))
+                }
+                _ => None,
+            }
+        })
+        .collect::<Vec<_>>();
+    acc.push(item(id.clone(), name, Some(runnable(test_path.clone()))));
+    for (idx, description) in cases.into_iter().enumerate() {
+        let mut case = format!("case_{}", idx + 1);
+        if let Some(description) = description {
+            format_to!(case, "_{description}");
+        }
+        acc.push(TestItem {
+            parent: Some(id.clone()),
+            ..item(
+                format!("{id}::{case}"),
+                case.clone(),
+                Some(runnable(format!("{test_path}::{case}"))),
+            )
+        });
+    }
+}
+
+fn crate_name(db: &RootDatabase, id: CrateId) -> Option<String> {
+    let crate_graph = db.crate_graph();
+    crate_graph[id].display_name.as_ref().map(|it| it.canonical_name().to_owned())
+}
+
+fn module_id(db: &RootDatabase, crate_name: &str, module: hir::Module) -> String {
+    let mut id = crate_name.to_owned();
+    for name in module.path_to_root(db).into_iter().rev().filter_map(|it| it.name(db)) {
+        format_to!(id, "::{}", name.display(db));
+    }
+    id
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::{expect, Expect};
+
+    use crate::fixture;
+
+    use super::TestItem;
+
+    fn render(items: Vec<TestItem>) -> String {
+        items
+            .into_iter()
+            .map(|it| {
+                let runnable = it.runnable.map(|it| it.label(None)).unwrap_or_default();
+                let line = format!(
+                    "{:?} {} parent={} {runnable}",
+                    it.kind,
+                    it.id,
+                    it.parent.as_deref().unwrap_or("-")
+                );
+                format!("{}\n", line.trim_end())
+            })
+            .collect()
+    }
+
+    fn check_crate(ra_fixture: &str, expect: Expect) {
+        let (analysis, _) = fixture::file(ra_fixture);
+        let roots = analysis.discover_test_roots().unwrap();
+        let mut actual = render(roots);
+        actual += &render(analysis.discover_tests_in_crate("foo").unwrap());
+        expect.assert_eq(&actual);
+    }
+
+    #[test]
+    fn test_tree() {
+        check_crate(
+            r#"
+//- /lib.rs crate:foo
+fn helper() {}
+
+#[test]
+fn top_level() {}
+
+mod tests {
+    #[test]
+    fn nested() {}
+
+    mod empty {
+        fn not_a_test() {}
+    }
+}
+"#,
+            expect![[r#"
+                Crate foo parent=-
+                Crate foo parent=- test-mod
+                Function foo::top_level parent=foo test top_level
+                Module foo::tests parent=foo test-mod tests
+                Function foo::tests::nested parent=foo::tests test tests::nested
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rstest_cases() {
+        check_crate(
+            r#"
+//- /lib.rs crate:foo
+#[rstest]
+#[case(1)]
+#[case::two(2)]
+fn parameterized(#[case] n: u32) {}
+"#,
+            expect![[r#"
+                Crate foo parent=-
+                Crate foo parent=-
+                Function foo::parameterized parent=foo test parameterized
+                Function foo::parameterized::case_1 parent=foo::parameterized test parameterized::case_1
+                Function foo::parameterized::case_2_two parent=foo::parameterized test parameterized::case_2_two
+            "#]],
+        );
+    }
+}
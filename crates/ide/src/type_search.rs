@@ -0,0 +1,314 @@
+use std::{fmt, str::FromStr};
+
+use hir::{HirDisplay, Semantics};
+use ide_db::{
+    base_db::{FileId, SourceDatabaseExt},
+    symbol_index::SymbolsDatabase,
+    RootDatabase, Snap,
+};
+use rayon::prelude::*;
+use syntax::{ast, AstNode, SourceFile, TextRange};
+
+/// An expression or binding whose type matches a [`TypePattern`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeSearchMatch {
+    pub file_id: FileId,
+    pub range: TextRange,
+    pub kind: TypeSearchMatchKind,
+    /// The type of the expression or binding, as shown on hover.
+    pub ty: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TypeSearchMatchKind {
+    Expr,
+    Binding,
+}
+
+/// A type to search for, like `Arc<Mutex<_>>`.
+///
+/// `_` matches any type, paths are compared by their last segment, and a path without generic
+/// arguments, or with fewer than the type has, leaves the missing ones unconstrained.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypePattern(Pattern);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypePatternError(String);
+
+impl fmt::Display for TypePatternError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Parse error: {}", self.0)
+    }
+}
+
+impl std::error::Error for TypePatternError {}
+
+impl FromStr for TypePattern {
+    type Err = TypePatternError;
+
+    fn from_str(query: &str) -> Result<TypePattern, TypePatternError> {
+        let query = query.trim();
+        if query.is_empty() {
+            return Err(TypePatternError("the type pattern is empty".to_owned()));
+        }
+        match parse_type(query) {
+            Some(ty) => Ok(TypePattern(Pattern::new(&ty))),
+            None => Err(TypePatternError(format!("`{query}` is not a valid type"))),
+        }
+    }
+}
+
+impl TypePattern {
+    fn matches(&self, ty: &hir::Type, db: &RootDatabase) -> Option<String> {
+        if ty.contains_unknown() {
+            return None;
+        }
+        let rendered = ty.display(db).to_string();
+        let ty = Pattern::new(&parse_type(&rendered)?);
+        self.0.matches(&ty).then_some(rendered)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Pattern {
+    Wildcard,
+    Path {
+        name: String,
+        args: Vec<Pattern>,
+    },
+    Ref {
+        mutable: bool,
+        inner: Box<Pattern>,
+    },
+    Ptr {
+        mutable: bool,
+        inner: Box<Pattern>,
+    },
+    Tuple(Vec<Pattern>),
+    Slice(Box<Pattern>),
+    Array {
+        inner: Box<Pattern>,
+        len: Option<String>,
+    },
+    /// Anything else, compared textually.
+    Other(String),
+}
+
+impl Pattern {
+    fn new(ty: &ast::Type) -> Pattern {
+        let inner =
+            |ty: Option<ast::Type>| Box::new(ty.map_or(Pattern::Wildcard, |it| Pattern::new(&it)));
+        match ty {
+            ast::Type::InferType(_) => Pattern::Wildcard,
+            ast::Type::ParenType(it) => it.ty().map_or(Pattern::Wildcard, |it| Pattern::new(&it)),
+            ast::Type::PathType(it) => {
+                let Some(segment) = it.path().and_then(|it| it.segment()) else {
+                    return Pattern::Other(normalize(ty.syntax().text().to_string()));
+                };
+                let name = segment.name_ref().map_or_else(String::new, |it| it.text().to_string());
+                let args = segment
+                    .generic_arg_list()
+                    .into_iter()
+                    .flat_map(|it| it.generic_args())
+                    .filter_map(|arg| match arg {
+                        ast::GenericArg::TypeArg(it) => Some(*inner(it.ty())),
+                        ast::GenericArg::LifetimeArg(_) => None,
+                        arg => Some(Pattern::Other(normalize(arg.syntax().text().to_string()))),
+                    })
+                    .collect();
+                Pattern::Path { name, args }
+            }
+            ast::Type::RefType(it) => {
+                Pattern::Ref { mutable: it.mut_token().is_some(), inner: inner(it.ty()) }
+            }
+            ast::Type::PtrType(it) => {
+                Pattern::Ptr { mutable: it.mut_token().is_some(), inner: inner(it.ty()) }
+            }
+            ast::Type::TupleType(it) => {
+                Pattern::Tuple(it.fields().map(|it| Pattern::new(&it)).collect())
+            }
+            ast::Type::SliceType(it) => Pattern::Slice(inner(it.ty())),
+            ast::Type::ArrayType(it) => {
+                let len = it.const_arg().map(|it| normalize(it.syntax().text().to_string()));
+                Pattern::Array { inner: inner(it.ty()), len: len.filter(|it| it != "_") }
+            }
+            _ => Pattern::Other(normalize(ty.syntax().text().to_string())),
+        }
+    }
+
+    fn matches(&self, ty: &Pattern) -> bool {
+        match (self, ty) {
+            (Pattern::Wildcard, _) => true,
+            (Pattern::Path { name, args }, Pattern::Path { name: ty_name, args: ty_args }) => {
+                name == ty_name
+                    && args.len() <= ty_args.len()
+                    && args.iter().zip(ty_args).all(|(arg, ty_arg)| arg.matches(ty_arg))
+            }
+            (
+                Pattern::Ref { mutable, inner },
+                Pattern::Ref { mutable: ty_mutable, inner: ty_inner },
+            )
+            | (
+                Pattern::Ptr { mutable, inner },
+                Pattern::Ptr { mutable: ty_mutable, inner: ty_inner },
+            ) => mutable == ty_mutable && inner.matches(ty_inner),
+            (Pattern::Tuple(fields), Pattern::Tuple(ty_fields)) => {
+                fields.len() == ty_fields.len()
+                    && fields.iter().zip(ty_fields).all(|(field, ty_field)| field.matches(ty_field))
+            }
+            (Pattern::Slice(inner), Pattern::Slice(ty_inner)) => inner.matches(ty_inner),
+            (Pattern::Array { inner, len }, Pattern::Array { inner: ty_inner, len: ty_len }) => {
+                inner.matches(ty_inner) && (len.is_none() || len == ty_len)
+            }
+            (Pattern::Other(text), Pattern::Other(ty_text)) => text == ty_text,
+            _ => false,
+        }
+    }
+}
+
+fn parse_type(text: &str) -> Option<ast::Type> {
+    let parse = SourceFile::parse(&format!("type T = {text};"));
+    if !parse.errors().is_empty() {
+        return None;
+    }
+    parse.tree().syntax().descendants().find_map(ast::TypeAlias::cast)?.ty()
+}
+
+fn normalize(text: String) -> String {
+    text.chars().filter(|c| !c.is_whitespace()).collect()
+}
+
+// Feature: Search by Type
+//
+// Finds the expressions and bindings of the workspace whose type matches a pattern like
+// `Arc<Mutex<_>>`, for example to audit the uses of a lock or to find the places to migrate
+// away from a type. `_` matches any type and paths only need to agree on their last segment,
+// so `Vec` matches every vector.
+//
+// |===
+// | Editor  | Action Name
+//
+// | VS Code | **rust-analyzer: Search by Type**
+// |===
+pub(crate) fn search_by_type(db: &RootDatabase, pattern: &TypePattern) -> Vec<TypeSearchMatch> {
+    let _p = tracing::span!(tracing::Level::INFO, "search_by_type").entered();
+    let mut files = Vec::new();
+    for &root in db.local_roots().iter() {
+        let source_root = db.source_root(root);
+        files.extend(source_root.iter().filter(|&file_id| {
+            source_root
+                .path_for_file(&file_id)
+                .and_then(|path| path.name_and_extension())
+                .map_or(false, |(_, ext)| ext == Some("rs"))
+        }));
+    }
+    files
+        .into_par_iter()
+        .map_with(Snap::new(db), |snap, file_id| search_file(snap, file_id, pattern))
+        .flatten()
+        .collect()
+}
+
+fn search_file(db: &RootDatabase, file_id: FileId, pattern: &TypePattern) -> Vec<TypeSearchMatch> {
+    let sema = Semantics::new(db);
+    let file = sema.parse(file_id);
+    let mut res = Vec::new();
+    for node in file.syntax().descendants() {
+        let (ty, kind) = if let Some(expr) = ast::Expr::cast(node.clone()) {
+            // The expression within the parentheses is reported instead.
+            if matches!(expr, ast::Expr::ParenExpr(_)) {
+                continue;
+            }
+            (sema.type_of_expr(&expr).map(|it| it.original), TypeSearchMatchKind::Expr)
+        } else if let Some(pat) = ast::IdentPat::cast(node.clone()) {
+            (sema.type_of_pat(&pat.into()).map(|it| it.original), TypeSearchMatchKind::Binding)
+        } else {
+            continue;
+        };
+        if let Some(ty) = ty.and_then(|ty| pattern.matches(&ty, db)) {
+            res.push(TypeSearchMatch { file_id, range: node.text_range(), kind, ty });
+        }
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::{expect, Expect};
+
+    use crate::fixture;
+
+    fn check(ra_fixture: &str, pattern: &str, expect: Expect) {
+        let (analysis, file_id) = fixture::file(ra_fixture);
+        let text = analysis.file_text(file_id).unwrap();
+        let matches = analysis.search_by_type(pattern).unwrap().unwrap();
+        let actual = matches
+            .into_iter()
+            .map(|it| format!("{:?} `{}`: {}\n", it.kind, &text[it.range], it.ty))
+            .collect::<String>();
+        expect.assert_eq(&actual);
+    }
+
+    #[test]
+    fn finds_expressions_and_bindings() {
+        check(
+            r#"
+struct Arc<T>(T);
+struct Mutex<T>(T);
+struct RwLock<T>(T);
+
+fn f(a: Arc<Mutex<i32>>, b: Arc<RwLock<i32>>) {
+    let c = a;
+    let d = Arc(Mutex(0u8));
+}
+"#,
+            "Arc<Mutex<_>>",
+            expect![[r#"
+                Binding `a`: Arc<Mutex<i32>>
+                Binding `c`: Arc<Mutex<i32>>
+                Expr `a`: Arc<Mutex<i32>>
+                Binding `d`: Arc<Mutex<u8>>
+                Expr `Arc(Mutex(0u8))`: Arc<Mutex<u8>>
+            "#]],
+        );
+    }
+
+    #[test]
+    fn matches_references_and_tuples() {
+        check(
+            r#"
+fn f(x: &mut (u32, bool), y: &(u32, bool), z: &mut (u32, u8)) {}
+"#,
+            "&mut (u32, _)",
+            expect![[r#"
+                Binding `x`: &mut (u32, bool)
+                Binding `z`: &mut (u32, u8)
+            "#]],
+        );
+    }
+
+    #[test]
+    fn path_without_arguments_matches_any_arguments() {
+        check(
+            r#"
+struct Wrapper<T>(T);
+
+fn f() {
+    let w = Wrapper(1i32);
+}
+"#,
+            "foo::Wrapper",
+            expect![[r#"
+                Binding `w`: Wrapper<i32>
+                Expr `Wrapper(1i32)`: Wrapper<i32>
+            "#]],
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_patterns() {
+        let (analysis, _) = fixture::file("");
+        let err = analysis.search_by_type("Arc<").unwrap().unwrap_err();
+        assert_eq!(err.to_string(), "Parse error: `Arc<` is not a valid type");
+    }
+}
@@ -111,6 +111,8 @@ pub fn load_workspace(
         load: project_folders.load,
         watch: vec![],
         version: 0,
+        exclude_globs: vec![],
+        respect_gitignore: false,
     });
 
     let host = load_crate_graph(
@@ -123,7 +125,7 @@ pub fn load_workspace(
     );
 
     if load_config.prefill_caches {
-        host.analysis().parallel_prime_caches(1, |_| {})?;
+        host.analysis().parallel_prime_caches(1, &[], |_| {})?;
     }
     Ok((host, vfs, proc_macro_server.ok()))
 }
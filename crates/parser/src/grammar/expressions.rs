@@ -16,6 +16,22 @@ pub(super) enum Semicolon {
 
 const EXPR_FIRST: TokenSet = LHS_FIRST;
 
+/// Tokens which can't continue an expression, but start the next statement or item. Unclosed
+/// argument and field lists stop at them, so that a half-typed call doesn't swallow the code
+/// after it.
+const STMT_RECOVERY_SET: TokenSet = TokenSet::new(&[
+    T![let],
+    T![fn],
+    T![struct],
+    T![enum],
+    T![impl],
+    T![trait],
+    T![mod],
+    T![use],
+    T![type],
+    T![pub],
+]);
+
 pub(super) fn expr(p: &mut Parser<'_>) -> Option<CompletedMarker> {
     let r = Restrictions { forbid_structs: false, prefer_stmt: false };
     expr_bp(p, None, r, 1).map(|(m, _)| m)
@@ -620,6 +636,14 @@ fn arg_list(p: &mut Parser<'_>) {
     // fn main() {
     //     foo(#[attr] 92)
     // }
+
+    // test_err arg_list_recovery_before_stmt
+    // fn main() {
+    //     foo.bar(
+    //     let x = 92;
+    //     foo(a, fn
+    // }
+    // fn f() {}
     delimited(
         p,
         T!['('],
@@ -627,7 +651,7 @@ fn arg_list(p: &mut Parser<'_>) {
         T![,],
         || "expected expression".into(),
         EXPR_FIRST.union(ATTRIBUTE_FIRST),
-        |p| expr(p).is_some(),
+        |p| !p.at_ts(STMT_RECOVERY_SET) && expr(p).is_some(),
     );
     m.complete(p, ARG_LIST);
 }
@@ -740,6 +764,16 @@ pub(crate) fn record_expr_field_list(p: &mut Parser<'_>) {
                 error_block(p, "expected a field");
                 m.abandon(p);
             }
+            // test_err record_literal_recovery_before_stmt
+            // fn main() {
+            //     let s = S { a: 1,
+            //     let x = 92;
+            // }
+            // fn f() {}
+            _ if p.at_ts(STMT_RECOVERY_SET) => {
+                m.abandon(p);
+                break;
+            }
             _ => {
                 p.err_and_bump("expected identifier");
                 m.abandon(p);
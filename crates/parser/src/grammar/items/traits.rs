@@ -94,12 +94,30 @@ pub(crate) fn assoc_item_list(p: &mut Parser<'_>) {
             error_block(p, "expected an item");
             continue;
         }
+        // test_err assoc_item_list_recovery
+        // impl S {
+        //     fn f() {
+        //         if true {
+        //     }
+        // }
+        // pub struct T;
+        // fn g() {}
+        if at_non_assoc_item(p) {
+            break;
+        }
         item_or_macro(p, true);
     }
     p.expect(T!['}']);
     m.complete(p, ASSOC_ITEM_LIST);
 }
 
+/// Whether the parser is at an item which can't be in an impl or trait. This usually means that
+/// a `}` is missing above, and the item belongs to the enclosing module.
+fn at_non_assoc_item(p: &Parser<'_>) -> bool {
+    let idx = usize::from(p.at(T![pub]));
+    matches!(p.nth(idx), T![struct] | T![enum] | T![trait] | T![impl] | T![mod] | T![use])
+}
+
 // test impl_type_params
 // impl<const N: u32> Bar<N> {}
 fn not_a_qualified_path(p: &Parser<'_>) -> bool {
@@ -0,0 +1,6 @@
+fn main() {
+    foo.bar(
+    let x = 92;
+    foo(a, fn
+}
+fn f() {}
@@ -0,0 +1,5 @@
+fn main() {
+    let s = S { a: 1,
+    let x = 92;
+}
+fn f() {}
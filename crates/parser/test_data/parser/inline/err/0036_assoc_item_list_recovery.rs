@@ -0,0 +1,7 @@
+impl S {
+    fn f() {
+        if true {
+    }
+}
+pub struct T;
+fn g() {}
@@ -100,6 +100,10 @@ impl Bytes {
     pub fn megabytes(self) -> isize {
         self.0 / 1024 / 1024
     }
+
+    pub fn bytes(self) -> isize {
+        self.0
+    }
 }
 
 impl fmt::Display for Bytes {
@@ -125,6 +129,12 @@ impl std::ops::AddAssign<usize> for Bytes {
     }
 }
 
+impl std::ops::AddAssign for Bytes {
+    fn add_assign(&mut self, rhs: Bytes) {
+        self.0 += rhs.0;
+    }
+}
+
 impl std::ops::Sub for Bytes {
     type Output = Bytes;
     fn sub(self, rhs: Bytes) -> Bytes {
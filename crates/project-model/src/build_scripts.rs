@@ -140,6 +140,9 @@ impl WorkspaceBuildScripts {
     ) -> io::Result<WorkspaceBuildScripts> {
         const RUST_1_75: Version = Version::new(1, 75, 0);
 
+        let config = config.with_overrides(workspace.metadata());
+        let config = &*config;
+
         let current_dir = match &config.invocation_location {
             InvocationLocation::Root(root) if config.run_build_script_command.is_some() => {
                 root.as_path()
@@ -350,6 +353,7 @@ impl WorkspaceBuildScripts {
                                 }
                                 acc
                             };
+                            data.cfgs = cfgs;
                             if !message.env.is_empty() {
                                 data.envs = mem::take(&mut message.env);
                             }
@@ -366,7 +370,6 @@ impl WorkspaceBuildScripts {
                                     data.envs.push(("OUT_DIR".to_owned(), out_dir));
                                 }
                                 data.out_dir = Some(out_dir);
-                                data.cfgs = cfgs;
                             }
                         });
                     }
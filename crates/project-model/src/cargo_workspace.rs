@@ -1,5 +1,6 @@
 //! See [`CargoWorkspace`].
 
+use std::borrow::Cow;
 use std::path::PathBuf;
 use std::str::from_utf8;
 use std::{ops, process::Command};
@@ -33,6 +34,7 @@ pub struct CargoWorkspace {
     targets: Arena<TargetData>,
     workspace_root: AbsPathBuf,
     target_directory: AbsPathBuf,
+    metadata: RustAnalyzerWorkspaceMetaData,
 }
 
 impl ops::Index<Package> for CargoWorkspace {
@@ -103,6 +105,23 @@ pub struct CargoConfig {
     pub target_dir: Option<PathBuf>,
 }
 
+impl CargoConfig {
+    /// Returns this configuration with the overrides of a workspace's
+    /// `[workspace.metadata.rust-analyzer]` table applied.
+    pub fn with_overrides(&self, metadata: &RustAnalyzerWorkspaceMetaData) -> Cow<'_, CargoConfig> {
+        match &metadata.features {
+            Some(features) => Cow::Owned(CargoConfig {
+                features: CargoFeatures::Selected {
+                    features: features.clone(),
+                    no_default_features: false,
+                },
+                ..self.clone()
+            }),
+            None => Cow::Borrowed(self),
+        }
+    }
+}
+
 pub type Package = Idx<PackageData>;
 
 pub type Target = Idx<TargetData>;
@@ -143,6 +162,16 @@ pub struct RustAnalyzerPackageMetaData {
     pub rustc_private: bool,
 }
 
+/// The contents of `[workspace.metadata.rust-analyzer]`, which take precedence over the client
+/// configuration for this workspace only.
+#[derive(Deserialize, Default, Debug, Clone, Eq, PartialEq)]
+pub struct RustAnalyzerWorkspaceMetaData {
+    /// The features to activate instead of the configured ones.
+    pub features: Option<Vec<String>>,
+    /// The cargo subcommand to run for `cargo check` diagnostics instead of the configured one.
+    pub check_command: Option<String>,
+}
+
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct PackageDependency {
     pub pkg: Package,
@@ -233,6 +262,12 @@ struct PackageMetadata {
     rust_analyzer: Option<RustAnalyzerPackageMetaData>,
 }
 
+#[derive(Deserialize, Default)]
+struct WorkspaceMetadata {
+    #[serde(rename = "rust-analyzer")]
+    rust_analyzer: Option<RustAnalyzerWorkspaceMetaData>,
+}
+
 impl CargoWorkspace {
     pub fn fetch_metadata(
         cargo_toml: &ManifestPath,
@@ -315,6 +350,8 @@ impl CargoWorkspace {
         let mut targets = Arena::default();
 
         let ws_members = &meta.workspace_members;
+        let ws_meta =
+            from_value::<WorkspaceMetadata>(meta.workspace_metadata.take()).unwrap_or_default();
 
         meta.packages.sort_by(|a, b| a.id.cmp(&b.id));
         for meta_pkg in meta.packages {
@@ -399,7 +436,13 @@ impl CargoWorkspace {
         let target_directory =
             AbsPathBuf::assert(PathBuf::from(meta.target_directory.into_os_string()));
 
-        CargoWorkspace { packages, targets, workspace_root, target_directory }
+        CargoWorkspace {
+            packages,
+            targets,
+            workspace_root,
+            target_directory,
+            metadata: ws_meta.rust_analyzer.unwrap_or_default(),
+        }
     }
 
     pub fn packages(&self) -> impl ExactSizeIterator<Item = Package> + '_ {
@@ -417,6 +460,11 @@ impl CargoWorkspace {
         &self.workspace_root
     }
 
+    /// The contents of the workspace's `[workspace.metadata.rust-analyzer]` table.
+    pub fn metadata(&self) -> &RustAnalyzerWorkspaceMetaData {
+        &self.metadata
+    }
+
     pub fn target_directory(&self) -> &AbsPath {
         &self.target_directory
     }
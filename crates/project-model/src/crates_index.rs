@@ -0,0 +1,240 @@
+//! Read access to the crates.io index that cargo caches locally, used to complete and check the
+//! dependencies of `Cargo.toml` files without touching the network.
+//!
+//! Cargo keeps a cache entry for every crate it has resolved in
+//! `$CARGO_HOME/registry/index/<registry>/.cache`, laid out like the index itself: `1/a`, `2/ab`,
+//! `3/a/abc` and `ab/cd/abcd...`. An entry consists of NUL-terminated fields, a header followed by
+//! pairs of a version and the JSON line the index has for that version.
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::Deserialize;
+
+/// The locally cached part of the crates.io index.
+#[derive(Debug, Clone)]
+pub struct CratesIndex {
+    cache_dirs: Vec<PathBuf>,
+}
+
+/// A published version of a crate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexedVersion {
+    pub version: semver::Version,
+    pub features: Vec<String>,
+    pub yanked: bool,
+}
+
+#[derive(Deserialize)]
+struct IndexLine {
+    vers: String,
+    #[serde(default)]
+    features: BTreeMap<String, Vec<String>>,
+    #[serde(default)]
+    features2: Option<BTreeMap<String, Vec<String>>>,
+    #[serde(default)]
+    deps: Vec<IndexDep>,
+    #[serde(default)]
+    yanked: bool,
+}
+
+#[derive(Deserialize)]
+struct IndexDep {
+    name: String,
+    #[serde(default)]
+    optional: bool,
+}
+
+impl CratesIndex {
+    /// Finds the index caches of the registries in the cargo home directory.
+    pub fn discover() -> Option<CratesIndex> {
+        let index = toolchain::get_cargo_home()?.join("registry").join("index");
+        CratesIndex::from_registry_index(&index)
+    }
+
+    /// Uses the index caches of the registries in `index`, like `~/.cargo/registry/index`.
+    pub fn from_registry_index(index: &Path) -> Option<CratesIndex> {
+        let cache_dirs = fs::read_dir(index)
+            .ok()?
+            .filter_map(|entry| Some(entry.ok()?.path().join(".cache")))
+            .filter(|it| it.is_dir())
+            .collect::<Vec<_>>();
+        (!cache_dirs.is_empty()).then_some(CratesIndex { cache_dirs })
+    }
+
+    /// The names of the cached crates starting with `prefix`, sorted.
+    pub fn crate_names(&self, prefix: &str) -> Vec<String> {
+        let prefix = prefix.to_ascii_lowercase();
+        let mut res = Vec::new();
+        for dir in &self.cache_dirs {
+            collect_names(dir, &prefix, &mut res);
+        }
+        res.sort();
+        res.dedup();
+        res
+    }
+
+    /// The versions of `name` known to the index, oldest first.
+    pub fn versions(&self, name: &str) -> Vec<IndexedVersion> {
+        if name.is_empty() || !name.is_ascii() {
+            return Vec::new();
+        }
+        let path = entry_path(&name.to_ascii_lowercase());
+        let mut res = self
+            .cache_dirs
+            .iter()
+            .filter_map(|dir| fs::read(dir.join(&path)).ok())
+            .flat_map(|entry| parse_entry(&entry))
+            .collect::<Vec<_>>();
+        res.sort_by(|a, b| a.version.cmp(&b.version));
+        res.dedup_by(|a, b| a.version == b.version);
+        res
+    }
+
+    /// The newest version of `name` that is not yanked and matches the version requirement
+    /// `req`, if any.
+    pub fn newest_matching(&self, name: &str, req: Option<&str>) -> Option<IndexedVersion> {
+        let req = match req {
+            Some(req) => Some(semver::VersionReq::parse(req).ok()?),
+            None => None,
+        };
+        self.versions(name)
+            .into_iter()
+            .rev()
+            .filter(|it| !it.yanked)
+            .find(|it| req.as_ref().map_or(true, |req| req.matches(&it.version)))
+    }
+}
+
+/// The path of the index entry of a crate relative to the index root.
+fn entry_path(name: &str) -> PathBuf {
+    match name.len() {
+        1 => ["1", name].iter().collect(),
+        2 => ["2", name].iter().collect(),
+        3 => ["3", &name[..1], name].iter().collect(),
+        _ => [&name[..2], &name[2..4], name].iter().collect(),
+    }
+}
+
+fn collect_names(cache_dir: &Path, prefix: &str, acc: &mut Vec<String>) {
+    for (name, path) in dir_entries(cache_dir) {
+        let leaves = match &*name {
+            "1" | "2" => vec![path],
+            "3" => dir_entries(&path).map(|(_, path)| path).collect(),
+            // The two levels of directories hold the first four characters of the names below
+            // them, skip those that cannot lead to a match.
+            _ if shares_prefix(&name, prefix, 0) => dir_entries(&path)
+                .filter(|(name, _)| shares_prefix(name, prefix, 2))
+                .map(|(_, path)| path)
+                .collect(),
+            _ => continue,
+        };
+        acc.extend(
+            leaves
+                .iter()
+                .flat_map(|dir| dir_entries(dir))
+                .map(|(name, _)| name)
+                .filter(|name| name.starts_with(prefix)),
+        );
+    }
+}
+
+fn dir_entries(dir: &Path) -> impl Iterator<Item = (String, PathBuf)> {
+    fs::read_dir(dir).into_iter().flatten().filter_map(|entry| {
+        let entry = entry.ok()?;
+        Some((entry.file_name().into_string().ok()?, entry.path()))
+    })
+}
+
+/// Whether `part`, the characters of a name starting at `offset`, agrees with `prefix`.
+fn shares_prefix(part: &str, prefix: &str, offset: usize) -> bool {
+    let prefix = prefix.get(offset..).unwrap_or_default();
+    let len = part.len().min(prefix.len());
+    part.get(..len) == prefix.get(..len)
+}
+
+fn parse_entry(entry: &[u8]) -> Vec<IndexedVersion> {
+    entry
+        .split(|&b| b == 0)
+        .filter(|field| field.first() == Some(&b'{'))
+        .filter_map(|line| serde_json::from_slice::<IndexLine>(line).ok())
+        .filter_map(|line| {
+            let version = semver::Version::parse(&line.vers).ok()?;
+            // Optional dependencies are implicit features, unless referred to as `dep:name`.
+            let explicit_deps = line
+                .features
+                .values()
+                .chain(line.features2.iter().flat_map(|it| it.values()))
+                .flatten()
+                .filter_map(|it| it.strip_prefix("dep:"))
+                .map(ToOwned::to_owned)
+                .collect::<Vec<_>>();
+            let mut features = line
+                .features
+                .keys()
+                .chain(line.features2.iter().flat_map(|it| it.keys()))
+                .cloned()
+                .chain(
+                    line.deps
+                        .into_iter()
+                        .filter(|dep| dep.optional && !explicit_deps.contains(&dep.name))
+                        .map(|dep| dep.name),
+                )
+                .collect::<Vec<_>>();
+            features.sort();
+            features.dedup();
+            Some(IndexedVersion { version, features, yanked: line.yanked })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entry_paths() {
+        assert_eq!(entry_path("a"), Path::new("1/a"));
+        assert_eq!(entry_path("cc"), Path::new("2/cc"));
+        assert_eq!(entry_path("syn"), Path::new("3/s/syn"));
+        assert_eq!(entry_path("serde"), Path::new("se/rd/serde"));
+    }
+
+    #[test]
+    fn parses_cache_entries() {
+        let mut entry = b"\x03\x00\x00\x00\x01etag\0".to_vec();
+        for line in [
+            r#"{"name":"foo","vers":"1.0.0","deps":[],"features":{"std":[]},"yanked":false}"#,
+            r#"{"name":"foo","vers":"1.1.0","deps":[{"name":"bar","optional":true}],"features":{"std":[]},"features2":{"baz":["dep:baz"]},"yanked":true}"#,
+        ] {
+            let version = serde_json::from_str::<serde_json::Value>(line).unwrap()["vers"]
+                .as_str()
+                .unwrap()
+                .to_owned();
+            entry.extend(version.as_bytes());
+            entry.push(0);
+            entry.extend(line.as_bytes());
+            entry.push(0);
+        }
+
+        let versions = parse_entry(&entry);
+        assert_eq!(
+            versions,
+            [
+                IndexedVersion {
+                    version: semver::Version::new(1, 0, 0),
+                    features: vec!["std".to_owned()],
+                    yanked: false,
+                },
+                IndexedVersion {
+                    version: semver::Version::new(1, 1, 0),
+                    features: vec!["bar".to_owned(), "baz".to_owned(), "std".to_owned()],
+                    yanked: true,
+                },
+            ]
+        );
+    }
+}
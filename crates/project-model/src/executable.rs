@@ -0,0 +1,129 @@
+//! Builds the executable of a cargo runnable, so that it can be launched under a debugger.
+
+use std::{
+    path::PathBuf,
+    process::{Command, Stdio},
+    sync::mpsc,
+    thread,
+    time::Duration,
+};
+
+use anyhow::{bail, format_err};
+use cargo_metadata::Message;
+use paths::AbsPathBuf;
+use serde::Deserialize;
+use stdx::JodChild;
+
+/// A compiled test or binary.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CargoExecutable {
+    pub executable: AbsPathBuf,
+    /// The directory of the manifest of the package the executable belongs to.
+    pub package_dir: AbsPathBuf,
+}
+
+/// Runs `cmd` with the build equivalent of `cargo_args`, that is `cargo build` instead of
+/// `cargo run` and `cargo test --no-run` instead of `cargo test` (same for `bench`), and returns the single
+/// executable it produced. The build is killed as soon as `is_cancelled` returns `true`.
+pub fn build_executable(
+    mut cmd: Command,
+    cargo_args: &[String],
+    is_cancelled: &dyn Fn() -> bool,
+) -> anyhow::Result<CargoExecutable> {
+    let Some((subcommand, rest)) = cargo_args.split_first() else {
+        bail!("missing cargo subcommand");
+    };
+    let tests_only = matches!(subcommand.as_str(), "test" | "bench");
+    match subcommand.as_str() {
+        "run" => cmd.arg("build"),
+        _ => cmd.arg(subcommand),
+    };
+    cmd.args(rest);
+    if tests_only && !rest.iter().any(|it| it == "--no-run") {
+        cmd.arg("--no-run");
+    }
+    cmd.arg("--message-format=json");
+
+    tracing::info!("Building executable: {:?}", cmd);
+    let mut executables = Vec::new();
+    let mut errors = String::new();
+    cmd.stdout(Stdio::piped()).stderr(Stdio::piped()).stdin(Stdio::null());
+    let mut child = JodChild::spawn(cmd)?;
+    let (stdout, stderr) = (child.stdout.take().unwrap(), child.stderr.take().unwrap());
+    let (sender, receiver) = mpsc::channel();
+    // Read the output on another thread, so that the build can be killed while cargo is quiet.
+    let reader = thread::spawn(move || {
+        let stderr_sender = sender.clone();
+        stdx::process::streaming_output(
+            stdout,
+            stderr,
+            &mut |line| {
+                let _ = sender.send(Ok(line.to_owned()));
+            },
+            &mut |line| {
+                let _ = stderr_sender.send(Err(line.to_owned()));
+            },
+        )
+    });
+    loop {
+        let line = match receiver.recv_timeout(Duration::from_millis(100)) {
+            Ok(line) => line,
+            Err(mpsc::RecvTimeoutError::Timeout) => {
+                if is_cancelled() {
+                    child.kill()?;
+                    bail!("the build was cancelled");
+                }
+                continue;
+            }
+            Err(mpsc::RecvTimeoutError::Disconnected) => break,
+        };
+        let line = match line {
+            Ok(line) => line,
+            Err(line) => {
+                errors.push_str(&line);
+                errors.push('\n');
+                continue;
+            }
+        };
+        let mut deserializer = serde_json::Deserializer::from_str(&line);
+        deserializer.disable_recursion_limit();
+        match Message::deserialize(&mut deserializer) {
+            Ok(Message::CompilerArtifact(artifact)) => {
+                let Some(executable) = artifact.executable else { continue };
+                let is_bin = artifact.target.crate_types.iter().any(|it| it == "bin");
+                let is_build_script = artifact.target.kind.iter().any(|it| it == "custom-build");
+                let is_test = artifact.profile.test;
+                if is_test || (is_bin && !is_build_script && !tests_only) {
+                    let manifest_path = PathBuf::from(artifact.manifest_path);
+                    let package_dir = manifest_path.parent().map(PathBuf::from);
+                    executables.push((executable, package_dir));
+                }
+            }
+            Ok(Message::CompilerMessage(message)) => {
+                if let Some(rendered) = message.message.rendered {
+                    errors.push_str(&rendered);
+                }
+            }
+            _ => {}
+        }
+    }
+    reader.join().map_err(|_| format_err!("failed to read the output of cargo"))??;
+    let status = child.wait()?;
+    if !status.success() {
+        bail!("cargo failed to build the executable:\n{errors}");
+    }
+
+    match &*executables {
+        [] => bail!("cargo produced no executable"),
+        [(executable, package_dir)] => {
+            let executable = AbsPathBuf::try_from(PathBuf::from(executable.clone()))
+                .map_err(|it| format_err!("executable path is not absolute: {}", it.display()))?;
+            let package_dir = package_dir
+                .clone()
+                .and_then(|it| AbsPathBuf::try_from(it).ok())
+                .ok_or_else(|| format_err!("invalid manifest path"))?;
+            Ok(CargoExecutable { executable, package_dir })
+        }
+        _ => bail!("cargo produced several executables, select a single target to debug"),
+    }
+}
@@ -20,6 +20,8 @@
 mod build_scripts;
 mod cargo_workspace;
 mod cfg_flag;
+mod crates_index;
+mod executable;
 mod manifest_path;
 mod project_json;
 mod rustc_cfg;
@@ -47,6 +49,8 @@ pub use crate::{
         CargoConfig, CargoFeatures, CargoWorkspace, Package, PackageData, PackageDependency,
         RustLibSource, Target, TargetData, TargetKind,
     },
+    crates_index::{CratesIndex, IndexedVersion},
+    executable::{build_executable, CargoExecutable},
     manifest_path::ManifestPath,
     project_json::{ProjectJson, ProjectJsonData},
     sysroot::Sysroot,
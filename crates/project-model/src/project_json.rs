@@ -34,6 +34,8 @@
 //! * file on disk
 //! * a field in the config (ie, you can send a JSON request with the contents
 //!   of rust-project.json to rust-analyzer, no need to write anything to disk)
+//! * the output of a discovery command, which lets the build system compute
+//!   the project on the fly
 //!
 //! Another possible thing we don't do today, but which would be totally valid,
 //! is to add an extension point to VS Code extension to register custom
@@ -82,6 +84,8 @@ pub struct Crate {
     pub(crate) target: Option<String>,
     pub(crate) env: FxHashMap<String, String>,
     pub(crate) proc_macro_dylib_path: Option<AbsPathBuf>,
+    /// The directory a build script placed its output in, exposed as `OUT_DIR`.
+    pub(crate) out_dir: Option<AbsPathBuf>,
     pub(crate) is_workspace_member: bool,
     pub(crate) include: Vec<AbsPathBuf>,
     pub(crate) exclude: Vec<AbsPathBuf>,
@@ -144,6 +148,7 @@ impl ProjectJson {
                         proc_macro_dylib_path: crate_data
                             .proc_macro_dylib_path
                             .map(absolutize_on_base),
+                        out_dir: crate_data.out_dir.map(absolutize_on_base),
                         is_workspace_member,
                         include,
                         exclude,
@@ -195,6 +200,7 @@ struct CrateData {
     #[serde(default)]
     env: FxHashMap<String, String>,
     proc_macro_dylib_path: Option<PathBuf>,
+    out_dir: Option<PathBuf>,
     is_workspace_member: Option<bool>,
     source: Option<CrateSource>,
     #[serde(default)]
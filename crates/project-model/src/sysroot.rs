@@ -104,6 +104,22 @@ impl Sysroot {
         self.src_root.as_ref()?.as_deref().ok()
     }
 
+    /// Returns the name of the rustup toolchain the sysroot belongs to, like
+    /// `nightly-2022-07-23-x86_64-unknown-linux-gnu`, if it is managed by rustup.
+    pub fn rustup_toolchain(&self) -> Option<&str> {
+        let toolchains = self.root.parent()?;
+        if toolchains.file_name()? != "toolchains" {
+            return None;
+        }
+        self.root.file_name()?.to_str()
+    }
+
+    /// Whether the library sources of the sysroot were looked for, but couldn't be found, which
+    /// means that the `rust-src` component is missing for rustup toolchains.
+    pub fn is_missing_rust_src(&self) -> bool {
+        matches!(self.src_root, Some(Err(_)))
+    }
+
     pub fn is_empty(&self) -> bool {
         match &self.mode {
             SysrootMode::Workspace(ws) => ws.packages().next().is_none(),
@@ -155,8 +171,7 @@ impl Sysroot {
     ) -> Result<Sysroot> {
         tracing::debug!("discovering sysroot for {dir}");
         let sysroot_dir = discover_sysroot_dir(dir, extra_env)?;
-        let sysroot_src_dir =
-            discover_sysroot_src_dir_or_add_component(&sysroot_dir, dir, extra_env);
+        let sysroot_src_dir = discover_sysroot_src_dir_or_err(&sysroot_dir);
         Ok(Sysroot::load(sysroot_dir, Some(sysroot_src_dir), metadata))
     }
 
@@ -166,8 +181,7 @@ impl Sysroot {
     ) -> Result<Sysroot> {
         tracing::debug!("discovering sysroot for {dir}");
         let sysroot_dir = discover_sysroot_dir(dir, extra_env)?;
-        let sysroot_src_dir =
-            discover_sysroot_src_dir_or_add_component(&sysroot_dir, dir, extra_env);
+        let sysroot_src_dir = discover_sysroot_src_dir_or_err(&sysroot_dir);
         Ok(Sysroot::load(sysroot_dir, Some(sysroot_src_dir), false))
     }
 
@@ -423,29 +437,17 @@ fn discover_sysroot_src_dir(sysroot_path: &AbsPathBuf) -> Option<AbsPathBuf> {
     get_rust_src(sysroot_path)
 }
 
-fn discover_sysroot_src_dir_or_add_component(
-    sysroot_path: &AbsPathBuf,
-    current_dir: &AbsPath,
-    extra_env: &FxHashMap<String, String>,
-) -> Result<AbsPathBuf> {
-    discover_sysroot_src_dir(sysroot_path)
-        .or_else(|| {
-            let mut rustup = Command::new(toolchain::rustup());
-            rustup.envs(extra_env);
-            rustup.current_dir(current_dir).args(["component", "add", "rust-src"]);
-            tracing::info!("adding rust-src component by {:?}", rustup);
-            utf8_stdout(rustup).ok()?;
-            get_rust_src(sysroot_path)
-        })
-        .ok_or_else(|| {
-            format_err!(
-                "\
+fn discover_sysroot_src_dir_or_err(sysroot_path: &AbsPathBuf) -> Result<AbsPathBuf> {
+    discover_sysroot_src_dir(sysroot_path).ok_or_else(|| {
+        format_err!(
+            "\
 can't load standard library from sysroot
 {sysroot_path}
 (discovered via `rustc --print sysroot`)
-try installing the Rust source the same way you installed rustc",
-            )
-        })
+try installing the Rust source the same way you installed rustc, for rustup toolchains that's
+`rustup component add rust-src`",
+        )
+    })
 }
 
 fn get_rustc_src(sysroot_path: &AbsPath) -> Option<ManifestPath> {
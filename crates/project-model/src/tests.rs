@@ -12,8 +12,8 @@ use serde::de::DeserializeOwned;
 use triomphe::Arc;
 
 use crate::{
-    CargoWorkspace, CfgOverrides, ProjectJson, ProjectJsonData, ProjectWorkspace, Sysroot,
-    WorkspaceBuildScripts,
+    CargoConfig, CargoFeatures, CargoWorkspace, CfgOverrides, ProjectJson, ProjectJsonData,
+    ProjectWorkspace, Sysroot, WorkspaceBuildScripts,
 };
 
 fn load_cargo(file: &str) -> (CrateGraph, ProcMacroPaths) {
@@ -230,6 +230,45 @@ fn rust_project_is_proc_macro_has_proc_macro_dep() {
     crate_data.dependencies.iter().find(|&dep| dep.name.deref() == "proc_macro").unwrap();
 }
 
+#[test]
+fn rust_project_out_dir_sets_env() {
+    let (crate_graph, _proc_macros) = load_rust_project("out-dir-project.json");
+    let crate_id = crate_graph.iter().max().unwrap();
+    let out_dir = crate_graph[crate_id].env.get("OUT_DIR").unwrap();
+    assert!(Path::new(&out_dir).ends_with("target/out"), "{out_dir}");
+}
+
+#[test]
+fn sysroot_missing_rust_src() {
+    let mut root = "$ROOT$.rustup/toolchains/stable-x86_64-unknown-linux-gnu".to_owned();
+    replace_root(&mut root, true);
+    let sysroot =
+        Sysroot::load(AbsPathBuf::assert(root.into()), Some(Err(anyhow::anyhow!("no src"))), false);
+    assert!(sysroot.is_missing_rust_src());
+    assert_eq!(sysroot.rustup_toolchain(), Some("stable-x86_64-unknown-linux-gnu"));
+
+    let sysroot = get_fake_sysroot();
+    assert!(!sysroot.is_missing_rust_src());
+    assert_eq!(sysroot.rustup_toolchain(), None);
+}
+
+#[test]
+fn cargo_workspace_metadata_overrides() {
+    let mut meta: cargo_metadata::Metadata = get_test_json_file("hello-world-metadata.json");
+    meta.workspace_metadata = serde_json::json!({
+        "rust-analyzer": { "features": ["serde"], "check_command": "clippy" }
+    });
+    let cargo = CargoWorkspace::new(meta);
+    assert_eq!(cargo.metadata().check_command.as_deref(), Some("clippy"));
+
+    let config = CargoConfig::default();
+    let ws_config = config.with_overrides(cargo.metadata());
+    assert_eq!(
+        ws_config.features,
+        CargoFeatures::Selected { features: vec!["serde".to_owned()], no_default_features: false }
+    );
+}
+
 #[test]
 fn crate_graph_dedup_identical() {
     let (mut crate_graph, proc_macros) =
@@ -333,7 +333,24 @@ impl ProjectWorkspace {
                         "Failed to read Cargo metadata from Cargo.toml file {cargo_toml}, {toolchain:?}",
                     )
                 })?;
-                let cargo = CargoWorkspace::new(meta);
+                let mut cargo = CargoWorkspace::new(meta);
+                let ws_config = config.with_overrides(cargo.metadata());
+                if ws_config.features != config.features {
+                    // The workspace asks for its own features, which change the resolve.
+                    let meta = CargoWorkspace::fetch_metadata(
+                        cargo_toml,
+                        cargo_toml.parent(),
+                        &ws_config,
+                        sysroot_ref,
+                        progress,
+                    )
+                    .with_context(|| {
+                        format!(
+                            "Failed to read Cargo metadata from Cargo.toml file {cargo_toml}, {toolchain:?}",
+                        )
+                    })?;
+                    cargo = CargoWorkspace::new(meta);
+                }
 
                 let cargo_config_extra_env =
                     cargo_config_env(cargo_toml, &config.extra_env, sysroot_ref);
@@ -356,6 +373,28 @@ impl ProjectWorkspace {
         Ok(res)
     }
 
+    /// Runs the project discovery `command` in `root` and loads the `rust-project.json` it prints
+    /// to its standard output. Relative paths in it are relative to `root`.
+    pub fn load_discovered(
+        command: &[String],
+        root: &AbsPath,
+        config: &CargoConfig,
+    ) -> anyhow::Result<ProjectWorkspace> {
+        let [program, args @ ..] = command else {
+            return Err(format_err!("the project discovery command is empty"));
+        };
+        let mut cmd = Command::new(program);
+        cmd.args(args).current_dir(root).envs(&config.extra_env);
+        let stdout = utf8_stdout(cmd)?;
+        let data = serde_json::from_str(&stdout)
+            .with_context(|| format!("Failed to deserialize the output of `{program}`"))?;
+        Ok(ProjectWorkspace::load_inline(
+            ProjectJson::new(root, data),
+            config.target.as_deref(),
+            &config.extra_env,
+        ))
+    }
+
     pub fn load_inline(
         project_json: ProjectJson,
         target: Option<&str>,
@@ -619,7 +658,7 @@ impl ProjectWorkspace {
                 .crates()
                 .map(|(_, krate)| PackageRoot {
                     is_local: krate.is_workspace_member,
-                    include: krate.include.clone(),
+                    include: krate.include.iter().chain(&krate.out_dir).cloned().collect(),
                     exclude: krate.exclude.clone(),
                 })
                 .collect::<FxHashSet<_>>()
@@ -881,13 +920,19 @@ fn project_json_to_crate_graph(
                     target,
                     env,
                     proc_macro_dylib_path,
+                    out_dir,
                     is_proc_macro,
                     repository,
                     ..
                 },
                 file_id,
             )| {
-                let env = env.clone().into_iter().collect();
+                let mut env: Env = env.clone().into_iter().collect();
+                if let Some(out_dir) = out_dir.as_ref().and_then(|it| it.as_os_str().to_str()) {
+                    if env.get("OUT_DIR").is_none() {
+                        env.set("OUT_DIR", out_dir.to_owned());
+                    }
+                }
 
                 let target_cfgs = match target.as_deref() {
                     Some(target) => cfg_cache.entry(target).or_insert_with(|| {
@@ -11,10 +11,16 @@ extern crate rustc_driver as _;
 
 mod rustc_wrapper;
 
-use std::{env, fs, path::PathBuf, process::ExitCode, sync::Arc};
+use std::{
+    env, fs,
+    net::{SocketAddr, TcpListener, ToSocketAddrs},
+    path::PathBuf,
+    process::ExitCode,
+    sync::Arc,
+};
 
 use anyhow::Context;
-use lsp_server::Connection;
+use lsp_server::{Connection, ProtocolError};
 use rust_analyzer::{cli::flags, config::Config, from_json};
 use tracing_subscriber::fmt::writer::BoxMakeWriter;
 use vfs::AbsPathBuf;
@@ -66,11 +72,18 @@ fn actual_main() -> anyhow::Result<ExitCode> {
             // a secondary latency-sensitive thread with an increased stack size.
             // We use this thread intent because any delay in the main loop
             // will make actions like hitting enter in the editor slow.
-            with_extra_thread(
-                "LspServer",
-                stdx::thread::ThreadIntent::LatencySensitive,
-                run_server,
-            )?;
+            match cmd.listen {
+                Some(addr) => with_extra_thread(
+                    "LspServer",
+                    stdx::thread::ThreadIntent::LatencySensitive,
+                    move || run_daemon(addr),
+                )?,
+                None => with_extra_thread(
+                    "LspServer",
+                    stdx::thread::ThreadIntent::LatencySensitive,
+                    run_server,
+                )?,
+            }
         }
         flags::RustAnalyzerCmd::Parse(cmd) => cmd.run()?,
         flags::RustAnalyzerCmd::Symbols(cmd) => cmd.run()?,
@@ -162,19 +175,80 @@ fn run_server() -> anyhow::Result<()> {
 
     let (connection, io_threads) = Connection::stdio();
 
-    let (initialize_id, initialize_params) = match connection.initialize_start() {
+    let config = match initialize(&connection, false) {
         Ok(it) => it,
         Err(e) => {
-            if e.channel_is_disconnected() {
+            if e.downcast_ref::<ProtocolError>().map_or(false, |e| e.channel_is_disconnected()) {
                 io_threads.join()?;
             }
-            return Err(e.into());
+            return Err(e);
         }
     };
+
+    rust_analyzer::main_loop(config, connection)?;
+
+    io_threads.join()?;
+    tracing::info!("server did shut down");
+    Ok(())
+}
+
+fn run_daemon(addr: String) -> anyhow::Result<()> {
+    tracing::info!("server version {} will listen on {addr}", rust_analyzer::version());
+
+    let addrs: Vec<SocketAddr> =
+        addr.to_socket_addrs().with_context(|| format!("invalid address {addr}"))?.collect();
+    // Clients can make the server run cargo and build scripts, so only let this machine in.
+    if let Some(addr) = addrs.iter().find(|it| !it.ip().is_loopback()) {
+        anyhow::bail!("can't listen on {addr}, only loopback addresses are allowed");
+    }
+    let listener = TcpListener::bind(&*addrs).with_context(|| format!("can't listen on {addr}"))?;
+
+    let (sender, clients) = crossbeam_channel::unbounded();
+    stdx::thread::Builder::new(stdx::thread::ThreadIntent::Worker)
+        .name("DaemonListener".to_owned())
+        .allow_leak(true)
+        .spawn(move || loop {
+            let (connection, io_threads) = match Connection::accept(&listener) {
+                Ok(it) => it,
+                Err(e) => {
+                    tracing::error!("can't accept clients anymore: {e}");
+                    break;
+                }
+            };
+            let sender = sender.clone();
+            // A client that is slow to initialize shouldn't keep the others waiting.
+            let handshake = stdx::thread::Builder::new(stdx::thread::ThreadIntent::Worker)
+                .name("DaemonHandshake".to_owned())
+                .allow_leak(true)
+                .spawn(move || {
+                    match initialize(&connection, true) {
+                        Ok(config) => {
+                            if sender.send((config, connection)).is_err() {
+                                return;
+                            }
+                        }
+                        Err(e) => tracing::warn!("failed to initialize a client: {e}"),
+                    }
+                    if let Err(e) = io_threads.join() {
+                        tracing::warn!("connection to a client failed: {e}");
+                    }
+                });
+            if let Err(e) = handshake {
+                tracing::error!("failed to spawn a thread: {e}");
+            }
+        })?;
+
+    rust_analyzer::daemon_main_loop(clients)
+}
+
+/// Performs the initialization handshake with the client of `connection`, which is `shared`
+/// when it's one of the clients of a daemon.
+fn initialize(connection: &Connection, shared: bool) -> anyhow::Result<Config> {
+    let (initialize_id, initialize_params) = connection.initialize_start()?;
     tracing::info!("InitializeParams: {}", initialize_params);
     let lsp_types::InitializeParams {
         root_uri,
-        capabilities,
+        mut capabilities,
         workspace_folders,
         initialization_options,
         client_info,
@@ -210,6 +284,13 @@ fn run_server() -> anyhow::Result<()> {
         })
         .filter(|workspaces| !workspaces.is_empty())
         .unwrap_or_else(|| vec![root_path.clone()]);
+    if shared {
+        // The clients of a daemon share the open documents, so they must agree on how positions
+        // are encoded, and UTF-16 is the encoding every client supports.
+        if let Some(general) = &mut capabilities.general {
+            general.position_encodings = None;
+        }
+    }
     let mut config = Config::new(root_path, capabilities, workspace_roots, is_visual_studio_code);
     if let Some(json) = initialization_options {
         if let Err(e) = config.update(json) {
@@ -238,22 +319,12 @@ fn run_server() -> anyhow::Result<()> {
 
     let initialize_result = serde_json::to_value(initialize_result).unwrap();
 
-    if let Err(e) = connection.initialize_finish(initialize_id, initialize_result) {
-        if e.channel_is_disconnected() {
-            io_threads.join()?;
-        }
-        return Err(e.into());
-    }
+    connection.initialize_finish(initialize_id, initialize_result)?;
 
     if !config.has_linked_projects() && config.detached_files().is_empty() {
         config.rediscover_workspaces();
     }
-
-    rust_analyzer::main_loop(config, connection)?;
-
-    io_threads.join()?;
-    tracing::info!("server did shut down");
-    Ok(())
+    Ok(config)
 }
 
 fn patch_path_prefix(path: PathBuf) -> PathBuf {
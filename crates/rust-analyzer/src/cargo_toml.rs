@@ -0,0 +1,501 @@
+//! A forgiving reader for the dependency tables of `Cargo.toml` files, used to complete and check
+//! dependency names, versions and features while the file is being edited.
+//!
+//! This is not a TOML parser: it only tracks the keys and string values of the document together
+//! with their dotted paths and ranges, and recovers from the incomplete input that typing produces.
+
+use std::ops::Range;
+
+/// What is being written at a position in a `Cargo.toml`.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum CompletionTarget {
+    /// The name of a dependency.
+    CrateName,
+    /// The version requirement of a dependency.
+    Version { krate: String },
+    /// A feature of a dependency.
+    Feature { krate: String },
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct CompletionContext {
+    pub(crate) target: CompletionTarget,
+    /// The text typed so far.
+    pub(crate) prefix: String,
+    /// The range the completion replaces.
+    pub(crate) range: Range<usize>,
+}
+
+/// A feature enabled on a dependency.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct DependencyFeature {
+    /// The name of the crate, taking `package` renames into account.
+    pub(crate) krate: String,
+    /// The version requirement of the dependency, if any.
+    pub(crate) version: Option<String>,
+    pub(crate) feature: String,
+    pub(crate) range: Range<usize>,
+}
+
+pub(crate) fn completion_context(text: &str, offset: usize) -> Option<CompletionContext> {
+    let doc = Document::parse(text);
+    if let Some(value) =
+        doc.strings.iter().find(|it| it.range.start <= offset && offset <= it.range.end)
+    {
+        let (table, dep, rest) = dependency_path(&value.path)?;
+        let krate = doc.package_name(table, dep);
+        let target = match rest {
+            [] => CompletionTarget::Version { krate },
+            [key] if key == "version" && !value.in_array => CompletionTarget::Version { krate },
+            [key] if key == "features" && value.in_array => CompletionTarget::Feature { krate },
+            _ => return None,
+        };
+        return Some(CompletionContext {
+            target,
+            prefix: text[value.range.start..offset].to_owned(),
+            range: value.range.clone(),
+        });
+    }
+    let key = doc.keys.iter().find(|it| it.range.start < offset && offset <= it.range.end)?;
+    match dependency_path(&key.path)? {
+        (_, _, []) => Some(CompletionContext {
+            target: CompletionTarget::CrateName,
+            prefix: text[key.range.start..offset].to_owned(),
+            range: key.range.clone(),
+        }),
+        _ => None,
+    }
+}
+
+pub(crate) fn dependency_features(text: &str) -> Vec<DependencyFeature> {
+    let doc = Document::parse(text);
+    doc.strings
+        .iter()
+        .filter(|it| it.in_array)
+        .filter_map(|value| {
+            let (table, dep, rest) = dependency_path(&value.path)?;
+            if rest != ["features"] {
+                return None;
+            }
+            Some(DependencyFeature {
+                krate: doc.package_name(table, dep),
+                version: doc.dependency_version(table, dep),
+                feature: value.text.clone(),
+                range: value.range.clone(),
+            })
+        })
+        .collect()
+}
+
+/// Splits the path of a key or value in a dependency table into the path of the table, the name
+/// of the dependency and the path within the dependency.
+fn dependency_path(path: &[String]) -> Option<(&[String], &str, &[String])> {
+    let idx = path.iter().position(|it| {
+        matches!(it.as_str(), "dependencies" | "dev-dependencies" | "build-dependencies")
+    })?;
+    let is_dependency_table = match &path[..idx] {
+        [] => true,
+        [workspace] => workspace == "workspace",
+        [target, _] => target == "target",
+        _ => false,
+    };
+    if !is_dependency_table {
+        return None;
+    }
+    let dep = path.get(idx + 1)?;
+    Some((&path[..=idx], dep, &path[idx + 2..]))
+}
+
+#[derive(Debug)]
+struct Key {
+    /// The full path of the key, including the table it is in.
+    path: Vec<String>,
+    range: Range<usize>,
+}
+
+#[derive(Debug)]
+struct StringValue {
+    path: Vec<String>,
+    in_array: bool,
+    text: String,
+    /// The range of the contents, without the quotes.
+    range: Range<usize>,
+}
+
+#[derive(Default)]
+struct Document {
+    keys: Vec<Key>,
+    strings: Vec<StringValue>,
+}
+
+impl Document {
+    fn parse(text: &str) -> Document {
+        let mut parser = Parser { tokens: tokenize(text), pos: 0, doc: Document::default() };
+        parser.parse();
+        parser.doc
+    }
+
+    fn string_at(&self, path: &[&str]) -> Option<&StringValue> {
+        self.strings.iter().find(|it| !it.in_array && it.path.iter().eq(path.iter().copied()))
+    }
+
+    fn package_name(&self, table: &[String], dep: &str) -> String {
+        let mut path = table.iter().map(String::as_str).collect::<Vec<_>>();
+        path.extend([dep, "package"]);
+        self.string_at(&path).map_or_else(|| dep.to_owned(), |it| it.text.clone())
+    }
+
+    fn dependency_version(&self, table: &[String], dep: &str) -> Option<String> {
+        let mut path = table.iter().map(String::as_str).collect::<Vec<_>>();
+        path.push(dep);
+        let version = self.string_at(&path).or_else(|| {
+            path.push("version");
+            self.string_at(&path)
+        })?;
+        Some(version.text.clone())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TokenKind {
+    LBrack,
+    RBrack,
+    LCurly,
+    RCurly,
+    Eq,
+    Comma,
+    Dot,
+    Newline,
+    Str,
+    Bare,
+}
+
+#[derive(Debug)]
+struct Token {
+    kind: TokenKind,
+    /// The contents of strings without quotes, the text of other tokens.
+    text: String,
+    range: Range<usize>,
+    /// Whether the token is the first one on its line.
+    starts_line: bool,
+}
+
+fn tokenize(text: &str) -> Vec<Token> {
+    let bytes = text.as_bytes();
+    let mut tokens = Vec::new();
+    let mut starts_line = true;
+    let mut pos = 0;
+    while pos < bytes.len() {
+        let start = pos;
+        let kind = match bytes[pos] {
+            b' ' | b'\t' | b'\r' => {
+                pos += 1;
+                continue;
+            }
+            b'#' => {
+                while pos < bytes.len() && bytes[pos] != b'\n' {
+                    pos += 1;
+                }
+                continue;
+            }
+            b'\n' => TokenKind::Newline,
+            b'[' => TokenKind::LBrack,
+            b']' => TokenKind::RBrack,
+            b'{' => TokenKind::LCurly,
+            b'}' => TokenKind::RCurly,
+            b'=' => TokenKind::Eq,
+            b',' => TokenKind::Comma,
+            b'.' => TokenKind::Dot,
+            quote @ (b'"' | b'\'') => {
+                // Unterminated strings end at the end of the line, as they do while typing.
+                pos += 1;
+                while pos < bytes.len() && bytes[pos] != quote && bytes[pos] != b'\n' {
+                    if quote == b'"' && bytes[pos] == b'\\' {
+                        pos += 1;
+                    }
+                    pos += 1;
+                }
+                let end = pos.min(bytes.len());
+                if pos < bytes.len() && bytes[pos] == quote {
+                    pos += 1;
+                }
+                tokens.push(Token {
+                    kind: TokenKind::Str,
+                    text: text[start + 1..end].to_owned(),
+                    range: start + 1..end,
+                    starts_line,
+                });
+                starts_line = false;
+                continue;
+            }
+            c if is_bare_char(c) => {
+                while pos < bytes.len() && is_bare_char(bytes[pos]) {
+                    pos += 1;
+                }
+                tokens.push(Token {
+                    kind: TokenKind::Bare,
+                    text: text[start..pos].to_owned(),
+                    range: start..pos,
+                    starts_line,
+                });
+                starts_line = false;
+                continue;
+            }
+            _ => {
+                pos += 1;
+                continue;
+            }
+        };
+        pos += 1;
+        tokens.push(Token {
+            kind,
+            text: text[start..pos].to_owned(),
+            range: start..pos,
+            starts_line,
+        });
+        starts_line = kind == TokenKind::Newline;
+    }
+    tokens
+}
+
+fn is_bare_char(c: u8) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, b'_' | b'-' | b'+' | b':')
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    doc: Document,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn at(&self, kind: TokenKind) -> bool {
+        self.peek().map_or(false, |it| it.kind == kind)
+    }
+
+    fn eat(&mut self, kind: TokenKind) -> bool {
+        let res = self.at(kind);
+        if res {
+            self.pos += 1;
+        }
+        res
+    }
+
+    fn skip_line(&mut self) {
+        while !self.at(TokenKind::Newline) && self.peek().is_some() {
+            self.pos += 1;
+        }
+    }
+
+    fn skip_newlines(&mut self) {
+        while self.eat(TokenKind::Newline) {}
+    }
+
+    fn parse(&mut self) {
+        let mut table = Vec::new();
+        while let Some(token) = self.peek() {
+            match token.kind {
+                TokenKind::Newline => self.pos += 1,
+                TokenKind::LBrack => {
+                    self.pos += 1;
+                    // Arrays of tables, like `[[bin]]`, hold no dependencies but may hold keys
+                    // that look like them.
+                    let is_array = self.eat(TokenKind::LBrack);
+                    table = self.key_path(&[]);
+                    if is_array {
+                        table.insert(0, String::new());
+                    }
+                    self.skip_line();
+                }
+                TokenKind::Str | TokenKind::Bare => {
+                    let path = self.key_path(&table);
+                    if self.eat(TokenKind::Eq) {
+                        self.value(&path, false);
+                    }
+                    self.skip_line();
+                }
+                _ => self.skip_line(),
+            }
+        }
+    }
+
+    /// Parses a dotted key, recording every segment of it.
+    fn key_path(&mut self, prefix: &[String]) -> Vec<String> {
+        let mut path = prefix.to_vec();
+        while let Some(token) = self.peek() {
+            if !matches!(token.kind, TokenKind::Str | TokenKind::Bare) {
+                break;
+            }
+            path.push(token.text.clone());
+            self.doc.keys.push(Key { path: path.clone(), range: token.range.clone() });
+            self.pos += 1;
+            if !self.eat(TokenKind::Dot) {
+                break;
+            }
+        }
+        path
+    }
+
+    fn value(&mut self, path: &[String], in_array: bool) {
+        let Some(token) = self.peek() else { return };
+        match token.kind {
+            TokenKind::Str => {
+                self.doc.strings.push(StringValue {
+                    path: path.to_vec(),
+                    in_array,
+                    text: token.text.clone(),
+                    range: token.range.clone(),
+                });
+                self.pos += 1;
+            }
+            TokenKind::Bare => {
+                // Numbers, booleans and dates, which may contain dots.
+                self.pos += 1;
+                while self.at(TokenKind::Dot) {
+                    self.pos += 1;
+                    self.eat(TokenKind::Bare);
+                }
+            }
+            TokenKind::LBrack => {
+                self.pos += 1;
+                loop {
+                    self.skip_newlines();
+                    match self.peek() {
+                        None => break,
+                        Some(token) if token.kind == TokenKind::RBrack => {
+                            self.pos += 1;
+                            break;
+                        }
+                        // An unclosed array is ended by the next table header.
+                        Some(token) if token.kind == TokenKind::LBrack && token.starts_line => {
+                            break
+                        }
+                        Some(_) => (),
+                    }
+                    let pos = self.pos;
+                    self.value(path, true);
+                    self.skip_newlines();
+                    if !self.eat(TokenKind::Comma) && self.pos == pos {
+                        self.pos += 1;
+                    }
+                }
+            }
+            TokenKind::LCurly => {
+                self.pos += 1;
+                // Inline tables cannot span lines, an unclosed one ends with its line.
+                while let Some(token) = self.peek() {
+                    match token.kind {
+                        TokenKind::RCurly => {
+                            self.pos += 1;
+                            break;
+                        }
+                        TokenKind::Newline => break,
+                        TokenKind::Str | TokenKind::Bare => {
+                            let path = self.key_path(path);
+                            if self.eat(TokenKind::Eq) {
+                                self.value(&path, false);
+                            }
+                            self.eat(TokenKind::Comma);
+                        }
+                        _ => self.pos += 1,
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn completion(text: &str) -> Option<CompletionContext> {
+        let offset = text.find("$0").unwrap();
+        let text = text.replace("$0", "");
+        completion_context(&text, offset)
+    }
+
+    #[test]
+    fn completes_crate_names() {
+        let ctx = completion("[dependencies]\nser$0\n").unwrap();
+        assert_eq!(ctx.target, CompletionTarget::CrateName);
+        assert_eq!(ctx.prefix, "ser");
+        assert_eq!(ctx.range, 15..18);
+
+        let ctx = completion("[target.'cfg(unix)'.dev-dependencies]\nli$0 = \"0.2\"\n").unwrap();
+        assert_eq!(ctx.target, CompletionTarget::CrateName);
+
+        assert_eq!(completion("[package]\nna$0\n"), None);
+        assert_eq!(completion("[dependencies]\nserde = { vers$0 }\n"), None);
+    }
+
+    #[test]
+    fn completes_versions() {
+        let ctx = completion("[dependencies]\nserde = \"1.$0\n").unwrap();
+        assert_eq!(ctx.target, CompletionTarget::Version { krate: "serde".to_owned() });
+        assert_eq!(ctx.prefix, "1.");
+
+        let ctx =
+            completion("[dependencies]\nfoo = { package = \"bar\", version = \"$0\" }\n").unwrap();
+        assert_eq!(ctx.target, CompletionTarget::Version { krate: "bar".to_owned() });
+        assert_eq!(ctx.prefix, "");
+
+        let ctx = completion("[dependencies.serde]\nversion = \"1$0\"\n").unwrap();
+        assert_eq!(ctx.target, CompletionTarget::Version { krate: "serde".to_owned() });
+    }
+
+    #[test]
+    fn completes_features() {
+        let ctx =
+            completion("[dependencies]\nserde = { version = \"1\", features = [\"de$0\n").unwrap();
+        assert_eq!(ctx.target, CompletionTarget::Feature { krate: "serde".to_owned() });
+        assert_eq!(ctx.prefix, "de");
+
+        let ctx = completion(
+            "[workspace.dependencies.tokio]\nfeatures = [\n    \"rt\",\n    \"ma$0\",\n]\n",
+        )
+        .unwrap();
+        assert_eq!(ctx.target, CompletionTarget::Feature { krate: "tokio".to_owned() });
+        assert_eq!(ctx.prefix, "ma");
+    }
+
+    #[test]
+    fn collects_dependency_features() {
+        let text = r#"
+[package]
+name = "foo"
+features = ["not-a-dependency"]
+
+[dependencies]
+serde = { version = "1.0", features = ["derive", "rc"] }
+renamed = { package = "tokio", features = ["full"] }
+
+[dev-dependencies.regex]
+version = "1"
+features = [
+    "unicode", # comment
+]
+"#;
+        let features = dependency_features(text)
+            .into_iter()
+            .map(|it| {
+                assert_eq!(&text[it.range.clone()], it.feature);
+                format!("{} {:?} {}", it.krate, it.version, it.feature)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            features,
+            [
+                "serde Some(\"1.0\") derive",
+                "serde Some(\"1.0\") rc",
+                "tokio None full",
+                "regex Some(\"1\") unicode",
+            ]
+        );
+    }
+}
@@ -23,6 +23,8 @@ use hir::{Module, Name};
 use hir_ty::db::HirDatabase;
 use ide::AnalysisHost;
 use itertools::Itertools;
+use profile::Bytes;
+use rustc_hash::FxHashMap;
 use vfs::Vfs;
 
 #[derive(Clone, Copy)]
@@ -55,9 +57,29 @@ fn report_metric(metric: &str, value: u64, unit: &str) {
     println!("METRIC:{metric}:{value}:{unit}")
 }
 
-fn print_memory_usage(mut host: AnalysisHost, vfs: Vfs) {
-    let mem = host.per_query_memory_usage();
+/// The memory used by the queries of the database, grouped by query group, largest group first.
+fn memory_usage_by_group(
+    host: &mut AnalysisHost,
+) -> Vec<(&'static str, Bytes, Vec<(String, Bytes, usize)>)> {
+    let mut groups = FxHashMap::<_, (Bytes, Vec<_>)>::default();
+    for (group, name, bytes, entries) in host.per_query_memory_usage() {
+        let (total, queries) = groups.entry(group).or_default();
+        *total += bytes;
+        queries.push((name, bytes, entries));
+    }
+    let mut groups =
+        groups.into_iter().map(|(group, (total, queries))| (group, total, queries)).collect_vec();
+    groups.sort_by_key(|&(_, total, _)| std::cmp::Reverse(total));
+    groups
+}
 
+/// Prints the memory usage of `mem`, as collected by [`memory_usage_by_group`], along with the
+/// memory used by the VFS and the rest of the database.
+fn print_memory_usage(
+    host: AnalysisHost,
+    vfs: Vfs,
+    mem: Vec<(&'static str, Bytes, Vec<(String, Bytes, usize)>)>,
+) {
     let before = profile::memory_usage();
     drop(vfs);
     let vfs = before.allocated - profile::memory_usage().allocated;
@@ -67,9 +89,12 @@ fn print_memory_usage(mut host: AnalysisHost, vfs: Vfs) {
     let unaccounted = before.allocated - profile::memory_usage().allocated;
     let remaining = profile::memory_usage().allocated;
 
-    for (name, bytes, entries) in mem {
+    for (group, total, queries) in mem {
         // NOTE: Not a debug print, so avoid going through the `eprintln` defined above.
-        eprintln!("{bytes:>8} {entries:>6} {name}");
+        eprintln!("{total:>8}        {group}");
+        for (name, bytes, entries) in queries {
+            eprintln!("{bytes:>8} {entries:>6}   {name}");
+        }
     }
     eprintln!("{vfs:>8}        VFS");
 
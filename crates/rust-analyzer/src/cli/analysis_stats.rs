@@ -8,7 +8,8 @@ use std::{
 
 use hir::{
     db::{DefDatabase, ExpandDatabase, HirDatabase},
-    Adt, AssocItem, Crate, DefWithBody, HasSource, HirDisplay, HirFileIdExt, ModuleDef, Name,
+    Adt, AssocItem, Crate, DefWithBody, HasSource, HirDisplay, HirFileIdExt, InFile, ModuleDef,
+    Name,
 };
 use hir_def::{
     body::{BodySourceMap, SyntheticSyntax},
@@ -33,12 +34,13 @@ use profile::{Bytes, StopWatch};
 use project_model::{CargoConfig, ProjectManifest, ProjectWorkspace, RustLibSource};
 use rayon::prelude::*;
 use rustc_hash::{FxHashMap, FxHashSet};
+use serde::Serialize;
 use syntax::{AstNode, SyntaxNode};
 use vfs::{AbsPathBuf, FileId, Vfs, VfsPath};
 
 use crate::cli::{
     flags::{self, OutputFormat},
-    full_name_of_item, print_memory_usage,
+    full_name_of_item, memory_usage_by_group, print_memory_usage,
     progress_report::ProgressReport,
     report_metric, Verbosity,
 };
@@ -90,7 +92,7 @@ impl flags::AnalysisStats {
             Some(build_scripts_sw.elapsed())
         };
 
-        let (host, vfs, _proc_macro) =
+        let (mut host, vfs, _proc_macro) =
             load_workspace(workspace.clone(), &cargo_config.extra_env, &load_cargo_config)?;
         let db = host.raw_database();
         eprint!("{:<20} {}", "Database loaded:", db_load_sw.elapsed());
@@ -101,6 +103,11 @@ impl flags::AnalysisStats {
         eprintln!(")");
 
         let mut analysis_sw = self.stop_watch();
+        let mut report = Report {
+            // Keep stdout for the JSON output only.
+            metrics_to_stderr: self.output == Some(OutputFormat::Json),
+            ..Report::default()
+        };
 
         let mut krates = Crate::all(db);
         if self.randomize {
@@ -127,7 +134,7 @@ impl flags::AnalysisStats {
         eprintln!("  item trees: {num_item_trees}");
         let item_tree_time = item_tree_sw.elapsed();
         eprintln!("{:<20} {}", "Item Tree Collection:", item_tree_time);
-        report_metric("item tree time", item_tree_time.time.as_millis() as u64, "ms");
+        report.metric("item tree time", item_tree_time.time.as_millis() as u64, "ms");
 
         let mut crate_def_map_sw = self.stop_watch();
         let mut num_crates = 0;
@@ -205,30 +212,30 @@ impl flags::AnalysisStats {
         );
         let crate_def_map_time = crate_def_map_sw.elapsed();
         eprintln!("{:<20} {}", "Item Collection:", crate_def_map_time);
-        report_metric("crate def map time", crate_def_map_time.time.as_millis() as u64, "ms");
+        report.metric("crate def map time", crate_def_map_time.time.as_millis() as u64, "ms");
 
         if self.randomize {
             shuffle(&mut rng, &mut bodies);
         }
 
         if !self.skip_lowering {
-            self.run_body_lowering(db, &vfs, &bodies, verbosity);
+            self.run_body_lowering(db, &vfs, &bodies, verbosity, &mut report);
         }
 
         if !self.skip_inference {
-            self.run_inference(db, &vfs, &bodies, verbosity);
+            self.run_inference(db, &vfs, &bodies, verbosity, &mut report);
         }
 
         if !self.skip_mir_stats {
-            self.run_mir_lowering(db, &bodies, verbosity);
+            self.run_mir_lowering(db, &bodies, verbosity, &mut report);
         }
 
         if !self.skip_data_layout {
-            self.run_data_layout(db, &adts, verbosity);
+            self.run_data_layout(db, &adts, verbosity, &mut report);
         }
 
         if !self.skip_const_eval {
-            self.run_const_eval(db, &consts, verbosity);
+            self.run_const_eval(db, &consts, verbosity, &mut report);
         }
 
         if self.run_all_ide_things {
@@ -236,16 +243,16 @@ impl flags::AnalysisStats {
         }
 
         if self.run_term_search {
-            self.run_term_search(&workspace, db, &vfs, file_ids, verbosity);
+            self.run_term_search(&workspace, db, &vfs, file_ids, verbosity, &mut report);
         }
 
         let total_span = analysis_sw.elapsed();
         eprintln!("{:<20} {total_span}", "Total:");
-        report_metric("total time", total_span.time.as_millis() as u64, "ms");
+        report.metric("total time", total_span.time.as_millis() as u64, "ms");
         if let Some(instructions) = total_span.instructions {
-            report_metric("total instructions", instructions, "#instr");
+            report.metric("total instructions", instructions, "#instr");
         }
-        report_metric("total memory", total_span.memory.allocated.megabytes() as u64, "MB");
+        report.metric("total memory", total_span.memory.allocated.megabytes() as u64, "MB");
 
         if env::var("RA_COUNT").is_ok() {
             eprintln!("{}", profile::countme::get_all());
@@ -265,14 +272,44 @@ impl flags::AnalysisStats {
             eprintln!("source files: {total_file_size}, macro files: {total_macro_file_size}");
         }
 
+        // Collecting the memory usage clears the database, so do it only once.
+        let memory = (self.output == Some(OutputFormat::Json) || verbosity.is_verbose())
+            .then(|| memory_usage_by_group(&mut host))
+            .unwrap_or_default();
+
+        if self.output == Some(OutputFormat::Json) {
+            report.memory = memory
+                .iter()
+                .map(|(group, total, queries)| QueryGroupMemory {
+                    group: *group,
+                    bytes: total.bytes(),
+                    queries: queries
+                        .iter()
+                        .map(|(name, bytes, entries)| QueryMemory {
+                            name: name.clone(),
+                            bytes: bytes.bytes(),
+                            entries: *entries,
+                        })
+                        .collect(),
+                })
+                .collect();
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        }
+
         if verbosity.is_verbose() {
-            print_memory_usage(host, vfs);
+            print_memory_usage(host, vfs, memory);
         }
 
         Ok(())
     }
 
-    fn run_data_layout(&self, db: &RootDatabase, adts: &[hir::Adt], verbosity: Verbosity) {
+    fn run_data_layout(
+        &self,
+        db: &RootDatabase,
+        adts: &[hir::Adt],
+        verbosity: Verbosity,
+        report: &mut Report,
+    ) {
         let mut sw = self.stop_watch();
         let mut all = 0;
         let mut fail = 0;
@@ -298,11 +335,17 @@ impl flags::AnalysisStats {
         let data_layout_time = sw.elapsed();
         eprintln!("{:<20} {}", "Data layouts:", data_layout_time);
         eprintln!("Failed data layouts: {fail} ({}%)", percentage(fail, all));
-        report_metric("failed data layouts", fail, "#");
-        report_metric("data layout time", data_layout_time.time.as_millis() as u64, "ms");
+        report.metric("failed data layouts", fail, "#");
+        report.metric("data layout time", data_layout_time.time.as_millis() as u64, "ms");
     }
 
-    fn run_const_eval(&self, db: &RootDatabase, consts: &[hir::Const], verbosity: Verbosity) {
+    fn run_const_eval(
+        &self,
+        db: &RootDatabase,
+        consts: &[hir::Const],
+        verbosity: Verbosity,
+        report: &mut Report,
+    ) {
         let mut sw = self.stop_watch();
         let mut all = 0;
         let mut fail = 0;
@@ -321,8 +364,8 @@ impl flags::AnalysisStats {
         let const_eval_time = sw.elapsed();
         eprintln!("{:<20} {}", "Const evaluation:", const_eval_time);
         eprintln!("Failed const evals: {fail} ({}%)", percentage(fail, all));
-        report_metric("failed const evals", fail, "#");
-        report_metric("const eval time", const_eval_time.time.as_millis() as u64, "ms");
+        report.metric("failed const evals", fail, "#");
+        report.metric("const eval time", const_eval_time.time.as_millis() as u64, "ms");
     }
 
     fn run_term_search(
@@ -332,6 +375,7 @@ impl flags::AnalysisStats {
         vfs: &Vfs,
         mut file_ids: Vec<FileId>,
         verbosity: Verbosity,
+        report: &mut Report,
     ) {
         let cargo_config = CargoConfig {
             sysroot: match self.no_sysroot {
@@ -526,12 +570,18 @@ impl flags::AnalysisStats {
             term_search_time.time.as_millis() as u64 / acc.total_tail_exprs
         ));
         bar.println(format!("{:<20} {}", "Term search:", term_search_time));
-        report_metric("term search time", term_search_time.time.as_millis() as u64, "ms");
+        report.metric("term search time", term_search_time.time.as_millis() as u64, "ms");
 
         bar.finish_and_clear();
     }
 
-    fn run_mir_lowering(&self, db: &RootDatabase, bodies: &[DefWithBody], verbosity: Verbosity) {
+    fn run_mir_lowering(
+        &self,
+        db: &RootDatabase,
+        bodies: &[DefWithBody],
+        verbosity: Verbosity,
+        report: &mut Report,
+    ) {
         let mut sw = self.stop_watch();
         let mut all = 0;
         let mut fail = 0;
@@ -560,8 +610,8 @@ impl flags::AnalysisStats {
         let mir_lowering_time = sw.elapsed();
         eprintln!("{:<20} {}", "MIR lowering:", mir_lowering_time);
         eprintln!("Mir failed bodies: {fail} ({}%)", percentage(fail, all));
-        report_metric("mir failed bodies", fail, "#");
-        report_metric("mir lowering time", mir_lowering_time.time.as_millis() as u64, "ms");
+        report.metric("mir failed bodies", fail, "#");
+        report.metric("mir lowering time", mir_lowering_time.time.as_millis() as u64, "ms");
     }
 
     fn run_inference(
@@ -570,6 +620,7 @@ impl flags::AnalysisStats {
         vfs: &Vfs,
         bodies: &[DefWithBody],
         verbosity: Verbosity,
+        report: &mut Report,
     ) {
         let mut bar = match verbosity {
             Verbosity::Quiet | Verbosity::Spammy => ProgressReport::hidden(),
@@ -627,14 +678,7 @@ impl flags::AnalysisStats {
             }
             let msg = move || {
                 if verbosity.is_verbose() {
-                    let source = match body_id {
-                        DefWithBody::Function(it) => it.source(db).map(|it| it.syntax().cloned()),
-                        DefWithBody::Static(it) => it.source(db).map(|it| it.syntax().cloned()),
-                        DefWithBody::Const(it) => it.source(db).map(|it| it.syntax().cloned()),
-                        DefWithBody::Variant(it) => it.source(db).map(|it| it.syntax().cloned()),
-                        DefWithBody::InTypeConst(_) => unimplemented!(),
-                    };
-                    if let Some(src) = source {
+                    if let Some(src) = body_syntax(db, body_id) {
                         let original_file = src.file_id.original_file(db);
                         let path = vfs.file_path(original_file);
                         let syntax_range = src.value.text_range();
@@ -749,6 +793,7 @@ impl flags::AnalysisStats {
                 ));
             }
             // endregion:expressions
+            let body_exprs_unknown = num_exprs_unknown - previous_unknown;
 
             // region:patterns
             let (previous_pats, previous_unknown, previous_partially_unknown) =
@@ -846,6 +891,15 @@ impl flags::AnalysisStats {
                 ));
             }
             // endregion:patterns
+            let body_pats_unknown = num_pats_unknown - previous_unknown;
+            if body_exprs_unknown > 0 || body_pats_unknown > 0 {
+                report.unknown_types.push(UnknownTypes {
+                    function: full_name(),
+                    location: body_location(db, vfs, body_id),
+                    exprs: body_exprs_unknown,
+                    pats: body_pats_unknown,
+                });
+            }
             bar.inc(1);
         }
 
@@ -869,12 +923,25 @@ impl flags::AnalysisStats {
             percentage(num_pats_partially_unknown, num_pats),
             num_pat_type_mismatches
         );
+        eprintln!("  bodies with unknown types: {}", report.unknown_types.len());
+        if verbosity.is_verbose() {
+            for it in &report.unknown_types {
+                eprintln!(
+                    "    {} ({}): {} exprs, {} pats",
+                    it.function,
+                    it.location.as_deref().unwrap_or("unknown location"),
+                    it.exprs,
+                    it.pats
+                );
+            }
+        }
         eprintln!("{:<20} {}", "Inference:", inference_time);
-        report_metric("unknown type", num_exprs_unknown, "#");
-        report_metric("type mismatches", num_expr_type_mismatches, "#");
-        report_metric("pattern unknown type", num_pats_unknown, "#");
-        report_metric("pattern type mismatches", num_pat_type_mismatches, "#");
-        report_metric("inference time", inference_time.time.as_millis() as u64, "ms");
+        report.metric("unknown type", num_exprs_unknown, "#");
+        report.metric("bodies with unknown types", report.unknown_types.len() as u64, "#");
+        report.metric("type mismatches", num_expr_type_mismatches, "#");
+        report.metric("pattern unknown type", num_pats_unknown, "#");
+        report.metric("pattern type mismatches", num_pat_type_mismatches, "#");
+        report.metric("inference time", inference_time.time.as_millis() as u64, "ms");
     }
 
     fn run_body_lowering(
@@ -883,6 +950,7 @@ impl flags::AnalysisStats {
         vfs: &Vfs,
         bodies: &[DefWithBody],
         verbosity: Verbosity,
+        report: &mut Report,
     ) {
         let mut bar = match verbosity {
             Verbosity::Quiet | Verbosity::Spammy => ProgressReport::hidden(),
@@ -921,14 +989,7 @@ impl flags::AnalysisStats {
             }
             let msg = move || {
                 if verbosity.is_verbose() {
-                    let source = match body_id {
-                        DefWithBody::Function(it) => it.source(db).map(|it| it.syntax().cloned()),
-                        DefWithBody::Static(it) => it.source(db).map(|it| it.syntax().cloned()),
-                        DefWithBody::Const(it) => it.source(db).map(|it| it.syntax().cloned()),
-                        DefWithBody::Variant(it) => it.source(db).map(|it| it.syntax().cloned()),
-                        DefWithBody::InTypeConst(_) => unimplemented!(),
-                    };
-                    if let Some(src) = source {
+                    if let Some(src) = body_syntax(db, body_id) {
                         let original_file = src.file_id.original_file(db);
                         let path = vfs.file_path(original_file);
                         let syntax_range = src.value.text_range();
@@ -951,7 +1012,7 @@ impl flags::AnalysisStats {
         bar.finish_and_clear();
         let body_lowering_time = sw.elapsed();
         eprintln!("{:<20} {}", "Body lowering:", body_lowering_time);
-        report_metric("body lowering time", body_lowering_time.time.as_millis() as u64, "ms");
+        report.metric("body lowering time", body_lowering_time.time.as_millis() as u64, "ms");
     }
 
     fn run_ide_things(&self, analysis: Analysis, mut file_ids: Vec<FileId>) {
@@ -1040,6 +1101,79 @@ impl flags::AnalysisStats {
     }
 }
 
+/// The statistics printed by `--output json`.
+#[derive(Default, Serialize)]
+struct Report {
+    metrics: Vec<Metric>,
+    /// The bodies whose inference produced unknown types.
+    unknown_types: Vec<UnknownTypes>,
+    memory: Vec<QueryGroupMemory>,
+    #[serde(skip)]
+    metrics_to_stderr: bool,
+}
+
+impl Report {
+    fn metric(&mut self, name: &'static str, value: u64, unit: &'static str) {
+        if self.metrics_to_stderr {
+            if std::env::var("RA_METRICS").is_ok() {
+                eprintln!("METRIC:{name}:{value}:{unit}");
+            }
+        } else {
+            report_metric(name, value, unit);
+        }
+        self.metrics.push(Metric { name, value, unit });
+    }
+}
+
+#[derive(Serialize)]
+struct Metric {
+    name: &'static str,
+    value: u64,
+    unit: &'static str,
+}
+
+#[derive(Serialize)]
+struct UnknownTypes {
+    function: String,
+    /// `path:line` of the body.
+    location: Option<String>,
+    exprs: u64,
+    pats: u64,
+}
+
+#[derive(Serialize)]
+struct QueryGroupMemory {
+    group: &'static str,
+    bytes: isize,
+    queries: Vec<QueryMemory>,
+}
+
+#[derive(Serialize)]
+struct QueryMemory {
+    name: String,
+    bytes: isize,
+    entries: usize,
+}
+
+fn body_syntax(db: &RootDatabase, body_id: DefWithBody) -> Option<InFile<SyntaxNode>> {
+    match body_id {
+        DefWithBody::Function(it) => it.source(db).map(|it| it.syntax().cloned()),
+        DefWithBody::Static(it) => it.source(db).map(|it| it.syntax().cloned()),
+        DefWithBody::Const(it) => it.source(db).map(|it| it.syntax().cloned()),
+        DefWithBody::Variant(it) => it.source(db).map(|it| it.syntax().cloned()),
+        DefWithBody::InTypeConst(_) => None,
+    }
+}
+
+fn body_location(db: &RootDatabase, vfs: &Vfs, body_id: DefWithBody) -> Option<String> {
+    let src = body_syntax(db, body_id)?;
+    let original_range = src.as_ref().original_file_range(db);
+    let path = vfs.file_path(original_range.file_id);
+    let line_index = db.line_index(original_range.file_id);
+    let start = line_index.line_col(original_range.range.start());
+    Some(format!("{path}:{}", start.line + 1))
+}
+
 fn location_csv_expr(db: &RootDatabase, vfs: &Vfs, sm: &BodySourceMap, expr_id: ExprId) -> String {
     let src = match sm.expr_syntax(expr_id) {
         Ok(s) => s,
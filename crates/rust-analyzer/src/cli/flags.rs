@@ -34,6 +34,10 @@ xflags::xflags! {
 
             /// Dump a LSP config JSON schema.
             optional --print-config-schema
+
+            /// [Unstable] Run as a daemon which serves the clients connecting to this loopback
+            /// address at the same time, keeping the analysis while they come and go.
+            optional --listen addr: String
         }
 
         /// Parse stdin.
@@ -56,6 +60,8 @@ xflags::xflags! {
             /// Directory with Cargo.toml.
             required path: PathBuf
 
+            /// Print every unknown type and type mismatch as `csv`, or a summary of all
+            /// statistics as `json`.
             optional --output format: OutputFormat
 
             /// Randomize order in which crates, modules, and items are processed.
@@ -188,6 +194,7 @@ pub enum RustAnalyzerCmd {
 pub struct LspServer {
     pub version: bool,
     pub print_config_schema: bool,
+    pub listen: Option<String>,
 }
 
 #[derive(Debug)]
@@ -293,6 +300,7 @@ impl RustAnalyzer {
 #[derive(Debug, PartialEq, Eq)]
 pub enum OutputFormat {
     Csv,
+    Json,
 }
 
 impl RustAnalyzer {
@@ -314,6 +322,7 @@ impl FromStr for OutputFormat {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             "csv" => Ok(Self::Csv),
+            "json" => Ok(Self::Json),
             _ => Err(format!("unknown output format `{s}`")),
         }
     }
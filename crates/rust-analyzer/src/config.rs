@@ -11,6 +11,7 @@ use std::{
     fmt, iter,
     ops::Not,
     path::{Path, PathBuf},
+    time::Duration,
 };
 
 use cfg::{CfgAtom, CfgDiff};
@@ -19,7 +20,7 @@ use ide::{
     AssistConfig, CallableSnippets, CompletionConfig, DiagnosticsConfig, ExprFillDefaultMode,
     HighlightConfig, HighlightRelatedConfig, HoverConfig, HoverDocFormat, InlayFieldsToResolve,
     InlayHintsConfig, JoinLinesConfig, MemoryLayoutHoverConfig, MemoryLayoutHoverRenderKind,
-    Snippet, SnippetScope,
+    PanicErrorType, PromotedConstPlacement, Snippet, SnippetScope, WrapReturnTypeCallers,
 };
 use ide_db::{
     imports::insert_use::{ImportGranularity, InsertUseConfig, PrefixKind},
@@ -67,6 +68,18 @@ config_data! {
         assist_emitMustUse: bool               = "false",
         /// Placeholder expression to use for missing expressions in assists.
         assist_expressionFillDefault: ExprFillDefaultDef              = "\"todo\"",
+        /// Whether the fill match arms assist should add all missing patterns as a single arm
+        /// joined with `|`, instead of one arm per pattern.
+        assist_groupMissingMatchArms: bool = "false",
+        /// Whether implementing missing trait members fills in delegating bodies for well-known
+        /// traits, like `Display` through `Debug` or `Deref` to the only field, instead of `todo!()`.
+        assist_smartImplMemberStubs: bool = "false",
+        /// Which error type the convert panics to `Result` assist gives the function.
+        assist_panicToResult_errorType: PanicErrorTypeDef = "\"enum\"",
+        /// Where the promote local to module-level constant assist declares the constant.
+        assist_promoteLocalToConst_placement: PromotedConstPlacementDef = "\"before_item\"",
+        /// How the wrap return type assists update calls to the function.
+        assist_wrapReturnType_updateCallers: WrapReturnTypeCallersDef = "\"none\"",
 
         /// Warm up caches on project load.
         cachePriming_enable: bool = "true",
@@ -211,8 +224,12 @@ config_data! {
         ///
         /// If `$saved_file` is part of the command, rust-analyzer will pass
         /// the absolute path of the saved file to the provided command. This is
-        /// intended to be used with non-Cargo build systems.
-        /// Note that `$saved_file` is experimental and may be removed in the futureg.
+        /// intended to be used with non-Cargo build systems. Saving files of
+        /// dependencies outside of the workspace doesn't run the command.
+        /// Note that `$saved_file` is experimental and may be removed in the future.
+        ///
+        /// `$workspace_root` is replaced with the root of the workspace being checked,
+        /// both in the command and in the values of `#rust-analyzer.check.extraEnv#`.
         ///
         /// An example command would be:
         ///
@@ -291,16 +308,34 @@ config_data! {
                 "scope": "expr"
             }
         }"#,
+        /// Milliseconds after which a completion request that isn't done yet is answered with the
+        /// names declared in the current file, which don't need any semantic analysis. The client
+        /// asks again as the user keeps typing. If `None`, completion always waits for the full
+        /// results.
+        completion_softBudget: Option<usize> = "null",
+        /// Whether struct literal completions of types implementing `Default` should only fill in
+        /// the fields whose types don't implement `Default`, leaving the rest to a
+        /// `..Default::default()`.
+        completion_structLiteral_defaultRest_enable: bool = "false",
         /// Whether to enable term search based snippets like `Some(foo.bar().baz())`.
         completion_termSearch_enable: bool = "false",
 
         /// List of rust-analyzer diagnostics to disable.
         diagnostics_disabled: FxHashSet<String> = "[]",
+        /// `.gitignore`-style glob patterns of files, relative to the workspace root, for which
+        /// rust-analyzer will not report its own diagnostics, e.g. `"**/generated/**"`. The files
+        /// are still analyzed as usual and `cargo check` diagnostics are still shown.
+        diagnostics_disabledIn: Vec<String> = "[]",
         /// Whether to show native rust-analyzer diagnostics.
         diagnostics_enable: bool                = "true",
         /// Whether to show experimental rust-analyzer diagnostics that might
         /// have more false positives than usual.
         diagnostics_experimental_enable: bool    = "false",
+        /// Whether the client requests the diagnostics of the documents it shows (pull
+        /// diagnostics), instead of rust-analyzer sending them after every change. Only
+        /// has an effect if the client supports pull diagnostics. Diagnostics of
+        /// `#rust-analyzer.check.command#` are always sent.
+        diagnostics_pull_enable: bool = "false",
         /// Map of prefixes to be substituted when parsing diagnostic file paths.
         /// This should be the reverse mapping of what is passed to `rustc` as `--remap-path-prefix`.
         diagnostics_remapPrefix: FxHashMap<String, String> = "{}",
@@ -318,6 +353,24 @@ config_data! {
         /// relative to the workspace root, and globs are not supported. You may
         /// also need to add the folders to Code's `files.watcherExclude`.
         files_excludeDirs: Vec<PathBuf> = "[]",
+        /// `.gitignore`-style glob patterns of files and directories that rust-analyzer
+        /// will not load, relative to the roots of the workspace, e.g. `"data/"` or `"**/*.bin"`.
+        files_excludeGlobs: Vec<String> = "[]",
+        /// These directories contain generated code. Their files are analyzed as
+        /// usual, but rust-analyzer will not report diagnostics for them. They are relative
+        /// to the workspace root.
+        files_generatedDirs: Vec<PathBuf> = "[]",
+        /// Files larger than this many bytes are treated as generated code, like the files in
+        /// `#rust-analyzer.files.generatedDirs#`. A file stays generated once it exceeds the size,
+        /// until it is deleted. The function bodies of such files are only lowered once they are
+        /// opened or used, so items declared inside them are left out of the symbol index.
+        files_generatedMinSize: Option<usize> = "null",
+        /// Files larger than this many bytes are not analyzed.
+        files_maxSize: usize = "10000000",
+        /// Whether to skip directories ignored by `.gitignore` files when loading the
+        /// workspace. Note that ignored directories are often still part of the build, like
+        /// generated or vendored sources.
+        files_respectGitignore: bool = "false",
         /// Controls file watching implementation.
         files_watcher: FilesWatcherDef = "\"client\"",
 
@@ -355,6 +408,9 @@ config_data! {
         /// Whether to show keyword hover popups. Only applies when
         /// `#rust-analyzer.hover.documentation.enable#` is set.
         hover_documentation_keywords_enable: bool  = "true",
+        /// Whether to show whether dropping a local variable runs any code, that is whether its
+        /// type or anything it owns implements `Drop`.
+        hover_dropGlue_enable: bool = "false",
         /// Use markdown syntax for links on hover.
         hover_links_enable: bool = "true",
         /// How to render the align information in a memory layout hover.
@@ -367,6 +423,10 @@ config_data! {
         hover_memoryLayout_offset: Option<MemoryLayoutHoverRenderKindDef> = "\"hexadecimal\"",
         /// How to render the size information in a memory layout hover.
         hover_memoryLayout_size: Option<MemoryLayoutHoverRenderKindDef> = "\"both\"",
+        /// Milliseconds after which a hover request that isn't done yet is answered without the
+        /// hover actions and the memory layout. If `None`, hover always waits for the full
+        /// results.
+        hover_softBudget: Option<usize> = "null",
 
         /// Whether to enforce the import granularity setting for all files. If set to false rust-analyzer will try to keep import styles consistent per file.
         imports_granularity_enforce: bool              = "false",
@@ -482,7 +542,10 @@ config_data! {
 
         /// Number of syntax trees rust-analyzer keeps in memory. Defaults to 128.
         lru_capacity: Option<usize>                 = "null",
-        /// Sets the LRU capacity of the specified queries.
+        /// Sets the LRU capacity of the specified queries, e.g.
+        /// `{ "ParseMacroExpansionQuery": 128 }`. Lower capacities reduce memory
+        /// usage at the cost of recomputing more often. The `Memory Usage` command
+        /// shows which queries use the most memory.
         lru_query_capacities: FxHashMap<Box<str>, usize> = "{}",
 
         /// Whether to show `can't find Cargo.toml` error message.
@@ -585,6 +648,10 @@ config_data! {
         /// By disabling semantic tokens for strings, other grammars can be used to highlight
         /// their contents.
         semanticHighlighting_strings_enable: bool = "true",
+        /// Inject regex and SQL highlighting into string literals marked with a
+        /// `// language=regex` or `// language=sql` comment, placed right before the literal or
+        /// above the statement containing it.
+        semanticHighlighting_strings_injections_enable: bool = "false",
 
         /// Show full signature of the callable. Only shows parameters if disabled.
         signatureInfo_detail: SignatureDetail                           = "\"full\"",
@@ -594,6 +661,18 @@ config_data! {
         /// Whether to insert closing angle brackets when typing an opening angle bracket of a generic argument list.
         typing_autoClosingAngleBrackets_enable: bool = "false",
 
+        /// Command to run in the workspace root to discover the project, for build systems other
+        /// than Cargo. It must print the project in the `rust-project.json` format to its standard
+        /// output, paths in it are relative to the workspace root.
+        workspace_discoverCommand: Option<Vec<String>> = "null",
+        /// Directory in which the symbols of dependencies are persisted between sessions, so that
+        /// workspace symbol search covers them right after startup, before they are indexed
+        /// again. Relative paths are relative to the workspace root. Nothing is persisted if this
+        /// is not set.
+        workspace_symbol_cachePath: Option<PathBuf> = "null",
+        /// Exclude `#[doc(hidden)]` and non-public items of dependencies from workspace symbol
+        /// search. Adding `~` to the query includes them again.
+        workspace_symbol_search_excludeHidden: bool = "true",
         /// Workspace symbol search kind.
         workspace_symbol_search_kind: WorkspaceSymbolSearchKindDef = "\"only_types\"",
         /// Limits the number of items returned from a workspace symbol search (Defaults to 128).
@@ -621,6 +700,7 @@ pub struct Config {
     data: ConfigData,
     detached_files: Vec<AbsPathBuf>,
     snippets: Vec<Snippet>,
+    diagnostics_disabled_in: Vec<PathGlob>,
     is_visual_studio_code: bool,
 }
 
@@ -744,6 +824,88 @@ impl HoverActionsConfig {
 pub struct FilesConfig {
     pub watcher: FilesWatcher,
     pub exclude: Vec<AbsPathBuf>,
+    pub exclude_globs: Vec<String>,
+    pub respect_gitignore: bool,
+    pub generated: Vec<AbsPathBuf>,
+    pub generated_min_size: Option<usize>,
+    pub max_size: usize,
+}
+
+impl FilesConfig {
+    pub fn is_generated(&self, path: &AbsPath) -> bool {
+        self.generated.iter().any(|dir| path.starts_with(dir))
+    }
+}
+
+/// A glob of `diagnostics.disabledIn`, matched against the path components relative to the
+/// workspace root. Like in `.gitignore`, a glob without a `/` matches at any depth, a trailing `/`
+/// only matches directories, and matching a directory matches the files inside of it.
+#[derive(Debug, Clone)]
+struct PathGlob {
+    components: Vec<String>,
+    dir_only: bool,
+}
+
+impl PathGlob {
+    fn new(glob: &str) -> Option<PathGlob> {
+        let glob = glob.trim();
+        let dir_only = glob.ends_with('/');
+        let glob = glob.trim_end_matches('/');
+        let anchored = glob.contains('/');
+        let mut components =
+            glob.split('/').filter(|it| !it.is_empty()).map(ToOwned::to_owned).collect::<Vec<_>>();
+        if components.is_empty() {
+            return None;
+        }
+        if !anchored {
+            components.insert(0, "**".to_owned());
+        }
+        Some(PathGlob { components, dir_only })
+    }
+
+    fn matches(&self, path: &[&str]) -> bool {
+        // Every proper prefix of the path is a directory containing the file.
+        let max_len = if self.dir_only { path.len().saturating_sub(1) } else { path.len() };
+        (1..=max_len).any(|len| glob_matches_components(&self.components, &path[..len]))
+    }
+}
+
+fn glob_matches_components(glob: &[String], path: &[&str]) -> bool {
+    match glob.split_first() {
+        None => path.is_empty(),
+        Some((first, rest)) if first == "**" => {
+            (0..=path.len()).any(|skip| glob_matches_components(rest, &path[skip..]))
+        }
+        Some((first, rest)) => match path.split_first() {
+            Some((name, path_rest)) => {
+                glob_matches_name(first, name) && glob_matches_components(rest, path_rest)
+            }
+            None => false,
+        },
+    }
+}
+
+fn glob_matches_name(glob: &str, name: &str) -> bool {
+    let mut glob_chars = glob.chars();
+    match glob_chars.next() {
+        None => name.is_empty(),
+        Some('*') => {
+            let rest = glob_chars.as_str();
+            name.char_indices()
+                .map(|(idx, _)| idx)
+                .chain([name.len()])
+                .any(|idx| glob_matches_name(rest, &name[idx..]))
+        }
+        Some(c) => {
+            let mut name_chars = name.chars();
+            match name_chars.next() {
+                Some(n) if c == '?' || c == n => {
+                    glob_matches_name(glob_chars.as_str(), name_chars.as_str())
+                }
+                _ => false,
+            }
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -782,6 +944,8 @@ pub struct WorkspaceSymbolConfig {
     pub search_kind: WorkspaceSymbolSearchKind,
     /// How many items are returned at most.
     pub search_limit: usize,
+    /// Whether `#[doc(hidden)]` and non-public items of dependencies are skipped.
+    pub exclude_hidden: bool,
 }
 
 pub struct ClientCommandsConfig {
@@ -790,6 +954,8 @@ pub struct ClientCommandsConfig {
     pub show_reference: bool,
     pub goto_location: bool,
     pub trigger_parameter_hints: bool,
+    pub enable_cfg: bool,
+    pub apply_ssr_rule: bool,
 }
 
 #[derive(Debug)]
@@ -829,6 +995,7 @@ impl Config {
             discovered_projects: Vec::new(),
             root_path,
             snippets: Default::default(),
+            diagnostics_disabled_in: Vec::new(),
             workspace_roots,
             is_visual_studio_code,
         }
@@ -894,6 +1061,8 @@ impl Config {
                 )),
             }
         }
+        self.diagnostics_disabled_in =
+            self.data.diagnostics_disabledIn.iter().filter_map(|it| PathGlob::new(it)).collect();
 
         self.validate(&mut errors);
 
@@ -929,6 +1098,10 @@ impl Config {
     pub fn detached_files(&self) -> &[AbsPathBuf] {
         &self.detached_files
     }
+
+    pub fn discover_command(&self) -> Option<&[String]> {
+        self.data.workspace_discoverCommand.as_deref().filter(|it| !it.is_empty())
+    }
 }
 
 macro_rules! try_ {
@@ -1138,6 +1311,11 @@ impl Config {
         self.experimental("serverStatusNotification")
     }
 
+    /// Whether the client implements a test explorer and wants to be notified about test changes.
+    pub fn test_explorer(&self) -> bool {
+        self.experimental("testExplorer")
+    }
+
     /// Whether the client supports colored output for full diagnostics from `checkOnSave`.
     pub fn color_diagnostic_output(&self) -> bool {
         self.experimental("colorDiagnosticOutput")
@@ -1147,6 +1325,20 @@ impl Config {
         self.data.diagnostics_enable
     }
 
+    /// Whether native diagnostics are requested by the client, rather than published.
+    pub fn pull_diagnostics(&self) -> bool {
+        self.data.diagnostics_pull_enable
+            && try_!(self.caps.text_document.as_ref()?.diagnostic.as_ref()?).is_some()
+    }
+
+    pub fn diagnostic_dynamic_registration(&self) -> bool {
+        try_or_def!(self.caps.text_document.as_ref()?.diagnostic.as_ref()?.dynamic_registration?)
+    }
+
+    pub fn diagnostics_refresh(&self) -> bool {
+        try_or_def!(self.caps.workspace.as_ref()?.diagnostic.as_ref()?.refresh_support?)
+    }
+
     pub fn diagnostics(&self) -> DiagnosticsConfig {
         DiagnosticsConfig {
             enabled: self.data.diagnostics_enable,
@@ -1164,6 +1356,22 @@ impl Config {
         }
     }
 
+    pub fn diagnostics_disabled_in_globs(&self) -> &[String] {
+        &self.data.diagnostics_disabledIn
+    }
+
+    /// Whether native diagnostics are disabled for `path` by `diagnostics.disabledIn`.
+    pub fn diagnostics_disabled_in(&self, path: &AbsPath) -> bool {
+        if self.diagnostics_disabled_in.is_empty() {
+            return false;
+        }
+        let Some(path) = path.strip_prefix(&self.root_path) else { return false };
+        let path: &Path = path.as_ref();
+        let components =
+            path.components().filter_map(|it| it.as_os_str().to_str()).collect::<Vec<_>>();
+        self.diagnostics_disabled_in.iter().any(|glob| glob.matches(&components))
+    }
+
     pub fn diagnostics_map(&self) -> DiagnosticsMapConfig {
         DiagnosticsMapConfig {
             remap_prefix: self.data.diagnostics_remapPrefix.clone(),
@@ -1227,6 +1435,16 @@ impl Config {
                 _ => FilesWatcher::Server,
             },
             exclude: self.data.files_excludeDirs.iter().map(|it| self.root_path.join(it)).collect(),
+            exclude_globs: self.data.files_excludeGlobs.clone(),
+            respect_gitignore: self.data.files_respectGitignore,
+            generated: self
+                .data
+                .files_generatedDirs
+                .iter()
+                .map(|it| self.root_path.join(it))
+                .collect(),
+            generated_min_size: self.data.files_generatedMinSize,
+            max_size: self.data.files_maxSize,
         }
     }
 
@@ -1532,6 +1750,10 @@ impl Config {
         }
     }
 
+    pub fn completion_soft_budget(&self) -> Option<Duration> {
+        self.data.completion_softBudget.map(|it| Duration::from_millis(it as u64))
+    }
+
     pub fn completion(&self) -> CompletionConfig {
         CompletionConfig {
             enable_postfix_completions: self.data.completion_postfix_enable,
@@ -1541,6 +1763,7 @@ impl Config {
             enable_private_editable: self.data.completion_privateEditable_enable,
             enable_term_search: self.data.completion_termSearch_enable,
             full_function_signatures: self.data.completion_fullFunctionSignatures_enable,
+            struct_literal_default_rest: self.data.completion_structLiteral_defaultRest_enable,
             callable: match self.data.completion_callable_snippets {
                 CallableCompletionDef::FillArguments => Some(CallableSnippets::FillArguments),
                 CallableCompletionDef::AddParentheses => Some(CallableSnippets::AddParentheses),
@@ -1584,6 +1807,21 @@ impl Config {
             prefer_no_std: self.data.imports_preferNoStd,
             prefer_prelude: self.data.imports_preferPrelude,
             assist_emit_must_use: self.data.assist_emitMustUse,
+            group_missing_match_arms: self.data.assist_groupMissingMatchArms,
+            smart_impl_member_stubs: self.data.assist_smartImplMemberStubs,
+            wrap_return_type_callers: match self.data.assist_wrapReturnType_updateCallers {
+                WrapReturnTypeCallersDef::None => WrapReturnTypeCallers::None,
+                WrapReturnTypeCallersDef::Try => WrapReturnTypeCallers::Try,
+                WrapReturnTypeCallersDef::Unwrap => WrapReturnTypeCallers::Unwrap,
+            },
+            promoted_const_placement: match self.data.assist_promoteLocalToConst_placement {
+                PromotedConstPlacementDef::BeforeItem => PromotedConstPlacement::BeforeItem,
+                PromotedConstPlacementDef::ModuleTop => PromotedConstPlacement::ModuleTop,
+            },
+            panic_error_type: match self.data.assist_panicToResult_errorType {
+                PanicErrorTypeDef::Enum => PanicErrorType::Enum,
+                PanicErrorTypeDef::Anyhow => PanicErrorType::Anyhow,
+            },
         }
     }
 
@@ -1620,6 +1858,10 @@ impl Config {
         }
     }
 
+    pub fn hover_soft_budget(&self) -> Option<Duration> {
+        self.data.hover_softBudget.map(|it| Duration::from_millis(it as u64))
+    }
+
     pub fn hover_actions(&self) -> HoverActionsConfig {
         let enable = self.experimental("hoverActions") && self.data.hover_actions_enable;
         HoverActionsConfig {
@@ -1646,6 +1888,7 @@ impl Config {
             operator: self.data.semanticHighlighting_operator_enable,
             specialize_operator: self.data.semanticHighlighting_operator_specialization_enable,
             inject_doc_comment: self.data.semanticHighlighting_doc_comment_inject_enable,
+            inject_string_languages: self.data.semanticHighlighting_strings_injections_enable,
             syntactic_name_ref_highlighting: false,
         }
     }
@@ -1683,6 +1926,7 @@ impl Config {
                 }
             },
             keywords: self.data.hover_documentation_keywords_enable,
+            drop_glue: self.data.hover_dropGlue_enable,
         }
     }
 
@@ -1699,9 +1943,14 @@ impl Config {
                 WorkspaceSymbolSearchKindDef::AllSymbols => WorkspaceSymbolSearchKind::AllSymbols,
             },
             search_limit: self.data.workspace_symbol_search_limit,
+            exclude_hidden: self.data.workspace_symbol_search_excludeHidden,
         }
     }
 
+    pub fn workspace_symbol_cache_path(&self) -> Option<AbsPathBuf> {
+        self.data.workspace_symbol_cachePath.as_ref().map(|it| self.root_path.join(it))
+    }
+
     pub fn semantic_tokens_refresh(&self) -> bool {
         try_or_def!(self.caps.workspace.as_ref()?.semantic_tokens.as_ref()?.refresh_support?)
     }
@@ -1743,6 +1992,8 @@ impl Config {
             show_reference: get("rust-analyzer.showReferences"),
             goto_location: get("rust-analyzer.gotoLocation"),
             trigger_parameter_hints: get("editor.action.triggerParameterHints"),
+            enable_cfg: get("rust-analyzer.enableCfg"),
+            apply_ssr_rule: get("rust-analyzer.applySsrRule"),
         }
     }
 
@@ -1954,6 +2205,28 @@ enum ExprFillDefaultDef {
     Default,
 }
 
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "snake_case")]
+enum WrapReturnTypeCallersDef {
+    None,
+    Try,
+    Unwrap,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "snake_case")]
+enum PromotedConstPlacementDef {
+    BeforeItem,
+    ModuleTop,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "snake_case")]
+enum PanicErrorTypeDef {
+    Enum,
+    Anyhow,
+}
+
 #[derive(Deserialize, Debug, Clone)]
 #[serde(rename_all = "snake_case")]
 enum ImportGranularityDef {
@@ -2301,6 +2574,31 @@ fn field_props(field: &str, ty: &str, doc: &[&str], default: &str) -> serde_json
                 "Fill missing expressions with reasonable defaults, `new` or `default` constructors."
             ],
         },
+        "WrapReturnTypeCallersDef" => set! {
+            "type": "string",
+            "enum": ["none", "try", "unwrap"],
+            "enumDescriptions": [
+                "Leave calls to the function unchanged.",
+                "Propagate the new return value with `?` in callers returning the same type, unwrap it elsewhere.",
+                "Unwrap the new return value at all calls."
+            ],
+        },
+        "PromotedConstPlacementDef" => set! {
+            "type": "string",
+            "enum": ["before_item", "module_top"],
+            "enumDescriptions": [
+                "Declare the constant right before the item containing the local.",
+                "Declare the constant at the top of the module, after its imports."
+            ],
+        },
+        "PanicErrorTypeDef" => set! {
+            "type": "string",
+            "enum": ["enum", "anyhow"],
+            "enumDescriptions": [
+                "Generate an error enum for the function, with a variant per distinct error type.",
+                "Use `anyhow::Error`, attaching panic messages as context."
+            ],
+        },
         "ImportGranularityDef" => set! {
             "type": "string",
             "enum": ["preserve", "crate", "module", "item", "one"],
@@ -2794,4 +3092,29 @@ mod tests {
             matches!(config.flycheck(), FlycheckConfig::CargoCommand { target_dir, .. } if target_dir == Some(PathBuf::from("other_folder")))
         );
     }
+
+    #[test]
+    fn diagnostics_disabled_in() {
+        let mut config = Config::new(
+            AbsPathBuf::try_from(project_root()).unwrap(),
+            Default::default(),
+            vec![],
+            false,
+        );
+        config
+            .update(serde_json::json!({
+                "diagnostics": {
+                    "disabledIn": ["**/generated/**", "migrations/", "*.gen.rs", "/tests/ui"]
+                }
+            }))
+            .unwrap();
+        let path = |it: &str| AbsPathBuf::try_from(project_root().join(it)).unwrap();
+        assert!(config.diagnostics_disabled_in(&path("crates/foo/src/generated/bar.rs")));
+        assert!(config.diagnostics_disabled_in(&path("crates/foo/migrations/m1.rs")));
+        assert!(config.diagnostics_disabled_in(&path("crates/foo/src/parser.gen.rs")));
+        assert!(config.diagnostics_disabled_in(&path("tests/ui/main.rs")));
+        assert!(!config.diagnostics_disabled_in(&path("crates/foo/tests/ui/main.rs")));
+        assert!(!config.diagnostics_disabled_in(&path("crates/foo/migrations.rs")));
+        assert!(!config.diagnostics_disabled_in(&path("crates/foo/src/lib.rs")));
+    }
 }
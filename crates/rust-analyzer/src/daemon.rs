@@ -0,0 +1,246 @@
+//! Serving several clients at once from a single main loop, for `rust-analyzer --listen`.
+//!
+//! All clients share the analysis and the overlay of open documents. Their messages are merged
+//! into one inbox for the main loop, with request ids made unique by prefixing them with the id
+//! of the client, and the messages of the main loop are routed back: responses go to the client
+//! which sent the request, diagnostics go to every client, and everything else, like progress
+//! reports and requests of the server, goes to the primary client, whose configuration the
+//! server uses. When the primary client goes away, the oldest remaining client takes over.
+use std::collections::BTreeMap;
+
+use crossbeam_channel::{unbounded, Receiver, Sender};
+use lsp_server::{Connection, Message, Notification, RequestId, Response};
+use lsp_types::{
+    notification::{
+        Cancel, DidCloseTextDocument, DidOpenTextDocument, Exit, Notification as _,
+        PublishDiagnostics,
+    },
+    request::{Request as _, Shutdown},
+};
+use parking_lot::Mutex;
+use rustc_hash::{FxHashMap, FxHashSet};
+use serde_json::json;
+use stdx::thread::ThreadIntent;
+use triomphe::Arc;
+
+use crate::config::Config;
+
+pub(crate) type ClientId = u32;
+
+/// Merges the clients received from `clients` into the returned connection for the main loop.
+///
+/// The configuration of each client which becomes the primary one is sent to the returned
+/// receiver.
+pub(crate) fn multiplex(clients: Receiver<(Config, Connection)>) -> (Connection, Receiver<Config>) {
+    let (inbox, receiver) = unbounded();
+    let (sender, outbox) = unbounded();
+    let (attach, attached) = unbounded();
+    let state = Arc::new(Mutex::new(State::default()));
+
+    let router_state = state.clone();
+    spawn("DaemonRouter".to_owned(), move || {
+        for message in outbox {
+            route(&router_state, message);
+        }
+    });
+
+    let mux = Arc::new(Mux { inbox, attach, state });
+    spawn("DaemonClients".to_owned(), move || {
+        for (config, connection) in clients {
+            let id = mux.add(config, connection.sender);
+            let mux = mux.clone();
+            spawn(format!("DaemonClient{id}"), move || mux.serve(id, connection.receiver));
+        }
+    });
+
+    (Connection { sender, receiver }, attached)
+}
+
+/// The client which sent the request the main loop knows by `id`, if it came through
+/// [`multiplex`].
+pub(crate) fn client_of(id: &RequestId) -> Option<ClientId> {
+    let id = serde_json::to_value(id).ok()?;
+    let (client, _) = id.as_str()?.split_once(':')?;
+    client.parse().ok()
+}
+
+fn muxed_id(client: ClientId, id: &RequestId) -> RequestId {
+    format!("{client}:{id}").into()
+}
+
+fn spawn(name: String, f: impl FnOnce() + Send + 'static) {
+    stdx::thread::Builder::new(ThreadIntent::Worker)
+        .name(name)
+        .allow_leak(true)
+        .spawn(f)
+        .expect("failed to spawn a thread");
+}
+
+struct Client {
+    sender: Sender<Message>,
+    /// The configuration the server switches to when the client becomes the primary one.
+    config: Config,
+}
+
+#[derive(Default)]
+struct State {
+    next_id: ClientId,
+    clients: BTreeMap<ClientId, Client>,
+    primary: Option<ClientId>,
+    /// The requests in flight, by the id the main loop knows them by.
+    requests: FxHashMap<RequestId, (ClientId, RequestId)>,
+    /// The clients which have each document open.
+    open_docs: FxHashMap<String, FxHashSet<ClientId>>,
+}
+
+impl State {
+    fn sender(&self, client: ClientId) -> Option<Sender<Message>> {
+        Some(self.clients.get(&client)?.sender.clone())
+    }
+}
+
+struct Mux {
+    inbox: Sender<Message>,
+    attach: Sender<Config>,
+    state: Arc<Mutex<State>>,
+}
+
+impl Mux {
+    fn add(&self, config: Config, sender: Sender<Message>) -> ClientId {
+        let mut state = self.state.lock();
+        let id = state.next_id;
+        state.next_id += 1;
+        if state.primary.is_none() {
+            state.primary = Some(id);
+            let _ = self.attach.send(config.clone());
+        }
+        state.clients.insert(id, Client { sender, config });
+        tracing::info!("client {id} attached");
+        id
+    }
+
+    /// Passes the messages of client `id` on to the main loop, until the client goes away.
+    fn serve(&self, id: ClientId, receiver: Receiver<Message>) {
+        for message in receiver {
+            let message = match message {
+                Message::Request(req) if req.method == Shutdown::METHOD => {
+                    // The other clients still need the server.
+                    let sender = self.state.lock().sender(id);
+                    if let Some(sender) = sender {
+                        let _ = sender.send(Response::new_ok(req.id, ()).into());
+                    }
+                    continue;
+                }
+                Message::Request(mut req) => {
+                    let muxed = muxed_id(id, &req.id);
+                    self.state.lock().requests.insert(muxed.clone(), (id, req.id));
+                    req.id = muxed;
+                    req.into()
+                }
+                Message::Response(resp) => {
+                    // The server only sends requests to the primary client.
+                    if self.state.lock().primary != Some(id) {
+                        continue;
+                    }
+                    resp.into()
+                }
+                Message::Notification(not) if not.method == Exit::METHOD => break,
+                Message::Notification(mut not) => {
+                    let uri = not.params.pointer("/textDocument/uri").and_then(|it| it.as_str());
+                    let uri = uri.map(ToOwned::to_owned);
+                    if not.method == DidOpenTextDocument::METHOD {
+                        if let Some(uri) = uri {
+                            self.state.lock().open_docs.entry(uri).or_default().insert(id);
+                        }
+                    } else if not.method == DidCloseTextDocument::METHOD {
+                        if let Some(uri) = &uri {
+                            let mut state = self.state.lock();
+                            let clients = state.open_docs.entry(uri.clone()).or_default();
+                            clients.remove(&id);
+                            // The document stays open for the other clients.
+                            if !clients.is_empty() {
+                                continue;
+                            }
+                            state.open_docs.remove(uri);
+                        }
+                    } else if not.method == Cancel::METHOD {
+                        let Ok(req_id) =
+                            serde_json::from_value::<RequestId>(not.params["id"].clone())
+                        else {
+                            continue;
+                        };
+                        not.params = json!({ "id": muxed_id(id, &req_id) });
+                    }
+                    not.into()
+                }
+            };
+            if self.inbox.send(message).is_err() {
+                break;
+            }
+        }
+        self.detach(id);
+    }
+
+    /// Cancels the requests of client `id` and closes the documents only it had open.
+    fn detach(&self, id: ClientId) {
+        let mut state = self.state.lock();
+        state.clients.remove(&id);
+
+        let mut messages = Vec::new();
+        state.requests.retain(|muxed, (client, _)| {
+            if *client != id {
+                return true;
+            }
+            messages.push(Notification::new(Cancel::METHOD.to_owned(), json!({ "id": muxed })));
+            false
+        });
+        state.open_docs.retain(|uri, clients| {
+            if !clients.remove(&id) || !clients.is_empty() {
+                return true;
+            }
+            messages.push(Notification::new(
+                DidCloseTextDocument::METHOD.to_owned(),
+                json!({ "textDocument": { "uri": uri } }),
+            ));
+            false
+        });
+        for message in messages {
+            let _ = self.inbox.send(message.into());
+        }
+
+        if state.primary == Some(id) {
+            state.primary = state.clients.first_key_value().map(|(&id, client)| {
+                let _ = self.attach.send(client.config.clone());
+                id
+            });
+        }
+        tracing::info!("client {id} detached");
+    }
+}
+
+/// Sends `message` of the main loop to the clients it concerns.
+fn route(state: &Mutex<State>, message: Message) {
+    // Writing to a client may block, so the state isn't locked meanwhile.
+    let (message, senders) = {
+        let mut state = state.lock();
+        match message {
+            Message::Response(mut resp) => {
+                // The request was cancelled when its client went away.
+                let Some((client, id)) = state.requests.remove(&resp.id) else { return };
+                resp.id = id;
+                (resp.into(), state.sender(client).into_iter().collect())
+            }
+            Message::Notification(not) if not.method == PublishDiagnostics::METHOD => {
+                (not.into(), state.clients.values().map(|it| it.sender.clone()).collect())
+            }
+            message => {
+                let senders: Vec<_> =
+                    state.primary.and_then(|it| state.sender(it)).into_iter().collect();
+                (message, senders)
+            }
+        }
+    };
+    for sender in senders {
+        let _ = sender.send(message.clone());
+    }
+}
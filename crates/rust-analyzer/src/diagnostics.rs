@@ -12,7 +12,10 @@ use triomphe::Arc;
 
 use crate::{global_state::GlobalStateSnapshot, lsp, lsp_ext};
 
-pub(crate) type CheckFixes = Arc<IntMap<usize, IntMap<FileId, Vec<Fix>>>>;
+/// The diagnostics of each flycheck, by the package they were reported for.
+pub(crate) type PerPackage<T> = FxHashMap<Option<String>, IntMap<FileId, Vec<T>>>;
+
+pub(crate) type CheckFixes = Arc<IntMap<usize, PerPackage<Fix>>>;
 
 #[derive(Debug, Default, Clone)]
 pub struct DiagnosticsMapConfig {
@@ -27,11 +30,23 @@ pub(crate) struct DiagnosticCollection {
     // FIXME: should be IntMap<FileId, Vec<ra_id::Diagnostic>>
     pub(crate) native: IntMap<FileId, Vec<lsp_types::Diagnostic>>,
     // FIXME: should be Vec<flycheck::Diagnostic>
-    pub(crate) check: IntMap<usize, IntMap<FileId, Vec<lsp_types::Diagnostic>>>,
+    pub(crate) check: IntMap<usize, PerPackage<lsp_types::Diagnostic>>,
     pub(crate) check_fixes: CheckFixes,
+    /// The running check of each flycheck. The previous diagnostics of a package are kept until
+    /// its new ones arrive, or the check finishes, so that they don't disappear for the whole
+    /// duration of the check.
+    running_checks: IntMap<usize, CheckRun>,
     changes: IntSet<FileId>,
 }
 
+#[derive(Debug, Default, Clone)]
+struct CheckRun {
+    /// The package being checked, or `None` for the whole workspace.
+    package: Option<String>,
+    /// The packages that reported diagnostics so far.
+    reported: FxHashSet<Option<String>>,
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct Fix {
     // Fixes may be triggerable from multiple ranges.
@@ -40,19 +55,45 @@ pub(crate) struct Fix {
 }
 
 impl DiagnosticCollection {
-    pub(crate) fn clear_check(&mut self, flycheck_id: usize) {
+    /// Records the start of a check of `package`, or of the whole workspace if `None`.
+    pub(crate) fn start_check(&mut self, flycheck_id: usize, package: Option<String>) {
+        self.running_checks.insert(flycheck_id, CheckRun { package, reported: Default::default() });
+    }
+
+    /// Clears the diagnostics of the checked packages the finished check didn't report for.
+    pub(crate) fn finish_check(&mut self, flycheck_id: usize) {
+        let Some(run) = self.running_checks.remove(&flycheck_id) else { return };
+        let outdated: Vec<_> = match run.package {
+            Some(package) => vec![Some(package)],
+            None => {
+                self.check.get(&flycheck_id).into_iter().flat_map(|it| it.keys()).cloned().collect()
+            }
+        };
+        for package in outdated.iter().filter(|it| !run.reported.contains(*it)) {
+            self.clear_check_package(flycheck_id, package);
+        }
+    }
+
+    /// Keeps the previous diagnostics of the packages a cancelled check didn't report for.
+    pub(crate) fn cancel_check(&mut self, flycheck_id: usize) {
+        self.running_checks.remove(&flycheck_id);
+    }
+
+    fn clear_check_package(&mut self, flycheck_id: usize, package: &Option<String>) {
         if let Some(it) = Arc::make_mut(&mut self.check_fixes).get_mut(&flycheck_id) {
-            it.clear();
+            it.remove(package);
         }
-        if let Some(it) = self.check.get_mut(&flycheck_id) {
-            self.changes.extend(it.drain().map(|(key, _value)| key));
+        if let Some(it) = self.check.get_mut(&flycheck_id).and_then(|it| it.remove(package)) {
+            self.changes.extend(it.into_keys());
         }
     }
 
     pub(crate) fn clear_check_all(&mut self) {
         Arc::make_mut(&mut self.check_fixes).clear();
-        self.changes
-            .extend(self.check.values_mut().flat_map(|it| it.drain().map(|(key, _value)| key)))
+        self.running_checks.clear();
+        self.changes.extend(
+            self.check.values_mut().flat_map(|it| it.drain()).flat_map(|(_, it)| it.into_keys()),
+        )
     }
 
     pub(crate) fn clear_native_for(&mut self, file_id: FileId) {
@@ -63,11 +104,26 @@ impl DiagnosticCollection {
     pub(crate) fn add_check_diagnostic(
         &mut self,
         flycheck_id: usize,
+        package: Option<String>,
         file_id: FileId,
         diagnostic: lsp_types::Diagnostic,
         fix: Option<Fix>,
     ) {
-        let diagnostics = self.check.entry(flycheck_id).or_default().entry(file_id).or_default();
+        let first_of_package = self
+            .running_checks
+            .get_mut(&flycheck_id)
+            .map_or(false, |run| run.reported.insert(package.clone()));
+        if first_of_package {
+            self.clear_check_package(flycheck_id, &package);
+        }
+        let diagnostics = self
+            .check
+            .entry(flycheck_id)
+            .or_default()
+            .entry(package.clone())
+            .or_default()
+            .entry(file_id)
+            .or_default();
         for existing_diagnostic in diagnostics.iter() {
             if are_diagnostics_equal(existing_diagnostic, &diagnostic) {
                 return;
@@ -75,7 +131,14 @@ impl DiagnosticCollection {
         }
 
         let check_fixes = Arc::make_mut(&mut self.check_fixes);
-        check_fixes.entry(flycheck_id).or_default().entry(file_id).or_default().extend(fix);
+        check_fixes
+            .entry(flycheck_id)
+            .or_default()
+            .entry(package)
+            .or_default()
+            .entry(file_id)
+            .or_default()
+            .extend(fix);
         diagnostics.push(diagnostic);
         self.changes.insert(file_id);
     }
@@ -105,7 +168,12 @@ impl DiagnosticCollection {
         file_id: FileId,
     ) -> impl Iterator<Item = &lsp_types::Diagnostic> {
         let native = self.native.get(&file_id).into_iter().flatten();
-        let check = self.check.values().filter_map(move |it| it.get(&file_id)).flatten();
+        let check = self
+            .check
+            .values()
+            .flat_map(|it| it.values())
+            .filter_map(move |it| it.get(&file_id))
+            .flatten();
         native.chain(check)
     }
 
@@ -131,21 +199,6 @@ pub(crate) fn fetch_native_diagnostics(
     let _p = tracing::span!(tracing::Level::INFO, "fetch_native_diagnostics").entered();
     let _ctx = stdx::panic_context::enter("fetch_native_diagnostics".to_owned());
 
-    let convert_diagnostic =
-        |line_index: &crate::line_index::LineIndex, d: ide::Diagnostic| lsp_types::Diagnostic {
-            range: lsp::to_proto::range(line_index, d.range.range),
-            severity: Some(lsp::to_proto::diagnostic_severity(d.severity)),
-            code: Some(lsp_types::NumberOrString::String(d.code.as_str().to_owned())),
-            code_description: Some(lsp_types::CodeDescription {
-                href: lsp_types::Url::parse(&d.code.url()).unwrap(),
-            }),
-            source: Some("rust-analyzer".to_owned()),
-            message: d.message,
-            related_information: None,
-            tags: d.unused.then(|| vec![lsp_types::DiagnosticTag::UNNECESSARY]),
-            data: None,
-        };
-
     // the diagnostics produced may point to different files not requested by the concrete request,
     // put those into here and filter later
     let mut odd_ones = Vec::new();
@@ -199,3 +252,22 @@ pub(crate) fn fetch_native_diagnostics(
     }
     diagnostics
 }
+
+pub(crate) fn convert_diagnostic(
+    line_index: &crate::line_index::LineIndex,
+    d: ide::Diagnostic,
+) -> lsp_types::Diagnostic {
+    lsp_types::Diagnostic {
+        range: lsp::to_proto::range(line_index, d.range.range),
+        severity: Some(lsp::to_proto::diagnostic_severity(d.severity)),
+        code: Some(lsp_types::NumberOrString::String(d.code.as_str().to_owned())),
+        code_description: Some(lsp_types::CodeDescription {
+            href: lsp_types::Url::parse(&d.code.url()).unwrap(),
+        }),
+        source: Some("rust-analyzer".to_owned()),
+        message: d.message,
+        related_information: None,
+        tags: d.unused.then(|| vec![lsp_types::DiagnosticTag::UNNECESSARY]),
+        data: d.ssr_rule.map(|rule| serde_json::json!({ "ssrRule": rule })),
+    }
+}
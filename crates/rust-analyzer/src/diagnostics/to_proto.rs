@@ -189,6 +189,7 @@ fn map_rust_child_diagnostic(
     let mut edit_map: FxHashMap<lsp_types::Url, Vec<lsp_types::TextEdit>> = FxHashMap::default();
     let mut suggested_replacements = Vec::new();
     let mut is_preferred = true;
+    let mut is_applicable = true;
     for &span in &spans {
         if let Some(suggested_replacement) = &span.suggested_replacement {
             if !suggested_replacement.is_empty() {
@@ -201,16 +202,20 @@ fn map_rust_child_diagnostic(
             // We accept both "MaybeIncorrect" and "MachineApplicable". "MaybeIncorrect" means that
             // the suggestion is *complete* (contains no placeholders where code needs to be
             // inserted), but might not be what the user wants, or might need minor adjustments.
-            if matches!(
+            // The spans of a multi-span suggestion only make sense together, so either all of them
+            // are applied or none.
+            is_applicable &= matches!(
                 span.suggestion_applicability,
                 None | Some(Applicability::MaybeIncorrect | Applicability::MachineApplicable)
-            ) {
-                edit_map.entry(location.uri).or_default().push(edit);
-            }
+            );
+            edit_map.entry(location.uri).or_default().push(edit);
             is_preferred &=
                 matches!(span.suggestion_applicability, Some(Applicability::MachineApplicable));
         }
     }
+    if !is_applicable {
+        edit_map.clear();
+    }
 
     // rustc renders suggestion diagnostics by appending the suggested replacement, so do the same
     // here, otherwise the diagnostic text is missing useful information.
@@ -1,16 +1,20 @@
 //! See [RequestDispatcher].
 use std::{
     fmt::{self, Debug},
-    panic, thread,
+    panic,
+    sync::atomic::Ordering,
+    thread,
+    time::Duration,
 };
 
+use crossbeam_channel::RecvTimeoutError;
 use ide::Cancelled;
 use lsp_server::ExtractError;
 use serde::{de::DeserializeOwned, Serialize};
 use stdx::thread::ThreadIntent;
 
 use crate::{
-    global_state::{GlobalState, GlobalStateSnapshot},
+    global_state::{CancellationToken, GlobalState, GlobalStateSnapshot, Superseded},
     lsp::LspError,
     main_loop::Task,
     version::version,
@@ -132,6 +136,40 @@ impl RequestDispatcher<'_> {
         self
     }
 
+    /// Dispatches a long running request onto the thread pool, handing it a token that is set
+    /// once the client cancels the request.
+    pub(crate) fn on_cancellable<R>(
+        &mut self,
+        f: fn(GlobalStateSnapshot, R::Params, CancellationToken) -> anyhow::Result<R::Result>,
+    ) -> &mut Self
+    where
+        R: lsp_types::request::Request + 'static,
+        R::Params: DeserializeOwned + panic::UnwindSafe + Send + fmt::Debug,
+        R::Result: Serialize,
+    {
+        let (req, params, panic_context) = match self.parse::<R>() {
+            Some(it) => it,
+            None => return self,
+        };
+        let _guard = tracing::span!(tracing::Level::INFO, "request", method = ?req.method, "request_id" = ?req.id).entered();
+        tracing::debug!(?params);
+
+        let world = self.global_state.snapshot();
+        let token = self.global_state.cancellation_token(req.id.clone());
+        self.global_state.task_pool.handle.spawn(ThreadIntent::Worker, move || {
+            let result = panic::catch_unwind(move || {
+                let _pctx = stdx::panic_context::enter(panic_context);
+                f(world, params, token)
+            });
+            match thread_result_to_response::<R>(req.id.clone(), result) {
+                Ok(response) => Task::Response(response),
+                Err(_) => Task::Retry(req),
+            }
+        });
+
+        self
+    }
+
     /// Dispatches a non-latency-sensitive request onto the thread pool.
     pub(crate) fn on<R>(
         &mut self,
@@ -158,6 +196,62 @@ impl RequestDispatcher<'_> {
         self.on_with_thread_intent::<true, R>(ThreadIntent::LatencySensitive, f)
     }
 
+    /// Dispatches a request onto the thread pool. If `f` is still busy once `budget` has passed,
+    /// the client gets the result of the cheaper `partial` instead, and the result of `f` is
+    /// dropped.
+    pub(crate) fn on_with_budget<R>(
+        &mut self,
+        intent: ThreadIntent,
+        budget: Option<Duration>,
+        f: fn(GlobalStateSnapshot, R::Params) -> anyhow::Result<R::Result>,
+        partial: fn(GlobalStateSnapshot, R::Params) -> anyhow::Result<R::Result>,
+    ) -> &mut Self
+    where
+        R: lsp_types::request::Request + 'static,
+        R::Params: DeserializeOwned + panic::UnwindSafe + Clone + Send + fmt::Debug,
+        R::Result: Serialize,
+    {
+        let Some(budget) = budget else {
+            return self.on_with_thread_intent::<true, R>(intent, f);
+        };
+        let (req, params, panic_context) = match self.parse::<R>() {
+            Some(it) => it,
+            None => return self,
+        };
+        let _guard = tracing::span!(tracing::Level::INFO, "request", method = ?req.method, "request_id" = ?req.id).entered();
+        tracing::debug!(?params);
+
+        let world = self.global_state.snapshot();
+        let partial_world = self.global_state.snapshot();
+        let superseded = self.global_state.superseded(&req);
+        let (done, finished) = crossbeam_channel::bounded::<()>(0);
+
+        let task = {
+            let (req, params, panic_context) = (req.clone(), params.clone(), panic_context.clone());
+            move || run_request::<R>(req, world, params, panic_context, superseded, f)
+        };
+        self.global_state.task_pool.handle.spawn_with_sender(intent, move |sender| {
+            sender.send(task()).unwrap();
+            // Only now, so that the partial result can't overtake the full one.
+            drop(done);
+        });
+        self.global_state.task_pool.handle.spawn_with_sender(intent, move |sender| {
+            if !matches!(finished.recv_timeout(budget), Err(RecvTimeoutError::Timeout)) {
+                return;
+            }
+            let result = panic::catch_unwind(move || {
+                let _pctx = stdx::panic_context::enter(panic_context);
+                partial(partial_world, params)
+            });
+            // Whichever response comes first is sent to the client, the other one is dropped.
+            if let Ok(response) = thread_result_to_response::<R>(req.id, result) {
+                sender.send(Task::Response(response)).unwrap();
+            }
+        });
+
+        self
+    }
+
     /// Formatting requests should never block on waiting a for task thread to open up, editors will wait
     /// on the response and a late formatting update might mess with the document and user.
     /// We can't run this on the main thread though as we invoke rustfmt which may take arbitrary time to complete!
@@ -203,21 +297,13 @@ impl RequestDispatcher<'_> {
         tracing::debug!(?params);
 
         let world = self.global_state.snapshot();
+        let superseded = self.global_state.superseded(&req);
         if MAIN_POOL {
             &mut self.global_state.task_pool.handle
         } else {
             &mut self.global_state.fmt_pool.handle
         }
-        .spawn(intent, move || {
-            let result = panic::catch_unwind(move || {
-                let _pctx = stdx::panic_context::enter(panic_context);
-                f(world, params)
-            });
-            match thread_result_to_response::<R>(req.id.clone(), result) {
-                Ok(response) => Task::Response(response),
-                Err(_) => Task::Retry(req),
-            }
-        });
+        .spawn(intent, move || run_request::<R>(req, world, params, panic_context, superseded, f));
 
         self
     }
@@ -252,6 +338,38 @@ impl RequestDispatcher<'_> {
     }
 }
 
+/// Runs the handler `f` of `req` on a worker thread, unless the request was superseded while it
+/// was queued, in which case the client already got its cancellation.
+fn run_request<R>(
+    req: lsp_server::Request,
+    world: GlobalStateSnapshot,
+    params: R::Params,
+    panic_context: String,
+    superseded: Option<Superseded>,
+    f: fn(GlobalStateSnapshot, R::Params) -> anyhow::Result<R::Result>,
+) -> Task
+where
+    R: lsp_types::request::Request,
+    R::Params: DeserializeOwned + panic::UnwindSafe,
+    R::Result: Serialize,
+{
+    if superseded.map_or(false, |it| it.load(Ordering::Relaxed)) {
+        return Task::Response(lsp_server::Response::new_err(
+            req.id,
+            lsp_server::ErrorCode::RequestCanceled as i32,
+            "superseded by a newer request".to_owned(),
+        ));
+    }
+    let result = panic::catch_unwind(move || {
+        let _pctx = stdx::panic_context::enter(panic_context);
+        f(world, params)
+    });
+    match thread_result_to_response::<R>(req.id.clone(), result) {
+        Ok(response) => Task::Response(response),
+        Err(_) => Task::Retry(req),
+    }
+}
+
 fn thread_result_to_response<R>(
     id: lsp_server::RequestId,
     result: thread::Result<anyhow::Result<R::Result>>,
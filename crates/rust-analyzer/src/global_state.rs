@@ -3,13 +3,20 @@
 //!
 //! Each tick provides an immutable snapshot of the state as `WorldSnapshot`.
 
-use std::{collections::hash_map::Entry, time::Instant};
+use std::{
+    collections::hash_map::Entry,
+    sync::atomic::{AtomicBool, Ordering},
+    time::Instant,
+};
 
 use crossbeam_channel::{unbounded, Receiver, Sender};
 use flycheck::FlycheckHandle;
 use hir::Change;
 use ide::{Analysis, AnalysisHost, Cancellable, FileId};
-use ide_db::base_db::{CrateId, ProcMacroPaths};
+use ide_db::{
+    base_db::{salsa::Durability, CrateId, ProcMacroPaths},
+    symbol_index::SymbolsDatabase,
+};
 use load_cargo::SourceRootConfig;
 use lsp_types::{SemanticTokens, Url};
 use nohash_hasher::IntMap;
@@ -18,13 +25,14 @@ use parking_lot::{
     RwLockWriteGuard,
 };
 use proc_macro_api::ProcMacroServer;
-use project_model::{CargoWorkspace, ProjectWorkspace, Target, WorkspaceBuildScripts};
+use project_model::{CargoWorkspace, CratesIndex, ProjectWorkspace, Target, WorkspaceBuildScripts};
 use rustc_hash::{FxHashMap, FxHashSet};
 use triomphe::Arc;
 use vfs::{AnchoredPathBuf, ChangedFile, Vfs};
 
 use crate::{
     config::{Config, ConfigError},
+    daemon::{self, ClientId},
     diagnostics::{CheckFixes, DiagnosticCollection},
     line_index::{LineEndings, LineIndex},
     lsp::{from_proto, to_proto::url_from_abs_path},
@@ -33,6 +41,7 @@ use crate::{
     mem_docs::MemDocs,
     op_queue::OpQueue,
     reload,
+    symbol_cache::CachedSymbol,
     task_pool::{TaskPool, TaskQueue},
 };
 
@@ -56,6 +65,10 @@ type ReqQueue = lsp_server::ReqQueue<(String, Instant), ReqHandler>;
 pub(crate) struct GlobalState {
     sender: Sender<lsp_server::Message>,
     req_queue: ReqQueue,
+    /// The latest request of each of the [`SUPERSEDABLE_REQUESTS`], per client of a daemon.
+    latest_requests: FxHashMap<(String, Option<ClientId>), (lsp_server::RequestId, Superseded)>,
+    /// The tokens of the in-flight requests dispatched with `on_cancellable`.
+    request_cancellations: FxHashMap<lsp_server::RequestId, CancellationToken>,
 
     pub(crate) task_pool: Handle<TaskPool<Task>, Receiver<Task>>,
     pub(crate) fmt_pool: Handle<TaskPool<Task>, Receiver<Task>>,
@@ -67,10 +80,14 @@ pub(crate) struct GlobalState {
     pub(crate) mem_docs: MemDocs,
     pub(crate) source_root_config: SourceRootConfig,
     pub(crate) semantic_tokens_cache: Arc<Mutex<FxHashMap<Url, SemanticTokens>>>,
+    /// The local crates.io index cache, used by the `Cargo.toml` requests.
+    pub(crate) crates_index: Arc<Option<CratesIndex>>,
 
     // status
     pub(crate) shutdown_requested: bool,
     pub(crate) send_hint_refresh_query: bool,
+    /// Whether `textDocument/diagnostic` is currently registered with the client.
+    pub(crate) pull_diagnostics_registered: bool,
     pub(crate) last_reported_status: Option<lsp_ext::ServerStatusParams>,
 
     // proc macros
@@ -118,6 +135,16 @@ pub(crate) struct GlobalState {
     /// to invalidate any salsa caches.
     pub(crate) workspaces: Arc<Vec<ProjectWorkspace>>,
     pub(crate) crate_graph_file_dependencies: FxHashSet<vfs::VfsPath>,
+    /// Rust files opened outside of all workspaces, which are analyzed as detached files.
+    pub(crate) detached_files: FxHashSet<vfs::AbsPathBuf>,
+    /// Files whose contents are not analyzed, along with the reason why.
+    pub(crate) unindexed_files: FxHashMap<FileId, UnindexedReason>,
+    /// Files that grew larger than `files.generatedMinSize`, they are treated as generated code
+    /// until they are deleted. Their bodies are only lowered eagerly while they are open.
+    pub(crate) generated_files: Arc<FxHashSet<FileId>>,
+    /// The symbols of the dependencies persisted by an earlier session, searched until the
+    /// dependencies are indexed.
+    pub(crate) library_symbols_cache: Option<Arc<Vec<CachedSymbol>>>,
 
     // op queues
     pub(crate) fetch_workspaces_queue:
@@ -126,6 +153,7 @@ pub(crate) struct GlobalState {
         OpQueue<(), (Arc<Vec<ProjectWorkspace>>, Vec<anyhow::Result<WorkspaceBuildScripts>>)>,
     pub(crate) fetch_proc_macros_queue: OpQueue<Vec<ProcMacroPaths>, bool>,
     pub(crate) prime_caches_queue: OpQueue,
+    pub(crate) library_symbols_queue: OpQueue,
 
     /// A deferred task queue.
     ///
@@ -139,6 +167,31 @@ pub(crate) struct GlobalState {
     pub(crate) deferred_task_queue: TaskQueue,
 }
 
+/// Set once the request it was handed to is cancelled, for handlers whose work salsa
+/// cancellation can't interrupt, like a cargo invocation.
+pub(crate) type CancellationToken = Arc<AtomicBool>;
+
+/// Set once a request of the [`SUPERSEDABLE_REQUESTS`] is superseded by a newer one, so that a
+/// worker which didn't start it yet can skip it.
+pub(crate) type Superseded = Arc<AtomicBool>;
+
+/// Requests whose results are made obsolete by the next request of the same kind, as they're
+/// triggered by moving the cursor or typing.
+const SUPERSEDABLE_REQUESTS: &[&str] = &[
+    <lsp_types::request::Completion as lsp_types::request::Request>::METHOD,
+    <lsp_types::request::SignatureHelpRequest as lsp_types::request::Request>::METHOD,
+    <lsp_types::request::DocumentHighlightRequest as lsp_types::request::Request>::METHOD,
+    <lsp_ext::HoverRequest as lsp_types::request::Request>::METHOD,
+];
+
+/// Why the contents of a file are not passed on to the analysis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum UnindexedReason {
+    NotUtf8,
+    /// The file is larger than `files.maxSize`.
+    TooLarge,
+}
+
 /// An immutable snapshot of the world's state at a point in time.
 pub(crate) struct GlobalStateSnapshot {
     pub(crate) config: Arc<Config>,
@@ -146,11 +199,14 @@ pub(crate) struct GlobalStateSnapshot {
     pub(crate) check_fixes: CheckFixes,
     mem_docs: MemDocs,
     pub(crate) semantic_tokens_cache: Arc<Mutex<FxHashMap<Url, SemanticTokens>>>,
+    pub(crate) crates_index: Arc<Option<CratesIndex>>,
     vfs: Arc<RwLock<(vfs::Vfs, IntMap<FileId, LineEndings>)>>,
     pub(crate) workspaces: Arc<Vec<ProjectWorkspace>>,
     // used to signal semantic highlighting to fall back to syntax based highlighting until proc-macros have been loaded
     pub(crate) proc_macros_loaded: bool,
     pub(crate) flycheck: Arc<[FlycheckHandle]>,
+    pub(crate) generated_files: Arc<FxHashSet<FileId>>,
+    pub(crate) library_symbols_cache: Option<Arc<Vec<CachedSymbol>>>,
 }
 
 impl std::panic::UnwindSafe for GlobalStateSnapshot {}
@@ -189,6 +245,8 @@ impl GlobalState {
         let mut this = GlobalState {
             sender,
             req_queue: ReqQueue::default(),
+            latest_requests: FxHashMap::default(),
+            request_cancellations: FxHashMap::default(),
             task_pool,
             fmt_pool,
             loader,
@@ -197,8 +255,10 @@ impl GlobalState {
             diagnostics: Default::default(),
             mem_docs: MemDocs::default(),
             semantic_tokens_cache: Arc::new(Default::default()),
+            crates_index: Arc::new(None),
             shutdown_requested: false,
             send_hint_refresh_query: false,
+            pull_diagnostics_registered: false,
             last_reported_status: None,
             source_root_config: SourceRootConfig::default(),
             config_errors: Default::default(),
@@ -220,11 +280,16 @@ impl GlobalState {
 
             workspaces: Arc::from(Vec::new()),
             crate_graph_file_dependencies: FxHashSet::default(),
+            detached_files: FxHashSet::default(),
+            unindexed_files: FxHashMap::default(),
+            generated_files: Arc::default(),
+            library_symbols_cache: None,
             fetch_workspaces_queue: OpQueue::default(),
             fetch_build_data_queue: OpQueue::default(),
             fetch_proc_macros_queue: OpQueue::default(),
 
             prime_caches_queue: OpQueue::default(),
+            library_symbols_queue: OpQueue::default(),
 
             deferred_task_queue: task_queue,
         };
@@ -296,18 +361,29 @@ impl GlobalState {
             let mut has_structure_changes = false;
             let mut bytes = vec![];
             let mut modified_rust_files = vec![];
+            let files_config = self.config.files();
             for file in changed_files {
                 let vfs_path = &vfs.file_path(file.file_id);
                 if let Some(path) = vfs_path.as_path() {
                     let path = path.to_path_buf();
-                    if reload::should_refresh_for_change(&path, file.kind()) {
+                    let is_rust_file = path.extension() == Some("rs".as_ref());
+                    // Open manifests are synced for the `Cargo.toml` requests, their edits only
+                    // reload the workspace once they are saved.
+                    let is_open_manifest = !is_rust_file && self.mem_docs.contains(vfs_path);
+                    if !is_open_manifest && reload::should_refresh_for_change(&path, file.kind()) {
                         workspace_structure_change = Some((path.clone(), false));
                     }
                     if file.is_created_or_deleted() {
                         has_structure_changes = true;
-                        workspace_structure_change =
-                            Some((path, self.crate_graph_file_dependencies.contains(vfs_path)));
-                    } else if path.extension() == Some("rs".as_ref()) {
+                        if matches!(file.change, vfs::Change::Delete) {
+                            let url = file_id_to_url(vfs, file.file_id);
+                            self.semantic_tokens_cache.lock().remove(&url);
+                        }
+                        if !is_open_manifest {
+                            workspace_structure_change =
+                                Some((path, self.crate_graph_file_dependencies.contains(vfs_path)));
+                        }
+                    } else if is_rust_file {
                         modified_rust_files.push(file.file_id);
                     }
                 }
@@ -315,17 +391,35 @@ impl GlobalState {
                 // Clear native diagnostics when their file gets deleted
                 if !file.exists() {
                     self.diagnostics.clear_native_for(file.file_id);
+                    Arc::make_mut(&mut self.generated_files).remove(&file.file_id);
                 }
 
-                let text = if let vfs::Change::Create(v) | vfs::Change::Modify(v) = file.change {
-                    String::from_utf8(v).ok().map(|text| {
-                        // FIXME: Consider doing normalization in the `vfs` instead? That allows
-                        // getting rid of some locking
-                        let (text, line_endings) = LineEndings::normalize(text);
-                        (Arc::from(text), line_endings)
-                    })
-                } else {
-                    None
+                self.unindexed_files.remove(&file.file_id);
+                let text = match file.change {
+                    vfs::Change::Create(v) | vfs::Change::Modify(v)
+                        if v.len() > files_config.max_size =>
+                    {
+                        self.unindexed_files.insert(file.file_id, UnindexedReason::TooLarge);
+                        None
+                    }
+                    vfs::Change::Create(v) | vfs::Change::Modify(v) => match String::from_utf8(v) {
+                        Ok(text) => {
+                            if files_config.generated_min_size.map_or(false, |it| text.len() > it)
+                                && Arc::make_mut(&mut self.generated_files).insert(file.file_id)
+                            {
+                                self.diagnostics.clear_native_for(file.file_id);
+                            }
+                            // FIXME: Consider doing normalization in the `vfs` instead? That allows
+                            // getting rid of some locking
+                            let (text, line_endings) = LineEndings::normalize(text);
+                            Some((Arc::from(text), line_endings))
+                        }
+                        Err(_) => {
+                            self.unindexed_files.insert(file.file_id, UnindexedReason::NotUtf8);
+                            None
+                        }
+                    },
+                    vfs::Change::Delete => None,
                 };
                 // delay `line_endings_map` changes until we are done normalizing the text
                 // this allows delaying the re-acquisition of the write lock
@@ -347,8 +441,15 @@ impl GlobalState {
         };
 
         self.analysis_host.apply_change(change);
+        self.update_lazy_body_files();
 
         {
+            if self.config.test_explorer() && !modified_rust_files.is_empty() {
+                _ = self
+                    .deferred_task_queue
+                    .sender
+                    .send(crate::main_loop::QueuedTask::DiscoverTests(modified_rust_files.clone()));
+            }
             if !matches!(&workspace_structure_change, Some((.., true))) {
                 _ = self
                     .deferred_task_queue
@@ -369,6 +470,22 @@ impl GlobalState {
         true
     }
 
+    fn update_lazy_body_files(&mut self) {
+        let lazy_body_files = {
+            let vfs = &self.vfs.read().0;
+            self.generated_files
+                .iter()
+                .copied()
+                .filter(|&file_id| !self.mem_docs.contains(&vfs.file_path(file_id)))
+                .collect::<FxHashSet<_>>()
+        };
+        if *self.analysis_host.raw_database().lazy_body_files() != lazy_body_files {
+            self.analysis_host
+                .raw_database_mut()
+                .set_lazy_body_files_with_durability(Arc::new(lazy_body_files), Durability::MEDIUM);
+        }
+    }
+
     pub(crate) fn snapshot(&self) -> GlobalStateSnapshot {
         GlobalStateSnapshot {
             config: Arc::clone(&self.config),
@@ -378,9 +495,12 @@ impl GlobalState {
             check_fixes: Arc::clone(&self.diagnostics.check_fixes),
             mem_docs: self.mem_docs.clone(),
             semantic_tokens_cache: Arc::clone(&self.semantic_tokens_cache),
+            crates_index: Arc::clone(&self.crates_index),
             proc_macros_loaded: !self.config.expand_proc_macros()
                 || *self.fetch_proc_macros_queue.last_op_result(),
             flycheck: self.flycheck.clone(),
+            generated_files: Arc::clone(&self.generated_files),
+            library_symbols_cache: self.library_symbols_cache.clone(),
         }
     }
 
@@ -418,9 +538,34 @@ impl GlobalState {
         self.req_queue
             .incoming
             .register(request.id.clone(), (request.method.clone(), request_received));
+
+        if SUPERSEDABLE_REQUESTS.contains(&request.method.as_str()) {
+            let key = (request.method.clone(), daemon::client_of(&request.id));
+            let latest = (request.id.clone(), Superseded::default());
+            // The client has no use for the result of the previous request anymore, so don't keep
+            // it waiting for that.
+            if let Some((previous, superseded)) = self.latest_requests.insert(key, latest) {
+                superseded.store(true, Ordering::Relaxed);
+                self.cancel(previous);
+            }
+        }
+    }
+
+    /// The flag set once `request` is superseded, if it's one of the [`SUPERSEDABLE_REQUESTS`].
+    pub(crate) fn superseded(&self, request: &lsp_server::Request) -> Option<Superseded> {
+        let (_, superseded) = self.latest_requests.values().find(|(id, _)| *id == request.id)?;
+        Some(superseded.clone())
+    }
+
+    pub(crate) fn cancellation_token(
+        &mut self,
+        request_id: lsp_server::RequestId,
+    ) -> CancellationToken {
+        self.request_cancellations.entry(request_id).or_default().clone()
     }
 
     pub(crate) fn respond(&mut self, response: lsp_server::Response) {
+        self.request_cancellations.remove(&response.id);
         if let Some((method, start)) = self.req_queue.incoming.complete(response.id.clone()) {
             if let Some(err) = &response.error {
                 if err.message.starts_with("server panicked") {
@@ -435,6 +580,9 @@ impl GlobalState {
     }
 
     pub(crate) fn cancel(&mut self, request_id: lsp_server::RequestId) {
+        if let Some(token) = self.request_cancellations.remove(&request_id) {
+            token.store(true, Ordering::Relaxed);
+        }
         if let Some(response) = self.req_queue.incoming.cancel(request_id) {
             self.send(response.into());
         }
@@ -71,6 +71,17 @@ pub(crate) fn handle_did_open_text_document(
             tracing::error!("duplicate DidOpenTextDocument: {}", path);
         }
 
+        if let Some(abs_path) = path.as_path() {
+            if state.is_outside_of_workspaces(abs_path)
+                && state.detached_files.insert(abs_path.to_path_buf())
+            {
+                state.fetch_workspaces_queue.request_op(
+                    format!("opened a file outside of the workspaces {abs_path}"),
+                    false,
+                );
+            }
+        }
+
         state.vfs.write().0.set_file_contents(path, Some(params.text_document.text.into_bytes()));
         if state.config.notifications().unindexed_project {
             tracing::debug!("queuing task");
@@ -259,9 +270,23 @@ fn run_flycheck(state: &mut GlobalState, vfs_path: VfsPath) -> bool {
 
     let file_id = state.vfs.read().0.file_id(&vfs_path);
     if let Some(file_id) = file_id {
+        // Path dependencies and `[patch]`ed crates can live inside of the workspace, and still
+        // need to be checked.
+        let in_workspace = vfs_path.as_path().map_or(false, |path| {
+            state
+                .workspaces
+                .iter()
+                .filter_map(|ws| ws.workspace_definition_path())
+                .any(|root| path.starts_with(root))
+        });
         let world = state.snapshot();
         let mut updated = false;
         let task = move || -> std::result::Result<(), ide::Cancelled> {
+            // Saving a file of an external dependency can't change the diagnostics of the
+            // workspace.
+            if !in_workspace && world.analysis.is_library_file(file_id)? {
+                return Ok(());
+            }
             // Trigger flychecks for all workspaces that depend on the saved file
             // Crates containing or depending on the saved file
             let crate_ids: Vec<_> = world
@@ -297,7 +322,10 @@ fn run_flycheck(state: &mut GlobalState, vfs_path: VfsPath) -> bool {
                                 .targets
                                 .iter()
                                 .any(|&it| crate_root_paths.contains(&cargo[it].root.as_path()));
-                            has_target_with_root.then(|| cargo[pkg].name.clone())
+                            has_target_with_root.then(|| flycheck::PackageSpecifier {
+                                name: cargo[pkg].name.clone(),
+                                id: cargo[pkg].id.clone(),
+                            })
                         })
                     }
                     project_model::ProjectWorkspace::Json { project, .. } => {
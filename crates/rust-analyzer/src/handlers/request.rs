@@ -3,19 +3,22 @@
 
 use std::{
     fs,
+    hash::{Hash, Hasher},
     io::Write as _,
     path::PathBuf,
     process::{self, Stdio},
+    sync::atomic::Ordering,
 };
 
 use anyhow::Context;
 
 use ide::{
-    AnnotationConfig, AssistKind, AssistResolveStrategy, Cancellable, FilePosition, FileRange,
-    HoverAction, HoverGotoTypeData, InlayFieldsToResolve, Query, RangeInfo, RangeLimit,
-    ReferenceCategory, Runnable, RunnableKind, SingleResolve, SourceChange, TextEdit,
+    AnnotationConfig, AssistKind, AssistResolveStrategy, Cancellable, CompletionItemKind,
+    FilePosition, FileRange, HoverAction, HoverGotoTypeData, InlayFieldsToResolve, Query,
+    RangeInfo, RangeLimit, ReferenceCategory, Runnable, RunnableKind, SingleResolve, SourceChange,
+    StructureNodeKind, TextEdit,
 };
-use ide_db::SymbolKind;
+use ide_db::{FxHashMap, SymbolKind};
 use itertools::Itertools;
 use lsp_server::ErrorCode;
 use lsp_types::{
@@ -27,7 +30,7 @@ use lsp_types::{
     SemanticTokensParams, SemanticTokensRangeParams, SemanticTokensRangeResult,
     SemanticTokensResult, SymbolInformation, SymbolTag, TextDocumentIdentifier, Url, WorkspaceEdit,
 };
-use project_model::{ManifestPath, ProjectWorkspace, TargetKind};
+use project_model::{CratesIndex, ManifestPath, ProjectWorkspace, TargetKind};
 use serde_json::json;
 use stdx::{format_to, never};
 use syntax::{algo, ast, AstNode, TextRange, TextSize};
@@ -36,10 +39,11 @@ use vfs::{AbsPath, AbsPathBuf, FileId, VfsPath};
 
 use crate::{
     cargo_target_spec::CargoTargetSpec,
+    cargo_toml,
     config::{Config, RustfmtConfig, WorkspaceSymbolConfig},
     diff::diff,
-    global_state::{GlobalState, GlobalStateSnapshot},
-    line_index::LineEndings,
+    global_state::{CancellationToken, GlobalState, GlobalStateSnapshot},
+    line_index::{LineEndings, LineIndex},
     lsp::{
         from_proto, to_proto,
         utils::{all_edits_are_disjoint, invalid_params_error},
@@ -49,6 +53,7 @@ use crate::{
         self, CrateInfoResult, ExternalDocsPair, ExternalDocsResponse, FetchDependencyListParams,
         FetchDependencyListResult, PositionOrRange, ViewCrateGraphParams, WorkspaceSymbolParams,
     },
+    symbol_cache,
 };
 
 pub(crate) fn handle_workspace_reload(state: &mut GlobalState, _: ()) -> anyhow::Result<()> {
@@ -118,9 +123,21 @@ pub(crate) fn handle_memory_usage(state: &mut GlobalState, _: ()) -> anyhow::Res
     let _p = tracing::span!(tracing::Level::INFO, "handle_memory_usage").entered();
     let mem = state.analysis_host.per_query_memory_usage();
 
+    let mut groups = FxHashMap::<_, (profile::Bytes, Vec<_>)>::default();
+    for (group, name, bytes, entries) in mem {
+        let (total, queries) = groups.entry(group).or_default();
+        *total += bytes;
+        queries.push((name, bytes, entries));
+    }
+    let mut groups = groups.into_iter().collect::<Vec<_>>();
+    groups.sort_by_key(|(_, (total, _))| std::cmp::Reverse(*total));
+
     let mut out = String::new();
-    for (name, bytes, entries) in mem {
-        format_to!(out, "{:>8} {:>6} {}\n", bytes, entries, name);
+    for (group, (total, queries)) in groups {
+        format_to!(out, "{:>8}        {}\n", total, group);
+        for (name, bytes, entries) in queries {
+            format_to!(out, "{:>8} {:>6}   {}\n", bytes, entries, name);
+        }
     }
     format_to!(out, "{:>8}        Remaining\n", profile::memory_usage().allocated);
 
@@ -192,6 +209,356 @@ pub(crate) fn handle_view_item_tree(
     Ok(res)
 }
 
+pub(crate) fn handle_crate_features(
+    snap: GlobalStateSnapshot,
+    params: lsp_ext::CrateFeaturesParams,
+) -> anyhow::Result<Vec<lsp_ext::CrateFeature>> {
+    let _p = tracing::span!(tracing::Level::INFO, "handle_crate_features").entered();
+    let file_id = from_proto::file_id(&snap, &params.text_document.uri)?;
+    let Some(&crate_id) = snap.analysis.crates_for(file_id)?.first() else {
+        return Ok(Vec::new());
+    };
+    let res = snap
+        .analysis
+        .crate_features(crate_id)?
+        .into_iter()
+        .map(|(name, enabled)| lsp_ext::CrateFeature { name, enabled })
+        .collect();
+    Ok(res)
+}
+
+pub(crate) fn handle_debug_launch_info(
+    snap: GlobalStateSnapshot,
+    params: lsp_ext::Runnable,
+    cancellation: CancellationToken,
+) -> anyhow::Result<lsp_ext::DebugLaunchInfoResult> {
+    let _p = tracing::span!(tracing::Level::INFO, "handle_debug_launch_info").entered();
+    let args = params.args;
+    let Some(workspace_root) = args.workspace_root else {
+        anyhow::bail!("runnable `{}` has no workspace root", params.label);
+    };
+
+    let mut cmd = match &args.override_cargo {
+        Some(cargo) => process::Command::new(cargo),
+        None => process::Command::new(toolchain::cargo()),
+    };
+    cmd.envs(snap.config.extra_env());
+    cmd.current_dir(&workspace_root);
+    let cargo_args = args.cargo_args.into_iter().chain(args.cargo_extra_args).collect::<Vec<_>>();
+    let res = project_model::build_executable(cmd, &cargo_args, &|| {
+        cancellation.load(Ordering::Relaxed)
+    })?;
+
+    let mut env = snap.config.extra_env().clone();
+    env.entry("RUST_BACKTRACE".to_owned()).or_insert_with(|| "short".to_owned());
+    if args.expect_test == Some(true) {
+        env.insert("UPDATE_EXPECT".to_owned(), "1".to_owned());
+    }
+    Ok(lsp_ext::DebugLaunchInfoResult {
+        executable: res.executable.into(),
+        args: args.executable_args,
+        env,
+        cwd: res.package_dir.into(),
+    })
+}
+
+pub(crate) fn handle_discover_tests(
+    snap: GlobalStateSnapshot,
+    params: lsp_ext::DiscoverTestParams,
+) -> anyhow::Result<lsp_ext::DiscoverTestResults> {
+    let _p = tracing::span!(tracing::Level::INFO, "handle_discover_tests").entered();
+    let (tests, scope) = match params.test_id {
+        Some(id) => {
+            let crate_name = id.split_once("::").map_or(&*id, |it| it.0).to_owned();
+            (snap.analysis.discover_tests_in_crate(&crate_name)?, Some(vec![crate_name]))
+        }
+        None => (snap.analysis.discover_test_roots()?, None),
+    };
+    Ok(lsp_ext::DiscoverTestResults {
+        tests: tests
+            .into_iter()
+            .map(|it| to_proto::test_item(&snap, it, None))
+            .collect::<Cancellable<_>>()?,
+        scope,
+        scope_file: None,
+    })
+}
+
+/// Returns the tests of `file_id`, replacing all previously reported tests of the file.
+pub(crate) fn discover_tests_in_file(
+    snap: &GlobalStateSnapshot,
+    file_id: FileId,
+) -> anyhow::Result<lsp_ext::DiscoverTestResults> {
+    let line_index = snap.file_line_index(file_id)?;
+    let tests = snap.analysis.discover_tests_in_file(file_id)?;
+    Ok(lsp_ext::DiscoverTestResults {
+        tests: tests
+            .into_iter()
+            .map(|it| to_proto::test_item(snap, it, Some(&line_index)))
+            .collect::<Cancellable<_>>()?,
+        scope: None,
+        scope_file: Some(vec![TextDocumentIdentifier { uri: to_proto::url(snap, file_id) }]),
+    })
+}
+
+pub(crate) fn handle_explain_expression(
+    snap: GlobalStateSnapshot,
+    params: lsp_ext::ExplainExpressionParams,
+) -> anyhow::Result<Option<lsp_ext::ExplainExpressionResult>> {
+    let _p = tracing::span!(tracing::Level::INFO, "handle_explain_expression").entered();
+    let frange = from_proto::file_range(&snap, &params.text_document, params.range)?;
+    let line_index = snap.file_line_index(frange.file_id)?;
+    let Some(res) = snap.analysis.explain_expression(frange)? else {
+        return Ok(None);
+    };
+    Ok(Some(lsp_ext::ExplainExpressionResult {
+        range: to_proto::range(&line_index, res.range),
+        ty: res.ty,
+        coerced_type: res.coerced_ty,
+        adjustments: res
+            .adjustments
+            .into_iter()
+            .map(|it| lsp_ext::ExplainedAdjustment {
+                kind: it.kind.to_owned(),
+                source: it.source,
+                target: it.target,
+            })
+            .collect(),
+        callee: res.callee,
+        callee_trait: res.callee_trait,
+    }))
+}
+
+pub(crate) fn handle_tasks(
+    snap: GlobalStateSnapshot,
+    params: lsp_ext::TasksParams,
+) -> anyhow::Result<Vec<lsp_ext::Task>> {
+    let _p = tracing::span!(tracing::Level::INFO, "handle_tasks").entered();
+    let tasks = match (params.text_document, params.position) {
+        (Some(text_document), Some(position)) => {
+            let position = from_proto::file_position(
+                &snap,
+                lsp_types::TextDocumentPositionParams { text_document, position },
+            )?;
+            snap.analysis.item_tasks(position)?
+        }
+        (Some(text_document), None) => {
+            let file_id = from_proto::file_id(&snap, &text_document.uri)?;
+            snap.analysis.file_tasks(file_id)?
+        }
+        (None, _) => snap.analysis.workspace_tasks()?,
+    };
+    tasks
+        .into_iter()
+        .map(|task| {
+            let location =
+                to_proto::location(&snap, FileRange { file_id: task.file_id, range: task.range })?;
+            Ok(lsp_ext::Task {
+                location,
+                kind: match task.kind {
+                    ide::TaskKind::Todo => lsp_ext::TaskKind::Todo,
+                    ide::TaskKind::Fixme => lsp_ext::TaskKind::Fixme,
+                    ide::TaskKind::Xxx => lsp_ext::TaskKind::Xxx,
+                },
+                text: task.text,
+                owner: task.owner.map(|it| it.name),
+            })
+        })
+        .collect()
+}
+
+pub(crate) fn handle_cargo_toml_completion(
+    snap: GlobalStateSnapshot,
+    params: lsp_types::TextDocumentPositionParams,
+) -> anyhow::Result<Vec<lsp_types::CompletionItem>> {
+    let _p = tracing::span!(tracing::Level::INFO, "handle_cargo_toml_completion").entered();
+    let position = from_proto::file_position(&snap, params)?;
+    let text = snap.analysis.file_text(position.file_id)?;
+    let line_index = snap.file_line_index(position.file_id)?;
+    let Some(ctx) = cargo_toml::completion_context(&text, position.offset.into()) else {
+        return Ok(Vec::new());
+    };
+    let manifest = snap.file_id_to_file_path(position.file_id).as_path().map(ToOwned::to_owned);
+    let index = snap.crates_index.as_ref().as_ref();
+
+    let (kind, labels) = match &ctx.target {
+        cargo_toml::CompletionTarget::CrateName => {
+            let mut names = index.map(|it| it.crate_names(&ctx.prefix)).unwrap_or_default();
+            names.extend(
+                workspace_packages(&snap, None).map(|(cargo, pkg)| cargo[pkg].name.clone()),
+            );
+            names.retain(|it| it.starts_with(&ctx.prefix));
+            names.sort();
+            names.dedup();
+            (lsp_types::CompletionItemKind::MODULE, names)
+        }
+        cargo_toml::CompletionTarget::Version { krate } => {
+            let mut versions = index
+                .map(|it| it.versions(krate))
+                .unwrap_or_default()
+                .into_iter()
+                .rev()
+                .filter(|it| !it.yanked)
+                .map(|it| it.version.to_string())
+                .collect::<Vec<_>>();
+            if versions.is_empty() {
+                versions.extend(
+                    workspace_packages(&snap, manifest.as_deref())
+                        .filter(|&(cargo, pkg)| cargo[pkg].name == *krate)
+                        .map(|(cargo, pkg)| cargo[pkg].version.to_string()),
+                );
+            }
+            (lsp_types::CompletionItemKind::VALUE, versions)
+        }
+        cargo_toml::CompletionTarget::Feature { krate } => {
+            let features = dependency_features(&snap, index, manifest.as_deref(), krate, None)
+                .unwrap_or_default();
+            (lsp_types::CompletionItemKind::PROPERTY, features)
+        }
+    };
+
+    let range = to_proto::range(
+        &line_index,
+        TextRange::new(
+            TextSize::from(ctx.range.start as u32),
+            TextSize::from(ctx.range.end as u32),
+        ),
+    );
+    // Keep the order of the candidates, newest versions come first.
+    let items = labels
+        .into_iter()
+        .enumerate()
+        .map(|(idx, label)| lsp_types::CompletionItem {
+            kind: Some(kind),
+            sort_text: Some(format!("{idx:05}")),
+            text_edit: Some(lsp_types::CompletionTextEdit::Edit(lsp_types::TextEdit::new(
+                range,
+                label.clone(),
+            ))),
+            label,
+            ..Default::default()
+        })
+        .collect();
+    Ok(items)
+}
+
+pub(crate) fn handle_cargo_toml_diagnostics(
+    snap: GlobalStateSnapshot,
+    params: TextDocumentIdentifier,
+) -> anyhow::Result<Vec<lsp_types::Diagnostic>> {
+    let _p = tracing::span!(tracing::Level::INFO, "handle_cargo_toml_diagnostics").entered();
+    let file_id = from_proto::file_id(&snap, &params.uri)?;
+    let text = snap.analysis.file_text(file_id)?;
+    let line_index = snap.file_line_index(file_id)?;
+    let manifest = snap.file_id_to_file_path(file_id).as_path().map(ToOwned::to_owned);
+    let index = snap.crates_index.as_ref().as_ref();
+
+    let mut known = FxHashMap::default();
+    let mut res = Vec::new();
+    for feature in cargo_toml::dependency_features(&text) {
+        let features =
+            known.entry((feature.krate.clone(), feature.version.clone())).or_insert_with(|| {
+                dependency_features(
+                    &snap,
+                    index,
+                    manifest.as_deref(),
+                    &feature.krate,
+                    feature.version.as_deref(),
+                )
+            });
+        // Without knowing the features of the dependency, there is nothing to check.
+        let Some(features) = features else { continue };
+        if features.contains(&feature.feature) {
+            continue;
+        }
+        let range = TextRange::new(
+            TextSize::from(feature.range.start as u32),
+            TextSize::from(feature.range.end as u32),
+        );
+        res.push(lsp_types::Diagnostic {
+            range: to_proto::range(&line_index, range),
+            severity: Some(lsp_types::DiagnosticSeverity::WARNING),
+            code: Some(lsp_types::NumberOrString::String("unknown-feature".to_owned())),
+            source: Some("rust-analyzer".to_owned()),
+            message: format!(
+                "package `{}` does not have feature `{}`",
+                feature.krate, feature.feature
+            ),
+            ..Default::default()
+        });
+    }
+    Ok(res)
+}
+
+/// The packages of the cargo workspaces, restricted to the workspace of `manifest` if given.
+fn workspace_packages<'a>(
+    snap: &'a GlobalStateSnapshot,
+    manifest: Option<&'a AbsPath>,
+) -> impl Iterator<Item = (&'a project_model::CargoWorkspace, project_model::Package)> + 'a {
+    snap.workspaces
+        .iter()
+        .filter_map(|ws| match ws {
+            ProjectWorkspace::Cargo { cargo, .. } => Some(cargo),
+            _ => None,
+        })
+        .filter(move |cargo| {
+            manifest.map_or(true, |manifest| {
+                cargo.packages().any(|pkg| *cargo[pkg].manifest == *manifest)
+                    || *cargo.workspace_root().join("Cargo.toml") == *manifest
+            })
+        })
+        .flat_map(|cargo| cargo.packages().map(move |pkg| (cargo, pkg)))
+}
+
+/// The features of the dependency `krate` of the manifest, preferring the versions cargo resolved
+/// for the workspace over the crates.io index.
+fn dependency_features(
+    snap: &GlobalStateSnapshot,
+    index: Option<&CratesIndex>,
+    manifest: Option<&AbsPath>,
+    krate: &str,
+    version_req: Option<&str>,
+) -> Option<Vec<String>> {
+    let resolved = workspace_packages(snap, manifest)
+        .filter(|&(cargo, pkg)| cargo[pkg].name == krate)
+        .collect::<Vec<_>>();
+    if !resolved.is_empty() {
+        let mut features = resolved
+            .into_iter()
+            .flat_map(|(cargo, pkg)| cargo[pkg].features.keys().cloned())
+            .collect::<Vec<_>>();
+        features.sort();
+        features.dedup();
+        return Some(features);
+    }
+    let version = index?.newest_matching(krate, version_req)?;
+    Some(version.features)
+}
+
+pub(crate) fn handle_imports_for_paste(
+    snap: GlobalStateSnapshot,
+    params: lsp_ext::ImportsForPasteParams,
+) -> anyhow::Result<Option<lsp_ext::ImportsForPasteResult>> {
+    let _p = tracing::span!(tracing::Level::INFO, "handle_imports_for_paste").entered();
+    let frange = from_proto::file_range(&snap, &params.text_document, params.range)?;
+    let line_index = snap.file_line_index(frange.file_id)?;
+    let Some(res) = snap.analysis.paste_imports(&snap.config.assist(), frange, &params.chosen)?
+    else {
+        return Ok(None);
+    };
+    let ambiguous = res
+        .ambiguous
+        .into_iter()
+        .map(|it| lsp_ext::AmbiguousImport {
+            name: it.name,
+            range: to_proto::range(&line_index, it.range),
+            candidates: it.candidates,
+        })
+        .collect();
+    let edit = to_proto::workspace_edit(&snap, res.edit)?;
+    Ok(Some(lsp_ext::ImportsForPasteResult { edit, ambiguous }))
+}
+
 pub(crate) fn handle_view_crate_graph(
     snap: GlobalStateSnapshot,
     params: ViewCrateGraphParams,
@@ -214,6 +581,30 @@ pub(crate) fn handle_expand_macro(
     Ok(res.map(|it| lsp_ext::ExpandedMacro { name: it.name, expansion: it.expansion }))
 }
 
+pub(crate) fn handle_expand_macro_diagnostic(
+    snap: GlobalStateSnapshot,
+    params: lsp_ext::ExpandMacroDiagnosticParams,
+) -> anyhow::Result<Option<lsp_ext::ExpandedMacroDiagnostic>> {
+    let _p = tracing::span!(tracing::Level::INFO, "handle_expand_macro_diagnostic").entered();
+    let frange = from_proto::file_range(&snap, &params.text_document, params.range)?;
+
+    let Some(res) = snap.analysis.expand_macro_diagnostic(&snap.config.diagnostics(), frange)?
+    else {
+        return Ok(None);
+    };
+    let line_index = LineIndex {
+        index: Arc::new(ide::LineIndex::new(&res.expansion)),
+        endings: LineEndings::Unix,
+        encoding: snap.config.position_encoding(),
+    };
+    Ok(Some(lsp_ext::ExpandedMacroDiagnostic {
+        name: res.name,
+        range: to_proto::range(&line_index, res.range),
+        expansion: res.expansion,
+        call_site: to_proto::location(&snap, res.call_site)?,
+    }))
+}
+
 pub(crate) fn handle_selection_range(
     snap: GlobalStateSnapshot,
     params: lsp_types::SelectionRangeParams,
@@ -460,7 +851,8 @@ pub(crate) fn handle_workspace_symbol(
     let (all_symbols, libs) = decide_search_scope_and_kind(&params, &config);
 
     let query = {
-        let query: String = params.query.chars().filter(|&c| c != '#' && c != '*').collect();
+        let query: String =
+            params.query.chars().filter(|&c| c != '#' && c != '*' && c != '~').collect();
         let mut q = Query::new(query);
         if !all_symbols {
             q.only_types();
@@ -468,11 +860,14 @@ pub(crate) fn handle_workspace_symbol(
         if libs {
             q.libs();
         }
+        if config.exclude_hidden && !params.query.contains('~') {
+            q.exclude_hidden();
+        }
         q
     };
-    let mut res = exec_query(&snap, query, config.search_limit)?;
+    let mut res = exec_query(&snap, query, libs, config.search_limit)?;
     if res.is_empty() && !all_symbols {
-        res = exec_query(&snap, Query::new(params.query), config.search_limit)?;
+        res = exec_query(&snap, Query::new(params.query), false, config.search_limit)?;
     }
 
     return Ok(Some(lsp_types::WorkspaceSymbolResponse::Nested(res)));
@@ -515,28 +910,17 @@ pub(crate) fn handle_workspace_symbol(
     fn exec_query(
         snap: &GlobalStateSnapshot,
         query: Query,
+        libs: bool,
         limit: usize,
     ) -> anyhow::Result<Vec<lsp_types::WorkspaceSymbol>> {
+        // Until the dependencies are indexed, their symbols persisted by an earlier session are
+        // searched instead.
+        if let Some(cached) = snap.library_symbols_cache.as_deref().filter(|_| libs) {
+            return Ok(symbol_cache::search(cached, &query, limit));
+        }
         let mut res = Vec::new();
         for nav in snap.analysis.symbol_search(query, limit)? {
-            let container_name = nav.container_name.as_ref().map(|v| v.to_string());
-
-            let info = lsp_types::WorkspaceSymbol {
-                name: match &nav.alias {
-                    Some(alias) => format!("{} (alias for {})", alias, nav.name),
-                    None => format!("{}", nav.name),
-                },
-                kind: nav
-                    .kind
-                    .map(to_proto::symbol_kind)
-                    .unwrap_or(lsp_types::SymbolKind::VARIABLE),
-                // FIXME: Set deprecation
-                tags: None,
-                container_name,
-                location: lsp_types::OneOf::Left(to_proto::location_from_nav(snap, nav)?),
-                data: None,
-            };
-            res.push(info);
+            res.push(to_proto::workspace_symbol(snap, nav)?);
         }
         Ok(res)
     }
@@ -872,6 +1256,36 @@ pub(crate) fn handle_completion(
     Ok(Some(completion_list.into()))
 }
 
+/// The completions for when [`handle_completion`] takes longer than `completion.softBudget`: the
+/// names declared in the file, which only need its syntax tree.
+pub(crate) fn handle_completion_partial(
+    snap: GlobalStateSnapshot,
+    params: lsp_types::CompletionParams,
+) -> anyhow::Result<Option<lsp_types::CompletionResponse>> {
+    let _p = tracing::span!(tracing::Level::INFO, "handle_completion_partial").entered();
+    let file_id = from_proto::file_id(&snap, &params.text_document_position.text_document.uri)?;
+
+    let items = snap
+        .analysis
+        .file_structure(file_id)?
+        .into_iter()
+        .filter_map(|node| match node.kind {
+            StructureNodeKind::SymbolKind(SymbolKind::Impl) | StructureNodeKind::Region => None,
+            StructureNodeKind::SymbolKind(kind) => Some((node.label, kind)),
+        })
+        .unique()
+        .map(|(label, kind)| CompletionItem {
+            label,
+            kind: Some(to_proto::completion_item_kind(CompletionItemKind::SymbolKind(kind))),
+            ..Default::default()
+        })
+        .collect();
+
+    // The client asks again as the user keeps typing, by then the full completions may be ready.
+    let completion_list = lsp_types::CompletionList { is_incomplete: true, items };
+    Ok(Some(completion_list.into()))
+}
+
 pub(crate) fn handle_completion_resolve(
     snap: GlobalStateSnapshot,
     mut original_completion: CompletionItem,
@@ -964,30 +1378,50 @@ pub(crate) fn handle_hover(
     snap: GlobalStateSnapshot,
     params: lsp_ext::HoverParams,
 ) -> anyhow::Result<Option<lsp_ext::Hover>> {
-    let _p = tracing::span!(tracing::Level::INFO, "handle_hover").entered();
+    hover(snap, params, true)
+}
+
+/// The hover for when [`handle_hover`] takes longer than `hover.softBudget`, without the hover
+/// actions and the memory layout.
+pub(crate) fn handle_hover_partial(
+    snap: GlobalStateSnapshot,
+    params: lsp_ext::HoverParams,
+) -> anyhow::Result<Option<lsp_ext::Hover>> {
+    hover(snap, params, false)
+}
+
+fn hover(
+    snap: GlobalStateSnapshot,
+    params: lsp_ext::HoverParams,
+    full: bool,
+) -> anyhow::Result<Option<lsp_ext::Hover>> {
+    let _p = tracing::span!(tracing::Level::INFO, "handle_hover", full).entered();
     let range = match params.position {
         PositionOrRange::Position(position) => Range::new(position, position),
         PositionOrRange::Range(range) => range,
     };
 
     let file_range = from_proto::file_range(&snap, &params.text_document, range)?;
-    let info = match snap.analysis.hover(&snap.config.hover(), file_range)? {
+    let mut config = snap.config.hover();
+    if !full {
+        config.memory_layout = None;
+    }
+    let info = match snap.analysis.hover(&config, file_range)? {
         None => return Ok(None),
         Some(info) => info,
     };
 
     let line_index = snap.file_line_index(file_range.file_id)?;
     let range = to_proto::range(&line_index, info.range);
-    let markup_kind = snap.config.hover().format;
     let hover = lsp_ext::Hover {
         hover: lsp_types::Hover {
             contents: HoverContents::Markup(to_proto::markup_content(
                 info.info.markup,
-                markup_kind,
+                config.format,
             )),
             range: Some(range),
         },
-        actions: if snap.config.hover_actions().none() {
+        actions: if !full || snap.config.hover_actions().none() {
             Vec::new()
         } else {
             prepare_hover_actions(&snap, &info.info.actions)
@@ -1166,7 +1600,13 @@ pub(crate) fn handle_code_action(
     }
 
     // Fixes from `cargo check`.
-    for fix in snap.check_fixes.values().filter_map(|it| it.get(&frange.file_id)).flatten() {
+    for fix in snap
+        .check_fixes
+        .values()
+        .flat_map(|it| it.values())
+        .filter_map(|it| it.get(&frange.file_id))
+        .flatten()
+    {
         // FIXME: this mapping is awkward and shouldn't exist. Refactor
         // `snap.check_fixes` to not convert to LSP prematurely.
         let intersect_fix_range = fix
@@ -1179,10 +1619,130 @@ pub(crate) fn handle_code_action(
             res.push(fix.action.clone());
         }
     }
+    if let Some(action) = check_fixes_fix_all(&snap, frange.file_id, params.context.only.as_deref())
+    {
+        res.push(action);
+    }
+
+    let quick_fixes_requested = params.context.only.as_ref().map_or(true, |kinds| {
+        kinds.iter().any(|it| it.as_str().starts_with(lsp_types::CodeActionKind::QUICKFIX.as_str()))
+    });
+    if snap.config.client_commands().apply_ssr_rule && quick_fixes_requested {
+        let position = lsp_types::TextDocumentPositionParams {
+            text_document: params.text_document.clone(),
+            position: params.range.start,
+        };
+        // The rules come with the native diagnostics we published, see `convert_diagnostic`.
+        let rules = params
+            .context
+            .diagnostics
+            .iter()
+            .filter(|it| it.source.as_deref() == Some("rust-analyzer"))
+            .filter_map(|it| it.data.as_ref()?.get("ssrRule")?.as_str())
+            .unique();
+        for rule in rules {
+            let title = format!("Apply `{rule}` across the workspace");
+            let rule = rule.to_owned();
+            res.push(lsp_ext::CodeAction {
+                title: title.clone(),
+                group: None,
+                kind: Some(lsp_types::CodeActionKind::QUICKFIX),
+                command: Some(to_proto::command::apply_ssr_rule(title, rule, position.clone())),
+                edit: None,
+                is_preferred: None,
+                data: None,
+            });
+        }
+    }
+
+    if snap.config.client_commands().enable_cfg && params.context.only.is_none() {
+        let position = FilePosition { file_id: frange.file_id, offset: frange.range.start() };
+        for atoms in snap.analysis.cfg_enable_hints(position)? {
+            let title = format!("Analyze as if `{}` were enabled", atoms.iter().format("`, `"));
+            res.push(lsp_ext::CodeAction {
+                title: title.clone(),
+                group: None,
+                kind: None,
+                command: Some(to_proto::command::enable_cfg(title, &atoms)),
+                edit: None,
+                is_preferred: None,
+                data: None,
+            });
+        }
+    }
 
     Ok(Some(res))
 }
 
+/// Combines all machine-applicable fixes of a file from `cargo check` into a single action, like
+/// `cargo fix` applies them. Edits overlapping with the ones of a previous fix are skipped.
+fn check_fixes_fix_all(
+    snap: &GlobalStateSnapshot,
+    file_id: FileId,
+    only: Option<&[lsp_types::CodeActionKind]>,
+) -> Option<lsp_ext::CodeAction> {
+    let fix_all = lsp_types::CodeActionKind::SOURCE_FIX_ALL;
+    let requested = match only {
+        Some(kinds) => {
+            if !kinds.iter().any(|kind| fix_all.as_str().starts_with(kind.as_str())) {
+                return None;
+            }
+            true
+        }
+        None => false,
+    };
+
+    let fixes = snap
+        .check_fixes
+        .values()
+        .flat_map(|it| it.values())
+        .filter_map(|it| it.get(&file_id))
+        .flatten()
+        .filter(|fix| fix.action.is_preferred == Some(true))
+        .collect::<Vec<_>>();
+    // A single fix is offered on its own already.
+    if fixes.is_empty() || (fixes.len() == 1 && !requested) {
+        return None;
+    }
+
+    let mut changes: FxHashMap<lsp_types::Url, Vec<lsp_types::TextEdit>> = FxHashMap::default();
+    for fix in &fixes {
+        let Some(fix_changes) = fix.action.edit.as_ref().and_then(|it| it.changes.as_ref()) else {
+            continue;
+        };
+        let overlaps = fix_changes.iter().any(|(url, edits)| {
+            changes.get(url).map_or(false, |existing| {
+                edits.iter().any(|edit| {
+                    existing.iter().any(|it| {
+                        (it.range.start < edit.range.end && edit.range.start < it.range.end)
+                            || it.range.start == edit.range.start
+                    })
+                })
+            })
+        });
+        if overlaps {
+            continue;
+        }
+        for (url, edits) in fix_changes {
+            changes.entry(url.clone()).or_default().extend(edits.iter().cloned());
+        }
+    }
+
+    Some(lsp_ext::CodeAction {
+        title: "Apply all machine-applicable fixes from `cargo check`".to_owned(),
+        group: None,
+        kind: Some(fix_all),
+        edit: Some(lsp_ext::SnippetWorkspaceEdit {
+            changes: Some(changes),
+            document_changes: None,
+            change_annotations: None,
+        }),
+        is_preferred: None,
+        data: None,
+        command: None,
+    })
+}
+
 pub(crate) fn handle_code_action_resolve(
     snap: GlobalStateSnapshot,
     mut code_action: lsp_ext::CodeAction,
@@ -1382,7 +1942,34 @@ pub(crate) fn handle_ssr(
         position,
         selections,
     )??;
-    to_proto::workspace_edit(&snap, source_change).map_err(Into::into)
+    let mut edit = to_proto::workspace_edit(&snap, source_change)?;
+    if params.preview && snap.config.change_annotation_support() {
+        to_proto::annotate_for_preview(&mut edit, format!("Apply `{}`", params.query));
+    }
+    Ok(edit)
+}
+
+pub(crate) fn handle_search_by_type(
+    snap: GlobalStateSnapshot,
+    params: lsp_ext::SearchByTypeParams,
+) -> anyhow::Result<Vec<lsp_ext::TypeSearchMatch>> {
+    let _p = tracing::span!(tracing::Level::INFO, "handle_search_by_type").entered();
+    snap.analysis
+        .search_by_type(&params.query)??
+        .into_iter()
+        .map(|it| {
+            let location =
+                to_proto::location(&snap, FileRange { file_id: it.file_id, range: it.range })?;
+            Ok(lsp_ext::TypeSearchMatch {
+                location,
+                kind: match it.kind {
+                    ide::TypeSearchMatchKind::Expr => lsp_ext::TypeSearchMatchKind::Expr,
+                    ide::TypeSearchMatchKind::Binding => lsp_ext::TypeSearchMatchKind::Binding,
+                },
+                ty: it.ty,
+            })
+        })
+        .collect()
 }
 
 pub(crate) fn handle_inlay_hints(
@@ -1431,7 +2018,10 @@ pub(crate) fn handle_inlay_hints_resolve(
     anyhow::ensure!(snap.file_exists(file_id), "Invalid LSP resolve data");
 
     let line_index = snap.file_line_index(file_id)?;
-    let hint_position = from_proto::offset(&line_index, original_hint.position)?;
+    // The file may have changed since the hint was sent, in which case it can't be resolved.
+    let Ok(hint_position) = from_proto::offset(&line_index, original_hint.position) else {
+        return Ok(original_hint);
+    };
     let mut forced_resolve_inlay_hints_config = snap.config.inlay_hints();
     forced_resolve_inlay_hints_config.fields_to_resolve = InlayFieldsToResolve::empty();
     let resolve_hints = snap.analysis.inlay_hints(
@@ -1653,6 +2243,66 @@ pub(crate) fn handle_semantic_tokens_range(
     Ok(Some(semantic_tokens.into()))
 }
 
+pub(crate) fn handle_document_diagnostics(
+    snap: GlobalStateSnapshot,
+    params: lsp_types::DocumentDiagnosticParams,
+) -> anyhow::Result<lsp_types::DocumentDiagnosticReportResult> {
+    let _p = tracing::span!(tracing::Level::INFO, "handle_document_diagnostics").entered();
+
+    let file_id = from_proto::file_id(&snap, &params.text_document.uri)?;
+    let line_index = snap.file_line_index(file_id)?;
+    let is_suppressed = from_proto::abs_path(&params.text_document.uri).map_or(false, |path| {
+        snap.config.files().is_generated(&path) || snap.config.diagnostics_disabled_in(&path)
+    });
+
+    // Like published diagnostics, these are left out for dependencies and the sysroot. Diagnostics
+    // pointing into other files are dropped, the client requests those files separately.
+    let mut items = if !snap.config.publish_diagnostics()
+        || is_suppressed
+        || snap.generated_files.contains(&file_id)
+        || snap.analysis.is_library_file(file_id)?
+    {
+        Vec::new()
+    } else {
+        snap.analysis
+            .diagnostics(&snap.config.diagnostics(), ide::AssistResolveStrategy::None, file_id)?
+            .into_iter()
+            .filter(|it| it.range.file_id == file_id)
+            .map(|it| crate::diagnostics::convert_diagnostic(&line_index, it))
+            .collect::<Vec<_>>()
+    };
+    // VSCode assumes diagnostic messages to be non-empty strings.
+    for item in &mut items {
+        if item.message.is_empty() {
+            item.message = " ".to_owned();
+        }
+    }
+
+    let result_id = {
+        let mut hasher = rustc_hash::FxHasher::default();
+        serde_json::to_string(&items)?.hash(&mut hasher);
+        hasher.finish().to_string()
+    };
+    let report = if params.previous_result_id.as_deref() == Some(&*result_id) {
+        lsp_types::DocumentDiagnosticReport::Unchanged(
+            lsp_types::RelatedUnchangedDocumentDiagnosticReport {
+                related_documents: None,
+                unchanged_document_diagnostic_report:
+                    lsp_types::UnchangedDocumentDiagnosticReport { result_id },
+            },
+        )
+    } else {
+        lsp_types::DocumentDiagnosticReport::Full(lsp_types::RelatedFullDocumentDiagnosticReport {
+            related_documents: None,
+            full_document_diagnostic_report: lsp_types::FullDocumentDiagnosticReport {
+                result_id: Some(result_id),
+                items,
+            },
+        })
+    };
+    Ok(lsp_types::DocumentDiagnosticReportResult::Report(report))
+}
+
 pub(crate) fn handle_open_docs(
     snap: GlobalStateSnapshot,
     params: lsp_types::TextDocumentPositionParams,
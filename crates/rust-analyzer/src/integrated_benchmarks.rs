@@ -134,6 +134,7 @@ fn integrated_completion_benchmark() {
             enable_private_editable: true,
             enable_term_search: true,
             full_function_signatures: false,
+            struct_literal_default_rest: false,
             callable: Some(CallableSnippets::FillArguments),
             snippet_cap: SnippetCap::new(true),
             insert_use: InsertUseConfig {
@@ -178,6 +179,7 @@ fn integrated_completion_benchmark() {
             enable_private_editable: true,
             enable_term_search: true,
             full_function_signatures: false,
+            struct_literal_default_rest: false,
             callable: Some(CallableSnippets::FillArguments),
             snippet_cap: SnippetCap::new(true),
             insert_use: InsertUseConfig {
@@ -220,6 +222,7 @@ fn integrated_completion_benchmark() {
             enable_private_editable: true,
             enable_term_search: true,
             full_function_signatures: false,
+            struct_literal_default_rest: false,
             callable: Some(CallableSnippets::FillArguments),
             snippet_cap: SnippetCap::new(true),
             insert_use: InsertUseConfig {
@@ -15,6 +15,8 @@ pub mod cli;
 
 mod caps;
 mod cargo_target_spec;
+mod cargo_toml;
+mod daemon;
 mod diagnostics;
 mod diff;
 mod dispatch;
@@ -24,6 +26,7 @@ mod main_loop;
 mod mem_docs;
 mod op_queue;
 mod reload;
+mod symbol_cache;
 mod task_pool;
 mod version;
 
@@ -48,7 +51,10 @@ mod integrated_benchmarks;
 use serde::de::DeserializeOwned;
 
 pub use crate::{
-    caps::server_capabilities, main_loop::main_loop, reload::ws_to_crate_graph, version::version,
+    caps::server_capabilities,
+    main_loop::{daemon_main_loop, main_loop},
+    reload::ws_to_crate_graph,
+    version::version,
 };
 
 pub fn from_json<T: DeserializeOwned>(
@@ -163,6 +163,209 @@ impl Request for ViewItemTree {
     const METHOD: &'static str = "rust-analyzer/viewItemTree";
 }
 
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CrateFeaturesParams {
+    pub text_document: TextDocumentIdentifier,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CrateFeature {
+    pub name: String,
+    pub enabled: bool,
+}
+
+pub enum CrateFeatures {}
+
+impl Request for CrateFeatures {
+    type Params = CrateFeaturesParams;
+    type Result = Vec<CrateFeature>;
+    const METHOD: &'static str = "rust-analyzer/crateFeatures";
+}
+
+pub enum DebugLaunchInfo {}
+
+impl Request for DebugLaunchInfo {
+    type Params = Runnable;
+    type Result = DebugLaunchInfoResult;
+    const METHOD: &'static str = "rust-analyzer/debugLaunchInfo";
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DebugLaunchInfoResult {
+    pub executable: PathBuf,
+    pub args: Vec<String>,
+    pub env: FxHashMap<String, String>,
+    pub cwd: PathBuf,
+}
+
+pub enum DiscoverTests {}
+
+impl Request for DiscoverTests {
+    type Params = DiscoverTestParams;
+    type Result = DiscoverTestResults;
+    const METHOD: &'static str = "rust-analyzer/discoverTests";
+}
+
+pub enum DiscoveredTests {}
+
+impl Notification for DiscoveredTests {
+    type Params = DiscoverTestResults;
+    const METHOD: &'static str = "rust-analyzer/discoveredTests";
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DiscoverTestParams {
+    pub test_id: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DiscoverTestResults {
+    pub tests: Vec<TestItem>,
+    pub scope: Option<Vec<String>>,
+    pub scope_file: Option<Vec<TextDocumentIdentifier>>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub enum TestItemKind {
+    Package,
+    Module,
+    Test,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TestItem {
+    pub id: String,
+    pub label: String,
+    pub kind: TestItemKind,
+    pub can_resolve_children: bool,
+    pub parent: Option<String>,
+    pub text_document: Option<TextDocumentIdentifier>,
+    pub range: Option<Range>,
+    pub runnable: Option<Runnable>,
+}
+
+pub enum ExplainExpression {}
+
+impl Request for ExplainExpression {
+    type Params = ExplainExpressionParams;
+    type Result = Option<ExplainExpressionResult>;
+    const METHOD: &'static str = "rust-analyzer/explainExpression";
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ExplainExpressionParams {
+    pub text_document: TextDocumentIdentifier,
+    pub range: Range,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ExplainExpressionResult {
+    pub range: Range,
+    #[serde(rename = "type")]
+    pub ty: String,
+    pub coerced_type: Option<String>,
+    pub adjustments: Vec<ExplainedAdjustment>,
+    pub callee: Option<String>,
+    pub callee_trait: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ExplainedAdjustment {
+    pub kind: String,
+    pub source: String,
+    pub target: String,
+}
+
+pub enum ImportsForPaste {}
+
+impl Request for ImportsForPaste {
+    type Params = ImportsForPasteParams;
+    type Result = Option<ImportsForPasteResult>;
+    const METHOD: &'static str = "rust-analyzer/importsForPaste";
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportsForPasteParams {
+    pub text_document: TextDocumentIdentifier,
+    pub range: Range,
+    #[serde(default)]
+    pub chosen: Vec<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportsForPasteResult {
+    pub edit: lsp_types::WorkspaceEdit,
+    pub ambiguous: Vec<AmbiguousImport>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AmbiguousImport {
+    pub name: String,
+    pub range: Range,
+    pub candidates: Vec<String>,
+}
+
+pub enum CargoTomlCompletion {}
+
+impl Request for CargoTomlCompletion {
+    type Params = lsp_types::TextDocumentPositionParams;
+    type Result = Vec<lsp_types::CompletionItem>;
+    const METHOD: &'static str = "rust-analyzer/cargoTomlCompletion";
+}
+
+pub enum CargoTomlDiagnostics {}
+
+impl Request for CargoTomlDiagnostics {
+    type Params = TextDocumentIdentifier;
+    type Result = Vec<lsp_types::Diagnostic>;
+    const METHOD: &'static str = "rust-analyzer/cargoTomlDiagnostics";
+}
+
+pub enum Tasks {}
+
+impl Request for Tasks {
+    type Params = TasksParams;
+    type Result = Vec<Task>;
+    const METHOD: &'static str = "rust-analyzer/tasks";
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TasksParams {
+    pub text_document: Option<TextDocumentIdentifier>,
+    pub position: Option<Position>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Task {
+    pub location: lsp_types::Location,
+    pub kind: TaskKind,
+    pub text: String,
+    pub owner: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub enum TaskKind {
+    Todo,
+    Fixme,
+    Xxx,
+}
+
 pub enum ExpandMacro {}
 
 impl Request for ExpandMacro {
@@ -185,6 +388,32 @@ pub struct ExpandedMacro {
     pub expansion: String,
 }
 
+pub enum ExpandMacroDiagnostic {}
+
+impl Request for ExpandMacroDiagnostic {
+    type Params = ExpandMacroDiagnosticParams;
+    type Result = Option<ExpandedMacroDiagnostic>;
+    const METHOD: &'static str = "rust-analyzer/expandMacroDiagnostic";
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ExpandMacroDiagnosticParams {
+    pub text_document: TextDocumentIdentifier,
+    /// The range of the diagnostic.
+    pub range: Range,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ExpandedMacroDiagnostic {
+    pub name: String,
+    pub expansion: String,
+    /// The range of the diagnostic within `expansion`.
+    pub range: Range,
+    pub call_site: lsp_types::Location,
+}
+
 pub enum ViewRecursiveMemoryLayout {}
 
 impl Request for ViewRecursiveMemoryLayout {
@@ -381,6 +610,41 @@ pub struct SsrParams {
 
     /// Current selections. Search/replace will be restricted to these if non-empty.
     pub selections: Vec<lsp_types::Range>,
+
+    /// Whether to mark the edits as needing confirmation, so that the client previews them.
+    #[serde(default)]
+    pub preview: bool,
+}
+
+pub enum SearchByType {}
+
+impl Request for SearchByType {
+    type Params = SearchByTypeParams;
+    type Result = Vec<TypeSearchMatch>;
+    const METHOD: &'static str = "experimental/searchByType";
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchByTypeParams {
+    /// The type to search for, like `Arc<Mutex<_>>`.
+    pub query: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TypeSearchMatch {
+    pub location: lsp_types::Location,
+    pub kind: TypeSearchMatchKind,
+    /// The type of the expression or binding.
+    pub ty: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TypeSearchMatchKind {
+    Expr,
+    Binding,
 }
 
 pub enum ServerStatusNotification {}
@@ -7,10 +7,10 @@ use std::{
 
 use ide::{
     Annotation, AnnotationKind, Assist, AssistKind, Cancellable, CompletionItem,
-    CompletionItemKind, CompletionRelevance, Documentation, FileId, FileRange, FileSystemEdit,
-    Fold, FoldKind, Highlight, HlMod, HlOperator, HlPunct, HlRange, HlTag, Indel,
-    InlayFieldsToResolve, InlayHint, InlayHintLabel, InlayHintLabelPart, InlayKind, Markup,
-    NavigationTarget, ReferenceCategory, RenameError, Runnable, Severity, SignatureHelp,
+    CompletionItemKind, CompletionRelevance, CompletionRelevanceFuzzyMatch, Documentation, FileId,
+    FileRange, FileSystemEdit, Fold, FoldKind, Highlight, HlMod, HlOperator, HlPunct, HlRange,
+    HlTag, Indel, InlayFieldsToResolve, InlayHint, InlayHintLabel, InlayHintLabelPart, InlayKind,
+    Markup, NavigationTarget, ReferenceCategory, RenameError, Runnable, Severity, SignatureHelp,
     SnippetEdit, SourceChange, StructureNodeKind, SymbolKind, TextEdit, TextRange, TextSize,
 };
 use ide_db::rust_doc::format_docs;
@@ -354,7 +354,17 @@ fn completion_item(
         // by the client. Hex format is used because it is easier to
         // visually compare very large values, which the sort text
         // tends to be since it is the opposite of the score.
-        res.sort_text = Some(format!("{sort_score:08x}"));
+        // Among the ones with equal scores, items that match the typed text better go first, then
+        // recently used ones.
+        let fuzzy_match = match relevance.fuzzy_match {
+            Some(CompletionRelevanceFuzzyMatch::Prefix) => 0,
+            Some(CompletionRelevanceFuzzyMatch::WordBoundary) => 1,
+            Some(CompletionRelevanceFuzzyMatch::Subsequence) => 2,
+            Some(CompletionRelevanceFuzzyMatch::Typo) => 3,
+            None => 4,
+        };
+        let recency = if relevance.is_recently_used { 0 } else { 1 };
+        res.sort_text = Some(format!("{sort_score:08x}{fuzzy_match}{recency}"));
     }
 }
 
@@ -509,6 +519,11 @@ fn inlay_hint_label(
             (lsp_types::InlayHintLabel::String(text), hint_tooltip)
         }
         _ => {
+            // Clicking a part of the label jumps to its definition, if the client can do that.
+            let with_command = snap.config.client_commands().goto_location;
+            let resolve_location = needs_resolve && fields_to_resolve.resolve_label_location;
+            let resolve_command =
+                with_command && needs_resolve && fields_to_resolve.resolve_label_command;
             let parts = label
                 .parts
                 .into_iter()
@@ -532,17 +547,34 @@ fn inlay_hint_label(
                             None => None,
                         }
                     };
-                    let location = if needs_resolve && fields_to_resolve.resolve_label_location {
-                        something_to_resolve |= part.linked_location.is_some();
-                        None
-                    } else {
-                        part.linked_location.map(|range| location(snap, range)).transpose()?
+                    something_to_resolve |=
+                        part.linked_location.is_some() && (resolve_location || resolve_command);
+                    let location = match part.linked_location {
+                        Some(range) if !resolve_location => Some(location(snap, range)?),
+                        _ => None,
+                    };
+                    let command = match part.linked_location {
+                        Some(range) if with_command && !resolve_command => {
+                            let nav = NavigationTarget {
+                                file_id: range.file_id,
+                                full_range: range.range,
+                                focus_range: None,
+                                name: part.text.as_str().into(),
+                                kind: None,
+                                container_name: None,
+                                description: None,
+                                docs: None,
+                                alias: None,
+                            };
+                            command::goto_location(snap, &nav)
+                        }
+                        _ => None,
                     };
                     Ok(lsp_types::InlayHintLabelPart {
                         value: part.text,
                         tooltip,
                         location,
-                        command: None,
+                        command,
                     })
                 })
                 .collect::<Cancellable<_>>()?;
@@ -680,6 +712,7 @@ fn semantic_token_type_and_modifiers(
         HlTag::InvalidEscapeSequence => semantic_tokens::INVALID_ESCAPE_SEQUENCE,
         HlTag::FormatSpecifier => semantic_tokens::FORMAT_SPECIFIER,
         HlTag::Keyword => semantic_tokens::KEYWORD,
+        HlTag::MacroMetavariable => semantic_tokens::MACRO_METAVARIABLE,
         HlTag::None => semantic_tokens::GENERIC,
         HlTag::Operator(op) => match op {
             HlOperator::Bitwise => semantic_tokens::BITWISE,
@@ -714,6 +747,7 @@ fn semantic_token_type_and_modifiers(
             HlMod::ControlFlow => semantic_tokens::CONTROL_FLOW,
             HlMod::CrateRoot => semantic_tokens::CRATE_ROOT,
             HlMod::DefaultLibrary => semantic_tokens::DEFAULT_LIBRARY,
+            HlMod::Deprecated => semantic_tokens::DEPRECATED,
             HlMod::Definition => semantic_tokens::DECLARATION,
             HlMod::Documentation => semantic_tokens::DOCUMENTATION,
             HlMod::Injected => semantic_tokens::INJECTED,
@@ -860,6 +894,25 @@ pub(crate) fn location_from_nav(
     Ok(loc)
 }
 
+pub(crate) fn workspace_symbol(
+    snap: &GlobalStateSnapshot,
+    nav: NavigationTarget,
+) -> Cancellable<lsp_types::WorkspaceSymbol> {
+    let container_name = nav.container_name.as_ref().map(|v| v.to_string());
+    Ok(lsp_types::WorkspaceSymbol {
+        name: match &nav.alias {
+            Some(alias) => format!("{} (alias for {})", alias, nav.name),
+            None => format!("{}", nav.name),
+        },
+        kind: nav.kind.map(symbol_kind).unwrap_or(lsp_types::SymbolKind::VARIABLE),
+        // FIXME: Set deprecation
+        tags: None,
+        container_name,
+        location: lsp_types::OneOf::Left(location_from_nav(snap, nav)?),
+        data: None,
+    })
+}
+
 pub(crate) fn location_link(
     snap: &GlobalStateSnapshot,
     src: Option<FileRange>,
@@ -1209,6 +1262,35 @@ pub(crate) fn snippet_workspace_edit(
     Ok(workspace_edit)
 }
 
+/// Marks all text edits of `edit` as needing confirmation, so that clients show a preview of the
+/// edit before applying it.
+pub(crate) fn annotate_for_preview(edit: &mut lsp_types::WorkspaceEdit, label: String) {
+    let annotation_id = String::from("Preview");
+    let Some(lsp_types::DocumentChanges::Operations(ops)) = &mut edit.document_changes else {
+        return;
+    };
+    for op in ops {
+        let lsp_types::DocumentChangeOperation::Edit(edit) = op else { continue };
+        for text_edit in &mut edit.edits {
+            let annotated = match text_edit {
+                lsp_types::OneOf::Left(it) => lsp_types::AnnotatedTextEdit {
+                    text_edit: it.clone(),
+                    annotation_id: annotation_id.clone(),
+                },
+                lsp_types::OneOf::Right(it) => lsp_types::AnnotatedTextEdit {
+                    annotation_id: annotation_id.clone(),
+                    ..it.clone()
+                },
+            };
+            *text_edit = lsp_types::OneOf::Right(annotated);
+        }
+    }
+    edit.change_annotations.get_or_insert_with(Default::default).insert(
+        annotation_id,
+        lsp_types::ChangeAnnotation { label, needs_confirmation: Some(true), description: None },
+    );
+}
+
 pub(crate) fn workspace_edit(
     snap: &GlobalStateSnapshot,
     source_change: SourceChange,
@@ -1290,6 +1372,7 @@ pub(crate) fn code_action_kind(kind: AssistKind) -> lsp_types::CodeActionKind {
         AssistKind::RefactorExtract => lsp_types::CodeActionKind::REFACTOR_EXTRACT,
         AssistKind::RefactorInline => lsp_types::CodeActionKind::REFACTOR_INLINE,
         AssistKind::RefactorRewrite => lsp_types::CodeActionKind::REFACTOR_REWRITE,
+        AssistKind::SourceOrganizeImports => lsp_types::CodeActionKind::SOURCE_ORGANIZE_IMPORTS,
     }
 }
 
@@ -1355,6 +1438,37 @@ pub(crate) fn runnable(
     })
 }
 
+pub(crate) fn test_item(
+    snap: &GlobalStateSnapshot,
+    test_item: ide::TestItem,
+    line_index: Option<&LineIndex>,
+) -> Cancellable<lsp_ext::TestItem> {
+    let kind = match test_item.kind {
+        ide::TestItemKind::Crate => lsp_ext::TestItemKind::Package,
+        ide::TestItemKind::Module => lsp_ext::TestItemKind::Module,
+        ide::TestItemKind::Function => lsp_ext::TestItemKind::Test,
+    };
+    let range = match (test_item.file, test_item.text_range) {
+        (Some(file_id), Some(text_range)) => match line_index {
+            Some(line_index) => Some(range(line_index, text_range)),
+            None => Some(range(&snap.file_line_index(file_id)?, text_range)),
+        },
+        _ => None,
+    };
+    Ok(lsp_ext::TestItem {
+        can_resolve_children: matches!(kind, lsp_ext::TestItemKind::Package),
+        id: test_item.id,
+        label: test_item.label,
+        kind,
+        parent: test_item.parent,
+        text_document: test_item
+            .file
+            .map(|file_id| lsp_types::TextDocumentIdentifier { uri: url(snap, file_id) }),
+        range,
+        runnable: test_item.runnable.map(|it| runnable(snap, it)).transpose()?,
+    })
+}
+
 pub(crate) fn code_lens(
     acc: &mut Vec<lsp_types::CodeLens>,
     snap: &GlobalStateSnapshot,
@@ -1499,6 +1613,7 @@ pub(crate) fn code_lens(
 }
 
 pub(crate) mod command {
+    use cfg::CfgAtom;
     use ide::{FileRange, NavigationTarget};
     use serde_json::to_value;
 
@@ -1554,6 +1669,35 @@ pub(crate) mod command {
         }
     }
 
+    pub(crate) fn enable_cfg(title: String, atoms: &[CfgAtom]) -> lsp_types::Command {
+        // The arguments mirror the `rust-analyzer.cargo.cfgs` setting, where flags have an empty
+        // value.
+        let cfgs = atoms
+            .iter()
+            .map(|atom| match atom {
+                CfgAtom::Flag(name) => (name.to_string(), String::new()),
+                CfgAtom::KeyValue { key, value } => (key.to_string(), value.to_string()),
+            })
+            .collect::<Vec<_>>();
+        lsp_types::Command {
+            title,
+            command: "rust-analyzer.enableCfg".into(),
+            arguments: Some(vec![to_value(cfgs).unwrap()]),
+        }
+    }
+
+    pub(crate) fn apply_ssr_rule(
+        title: String,
+        rule: String,
+        position: lsp_types::TextDocumentPositionParams,
+    ) -> lsp_types::Command {
+        lsp_types::Command {
+            title,
+            command: "rust-analyzer.applySsrRule".into(),
+            arguments: Some(vec![to_value(rule).unwrap(), to_value(position).unwrap()]),
+        }
+    }
+
     pub(crate) fn goto_location(
         snap: &GlobalStateSnapshot,
         nav: &NavigationTarget,
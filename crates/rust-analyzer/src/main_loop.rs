@@ -7,15 +7,17 @@ use std::{
 };
 
 use always_assert::always;
-use crossbeam_channel::{select, Receiver};
+use crossbeam_channel::{never, select, Receiver};
 use ide_db::base_db::{SourceDatabase, SourceDatabaseExt, VfsPath};
 use lsp_server::{Connection, Notification, Request};
 use lsp_types::notification::Notification as _;
 use stdx::thread::ThreadIntent;
+use triomphe::Arc;
 use vfs::FileId;
 
 use crate::{
     config::Config,
+    daemon,
     diagnostics::fetch_native_diagnostics,
     dispatch::{NotificationDispatcher, RequestDispatcher},
     global_state::{file_id_to_url, url_to_file_id, GlobalState},
@@ -25,6 +27,7 @@ use crate::{
     },
     lsp_ext,
     reload::{BuildDataProgress, ProcMacroProgress, ProjectWorkspaceProgress},
+    symbol_cache::{self, CachedSymbol},
 };
 
 pub fn main_loop(config: Config, connection: Connection) -> anyhow::Result<()> {
@@ -49,7 +52,20 @@ pub fn main_loop(config: Config, connection: Connection) -> anyhow::Result<()> {
         SetThreadPriority(thread, thread_priority_above_normal);
     }
 
-    GlobalState::new(connection.sender, config).run(connection.receiver)
+    GlobalState::new(connection.sender, config).run(connection.receiver, never())
+}
+
+/// Serves the clients received from `clients` at the same time, keeping the analysis of the
+/// workspace warm while they come and go, so that switching or restarting editors doesn't
+/// re-index it.
+///
+/// Returns once `clients` is disconnected and all clients have gone away.
+pub fn daemon_main_loop(clients: Receiver<(Config, Connection)>) -> anyhow::Result<()> {
+    let (connection, attached) = daemon::multiplex(clients);
+    let Ok(config) = attached.recv() else { return Ok(()) };
+    tracing::info!("initial config: {:#?}", config);
+
+    GlobalState::new(connection.sender, config).run(connection.receiver, attached)
 }
 
 enum Event {
@@ -58,6 +74,8 @@ enum Event {
     QueuedTask(QueuedTask),
     Vfs(vfs::loader::Message),
     Flycheck(flycheck::Message),
+    /// A new client became the primary one, see [`daemon_main_loop`].
+    Attach(Box<Config>),
 }
 
 impl fmt::Display for Event {
@@ -68,6 +86,7 @@ impl fmt::Display for Event {
             Event::Vfs(_) => write!(f, "Event::Vfs"),
             Event::Flycheck(_) => write!(f, "Event::Flycheck"),
             Event::QueuedTask(_) => write!(f, "Event::QueuedTask"),
+            Event::Attach(_) => write!(f, "Event::Attach"),
         }
     }
 }
@@ -76,19 +95,23 @@ impl fmt::Display for Event {
 pub(crate) enum QueuedTask {
     CheckIfIndexed(lsp_types::Url),
     CheckProcMacroSources(Vec<FileId>),
+    DiscoverTests(Vec<FileId>),
 }
 
 #[derive(Debug)]
 pub(crate) enum Task {
     Response(lsp_server::Response),
     ClientNotification(ext::UnindexedProjectParams),
+    DiscoverTest(ext::DiscoverTestResults),
     Retry(lsp_server::Request),
     Diagnostics(Vec<(FileId, Vec<lsp_types::Diagnostic>)>),
     PrimeCaches(PrimeCachesProgress),
+    LibrarySymbols(LibrarySymbolsProgress),
     FetchWorkspace(ProjectWorkspaceProgress),
     FetchBuildData(BuildDataProgress),
     LoadProcMacros(ProcMacroProgress),
     BuildDepsHaveChanged,
+    RustSrcInstalled(Result<(), String>),
 }
 
 #[derive(Debug)]
@@ -98,6 +121,15 @@ pub(crate) enum PrimeCachesProgress {
     End { cancelled: bool },
 }
 
+#[derive(Debug)]
+pub(crate) enum LibrarySymbolsProgress {
+    /// The symbols of the dependencies persisted by an earlier session.
+    Loaded(Arc<Vec<CachedSymbol>>),
+    Indexed {
+        cancelled: bool,
+    },
+}
+
 impl fmt::Debug for Event {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let debug_non_verbose = |not: &Notification, f: &mut fmt::Formatter<'_>| {
@@ -127,17 +159,18 @@ impl fmt::Debug for Event {
             Event::QueuedTask(it) => fmt::Debug::fmt(it, f),
             Event::Vfs(it) => fmt::Debug::fmt(it, f),
             Event::Flycheck(it) => fmt::Debug::fmt(it, f),
+            Event::Attach(_) => f.debug_struct("Attach").finish_non_exhaustive(),
         }
     }
 }
 
 impl GlobalState {
-    fn run(mut self, inbox: Receiver<lsp_server::Message>) -> anyhow::Result<()> {
-        self.update_status_or_notify();
-
-        if self.config.did_save_text_document_dynamic_registration() {
-            self.register_did_save_capability();
-        }
+    fn run(
+        mut self,
+        inbox: Receiver<lsp_server::Message>,
+        attached: Receiver<Config>,
+    ) -> anyhow::Result<()> {
+        self.register_capabilities();
 
         self.fetch_workspaces_queue.request_op("startup".to_owned(), false);
         if let Some((cause, force_crate_graph_reload)) =
@@ -146,7 +179,7 @@ impl GlobalState {
             self.fetch_workspaces(cause, force_crate_graph_reload);
         }
 
-        while let Some(event) = self.next_event(&inbox) {
+        while let Some(event) = self.next_event(&inbox, &attached) {
             if matches!(
                 &event,
                 Event::Lsp(lsp_server::Message::Notification(Notification { method, .. }))
@@ -160,6 +193,29 @@ impl GlobalState {
         anyhow::bail!("client exited without proper shutdown sequence")
     }
 
+    fn register_capabilities(&mut self) {
+        self.update_status_or_notify();
+
+        if self.config.did_save_text_document_dynamic_registration() {
+            self.register_did_save_capability();
+        }
+        self.update_pull_diagnostics_registration();
+    }
+
+    /// Switches to the configuration of the new primary client, which hasn't seen any of the
+    /// status, registrations or check diagnostics of the previous one.
+    fn attach(&mut self, config: Config) {
+        self.pull_diagnostics_registered = false;
+        self.last_reported_status = None;
+
+        // Reloads the workspace if the client configured it differently.
+        self.update_configuration(config);
+        self.register_capabilities();
+        if self.config.check_on_save() {
+            self.flycheck.iter().for_each(|flycheck| flycheck.restart_workspace(None));
+        }
+    }
+
     fn register_did_save_capability(&mut self) {
         let save_registration_options = lsp_types::TextDocumentSaveRegistrationOptions {
             include_text: Some(false),
@@ -180,6 +236,11 @@ impl GlobalState {
                         scheme: None,
                         pattern: Some("**/Cargo.lock".into()),
                     },
+                    lsp_types::DocumentFilter {
+                        language: None,
+                        scheme: None,
+                        pattern: Some("**/rust-toolchain{,.toml}".into()),
+                    },
                 ]),
             },
         };
@@ -195,11 +256,18 @@ impl GlobalState {
         );
     }
 
-    fn next_event(&self, inbox: &Receiver<lsp_server::Message>) -> Option<Event> {
+    fn next_event(
+        &self,
+        inbox: &Receiver<lsp_server::Message>,
+        attached: &Receiver<Config>,
+    ) -> Option<Event> {
         select! {
             recv(inbox) -> msg =>
                 msg.ok().map(Event::Lsp),
 
+            recv(attached) -> config =>
+                config.ok().map(|it| Event::Attach(Box::new(it))),
+
             recv(self.task_pool.receiver) -> task =>
                 Some(Event::Task(task.unwrap())),
 
@@ -322,6 +390,7 @@ impl GlobalState {
                     self.handle_flycheck_msg(message);
                 }
             }
+            Event::Attach(config) => self.attach(*config),
         }
         let event_handling_duration = loop_start.elapsed();
 
@@ -342,13 +411,24 @@ impl GlobalState {
                 if self.config.prefill_caches() {
                     self.prime_caches_queue.request_op("became quiescent".to_owned(), ());
                 }
+                if self.config.workspace_symbol_cache_path().is_some() {
+                    self.library_symbols_queue.request_op("became quiescent".to_owned(), ());
+                }
+                // The workspace changed, so let the client pull the diagnostics again.
+                if self.config.pull_diagnostics() && self.config.diagnostics_refresh() {
+                    self.send_request::<lsp_types::request::WorkspaceDiagnosticRefresh>(
+                        (),
+                        |_, _| (),
+                    );
+                }
             }
 
             let client_refresh = !was_quiescent || state_changed;
             if client_refresh {
                 // Refresh semantic tokens if the client supports it.
+                // The cached tokens are kept, so that the client's follow-up delta requests can
+                // be answered with just the changed tokens rather than all of them.
                 if self.config.semantic_tokens_refresh() {
-                    self.semantic_tokens_cache.lock().clear();
                     self.send_request::<lsp_types::request::SemanticTokensRefresh>((), |_, _| ());
                 }
 
@@ -365,7 +445,8 @@ impl GlobalState {
             }
 
             let update_diagnostics = (!was_quiescent || state_changed || memdocs_added_or_removed)
-                && self.config.publish_diagnostics();
+                && self.config.publish_diagnostics()
+                && !self.config.pull_diagnostics();
             if update_diagnostics {
                 self.update_diagnostics()
             }
@@ -430,6 +511,9 @@ impl GlobalState {
         if let Some((cause, ())) = self.prime_caches_queue.should_start_op() {
             self.prime_caches(cause);
         }
+        if let Some((cause, ())) = self.library_symbols_queue.should_start_op() {
+            self.index_library_symbols(cause);
+        }
 
         self.update_status_or_notify();
 
@@ -446,15 +530,20 @@ impl GlobalState {
     fn prime_caches(&mut self, cause: String) {
         tracing::debug!(%cause, "will prime caches");
         let num_worker_threads = self.config.prime_caches_num_threads();
+        let open_files = {
+            let vfs = &self.vfs.read().0;
+            self.mem_docs.iter().filter_map(|path| vfs.file_id(path)).collect::<Vec<_>>()
+        };
 
         self.task_pool.handle.spawn_with_sender(ThreadIntent::Worker, {
             let analysis = self.snapshot().analysis;
             move |sender| {
                 sender.send(Task::PrimeCaches(PrimeCachesProgress::Begin)).unwrap();
-                let res = analysis.parallel_prime_caches(num_worker_threads, |progress| {
-                    let report = PrimeCachesProgress::Report(progress);
-                    sender.send(Task::PrimeCaches(report)).unwrap();
-                });
+                let res =
+                    analysis.parallel_prime_caches(num_worker_threads, &open_files, |progress| {
+                        let report = PrimeCachesProgress::Report(progress);
+                        sender.send(Task::PrimeCaches(report)).unwrap();
+                    });
                 sender
                     .send(Task::PrimeCaches(PrimeCachesProgress::End { cancelled: res.is_err() }))
                     .unwrap();
@@ -462,12 +551,33 @@ impl GlobalState {
         });
     }
 
-    fn update_diagnostics(&mut self) {
+    fn index_library_symbols(&mut self, cause: String) {
+        let Some(dir) = self.config.workspace_symbol_cache_path() else {
+            return self.library_symbols_queue.op_completed(());
+        };
+        tracing::debug!(%cause, "will index library symbols");
+        let snapshot = self.snapshot();
+        self.task_pool.handle.spawn_with_sender(ThreadIntent::Worker, move |sender| {
+            symbol_cache::index_library_symbols(snapshot, dir, sender)
+        });
+    }
+
+    pub(crate) fn update_diagnostics(&mut self) {
         let db = self.analysis_host.raw_database();
+        let files_config = self.config.files();
         let subscriptions = self
             .mem_docs
             .iter()
+            .filter(|path| {
+                // Open `Cargo.toml`s are synced too, but aren't Rust files.
+                path.as_path().map_or(true, |it| {
+                    it.extension() == Some("rs".as_ref())
+                        && !files_config.is_generated(it)
+                        && !self.config.diagnostics_disabled_in(it)
+                })
+            })
             .map(|path| self.vfs.read().0.file_id(path).unwrap())
+            .filter(|file_id| !self.generated_files.contains(file_id))
             .filter(|&file_id| {
                 let source_root = db.file_source_root(file_id);
                 // Only publish diagnostics for files in the workspace, not from crates.io deps
@@ -522,6 +632,9 @@ impl GlobalState {
             Task::ClientNotification(params) => {
                 self.send_notification::<lsp_ext::UnindexedProject>(params)
             }
+            Task::DiscoverTest(params) => {
+                self.send_notification::<lsp_ext::DiscoveredTests>(params)
+            }
             // Only retry requests that haven't been cancelled. Otherwise we do unnecessary work.
             Task::Retry(req) if !self.is_completed(&req) => self.on_request(req),
             Task::Retry(_) => (),
@@ -543,6 +656,22 @@ impl GlobalState {
                 }
                 PrimeCachesProgress::End { .. } => prime_caches_progress.push(progress),
             },
+            Task::LibrarySymbols(progress) => match progress {
+                LibrarySymbolsProgress::Loaded(symbols) => {
+                    self.library_symbols_cache = Some(symbols)
+                }
+                LibrarySymbolsProgress::Indexed { cancelled } => {
+                    self.library_symbols_queue.op_completed(());
+                    if cancelled {
+                        self.library_symbols_queue
+                            .request_op("restart after cancellation".to_owned(), ());
+                    } else {
+                        // The symbol index is up to date now, and may have diverged from the
+                        // persisted symbols while it was built.
+                        self.library_symbols_cache = None;
+                    }
+                }
+            },
             Task::FetchWorkspace(progress) => {
                 let (state, msg) = match progress {
                     ProjectWorkspaceProgress::Begin => (Progress::Begin, None),
@@ -598,6 +727,13 @@ impl GlobalState {
                 }
             }
             Task::BuildDepsHaveChanged => self.build_deps_changed = true,
+            Task::RustSrcInstalled(Ok(())) => {
+                self.fetch_workspaces_queue.request_op("installed rust-src".to_owned(), false)
+            }
+            Task::RustSrcInstalled(Err(e)) => self.show_and_log_error(
+                "Failed to install the `rust-src` component".to_owned(),
+                Some(e),
+            ),
         }
     }
 
@@ -695,12 +831,25 @@ impl GlobalState {
                     }
                 });
             }
+            QueuedTask::DiscoverTests(modified_rust_files) => {
+                let snap = self.snapshot();
+                self.task_pool.handle.spawn_with_sender(ThreadIntent::Worker, move |sender| {
+                    let _p = tracing::span!(tracing::Level::INFO, "GlobalState::discover_tests")
+                        .entered();
+                    for file_id in modified_rust_files {
+                        match crate::handlers::request::discover_tests_in_file(&snap, file_id) {
+                            Ok(tests) => sender.send(Task::DiscoverTest(tests)).unwrap(),
+                            Err(e) => tracing::debug!(?file_id, "failed to discover tests: {e}"),
+                        }
+                    }
+                });
+            }
         }
     }
 
     fn handle_flycheck_msg(&mut self, message: flycheck::Message) {
         match message {
-            flycheck::Message::AddDiagnostic { id, workspace_root, diagnostic } => {
+            flycheck::Message::AddDiagnostic { id, workspace_root, diagnostic, package_id } => {
                 let snap = self.snapshot();
                 let diagnostics = crate::diagnostics::to_proto::map_rust_diagnostic_to_lsp(
                     &self.config.diagnostics_map(),
@@ -708,10 +857,18 @@ impl GlobalState {
                     &workspace_root,
                     &snap,
                 );
+                let files_config = self.config.files();
                 for diag in diagnostics {
+                    if from_proto::abs_path(&diag.url)
+                        .map_or(false, |it| files_config.is_generated(&it))
+                    {
+                        continue;
+                    }
                     match url_to_file_id(&self.vfs.read().0, &diag.url) {
+                        Ok(file_id) if self.generated_files.contains(&file_id) => (),
                         Ok(file_id) => self.diagnostics.add_check_diagnostic(
                             id,
+                            package_id.clone(),
                             file_id,
                             diag.diagnostic,
                             diag.fix,
@@ -728,12 +885,13 @@ impl GlobalState {
 
             flycheck::Message::Progress { id, progress } => {
                 let (state, message) = match progress {
-                    flycheck::Progress::DidStart => {
-                        self.diagnostics.clear_check(id);
+                    flycheck::Progress::DidStart { package_id } => {
+                        self.diagnostics.start_check(id, package_id);
                         (Progress::Begin, None)
                     }
                     flycheck::Progress::DidCheckCrate(target) => (Progress::Report, Some(target)),
                     flycheck::Progress::DidCancel => {
+                        self.diagnostics.cancel_check(id);
                         self.last_flycheck_error = None;
                         (Progress::End, None)
                     }
@@ -743,6 +901,7 @@ impl GlobalState {
                         return;
                     }
                     flycheck::Progress::DidFinish(result) => {
+                        self.diagnostics.finish_check(id);
                         self.last_flycheck_error =
                             result.err().map(|err| format!("cargo check failed to start: {err}"));
                         (Progress::End, None)
@@ -775,6 +934,8 @@ impl GlobalState {
 
     /// Handles a request.
     fn on_request(&mut self, req: Request) {
+        let completion_budget = self.config.completion_soft_budget();
+        let hover_budget = self.config.hover_soft_budget();
         let mut dispatcher = RequestDispatcher { req: Some(req), global_state: self };
         dispatcher.on_sync_mut::<lsp_types::request::Shutdown>(|s, ()| {
             s.shutdown_requested = true;
@@ -820,7 +981,12 @@ impl GlobalState {
             // analysis on the main thread because that would block other
             // requests. Instead, we run these request handlers on higher priority
             // threads in the threadpool.
-            .on_latency_sensitive::<lsp_request::Completion>(handlers::handle_completion)
+            .on_with_budget::<lsp_request::Completion>(
+                ThreadIntent::LatencySensitive,
+                completion_budget,
+                handlers::handle_completion,
+                handlers::handle_completion_partial,
+            )
             .on_latency_sensitive::<lsp_request::ResolveCompletionItem>(
                 handlers::handle_completion_resolve,
             )
@@ -843,13 +1009,30 @@ impl GlobalState {
             .on::<lsp_ext::ViewFileText>(handlers::handle_view_file_text)
             .on::<lsp_ext::ViewCrateGraph>(handlers::handle_view_crate_graph)
             .on::<lsp_ext::ViewItemTree>(handlers::handle_view_item_tree)
+            .on::<lsp_ext::CrateFeatures>(handlers::handle_crate_features)
+            .on::<lsp_ext::ImportsForPaste>(handlers::handle_imports_for_paste)
+            .on_cancellable::<lsp_ext::DebugLaunchInfo>(handlers::handle_debug_launch_info)
+            .on::<lsp_ext::DiscoverTests>(handlers::handle_discover_tests)
+            .on::<lsp_ext::ExplainExpression>(handlers::handle_explain_expression)
+            .on::<lsp_ext::Tasks>(handlers::handle_tasks)
+            .on::<lsp_ext::CargoTomlCompletion>(handlers::handle_cargo_toml_completion)
+            .on::<lsp_ext::CargoTomlDiagnostics>(handlers::handle_cargo_toml_diagnostics)
             .on::<lsp_ext::ExpandMacro>(handlers::handle_expand_macro)
+            .on::<lsp_ext::ExpandMacroDiagnostic>(handlers::handle_expand_macro_diagnostic)
             .on::<lsp_ext::ParentModule>(handlers::handle_parent_module)
             .on::<lsp_ext::Runnables>(handlers::handle_runnables)
             .on::<lsp_ext::RelatedTests>(handlers::handle_related_tests)
             .on::<lsp_ext::CodeActionRequest>(handlers::handle_code_action)
             .on::<lsp_ext::CodeActionResolveRequest>(handlers::handle_code_action_resolve)
-            .on::<lsp_ext::HoverRequest>(handlers::handle_hover)
+            .on_with_budget::<lsp_ext::HoverRequest>(
+                ThreadIntent::Worker,
+                hover_budget,
+                handlers::handle_hover,
+                handlers::handle_hover_partial,
+            )
+            .on::<lsp_types::request::DocumentDiagnosticRequest>(
+                handlers::handle_document_diagnostics,
+            )
             .on::<lsp_ext::ExternalDocs>(handlers::handle_open_docs)
             .on::<lsp_ext::OpenCargoToml>(handlers::handle_open_cargo_toml)
             .on::<lsp_ext::MoveItem>(handlers::handle_move_item)
@@ -874,6 +1057,7 @@ impl GlobalState {
             .on::<lsp_request::CallHierarchyOutgoingCalls>(handlers::handle_call_hierarchy_outgoing)
             .on::<lsp_request::WillRenameFiles>(handlers::handle_will_rename_files)
             .on::<lsp_ext::Ssr>(handlers::handle_ssr)
+            .on::<lsp_ext::SearchByType>(handlers::handle_search_by_type)
             .on::<lsp_ext::ViewRecursiveMemoryLayout>(handlers::handle_view_recursive_memory_layout)
             .finish();
     }
@@ -19,13 +19,13 @@ use flycheck::{FlycheckConfig, FlycheckHandle};
 use hir::{db::DefDatabase, Change, ProcMacros};
 use ide::CrateId;
 use ide_db::{
-    base_db::{salsa::Durability, CrateGraph, ProcMacroPaths, Version},
+    base_db::{salsa::Durability, CrateGraph, ProcMacroPaths, SourceDatabase, Version},
     FxHashMap,
 };
 use itertools::Itertools;
 use load_cargo::{load_proc_macro, ProjectFolders};
 use proc_macro_api::ProcMacroServer;
-use project_model::{ProjectWorkspace, WorkspaceBuildScripts};
+use project_model::{CratesIndex, ProjectWorkspace, WorkspaceBuildScripts};
 use rustc_hash::FxHashSet;
 use stdx::{format_to, thread::ThreadIntent};
 use triomphe::Arc;
@@ -33,7 +33,7 @@ use vfs::{AbsPath, AbsPathBuf, ChangeKind};
 
 use crate::{
     config::{Config, FilesWatcher, LinkedProject},
-    global_state::GlobalState,
+    global_state::{GlobalState, UnindexedReason},
     lsp_ext,
     main_loop::Task,
     op_queue::Cause,
@@ -70,6 +70,21 @@ impl GlobalState {
             || self.vfs_progress_n_done < self.vfs_progress_n_total)
     }
 
+    /// Whether `path` is a Rust file outside of the roots of all loaded workspaces, and should thus
+    /// be analyzed as a detached file.
+    pub(crate) fn is_outside_of_workspaces(&self, path: &AbsPath) -> bool {
+        if path.extension().unwrap_or_default() != "rs"
+            || self.fetch_workspaces_queue.op_in_progress()
+            || self.fetch_workspaces_queue.last_op_result().is_none()
+        {
+            return false;
+        }
+        self.workspaces
+            .iter()
+            .flat_map(|ws| ws.to_roots())
+            .all(|root| !root.include.iter().any(|it| path.starts_with(it)))
+    }
+
     pub(crate) fn update_configuration(&mut self, config: Config) {
         let _p =
             tracing::span!(tracing::Level::INFO, "GlobalState::update_configuration").entered();
@@ -83,12 +98,51 @@ impl GlobalState {
             );
         }
         if self.config.linked_or_discovered_projects() != old_config.linked_or_discovered_projects()
+            || self.config.discover_command() != old_config.discover_command()
         {
             self.fetch_workspaces_queue.request_op("discovered projects changed".to_owned(), false)
+        } else if self.config.cargo() != old_config.cargo() {
+            self.fetch_workspaces_queue.request_op("cargo configuration changed".to_owned(), false)
         } else if self.config.flycheck() != old_config.flycheck() {
             self.reload_flycheck();
         }
 
+        if self.config.pull_diagnostics() != old_config.pull_diagnostics() {
+            self.update_pull_diagnostics_registration();
+        }
+
+        if self.config.diagnostics_disabled_in_globs() != old_config.diagnostics_disabled_in_globs()
+        {
+            if self.config.pull_diagnostics() {
+                if self.config.diagnostics_refresh() {
+                    self.send_request::<lsp_types::request::WorkspaceDiagnosticRefresh>(
+                        (),
+                        |_, _| (),
+                    );
+                }
+            } else if self.config.publish_diagnostics() {
+                // Drop the diagnostics of the files that are disabled now, the others are
+                // recomputed to pick up the files that aren't anymore.
+                let disabled = {
+                    let vfs = &self.vfs.read().0;
+                    self.diagnostics
+                        .native
+                        .keys()
+                        .copied()
+                        .filter(|&file_id| {
+                            vfs.file_path(file_id)
+                                .as_path()
+                                .map_or(false, |it| self.config.diagnostics_disabled_in(it))
+                        })
+                        .collect::<Vec<_>>()
+                };
+                for file_id in disabled {
+                    self.diagnostics.clear_native_for(file_id);
+                }
+                self.update_diagnostics();
+            }
+        }
+
         if self.analysis_host.raw_database().expand_proc_attr_macros()
             != self.config.expand_proc_attr_macros()
         {
@@ -99,6 +153,61 @@ impl GlobalState {
         }
     }
 
+    /// Registers the `textDocument/diagnostic` request with the client if pull diagnostics are
+    /// enabled, and unregisters it otherwise.
+    pub(crate) fn update_pull_diagnostics_registration(&mut self) {
+        if !self.config.diagnostic_dynamic_registration() {
+            return;
+        }
+        let pull = self.config.pull_diagnostics();
+        if pull == self.pull_diagnostics_registered {
+            return;
+        }
+        self.pull_diagnostics_registered = pull;
+
+        let method = "textDocument/diagnostic".to_owned();
+        if pull {
+            let registration_options = lsp_types::DiagnosticRegistrationOptions {
+                text_document_registration_options: lsp_types::TextDocumentRegistrationOptions {
+                    document_selector: Some(vec![lsp_types::DocumentFilter {
+                        language: None,
+                        scheme: None,
+                        pattern: Some("**/*.rs".into()),
+                    }]),
+                },
+                diagnostic_options: crate::caps::diagnostic_options(),
+                static_registration_options: lsp_types::StaticRegistrationOptions { id: None },
+            };
+            let registration = lsp_types::Registration {
+                id: method.clone(),
+                method,
+                register_options: Some(serde_json::to_value(registration_options).unwrap()),
+            };
+            self.send_request::<lsp_types::request::RegisterCapability>(
+                lsp_types::RegistrationParams { registrations: vec![registration] },
+                |_, _| (),
+            );
+
+            // The published diagnostics would show up next to the pulled ones.
+            let published = self.diagnostics.native.keys().copied().collect::<Vec<_>>();
+            for file_id in published {
+                self.diagnostics.clear_native_for(file_id);
+            }
+            if self.config.diagnostics_refresh() {
+                self.send_request::<lsp_types::request::WorkspaceDiagnosticRefresh>((), |_, _| ());
+            }
+        } else {
+            let unregistration = lsp_types::Unregistration { id: method.clone(), method };
+            self.send_request::<lsp_types::request::UnregisterCapability>(
+                lsp_types::UnregistrationParams { unregisterations: vec![unregistration] },
+                |_, _| (),
+            );
+            if self.config.publish_diagnostics() {
+                self.update_diagnostics();
+            }
+        }
+    }
+
     pub(crate) fn current_status(&self) -> lsp_ext::ServerStatusParams {
         let mut status = lsp_ext::ServerStatusParams {
             health: lsp_ext::Health::Ok,
@@ -134,6 +243,7 @@ impl GlobalState {
             message.push_str("Auto-reloading is disabled and the workspace has changed, a manual workspace reload is required.\n\n");
         }
         if self.config.linked_or_discovered_projects().is_empty()
+            && self.config.discover_command().is_none()
             && self.config.detached_files().is_empty()
             && self.config.notifications().cargo_toml_not_found
         {
@@ -141,6 +251,24 @@ impl GlobalState {
             message.push_str("Failed to discover workspace.\n");
             message.push_str("Consider adding the `Cargo.toml` of the workspace to the [`linkedProjects`](https://rust-analyzer.github.io/manual.html#rust-analyzer.linkedProjects) setting.\n\n");
         }
+        if !self.detached_files.is_empty() {
+            message.push_str("Some open files don't belong to any workspace, they are analyzed on their own with only the standard library available.\n\n");
+        }
+        if !self.unindexed_files.is_empty() {
+            let vfs = &self.vfs.read().0;
+            message.push_str("Some files are not analyzed:\n");
+            for (&file_id, reason) in self.unindexed_files.iter().take(10) {
+                let reason = match reason {
+                    UnindexedReason::NotUtf8 => "not valid UTF-8",
+                    UnindexedReason::TooLarge => "larger than `rust-analyzer.files.maxSize`",
+                };
+                format_to!(message, "- {} ({reason})\n", vfs.file_path(file_id));
+            }
+            if let Some(more) = self.unindexed_files.len().checked_sub(10).filter(|&it| it > 0) {
+                format_to!(message, "- and {more} more\n");
+            }
+            message.push('\n');
+        }
         if let Some(err) = &self.config_errors {
             status.health = lsp_ext::Health::Warning;
             format_to!(message, "{err}\n");
@@ -207,7 +335,16 @@ impl GlobalState {
 
         self.task_pool.handle.spawn_with_sender(ThreadIntent::Worker, {
             let linked_projects = self.config.linked_or_discovered_projects();
-            let detached_files = self.config.detached_files().to_vec();
+            let detached_files = self
+                .config
+                .detached_files()
+                .iter()
+                .chain(&self.detached_files)
+                .unique()
+                .cloned()
+                .collect::<Vec<_>>();
+            let discover_command = self.config.discover_command().map(<[_]>::to_vec);
+            let root_path = self.config.root_path().clone();
             let cargo_config = self.config.cargo();
 
             move |sender| {
@@ -241,6 +378,14 @@ impl GlobalState {
                         }
                     })
                     .collect::<Vec<_>>();
+                if let Some(command) = &discover_command {
+                    progress("running the project discovery command".to_owned());
+                    workspaces.push(project_model::ProjectWorkspace::load_discovered(
+                        command,
+                        &root_path,
+                        &cargo_config,
+                    ));
+                }
 
                 let mut i = 0;
                 while i < workspaces.len() {
@@ -362,6 +507,55 @@ impl GlobalState {
         self.analysis_host.apply_change(change);
     }
 
+    /// Offers to install the `rust-src` component for the rustup toolchains that miss it, the
+    /// standard library can't be analyzed without it.
+    fn report_missing_rust_src(&mut self) {
+        let toolchains = toolchains_missing_rust_src(&self.workspaces);
+        if toolchains.is_empty() {
+            return;
+        }
+        let toolchains = toolchains.iter().map(|it| format!("`{it}`")).join(", ");
+        self.send_request::<lsp_types::request::ShowMessageRequest>(
+            lsp_types::ShowMessageRequestParams {
+                typ: lsp_types::MessageType::WARNING,
+                message: format!(
+                    "The `rust-src` component is not installed for {toolchains}, \
+                     the standard library can't be analyzed without it."
+                ),
+                actions: Some(vec![lsp_types::MessageActionItem {
+                    title: "Install rust-src".to_owned(),
+                    properties: Default::default(),
+                }]),
+            },
+            |this, resp| {
+                let lsp_server::Response { error: None, result: Some(result), .. } = resp else {
+                    return;
+                };
+                let Ok(Some(_)) = crate::from_json::<
+                    <lsp_types::request::ShowMessageRequest as lsp_types::request::Request>::Result,
+                >(
+                    lsp_types::request::ShowMessageRequest::METHOD, &result
+                ) else {
+                    return;
+                };
+                let toolchains = toolchains_missing_rust_src(&this.workspaces);
+                this.task_pool.handle.spawn_with_sender(ThreadIntent::Worker, move |sender| {
+                    let result = toolchains.iter().try_for_each(|toolchain| {
+                        let mut cmd = std::process::Command::new(toolchain::rustup());
+                        cmd.args(["component", "add", "rust-src", "--toolchain", toolchain]);
+                        tracing::info!("installing rust-src by {:?}", cmd);
+                        let output = cmd.output().map_err(|e| format!("{cmd:?} failed: {e}"))?;
+                        if !output.status.success() {
+                            return Err(String::from_utf8_lossy(&output.stderr).into_owned());
+                        }
+                        Ok(())
+                    });
+                    sender.send(Task::RustSrcInstalled(result)).unwrap();
+                });
+            },
+        );
+    }
+
     pub(crate) fn switch_workspaces(&mut self, cause: Cause) {
         let _p = tracing::span!(tracing::Level::INFO, "GlobalState::switch_workspaces").entered();
         tracing::info!(%cause, "will switch workspaces");
@@ -423,11 +617,14 @@ impl GlobalState {
             // we don't care about build-script results, they are stale.
             // FIXME: can we abort the build scripts here if they are already running?
             self.workspaces = Arc::new(workspaces);
+            // Fetching the metadata may have populated the index cache of a new registry.
+            self.crates_index = Arc::new(CratesIndex::discover());
 
             if self.config.run_build_scripts() {
                 self.build_deps_changed = false;
                 self.fetch_build_data_queue.request_op("workspace updated".to_owned(), ());
             }
+            self.report_missing_rust_src();
         }
 
         if let FilesWatcher::Client = self.config.files().watcher {
@@ -443,6 +640,8 @@ impl GlobalState {
                                 format!("{it}/**/*.rs"),
                                 format!("{it}/**/Cargo.toml"),
                                 format!("{it}/**/Cargo.lock"),
+                                format!("{it}/rust-toolchain"),
+                                format!("{it}/rust-toolchain.toml"),
                             ]
                         })
                     })
@@ -513,6 +712,8 @@ impl GlobalState {
             load: project_folders.load,
             watch,
             version: self.vfs_config_version,
+            exclude_globs: files_config.exclude_globs,
+            respect_gitignore: files_config.respect_gitignore,
         });
         self.source_root_config = project_folders.source_root_config;
 
@@ -548,17 +749,26 @@ impl GlobalState {
 
             ws_to_crate_graph(&self.workspaces, self.config.extra_env(), load)
         };
+        // Manifest edits often leave the crate graph as it was, in which case setting it again would
+        // needlessly invalidate all analysis results.
+        let crate_graph_changed = *self.analysis_host.raw_database().crate_graph() != crate_graph;
         let mut change = Change::new();
         if self.config.expand_proc_macros() {
-            change.set_proc_macros(
-                crate_graph
-                    .iter()
-                    .map(|id| (id, Err("Proc-macros have not been built yet".to_owned())))
-                    .collect(),
-            );
+            // The crate ids of an unchanged graph are still valid, so the loaded proc-macros can be
+            // used until their replacements are.
+            if crate_graph_changed {
+                change.set_proc_macros(
+                    crate_graph
+                        .iter()
+                        .map(|id| (id, Err("Proc-macros have not been built yet".to_owned())))
+                        .collect(),
+                );
+            }
             self.fetch_proc_macros_queue.request_op(cause, proc_macro_paths);
         }
-        change.set_crate_graph(crate_graph);
+        if crate_graph_changed {
+            change.set_crate_graph(crate_graph);
+        }
         change.set_target_data_layouts(layouts);
         change.set_toolchains(toolchains);
         self.analysis_host.apply_change(change);
@@ -639,6 +849,7 @@ impl GlobalState {
                             id,
                             cargo.workspace_root(),
                             sysroot.as_ref().ok().map(|sysroot| sysroot.root().to_owned()),
+                            Some(cargo.metadata()),
                         )),
                         ProjectWorkspace::Json { project, sysroot, .. } => {
                             // Enable flychecks for json projects if a custom flycheck command was supplied
@@ -648,18 +859,41 @@ impl GlobalState {
                                     id,
                                     project.path(),
                                     sysroot.as_ref().ok().map(|sysroot| sysroot.root().to_owned()),
+                                    None,
                                 )),
                                 _ => None,
                             }
                         }
                         ProjectWorkspace::DetachedFiles { .. } => None,
                     })
-                    .map(|(id, root, sysroot_root)| {
+                    .map(|(id, root, sysroot_root, metadata)| {
                         let sender = sender.clone();
+                        let mut config = config.clone();
+                        // Workspaces may override the check command and features for themselves.
+                        if let (
+                            FlycheckConfig::CargoCommand {
+                                command,
+                                features,
+                                all_features,
+                                no_default_features,
+                                ..
+                            },
+                            Some(metadata),
+                        ) = (&mut config, metadata)
+                        {
+                            if let Some(check_command) = &metadata.check_command {
+                                command.clone_from(check_command);
+                            }
+                            if let Some(ws_features) = &metadata.features {
+                                features.clone_from(ws_features);
+                                *all_features = false;
+                                *no_default_features = false;
+                            }
+                        }
                         FlycheckHandle::spawn(
                             id,
                             Box::new(move |msg| sender.send(msg).unwrap()),
-                            config.clone(),
+                            config,
                             sysroot_root,
                             root.to_path_buf(),
                         )
@@ -734,6 +968,21 @@ pub fn ws_to_crate_graph(
     (crate_graph, proc_macro_paths, layouts, toolchains)
 }
 
+/// The rustup toolchains used by `workspaces` whose `rust-src` component is missing.
+fn toolchains_missing_rust_src(workspaces: &[ProjectWorkspace]) -> Vec<String> {
+    workspaces
+        .iter()
+        .filter_map(|ws| {
+            let (ProjectWorkspace::Cargo { sysroot, .. }
+            | ProjectWorkspace::Json { sysroot, .. }
+            | ProjectWorkspace::DetachedFiles { sysroot, .. }) = ws;
+            let sysroot = sysroot.as_ref().ok().filter(|it| it.is_missing_rust_src())?;
+            sysroot.rustup_toolchain().map(ToOwned::to_owned)
+        })
+        .unique()
+        .collect()
+}
+
 pub(crate) fn should_refresh_for_change(path: &AbsPath, change_kind: ChangeKind) -> bool {
     const IMPLICIT_TARGET_FILES: &[&str] = &["build.rs", "src/main.rs", "src/lib.rs"];
     const IMPLICIT_TARGET_DIRS: &[&str] = &["src/bin", "examples", "tests", "benches"];
@@ -743,7 +992,7 @@ pub(crate) fn should_refresh_for_change(path: &AbsPath, change_kind: ChangeKind)
         None => return false,
     };
 
-    if let "Cargo.toml" | "Cargo.lock" = file_name {
+    if let "Cargo.toml" | "Cargo.lock" | "rust-toolchain" | "rust-toolchain.toml" = file_name {
         return true;
     }
     if change_kind == ChangeKind::Modify {
@@ -0,0 +1,204 @@
+//! Persists the workspace symbols of dependencies between sessions.
+//!
+//! Indexing the symbols of all dependencies takes a while after startup, and salsa keeps its
+//! memos in memory only. So once the dependencies are indexed, their symbols are written to
+//! `workspaceSymbol.cachePath`, keyed by the contents of the library source roots. On the next
+//! startup, workspace symbol searches which include dependencies are answered from these files
+//! until the dependencies are indexed again.
+//!
+//! Only the symbol index is persisted. Item trees and def maps are salsa memos without a stable
+//! format, so they are recomputed on every startup (see "Serialization" in
+//! `docs/dev/architecture.md`).
+use std::{
+    fs,
+    hash::{Hash, Hasher},
+    io,
+};
+
+use crossbeam_channel::Sender;
+use ide::{Cancellable, FileId, LibrarySymbol, Query, SourceRootId};
+use ide_db::line_index::WideEncoding;
+use rustc_hash::FxHasher;
+use serde::{Deserialize, Serialize};
+use triomphe::Arc;
+use vfs::{AbsPath, AbsPathBuf};
+
+use crate::{
+    global_state::GlobalStateSnapshot,
+    line_index::PositionEncoding,
+    lsp::to_proto,
+    main_loop::{LibrarySymbolsProgress, Task},
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct CachedSymbol {
+    /// The name queries are matched against, the alias for aliased symbols.
+    name: String,
+    is_type: bool,
+    is_assoc: bool,
+    is_hidden: bool,
+    symbol: lsp_types::WorkspaceSymbol,
+}
+
+impl CachedSymbol {
+    fn new(snap: &GlobalStateSnapshot, library_symbol: LibrarySymbol) -> Cancellable<CachedSymbol> {
+        let LibrarySymbol { nav, is_type, is_assoc, is_hidden } = library_symbol;
+        let name = nav.alias.as_ref().unwrap_or(&nav.name).to_string();
+        let symbol = to_proto::workspace_symbol(snap, nav)?;
+        Ok(CachedSymbol { name, is_type, is_assoc, is_hidden, symbol })
+    }
+}
+
+pub(crate) fn search(
+    symbols: &[CachedSymbol],
+    query: &Query,
+    limit: usize,
+) -> Vec<lsp_types::WorkspaceSymbol> {
+    symbols
+        .iter()
+        .filter(|it| query.matches(&it.name, it.is_type, it.is_assoc, it.is_hidden))
+        .take(limit)
+        .map(|it| it.symbol.clone())
+        .collect()
+}
+
+/// Loads the persisted symbols of all library source roots, then indexes the roots and persists
+/// the symbols of those that were missing.
+pub(crate) fn index_library_symbols(
+    snap: GlobalStateSnapshot,
+    dir: AbsPathBuf,
+    sender: Sender<Task>,
+) {
+    let res = (|| -> Cancellable<()> {
+        let roots = snap
+            .analysis
+            .library_roots()?
+            .into_iter()
+            .map(|(root, files)| Ok((root, cache_key(&snap, &files)?)))
+            .collect::<Cancellable<Vec<(SourceRootId, u64)>>>()?;
+
+        let mut loaded = Vec::new();
+        let mut missing = Vec::new();
+        for &(root, key) in &roots {
+            match load(&dir, key) {
+                Some(symbols) => loaded.extend(symbols),
+                None => missing.push((root, key)),
+            }
+        }
+        if missing.is_empty() {
+            let progress = LibrarySymbolsProgress::Loaded(Arc::new(loaded));
+            sender.send(Task::LibrarySymbols(progress)).unwrap();
+        }
+
+        // Index all roots, so that the searches which follow don't have to.
+        for (root, key) in roots {
+            let symbols = snap
+                .analysis
+                .library_symbols(root)?
+                .into_iter()
+                .map(|it| CachedSymbol::new(&snap, it))
+                .collect::<Cancellable<Vec<_>>>()?;
+            if missing.contains(&(root, key)) {
+                if let Err(e) = store(&dir, key, &symbols) {
+                    tracing::warn!("failed to persist library symbols: {e}");
+                }
+            }
+        }
+        Ok(())
+    })();
+    let progress = LibrarySymbolsProgress::Indexed { cancelled: res.is_err() };
+    sender.send(Task::LibrarySymbols(progress)).unwrap();
+}
+
+/// Hashes everything the persisted symbols of a source root depend on.
+fn cache_key(snap: &GlobalStateSnapshot, files: &[FileId]) -> Cancellable<u64> {
+    let mut hasher = FxHasher::default();
+    crate::version().to_string().hash(&mut hasher);
+    let encoding = match snap.config.position_encoding() {
+        PositionEncoding::Utf8 => "utf-8",
+        PositionEncoding::Wide(WideEncoding::Utf16) => "utf-16",
+        PositionEncoding::Wide(WideEncoding::Utf32) => "utf-32",
+    };
+    encoding.hash(&mut hasher);
+    let mut files =
+        files.iter().map(|&file_id| (snap.file_id_to_url(file_id), file_id)).collect::<Vec<_>>();
+    files.sort();
+    for (url, file_id) in files {
+        url.as_str().hash(&mut hasher);
+        snap.analysis.file_text(file_id)?.hash(&mut hasher);
+    }
+    Ok(hasher.finish())
+}
+
+fn file_name(key: u64) -> String {
+    format!("symbols-{key:016x}.json")
+}
+
+fn load(dir: &AbsPath, key: u64) -> Option<Vec<CachedSymbol>> {
+    let text = fs::read_to_string(dir.join(file_name(key))).ok()?;
+    serde_json::from_str(&text).ok()
+}
+
+fn store(dir: &AbsPath, key: u64, symbols: &[CachedSymbol]) -> io::Result<()> {
+    fs::create_dir_all(dir)?;
+    // Write to a temporary file first, so that concurrent sessions never read half a file.
+    let tmp = dir.join(format!("{}.{}", file_name(key), std::process::id()));
+    fs::write(&tmp, serde_json::to_vec(symbols)?)?;
+    fs::rename(tmp, dir.join(file_name(key)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn symbol(name: &str, is_type: bool, is_hidden: bool) -> CachedSymbol {
+        CachedSymbol {
+            name: name.to_owned(),
+            is_type,
+            is_assoc: false,
+            is_hidden,
+            symbol: lsp_types::WorkspaceSymbol {
+                name: name.to_owned(),
+                kind: lsp_types::SymbolKind::STRUCT,
+                tags: None,
+                container_name: None,
+                location: lsp_types::OneOf::Right(lsp_types::WorkspaceLocation {
+                    uri: "file:///lib.rs".parse().unwrap(),
+                }),
+                data: None,
+            },
+        }
+    }
+
+    #[test]
+    fn search_filters_like_the_symbol_index() {
+        let symbols = [
+            symbol("HashMap", true, false),
+            symbol("hash_map", false, false),
+            symbol("RawHashMap", true, true),
+        ];
+        let names = |query: &Query| {
+            search(&symbols, query, 10).into_iter().map(|it| it.name).collect::<Vec<_>>()
+        };
+
+        let mut query = Query::new("hm".to_owned());
+        query.libs();
+        assert_eq!(names(&query), ["HashMap", "hash_map", "RawHashMap"]);
+        query.exclude_hidden();
+        assert_eq!(names(&query), ["HashMap", "hash_map"]);
+        query.only_types();
+        assert_eq!(names(&query), ["HashMap"]);
+    }
+
+    #[test]
+    fn cached_symbols_round_trip() {
+        let dir = AbsPathBuf::assert(
+            std::env::temp_dir().join(format!("ra-symbol-cache-{}", std::process::id())),
+        );
+        store(&dir, 1, &[symbol("HashMap", true, false)]).unwrap();
+        let loaded = load(&dir, 1).unwrap();
+        assert_eq!(loaded[0].name, "HashMap");
+        assert!(load(&dir, 2).is_none());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -20,10 +20,11 @@ mod tidy;
 use std::{collections::HashMap, path::PathBuf, time::Instant};
 
 use lsp_types::{
-    notification::DidOpenTextDocument,
+    notification::{DidChangeTextDocument, DidOpenTextDocument},
     request::{
         CodeActionRequest, Completion, Formatting, GotoTypeDefinition, HoverRequest,
-        WillRenameFiles, WorkspaceSymbolRequest,
+        SemanticTokensFullDeltaRequest, SemanticTokensFullRequest, WillRenameFiles,
+        WorkspaceSymbolRequest,
     },
     CodeActionContext, CodeActionParams, CompletionParams, DidOpenTextDocumentParams,
     DocumentFormattingParams, FileRename, FormattingOptions, GotoDefinitionParams, HoverParams,
@@ -1231,3 +1232,139 @@ version = "0.0.0"
 
     server.request::<WorkspaceSymbolRequest>(Default::default(), json!([]));
 }
+
+#[test]
+fn daemon_keeps_the_workspace_between_clients() {
+    if skip_slow_tests() {
+        return;
+    }
+
+    let daemon = Project::with_fixture(
+        r#"
+//- /Cargo.toml
+[package]
+name = "foo"
+version = "0.0.0"
+
+//- /src/lib.rs
+pub struct Foo;
+"#,
+    )
+    .daemon();
+
+    let symbols = |server: &support::Server| {
+        server.send_request::<WorkspaceSymbolRequest>(lsp_types::WorkspaceSymbolParams {
+            query: "Foo".to_owned(),
+            ..Default::default()
+        })
+    };
+
+    let first = daemon.attach().wait_until_workspace_is_loaded();
+    let res = symbols(&first);
+    assert_eq!(res.as_array().map(Vec::len), Some(1), "{res}");
+    drop(first);
+
+    let second = daemon.attach().wait_until_workspace_is_loaded();
+    assert_eq!(symbols(&second), res);
+}
+
+#[test]
+fn semantic_tokens_delta_after_edit() {
+    if skip_slow_tests() {
+        return;
+    }
+
+    let server = Project::with_fixture(
+        r#"
+//- /Cargo.toml
+[package]
+name = "foo"
+version = "0.0.0"
+
+//- /src/lib.rs
+pub fn foo() {}
+"#,
+    )
+    .server()
+    .wait_until_workspace_is_loaded();
+
+    let doc = server.doc_id("src/lib.rs");
+    server.notification::<DidOpenTextDocument>(DidOpenTextDocumentParams {
+        text_document: TextDocumentItem {
+            uri: doc.uri.clone(),
+            language_id: "rust".to_owned(),
+            version: 0,
+            text: "pub fn foo() {}\n".to_owned(),
+        },
+    });
+    let full = server.send_request::<SemanticTokensFullRequest>(lsp_types::SemanticTokensParams {
+        text_document: doc.clone(),
+        work_done_progress_params: WorkDoneProgressParams::default(),
+        partial_result_params: PartialResultParams::default(),
+    });
+    let previous_result_id = full["resultId"].as_str().unwrap().to_owned();
+
+    // The edit makes the server ask the client to refresh its tokens, which must not throw away
+    // the tokens the client's delta request refers to.
+    server.notification::<DidChangeTextDocument>(lsp_types::DidChangeTextDocumentParams {
+        text_document: lsp_types::VersionedTextDocumentIdentifier {
+            uri: doc.uri.clone(),
+            version: 1,
+        },
+        content_changes: vec![lsp_types::TextDocumentContentChangeEvent {
+            range: None,
+            range_length: None,
+            text: "pub fn foo() {}\npub fn bar() {}\n".to_owned(),
+        }],
+    });
+    let delta = server.send_request::<SemanticTokensFullDeltaRequest>(
+        lsp_types::SemanticTokensDeltaParams {
+            text_document: doc,
+            previous_result_id,
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            partial_result_params: PartialResultParams::default(),
+        },
+    );
+
+    // Only the tokens of `bar` are sent, the ones of `foo` are kept.
+    let edits = delta["edits"].as_array().unwrap_or_else(|| panic!("not a delta: {delta}"));
+    assert_eq!(edits.len(), 1, "{delta}");
+    assert_eq!(edits[0]["start"], full["data"].as_array().unwrap().len(), "{delta}");
+}
+
+#[test]
+fn daemon_serves_clients_at_the_same_time() {
+    if skip_slow_tests() {
+        return;
+    }
+
+    let daemon = Project::with_fixture(
+        r#"
+//- /Cargo.toml
+[package]
+name = "foo"
+version = "0.0.0"
+
+//- /src/lib.rs
+pub struct Foo;
+"#,
+    )
+    .daemon();
+
+    let symbols = |server: &support::Server| {
+        server.send_request::<WorkspaceSymbolRequest>(lsp_types::WorkspaceSymbolParams {
+            query: "Foo".to_owned(),
+            ..Default::default()
+        })
+    };
+
+    let first = daemon.attach().wait_until_workspace_is_loaded();
+    let second = daemon.attach();
+    let res = symbols(&first);
+    assert_eq!(res.as_array().map(Vec::len), Some(1), "{res}");
+    assert_eq!(symbols(&second), res);
+
+    // Shutting down one client leaves the server to the other.
+    drop(first);
+    assert_eq!(symbols(&second), res);
+}
@@ -2,14 +2,14 @@ use std::{
     cell::{Cell, RefCell},
     fs,
     path::{Path, PathBuf},
-    sync::Once,
+    sync::{Arc, Once},
     time::Duration,
 };
 
-use crossbeam_channel::{after, select, Receiver};
+use crossbeam_channel::{after, select, unbounded, Receiver, Sender};
 use lsp_server::{Connection, Message, Notification, Request};
 use lsp_types::{notification::Exit, request::Shutdown, TextDocumentIdentifier, Url};
-use rust_analyzer::{config::Config, lsp, main_loop};
+use rust_analyzer::{config::Config, daemon_main_loop, lsp, main_loop};
 use serde::Serialize;
 use serde_json::{json, to_string_pretty, Value};
 use test_utils::FixtureWithProjectMeta;
@@ -81,6 +81,16 @@ impl Project<'_> {
     }
 
     pub(crate) fn server(self) -> Server {
+        let (tmp_dir, config) = self.config();
+        Server::new(tmp_dir, config)
+    }
+
+    pub(crate) fn daemon(self) -> Daemon {
+        let (tmp_dir, config) = self.config();
+        Daemon::new(tmp_dir, config)
+    }
+
+    fn config(self) -> (TestDir, Config) {
         let tmp_dir = self.tmp_dir.unwrap_or_else(|| {
             if self.root_dir_contains_symlink {
                 TestDir::new_symlink()
@@ -142,6 +152,9 @@ impl Project<'_> {
                         ]),
                         ..Default::default()
                     }),
+                    semantic_tokens: Some(lsp_types::SemanticTokensWorkspaceClientCapabilities {
+                        refresh_support: Some(true),
+                    }),
                     ..Default::default()
                 }),
                 text_document: Some(lsp_types::TextDocumentClientCapabilities {
@@ -176,7 +189,7 @@ impl Project<'_> {
         config.update(self.config).expect("invalid config");
         config.rediscover_workspaces();
 
-        Server::new(tmp_dir, config)
+        (tmp_dir, config)
     }
 }
 
@@ -184,13 +197,50 @@ pub(crate) fn project(fixture: &str) -> Server {
     Project::with_fixture(fixture).server()
 }
 
+/// A server that serves all the [`Server`]s attached to it at the same time.
+pub(crate) struct Daemon {
+    clients: Sender<(Config, Connection)>,
+    _thread: stdx::thread::JoinHandle,
+    config: Config,
+    dir: Arc<TestDir>,
+}
+
+impl Daemon {
+    fn new(dir: TestDir, config: Config) -> Daemon {
+        let (clients, receiver) = unbounded::<(Config, Connection)>();
+
+        let _thread = stdx::thread::Builder::new(stdx::thread::ThreadIntent::Worker)
+            .name("test daemon".to_owned())
+            .spawn(move || {
+                // Returns once the daemon is dropped.
+                let _ = daemon_main_loop(receiver);
+            })
+            .expect("failed to spawn a thread");
+
+        Daemon { clients, _thread, config, dir: Arc::new(dir) }
+    }
+
+    /// Attaches a new client.
+    pub(crate) fn attach(&self) -> Server {
+        let (connection, client) = Connection::memory();
+        self.clients.send((self.config.clone(), connection)).unwrap();
+        Server {
+            req_id: Cell::new(1),
+            dir: Arc::clone(&self.dir),
+            messages: Default::default(),
+            client,
+            _thread: None,
+        }
+    }
+}
+
 pub(crate) struct Server {
     req_id: Cell<i32>,
     messages: RefCell<Vec<Message>>,
-    _thread: stdx::thread::JoinHandle,
+    _thread: Option<stdx::thread::JoinHandle>,
     client: Connection,
     /// XXX: remove the tempdir last
-    dir: TestDir,
+    dir: Arc<TestDir>,
 }
 
 impl Server {
@@ -202,7 +252,13 @@ impl Server {
             .spawn(move || main_loop(config, connection).unwrap())
             .expect("failed to spawn a thread");
 
-        Server { req_id: Cell::new(1), dir, messages: Default::default(), client, _thread }
+        Server {
+            req_id: Cell::new(1),
+            dir: Arc::new(dir),
+            messages: Default::default(),
+            client,
+            _thread: Some(_thread),
+        }
     }
 
     pub(crate) fn doc_id(&self, rel_path: &str) -> TextDocumentIdentifier {
@@ -288,6 +344,9 @@ impl Server {
         while let Some(msg) = self.recv().unwrap_or_else(|Timeout| panic!("timeout: {r:?}")) {
             match msg {
                 Message::Request(req) => {
+                    if req.method == "workspace/semanticTokens/refresh" {
+                        continue;
+                    }
                     if req.method == "client/registerCapability" {
                         let params = req.params.to_string();
                         if ["workspace/didChangeWatchedFiles", "textDocument/didSave"]
@@ -417,6 +417,14 @@ pub fn record_expr_field_list(
     ast_from_text(&format!("fn f() {{ S {{ {fields} }} }}"))
 }
 
+pub fn record_expr_field_list_with_spread(
+    fields: impl IntoIterator<Item = ast::RecordExprField>,
+    spread: ast::Expr,
+) -> ast::RecordExprFieldList {
+    let fields = fields.into_iter().map(|it| format!("{it}, ")).join("");
+    ast_from_text(&format!("fn f() {{ S {{ {fields}..{spread} }} }}"))
+}
+
 pub fn record_expr_field(name: ast::NameRef, expr: Option<ast::Expr>) -> ast::RecordExprField {
     return match expr {
         Some(expr) => from_text(&format!("{name}: {expr}")),
@@ -582,6 +590,12 @@ pub fn expr_for_loop(pat: ast::Pat, expr: ast::Expr, block: ast::BlockExpr) -> a
 pub fn expr_loop(block: ast::BlockExpr) -> ast::Expr {
     expr_from_text(&format!("loop {block}"))
 }
+pub fn expr_unsafe(block: ast::BlockExpr) -> ast::Expr {
+    expr_from_text(&format!("unsafe {block}"))
+}
+pub fn expr_async_move(block: ast::BlockExpr) -> ast::Expr {
+    expr_from_text(&format!("async move {block}"))
+}
 
 pub fn expr_prefix(op: SyntaxKind, expr: ast::Expr) -> ast::Expr {
     let token = token(op);
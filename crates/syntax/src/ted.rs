@@ -124,6 +124,86 @@ pub fn remove_all_iter(range: impl IntoIterator<Item = SyntaxElement>) {
     }
 }
 
+/// Removes `elem` together with the comments attached to it (see [`attached_comments`] and
+/// [`trailing_comment`]) and the whitespace in front of them, so that neither the comments nor an
+/// empty line are left behind. Blank lines separating the removed lines from their neighbours
+/// are kept.
+pub fn remove_with_comments(elem: impl Element) {
+    let elem = elem.syntax_element();
+    let first = match attached_comments(elem.clone()).first() {
+        Some(comment) => comment.clone().into(),
+        None => elem.clone(),
+    };
+    let last = match trailing_comment(elem.clone()) {
+        Some(comment) => comment.into(),
+        None => elem,
+    };
+    let is_ws = |it: &SyntaxElement| it.kind() == SyntaxKind::WHITESPACE;
+    let prev_ws = first.prev_sibling_or_token().filter(is_ws);
+    let next_ws = last.next_sibling_or_token().filter(is_ws);
+    match (prev_ws, next_ws) {
+        (Some(prev_ws), Some(next_ws)) => {
+            let (prev_text, next_text) = (prev_ws.to_string(), next_ws.to_string());
+            let newlines = prev_text.matches('\n').count();
+            let before_closing_token = next_ws
+                .next_sibling_or_token()
+                .map_or(true, |it| it.next_sibling_or_token().is_none());
+            if !before_closing_token && newlines > next_text.matches('\n').count() {
+                let indent = next_text.rsplit('\n').next().unwrap_or_default();
+                let ws = format!("{}{indent}", "\n".repeat(newlines));
+                replace(next_ws, make::tokens::whitespace(&ws));
+            }
+            remove_all(prev_ws..=last);
+        }
+        (Some(prev_ws), None) => remove_all(prev_ws..=last),
+        (None, Some(next_ws)) => {
+            remove(next_ws);
+            remove_all(first..=last);
+        }
+        (None, None) => remove_all(first..=last),
+    }
+}
+
+/// Returns the comments on the lines directly above `elem`, up to the first empty line, which
+/// usually describe it. Comments following other code on their line are not included.
+pub fn attached_comments(elem: impl Element) -> Vec<SyntaxToken> {
+    let mut comments = Vec::new();
+    let mut prev = elem.syntax_element().prev_sibling_or_token();
+    while let Some(ws) = prev.filter(|it| it.kind() == SyntaxKind::WHITESPACE) {
+        let newlines = ws.to_string().matches('\n').count();
+        if newlines != 1 {
+            break;
+        }
+        let Some(comment) = ws.prev_sibling_or_token().and_then(|it| it.into_token()) else {
+            break;
+        };
+        if comment.kind() != SyntaxKind::COMMENT || !starts_line(&comment.clone().into()) {
+            break;
+        }
+        prev = comment.prev_sibling_or_token();
+        comments.push(comment);
+    }
+    comments.reverse();
+    comments
+}
+
+/// Returns the comment following `elem` on its last line, like in `let x = 92; // comment`.
+pub fn trailing_comment(elem: impl Element) -> Option<SyntaxToken> {
+    let ws = elem.syntax_element().next_sibling_or_token()?;
+    if ws.kind() != SyntaxKind::WHITESPACE || ws.to_string().contains('\n') {
+        return None;
+    }
+    ws.next_sibling_or_token()?.into_token().filter(|it| it.kind() == SyntaxKind::COMMENT)
+}
+
+fn starts_line(elem: &SyntaxElement) -> bool {
+    match elem.prev_sibling_or_token() {
+        Some(ws) if ws.kind() == SyntaxKind::WHITESPACE => ws.to_string().contains('\n'),
+        Some(_) => false,
+        None => true,
+    }
+}
+
 pub fn replace(old: impl Element, new: impl Element) {
     replace_with_many(old, vec![new.syntax_element()]);
 }
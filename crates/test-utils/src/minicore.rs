@@ -12,6 +12,7 @@
 //!     asm:
 //!     assert:
 //!     as_ref: sized
+//!     async_fn: fn, future
 //!     bool_impl: option, fn
 //!     builtin_impls:
 //!     cell: copy, drop
@@ -31,6 +32,7 @@
 //!     fmt: option, result, transmute, coerce_unsized
 //!     fn:
 //!     from: sized
+//!     from_str: result, sized
 //!     future: pin
 //!     coroutine: pin
 //!     hash:
@@ -58,6 +60,7 @@
 //!     sync: sized
 //!     transmute:
 //!     try: infallible
+//!     try_from: result, sized
 //!     unpin: sized
 //!     unsize: sized
 //!     todo: panic
@@ -319,6 +322,13 @@ pub mod convert {
     }
     // endregion:from
 
+    // region:try_from
+    pub trait TryFrom<T>: Sized {
+        type Error;
+        fn try_from(value: T) -> Result<Self, Self::Error>;
+    }
+    // endregion:try_from
+
     // region:as_ref
     pub trait AsRef<T: ?Sized> {
         fn as_ref(&self) -> &T;
@@ -669,6 +679,39 @@ pub mod ops {
     }
     pub use self::function::{Fn, FnMut, FnOnce};
     // endregion:fn
+
+    // region:async_fn
+    mod async_function {
+        use crate::{future::Future, marker::Tuple};
+
+        #[lang = "async_fn"]
+        #[fundamental]
+        pub trait AsyncFn<Args: Tuple>: AsyncFnMut<Args> {
+            extern "rust-call" fn async_call(&self, args: Args) -> Self::CallRefFuture<'_>;
+        }
+
+        #[lang = "async_fn_mut"]
+        #[fundamental]
+        pub trait AsyncFnMut<Args: Tuple>: AsyncFnOnce<Args> {
+            #[lang = "call_ref_future"]
+            type CallRefFuture<'a>: Future<Output = Self::Output>
+            where
+                Self: 'a;
+            extern "rust-call" fn async_call_mut(&mut self, args: Args) -> Self::CallRefFuture<'_>;
+        }
+
+        #[lang = "async_fn_once"]
+        #[fundamental]
+        pub trait AsyncFnOnce<Args: Tuple> {
+            #[lang = "async_fn_once_output"]
+            type Output;
+            #[lang = "call_once_future"]
+            type CallOnceFuture: Future<Output = Self::Output>;
+            extern "rust-call" fn async_call_once(self, args: Args) -> Self::CallOnceFuture;
+        }
+    }
+    pub use self::async_function::{AsyncFn, AsyncFnMut, AsyncFnOnce};
+    // endregion:async_fn
     // region:try
     mod try_ {
         use super::super::convert::Infallible;
@@ -1561,6 +1604,21 @@ pub mod error {
 }
 // endregion:error
 
+// region:from_str
+pub mod str {
+    pub trait FromStr: Sized {
+        type Err;
+        fn from_str(s: &str) -> Result<Self, Self::Err>;
+    }
+
+    impl str {
+        pub fn parse<F: FromStr>(&self) -> Result<F, F::Err> {
+            FromStr::from_str(self)
+        }
+    }
+}
+// endregion:from_str
+
 pub mod prelude {
     pub mod v1 {
         pub use crate::{
@@ -1568,6 +1626,7 @@ pub mod prelude {
             cmp::{Eq, PartialEq},                    // :eq
             cmp::{Ord, PartialOrd},                  // :ord
             convert::AsRef,                          // :as_ref
+            convert::TryFrom,                        // :try_from
             convert::{From, Into},                   // :from
             default::Default,                        // :default
             iter::{IntoIterator, Iterator},          // :iterator
@@ -1578,6 +1637,7 @@ pub mod prelude {
             marker::Sync,                            // :sync
             mem::drop,                               // :drop
             ops::Drop,                               // :drop
+            ops::{AsyncFn, AsyncFnMut, AsyncFnOnce}, // :async_fn
             ops::{Fn, FnMut, FnOnce},                // :fn
             option::Option::{self, None, Some},      // :option
             panic,                                   // :panic
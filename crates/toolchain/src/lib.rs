@@ -94,7 +94,8 @@ fn lookup_in_path(exec: &str) -> bool {
     env::split_paths(&paths).map(|path| path.join(exec)).find_map(probe_for_binary).is_some()
 }
 
-fn get_cargo_home() -> Option<PathBuf> {
+/// The cargo home directory, `$CARGO_HOME` or `~/.cargo`.
+pub fn get_cargo_home() -> Option<PathBuf> {
     if let Some(path) = env::var_os("CARGO_HOME") {
         return Some(path.into());
     }
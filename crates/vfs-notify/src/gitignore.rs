@@ -0,0 +1,130 @@
+//! A small subset of `.gitignore` matching, used to skip ignored directories while walking the
+//! workspace.
+//!
+//! Supported are comments, negations (`!`), directory-only patterns (`dir/`), patterns anchored to
+//! the directory of the `.gitignore` (`/dir`, `a/b`), and the `*`, `?` and `**` wildcards.
+//! Character classes are matched literally.
+
+use std::fs;
+
+use paths::{AbsPath, AbsPathBuf};
+
+#[derive(Debug, Clone)]
+pub(crate) struct Pattern {
+    /// The directory the pattern is relative to.
+    base: AbsPathBuf,
+    components: Vec<String>,
+    anchored: bool,
+    dir_only: bool,
+    negated: bool,
+}
+
+impl Pattern {
+    /// Parses a single line of a `.gitignore` in `base`.
+    pub(crate) fn parse(base: &AbsPath, line: &str) -> Option<Pattern> {
+        let line = line.trim_end();
+        if line.is_empty() || line.starts_with('#') {
+            return None;
+        }
+        let (negated, line) = match line.strip_prefix('!') {
+            Some(it) => (true, it),
+            None => (false, line.strip_prefix('\\').unwrap_or(line)),
+        };
+        let (dir_only, line) = match line.strip_suffix('/') {
+            Some(it) => (true, it),
+            None => (false, line),
+        };
+        let anchored = line.contains('/');
+        let components =
+            line.split('/').filter(|it| !it.is_empty()).map(ToOwned::to_owned).collect::<Vec<_>>();
+        if components.is_empty() {
+            return None;
+        }
+        Some(Pattern { base: base.to_path_buf(), components, anchored, dir_only, negated })
+    }
+
+    fn matches(&self, path: &AbsPath, is_dir: bool) -> bool {
+        if self.dir_only && !is_dir {
+            return false;
+        }
+        let Some(rel) = path.strip_prefix(&self.base) else { return false };
+        let rel = rel.as_ref().iter().filter_map(|it| it.to_str()).collect::<Vec<_>>();
+        match rel.last() {
+            Some(name) if !self.anchored => match_glob(&self.components[0], name),
+            Some(_) => match_components(&self.components, &rel),
+            None => false,
+        }
+    }
+}
+
+/// Reads the patterns of the `.gitignore` in `dir`, if any.
+pub(crate) fn read_gitignore(dir: &AbsPath) -> Vec<Pattern> {
+    let Ok(text) = fs::read_to_string(dir.join(".gitignore")) else { return Vec::new() };
+    text.lines().filter_map(|line| Pattern::parse(dir, line)).collect()
+}
+
+/// Whether `path` is ignored by `patterns`, the last matching pattern wins.
+pub(crate) fn is_ignored<'a>(
+    patterns: impl DoubleEndedIterator<Item = &'a Pattern>,
+    path: &AbsPath,
+    is_dir: bool,
+) -> bool {
+    patterns.rev().find(|it| it.matches(path, is_dir)).map_or(false, |it| !it.negated)
+}
+
+fn match_components(pattern: &[String], path: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((first, rest)) if first == "**" => {
+            (0..=path.len()).any(|skip| match_components(rest, &path[skip..]))
+        }
+        Some((first, rest)) => match path.split_first() {
+            Some((name, path)) => match_glob(first, name) && match_components(rest, path),
+            None => false,
+        },
+    }
+}
+
+fn match_glob(pattern: &str, name: &str) -> bool {
+    fn go(pattern: &[char], name: &[char]) -> bool {
+        match pattern.split_first() {
+            None => name.is_empty(),
+            Some(('*', rest)) => (0..=name.len()).any(|skip| go(rest, &name[skip..])),
+            Some((&p, rest)) => match name.split_first() {
+                Some((&c, name)) => (p == '?' || p == c) && go(rest, name),
+                None => false,
+            },
+        }
+    }
+    let pattern = pattern.chars().collect::<Vec<_>>();
+    let name = name.chars().collect::<Vec<_>>();
+    go(&pattern, &name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(lines: &[&str], path: &str, is_dir: bool, expected: bool) {
+        let base = AbsPathBuf::assert("/ws".into());
+        let patterns =
+            lines.iter().filter_map(|line| Pattern::parse(&base, line)).collect::<Vec<_>>();
+        let path = AbsPathBuf::assert(path.into());
+        assert_eq!(is_ignored(patterns.iter(), &path, is_dir), expected, "{lines:?} {path}");
+    }
+
+    #[test]
+    fn gitignore_patterns() {
+        check(&["target/"], "/ws/target", true, true);
+        check(&["target/"], "/ws/crates/a/target", true, true);
+        check(&["target/"], "/ws/target", false, false);
+        check(&["/target"], "/ws/crates/target", true, false);
+        check(&["# target"], "/ws/target", true, false);
+        check(&["*.log"], "/ws/logs/a.log", false, true);
+        check(&["data/**/raw"], "/ws/data/2023/01/raw", true, true);
+        check(&["data/**/raw"], "/ws/data/raw", true, true);
+        check(&["data/**/raw"], "/ws/other/data/raw", true, false);
+        check(&["node_modules", "!node_modules"], "/ws/node_modules", true, false);
+        check(&["/gen?"], "/ws/gen1", true, true);
+    }
+}
@@ -9,6 +9,8 @@
 
 #![warn(rust_2018_idioms, unused_lifetimes)]
 
+mod gitignore;
+
 use std::fs;
 
 use crossbeam_channel::{never, select, unbounded, Receiver, Sender};
@@ -59,6 +61,8 @@ type NotifyEvent = notify::Result<notify::Event>;
 struct NotifyActor {
     sender: loader::Sender,
     watched_entries: Vec<loader::Entry>,
+    exclude_globs: Vec<String>,
+    respect_gitignore: bool,
     // Drop order is significant.
     watcher: Option<(RecommendedWatcher, Receiver<NotifyEvent>)>,
 }
@@ -71,7 +75,13 @@ enum Event {
 
 impl NotifyActor {
     fn new(sender: loader::Sender) -> NotifyActor {
-        NotifyActor { sender, watched_entries: Vec::new(), watcher: None }
+        NotifyActor {
+            sender,
+            watched_entries: Vec::new(),
+            exclude_globs: Vec::new(),
+            respect_gitignore: false,
+            watcher: None,
+        }
     }
 
     fn next_event(&self, receiver: &Receiver<Message>) -> Option<Event> {
@@ -111,6 +121,8 @@ impl NotifyActor {
                         });
 
                         self.watched_entries.clear();
+                        self.exclude_globs = config.exclude_globs;
+                        self.respect_gitignore = config.respect_gitignore;
 
                         for (i, entry) in config.load.into_iter().enumerate() {
                             let watch = config.watch.contains(&i);
@@ -200,14 +212,46 @@ impl NotifyActor {
 
                 for root in &dirs.include {
                     self.send(make_message(root.clone()));
+                    let excluded = self
+                        .exclude_globs
+                        .iter()
+                        .filter_map(|it| gitignore::Pattern::parse(root, it))
+                        .collect::<Vec<_>>();
+                    let respect_gitignore = self.respect_gitignore;
+                    // The `.gitignore` patterns of the directories on the path to the current
+                    // entry, along with their depth.
+                    let mut ignored: Vec<(usize, Vec<gitignore::Pattern>)> = Vec::new();
                     let walkdir =
                         WalkDir::new(root).follow_links(true).into_iter().filter_entry(|entry| {
-                            if !entry.file_type().is_dir() {
+                            let path = AbsPath::assert(entry.path());
+                            let is_dir = entry.file_type().is_dir();
+                            if root == path {
+                                if respect_gitignore {
+                                    ignored.push((0, gitignore::read_gitignore(path)));
+                                }
                                 return true;
                             }
-                            let path = AbsPath::assert(entry.path());
-                            root == path
-                                || dirs.exclude.iter().chain(&dirs.include).all(|it| it != path)
+                            if gitignore::is_ignored(excluded.iter(), path, is_dir) {
+                                return false;
+                            }
+                            if !is_dir {
+                                return true;
+                            }
+                            if dirs.exclude.iter().chain(&dirs.include).any(|it| it == path) {
+                                return false;
+                            }
+                            if respect_gitignore {
+                                let depth = entry.depth();
+                                while ignored.last().map_or(false, |&(it, _)| it >= depth) {
+                                    ignored.pop();
+                                }
+                                let patterns = ignored.iter().flat_map(|(_, it)| it);
+                                if gitignore::is_ignored(patterns, path, true) {
+                                    return false;
+                                }
+                                ignored.push((depth, gitignore::read_gitignore(path)));
+                            }
+                            true
                         });
 
                     let files = walkdir.filter_map(|it| it.ok()).filter_map(|entry| {
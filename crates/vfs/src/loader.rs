@@ -41,6 +41,12 @@ pub struct Config {
     ///
     /// If a path in a watched entry is modified,the [`Handle`] should notify it.
     pub watch: Vec<usize>,
+    /// `.gitignore`-style patterns of paths to skip when loading [`Directories`], relative to
+    /// each of their `include` paths.
+    pub exclude_globs: Vec<String>,
+    /// Whether directories ignored by `.gitignore` files are skipped when loading
+    /// [`Directories`].
+    pub respect_gitignore: bool,
 }
 
 /// Message about an action taken by a [`Handle`].
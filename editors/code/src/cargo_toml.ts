@@ -0,0 +1,87 @@
+import * as vscode from "vscode";
+import * as lc from "vscode-languageclient/node";
+import * as ra from "./lsp_ext";
+
+const selector: vscode.DocumentSelector = { scheme: "file", pattern: "**/Cargo.toml" };
+
+// The client only synchronizes Rust files with the server, so `Cargo.toml` files are synchronized
+// here, for the server to answer the completion and diagnostics requests from its own copy.
+export function prepareCargoToml(client: lc.LanguageClient): vscode.Disposable {
+    const diagnostics = vscode.languages.createDiagnosticCollection("rust-analyzer-cargo-toml");
+
+    const isCargoToml = (doc: vscode.TextDocument) => vscode.languages.match(selector, doc) > 0;
+
+    const updateDiagnostics = async (doc: vscode.TextDocument) => {
+        const version = doc.version;
+        const res = await client.sendRequest(
+            ra.cargoTomlDiagnostics,
+            client.code2ProtocolConverter.asTextDocumentIdentifier(doc),
+        );
+        // Drop results that are outdated by an edit made in the meantime.
+        if (doc.version !== version) return;
+        diagnostics.set(doc.uri, await client.protocol2CodeConverter.asDiagnostics(res));
+    };
+
+    const didOpen = async (doc: vscode.TextDocument) => {
+        if (!isCargoToml(doc)) return;
+        await client.sendNotification(
+            lc.DidOpenTextDocumentNotification.type,
+            client.code2ProtocolConverter.asOpenTextDocumentParams(doc),
+        );
+        await updateDiagnostics(doc);
+    };
+
+    const completionProvider = vscode.languages.registerCompletionItemProvider(
+        selector,
+        {
+            async provideCompletionItems(doc, position, token) {
+                const items = await client.sendRequest(
+                    ra.cargoTomlCompletion,
+                    client.code2ProtocolConverter.asTextDocumentPositionParams(doc, position),
+                    token,
+                );
+                return client.protocol2CodeConverter.asCompletionResult(items, undefined, token);
+            },
+        },
+        '"',
+        ".",
+    );
+
+    const subscriptions = [
+        diagnostics,
+        completionProvider,
+        vscode.workspace.onDidOpenTextDocument(didOpen),
+        vscode.workspace.onDidChangeTextDocument(async (e) => {
+            if (!isCargoToml(e.document) || e.contentChanges.length === 0) return;
+            await client.sendNotification(
+                lc.DidChangeTextDocumentNotification.type,
+                client.code2ProtocolConverter.asChangeTextDocumentParams(
+                    e,
+                    e.document.uri,
+                    e.document.version,
+                ),
+            );
+            await updateDiagnostics(e.document);
+        }),
+        vscode.workspace.onDidSaveTextDocument(async (doc) => {
+            if (!isCargoToml(doc)) return;
+            await client.sendNotification(
+                lc.DidSaveTextDocumentNotification.type,
+                client.code2ProtocolConverter.asSaveTextDocumentParams(doc),
+            );
+        }),
+        vscode.workspace.onDidCloseTextDocument(async (doc) => {
+            if (!isCargoToml(doc)) return;
+            diagnostics.delete(doc.uri);
+            await client.sendNotification(
+                lc.DidCloseTextDocumentNotification.type,
+                client.code2ProtocolConverter.asCloseTextDocumentParams(doc),
+            );
+        }),
+    ];
+    for (const doc of vscode.workspace.textDocuments) {
+        void didOpen(doc);
+    }
+
+    return vscode.Disposable.from(...subscriptions);
+}
@@ -26,6 +26,7 @@ import { LINKED_COMMANDS } from "./client";
 import type { DependencyId } from "./dependencies_provider";
 import { unwrapUndefinable } from "./undefinable";
 import { log } from "./util";
+import { getPathForExecutable } from "./toolchain";
 
 export * from "./ast_inspector";
 export * from "./run";
@@ -409,6 +410,45 @@ export function ssr(ctx: CtxInit): Cmd {
     };
 }
 
+export function searchByType(ctx: CtxInit): Cmd {
+    return async () => {
+        const client = ctx.client;
+
+        const query = await vscode.window.showInputBox({
+            prompt: "Enter a type, for example 'Arc<Mutex<_>>'",
+        });
+        if (!query) return;
+
+        const matches = await vscode.window.withProgress(
+            {
+                location: vscode.ProgressLocation.Notification,
+                title: "Searching by type...",
+                cancellable: true,
+            },
+            (_progress, token) => client.sendRequest(ra.searchByType, { query }, token),
+        );
+        if (matches.length === 0) {
+            void vscode.window.showInformationMessage(`No expressions of type \`${query}\` found`);
+            return;
+        }
+
+        const items = matches.map((match) => {
+            const location = client.protocol2CodeConverter.asLocation(match.location);
+            return {
+                label: match.ty,
+                description: vscode.workspace.asRelativePath(location.uri),
+                detail: `${match.kind} at line ${location.range.start.line + 1}`,
+                location,
+            };
+        });
+        const item = await vscode.window.showQuickPick(items, { matchOnDescription: true });
+        if (!item) return;
+        await vscode.window.showTextDocument(item.location.uri, {
+            selection: item.location.range,
+        });
+    };
+}
+
 export function serverVersion(ctx: CtxInit): Cmd {
     return async () => {
         if (!ctx.serverPath) {
@@ -866,6 +906,105 @@ export function expandMacro(ctx: CtxInit): Cmd {
     };
 }
 
+// Opens a virtual file showing the expansion of the macro that the diagnostic at the caret was
+// reported in, with the diagnostic highlighted and a link back to the macro call.
+export function expandMacroDiagnostic(ctx: CtxInit): Cmd {
+    // The header placed above the expansion, its second line links to the macro call.
+    const headerLines = 3;
+    let expanded: ra.ExpandedMacroDiagnostic | null = null;
+
+    const tdcp = new (class implements vscode.TextDocumentContentProvider {
+        uri = vscode.Uri.parse("rust-analyzer-expand-macro-diagnostic://expand/[EXPANSION].rs");
+        eventEmitter = new vscode.EventEmitter<vscode.Uri>();
+        provideTextDocumentContent(_uri: vscode.Uri): string {
+            if (!expanded) return "Not available";
+            const callSite = expanded.callSite;
+            return (
+                `// Expansion of ${expanded.name}\n` +
+                `// Called at ${callSite.uri}:${callSite.range.start.line + 1}\n\n` +
+                expanded.expansion
+            );
+        }
+
+        get onDidChange(): vscode.Event<vscode.Uri> {
+            return this.eventEmitter.event;
+        }
+    })();
+
+    const linkProvider: vscode.DocumentLinkProvider = {
+        provideDocumentLinks(document) {
+            if (!expanded) return [];
+            const callSite = expanded.callSite;
+            const target = vscode.Uri.parse(callSite.uri).with({
+                fragment: `L${callSite.range.start.line + 1},${callSite.range.start.character + 1}`,
+            });
+            return [new vscode.DocumentLink(document.lineAt(1).range, target)];
+        },
+    };
+
+    const decorationType = vscode.window.createTextEditorDecorationType({
+        textDecoration: "underline wavy",
+        backgroundColor: new vscode.ThemeColor("editorError.background"),
+    });
+
+    ctx.pushExtCleanup(
+        vscode.workspace.registerTextDocumentContentProvider(
+            "rust-analyzer-expand-macro-diagnostic",
+            tdcp,
+        ),
+    );
+    ctx.pushExtCleanup(
+        vscode.languages.registerDocumentLinkProvider(
+            { scheme: "rust-analyzer-expand-macro-diagnostic" },
+            linkProvider,
+        ),
+    );
+    ctx.pushExtCleanup(decorationType);
+
+    return async () => {
+        const editor = ctx.activeRustEditor;
+        if (!editor) return;
+        const client = ctx.client;
+
+        const position = editor.selection.active;
+        const diagnostic = vscode.languages
+            .getDiagnostics(editor.document.uri)
+            .find((it) => it.range.contains(position));
+        if (!diagnostic) {
+            await vscode.window.showInformationMessage("No diagnostic at the caret");
+            return;
+        }
+
+        expanded = await client.sendRequest(ra.expandMacroDiagnostic, {
+            textDocument: client.code2ProtocolConverter.asTextDocumentIdentifier(editor.document),
+            range: client.code2ProtocolConverter.asRange(diagnostic.range),
+        });
+        if (!expanded) {
+            await vscode.window.showInformationMessage(
+                "The diagnostic at the caret was not reported inside a macro expansion",
+            );
+            return;
+        }
+
+        const document = await vscode.workspace.openTextDocument(tdcp.uri);
+        tdcp.eventEmitter.fire(tdcp.uri);
+        const expansionEditor = await vscode.window.showTextDocument(
+            document,
+            vscode.ViewColumn.Two,
+            true,
+        );
+        const range = client.protocol2CodeConverter.asRange(expanded.range);
+        const shifted = new vscode.Range(
+            range.start.translate(headerLines),
+            range.end.translate(headerLines),
+        );
+        expansionEditor.setDecorations(decorationType, [
+            { range: shifted, hoverMessage: diagnostic.message },
+        ]);
+        expansionEditor.revealRange(shifted, vscode.TextEditorRevealType.InCenter);
+    };
+}
+
 export function reloadWorkspace(ctx: CtxInit): Cmd {
     return async () => ctx.client.sendRequest(ra.reloadWorkspace);
 }
@@ -1486,3 +1625,167 @@ export function toggleCheckOnSave(ctx: Ctx): Cmd {
         ctx.refreshServerStatus();
     };
 }
+
+export function toggleFeatures(ctx: CtxInit): Cmd {
+    return async () => {
+        const editor = ctx.activeRustEditor;
+        if (!editor) return;
+        const client = ctx.client;
+        const features = await client.sendRequest(ra.crateFeatures, {
+            textDocument: client.code2ProtocolConverter.asTextDocumentIdentifier(editor.document),
+        });
+        if (features.length === 0) {
+            void vscode.window.showInformationMessage("The current crate has no features.");
+            return;
+        }
+        const picked = await vscode.window.showQuickPick(
+            features.map((feature) => ({ label: feature.name, picked: feature.enabled })),
+            { canPickMany: true, placeHolder: "Select the features to analyze the crate with" },
+        );
+        if (!picked) return;
+        if (!(await ctx.config.setCargoFeatures(picked.map((it) => it.label)))) {
+            void vscode.window.showInformationMessage(
+                "Crate features can only be selected in a workspace.",
+            );
+        }
+    };
+}
+
+export function enableCfg(ctx: CtxInit): Cmd {
+    return async (cfgs: [string, string][]) => {
+        ctx.config.cfgOverrides = { ...ctx.config.cfgOverrides, ...Object.fromEntries(cfgs) };
+        await ctx.notifyRustAnalyzer();
+    };
+}
+
+export function applySsrRule(ctx: CtxInit): Cmd {
+    return async (rule: string, position: lc.TextDocumentPositionParams) => {
+        const client = ctx.client;
+        await vscode.window.withProgress(
+            {
+                location: vscode.ProgressLocation.Notification,
+                title: `Applying \`${rule}\` across the workspace...`,
+                cancellable: true,
+            },
+            async (_progress, token) => {
+                const edit = await client.sendRequest(
+                    ra.ssr,
+                    { query: rule, parseOnly: false, selections: [], preview: true, ...position },
+                    token,
+                );
+                if (token.isCancellationRequested) return;
+                // The edits need confirmation, so this opens the refactor preview.
+                await vscode.workspace.applyEdit(
+                    await client.protocol2CodeConverter.asWorkspaceEdit(edit, token),
+                );
+            },
+        );
+    };
+}
+
+export function explainExpression(ctx: CtxInit): Cmd {
+    return async () => {
+        const editor = ctx.activeRustEditor;
+        if (!editor) return;
+        const client = ctx.client;
+        const res = await client.sendRequest(ra.explainExpression, {
+            textDocument: client.code2ProtocolConverter.asTextDocumentIdentifier(editor.document),
+            range: client.code2ProtocolConverter.asRange(editor.selection),
+        });
+        if (!res) {
+            void vscode.window.showInformationMessage("No expression at the selection.");
+            return;
+        }
+        editor.selection = new vscode.Selection(
+            client.protocol2CodeConverter.asPosition(res.range.start),
+            client.protocol2CodeConverter.asPosition(res.range.end),
+        );
+        const lines = [`Type: ${res.type}`];
+        if (res.coercedType) lines.push(`Coerced to: ${res.coercedType}`);
+        for (const adjustment of res.adjustments) {
+            lines.push(`${adjustment.kind}: ${adjustment.source} -> ${adjustment.target}`);
+        }
+        if (res.callee) {
+            const trait = res.calleeTrait ? ` (trait ${res.calleeTrait})` : "";
+            lines.push(`Calls: ${res.callee}${trait}`);
+        }
+        await vscode.window.showInformationMessage("Explain Expression", {
+            modal: true,
+            detail: lines.join("\n"),
+        });
+    };
+}
+
+export function pasteWithImports(ctx: CtxInit): Cmd {
+    return async () => {
+        const editor = ctx.activeRustEditor;
+        if (!editor || editor.selections.length !== 1) {
+            await vscode.commands.executeCommand("editor.action.clipboardPasteAction");
+            return;
+        }
+        const document = editor.document;
+        const start = document.offsetAt(editor.selection.start);
+        const selected = document.offsetAt(editor.selection.end) - start;
+        const length = document.getText().length;
+        await vscode.commands.executeCommand("editor.action.clipboardPasteAction");
+        const end = start + selected + document.getText().length - length;
+        if (end <= start) return;
+
+        const client = ctx.client;
+        const params: ra.ImportsForPasteParams = {
+            textDocument: client.code2ProtocolConverter.asTextDocumentIdentifier(document),
+            range: client.code2ProtocolConverter.asRange(
+                new vscode.Range(document.positionAt(start), document.positionAt(end)),
+            ),
+            chosen: [],
+        };
+        let result = await client.sendRequest(ra.importsForPaste, params);
+        if (result && result.ambiguous.length > 0 && ctx.config.pasteImportsPrompt) {
+            for (const name of result.ambiguous) {
+                const picked = await vscode.window.showQuickPick(name.candidates, {
+                    placeHolder: `Import \`${name.name}\` from`,
+                });
+                if (picked) params.chosen.push(picked);
+            }
+            if (params.chosen.length > 0) {
+                result = await client.sendRequest(ra.importsForPaste, params);
+            }
+        }
+        if (!result) return;
+        const edit = await client.protocol2CodeConverter.asWorkspaceEdit(result.edit);
+        await vscode.workspace.applyEdit(edit);
+    };
+}
+
+export function selectTarget(ctx: Ctx): Cmd {
+    return async () => {
+        const rustcPath = await getPathForExecutable("rustc");
+        const cwd = vscode.workspace.workspaceFolders?.[0]?.uri.fsPath;
+        const { stdout, status, error } = spawnSync(rustcPath, ["--print", "target-list"], {
+            cwd,
+            encoding: "utf8",
+        });
+        if (error || status !== 0) {
+            log.error(`failed to list the targets of ${rustcPath}:`, error ?? status);
+            void vscode.window.showErrorMessage(`Failed to list the targets of ${rustcPath}`);
+            return;
+        }
+        const targets = stdout
+            .split("\n")
+            .map((it) => it.trim())
+            .filter((it) => it.length > 0);
+        const current = ctx.config.cargoTarget;
+        const host = "Host";
+        const items: vscode.QuickPickItem[] = [host, ...targets].map((label) => ({
+            label,
+            description:
+                (label === host ? current === null : label === current) ? "current" : undefined,
+        }));
+        const picked = await vscode.window.showQuickPick(items, {
+            placeHolder: "Select the target to analyze the workspace for",
+        });
+        if (!picked) return;
+        await ctx.config.setCargoTarget(picked.label === host ? null : picked.label);
+        ctx.refreshServerStatus();
+    };
+}
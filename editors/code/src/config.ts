@@ -20,7 +20,6 @@ export class Config {
 
     readonly rootSection = "rust-analyzer";
     private readonly requiresReloadOpts = [
-        "cargo",
         "procMacro",
         "serverPath",
         "server",
@@ -61,6 +60,10 @@ export class Config {
 
     public discoveredWorkspaces: JsonProject[];
 
+    // Cfgs enabled for this session only, they are added to `rust-analyzer.cargo.cfgs` without
+    // being written to the settings.
+    public cfgOverrides: Record<string, string> = {};
+
     private async onDidChangeConfiguration(event: vscode.ConfigurationChangeEvent) {
         this.refreshLogging();
 
@@ -250,6 +253,36 @@ export class Config {
         await this.cfg.update("checkOnSave", !(value || false), target || null, overrideInLanguage);
     }
 
+    get cargoTarget(): string | null {
+        return this.get<string | null>("cargo.target") ?? null;
+    }
+    async setCargoTarget(target: string | null) {
+        const configTarget = vscode.workspace.workspaceFolders
+            ? vscode.ConfigurationTarget.Workspace
+            : vscode.ConfigurationTarget.Global;
+        await this.cfg.update("cargo.target", target ?? undefined, configTarget);
+    }
+
+    // The features only make sense for the crates of one workspace, so they are never written to
+    // the user settings. Returns whether there was a workspace to write them to.
+    async setCargoFeatures(features: string[]): Promise<boolean> {
+        if (!vscode.workspace.workspaceFolders) return false;
+        const configTarget = vscode.ConfigurationTarget.Workspace;
+        // Default features are selected explicitly, so that they can be disabled too.
+        await this.cfg.update("cargo.noDefaultFeatures", true, configTarget);
+        await this.cfg.update("cargo.features", features, configTarget);
+        return true;
+    }
+
+    get testExplorer() {
+        return this.get<boolean>("testExplorer");
+    }
+
+    get pasteImportsPrompt() {
+        return this.get<boolean>("imports.paste.promptOnAmbiguity");
+    }
+
+
     get traceExtension() {
         return this.get<boolean>("trace.extension");
     }
@@ -368,6 +401,19 @@ export function prepareVSCodeConfig<T>(
     return resp;
 }
 
+// Adds the in-memory session state of the extension to the configuration sent to the server.
+export function addSessionConfig(config: Config, key: string, cfg: { [key: string]: any }) {
+    // we only want to set discovered workspaces on the right key
+    // and if a workspace has been discovered.
+    if (key === "linkedProjects" && config.discoveredWorkspaces.length > 0) {
+        cfg[key] = config.discoveredWorkspaces;
+    }
+    if (key === "cargo" && Object.keys(config.cfgOverrides).length > 0) {
+        const cargo = cfg[key] ?? {};
+        cfg[key] = { ...cargo, cfgs: { ...cargo.cfgs, ...config.cfgOverrides } };
+    }
+}
+
 // FIXME: Merge this with `substituteVSCodeVariables` above
 export function substituteVariablesInEnv(env: Env): Env {
     const missingDeps = new Set<string>();
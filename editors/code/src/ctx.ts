@@ -2,7 +2,7 @@ import * as vscode from "vscode";
 import * as lc from "vscode-languageclient/node";
 import * as ra from "./lsp_ext";
 
-import { Config, prepareVSCodeConfig } from "./config";
+import { Config, addSessionConfig, prepareVSCodeConfig } from "./config";
 import { createClient } from "./client";
 import {
     isDocumentInWorkspace,
@@ -22,6 +22,8 @@ import {
 import { execRevealDependency } from "./commands";
 import { PersistentState } from "./persistent_state";
 import { bootstrap } from "./bootstrap";
+import { prepareTestExplorer } from "./test_explorer";
+import { prepareCargoToml } from "./cargo_toml";
 import type { RustAnalyzerExtensionApi } from "./main";
 import type { JsonProject } from "./rust_project";
 
@@ -207,13 +209,7 @@ export class Ctx implements RustAnalyzerExtensionApi {
 
             const initializationOptions = prepareVSCodeConfig(
                 rawInitializationOptions,
-                (key, obj) => {
-                    // we only want to set discovered workspaces on the right key
-                    // and if a workspace has been discovered.
-                    if (key === "linkedProjects" && this.config.discoveredWorkspaces.length > 0) {
-                        obj["linkedProjects"] = this.config.discoveredWorkspaces;
-                    }
-                },
+                (key, obj) => addSessionConfig(this.config, key, obj),
             );
 
             this._client = await createClient(
@@ -229,6 +225,10 @@ export class Ctx implements RustAnalyzerExtensionApi {
                     this.setServerStatus(params),
                 ),
             );
+            if (this.config.testExplorer) {
+                this.pushClientCleanup(prepareTestExplorer(this, this._client));
+            }
+            this.pushClientCleanup(prepareCargoToml(this._client));
             this.pushClientCleanup(
                 this._client.onNotification(ra.openServerLogs, () => {
                     this.outputChannel!.show();
@@ -473,6 +473,11 @@ export class Ctx implements RustAnalyzerExtensionApi {
                 this.config.checkOnSave ? "Disable" : "Enable"
             } Check on Save](command:rust-analyzer.toggleCheckOnSave)`,
         );
+        statusBar.tooltip.appendMarkdown(
+            `\n\n[Select Target (${
+                this.config.cargoTarget ?? "host"
+            })](command:rust-analyzer.selectTarget)`,
+        );
         statusBar.tooltip.appendMarkdown(
             "\n\n[Reload Workspace](command:rust-analyzer.reloadWorkspace)",
         );
@@ -484,7 +489,8 @@ export class Ctx implements RustAnalyzerExtensionApi {
         );
         statusBar.tooltip.appendMarkdown("\n\n[Stop server](command:rust-analyzer.stopServer)");
         if (!status.quiescent) icon = "$(sync~spin) ";
-        statusBar.text = `${icon}rust-analyzer`;
+        const target = this.config.cargoTarget;
+        statusBar.text = target ? `${icon}rust-analyzer (${target})` : `${icon}rust-analyzer`;
     }
 
     pushExtCleanup(d: Disposable) {
@@ -1,9 +1,9 @@
 import * as os from "os";
 import * as vscode from "vscode";
 import * as path from "path";
-import type * as ra from "./lsp_ext";
+import * as ra from "./lsp_ext";
 
-import { Cargo, type ExecutableInfo, getRustcId, getSysroot } from "./toolchain";
+import { getRustcId, getSysroot } from "./toolchain";
 import type { Ctx } from "./ctx";
 import { prepareEnv } from "./run";
 import { unwrapUndefinable } from "./undefinable";
@@ -130,8 +130,7 @@ async function getDebugConfiguration(
         return path.normalize(p).replace(wsFolder, "${workspaceFolder" + workspaceQualifier + "}");
     }
 
-    const env = prepareEnv(runnable, ctx.config.runnablesExtraEnv);
-    const { executable, workspace: cargoWorkspace } = await getDebugExecutableInfo(runnable, env);
+    const { executable, env, cwd: cargoWorkspace } = await getDebugLaunchInfo(ctx, runnable);
     let sourceFileMap = debugOptions.sourceFileMap;
     if (sourceFileMap === "auto") {
         // let's try to use the default toolchain
@@ -173,15 +172,22 @@ async function getDebugConfiguration(
     return debugConfig;
 }
 
-async function getDebugExecutableInfo(
+async function getDebugLaunchInfo(
+    ctx: Ctx,
     runnable: ra.Runnable,
-    env: Record<string, string>,
-): Promise<ExecutableInfo> {
-    const cargo = new Cargo(runnable.args.workspaceRoot || ".", debugOutput, env);
-    const executableInfo = await cargo.executableInfoFromArgs(runnable.args.cargoArgs);
-
-    // if we are here, there were no compilation errors.
-    return executableInfo;
+): Promise<ra.DebugLaunchInfoResult> {
+    try {
+        const info = await unwrapUndefinable(ctx.client).sendRequest(ra.debugLaunchInfo, runnable);
+        // if we are here, there were no compilation errors.
+        return {
+            ...info,
+            env: { ...info.env, ...prepareEnv(runnable, ctx.config.runnablesExtraEnv) },
+        };
+    } catch (err) {
+        debugOutput.append(`${err}`);
+        debugOutput.show(true);
+        throw err;
+    }
 }
 
 function getLldbDebugConfig(
@@ -34,6 +34,11 @@ export const clearFlycheck = new lc.NotificationType0("rust-analyzer/clearFlyche
 export const expandMacro = new lc.RequestType<ExpandMacroParams, ExpandedMacro | null, void>(
     "rust-analyzer/expandMacro",
 );
+export const expandMacroDiagnostic = new lc.RequestType<
+    ExpandMacroDiagnosticParams,
+    ExpandedMacroDiagnostic | null,
+    void
+>("rust-analyzer/expandMacroDiagnostic");
 export const memoryUsage = new lc.RequestType0<string, void>("rust-analyzer/memoryUsage");
 export const openServerLogs = new lc.NotificationType0("rust-analyzer/openServerLogs");
 export const relatedTests = new lc.RequestType<lc.TextDocumentPositionParams, TestInfo[], void>(
@@ -67,6 +72,39 @@ export const interpretFunction = new lc.RequestType<lc.TextDocumentPositionParam
 export const viewItemTree = new lc.RequestType<ViewItemTreeParams, string, void>(
     "rust-analyzer/viewItemTree",
 );
+export const crateFeatures = new lc.RequestType<CrateFeaturesParams, CrateFeature[], void>(
+    "rust-analyzer/crateFeatures",
+);
+export const debugLaunchInfo = new lc.RequestType<Runnable, DebugLaunchInfoResult, void>(
+    "rust-analyzer/debugLaunchInfo",
+);
+export const discoverTests = new lc.RequestType<DiscoverTestParams, DiscoverTestResults, void>(
+    "rust-analyzer/discoverTests",
+);
+export const discoveredTests = new lc.NotificationType<DiscoverTestResults>(
+    "rust-analyzer/discoveredTests",
+);
+export const explainExpression = new lc.RequestType<
+    ExplainExpressionParams,
+    ExplainExpressionResult | null,
+    void
+>("rust-analyzer/explainExpression");
+export const tasks = new lc.RequestType<TasksParams, Task[], void>("rust-analyzer/tasks");
+export const cargoTomlCompletion = new lc.RequestType<
+    lc.TextDocumentPositionParams,
+    lc.CompletionItem[],
+    void
+>("rust-analyzer/cargoTomlCompletion");
+export const cargoTomlDiagnostics = new lc.RequestType<
+    lc.TextDocumentIdentifier,
+    lc.Diagnostic[],
+    void
+>("rust-analyzer/cargoTomlDiagnostics");
+export const importsForPaste = new lc.RequestType<
+    ImportsForPasteParams,
+    ImportsForPasteResult | null,
+    void
+>("rust-analyzer/importsForPaste");
 
 export type AnalyzerStatusParams = { textDocument?: lc.TextDocumentIdentifier };
 
@@ -110,6 +148,16 @@ export type ExpandedMacro = {
     name: string;
     expansion: string;
 };
+export type ExpandMacroDiagnosticParams = {
+    textDocument: lc.TextDocumentIdentifier;
+    range: lc.Range;
+};
+export type ExpandedMacroDiagnostic = {
+    name: string;
+    expansion: string;
+    range: lc.Range;
+    callSite: lc.Location;
+};
 export type TestInfo = { runnable: Runnable };
 export type SyntaxTreeParams = {
     textDocument: lc.TextDocumentIdentifier;
@@ -117,6 +165,62 @@ export type SyntaxTreeParams = {
 };
 export type ViewCrateGraphParams = { full: boolean };
 export type ViewItemTreeParams = { textDocument: lc.TextDocumentIdentifier };
+export type CrateFeaturesParams = { textDocument: lc.TextDocumentIdentifier };
+export type CrateFeature = { name: string; enabled: boolean };
+export type DebugLaunchInfoResult = {
+    executable: string;
+    args: string[];
+    env: Record<string, string>;
+    cwd: string;
+};
+export type DiscoverTestParams = { testId?: string | undefined };
+export type TestItemKind = "package" | "module" | "test";
+export type TestItem = {
+    id: string;
+    label: string;
+    kind: TestItemKind;
+    canResolveChildren: boolean;
+    parent?: string | undefined;
+    textDocument?: lc.TextDocumentIdentifier | undefined;
+    range?: lc.Range | undefined;
+    runnable?: Runnable | undefined;
+};
+export type DiscoverTestResults = {
+    tests: TestItem[];
+    scope: string[] | undefined;
+    scopeFile: lc.TextDocumentIdentifier[] | undefined;
+};
+export type ExplainExpressionParams = {
+    textDocument: lc.TextDocumentIdentifier;
+    range: lc.Range;
+};
+export type ExplainExpressionResult = {
+    range: lc.Range;
+    type: string;
+    coercedType?: string;
+    adjustments: { kind: string; source: string; target: string }[];
+    callee?: string;
+    calleeTrait?: string;
+};
+export type TasksParams = {
+    textDocument?: lc.TextDocumentIdentifier;
+    position?: lc.Position;
+};
+export type Task = {
+    location: lc.Location;
+    kind: "todo" | "fixme" | "xxx";
+    text: string;
+    owner?: string;
+};
+export type ImportsForPasteParams = {
+    textDocument: lc.TextDocumentIdentifier;
+    range: lc.Range;
+    chosen: string[];
+};
+export type ImportsForPasteResult = {
+    edit: lc.WorkspaceEdit;
+    ambiguous: { name: string; range: lc.Range; candidates: string[] }[];
+};
 
 // experimental extensions
 
@@ -153,6 +257,9 @@ export const runnables = new lc.RequestType<RunnablesParams, Runnable[], void>(
 export const serverStatus = new lc.NotificationType<ServerStatusParams>(
     "experimental/serverStatus",
 );
+export const searchByType = new lc.RequestType<SearchByTypeParams, TypeSearchMatch[], void>(
+    "experimental/searchByType",
+);
 export const ssr = new lc.RequestType<SsrParams, lc.WorkspaceEdit, void>("experimental/ssr");
 export const viewRecursiveMemoryLayout = new lc.RequestType<
     lc.TextDocumentPositionParams,
@@ -205,6 +312,16 @@ export type SsrParams = {
     textDocument: lc.TextDocumentIdentifier;
     position: lc.Position;
     selections: readonly lc.Range[];
+    preview?: boolean;
+};
+
+export type SearchByTypeParams = {
+    query: string;
+};
+export type TypeSearchMatch = {
+    location: lc.Location;
+    kind: "expr" | "binding";
+    ty: string;
 };
 
 export type RecursiveMemoryLayoutNode = {
@@ -160,6 +160,7 @@ function createCommands(): Record<string, CommandFactory> {
         viewCrateGraph: { enabled: commands.viewCrateGraph },
         viewFullCrateGraph: { enabled: commands.viewFullCrateGraph },
         expandMacro: { enabled: commands.expandMacro },
+        expandMacroDiagnostic: { enabled: commands.expandMacroDiagnostic },
         run: { enabled: commands.run },
         copyRunCommandLine: { enabled: commands.copyRunCommandLine },
         debug: { enabled: commands.debug },
@@ -173,14 +174,21 @@ function createCommands(): Record<string, CommandFactory> {
         cancelFlycheck: { enabled: commands.cancelFlycheck },
         clearFlycheck: { enabled: commands.clearFlycheck },
         runFlycheck: { enabled: commands.runFlycheck },
+        searchByType: { enabled: commands.searchByType },
         ssr: { enabled: commands.ssr },
         serverVersion: { enabled: commands.serverVersion },
         viewMemoryLayout: { enabled: commands.viewMemoryLayout },
         toggleCheckOnSave: { enabled: commands.toggleCheckOnSave },
+        selectTarget: { enabled: commands.selectTarget },
+        toggleFeatures: { enabled: commands.toggleFeatures },
+        pasteWithImports: { enabled: commands.pasteWithImports },
+        explainExpression: { enabled: commands.explainExpression },
         // Internal commands which are invoked by the server.
         applyActionGroup: { enabled: commands.applyActionGroup },
         applySnippetWorkspaceEdit: { enabled: commands.applySnippetWorkspaceEditCommand },
         debugSingle: { enabled: commands.debugSingle },
+        enableCfg: { enabled: commands.enableCfg },
+        applySsrRule: { enabled: commands.applySsrRule },
         gotoLocation: { enabled: commands.gotoLocation },
         linkToCommand: { enabled: commands.linkToCommand },
         resolveCodeAction: { enabled: commands.resolveCodeAction },
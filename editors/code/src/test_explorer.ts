@@ -0,0 +1,185 @@
+import * as vscode from "vscode";
+import type * as lc from "vscode-languageclient/node";
+import * as ra from "./lsp_ext";
+
+import type { Ctx } from "./ctx";
+import { startDebugSession } from "./debug";
+import { createTask } from "./run";
+import { unwrapUndefinable } from "./undefinable";
+
+export function prepareTestExplorer(ctx: Ctx, client: lc.LanguageClient): vscode.Disposable {
+    const controller = vscode.tests.createTestController("rustAnalyzerTestController", "Rust");
+    const runnables = new Map<string, ra.Runnable>();
+
+    // Items whose parent hasn't been reported yet, by the id of that parent.
+    const orphans = new Map<string, vscode.TestItem[]>();
+
+    const addItems = (results: ra.DiscoverTestResults) => {
+        for (const id of results.scope ?? []) {
+            controller.items.get(id)?.children.replace([]);
+        }
+        for (const doc of results.scopeFile ?? []) {
+            removeItemsOfFile(controller.items, doc.uri);
+        }
+        for (const test of results.tests) {
+            if (test.runnable) {
+                runnables.set(test.id, test.runnable);
+            }
+            // Keep the children of crates that were already resolved.
+            let item = test.kind === "package" ? controller.items.get(test.id) : undefined;
+            if (!item) {
+                const uri = test.textDocument
+                    ? client.protocol2CodeConverter.asUri(test.textDocument.uri)
+                    : undefined;
+                item = controller.createTestItem(test.id, test.label, uri);
+                if (test.range) {
+                    item.range = client.protocol2CodeConverter.asRange(test.range);
+                }
+                item.canResolveChildren = test.canResolveChildren;
+                if (!test.parent) {
+                    controller.items.add(item);
+                } else {
+                    const parent = findItem(controller.items, test.parent);
+                    if (parent) {
+                        parent.children.add(item);
+                    } else {
+                        orphans.set(test.parent, [...(orphans.get(test.parent) ?? []), item]);
+                    }
+                }
+            }
+            for (const child of orphans.get(test.id) ?? []) {
+                item.children.add(child);
+            }
+            orphans.delete(test.id);
+        }
+    };
+
+    const resolveItem = async (item: vscode.TestItem | undefined) => {
+        const results = await client.sendRequest(ra.discoverTests, { testId: item?.id });
+        addItems(results);
+    };
+    controller.resolveHandler = resolveItem;
+
+    // The items to run for `items`, resolving the children of the ones that have no runnable of
+    // their own, like crates that were never expanded.
+    const itemsToRun = async (
+        items: readonly vscode.TestItem[],
+        exclude: readonly vscode.TestItem[],
+    ): Promise<vscode.TestItem[]> => {
+        const result: vscode.TestItem[] = [];
+        for (const item of items) {
+            if (exclude.includes(item)) continue;
+            if (runnables.has(item.id)) {
+                result.push(item);
+                continue;
+            }
+            if (item.canResolveChildren && item.children.size === 0) {
+                await resolveItem(item);
+            }
+            result.push(...(await itemsToRun(gatherItems(item.children), exclude)));
+        }
+        return result;
+    };
+
+    const runHandler = async (
+        debug: boolean,
+        request: vscode.TestRunRequest,
+        token: vscode.CancellationToken,
+    ) => {
+        const run = controller.createTestRun(request);
+        const queue = await itemsToRun(
+            request.include ?? gatherItems(controller.items),
+            request.exclude ?? [],
+        );
+        for (const item of queue) {
+            if (token.isCancellationRequested) break;
+            const runnable = unwrapUndefinable(runnables.get(item.id));
+            run.started(item);
+            const start = Date.now();
+            try {
+                if (debug) {
+                    // The outcome of a debugged test isn't reported back, only failures to launch.
+                    if (!(await startDebugSession(ctx, runnable))) {
+                        run.errored(item, new vscode.TestMessage("Failed to start debugging"));
+                    }
+                    continue;
+                }
+                const task = await createTask(runnable, ctx.config);
+                const exitCode = await executeTask(task, token);
+                const duration = Date.now() - start;
+                if (exitCode === 0) {
+                    run.passed(item, duration);
+                } else {
+                    const message = `\`${runnable.label}\` exited with code ${exitCode}`;
+                    run.failed(item, new vscode.TestMessage(message), duration);
+                }
+            } catch (err) {
+                run.errored(item, new vscode.TestMessage(`${err}`), Date.now() - start);
+            }
+        }
+        run.end();
+    };
+
+    controller.createRunProfile(
+        "Run Tests",
+        vscode.TestRunProfileKind.Run,
+        (request, token) => runHandler(false, request, token),
+        true,
+    );
+    controller.createRunProfile(
+        "Debug Tests",
+        vscode.TestRunProfileKind.Debug,
+        (request, token) => runHandler(true, request, token),
+        true,
+    );
+
+    const subscription = client.onNotification(ra.discoveredTests, addItems);
+    return {
+        dispose() {
+            subscription.dispose();
+            controller.dispose();
+        },
+    };
+}
+
+/** Runs `task` to completion, returning the exit code of its process. */
+async function executeTask(
+    task: vscode.Task,
+    token: vscode.CancellationToken,
+): Promise<number | undefined> {
+    const execution = await vscode.tasks.executeTask(task);
+    const cancellation = token.onCancellationRequested(() => execution.terminate());
+    return new Promise((resolve) => {
+        const subscription = vscode.tasks.onDidEndTaskProcess((event) => {
+            if (event.execution !== execution) return;
+            subscription.dispose();
+            cancellation.dispose();
+            resolve(event.exitCode);
+        });
+    });
+}
+
+function findItem(items: vscode.TestItemCollection, id: string): vscode.TestItem | undefined {
+    let found: vscode.TestItem | undefined;
+    items.forEach((item) => {
+        found ??= item.id === id ? item : findItem(item.children, id);
+    });
+    return found;
+}
+
+function gatherItems(items: vscode.TestItemCollection): vscode.TestItem[] {
+    const result: vscode.TestItem[] = [];
+    items.forEach((item) => result.push(item));
+    return result;
+}
+
+function removeItemsOfFile(items: vscode.TestItemCollection, uri: string) {
+    items.forEach((item) => {
+        // Crates stay, as their root file also declares items of other files.
+        if (item.uri?.toString() === uri && item.parent) {
+            items.delete(item.id);
+        } else {
+            removeItemsOfFile(item.children, uri);
+        }
+    });
+}
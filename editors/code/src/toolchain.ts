@@ -1,7 +1,5 @@
-import * as cp from "child_process";
 import * as os from "os";
 import * as path from "path";
-import * as readline from "readline";
 import * as vscode from "vscode";
 import { execute, log, memoizeAsync } from "./util";
 import { unwrapNullable } from "./nullable";
@@ -15,11 +13,6 @@ interface CompilationArtifact {
     isTest: boolean;
 }
 
-export interface ExecutableInfo {
-    executable: string;
-    workspace: string;
-}
-
 export interface ArtifactSpec {
     cargoArgs: string[];
     filter?: (artifacts: CompilationArtifact[]) => CompilationArtifact[];
@@ -59,85 +52,6 @@ export class Cargo {
 
         return result;
     }
-
-    private async getArtifacts(spec: ArtifactSpec): Promise<CompilationArtifact[]> {
-        const artifacts: CompilationArtifact[] = [];
-
-        try {
-            await this.runCargo(
-                spec.cargoArgs,
-                (message) => {
-                    if (message.reason === "compiler-artifact" && message.executable) {
-                        const isBinary = message.target.crate_types.includes("bin");
-                        const isBuildScript = message.target.kind.includes("custom-build");
-                        if ((isBinary && !isBuildScript) || message.profile.test) {
-                            artifacts.push({
-                                fileName: message.executable,
-                                name: message.target.name,
-                                workspace: path.dirname(message.manifest_path),
-                                kind: message.target.kind[0],
-                                isTest: message.profile.test,
-                            });
-                        }
-                    } else if (message.reason === "compiler-message") {
-                        this.output.append(message.message.rendered);
-                    }
-                },
-                (stderr) => this.output.append(stderr),
-            );
-        } catch (err) {
-            this.output.show(true);
-            throw new Error(`Cargo invocation has failed: ${err}`);
-        }
-
-        return spec.filter?.(artifacts) ?? artifacts;
-    }
-
-    async executableInfoFromArgs(args: readonly string[]): Promise<ExecutableInfo> {
-        const artifacts = await this.getArtifacts(Cargo.artifactSpec(args));
-
-        if (artifacts.length === 0) {
-            throw new Error("No compilation artifacts");
-        } else if (artifacts.length > 1) {
-            throw new Error("Multiple compilation artifacts are not supported.");
-        }
-
-        const artifact = unwrapUndefinable(artifacts[0]);
-        return {
-            executable: artifact.fileName,
-            workspace: artifact.workspace,
-        };
-    }
-
-    private async runCargo(
-        cargoArgs: string[],
-        onStdoutJson: (obj: any) => void,
-        onStderrString: (data: string) => void,
-    ): Promise<number> {
-        const path = await cargoPath();
-        return await new Promise((resolve, reject) => {
-            const cargo = cp.spawn(path, cargoArgs, {
-                stdio: ["ignore", "pipe", "pipe"],
-                cwd: this.rootFolder,
-                env: this.env,
-            });
-
-            cargo.on("error", (err) => reject(new Error(`could not launch cargo: ${err}`)));
-
-            cargo.stderr.on("data", (chunk) => onStderrString(chunk.toString()));
-
-            const rl = readline.createInterface({ input: cargo.stdout });
-            rl.on("line", (line) => {
-                const message = JSON.parse(line);
-                onStdoutJson(message);
-            });
-
-            cargo.on("exit", (exitCode, _) => {
-                if (exitCode === 0) resolve(exitCode);
-                else reject(new Error(`exit code: ${exitCode}.`));
-            });
-        });
-    }
 }
 
 /** Mirrors `project_model::sysroot::discover_sysroot_dir()` implementation*/
@@ -63,6 +63,16 @@ impl Connection {
         Ok((Connection { sender, receiver }, io_threads))
     }
 
+    /// Accept the next connection on an already bound listener.
+    /// This call blocks until a connection is established.
+    ///
+    /// Use this to serve several clients.
+    pub fn accept(listener: &TcpListener) -> io::Result<(Connection, IoThreads)> {
+        let (stream, _) = listener.accept()?;
+        let (sender, receiver, io_threads) = socket::socket_transport(stream);
+        Ok((Connection { sender, receiver }, io_threads))
+    }
+
     /// Creates a pair of connected connections.
     ///
     /// Use this for testing.
@@ -24,7 +24,7 @@ fn make_reader(stream: TcpStream) -> (Receiver<Message>, thread::JoinHandle<io::
     let (reader_sender, reader_receiver) = bounded::<Message>(0);
     let reader = thread::spawn(move || {
         let mut buf_read = BufReader::new(stream);
-        while let Some(msg) = Message::read(&mut buf_read).unwrap() {
+        while let Some(msg) = Message::read(&mut buf_read)? {
             let is_exit = matches!(&msg, Message::Notification(n) if n.is_exit());
             reader_sender.send(msg).unwrap();
             if is_exit {
@@ -38,9 +38,7 @@ fn make_reader(stream: TcpStream) -> (Receiver<Message>, thread::JoinHandle<io::
 
 fn make_write(mut stream: TcpStream) -> (Sender<Message>, thread::JoinHandle<io::Result<()>>) {
     let (writer_sender, writer_receiver) = bounded::<Message>(0);
-    let writer = thread::spawn(move || {
-        writer_receiver.into_iter().try_for_each(|it| it.write(&mut stream)).unwrap();
-        Ok(())
-    });
+    let writer =
+        thread::spawn(move || writer_receiver.into_iter().try_for_each(|it| it.write(&mut stream)));
     (writer_sender, writer)
 }